@@ -1,6 +1,64 @@
-use crate::app::tab::Tab;
+use crate::app::finder::{self, Finder, Item, Target};
+use crate::app::hints::{self, Hint, Hints};
+use crate::app::input::Cursor;
+use crate::app::tab::{LoadTest, Retry, Run, Tab};
+use crate::collections::merge::{Resolution, Review};
+use crate::collections::revisions::{self, Version};
+use crate::collections::{self, Collection};
+use crate::config::{self, Config};
 use crate::error::{RestlessError, Result};
+use crate::export::copy::CopyFormat;
+use crate::export::{bugreport, Annotation, ExportFormat};
+use crate::i18n::{self, Catalog};
+use crate::import::{self, PasteKind};
+use crate::logic::assertions;
+use crate::logic::auth::{Auth, AuthKind};
+use crate::logic::bulk;
+use crate::logic::cookies::{self, Cookie, CookieJar};
+use crate::logic::environment::{self, Environment};
+use crate::logic::form;
+use crate::logic::headers;
+use crate::logic::lint::{self, Finding, Rules};
+use crate::logic::load::{self, Load, Report};
+use crate::logic::matrix::Outcome;
+use crate::logic::options::RequestOptions;
+use crate::logic::poll::{self, Poller};
+use crate::logic::query;
+use crate::logic::request::{build_url_with_params, Request, SentRequest};
+use crate::logic::runner::Step;
+use crate::logic::search::{self, Search};
+use crate::logic::signing::{self, Signature};
+use crate::logic::tls::Probe;
+use crate::logic::tree::Tree;
+use crate::logic::watch::Watcher;
 use crate::logic::HttpMethod;
+use crate::logic::{html, jsonpath};
+use crate::session::{self, Session};
+use crate::storage::{self, memory::MemoryStorage, Storage};
+use crate::ui::layouts::{LayoutMode, MainLayout};
+use crate::ui::Theme;
+use chrono::{DateTime, Utc};
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long a toast stays in the status bar
+const TOAST_DURATION: Duration = Duration::from_secs(2);
+
+/// Recent sends of the request included in a bug report
+const BUG_REPORT_SENDS: usize = 10;
+
+/// Last lines of the tracing log included in a bug report
+const BUG_REPORT_LOG_LINES: usize = 50;
+
+/// Most sent URLs suggested while typing one
+const URL_SUGGESTIONS: usize = 8;
+
+/// Columns one press of h/l scrolls a long body line sideways
+const COLUMN_STEP: usize = 8;
 
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
 pub enum CurrentScreen {
@@ -10,9 +68,44 @@ pub enum CurrentScreen {
 
     EditingUrl,
     EditingBody,
+    EditingForm,
     EditingHeaders,
     EditingParams,
+    /// Headers or params as lines of text
+    EditingBulk,
+    EditingAuth,
+    EditingOptions,
+    /// The assertions of the Tests tab
+    EditingTests,
+    EditingNote,
+    Searching,
+    EditingFilter,
+    ResendWith,
+    EditingWatch,
+    EditingPoll,
     Help,
+    Export,
+    Collections,
+    Timeline,
+    /// Fuzzy search over tabs, saved requests and history
+    Finder,
+    EditingImportPath,
+    ImportReview,
+    Copy,
+    Cookies,
+    EditingCookie,
+    SigningPreview,
+    /// The current request as it would be sent, without sending it
+    DryRun,
+    Latency,
+    EditingMatrix,
+    MatrixResults,
+    /// Summary of a run of a collection or of the open tabs
+    RunResults,
+    EditingLoad,
+    /// Progress and then the report of a load test
+    LoadResults,
+    PasteImport,
     Exiting,
 }
 
@@ -21,6 +114,21 @@ pub enum ValuesScreen {
     Body,
     Headers,
     Params,
+    Auth,
+    Options,
+    Tests,
+}
+
+impl ValuesScreen {
+    /// The values tabs from left to right
+    pub const ALL: [ValuesScreen; 6] = [
+        ValuesScreen::Body,
+        ValuesScreen::Headers,
+        ValuesScreen::Params,
+        ValuesScreen::Auth,
+        ValuesScreen::Options,
+        ValuesScreen::Tests,
+    ];
 }
 
 pub struct App {
@@ -30,59 +138,2046 @@ pub struct App {
     pub tabs: Vec<Tab>,
     pub selected_tab: usize,
     pub url_input: String,
+    pub url_cursor: Cursor,
+    /// Sent URLs starting with what's typed, newest first
+    pub url_suggestions: Vec<String>,
+    pub url_suggestion_selected: Option<usize>,
 
     pub selected_method: HttpMethod,
     pub method_dropdown_open: bool,
     pub method_dropdown_selected: usize,
 
+    /// Headers or params being edited as text, parsed back when done
+    pub bulk_input: String,
+    pub bulk_cursor: Cursor,
+    pub bulk_scroll: Cell<usize>,
+
     pub body_input: String,
+    pub body_cursor: Cursor,
+    /// First body line shown, while editing or viewing; kept in range by the
+    /// renderer, which is the one that knows how many lines fit
+    pub body_scroll: Cell<usize>,
+    /// Set by Ctrl+E; the main loop, which owns the terminal, then opens the
+    /// body in the external editor
+    pub body_editor_requested: bool,
+    /// Multipart fields; `None` while the body type is raw
+    pub form_input: Option<Vec<(String, String)>>,
+    pub current_form_input: String,
     pub headers_input: Vec<(String, String)>,
     pub params_input: Vec<(String, String)>,
+    /// Headers and params switched off, listed after the others
+    pub disabled_headers: Vec<(String, String)>,
+    pub disabled_params: Vec<(String, String)>,
+    /// Assertions run after each send, one per line
+    pub tests_input: String,
+    pub tests_cursor: Cursor,
+    pub tests_scroll: Cell<usize>,
     pub current_header_key: String,
     pub current_header_value: String,
+    /// Header names completing the one being typed
+    pub header_suggestions: Vec<String>,
+    pub header_suggestion_selected: Option<usize>,
     pub current_param_key: String,
     pub current_param_value: String,
-    #[allow(dead_code)]
+    pub auth_input: Auth,
+    pub current_auth_input: String,
+    pub options_input: RequestOptions,
+    pub current_options_input: String,
+    /// Header or param picked with j/k on the Values screen
+    pub selected_header: Option<usize>,
+    pub selected_param: Option<usize>,
+    /// Entry the edit fields were loaded from, replaced when they're added
     pub editing_header_index: Option<usize>,
-    #[allow(dead_code)]
     pub editing_param_index: Option<usize>,
 
-    pub response_tab_selected: usize,
-    pub response_scroll: usize,
-    pub response_scroll_state: ratatui::widgets::ScrollbarState,
+    pub response_tab_selected: usize,
+    pub response_scroll: usize,
+    /// First header shown on the Headers tab; kept in range by the renderer
+    pub response_headers_scroll: Cell<usize>,
+    /// First column of the body shown, for lines too long for the section;
+    /// kept in range by the renderer
+    pub response_column: Cell<usize>,
+    pub response_part_selected: usize,
+    /// Whether a JSON array body is shown as a table
+    pub response_table: bool,
+    /// Show HTML bodies rendered as text instead of markup
+    pub html_preview: bool,
+    /// Leftmost table column shown, which is also the one sorted by
+    pub table_column: usize,
+    /// JSON tree view of the body; `None` while the body is shown as text
+    pub response_tree: Option<Tree>,
+    /// Id of the tab whose response is shown beside the current one,
+    /// scrolling along
+    pub compare_tab: Option<usize>,
+    /// Whether the response section fills the screen, toggled with `z`
+    pub response_zoomed: bool,
+    /// Where the sections were drawn last, for mouse clicks; hidden
+    /// sections are empty
+    pub screen_layout: MainLayout,
+    /// How the request and the response are arranged, from the config
+    /// until switched with `\`
+    pub layout: LayoutMode,
+    /// Id of the tab warned about closing while its send is in flight;
+    /// closing it again cancels the send
+    pub close_warned: Option<usize>,
+    pub response_scroll_state: ratatui::widgets::ScrollbarState,
+
+    pub help_visible: bool,
+    pub help_scroll: usize,
+    pub previous_screen: CurrentScreen,
+
+    pub environments: Vec<Environment>,
+    pub active_environment: Option<usize>,
+    /// Variables the `set` lines of the Tests tab took from responses, used
+    /// by every later send on top of the active environment
+    pub extracted: BTreeMap<String, String>,
+
+    pub export_format: ExportFormat,
+    pub export_scroll: usize,
+
+    pub collections: Vec<Collection>,
+    pub collection_selected: usize,
+    /// Path of the HAR file, Postman collection or OpenAPI document to import
+    pub import_input: String,
+    /// Import waiting for its duplicates to be resolved
+    pub import_review: Option<Review>,
+    /// Versions of the selected saved request, newest first, while its
+    /// timeline is open
+    pub timeline: Vec<Version>,
+    pub timeline_selected: usize,
+
+    pub copy_format: CopyFormat,
+
+    pub cookie_jar: Arc<CookieJar>,
+    pub cookie_selected: usize,
+    pub cookie_input: String,
+    /// Cookie being edited; `None` while adding a new one
+    pub cookie_editing: Option<Cookie>,
+
+    pub signing_scroll: usize,
+
+    /// Request built by the last dry run
+    pub dry_run: Option<SentRequest>,
+    pub dry_run_scroll: usize,
+
+    pub note_input: String,
+
+    /// Search in the response body, highlighted until cleared with Esc
+    pub search: Option<Search>,
+    pub search_input: String,
+    pub filter_input: String,
+    pub matrix_input: String,
+    /// Variable and outcomes of the last matrix send
+    pub matrix_results: Option<(String, Vec<Outcome>)>,
+    /// What ran and how each request went, for the last run
+    pub run_results: Option<(String, Vec<Step>)>,
+    pub run_scroll: usize,
+    /// A run still sending, its finished steps kept in `run_results`
+    pub run: Option<Run>,
+    /// `requests x concurrency` of the last load test
+    pub load_input: String,
+    pub load_test: Option<LoadTest>,
+    /// Target and report of the last finished load test
+    pub load_report: Option<(String, Report)>,
+
+    /// Last `Header: value` / `param=value` used for a one-off resend
+    pub resend_input: String,
+
+    /// Files whose changes re-send a request, with the id of the tab it sends
+    pub watch: Option<(usize, Watcher)>,
+    pub watch_input: String,
+    /// Re-sends on an interval, with the id of the tab it sends
+    pub poll: Option<(usize, Poller)>,
+    /// Protocol and cipher lookup for the TLS tab, with the id of the tab
+    /// whose response it's for
+    pub tls_probe: Option<(usize, Probe)>,
+    pub poll_input: String,
+
+    /// The open finder
+    pub finder: Option<Finder>,
+
+    /// Watchers of the opened `.http` files
+    pub http_files: Vec<Watcher>,
+    /// Whether a reloaded `.http` request is sent right away
+    pub send_on_save: bool,
+
+    /// Pasted text waiting for the user to confirm an import
+    pub paste_import: Option<(PasteKind, String)>,
+
+    pub config: Config,
+    /// Translations of the interface text
+    pub i18n: Catalog,
+    /// Colors of the interface, from the config
+    pub theme: Theme,
+    /// First-use hints dismissed so far
+    pub hints: Hints,
+    /// Every send, kept in memory until `load_history` opens the
+    /// configured backend
+    pub history: Box<dyn Storage>,
+    /// Lint rules of the workspace; `None` when it has no rules file
+    pub lint: Option<Rules>,
+    /// Short confirmation shown in the status bar, and when it appeared
+    pub toast: Option<(String, Instant)>,
+}
+
+impl App {
+    pub fn new() -> App {
+        let tabs = vec![Tab::new("Tab 1".to_string(), String::new())];
+        App {
+            current_screen: CurrentScreen::Values,
+            values_screen: ValuesScreen::Body,
+            tabs,
+            selected_tab: 0,
+            url_input: String::new(),
+            url_cursor: Cursor::default(),
+            url_suggestions: Vec::new(),
+            url_suggestion_selected: None,
+            selected_method: HttpMethod::GET,
+            method_dropdown_open: false,
+            method_dropdown_selected: 0,
+            body_input: String::new(),
+            bulk_input: String::new(),
+            bulk_cursor: Cursor::default(),
+            bulk_scroll: Cell::new(0),
+            body_cursor: Cursor::default(),
+            body_scroll: Cell::new(0),
+            body_editor_requested: false,
+            form_input: None,
+            current_form_input: String::new(),
+            headers_input: Vec::new(),
+            params_input: Vec::new(),
+            disabled_headers: Vec::new(),
+            disabled_params: Vec::new(),
+            tests_input: String::new(),
+            tests_cursor: Cursor::default(),
+            tests_scroll: Cell::new(0),
+            current_header_key: String::new(),
+            current_header_value: String::new(),
+            header_suggestions: Vec::new(),
+            header_suggestion_selected: None,
+            current_param_key: String::new(),
+            current_param_value: String::new(),
+            auth_input: Auth::default(),
+            current_auth_input: String::new(),
+            options_input: RequestOptions::default(),
+            current_options_input: String::new(),
+            selected_header: None,
+            selected_param: None,
+            editing_header_index: None,
+            editing_param_index: None,
+            response_tab_selected: 1,
+            response_scroll: 0,
+            response_headers_scroll: Cell::new(0),
+            response_column: Cell::new(0),
+            response_part_selected: 0,
+            response_table: false,
+            html_preview: false,
+            table_column: 0,
+            response_tree: None,
+            compare_tab: None,
+            response_zoomed: false,
+            screen_layout: MainLayout::default(),
+            layout: LayoutMode::Stacked,
+            close_warned: None,
+            response_scroll_state: ratatui::widgets::ScrollbarState::default(),
+            help_visible: false,
+            help_scroll: 0,
+            previous_screen: CurrentScreen::Values,
+            environments: Vec::new(),
+            active_environment: None,
+            extracted: BTreeMap::new(),
+            export_format: ExportFormat::Curl,
+            export_scroll: 0,
+            collections: vec![collections::tutorial::tutorial()],
+            collection_selected: 0,
+            import_input: String::new(),
+            import_review: None,
+            timeline: Vec::new(),
+            timeline_selected: 0,
+            copy_format: CopyFormat::HeadersCurl,
+            cookie_jar: cookies::shared_jar(),
+            cookie_selected: 0,
+            cookie_input: String::new(),
+            cookie_editing: None,
+            signing_scroll: 0,
+            dry_run: None,
+            dry_run_scroll: 0,
+            note_input: String::new(),
+            search: None,
+            search_input: String::new(),
+            filter_input: String::new(),
+            matrix_input: String::new(),
+            matrix_results: None,
+            run_results: None,
+            run_scroll: 0,
+            run: None,
+            load_input: String::new(),
+            load_test: None,
+            load_report: None,
+            resend_input: String::new(),
+            watch: None,
+            watch_input: String::new(),
+            poll: None,
+            tls_probe: None,
+            poll_input: String::new(),
+            finder: None,
+            http_files: Vec::new(),
+            send_on_save: false,
+            paste_import: None,
+            config: Config::default(),
+            i18n: Catalog::default(),
+            theme: Theme::default(),
+            hints: Hints::default(),
+            history: Box::<MemoryStorage>::default(),
+            lint: None,
+            toast: None,
+        }
+    }
+
+    /// Loads `config.toml` from the config directory
+    pub fn load_config(&mut self) -> Result<()> {
+        if let Some(path) = crate::paths::config_file() {
+            self.config = config::load_config(&path)?;
+        }
+        self.theme = Theme::load(self.config.ui.theme.as_deref(), &self.config.theme)?;
+        self.layout = self.config.ui.layout;
+        Ok(())
+    }
+
+    /// Loads the translations of the configured locale, or of the system
+    /// locale when restless has them; call after `load_config`
+    pub fn load_locale(&mut self) -> Result<()> {
+        let dir = crate::paths::locales_dir();
+        self.i18n = match &self.config.ui.locale {
+            Some(locale) => i18n::load(locale, dir.as_deref())?,
+            None => i18n::system_locale()
+                .and_then(|locale| i18n::load(&locale, dir.as_deref()).ok())
+                .unwrap_or_default(),
+        };
+        Ok(())
+    }
+
+    /// The interface text in the active locale
+    pub fn tr<'a>(&'a self, text: &'a str) -> &'a str {
+        self.i18n.tr(text)
+    }
+
+    /// Loads environments from the config directory
+    pub fn load_environments(&mut self) -> Result<()> {
+        if let Some(path) = crate::paths::environments_file() {
+            self.environments = environment::load_environments(&path)?;
+        }
+        self.active_environment = None;
+        Ok(())
+    }
+
+    /// Opens the configured history backend and prunes old entries; call
+    /// after `load_config`
+    pub fn load_history(&mut self) -> Result<()> {
+        let settings = &self.config.history;
+        let path = crate::paths::history_file();
+        let mut history = storage::open(settings, path.as_deref())?;
+        history.prune(&settings.retention(), chrono::Utc::now())?;
+        self.history = history;
+        Ok(())
+    }
+
+    /// A history entry for a send of the current request with `env`
+    pub fn history_entry(
+        &self,
+        env: Option<&Environment>,
+        status: std::result::Result<u16, String>,
+        elapsed: std::time::Duration,
+        run: Option<String>,
+    ) -> storage::Entry {
+        self.history_entry_for(&self.current_request(), env, status, elapsed, run)
+    }
+
+    /// A history entry for a send of `request` with `env`
+    pub fn history_entry_for(
+        &self,
+        request: &Request,
+        env: Option<&Environment>,
+        status: std::result::Result<u16, String>,
+        elapsed: std::time::Duration,
+        run: Option<String>,
+    ) -> storage::Entry {
+        let (request, _) = environment::resolve_request(request, env, true);
+        storage::Entry {
+            sent_at: chrono::Utc::now(),
+            method: request.method.to_string(),
+            url: build_url_with_params(&request.url, &request.params).unwrap_or(request.url),
+            status,
+            elapsed,
+            run,
+        }
+    }
+
+    /// Loads the lint rules of the workspace around the working directory
+    pub fn load_lint(&mut self) -> Result<()> {
+        let path = std::env::current_dir()
+            .ok()
+            .and_then(|dir| lint::find_rules_file(&dir));
+        self.lint = path.map(|path| lint::load_rules(&path)).transpose()?;
+        Ok(())
+    }
+
+    /// What the lint rules find in `request` for the active environment
+    pub fn lint(&self, request: &Request) -> Vec<Finding> {
+        self.lint
+            .as_ref()
+            .map(|rules| rules.check(request, self.send_environment().as_ref()))
+            .unwrap_or_default()
+    }
+
+    /// Loads the dismissed first-use hints from the config directory
+    pub fn load_hints(&mut self) -> Result<()> {
+        if let Some(path) = crate::paths::hints_file() {
+            self.hints = Hints::load(&path)?;
+        }
+        Ok(())
+    }
+
+    /// The first-use hint to show for the current state, if any
+    pub fn hint(&self) -> Option<Hint> {
+        hints::current(self)
+    }
+
+    /// Stops showing the current hint, for good
+    pub fn dismiss_hint(&mut self) -> Result<()> {
+        match self.hint() {
+            Some(hint) => self.hints.dismiss(hint),
+            None => Ok(()),
+        }
+    }
+
+    /// Shows a confirmation in the status bar for a moment
+    pub fn show_toast(&mut self, message: impl Into<String>) {
+        self.toast = Some((message.into(), Instant::now()));
+    }
+
+    /// Drops the toast once it has been shown long enough, returning
+    /// whether one is still showing
+    pub fn expire_toast(&mut self, now: Instant) -> bool {
+        if let Some((_, shown)) = &self.toast {
+            if now.duration_since(*shown) >= TOAST_DURATION {
+                self.toast = None;
+            }
+        }
+        self.toast.is_some()
+    }
+
+    /// The response view on screen as text, with what it is: the headers,
+    /// the table as CSV or the body as shown
+    pub fn view_text(&self) -> Result<(&'static str, String)> {
+        let response = self.tabs[self.selected_tab]
+            .response
+            .as_ref()
+            .ok_or_else(|| RestlessError::app_state("No response to copy yet"))?;
+        match self.response_tab_selected {
+            0 => {
+                let headers: Vec<String> = response
+                    .headers
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value))
+                    .collect();
+                Ok(("headers", headers.join("\n")))
+            }
+            1 if self.response_table => Ok(("table", self.table_csv()?)),
+            1 if response.hex_bytes().is_some() => Err(RestlessError::app_state(
+                "A binary body can't be copied as text",
+            )),
+            1 => Ok(("body", self.body_text().into_owned())),
+            5 => match &response.sent {
+                Some(sent) => Ok((
+                    "request",
+                    sent.lines(response.version.as_deref()).join("\n"),
+                )),
+                None => Err(RestlessError::app_state("Nothing to copy in this view")),
+            },
+            _ => Err(RestlessError::app_state("Nothing to copy in this view")),
+        }
+    }
+
+    /// Opens the copy-format menu; fails when there is no response to copy
+    pub fn show_copy_menu(&mut self) -> Result<()> {
+        if self.tabs[self.selected_tab].response.is_none() {
+            return Err(RestlessError::app_state("No response to copy yet"));
+        }
+        if self.current_screen != CurrentScreen::Copy {
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::Copy;
+        }
+        Ok(())
+    }
+
+    pub fn hide_copy_menu(&mut self) {
+        if self.current_screen == CurrentScreen::Copy {
+            self.current_screen = self.previous_screen;
+        }
+    }
+
+    /// Copies the current response to the clipboard in the selected format
+    pub fn copy_response(&self, format: CopyFormat) -> Result<()> {
+        let response = self.tabs[self.selected_tab]
+            .response
+            .as_ref()
+            .ok_or_else(|| RestlessError::app_state("No response to copy yet"))?;
+        crate::clipboard::copy(&format.render(response))
+    }
+
+    /// Signs the current request as it would be sent right now
+    ///
+    /// Returns `None` when the auth type doesn't sign requests.
+    pub fn signing_preview(&self) -> Result<Option<Signature>> {
+        let env = self.send_environment();
+        let (request, _) =
+            environment::resolve_request(&self.current_request(), env.as_ref(), false);
+        let url = build_url_with_params(&request.url, &request.params)?;
+        Ok(signing::sign(
+            &request.auth,
+            &request.method,
+            &url,
+            request.body.as_deref(),
+            chrono::Utc::now(),
+        )?)
+    }
+
+    pub fn show_signing_preview(&mut self) -> Result<()> {
+        if self.signing_preview()?.is_none() {
+            return Err(RestlessError::Authentication {
+                message: "Only HMAC and AWS SigV4 auth produce a signature".to_string(),
+            });
+        }
+        if self.current_screen != CurrentScreen::SigningPreview {
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::SigningPreview;
+            self.signing_scroll = 0;
+        }
+        Ok(())
+    }
+
+    pub fn hide_signing_preview(&mut self) {
+        if self.current_screen == CurrentScreen::SigningPreview {
+            self.current_screen = self.previous_screen;
+        }
+    }
+
+    /// Shows `sent`, the current request built but not sent
+    pub fn show_dry_run(&mut self, sent: SentRequest) {
+        self.dry_run = Some(sent);
+        self.dry_run_scroll = 0;
+        if self.current_screen != CurrentScreen::DryRun {
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::DryRun;
+        }
+    }
+
+    pub fn hide_dry_run(&mut self) {
+        if self.current_screen == CurrentScreen::DryRun {
+            self.current_screen = self.previous_screen;
+        }
+    }
+
+    /// Shows the latency histogram of the current tab's repeated sends
+    pub fn show_latency(&mut self) -> Result<()> {
+        if self.tabs[self.selected_tab].latency.histogram().is_empty() {
+            return Err(RestlessError::app_state("Send the request first"));
+        }
+        if self.current_screen != CurrentScreen::Latency {
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::Latency;
+        }
+        Ok(())
+    }
+
+    pub fn hide_latency(&mut self) {
+        if self.current_screen == CurrentScreen::Latency {
+            self.current_screen = self.previous_screen;
+        }
+    }
+
+    /// Opens the prompt for the variable and values of a matrix send
+    pub fn show_matrix_prompt(&mut self) {
+        if self.current_screen != CurrentScreen::EditingMatrix {
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::EditingMatrix;
+        }
+    }
+
+    /// Shows the outcomes of a matrix send in place of its prompt
+    pub fn show_matrix_results(&mut self, variable: String, outcomes: Vec<Outcome>) {
+        self.matrix_results = Some((variable, outcomes));
+        self.current_screen = CurrentScreen::MatrixResults;
+    }
+
+    pub fn hide_matrix(&mut self) {
+        if matches!(
+            self.current_screen,
+            CurrentScreen::EditingMatrix | CurrentScreen::MatrixResults
+        ) {
+            self.current_screen = self.previous_screen;
+        }
+    }
+
+    /// Every request of the selected request's collection, by name, with the
+    /// collection's name
+    pub fn collection_run(&self) -> Result<(String, Vec<(String, Request)>)> {
+        let Some(&(c, _)) = self.collection_entries().get(self.collection_selected) else {
+            return Err(RestlessError::app_state("No collection selected"));
+        };
+        let collection = &self.collections[c];
+        let requests = collection
+            .requests
+            .iter()
+            .map(|saved| Ok((saved.name.clone(), saved.to_request(&collection.auth)?)))
+            .collect::<Result<_>>()?;
+        Ok((collection.name.clone(), requests))
+    }
+
+    /// The request of every open tab with a URL, by tab name
+    pub fn tabs_run(&mut self) -> Result<Vec<(String, Request)>> {
+        self.save_current_tab_state()?;
+        let requests: Vec<_> = self
+            .tabs
+            .iter()
+            .filter(|tab| !tab.request.url.trim().is_empty())
+            .map(|tab| (tab.name.clone(), tab.request.clone()))
+            .collect();
+        if requests.is_empty() {
+            return Err(RestlessError::app_state("No tab has a URL to send"));
+        }
+        Ok(requests)
+    }
+
+    /// Opens the prompt for the number of requests of a load test
+    pub fn show_load_prompt(&mut self) {
+        if self.load_input.is_empty() {
+            self.load_input = format!("200 x {}", load::DEFAULT_CONCURRENCY);
+        }
+        if self.current_screen != CurrentScreen::EditingLoad {
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::EditingLoad;
+        }
+    }
+
+    /// Starts a load test of `request` and shows its progress in place of
+    /// the prompt
+    pub fn start_load_test(&mut self, request: Request, load: Load) {
+        self.load_report = None;
+        self.load_test = Some(LoadTest::spawn(request, load));
+        self.current_screen = CurrentScreen::LoadResults;
+    }
+
+    /// Keeps the report of a finished load test; true when one finished
+    pub fn poll_load_test(&mut self) -> bool {
+        let Some(report) = self.load_test.as_mut().and_then(LoadTest::poll) else {
+            return false;
+        };
+        if let Some(test) = self.load_test.take() {
+            self.load_report = Some((test.target, report));
+        }
+        true
+    }
+
+    /// Closes the load test prompt or results, stopping a running test
+    pub fn hide_load_test(&mut self) {
+        self.load_test = None;
+        if matches!(
+            self.current_screen,
+            CurrentScreen::EditingLoad | CurrentScreen::LoadResults
+        ) {
+            self.current_screen = self.previous_screen;
+        }
+    }
+
+    /// Starts sending `requests` one after another and shows how each goes
+    /// as it finishes
+    pub fn start_run(&mut self, title: String, requests: Vec<(String, Request)>) {
+        self.run_results = Some((title.clone(), Vec::new()));
+        self.run_scroll = 0;
+        self.run = Some(Run::new(title, requests));
+        if self.current_screen != CurrentScreen::RunResults {
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::RunResults;
+        }
+    }
+
+    /// Stops a run, keeping the steps that finished
+    pub fn stop_run(&mut self) {
+        self.run = None;
+    }
+
+    pub fn hide_run_results(&mut self) {
+        if self.current_screen == CurrentScreen::RunResults {
+            self.current_screen = self.previous_screen;
+        }
+    }
+
+    /// Offers to import pasted text as a request instead of pasting it
+    pub fn show_paste_import(&mut self, kind: PasteKind, text: String) {
+        self.paste_import = Some((kind, text));
+        if self.current_screen != CurrentScreen::PasteImport {
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::PasteImport;
+        }
+    }
+
+    pub fn hide_paste_import(&mut self) {
+        self.paste_import = None;
+        if self.current_screen == CurrentScreen::PasteImport {
+            self.current_screen = self.previous_screen;
+        }
+    }
+
+    /// Replaces the current tab's request with the pending paste
+    pub fn apply_paste_import(&mut self) -> Result<()> {
+        let Some((kind, text)) = self.paste_import.take() else {
+            return Err(RestlessError::app_state("Nothing to import"));
+        };
+        let request = match kind.import(&text) {
+            Some(result) => result?,
+            None => {
+                return Err(RestlessError::app_state(format!(
+                    "A {} can't be imported",
+                    kind.label()
+                )))
+            }
+        };
+
+        self.tabs[self.selected_tab].request = request;
+        self.restore_current_tab_state()?;
+        self.hide_paste_import();
+        if self.current_screen == CurrentScreen::EditingUrl {
+            self.current_screen = CurrentScreen::Url;
+        }
+        Ok(())
+    }
+
+    /// Starts editing the URL, with the params written into its query and
+    /// the cursor at its end
+    pub fn start_url_edit(&mut self) {
+        self.response_zoomed = false;
+        self.url_input = query::join(&self.url_input, &self.params_input);
+        self.url_cursor = Cursor::at_end(&self.url_input);
+        self.url_suggestions.clear();
+        self.url_suggestion_selected = None;
+        self.current_screen = CurrentScreen::EditingUrl;
+    }
+
+    /// Moves the query of the URL just edited into the params, which then
+    /// are the ones the query lists
+    pub fn finish_url_edit(&mut self) {
+        let (url, params) = query::split(&self.url_input);
+        if params != self.params_input {
+            self.selected_param = None;
+        }
+        self.url_input = url;
+        self.params_input = params;
+        self.url_suggestions.clear();
+        self.url_suggestion_selected = None;
+    }
+
+    /// Looks up sent URLs that start with what's typed into the URL field
+    ///
+    /// Suggestions are a convenience, so a history that can't be read just
+    /// gives none.
+    pub fn update_url_suggestions(&mut self) {
+        self.url_suggestion_selected = None;
+        self.url_suggestions.clear();
+        let typed = self.url_input.trim();
+        if typed.is_empty() {
+            return;
+        }
+        let prefix = typed.to_lowercase();
+        for entry in self.history.search(typed, 500).unwrap_or_default() {
+            let url = entry.url.to_lowercase();
+            if url.starts_with(&prefix)
+                && url != prefix
+                && !self.url_suggestions.contains(&entry.url)
+            {
+                self.url_suggestions.push(entry.url);
+                if self.url_suggestions.len() == URL_SUGGESTIONS {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Moves the selection in the URL suggestions, wrapping around
+    pub fn select_url_suggestion(&mut self, down: bool) {
+        self.url_suggestion_selected = step_selection(
+            self.url_suggestion_selected,
+            self.url_suggestions.len(),
+            down,
+        );
+    }
+
+    /// Puts the selected URL suggestion into the URL field; `false` when
+    /// none is selected
+    pub fn accept_url_suggestion(&mut self) -> bool {
+        let Some(index) = self.url_suggestion_selected else {
+            return false;
+        };
+        self.url_input = self.url_suggestions[index].clone();
+        self.url_cursor = Cursor::at_end(&self.url_input);
+        self.url_suggestions.clear();
+        self.url_suggestion_selected = None;
+        true
+    }
+
+    /// Pastes text into the URL field at the cursor as-is, on a single line
+    pub fn paste_into_url(&mut self, text: &str) {
+        let text: String = text
+            .trim()
+            .chars()
+            .filter(|c| *c != '\n' && *c != '\r')
+            .collect();
+        self.url_cursor.insert(&mut self.url_input, &text);
+        self.update_url_suggestions();
+    }
+
+    pub fn show_cookies(&mut self) {
+        if self.current_screen != CurrentScreen::Cookies {
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::Cookies;
+            self.cookie_selected = 0;
+        }
+    }
+
+    pub fn hide_cookies(&mut self) {
+        if self.current_screen == CurrentScreen::Cookies {
+            self.current_screen = self.previous_screen;
+        }
+    }
+
+    pub fn selected_cookie(&self) -> Option<Cookie> {
+        self.cookie_jar.list().into_iter().nth(self.cookie_selected)
+    }
+
+    /// Opens the prompt for resending with a single header or param changed
+    pub fn show_resend_with(&mut self) {
+        if self.current_screen != CurrentScreen::ResendWith {
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::ResendWith;
+        }
+    }
+
+    pub fn hide_resend_with(&mut self) {
+        if self.current_screen == CurrentScreen::ResendWith {
+            self.current_screen = self.previous_screen;
+        }
+    }
+
+    /// Opens the prompt for the file or directory to watch
+    pub fn show_watch_prompt(&mut self) {
+        if self.current_screen != CurrentScreen::EditingWatch {
+            self.watch_input = self
+                .watch
+                .as_ref()
+                .map(|(_, watch)| watch.path.display().to_string())
+                .unwrap_or_default();
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::EditingWatch;
+        }
+    }
+
+    pub fn hide_watch_prompt(&mut self) {
+        if self.current_screen == CurrentScreen::EditingWatch {
+            self.current_screen = self.previous_screen;
+        }
+    }
+
+    /// Starts re-sending the current tab's request when files under a path
+    /// change, even once another tab is selected; an empty path stops watching
+    pub fn start_watch(&mut self, path: &str) -> Result<()> {
+        let path = path.trim();
+        self.watch = if path.is_empty() {
+            None
+        } else {
+            Some((
+                self.tabs[self.selected_tab].id,
+                Watcher::new(crate::paths::expand_home(path))?,
+            ))
+        };
+        Ok(())
+    }
+
+    /// The index of the watching tab when a watched change is due for a send
+    ///
+    /// Changes wait while a prompt or editor is open, so a half-typed
+    /// request isn't sent, and while the tab still waits for its last
+    /// response, so a change during a send is sent once it's in. Closing the
+    /// tab stops watching.
+    pub fn poll_watch(&mut self, now: std::time::Instant) -> Option<usize> {
+        let (id, _) = self.watch.as_ref()?;
+        let Some(index) = self.tab_index(*id) else {
+            self.watch = None;
+            return None;
+        };
+        let idle = matches!(
+            self.current_screen,
+            CurrentScreen::Url | CurrentScreen::Values | CurrentScreen::Response
+        ) && self.tabs[index].in_flight.is_none();
+        let due = idle
+            && self
+                .watch
+                .as_mut()
+                .is_some_and(|(_, watch)| watch.poll(now));
+        due.then_some(index)
+    }
+
+    /// Opens the prompt for the polling interval
+    pub fn show_poll_prompt(&mut self) {
+        if self.current_screen != CurrentScreen::EditingPoll {
+            let interval = self
+                .poll
+                .as_ref()
+                .map_or(poll::DEFAULT_INTERVAL, |(_, poll)| poll.interval);
+            self.poll_input = poll::format_interval(interval);
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::EditingPoll;
+        }
+    }
+
+    pub fn hide_poll_prompt(&mut self) {
+        if self.current_screen == CurrentScreen::EditingPoll {
+            self.current_screen = self.previous_screen;
+        }
+    }
+
+    /// Starts re-sending the current tab's request every interval, such as
+    /// `5s`, even once another tab is selected; an empty interval stops
+    /// polling
+    pub fn start_poll(&mut self, interval: &str) -> Result<()> {
+        self.poll = if interval.trim().is_empty() {
+            None
+        } else {
+            Some((
+                self.tabs[self.selected_tab].id,
+                Poller::new(poll::parse_interval(interval)?, std::time::Instant::now()),
+            ))
+        };
+        Ok(())
+    }
+
+    /// The index of the polled tab when its next send is due
+    ///
+    /// Like watched changes, it waits while a prompt or editor is open or the
+    /// tab still waits for the last response. Closing the tab stops polling.
+    pub fn poll_interval(&mut self, now: std::time::Instant) -> Option<usize> {
+        let (id, _) = self.poll.as_ref()?;
+        let Some(index) = self.tab_index(*id) else {
+            self.poll = None;
+            return None;
+        };
+        let idle = matches!(
+            self.current_screen,
+            CurrentScreen::Url | CurrentScreen::Values | CurrentScreen::Response
+        ) && self.tabs[index].in_flight.is_none();
+        let due = idle && self.poll.as_mut().is_some_and(|(_, poll)| poll.poll(now));
+        due.then_some(index)
+    }
+
+    /// The index of the open tab with `id`
+    pub fn tab_index(&self, id: usize) -> Option<usize> {
+        self.tabs.iter().position(|tab| tab.id == id)
+    }
+
+    /// Opens a `.http` file as a collection and selects its first request
+    ///
+    /// Opening a file again re-reads it.
+    pub fn open_http_file(&mut self, path: &Path) -> Result<()> {
+        self.add_collection(import::load_http_file(path)?);
+        if !self.http_files.iter().any(|watch| watch.path == path) {
+            self.http_files.push(Watcher::new(path.to_path_buf())?);
+        }
+        Ok(())
+    }
+
+    /// Opens a collection file given on the command line in the collection
+    /// browser; `.http` files are watched like with `--open`
+    pub fn open_collection_file(&mut self, path: &Path) -> Result<()> {
+        if path.extension().is_some_and(|ext| ext == "http") {
+            self.open_http_file(path)?;
+        } else {
+            self.add_collection(collections::load_collection(path)?);
+        }
+        self.show_collections();
+        Ok(())
+    }
+
+    /// Adds a collection, or replaces the one loaded from the same file, and
+    /// selects its first request
+    fn add_collection(&mut self, collection: Collection) {
+        let index = match self
+            .collections
+            .iter()
+            .position(|other| other.path.as_ref() == collection.path.as_ref())
+        {
+            Some(index) => {
+                self.collections[index] = collection;
+                index
+            }
+            None => {
+                self.collections.push(collection);
+                self.collections.len() - 1
+            }
+        };
+        self.collection_selected = self
+            .collection_entries()
+            .iter()
+            .position(|&(c, _)| c == index)
+            .unwrap_or(self.collection_selected);
+    }
+
+    /// Re-reads the `.http` files that changed on disk and reloads the tabs
+    /// opened from them, returning whether the current tab should be sent
+    ///
+    /// Like [`App::poll_watch`], changes wait while a prompt or editor is open.
+    pub fn poll_http_files(&mut self, now: std::time::Instant) -> Result<bool> {
+        let idle = matches!(
+            self.current_screen,
+            CurrentScreen::Url | CurrentScreen::Values | CurrentScreen::Response
+        );
+        if !idle {
+            return Ok(false);
+        }
+        let changed: Vec<PathBuf> = self
+            .http_files
+            .iter_mut()
+            .filter_map(|watch| watch.poll(now).then(|| watch.path.clone()))
+            .collect();
+
+        let mut reloaded = false;
+        for path in changed {
+            let collection = import::load_http_file(&path)?;
+            if let Some(existing) = self
+                .collections
+                .iter_mut()
+                .find(|other| other.path.as_ref() == Some(&path))
+            {
+                *existing = collection.clone();
+            }
+            for (index, tab) in self.tabs.iter_mut().enumerate() {
+                let Some((_, name)) = tab.http_file.as_ref().filter(|(file, _)| *file == path)
+                else {
+                    continue;
+                };
+                let saved = collection
+                    .requests
+                    .iter()
+                    .find(|saved| saved.name == *name)
+                    .ok_or_else(|| {
+                        RestlessError::app_state(format!(
+                            "{} no longer has a request named {}",
+                            path.display(),
+                            name
+                        ))
+                    })?;
+                tab.request = saved.to_request(&collection.auth)?;
+                reloaded |= index == self.selected_tab;
+            }
+        }
+        if reloaded {
+            self.restore_current_tab_state()?;
+        }
+        Ok(reloaded && self.send_on_save)
+    }
+
+    /// Switches the body between text and the table view
+    pub fn toggle_response_table(&mut self) -> Result<()> {
+        let has_table = self.tabs[self.selected_tab]
+            .response
+            .as_ref()
+            .is_some_and(|response| response.table.is_some());
+        if !has_table && !self.response_table {
+            return Err(RestlessError::app_state(
+                "The table view needs a JSON array of objects",
+            ));
+        }
+        self.response_table = !self.response_table;
+        self.response_tree = None;
+        self.response_tab_selected = 1;
+        self.response_scroll = 0;
+        Ok(())
+    }
+
+    /// Switches the body between text and the JSON tree view
+    pub fn toggle_response_tree(&mut self) -> Result<()> {
+        if self.response_tree.take().is_some() {
+            return Ok(());
+        }
+        let tree = self.tabs[self.selected_tab]
+            .response
+            .as_ref()
+            .and_then(|_| Tree::from_json(&self.body_text()))
+            .ok_or_else(|| {
+                RestlessError::app_state("The tree view needs a JSON object or array")
+            })?;
+        self.response_tree = Some(tree);
+        self.response_table = false;
+        self.search = None;
+        self.response_tab_selected = 1;
+        self.response_scroll = 0;
+        Ok(())
+    }
+
+    /// Switches HTML bodies between markup and the rendered text preview
+    pub fn toggle_html_preview(&mut self) -> Result<()> {
+        if !self.html_preview && !self.current_response_is_html() {
+            return Err(RestlessError::app_state(
+                "The preview needs a text/html response",
+            ));
+        }
+        self.html_preview = !self.html_preview;
+        self.response_table = false;
+        self.response_tree = None;
+        self.search = None;
+        self.response_tab_selected = 1;
+        self.response_scroll = 0;
+        Ok(())
+    }
+
+    /// Shows the cached copy a `304 Not Modified` stands for, or hides it
+    pub fn toggle_cached_body(&mut self) -> Result<()> {
+        let toggled = self.tabs[self.selected_tab]
+            .response
+            .as_mut()
+            .is_some_and(|response| response.toggle_cached());
+        if !toggled {
+            return Err(RestlessError::app_state(
+                "Only a 304 Not Modified to a revalidated GET has a cached copy",
+            ));
+        }
+        self.response_table = false;
+        self.response_tree = None;
+        self.search = None;
+        self.response_tab_selected = 1;
+        self.response_scroll = 0;
+        Ok(())
+    }
+
+    /// Maximizes the response section, moving there, or restores the layout
+    pub fn toggle_response_zoom(&mut self) {
+        self.response_zoomed = !self.response_zoomed;
+        if self.response_zoomed {
+            self.current_screen = CurrentScreen::Response;
+        }
+    }
+
+    /// Switches to the next layout, stacked, split and auto in turn
+    pub fn cycle_layout(&mut self) {
+        self.layout = self.layout.next();
+        let label = self.i18n.tr(self.layout.label()).to_string();
+        self.show_toast(self.i18n.format("Layout: {layout}", &[("layout", &label)]));
+    }
+
+    /// Compares the response with the next tab that has one, or stops
+    /// comparing after the last
+    pub fn cycle_compare(&mut self) -> Result<()> {
+        let candidates: Vec<usize> = (0..self.tabs.len())
+            .filter(|&i| i != self.selected_tab && self.tabs[i].response.is_some())
+            .collect();
+        if candidates.is_empty() || self.tabs[self.selected_tab].response.is_none() {
+            return Err(RestlessError::app_state(
+                "Comparing needs responses in two tabs",
+            ));
+        }
+        let next = match self.compare_tab.and_then(|id| self.tab_index(id)) {
+            None => candidates.first().copied(),
+            Some(current) => candidates.into_iter().find(|&i| i > current),
+        };
+        self.compare_tab = next.map(|i| self.tabs[i].id);
+        self.response_scroll = 0;
+        Ok(())
+    }
+
+    /// The tab shown beside the current one, while it still has a response
+    pub fn compared_tab(&self) -> Option<&Tab> {
+        self.compare_tab
+            .and_then(|id| self.tab_index(id))
+            .filter(|&i| i != self.selected_tab)
+            .and_then(|i| self.tabs.get(i))
+            .filter(|tab| tab.response.is_some())
+    }
+
+    /// Whether the body is shown as rendered HTML right now
+    pub fn html_preview_active(&self) -> bool {
+        self.html_preview && self.current_response_is_html()
+    }
+
+    fn current_response_is_html(&self) -> bool {
+        self.tabs[self.selected_tab]
+            .response
+            .as_ref()
+            .is_some_and(|response| response.is_html())
+    }
+
+    /// Whether keys go to the tree view: it is open and its tab is shown
+    pub fn tree_active(&self) -> bool {
+        self.response_tree.is_some() && self.response_tab_selected == 1
+    }
+
+    /// Shows the current tab's response in the open tree view, leaving the
+    /// view when that response isn't JSON
+    pub fn refresh_tree(&mut self) {
+        let has_response = self.tabs[self.selected_tab].response.is_some();
+        let body = self.body_text().into_owned();
+        if let Some(tree) = &mut self.response_tree {
+            if !has_response || !tree.reload(&body) {
+                self.response_tree = None;
+            }
+        }
+    }
+
+    /// Shows the response before (or after) the shown one in the current tab
+    pub fn step_response(&mut self, newer: bool) -> Result<()> {
+        let tab = &mut self.tabs[self.selected_tab];
+        let stepped = if newer {
+            tab.show_newer_response()
+        } else {
+            tab.show_older_response()
+        };
+        if !stepped {
+            return Err(RestlessError::app_state(if newer {
+                "This is the latest response"
+            } else {
+                "No older response kept for this tab"
+            }));
+        }
+        self.response_scroll = 0;
+        self.response_headers_scroll.set(0);
+        self.response_column.set(0);
+        self.response_part_selected = 0;
+        self.table_column = 0;
+        // A search belongs to the response it was typed over
+        self.search = None;
+        self.refresh_tree();
+        Ok(())
+    }
+
+    /// Scrolls the table view one column left or right
+    pub fn move_table_column(&mut self, right: bool) {
+        let columns = self.tabs[self.selected_tab]
+            .response
+            .as_ref()
+            .and_then(|response| response.table.as_ref())
+            .map_or(0, |table| table.columns.len());
+        self.table_column = if right {
+            (self.table_column + 1).min(columns.saturating_sub(1))
+        } else {
+            self.table_column.saturating_sub(1)
+        };
+    }
+
+    /// Sorts the table by its leftmost visible column
+    pub fn sort_table(&mut self) {
+        let column = self.table_column;
+        if let Some(table) = self.tabs[self.selected_tab]
+            .response
+            .as_mut()
+            .and_then(|response| response.table.as_mut())
+        {
+            table.sort_by(column);
+            self.response_scroll = 0;
+        }
+    }
+
+    /// The table view as CSV, in its current sort order
+    pub fn table_csv(&self) -> Result<String> {
+        self.tabs[self.selected_tab]
+            .response
+            .as_ref()
+            .and_then(|response| response.table.as_ref())
+            .map(|table| table.to_csv())
+            .ok_or_else(|| RestlessError::app_state("No table to export"))
+    }
+
+    /// Writes the table view as CSV to a file in the working directory
+    pub fn save_table_csv(&self) -> Result<PathBuf> {
+        let csv = self.table_csv()?;
+        let name: String = self.tabs[self.selected_tab]
+            .name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        let path = PathBuf::from(format!(
+            "restless-{}-{}.csv",
+            name.to_lowercase(),
+            chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        ));
+        std::fs::write(&path, csv)?;
+        Ok(path)
+    }
+
+    /// Appends newly arrived events to every streaming response, returning
+    /// whether any stream is still open
+    pub fn poll_streams(&mut self) -> bool {
+        let mut open = false;
+        for response in self.tabs.iter_mut().filter_map(|tab| tab.response.as_mut()) {
+            open |= response.poll_stream();
+        }
+        open
+    }
+
+    /// Whether any tab is waiting for a response, or a load test is running
+    pub fn sending(&self) -> bool {
+        self.load_test.is_some()
+            || self.run.is_some()
+            || self.tabs.iter().any(|tab| tab.in_flight.is_some())
+    }
+
+    /// Seconds until the window of the current tab's `429 Too Many
+    /// Requests` opens, and whether the send is retried then
+    pub fn rate_limit_wait(&self, now: DateTime<Utc>) -> Option<(i64, bool)> {
+        let tab = &self.tabs[self.selected_tab];
+        if let Some(retry) = &tab.retry {
+            return Some(((retry.at - now).num_seconds().max(0), true));
+        }
+        let at = tab
+            .response
+            .as_ref()
+            .filter(|response| response.status_code == 429)?
+            .rate_limit
+            .as_ref()?
+            .retry_at()?;
+        Some(((at - now).num_seconds().max(0), false))
+    }
+
+    /// Whether a retry or the countdown to a rate limit window is pending
+    pub fn rate_limited(&self, now: DateTime<Utc>) -> bool {
+        self.tabs.iter().any(|tab| tab.retry.is_some())
+            || self.rate_limit_wait(now).is_some_and(|(wait, _)| wait > 0)
+    }
+
+    /// Sends again the rate limited requests whose window has opened
+    pub fn start_due_retries(&mut self, now: DateTime<Utc>) {
+        for tab in &mut self.tabs {
+            if tab.in_flight.is_none() && tab.retry.as_ref().is_some_and(|retry| retry.at <= now) {
+                tab.in_flight = tab.retry.take().map(Retry::spawn);
+            }
+        }
+    }
+
+    /// Closes the event stream of the current tab's response
+    pub fn stop_stream(&mut self) -> bool {
+        self.tabs[self.selected_tab]
+            .response
+            .as_mut()
+            .is_some_and(|response| response.stop_stream())
+    }
+
+    /// Switches the response to the custom panel of the tab's saved request
+    pub fn show_panel(&mut self) -> Result<()> {
+        if self.tabs[self.selected_tab].panel.is_none() {
+            return Err(RestlessError::app_state(
+                "No panel for this request; give it a \"panel\" template in its collection",
+            ));
+        }
+        self.response_tab_selected = 3;
+        self.response_scroll = 0;
+        Ok(())
+    }
+
+    /// Switches the response to its TLS tab, starting to look up the
+    /// negotiated protocol and cipher the first time
+    pub fn show_tls_details(&mut self) {
+        self.response_tab_selected = 2;
+        self.response_scroll = 0;
+        let tab = &mut self.tabs[self.selected_tab];
+        let running = self.tls_probe.as_ref().is_some_and(|(id, _)| *id == tab.id);
+        if let Some(tls) = tab
+            .response
+            .as_mut()
+            .and_then(|response| response.tls.as_mut())
+        {
+            // Started again if another lookup took over before it was done
+            if tls.needs_probe() && !(running && tls.probing) {
+                self.tls_probe = Some((tab.id, tls.start_probe()));
+            }
+        }
+    }
+
+    /// Fills in the protocol and cipher once the lookup is done; true when
+    /// it finished
+    pub fn poll_tls_probe(&mut self) -> bool {
+        let Some(outcome) = self.tls_probe.as_mut().and_then(|(_, probe)| probe.poll()) else {
+            return false;
+        };
+        let Some((id, probe)) = self.tls_probe.take() else {
+            return false;
+        };
+        // The response may have been replaced or its tab closed meanwhile
+        let tls = self
+            .tab_index(id)
+            .and_then(|index| self.tabs[index].response.as_mut())
+            .and_then(|response| response.tls.as_mut())
+            .filter(|tls| tls.probing && tls.host == probe.host && tls.port == probe.port);
+        if let Some(tls) = tls {
+            tls.finish_probe(outcome);
+        }
+        true
+    }
+
+    /// Starts editing the note on the current response
+    pub fn start_note_edit(&mut self) -> Result<()> {
+        let response = self.tabs[self.selected_tab]
+            .response
+            .as_ref()
+            .ok_or_else(|| RestlessError::app_state("No response to annotate yet"))?;
+        self.note_input = response.note.clone().unwrap_or_default();
+        self.current_screen = CurrentScreen::EditingNote;
+        Ok(())
+    }
+
+    /// Stores the note on the current response; an empty note removes it
+    pub fn save_note(&mut self) {
+        let note = self.note_input.trim();
+        if let Some(response) = &mut self.tabs[self.selected_tab].response {
+            response.note = (!note.is_empty()).then(|| note.to_string());
+        }
+        self.note_input.clear();
+        self.current_screen = CurrentScreen::Response;
+    }
+
+    /// Opens the search prompt over the response body
+    pub fn start_search(&mut self) -> Result<()> {
+        let response = self.tabs[self.selected_tab]
+            .response
+            .as_ref()
+            .ok_or_else(|| RestlessError::app_state("No response to search yet"))?;
+        if !response.parts.is_empty() {
+            return Err(RestlessError::app_state(
+                "Search isn't available for multipart bodies",
+            ));
+        }
+        if response.hex_bytes().is_some() {
+            return Err(RestlessError::app_state(
+                "Search isn't available for binary bodies",
+            ));
+        }
+        self.response_tab_selected = 1;
+        self.response_table = false;
+        self.response_tree = None;
+        self.search_input.clear();
+        self.current_screen = CurrentScreen::Searching;
+        Ok(())
+    }
+
+    /// Searches for the typed query from the top of the visible body;
+    /// an empty query clears the search
+    pub fn run_search(&mut self) -> Result<()> {
+        self.current_screen = CurrentScreen::Response;
+        let query = std::mem::take(&mut self.search_input);
+        if query.is_empty() {
+            self.search = None;
+            return Ok(());
+        }
+        let mut search = Search::new(query);
+        let found = search.jump_from(&self.body_text(), self.response_scroll);
+        let Some(found) = found else {
+            self.search = None;
+            return Err(RestlessError::app_state(format!(
+                "Pattern not found: {}",
+                search.query
+            )));
+        };
+        self.response_scroll = found.line.saturating_sub(search::CONTEXT_LINES);
+        self.search = Some(search);
+        Ok(())
+    }
+
+    /// Scrolls to the next or previous match, returning false when no
+    /// search is active
+    pub fn step_search(&mut self, back: bool) -> bool {
+        let body = self.body_text().into_owned();
+        let Some(search) = self.search.as_mut() else {
+            return false;
+        };
+        if let Some(found) = search.step(&body, back) {
+            self.response_tab_selected = 1;
+            self.response_scroll = found.line.saturating_sub(search::CONTEXT_LINES);
+        }
+        true
+    }
+
+    /// Opens the filter prompt, starting from the tab's current filter
+    pub fn start_filter(&mut self) {
+        self.filter_input = self.tabs[self.selected_tab]
+            .filter
+            .clone()
+            .unwrap_or_default();
+        self.current_screen = CurrentScreen::EditingFilter;
+    }
+
+    /// Sets the typed filter on the current tab; an empty one clears it
+    pub fn apply_filter(&mut self) -> Result<()> {
+        let expression = self.filter_input.trim().to_string();
+        if !expression.is_empty() {
+            jsonpath::validate(&expression)?;
+        }
+        self.filter_input.clear();
+        self.current_screen = CurrentScreen::Response;
+        self.tabs[self.selected_tab].filter = (!expression.is_empty()).then_some(expression);
+        self.response_tab_selected = 1;
+        self.response_table = false;
+        self.response_scroll = 0;
+        self.search = None;
+        self.refresh_tree();
+        Ok(())
+    }
+
+    /// The current tab's response body run through its filter, or `None`
+    /// without a response or a filter
+    pub fn filtered_body(&self) -> Option<Result<String>> {
+        let tab = &self.tabs[self.selected_tab];
+        let filter = tab.filter.as_deref()?;
+        let response = tab.response.as_ref()?;
+        Some(jsonpath::filter(&response.body, filter))
+    }
+
+    /// The body as shown: filtered when the tab's filter applies, rendered
+    /// in the HTML preview
+    pub fn body_text(&self) -> Cow<'_, str> {
+        let Some(response) = self.tabs[self.selected_tab].response.as_ref() else {
+            return Cow::Borrowed("");
+        };
+        match self.filtered_body() {
+            Some(Ok(filtered)) => Cow::Owned(filtered),
+            _ if self.html_preview_active() => Cow::Owned(html::to_text(&response.body)),
+            _ => Cow::Borrowed(response.body.as_str()),
+        }
+    }
+
+    /// Starts editing the selected cookie, or adding one when `add` is set
+    pub fn start_cookie_edit(&mut self, add: bool) {
+        self.cookie_editing = if add { None } else { self.selected_cookie() };
+        self.cookie_input = match &self.cookie_editing {
+            Some(cookie) => cookie.to_set_cookie(),
+            None => String::new(),
+        };
+        self.current_screen = CurrentScreen::EditingCookie;
+    }
+
+    /// Stores the cookie being edited and returns to the cookie list
+    pub fn save_cookie_edit(&mut self) -> Result<()> {
+        let cookie = Cookie::parse_manual(&self.cookie_input).ok_or_else(|| {
+            RestlessError::invalid_header(
+                "Cookie must look like name=value; Domain=example.com[; Path=/]",
+            )
+        })?;
+        if let Some(original) = self.cookie_editing.take() {
+            self.cookie_jar.remove(&original);
+        }
+        self.cookie_jar.insert(cookie);
+        self.cookie_input.clear();
+        self.current_screen = CurrentScreen::Cookies;
+        Ok(())
+    }
+
+    pub fn delete_selected_cookie(&mut self) {
+        if let Some(cookie) = self.selected_cookie() {
+            self.cookie_jar.remove(&cookie);
+            let remaining = self.cookie_jar.list().len();
+            self.cookie_selected = self.cookie_selected.min(remaining.saturating_sub(1));
+        }
+    }
+
+    /// Loads user collections from the config directory
+    ///
+    /// The built-in tutorial always comes first and survives a failed load.
+    pub fn load_collections(&mut self) -> Result<()> {
+        self.collections = vec![collections::tutorial::tutorial()];
+        self.collection_selected = 0;
+        if let Some(dir) = crate::paths::collections_dir() {
+            self.collections
+                .extend(collections::load_collections(&dir)?);
+        }
+        Ok(())
+    }
+
+    /// Every saved request as (collection index, request index), in display order
+    pub fn collection_entries(&self) -> Vec<(usize, usize)> {
+        self.collections
+            .iter()
+            .enumerate()
+            .flat_map(|(c, collection)| (0..collection.requests.len()).map(move |r| (c, r)))
+            .collect()
+    }
+
+    pub fn show_collections(&mut self) {
+        if self.current_screen != CurrentScreen::Collections {
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::Collections;
+        }
+    }
+
+    pub fn hide_collections(&mut self) {
+        if self.current_screen == CurrentScreen::Collections {
+            self.current_screen = self.previous_screen;
+        }
+    }
+
+    /// Prompts for a HAR file, Postman collection or OpenAPI document to import
+    pub fn start_import(&mut self) {
+        self.import_input.clear();
+        self.current_screen = CurrentScreen::EditingImportPath;
+    }
+
+    /// Drops a pending import and returns to the collections
+    pub fn cancel_import(&mut self) {
+        self.import_review = None;
+        self.current_screen = CurrentScreen::Collections;
+    }
+
+    /// Reads the file named in the import prompt and opens the review
+    ///
+    /// The requests go into the collection of the selected request, or into
+    /// a new collection named after the import when the tutorial is selected.
+    pub fn load_import(&mut self) -> Result<()> {
+        let path = crate::paths::expand_home(self.import_input.trim());
+        if path.extension().is_some_and(|ext| ext == "http") {
+            self.open_http_file(&path)?;
+            self.current_screen = CurrentScreen::Collections;
+            return Ok(());
+        }
+        let (name, requests) = import::load_file(&path)?;
+        if requests.is_empty() {
+            return Err(RestlessError::app_state(format!(
+                "No requests to import in {}",
+                path.display()
+            )));
+        }
+
+        let selected = self
+            .collection_entries()
+            .get(self.collection_selected)
+            .map(|&(c, _)| c)
+            .filter(|&c| self.collections[c].path.is_some() && !self.collections[c].is_http_file());
+        let (collection, index) = match selected {
+            Some(c) => (self.collections[c].clone(), Some(c)),
+            None => self.collection_named(name)?,
+        };
+        self.import_review = Some(Review::new(
+            path.display().to_string(),
+            collection,
+            index,
+            requests,
+        ));
+        self.current_screen = CurrentScreen::ImportReview;
+        Ok(())
+    }
+
+    /// The user collection stored under a name's file, or a new one
+    fn collection_named(&self, name: String) -> Result<(Collection, Option<usize>)> {
+        let dir = crate::paths::collections_dir().ok_or_else(|| {
+            RestlessError::configuration("No config directory to save collections in")
+        })?;
+        let file_name: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        let file = dir.join(format!("{}.json", file_name.to_lowercase()));
+        match self
+            .collections
+            .iter()
+            .position(|collection| collection.path.as_ref() == Some(&file))
+        {
+            Some(c) => Ok((self.collections[c].clone(), Some(c))),
+            None => Ok((
+                Collection {
+                    name,
+                    path: Some(file),
+                    ..Default::default()
+                },
+                None,
+            )),
+        }
+    }
+
+    /// Moves the selection in the import review
+    pub fn move_import_selection(&mut self, down: bool) {
+        if let Some(review) = self.import_review.as_mut() {
+            review.selected = if down {
+                (review.selected + 1).min(review.items.len().saturating_sub(1))
+            } else {
+                review.selected.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Chooses what happens to the selected duplicate, or to all of them
+    pub fn resolve_import(&mut self, resolution: Resolution, all: bool) {
+        if let Some(review) = self.import_review.as_mut() {
+            review.resolve(resolution, all);
+        }
+    }
+
+    /// Applies the reviewed import and saves the collection
+    pub fn apply_import(&mut self) -> Result<String> {
+        let mut review = self
+            .import_review
+            .clone()
+            .ok_or_else(|| RestlessError::app_state("Nothing to import"))?;
+        let summary = review.apply();
+        collections::save_collection(&review.collection)?;
+        revisions::track(
+            self.history.as_mut(),
+            &review.collection,
+            chrono::Utc::now(),
+        )?;
+
+        let message = format!("Imported into {}: {}", review.collection.name, summary);
+        match review.index {
+            Some(index) => self.collections[index] = review.collection,
+            None => self.collections.push(review.collection),
+        }
+        self.cancel_import();
+        Ok(message)
+    }
+
+    /// Records revisions of the saved requests edited since the last run;
+    /// call after `load_collections` and `load_history`
+    pub fn track_revisions(&mut self) -> Result<()> {
+        for collection in &self.collections {
+            let modified = collection
+                .path
+                .as_ref()
+                .and_then(|path| path.metadata().ok()?.modified().ok())
+                .map(chrono::DateTime::from)
+                .unwrap_or_else(chrono::Utc::now);
+            revisions::track(self.history.as_mut(), collection, modified)?;
+        }
+        Ok(())
+    }
+
+    /// Opens the timeline of the selected saved request
+    pub fn show_timeline(&mut self) -> Result<()> {
+        let Some(&(c, r)) = self.collection_entries().get(self.collection_selected) else {
+            return Err(RestlessError::app_state("No saved request selected"));
+        };
+        let collection = &self.collections[c];
+        if !revisions::is_tracked(collection) {
+            return Err(RestlessError::app_state(
+                "Only collections in the collections directory keep revisions",
+            ));
+        }
+        let timeline = revisions::timeline(
+            self.history.as_ref(),
+            &collection.name,
+            &collection.requests[r].name,
+        )?;
+        if timeline.is_empty() {
+            return Err(RestlessError::app_state("No revisions of this request yet"));
+        }
+        self.timeline = timeline;
+        self.timeline_selected = 0;
+        self.current_screen = CurrentScreen::Timeline;
+        Ok(())
+    }
+
+    pub fn hide_timeline(&mut self) {
+        self.timeline.clear();
+        self.current_screen = CurrentScreen::Collections;
+    }
+
+    /// Writes the selected version back to the collection and opens it
+    pub fn restore_revision(&mut self) -> Result<()> {
+        let Some(&(c, r)) = self.collection_entries().get(self.collection_selected) else {
+            return Err(RestlessError::app_state("No saved request selected"));
+        };
+        let version = self
+            .timeline
+            .get(self.timeline_selected)
+            .ok_or_else(|| RestlessError::app_state("No revision selected"))?;
+        let collection = &mut self.collections[c];
+        collection.requests[r] = version.request.clone();
+        collections::save_collection(collection)?;
+        revisions::track(self.history.as_mut(), collection, chrono::Utc::now())?;
+        self.hide_timeline();
+        self.open_selected_collection_request()
+    }
+
+    /// Opens the selected saved request in a tab
+    pub fn open_selected_collection_request(&mut self) -> Result<()> {
+        let Some(&(c, r)) = self.collection_entries().get(self.collection_selected) else {
+            return Err(RestlessError::app_state("No saved request selected"));
+        };
+        self.open_collection_request(c, r)
+    }
+
+    /// Opens request `r` of collection `c` in a tab
+    ///
+    /// The current tab is reused when it is still blank, otherwise a new tab
+    /// is created.
+    pub fn open_collection_request(&mut self, c: usize, r: usize) -> Result<()> {
+        let collection = &self.collections[c];
+        let saved = &collection.requests[r];
+        let request = saved.to_request(&collection.auth)?;
+        let name = saved.name.clone();
+        let panel = saved.panel.clone();
+        let http_file = collection
+            .is_http_file()
+            .then(|| collection.path.clone())
+            .flatten()
+            .map(|path| (path, name.clone()));
+
+        if !self.current_tab_is_blank() {
+            self.add_new_tab()?;
+        }
+
+        let tab = &mut self.tabs[self.selected_tab];
+        tab.name = name;
+        tab.request = request;
+        tab.panel = panel;
+        tab.http_file = http_file;
+        self.restore_current_tab_state()?;
+        self.hide_collections();
+        Ok(())
+    }
+
+    /// Opens the finder over the open tabs, the saved requests and the
+    /// URLs sent before
+    ///
+    /// Like URL suggestions, a history that can't be read just adds nothing.
+    pub fn show_finder(&mut self) -> Result<()> {
+        self.save_current_tab_state()?;
+        let mut items: Vec<Item> = self
+            .tabs
+            .iter()
+            .enumerate()
+            .map(|(i, tab)| Item {
+                target: Target::Tab(i),
+                method: tab.request.method.to_string(),
+                name: tab.name.clone(),
+                url: tab.request.url.clone(),
+            })
+            .collect();
+        for (c, r) in self.collection_entries() {
+            let collection = &self.collections[c];
+            let saved = &collection.requests[r];
+            items.push(Item {
+                target: Target::Saved(c, r),
+                method: saved.method.to_uppercase(),
+                name: format!("{} / {}", collection.name, saved.name),
+                url: saved.url.clone(),
+            });
+        }
+        let mut sent: Vec<(String, String)> = Vec::new();
+        for entry in self
+            .history
+            .search("", finder::HISTORY_LIMIT * 5)
+            .unwrap_or_default()
+        {
+            let key = (entry.method, entry.url);
+            if !sent.contains(&key) {
+                sent.push(key);
+                if sent.len() == finder::HISTORY_LIMIT {
+                    break;
+                }
+            }
+        }
+        items.extend(sent.into_iter().map(|(method, url)| Item {
+            target: Target::History {
+                method: method.clone(),
+                url: url.clone(),
+            },
+            method,
+            name: String::new(),
+            url,
+        }));
+
+        self.finder = Some(Finder::new(items));
+        if self.current_screen != CurrentScreen::Finder {
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::Finder;
+        }
+        Ok(())
+    }
+
+    pub fn hide_finder(&mut self) {
+        self.finder = None;
+        if self.current_screen == CurrentScreen::Finder {
+            self.current_screen = self.previous_screen;
+        }
+    }
+
+    /// Closes the finder and jumps to what is selected in it
+    pub fn open_finder_selection(&mut self) -> Result<()> {
+        let target = self
+            .finder
+            .as_ref()
+            .and_then(|finder| finder.selected_item())
+            .map(|item| item.target.clone());
+        self.hide_finder();
+        match target {
+            None => Ok(()),
+            Some(Target::Tab(index)) => {
+                self.save_current_tab_state()?;
+                self.close_warned = None;
+                self.selected_tab = index;
+                self.restore_current_tab_state()
+            }
+            Some(Target::Saved(c, r)) => self.open_collection_request(c, r),
+            Some(Target::History { method, url }) => {
+                self.open_url(&url)?;
+                // Methods this app can't pick stay GET
+                if let Some(method) = reqwest::Method::from_bytes(method.as_bytes())
+                    .ok()
+                    .and_then(|method| HttpMethod::try_from(&method).ok())
+                {
+                    self.selected_method = method;
+                }
+                self.save_current_tab_state()
+            }
+        }
+    }
+
+    /// Opens a URL given on the command line, in a new tab unless the
+    /// current one is still empty; its query goes into the params
+    pub fn open_url(&mut self, url: &str) -> Result<()> {
+        if !self.current_tab_is_blank() {
+            self.add_new_tab()?;
+        }
+        self.url_input = url.trim().to_string();
+        self.finish_url_edit();
+        self.save_current_tab_state()
+    }
+
+    /// Whether the current tab has no request or response yet
+    fn current_tab_is_blank(&self) -> bool {
+        let current = self.current_request();
+        current.url.is_empty()
+            && current.body.is_none()
+            && current.headers.is_empty()
+            && current.params.is_empty()
+            && self.tabs[self.selected_tab].response.is_none()
+    }
+
+    pub fn active_environment(&self) -> Option<&Environment> {
+        self.active_environment
+            .and_then(|index| self.environments.get(index))
+    }
+
+    /// The environment requests are sent in: the active one with the
+    /// extracted variables over it
+    pub fn send_environment(&self) -> Option<Environment> {
+        if self.extracted.is_empty() {
+            return self.active_environment().cloned();
+        }
+        let mut env = self.active_environment().cloned().unwrap_or_default();
+        env.variables.extend(self.extracted.clone());
+        Some(env)
+    }
+
+    /// Cycles through "no environment" and every loaded environment
+    pub fn cycle_environment(&mut self) {
+        self.active_environment = match self.active_environment {
+            None if !self.environments.is_empty() => Some(0),
+            Some(index) if index + 1 < self.environments.len() => Some(index + 1),
+            _ => None,
+        };
+    }
+
+    /// Terminal title for the active tab, e.g. `restless: Users GET 200`
+    pub fn window_title(&self) -> String {
+        let tab = &self.tabs[self.selected_tab];
+        let mut title = format!("restless: {} {:?}", tab.name, self.selected_method);
+        if let Some(response) = &tab.response {
+            title.push_str(&format!(" {}", response.status_code));
+        }
+        title
+    }
+
+    /// Builds a request from the current (possibly unsaved) inputs
+    ///
+    /// Settings without an input field are taken from the tab's saved request.
+    pub fn current_request(&self) -> Request {
+        let mut request = self
+            .tabs
+            .get(self.selected_tab)
+            .map(|tab| tab.request.clone())
+            .unwrap_or_default();
+        request.url = self.url_input.clone();
+        request.method = (&self.selected_method).into();
+        request.headers = self.headers_input.clone();
+        request.body = if self.body_input.is_empty() {
+            None
+        } else {
+            Some(self.body_input.clone())
+        };
+        request.form = self.form_input.clone();
+        request.params = self.params_input.clone();
+        request.disabled_headers = self.disabled_headers.clone();
+        request.disabled_params = self.disabled_params.clone();
+        request.tests = self.tests_input.clone();
+        request.auth = self.auth_input.clone();
+        request.options = self.options_input.clone();
+        request
+    }
+
+    /// Renders the current request in the given export format
+    pub fn export_current(&self, format: ExportFormat) -> String {
+        let env = self.send_environment();
+        let env = env.as_ref();
+        let (request, used) = environment::resolve_request(&self.current_request(), env, true);
+        let tab = &self.tabs[self.selected_tab];
+        let note = tab.response.as_ref().and_then(|r| r.note.clone());
+        let annotation = Annotation::new(env, &used, chrono::Utc::now()).with_note(note);
+        let context = match format {
+            ExportFormat::BugReport => {
+                // Sends to longer URLs match the search too
+                let entry = self.history_entry(env, Ok(0), Duration::ZERO, None);
+                let sends = self
+                    .history
+                    .search(&entry.url, BUG_REPORT_SENDS * 5)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter(|sent| sent.method == entry.method && sent.url == entry.url)
+                    .take(BUG_REPORT_SENDS)
+                    .map(storage::format_entry)
+                    .collect();
+                let log = crate::logging::file().map(|path| {
+                    crate::logging::tail(path, BUG_REPORT_LOG_LINES).unwrap_or_else(|e| {
+                        vec![format!("(cannot read {}: {})", path.display(), e)]
+                    })
+                });
+                bugreport::Context { sends, log }
+            }
+            _ => bugreport::Context::default(),
+        };
+        format.render(&request, tab, &annotation, &context)
+    }
+
+    /// Writes the current export to a file in the working directory
+    pub fn save_export(&self, format: ExportFormat) -> Result<PathBuf> {
+        let name: String = self.tabs[self.selected_tab]
+            .name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        let path = PathBuf::from(format!(
+            "restless-{}-{}.{}",
+            name.to_lowercase(),
+            chrono::Local::now().format("%Y%m%d-%H%M%S"),
+            format.extension()
+        ));
+        std::fs::write(&path, self.export_current(format))?;
+        Ok(path)
+    }
 
-    pub help_visible: bool,
-    pub help_scroll: usize,
-    pub previous_screen: CurrentScreen,
-}
+    pub fn show_export(&mut self) {
+        if self.current_screen != CurrentScreen::Export {
+            self.previous_screen = self.current_screen;
+            self.current_screen = CurrentScreen::Export;
+            self.export_scroll = 0;
+        }
+    }
 
-impl App {
-    pub fn new() -> App {
-        let tabs = vec![Tab::new("Tab 1".to_string(), String::new())];
-        App {
-            current_screen: CurrentScreen::Values,
-            values_screen: ValuesScreen::Body,
-            tabs,
-            selected_tab: 0,
-            url_input: String::new(),
-            selected_method: HttpMethod::GET,
-            method_dropdown_open: false,
-            method_dropdown_selected: 0,
-            body_input: String::new(),
-            headers_input: Vec::new(),
-            params_input: Vec::new(),
-            current_header_key: String::new(),
-            current_header_value: String::new(),
-            current_param_key: String::new(),
-            current_param_value: String::new(),
-            editing_header_index: None,
-            editing_param_index: None,
-            response_tab_selected: 1,
-            response_scroll: 0,
-            response_scroll_state: ratatui::widgets::ScrollbarState::default(),
-            help_visible: false,
-            help_scroll: 0,
-            previous_screen: CurrentScreen::Values,
+    pub fn hide_export(&mut self) {
+        if self.current_screen == CurrentScreen::Export {
+            self.current_screen = self.previous_screen;
         }
     }
 
@@ -102,14 +2197,22 @@ impl App {
             if self.current_header_key.contains(':') {
                 let parts: Vec<&str> = self.current_header_key.splitn(2, ':').collect();
                 if parts.len() == 2 {
+                    // Typing goes on in the value once the key has its colon
                     let key = parts[0].trim().to_string();
-                    let value = parts[1].trim().to_string();
+                    let value = format!("{}{}", parts[1], self.current_header_value)
+                        .trim()
+                        .to_string();
 
                     if key.is_empty() {
                         return Err(RestlessError::invalid_header("Header key cannot be empty"));
                     }
 
-                    self.headers_input.push((key, value));
+                    put_entry(
+                        &mut self.headers_input,
+                        &mut self.disabled_headers,
+                        self.editing_header_index.take(),
+                        (key, value),
+                    );
                 }
             } else if !self.current_header_value.is_empty() {
                 let value = self.current_header_value.trim();
@@ -118,8 +2221,12 @@ impl App {
                         "Header value cannot contain newlines",
                     ));
                 }
-                self.headers_input
-                    .push((self.current_header_key.clone(), value.to_string()));
+                put_entry(
+                    &mut self.headers_input,
+                    &mut self.disabled_headers,
+                    self.editing_header_index.take(),
+                    (self.current_header_key.clone(), value.to_string()),
+                );
             }
             self.current_header_key.clear();
             self.current_header_value.clear();
@@ -127,6 +2234,63 @@ impl App {
         Ok(())
     }
 
+    /// Suggests header names for the one being typed, until its colon
+    pub fn update_header_suggestions(&mut self) {
+        self.header_suggestion_selected = None;
+        if let Some((name, value)) = self.current_header_key.split_once(':') {
+            let typed = format!("{}{}", value, self.current_header_value);
+            self.header_suggestions = headers::values(name, &typed);
+            return;
+        }
+        let tabs = self.tabs.iter().flat_map(|tab| &tab.request.headers);
+        let saved = self
+            .collections
+            .iter()
+            .flat_map(|collection| &collection.requests)
+            .flat_map(|request| &request.headers);
+        let used = self
+            .headers_input
+            .iter()
+            .chain(&self.disabled_headers)
+            .chain(tabs)
+            .chain(saved)
+            .map(|(name, _)| name.as_str());
+        self.header_suggestions = headers::complete(&self.current_header_key, used);
+    }
+
+    pub fn select_header_suggestion(&mut self, down: bool) {
+        self.header_suggestion_selected = step_selection(
+            self.header_suggestion_selected,
+            self.header_suggestions.len(),
+            down,
+        );
+    }
+
+    /// Completes the header name, or its value once the name is done, with
+    /// the selected suggestion, or the first one when `first` is set; `false`
+    /// when there is none to take
+    pub fn accept_header_suggestion(&mut self, first: bool) -> bool {
+        let index = match self.header_suggestion_selected {
+            Some(index) => index,
+            None if first && !self.header_suggestions.is_empty() => 0,
+            None => return false,
+        };
+        let suggestion = self.header_suggestions[index].clone();
+        match self.current_header_key.split_once(':') {
+            Some((name, _)) => {
+                self.current_header_key = format!("{}:", name);
+                self.current_header_value = suggestion;
+            }
+            None => {
+                self.current_header_key = format!("{}:", suggestion);
+                self.current_header_value.clear();
+            }
+        }
+        // Offers the values of the header just named
+        self.update_header_suggestions();
+        true
+    }
+
     pub fn add_param(&mut self) -> Result<()> {
         if !self.current_param_key.is_empty() {
             // Validate parameter key
@@ -140,7 +2304,9 @@ impl App {
                 let parts: Vec<&str> = self.current_param_key.splitn(2, '=').collect();
                 if parts.len() == 2 {
                     let key = parts[0].trim().to_string();
-                    let value = parts[1].trim().to_string();
+                    let value = format!("{}{}", parts[1], self.current_param_value)
+                        .trim()
+                        .to_string();
 
                     if key.is_empty() {
                         return Err(RestlessError::invalid_parameter(
@@ -148,7 +2314,12 @@ impl App {
                         ));
                     }
 
-                    self.params_input.push((key, value));
+                    put_entry(
+                        &mut self.params_input,
+                        &mut self.disabled_params,
+                        self.editing_param_index.take(),
+                        (key, value),
+                    );
                 }
             } else if !self.current_param_value.is_empty() {
                 let key = self.current_param_key.trim();
@@ -160,7 +2331,12 @@ impl App {
                     ));
                 }
 
-                self.params_input.push((key.to_string(), value.to_string()));
+                put_entry(
+                    &mut self.params_input,
+                    &mut self.disabled_params,
+                    self.editing_param_index.take(),
+                    (key.to_string(), value.to_string()),
+                );
             }
             self.current_param_key.clear();
             self.current_param_value.clear();
@@ -168,32 +2344,398 @@ impl App {
         Ok(())
     }
 
-    #[allow(dead_code)]
-    pub fn remove_header(&mut self, index: usize) -> Result<()> {
-        if index < self.headers_input.len() {
-            self.headers_input.remove(index);
-            Ok(())
+    /// Applies the `key=value` typed into the auth editor to the auth config
+    ///
+    /// An empty value removes the field again.
+    pub fn set_auth_field(&mut self) -> Result<()> {
+        let input = self.current_auth_input.trim();
+        if input.is_empty() {
+            return Ok(());
+        }
+
+        let (key, value) = input
+            .split_once('=')
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .ok_or_else(|| RestlessError::configuration("Auth fields use the format key=value"))?;
+
+        if key.is_empty() {
+            return Err(RestlessError::configuration(
+                "Auth field name cannot be empty",
+            ));
+        }
+
+        if value.is_empty() {
+            self.auth_input.remove_field(key);
         } else {
-            Err(RestlessError::app_state(format!(
+            self.auth_input.set_field(key, value);
+        }
+        self.current_auth_input.clear();
+        Ok(())
+    }
+
+    /// The request with the config's default headers added, except those it
+    /// sets itself (in any case)
+    pub fn with_default_headers(&self, request: &Request) -> Request {
+        let mut request = request.clone();
+        for (name, value) in &self.config.headers {
+            if !request
+                .headers
+                .iter()
+                .any(|(key, _)| key.eq_ignore_ascii_case(name))
+            {
+                request.headers.push((name.clone(), value.clone()));
+            }
+        }
+        request
+    }
+
+    /// Request options with the active environment's options and the config
+    /// defaults filled in
+    pub fn effective_options(&self, options: &RequestOptions) -> RequestOptions {
+        let env_options = self
+            .active_environment()
+            .map(|env| env.options.clone())
+            .unwrap_or_default();
+        options.merged(&env_options).merged(&self.config.connection)
+    }
+
+    /// Turns certificate verification off or back on for the current request
+    ///
+    /// Turning it back on only writes an explicit `false` when the environment
+    /// or config would otherwise keep it off.
+    pub fn toggle_insecure(&mut self) {
+        let mut options = self.options_input.clone();
+        if self.effective_options(&options).is_insecure() {
+            options.insecure = None;
+            if self.effective_options(&options).is_insecure() {
+                options.insecure = Some("false".to_string());
+            }
+        } else {
+            options.insecure = Some("true".to_string());
+        }
+        self.options_input = options;
+    }
+
+    /// Applies the `key=value` typed into the options editor
+    ///
+    /// An empty value unsets the option so the config default applies again.
+    pub fn set_option(&mut self) -> Result<()> {
+        let input = self.current_options_input.trim();
+        if input.is_empty() {
+            return Ok(());
+        }
+
+        let (key, value) = input
+            .split_once('=')
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .ok_or_else(|| RestlessError::configuration("Options use the format key=value"))?;
+
+        self.options_input.set(key, value)?;
+        self.current_options_input.clear();
+        Ok(())
+    }
+
+    /// Starts editing the raw body with the cursor at its end
+    pub fn start_body_edit(&mut self) {
+        self.body_cursor = Cursor::at_end(&self.body_input);
+        self.current_screen = CurrentScreen::EditingBody;
+    }
+
+    /// Starts editing the assertions with the cursor at their end
+    pub fn start_tests_edit(&mut self) {
+        self.tests_cursor = Cursor::at_end(&self.tests_input);
+        self.current_screen = CurrentScreen::EditingTests;
+    }
+
+    /// Leaves the assertions, checking that each can be read; they're kept
+    /// either way, and one that can't be read fails when it runs
+    pub fn finish_tests_edit(&mut self) -> Result<()> {
+        self.current_screen = CurrentScreen::Values;
+        self.save_current_tab_state()?;
+        assertions::validate(&self.tests_input)
+    }
+
+    /// Switches the response to the request that brought it, as sent
+    pub fn show_sent_request(&mut self) -> Result<()> {
+        let sent = self.tabs[self.selected_tab]
+            .response
+            .as_ref()
+            .is_some_and(|response| response.sent.is_some());
+        if !sent {
+            return Err(RestlessError::app_state(
+                "No request recorded; send the request to see it",
+            ));
+        }
+        self.response_tab_selected = 5;
+        self.response_scroll = 0;
+        Ok(())
+    }
+
+    /// Switches the response to the results of the request's assertions
+    pub fn show_test_results(&mut self) -> Result<()> {
+        let tested = self.tabs[self.selected_tab]
+            .response
+            .as_ref()
+            .is_some_and(|response| !response.tests.is_empty());
+        if !tested {
+            return Err(RestlessError::app_state(
+                "No test results; write assertions in the Tests tab and send again",
+            ));
+        }
+        self.response_tab_selected = 4;
+        self.response_scroll = 0;
+        Ok(())
+    }
+
+    /// Starts editing the headers or params, whichever tab is open, as one
+    /// `Name: value` or `name=value` line each
+    pub fn start_bulk_edit(&mut self) {
+        self.bulk_input = match self.values_screen {
+            ValuesScreen::Headers => {
+                bulk::format(&self.headers_input, &self.disabled_headers, ": ")
+            }
+            ValuesScreen::Params => bulk::format(&self.params_input, &self.disabled_params, "="),
+            _ => return,
+        };
+        self.bulk_cursor = Cursor::at_end(&self.bulk_input);
+        self.current_screen = CurrentScreen::EditingBulk;
+    }
+
+    /// Replaces the headers or params with the lines edited; on an error
+    /// nothing changes and editing goes on
+    pub fn apply_bulk_edit(&mut self) -> Result<()> {
+        match self.values_screen {
+            ValuesScreen::Headers => {
+                (self.headers_input, self.disabled_headers) =
+                    bulk::parse_headers(&self.bulk_input)?;
+                self.selected_header = None;
+            }
+            ValuesScreen::Params => {
+                (self.params_input, self.disabled_params) = bulk::parse_params(&self.bulk_input)?;
+                self.selected_param = None;
+            }
+            _ => {}
+        }
+        self.bulk_input.clear();
+        self.current_screen = CurrentScreen::Values;
+        Ok(())
+    }
+
+    /// File extension for the body in the external editor, so it picks the
+    /// right syntax
+    pub fn body_extension(&self) -> &'static str {
+        let content_type = self
+            .headers_input
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| value.to_lowercase())
+            .unwrap_or_default();
+        let body = self.body_input.trim_start();
+        if content_type.contains("json") || body.starts_with('{') || body.starts_with('[') {
+            "json"
+        } else if content_type.contains("xml") || body.starts_with('<') {
+            "xml"
+        } else {
+            "txt"
+        }
+    }
+
+    /// Takes the body back from the external editor
+    ///
+    /// Editors end the file with a newline; it's dropped again unless the
+    /// body had one before.
+    pub fn apply_body_edit(&mut self, mut edited: String) {
+        if !self.body_input.ends_with('\n') && edited.ends_with('\n') {
+            edited.pop();
+            if edited.ends_with('\r') {
+                edited.pop();
+            }
+        }
+        self.body_input = edited;
+    }
+
+    /// Switches the body between raw text and a multipart form
+    ///
+    /// The raw body is kept, so switching back doesn't lose it.
+    pub fn toggle_body_type(&mut self) {
+        self.form_input = match self.form_input {
+            Some(_) => None,
+            None => Some(Vec::new()),
+        };
+    }
+
+    /// Applies the `name=value` or `name=@path` typed into the form editor
+    ///
+    /// An empty value removes every field with that name.
+    pub fn set_form_field(&mut self) -> Result<()> {
+        let (name, value) = form::parse_field(&self.current_form_input)?;
+        let fields = self.form_input.get_or_insert_with(Vec::new);
+        if value.is_empty() {
+            fields.retain(|(key, _)| *key != name);
+        } else {
+            fields.push((name, value));
+        }
+        self.current_form_input.clear();
+        Ok(())
+    }
+
+    /// Switches to the next auth type
+    ///
+    /// Fields are kept so switching between the OAuth2 flows doesn't lose them,
+    /// except when leaving API key auth whose fields mean nothing elsewhere.
+    pub fn cycle_auth_kind(&mut self) {
+        let previous = self.auth_input.kind;
+        let kind = previous.next();
+        self.auth_input.kind = kind;
+        if kind == AuthKind::None || previous == AuthKind::ApiKey {
+            self.auth_input.fields.clear();
+        }
+    }
+
+    /// Removes header `index`, counting the disabled headers after the others
+    pub fn remove_header(&mut self, index: usize) -> Result<()> {
+        match remove_entry(&mut self.headers_input, &mut self.disabled_headers, index) {
+            Some(_) => Ok(()),
+            None => Err(RestlessError::app_state(format!(
                 "Cannot remove header at index {}: only {} headers exist",
                 index,
-                self.headers_input.len()
-            )))
+                self.header_count()
+            ))),
         }
     }
 
-    #[allow(dead_code)]
+    /// Removes param `index`, counting the disabled params after the others
     pub fn remove_param(&mut self, index: usize) -> Result<()> {
-        if index < self.params_input.len() {
-            self.params_input.remove(index);
-            Ok(())
-        } else {
-            Err(RestlessError::app_state(format!(
+        match remove_entry(&mut self.params_input, &mut self.disabled_params, index) {
+            Some(_) => Ok(()),
+            None => Err(RestlessError::app_state(format!(
                 "Cannot remove parameter at index {}: only {} parameters exist",
                 index,
-                self.params_input.len()
-            )))
+                self.param_count()
+            ))),
+        }
+    }
+
+    /// Headers listed, the disabled ones included
+    pub fn header_count(&self) -> usize {
+        self.headers_input.len() + self.disabled_headers.len()
+    }
+
+    pub fn param_count(&self) -> usize {
+        self.params_input.len() + self.disabled_params.len()
+    }
+
+    pub fn select_header(&mut self, down: bool) {
+        self.selected_header = step_selection(self.selected_header, self.header_count(), down);
+    }
+
+    pub fn select_param(&mut self, down: bool) {
+        self.selected_param = step_selection(self.selected_param, self.param_count(), down);
+    }
+
+    /// Scrolls the body shown outside editing by `lines`; the renderer stops
+    /// it at the last page
+    pub fn scroll_body(&self, down: bool, lines: usize) {
+        scroll_cell(&self.body_scroll, down, lines);
+    }
+
+    /// Scrolls the response headers by `lines`, like [`App::scroll_body`]
+    pub fn scroll_response_headers(&self, down: bool, lines: usize) {
+        scroll_cell(&self.response_headers_scroll, down, lines);
+    }
+
+    /// Scrolls the response body sideways; `false` when scrolling left with
+    /// the body already at its first column
+    pub fn scroll_response_columns(&self, right: bool) -> bool {
+        if !right && self.response_column.get() == 0 {
+            return false;
         }
+        scroll_cell(&self.response_column, right, COLUMN_STEP);
+        true
+    }
+
+    /// Switches the selected header off, or back on; it moves to the end of
+    /// the enabled or disabled headers. `false` without a selection
+    pub fn toggle_selected_header(&mut self) -> bool {
+        let Some(index) = self.selected_header else {
+            return false;
+        };
+        self.selected_header =
+            toggle_entry(&mut self.headers_input, &mut self.disabled_headers, index);
+        true
+    }
+
+    /// Switches the selected param off, or back on, like
+    /// [`App::toggle_selected_header`]
+    pub fn toggle_selected_param(&mut self) -> bool {
+        let Some(index) = self.selected_param else {
+            return false;
+        };
+        self.selected_param =
+            toggle_entry(&mut self.params_input, &mut self.disabled_params, index);
+        true
+    }
+
+    /// Loads the selected header into the edit fields; adding it replaces
+    /// the header instead of appending a new one. `false` without a selection
+    pub fn edit_selected_header(&mut self) -> bool {
+        let Some((name, value)) = self.selected_header.and_then(|index| {
+            self.headers_input
+                .iter()
+                .chain(&self.disabled_headers)
+                .nth(index)
+        }) else {
+            return false;
+        };
+        self.current_header_key = format!("{}:", name);
+        self.current_header_value = value.clone();
+        self.editing_header_index = self.selected_header;
+        self.current_screen = CurrentScreen::EditingHeaders;
+        true
+    }
+
+    /// Loads the selected param into the edit fields, like
+    /// [`App::edit_selected_header`]
+    pub fn edit_selected_param(&mut self) -> bool {
+        let Some((name, value)) = self.selected_param.and_then(|index| {
+            self.params_input
+                .iter()
+                .chain(&self.disabled_params)
+                .nth(index)
+        }) else {
+            return false;
+        };
+        self.current_param_key = format!("{}=", name);
+        self.current_param_value = value.clone();
+        self.editing_param_index = self.selected_param;
+        self.current_screen = CurrentScreen::EditingParams;
+        true
+    }
+
+    /// Removes the selected header, keeping a selection on its neighbour
+    pub fn delete_selected_header(&mut self) -> Result<bool> {
+        let Some(index) = self.selected_header else {
+            return Ok(false);
+        };
+        self.remove_header(index)?;
+        self.selected_header = self
+            .header_count()
+            .checked_sub(1)
+            .map(|last| index.min(last));
+        Ok(true)
+    }
+
+    /// Removes the selected param, keeping a selection on its neighbour
+    pub fn delete_selected_param(&mut self) -> Result<bool> {
+        let Some(index) = self.selected_param else {
+            return Ok(false);
+        };
+        self.remove_param(index)?;
+        self.selected_param = self
+            .param_count()
+            .checked_sub(1)
+            .map(|last| index.min(last));
+        Ok(true)
     }
 
     pub fn add_new_tab(&mut self) -> Result<()> {
@@ -219,6 +2761,24 @@ impl App {
         Ok(())
     }
 
+    /// Opens a copy of the current tab's request next to it, to try a
+    /// variant without retyping it. The response, and any link to a `.http`
+    /// file, stay with the original.
+    pub fn duplicate_current_tab(&mut self) -> Result<()> {
+        self.save_current_tab_state()?;
+        let original = &self.tabs[self.selected_tab];
+        let mut copy = Tab::new(format!("{} (copy)", original.name), String::new());
+        copy.request = original.request.clone();
+        copy.panel = original.panel.clone();
+        copy.filter = original.filter.clone();
+
+        let index = self.selected_tab + 1;
+        self.tabs.insert(index, copy);
+        self.close_warned = None;
+        self.selected_tab = index;
+        self.restore_current_tab_state()
+    }
+
     pub fn close_current_tab(&mut self) -> Result<()> {
         if self.tabs.len() <= 1 {
             return Err(RestlessError::tab("Cannot close the last remaining tab"));
@@ -232,7 +2792,9 @@ impl App {
             )));
         }
 
+        // Dropping the tab cancels its send, if any
         self.tabs.remove(self.selected_tab);
+        self.close_warned = None;
 
         // Adjust selected_tab if we removed the last tab
         if self.selected_tab >= self.tabs.len() {
@@ -304,22 +2866,28 @@ impl App {
             }
         }
 
+        // Validate authentication
+        self.auth_input.validate()?;
+
         Ok(())
     }
 
     #[allow(dead_code)]
     pub fn get_error_message(&self, error: &RestlessError) -> String {
-        match error {
-            RestlessError::Network(e) => format!("Network Error: {}", e),
-            RestlessError::InvalidUrl { url } => format!("Invalid URL: {}", url),
-            RestlessError::InvalidHeader { header } => format!("Invalid Header: {}", header),
-            RestlessError::InvalidParameter { param } => format!("Invalid Parameter: {}", param),
-            RestlessError::Timeout => "Request timed out".to_string(),
-            RestlessError::Tab { message } => format!("Tab Error: {}", message),
-            RestlessError::ResponseParsing { message } => format!("Response Error: {}", message),
-            RestlessError::AppState { message } => format!("App Error: {}", message),
-            _ => format!("Error: {}", error),
-        }
+        let (template, detail): (&str, &dyn std::fmt::Display) = match error {
+            RestlessError::Network(e) => ("Network Error: {error}", e),
+            RestlessError::InvalidUrl { url } => ("Invalid URL: {error}", url),
+            RestlessError::InvalidHeader { header } => ("Invalid Header: {error}", header),
+            RestlessError::InvalidParameter { param } => ("Invalid Parameter: {error}", param),
+            RestlessError::Timeout => return self.tr("Request timed out").to_string(),
+            RestlessError::Tab { message } => ("Tab Error: {error}", message),
+            RestlessError::ResponseParsing { message } => ("Response Error: {error}", message),
+            RestlessError::AppState { message } => ("App Error: {error}", message),
+            RestlessError::Authentication { message } => ("Auth Error: {error}", message),
+            RestlessError::Clipboard { message } => ("Clipboard Error: {error}", message),
+            _ => ("Error: {error}", error),
+        };
+        self.i18n.format(template, &[("error", detail)])
     }
 
     pub fn get_help_content(&self) -> Vec<(&'static str, &'static str)> {
@@ -329,24 +2897,186 @@ impl App {
                 "Ctrl+j/k",
                 "Navigate between sections (URL/Values/Response)",
             ),
-            ("h/l", "Navigate between Body/Headers/Params in Values"),
+            ("h/l", "Navigate between Body/Headers/Params/Auth in Values"),
+            (
+                "j/k",
+                "Pick a header or param (e edits it, d deletes it), or scroll the body",
+            ),
+            ("Space", "Switch the picked header or param off and on"),
+            ("b", "Edit all headers or params as text (Esc applies)"),
+            ("Z", "Dismiss the hint in the status bar"),
+            (
+                "Mouse",
+                "Click a tab or section to focus it; the wheel scrolls",
+            ),
+            ("z", "Zoom the response to the whole screen and back"),
+            (
+                "\\",
+                "Switch the layout: stacked, split, or split when wide",
+            ),
+            (
+                "Ctrl+P",
+                "Find a tab, saved request or sent URL by name, URL or method",
+            ),
             ("", ""),
             ("Tab Management", ""),
             ("t", "Create new tab"),
+            ("D", "Duplicate current tab"),
             ("x", "Close current tab"),
             ("Tab", "Next tab"),
             ("Shift+Tab", "Previous tab"),
+            ("1-9", "Go to tab 1-9 (also Alt+1-9)"),
+            ("< / >", "Move current tab left/right (also Ctrl+Shift+H/L)"),
             ("", ""),
             ("Editing", ""),
-            ("i", "Insert/edit mode (body/headers/params)"),
-            ("u", "Edit URL"),
+            ("i", "Insert/edit mode (body/headers/params/auth)"),
+            (
+                "u",
+                "Edit URL (paste a curl command or raw HTTP to import it)",
+            ),
+            ("", "Its query string is kept in sync with the params"),
+            (
+                "Ctrl+W/Ctrl+U",
+                "Delete a word of the URL / clear it (arrows move)",
+            ),
+            ("Up/Down", "Pick a URL sent before while typing one"),
+            ("Tab", "Complete the header name while adding a header"),
+            ("Tab", "Pick a media type after Content-Type:"),
             ("m", "Open method dropdown"),
             ("Enter", "Execute HTTP request"),
+            (
+                "P",
+                "Dry run: show the request as it would be sent, without sending it",
+            ),
+            ("R", "Resend with one header/param overridden (not saved)"),
+            (
+                "M",
+                "Send once per value of a variable, e.g. region = eu, us",
+            ),
+            ("A", "Run the requests of all open tabs in order"),
+            ("B", "Load test: send the request many times, e.g. 200 x 10"),
+            ("!", "Toggle insecure TLS (accept invalid certificates)"),
+            (
+                "W",
+                "Re-send whenever a file or directory changes (empty stops)",
+            ),
+            (
+                "I",
+                "Poll: re-send every few seconds, e.g. 5s (empty stops)",
+            ),
+            ("Ctrl+V", "Paste from the clipboard while editing"),
             ("Esc", "Exit edit mode"),
             ("", ""),
+            ("Body", ""),
+            (
+                "Space",
+                "Switch between raw body and multipart form (Body tab)",
+            ),
+            (
+                "i",
+                "Add form field name=value, or name=@path to upload a file",
+            ),
+            ("Tab", "Complete the file path while adding a form field"),
+            (
+                "Arrows/Home/End",
+                "Move the cursor while editing the body (Del deletes)",
+            ),
+            ("Ctrl+E", "Edit the raw body in $EDITOR"),
+            ("", ""),
+            ("Authentication", ""),
+            ("Space", "Change auth type (Auth tab)"),
+            ("i", "Set auth field as key=value (empty value removes)"),
+            ("r", "Forget cached OAuth2 tokens (Auth tab)"),
+            ("p", "Preview HMAC/SigV4 signature (Auth tab)"),
+            ("", ""),
+            ("Options", ""),
+            ("i", "Set an option as key=value (proxy, TLS certificates)"),
+            ("", ""),
+            ("Tests", ""),
+            (
+                "i",
+                "Write assertions checked after each send, e.g. status == 200",
+            ),
+            (
+                "i",
+                "Keep a response value for later requests: set token = json.body.token",
+            ),
+            ("", ""),
             ("Response Navigation", ""),
-            ("j/k", "Scroll response content"),
+            ("j/k", "Scroll the response body or headers"),
             ("h/b", "Switch between Headers/Body"),
+            (
+                "h/l",
+                "Scroll long body lines sideways (h at the first column: Headers)",
+            ),
+            ("T", "Show TLS version, cipher and certificate"),
+            ("p", "Show the saved request's custom panel"),
+            ("r", "Show which of the request's tests passed"),
+            ("V", "Show the request exactly as sent, like curl -v"),
+            ("K", "Show the cached copy of a 304 Not Modified response"),
+            ("L", "Latency histogram and percentiles of repeated sends"),
+            ("v", "Show a JSON array of objects as a table"),
+            ("J", "Show JSON as a tree (j/k select, Enter/Space fold)"),
+            ("S", "Close a streaming (text/event-stream) response"),
+            (
+                "|",
+                "Compare with another tab's response side by side (again: next tab, then off)",
+            ),
+            (
+                ", / .",
+                "Scroll table columns (leftmost is the sort column)",
+            ),
+            (
+                "s",
+                "Sort the table by the leftmost column (numbers by value)",
+            ),
+            ("w / Y", "Save the table as CSV / copy it to the clipboard"),
+            ("c", "Copy headers/body in a chosen format"),
+            (
+                "y",
+                "Copy the current view (headers, body or table) as shown",
+            ),
+            ("n", "Attach a note to the response"),
+            ("/", "Search the response body"),
+            ("n / N", "Next/previous match while searching (Esc clears)"),
+            ("f", "Filter the body with JSONPath, e.g. $.items[*].id"),
+            ("H", "Preview an HTML body as text"),
+            ("[ / ]", "Previous/next part of a multipart body"),
+            ("[ / ]", "Older/newer response of the tab, for other bodies"),
+            ("", ""),
+            ("Environments & Export", ""),
+            ("e", "Cycle active environment ({{var}} substitution)"),
+            (
+                "E",
+                "Export request (curl/HAR/bug report/code) with reproduction notes",
+            ),
+            (
+                "o",
+                "Open collections (includes the Learn restless tutorial)",
+            ),
+            ("C", "View and edit cookies (a add, i edit, d delete)"),
+            ("", ""),
+            ("Collections", ""),
+            (
+                "i",
+                "Import a HAR file, Postman collection or OpenAPI document, or open a .http file",
+            ),
+            (
+                "a",
+                "Toggle re-sending .http requests when the file is saved",
+            ),
+            (
+                "r",
+                "Show the revisions of a saved request (Enter restores)",
+            ),
+            (
+                "R",
+                "Run every request of the collection in order, with a summary",
+            ),
+            (
+                "m/s/d",
+                "Merge, skip or duplicate an imported request (M/S/D all)",
+            ),
             ("", ""),
             ("Application", ""),
             ("?", "Show/hide this help"),
@@ -354,17 +3084,47 @@ impl App {
         ]
     }
 
+    /// Reopens the tabs saved in a session file, unless restoring is
+    /// turned off; call after `load_config`
+    pub fn restore_session(&mut self, path: &Path) -> Result<()> {
+        if !self.config.ui.restore_session {
+            return Ok(());
+        }
+        let Some(session) = session::load(path)? else {
+            return Ok(());
+        };
+        let mut tabs = session.tabs();
+        if tabs.is_empty() {
+            return Ok(());
+        }
+        for tab in &mut tabs {
+            // Tabs from a `.http` file that's gone are kept as plain tabs
+            let reopened = match &tab.http_file {
+                Some((file, _)) => self.open_http_file(file).is_ok(),
+                None => true,
+            };
+            if !reopened {
+                tab.http_file = None;
+            }
+        }
+        self.selected_tab = session.selected.min(tabs.len() - 1);
+        self.tabs = tabs;
+        self.restore_current_tab_state()
+    }
+
+    /// Writes the open tabs to a session file for the next launch
+    pub fn save_session(&mut self, path: &Path) -> Result<()> {
+        if !self.config.ui.restore_session {
+            return Ok(());
+        }
+        self.save_current_tab_state()?;
+        session::save(path, &Session::capture(&self.tabs, self.selected_tab))
+    }
+
     pub fn save_current_tab_state(&mut self) -> Result<()> {
+        let request = self.current_request();
         if let Some(tab) = self.tabs.get_mut(self.selected_tab) {
-            tab.request.url = self.url_input.clone();
-            tab.request.method = (&self.selected_method).into();
-            tab.request.body = if self.body_input.is_empty() {
-                None
-            } else {
-                Some(self.body_input.clone())
-            };
-            tab.request.headers = self.headers_input.clone();
-            tab.request.params = self.params_input.clone();
+            tab.request = request;
             Ok(())
         } else {
             Err(RestlessError::app_state(format!(
@@ -382,8 +3142,22 @@ impl App {
                 RestlessError::app_state(format!("Invalid HTTP method in tab: {}", e))
             })?;
             self.body_input = tab.request.body.clone().unwrap_or_default();
+            self.body_scroll.set(0);
+            self.form_input = tab.request.form.clone();
             self.headers_input = tab.request.headers.clone();
             self.params_input = tab.request.params.clone();
+            self.disabled_headers = tab.request.disabled_headers.clone();
+            self.disabled_params = tab.request.disabled_params.clone();
+            self.tests_input = tab.request.tests.clone();
+            self.selected_header = None;
+            self.selected_param = None;
+            self.editing_header_index = None;
+            self.editing_param_index = None;
+            self.auth_input = tab.request.auth.clone();
+            self.options_input = tab.request.options.clone();
+            // A search belongs to the response it was typed over
+            self.search = None;
+            self.refresh_tree();
             Ok(())
         } else {
             Err(RestlessError::app_state(format!(
@@ -396,13 +3170,31 @@ impl App {
 
     pub fn next_tab(&mut self) -> Result<()> {
         self.save_current_tab_state()?;
+        self.close_warned = None;
         self.selected_tab = (self.selected_tab + 1) % self.tabs.len();
         self.restore_current_tab_state()?;
         Ok(())
     }
 
+    /// Moves the current tab one place left (`-1`) or right (`1`), keeping
+    /// it selected; a tab at the edge stays put
+    pub fn move_tab(&mut self, offset: isize) -> Result<()> {
+        let from = self.selected_tab;
+        let Some(to) = from
+            .checked_add_signed(offset)
+            .filter(|to| *to < self.tabs.len())
+        else {
+            return Ok(());
+        };
+        self.save_current_tab_state()?;
+        self.tabs.swap(from, to);
+        self.selected_tab = to;
+        Ok(())
+    }
+
     pub fn prev_tab(&mut self) -> Result<()> {
         self.save_current_tab_state()?;
+        self.close_warned = None;
         if self.selected_tab == 0 {
             self.selected_tab = self.tabs.len() - 1;
         } else {
@@ -412,3 +3204,82 @@ impl App {
         Ok(())
     }
 }
+
+/// The selection in a list of `count` after one step up or down, wrapping
+/// around; nothing is selected in an empty list
+fn step_selection(selected: Option<usize>, count: usize, down: bool) -> Option<usize> {
+    if count == 0 {
+        return None;
+    }
+    Some(match selected {
+        None if down => 0,
+        None => count - 1,
+        Some(index) if down => (index + 1) % count,
+        Some(index) => (index + count - 1) % count,
+    })
+}
+
+/// Moves a scroll offset kept in a [`Cell`] by `lines`
+fn scroll_cell(scroll: &Cell<usize>, down: bool, lines: usize) {
+    let top = scroll.get();
+    scroll.set(if down {
+        top.saturating_add(lines)
+    } else {
+        top.saturating_sub(lines)
+    });
+}
+
+/// Entry `index` of a list shown as its enabled entries followed by its
+/// disabled ones
+fn entry_mut<'a>(
+    enabled: &'a mut [(String, String)],
+    disabled: &'a mut [(String, String)],
+    index: usize,
+) -> Option<&'a mut (String, String)> {
+    match index.checked_sub(enabled.len()) {
+        Some(index) => disabled.get_mut(index),
+        None => enabled.get_mut(index),
+    }
+}
+
+/// Replaces the entry at `index` or, without one, appends it to the enabled
+/// entries
+fn put_entry(
+    enabled: &mut Vec<(String, String)>,
+    disabled: &mut [(String, String)],
+    index: Option<usize>,
+    entry: (String, String),
+) {
+    match index.and_then(|index| entry_mut(enabled, disabled, index)) {
+        Some(slot) => *slot = entry,
+        None => enabled.push(entry),
+    }
+}
+
+fn remove_entry(
+    enabled: &mut Vec<(String, String)>,
+    disabled: &mut Vec<(String, String)>,
+    index: usize,
+) -> Option<(String, String)> {
+    match index.checked_sub(enabled.len()) {
+        Some(index) => (index < disabled.len()).then(|| disabled.remove(index)),
+        None => Some(enabled.remove(index)),
+    }
+}
+
+/// Moves the entry at `index` to the end of the other group, returning
+/// where it is now
+fn toggle_entry(
+    enabled: &mut Vec<(String, String)>,
+    disabled: &mut Vec<(String, String)>,
+    index: usize,
+) -> Option<usize> {
+    if index < enabled.len() {
+        disabled.push(enabled.remove(index));
+        Some(enabled.len() + disabled.len() - 1)
+    } else {
+        let entry = remove_entry(enabled, disabled, index)?;
+        enabled.push(entry);
+        Some(enabled.len() - 1)
+    }
+}
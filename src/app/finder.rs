@@ -0,0 +1,160 @@
+//! The finder: one fuzzy search over open tabs, saved requests and history
+//!
+//! Opened with `Ctrl+P`, it lists what can be jumped to as one line each of
+//! method, name and URL, best matches first. Open tabs come before saved
+//! requests and those before history, which also breaks ties between equal
+//! scores.
+
+use crate::logic::fuzzy::{self, Match};
+
+/// History URLs offered, newest first
+pub const HISTORY_LIMIT: usize = 200;
+
+/// What choosing an item does
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// Switches to the open tab
+    Tab(usize),
+    /// Opens the request of a collection, by collection and request index
+    Saved(usize, usize),
+    /// Opens the URL in a new tab
+    History { method: String, url: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Item {
+    pub target: Target,
+    pub method: String,
+    pub name: String,
+    pub url: String,
+}
+
+impl Item {
+    /// Where the item comes from, for its column in the list
+    pub fn kind(&self) -> &'static str {
+        match self.target {
+            Target::Tab(_) => "tab",
+            Target::Saved(..) => "saved",
+            Target::History { .. } => "history",
+        }
+    }
+
+    /// The line the query is matched against, as it is shown
+    pub fn text(&self) -> String {
+        if self.name.is_empty() {
+            format!("{:7} {}", self.method, self.url)
+        } else {
+            format!("{:7} {}  {}", self.method, self.name, self.url)
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Finder {
+    pub input: String,
+    items: Vec<Item>,
+    /// Indices into the items matching the input with where they matched,
+    /// best first
+    pub matches: Vec<(usize, Match)>,
+    pub selected: usize,
+}
+
+impl Finder {
+    pub fn new(items: Vec<Item>) -> Finder {
+        let mut finder = Finder {
+            items,
+            ..Default::default()
+        };
+        finder.update();
+        finder
+    }
+
+    /// Matches the items against the input again, selecting the best one
+    pub fn update(&mut self) {
+        self.matches = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| Some((i, fuzzy::score(&self.input, &item.text())?)))
+            .collect();
+        // Stable, so equal scores keep the order of the items
+        self.matches
+            .sort_by_key(|(_, matched)| std::cmp::Reverse(matched.score));
+        self.selected = 0;
+    }
+
+    pub fn item(&self, index: usize) -> &Item {
+        &self.items[index]
+    }
+
+    /// Moves the selection, wrapping around
+    pub fn select(&mut self, down: bool) {
+        let count = self.matches.len();
+        if count == 0 {
+            return;
+        }
+        self.selected = if down {
+            (self.selected + 1) % count
+        } else {
+            (self.selected + count - 1) % count
+        };
+    }
+
+    pub fn selected_item(&self) -> Option<&Item> {
+        let (index, _) = self.matches.get(self.selected)?;
+        Some(&self.items[*index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(target: Target, method: &str, name: &str, url: &str) -> Item {
+        Item {
+            target,
+            method: method.to_string(),
+            name: name.to_string(),
+            url: url.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_finder() {
+        let mut finder = Finder::new(vec![
+            item(Target::Tab(0), "GET", "Tab 1", "http://localhost/health"),
+            item(Target::Saved(0, 1), "POST", "Create user", "{{base}}/users"),
+            item(
+                Target::History {
+                    method: "GET".to_string(),
+                    url: "http://localhost/users/7".to_string(),
+                },
+                "GET",
+                "",
+                "http://localhost/users/7",
+            ),
+        ]);
+        assert_eq!(finder.matches.len(), 3);
+        assert_eq!(finder.selected_item().unwrap().kind(), "tab");
+        assert_eq!(finder.item(2).text(), "GET     http://localhost/users/7");
+
+        finder.input = "users".to_string();
+        finder.update();
+        assert_eq!(finder.matches.len(), 2);
+        assert_eq!(finder.selected_item().unwrap().target, Target::Saved(0, 1));
+        finder.select(true);
+        assert_eq!(finder.selected_item().unwrap().kind(), "history");
+        finder.select(true);
+        assert_eq!(finder.selected, 0);
+
+        finder.input = "get usr 7".to_string();
+        finder.update();
+        assert_eq!(finder.matches.len(), 1);
+
+        finder.input = "nothing here".to_string();
+        finder.update();
+        assert!(finder.selected_item().is_none());
+        finder.select(false);
+        assert_eq!(finder.selected, 0);
+    }
+}
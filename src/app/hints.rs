@@ -0,0 +1,173 @@
+//! One-time hints pointing new users at features
+//!
+//! A hint shows in the status bar while the state it's about holds, say an
+//! edited request that hasn't been sent, until it's dismissed with `z`.
+//! Dismissed hints are remembered in `hints.json` in the config directory
+//! and never come back.
+
+use crate::app::{App, CurrentScreen};
+use crate::error::{RestlessError, Result};
+use std::path::{Path, PathBuf};
+
+/// Bodies longer than this many lines are worth filtering or searching
+const LONG_BODY_LINES: usize = 100;
+
+/// Sends of the same URL before the latency histogram is suggested
+const REPEATED_SENDS: usize = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    UnsentEdits,
+    PickEnvironment,
+    HtmlPreview,
+    FilterJson,
+    SearchBody,
+    Latency,
+}
+
+impl Hint {
+    /// Name the hint is remembered by once dismissed
+    pub fn id(self) -> &'static str {
+        match self {
+            Hint::UnsentEdits => "unsent-edits",
+            Hint::PickEnvironment => "pick-environment",
+            Hint::HtmlPreview => "html-preview",
+            Hint::FilterJson => "filter-json",
+            Hint::SearchBody => "search-body",
+            Hint::Latency => "latency",
+        }
+    }
+
+    pub fn message(self) -> &'static str {
+        match self {
+            Hint::UnsentEdits => "You have unsent edits, press Enter to send",
+            Hint::PickEnvironment => {
+                "This request uses {{variables}}, press e to pick an environment"
+            }
+            Hint::HtmlPreview => "This is an HTML page, press H to read it as text",
+            Hint::FilterJson => "Long JSON response, press f to filter it or J for a tree",
+            Hint::SearchBody => "Long response, press / to search it",
+            Hint::Latency => "Sent a few times now, press L for the latency histogram",
+        }
+    }
+}
+
+/// Hints dismissed so far, and where to remember them
+#[derive(Debug, Default)]
+pub struct Hints {
+    dismissed: Vec<String>,
+    path: Option<PathBuf>,
+}
+
+impl Hints {
+    /// Loads dismissed hints from a JSON file; a missing file means none
+    pub fn load(path: &Path) -> Result<Hints> {
+        let dismissed = if path.exists() {
+            let content = std::fs::read_to_string(path)?;
+            serde_json::from_str(&content).map_err(|e| {
+                RestlessError::configuration(format!(
+                    "Invalid hints file {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?
+        } else {
+            Vec::new()
+        };
+        Ok(Hints {
+            dismissed,
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    pub fn is_dismissed(&self, hint: Hint) -> bool {
+        self.dismissed.iter().any(|id| id == hint.id())
+    }
+
+    /// Never shows the hint again, saving that when loaded from a file
+    pub fn dismiss(&mut self, hint: Hint) -> Result<()> {
+        if self.is_dismissed(hint) {
+            return Ok(());
+        }
+        self.dismissed.push(hint.id().to_string());
+        if let Some(path) = &self.path {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(path, serde_json::to_string_pretty(&self.dismissed)? + "\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// The hint for the current state, if any hasn't been dismissed yet
+pub fn current(app: &App) -> Option<Hint> {
+    if !matches!(
+        app.current_screen,
+        CurrentScreen::Url | CurrentScreen::Values | CurrentScreen::Response
+    ) {
+        return None;
+    }
+    let tab = &app.tabs[app.selected_tab];
+    let response = tab.response.as_ref();
+    let long_body = || app.body_text().lines().count() > LONG_BODY_LINES;
+    let uses_variables = [app.url_input.as_str(), app.body_input.as_str()]
+        .into_iter()
+        .chain(app.headers_input.iter().map(|(_, value)| value.as_str()))
+        .any(|text| text.contains("{{"));
+
+    let candidates = [
+        (
+            Hint::UnsentEdits,
+            tab.sent
+                .as_ref()
+                .is_some_and(|sent| *sent != app.current_request()),
+        ),
+        (
+            Hint::PickEnvironment,
+            uses_variables && app.active_environment.is_none() && !app.environments.is_empty(),
+        ),
+        (
+            Hint::HtmlPreview,
+            response.is_some_and(|r| r.is_html()) && !app.html_preview,
+        ),
+        (
+            Hint::FilterJson,
+            response.is_some_and(|r| r.is_json()) && tab.filter.is_none() && long_body(),
+        ),
+        (
+            Hint::SearchBody,
+            response.is_some_and(|r| !r.is_json() && r.hex_bytes().is_none()) && long_body(),
+        ),
+        (Hint::Latency, tab.latency.count() >= REPEATED_SENDS),
+    ];
+    candidates
+        .into_iter()
+        .find(|&(hint, applies)| applies && !app.hints.is_dismissed(hint))
+        .map(|(hint, _)| hint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dismissed_hints_are_saved() {
+        let dir = std::env::temp_dir().join(format!("restless-hints-test-{}", std::process::id()));
+        let path = dir.join("hints.json");
+
+        let mut hints = Hints::load(&path).unwrap();
+        assert!(!hints.is_dismissed(Hint::Latency));
+        hints.dismiss(Hint::Latency).unwrap();
+        hints.dismiss(Hint::Latency).unwrap();
+        assert!(hints.is_dismissed(Hint::Latency));
+
+        let hints = Hints::load(&path).unwrap();
+        assert!(hints.is_dismissed(Hint::Latency));
+        assert!(!hints.is_dismissed(Hint::UnsentEdits));
+
+        std::fs::write(&path, "{}").unwrap();
+        assert!(Hints::load(&path).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,214 @@
+//! Cursor editing of text inputs
+//!
+//! The text of an input stays a plain `String`, so everything that reads it
+//! keeps working; a [`Cursor`] next to it knows where edits go. The text may
+//! change under the cursor (switching tabs, an external editor), so every
+//! operation first moves the cursor back inside the text.
+
+/// Insertion point in a text, as a byte offset on a char boundary
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Cursor {
+    offset: usize,
+    /// Column to return to when moving up and down across shorter lines
+    column: Option<usize>,
+}
+
+impl Cursor {
+    /// A cursor at the end of `text`
+    pub fn at_end(text: &str) -> Cursor {
+        Cursor {
+            offset: text.len(),
+            column: None,
+        }
+    }
+
+    /// The byte offset, inside `text`
+    pub fn offset(&self, text: &str) -> usize {
+        let mut offset = self.offset.min(text.len());
+        while !text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
+    /// Line and column (in chars) of the cursor, both from 0
+    pub fn position(&self, text: &str) -> (usize, usize) {
+        let before = &text[..self.offset(text)];
+        let row = before.matches('\n').count();
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (row, before[line_start..].chars().count())
+    }
+
+    fn set(&mut self, offset: usize) {
+        self.offset = offset;
+        self.column = None;
+    }
+
+    pub fn insert(&mut self, text: &mut String, s: &str) {
+        let offset = self.offset(text);
+        text.insert_str(offset, s);
+        self.set(offset + s.len());
+    }
+
+    /// Deletes the char before the cursor
+    pub fn backspace(&mut self, text: &mut String) {
+        let offset = self.offset(text);
+        if let Some(c) = text[..offset].chars().next_back() {
+            let start = offset - c.len_utf8();
+            text.replace_range(start..offset, "");
+            self.set(start);
+        }
+    }
+
+    /// Deletes the char under the cursor
+    pub fn delete(&mut self, text: &mut String) {
+        let offset = self.offset(text);
+        if let Some(c) = text[offset..].chars().next() {
+            text.replace_range(offset..offset + c.len_utf8(), "");
+            self.set(offset);
+        }
+    }
+
+    /// Deletes the word before the cursor along with the spaces after it,
+    /// like Ctrl+W in a shell
+    pub fn delete_word(&mut self, text: &mut String) {
+        let offset = self.offset(text);
+        let before = text[..offset].trim_end();
+        let start = before
+            .rfind(|c: char| c.is_whitespace() || "/?&=#".contains(c))
+            .map_or(0, |i| i + 1);
+        // A separator right before the cursor goes on its own
+        let start = if start == before.len() && start > 0 {
+            start - 1
+        } else {
+            start
+        };
+        text.replace_range(start..offset, "");
+        self.set(start);
+    }
+
+    pub fn left(&mut self, text: &str) {
+        let offset = self.offset(text);
+        let c = text[..offset].chars().next_back();
+        self.set(offset - c.map_or(0, char::len_utf8));
+    }
+
+    pub fn right(&mut self, text: &str) {
+        let offset = self.offset(text);
+        let c = text[offset..].chars().next();
+        self.set(offset + c.map_or(0, char::len_utf8));
+    }
+
+    /// To the start of the line
+    pub fn home(&mut self, text: &str) {
+        let offset = self.offset(text);
+        self.set(text[..offset].rfind('\n').map_or(0, |i| i + 1));
+    }
+
+    /// To the end of the line
+    pub fn end(&mut self, text: &str) {
+        let offset = self.offset(text);
+        self.set(text[offset..].find('\n').map_or(text.len(), |i| offset + i));
+    }
+
+    pub fn up(&mut self, text: &str) {
+        let (row, column) = self.position(text);
+        if row > 0 {
+            self.move_to_line(text, row - 1, self.column.unwrap_or(column));
+        }
+    }
+
+    pub fn down(&mut self, text: &str) {
+        let (row, column) = self.position(text);
+        if row + 1 < text.split('\n').count() {
+            self.move_to_line(text, row + 1, self.column.unwrap_or(column));
+        }
+    }
+
+    /// Moves to `column` of line `row`, or its end when it's shorter, and
+    /// remembers `column` for the next move up or down
+    fn move_to_line(&mut self, text: &str, row: usize, column: usize) {
+        let start: usize = text.split('\n').take(row).map(|line| line.len() + 1).sum();
+        let line = text[start..].split('\n').next().unwrap_or("");
+        let within: usize = line.chars().take(column).map(char::len_utf8).sum();
+        self.offset = start + within;
+        self.column = Some(column);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_editing() {
+        let mut text = "{\n  \"näme\": 1\n}".to_string();
+        let mut cursor = Cursor::at_end(&text);
+        assert_eq!(cursor.position(&text), (2, 1));
+
+        cursor.up(&text);
+        assert_eq!(cursor.position(&text), (1, 1));
+        cursor.end(&text);
+        assert_eq!(cursor.position(&text), (1, 11));
+        cursor.insert(&mut text, ",");
+        assert_eq!(text, "{\n  \"näme\": 1,\n}");
+
+        // The column is kept across a shorter line
+        cursor.up(&text);
+        assert_eq!(cursor.position(&text), (0, 1));
+        cursor.down(&text);
+        assert_eq!(cursor.position(&text), (1, 12));
+        cursor.down(&text);
+        cursor.down(&text);
+        assert_eq!(cursor.position(&text), (2, 1));
+
+        cursor.home(&text);
+        cursor.backspace(&mut text);
+        assert_eq!(text, "{\n  \"näme\": 1,}");
+        cursor.left(&text);
+        cursor.left(&text);
+        cursor.delete(&mut text);
+        assert_eq!(text, "{\n  \"näme\": ,}");
+
+        // Multi-byte chars are stepped over whole
+        let mut cursor = Cursor::default();
+        cursor.down(&text);
+        for _ in 0..5 {
+            cursor.right(&text);
+        }
+        assert_eq!(cursor.position(&text), (1, 5));
+        cursor.backspace(&mut text);
+        assert_eq!(text, "{\n  \"nme\": ,}");
+
+        // A cursor left behind by a shorter text moves back inside it
+        let mut short = "ab".to_string();
+        cursor.insert(&mut short, "c");
+        assert_eq!(short, "abc");
+        cursor.home(&short);
+        cursor.right(&short);
+        cursor.backspace(&mut short);
+        cursor.backspace(&mut short);
+        assert_eq!(short, "bc");
+    }
+
+    #[test]
+    fn test_delete_word() {
+        let mut url = "https://api.test/users/42".to_string();
+        let mut cursor = Cursor::at_end(&url);
+        cursor.delete_word(&mut url);
+        assert_eq!(url, "https://api.test/users/");
+        cursor.delete_word(&mut url);
+        assert_eq!(url, "https://api.test/users");
+        cursor.delete_word(&mut url);
+        assert_eq!(url, "https://api.test/");
+
+        let mut text = "one two  ".to_string();
+        let mut cursor = Cursor::at_end(&text);
+        cursor.delete_word(&mut text);
+        assert_eq!(text, "one ");
+        cursor.delete_word(&mut text);
+        assert_eq!(text, "");
+        cursor.delete_word(&mut text);
+        assert_eq!(text, "");
+    }
+}
@@ -1,4 +1,8 @@
+#[allow(clippy::module_inception)]
 pub mod app;
+pub mod finder;
+pub mod hints;
+pub mod input;
 pub mod tab;
 
 pub use app::*;
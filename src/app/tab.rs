@@ -1,24 +1,132 @@
-use crate::logic::{request::Request, response::Response, HttpMethod};
+use crate::logic::{
+    auth::Auth,
+    environment::Environment,
+    lint::Finding,
+    load::{self, Load, Progress, Report},
+    metrics::{LatencyHistogram, LatencyStats},
+    options::RequestOptions,
+    request::{RawResponse, Request},
+    response::Response,
+    secrets, HttpMethod,
+};
+use crate::storage::Entry;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot::{self, error::TryRecvError};
+use tokio::task::JoinHandle;
+
+/// Id of the next tab opened
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
 
 pub struct Tab {
+    /// Stays with the tab while tabs are moved, opened and closed around it,
+    /// unlike its index
+    pub id: usize,
     pub name: String,
     pub request: Request,
+    /// The response shown, the latest unless stepped back with `[`
     pub response: Option<Response>,
+    /// Responses before the shown one, oldest first
+    older: Vec<Response>,
+    /// Responses after the shown one, newest first
+    newer: Vec<Response>,
+    pub latency: LatencyStats,
+    /// Custom response panel template of the saved request the tab came from
+    pub panel: Option<String>,
+    /// `.http` file and request name the tab was opened from; the request
+    /// is reloaded when the file changes
+    pub http_file: Option<(PathBuf, String)>,
+    /// JSONPath expression the response body is shown through
+    pub filter: Option<String>,
+    /// The request as last sent, to tell when it has been edited since
+    pub sent: Option<Request>,
+    /// A send still waiting for its response
+    pub in_flight: Option<InFlight>,
+    /// A send rate limited with `429`, repeated once the window opens
+    pub retry: Option<Retry>,
 }
 
 impl Tab {
     pub fn new(name: String, url: String) -> Self {
         Tab {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
             name,
             request: Request {
                 url: url.clone(),
                 method: (&HttpMethod::GET).into(),
                 headers: vec![],
                 body: None,
+                form: None,
                 params: vec![],
+                disabled_headers: vec![],
+                disabled_params: vec![],
+                tests: String::new(),
+                auth: Auth::default(),
+                options: RequestOptions::default(),
+                revalidate: false,
             },
             response: None,
+            older: Vec::new(),
+            newer: Vec::new(),
+            latency: LatencyStats::default(),
+            panel: None,
+            http_file: None,
+            filter: None,
+            sent: None,
+            in_flight: None,
+            retry: None,
+        }
+    }
+
+    /// Shows `response`, keeping the ones before it until there are `kept`
+    /// in all
+    pub fn push_response(&mut self, response: Response, kept: usize) {
+        if let Some(shown) = self.response.take() {
+            self.older.push(shown);
+        }
+        while let Some(newer) = self.newer.pop() {
+            self.older.push(newer);
+        }
+        let excess = (self.older.len() + 1).saturating_sub(kept.max(1));
+        self.older.drain(..excess);
+        self.response = Some(response);
+    }
+
+    /// The newest response, whichever one is shown
+    pub fn latest_response(&self) -> Option<&Response> {
+        self.newer.first().or(self.response.as_ref())
+    }
+
+    /// Shows the response before the shown one; false when there is none
+    pub fn show_older_response(&mut self) -> bool {
+        let Some(older) = self.older.pop() else {
+            return false;
+        };
+        if let Some(shown) = self.response.replace(older) {
+            self.newer.push(shown);
         }
+        true
+    }
+
+    /// Shows the response after the shown one; false when there is none
+    pub fn show_newer_response(&mut self) -> bool {
+        let Some(newer) = self.newer.pop() else {
+            return false;
+        };
+        if let Some(shown) = self.response.replace(newer) {
+            self.older.push(shown);
+        }
+        true
+    }
+
+    /// Which of the kept responses is shown and how many there are, from 1
+    pub fn response_position(&self) -> (usize, usize) {
+        let shown = self.older.len() + 1;
+        (shown, shown + self.newer.len())
     }
 
     #[allow(dead_code)]
@@ -31,3 +139,281 @@ impl Tab {
         &self.request.url
     }
 }
+
+/// A request being sent in the background, and what its response is
+/// checked and recorded against once it arrives. The send keeps going while
+/// other tabs are shown and is cancelled when this is dropped, which happens
+/// when its tab is closed or restless quits.
+pub struct InFlight {
+    pub started: Instant,
+    /// The tab's request as written when it was sent
+    pub written: Request,
+    /// The URL sent to, variables but not secrets filled in
+    pub url: String,
+    /// Environment it was sent in, for certificate pins
+    pub env: Option<Environment>,
+    pub findings: Vec<Finding>,
+    /// History entry to record, its status and time filled in when done
+    pub entry: Entry,
+    /// Automatic retries after a `429` that came before this send
+    pub retries: u32,
+    /// The request sent, variables filled in, for a retry; secrets are
+    /// looked up again each time
+    request: Request,
+    outcome: oneshot::Receiver<anyhow::Result<RawResponse>>,
+    _task: Task,
+}
+
+impl InFlight {
+    /// Starts sending `request`, after looking up its secrets
+    pub fn spawn(
+        request: Request,
+        written: Request,
+        env: Option<Environment>,
+        findings: Vec<Finding>,
+        entry: Entry,
+    ) -> InFlight {
+        let (sender, outcome) = oneshot::channel();
+        let url = request.url.clone();
+        let sent = request.clone();
+        let task = tokio::spawn(async move {
+            let outcome = match secrets::resolve(sent).await {
+                Ok(request) => request.send().await,
+                Err(e) => Err(e.into()),
+            };
+            let _ = sender.send(outcome);
+        });
+        InFlight {
+            started: Instant::now(),
+            written,
+            url,
+            env,
+            findings,
+            entry,
+            retries: 0,
+            request,
+            outcome,
+            _task: Task(task),
+        }
+    }
+
+    /// The same send, to be repeated at `at`
+    pub fn retry(&self, at: DateTime<Utc>) -> Retry {
+        Retry {
+            at,
+            retries: self.retries + 1,
+            request: self.request.clone(),
+            written: self.written.clone(),
+            env: self.env.clone(),
+            findings: self.findings.clone(),
+            entry: self.entry.clone(),
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// The elapsed time as shown while waiting, e.g. `3.2s`
+    pub fn label(&self) -> String {
+        format!("{:.1}s", self.elapsed().as_secs_f64())
+    }
+
+    /// The outcome, once the response or an error is in
+    pub fn poll(&mut self) -> Option<anyhow::Result<RawResponse>> {
+        match self.outcome.try_recv() {
+            Ok(outcome) => Some(outcome),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Closed) => Some(Err(cancelled())),
+        }
+    }
+
+    /// Waits for the outcome
+    pub async fn finish(&mut self) -> anyhow::Result<RawResponse> {
+        (&mut self.outcome)
+            .await
+            .unwrap_or_else(|_| Err(cancelled()))
+    }
+}
+
+/// A send waiting for a rate limit window to open
+pub struct Retry {
+    pub at: DateTime<Utc>,
+    retries: u32,
+    request: Request,
+    written: Request,
+    env: Option<Environment>,
+    findings: Vec<Finding>,
+    entry: Entry,
+}
+
+impl Retry {
+    /// Sends the request again
+    pub fn spawn(self) -> InFlight {
+        let mut in_flight = InFlight::spawn(
+            self.request,
+            self.written,
+            self.env,
+            self.findings,
+            self.entry,
+        );
+        in_flight.retries = self.retries;
+        in_flight
+    }
+}
+
+/// A load test running in the background; dropping it stops the sends
+pub struct LoadTest {
+    pub started: Instant,
+    pub load: Load,
+    /// Method and URL sent, variables filled in
+    pub target: String,
+    progress: Arc<Progress>,
+    report: oneshot::Receiver<Report>,
+    _task: Task,
+}
+
+impl LoadTest {
+    /// Starts sending `request` as `load` says, after looking up its secrets
+    /// once; if that fails, every send fails with the same error
+    pub fn spawn(request: Request, load: Load) -> LoadTest {
+        let (sender, report) = oneshot::channel();
+        let target = format!("{} {}", request.method, request.url);
+        let progress = Arc::new(Progress::default());
+        let counted = progress.clone();
+        let task = tokio::spawn(async move {
+            let report = match secrets::resolve(request).await {
+                Ok(request) => load::run_counted(&request, load, counted).await,
+                Err(e) => {
+                    counted.done.store(load.total, Ordering::Relaxed);
+                    Report {
+                        errors: [(e.to_string(), load.total)].into(),
+                        ..Report::default()
+                    }
+                }
+            };
+            let _ = sender.send(report);
+        });
+        LoadTest {
+            started: Instant::now(),
+            load,
+            target,
+            progress,
+            report,
+            _task: Task(task),
+        }
+    }
+
+    /// How many sends are done so far
+    pub fn done(&self) -> usize {
+        self.progress.done()
+    }
+
+    /// Latency of the sends answered so far
+    pub fn latency(&self) -> LatencyHistogram {
+        self.progress.latency()
+    }
+
+    /// Sends done per second since the start
+    pub fn requests_per_second(&self) -> f64 {
+        let seconds = self.started.elapsed().as_secs_f64();
+        if seconds == 0.0 {
+            0.0
+        } else {
+            self.done() as f64 / seconds
+        }
+    }
+
+    /// The report, once every send is done
+    pub fn poll(&mut self) -> Option<Report> {
+        match self.report.try_recv() {
+            Ok(report) => Some(report),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Closed) => Some(Report::default()),
+        }
+    }
+}
+
+/// A run of requests sent one after another in the background; dropping it
+/// stops the send under way and the ones after it
+pub struct Run {
+    pub title: String,
+    /// How many requests the run has, sent or not
+    pub total: usize,
+    /// Requests not sent yet, with their names
+    pub pending: VecDeque<(String, Request)>,
+    /// The request being sent
+    pub current: Option<RunSend>,
+}
+
+impl Run {
+    pub fn new(title: String, requests: Vec<(String, Request)>) -> Run {
+        Run {
+            title,
+            total: requests.len(),
+            pending: requests.into(),
+            current: None,
+        }
+    }
+}
+
+/// One request of a run being sent
+pub struct RunSend {
+    pub name: String,
+    pub started: Instant,
+    /// The request as written, for its tests and history entry
+    pub written: Request,
+    /// Environment it was sent in
+    pub env: Option<Environment>,
+    outcome: oneshot::Receiver<anyhow::Result<RawResponse>>,
+    _task: Task,
+}
+
+impl RunSend {
+    /// Starts sending `request`, after looking up its secrets
+    pub fn spawn(
+        name: String,
+        request: Request,
+        written: Request,
+        env: Option<Environment>,
+    ) -> RunSend {
+        let (sender, outcome) = oneshot::channel();
+        let task = tokio::spawn(async move {
+            let outcome = match secrets::resolve(request).await {
+                Ok(request) => request.send().await,
+                Err(e) => Err(e.into()),
+            };
+            let _ = sender.send(outcome);
+        });
+        RunSend {
+            name,
+            started: Instant::now(),
+            written,
+            env,
+            outcome,
+            _task: Task(task),
+        }
+    }
+
+    /// The outcome, once the response or an error is in
+    pub fn poll(&mut self) -> Option<anyhow::Result<RawResponse>> {
+        match self.outcome.try_recv() {
+            Ok(outcome) => Some(outcome),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Closed) => Some(Err(cancelled())),
+        }
+    }
+}
+
+/// Aborts the send when dropped
+struct Task(JoinHandle<()>);
+
+impl Drop for Task {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+fn cancelled() -> anyhow::Error {
+    anyhow::anyhow!("The send was cancelled")
+}
@@ -0,0 +1,345 @@
+//! Command line arguments
+
+use clap::{Parser, Subcommand};
+
+#[derive(Debug, Default, Parser)]
+#[command(name = "restless", version, about)]
+pub struct Cli {
+    /// Linear, screen-reader friendly mode: plain prompts and output instead
+    /// of the full-screen interface
+    #[arg(long)]
+    pub plain: bool,
+
+    /// With --plain, exit with status 1 if any send fails or gets a 4xx/5xx
+    #[arg(long, requires = "plain")]
+    pub fail: bool,
+
+    /// Re-send the current request whenever a file under this path changes
+    #[arg(long, value_name = "PATH", conflicts_with = "plain")]
+    pub watch: Option<String>,
+
+    /// Re-send the current request on an interval such as `5`, `30s` or `2m`
+    #[arg(long, value_name = "INTERVAL", conflicts_with = "plain")]
+    pub poll: Option<String>,
+
+    /// Open a `.http` file and its first request; the file is reloaded
+    /// whenever it changes
+    #[arg(long, value_name = "FILE", conflicts_with = "plain")]
+    pub open: Option<String>,
+
+    /// With --open, send the request again each time the file is saved
+    #[arg(long, requires = "open")]
+    pub send_on_save: bool,
+
+    /// A URL to open in a tab, or a collection file (`.json` or `.http`) to
+    /// open in the collection browser
+    #[arg(value_name = "URL|FILE", conflicts_with_all = ["plain", "open"])]
+    pub target: Option<String>,
+
+    /// Append tracing logs to this file; overrides `[log] file` in the
+    /// config
+    #[arg(long, value_name = "PATH", global = true)]
+    pub log_file: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// Print a collection as a smoke check script that replays it with
+    /// `restless --plain --fail`
+    ExportScript {
+        /// Name of the collection
+        collection: String,
+        /// Environment whose variables are filled in; secrets become
+        /// environment variables of the script
+        #[arg(long)]
+        env: Option<String>,
+        /// `sh` for a shell script, `gha` for a GitHub Actions workflow
+        #[arg(long, default_value = "sh")]
+        format: String,
+    },
+    /// Print recent sends from the history, newest first
+    History {
+        /// Only sends whose URL contains this, ignoring case
+        #[arg(default_value = "")]
+        query: String,
+        /// Number of sends to print
+        #[arg(long, short = 'n', default_value_t = 20)]
+        limit: usize,
+        /// Print sends, errors and average time per method and URL instead
+        #[arg(long)]
+        stats: bool,
+        /// Print the results of the last runs of this collection instead,
+        /// `Open tabs` for runs of the open tabs
+        #[arg(long, value_name = "TITLE")]
+        run: Option<String>,
+    },
+    /// Share settings between machines as named profile files
+    #[command(subcommand)]
+    Profile(ProfileCommand),
+    /// Check every request of a collection against the workspace's lint
+    /// rules; exits with status 1 when a rule at `error` level is broken
+    Lint {
+        /// Name of the collection
+        collection: String,
+        /// Environment the requests would be sent in
+        #[arg(long)]
+        env: Option<String>,
+    },
+    /// Send one request many times and print latency percentiles, status
+    /// codes and errors
+    Bench {
+        #[arg(long)]
+        url: String,
+        #[arg(long, short = 'X', default_value = "GET")]
+        method: String,
+        /// Header as `Name: value`; repeat for more
+        #[arg(long = "header", short = 'H', value_name = "HEADER")]
+        headers: Vec<String>,
+        #[arg(long, short = 'd')]
+        body: Option<String>,
+        /// Number of requests to send
+        #[arg(short = 'n', default_value_t = 200)]
+        requests: usize,
+        /// Number of requests in flight at once
+        #[arg(short = 'c', default_value_t = crate::logic::load::DEFAULT_CONCURRENCY)]
+        concurrency: usize,
+    },
+    /// Send one request and print the status line, headers and body; exits
+    /// with status 1 for a 4xx/5xx response and 2 when the send fails
+    Send {
+        #[arg(long)]
+        url: String,
+        #[arg(long, short = 'X', default_value = "GET")]
+        method: String,
+        /// Header as `Name: value`; repeat for more
+        #[arg(long = "header", short = 'H', value_name = "HEADER")]
+        headers: Vec<String>,
+        /// Request body
+        #[arg(long, short = 'd')]
+        data: Option<String>,
+    },
+    /// Serve canned responses per route on a local port, for frontend work
+    /// while the real API is unavailable
+    Mock {
+        /// Routes file
+        #[arg(default_value = crate::mock::FILE_NAME)]
+        file: std::path::PathBuf,
+        #[arg(long, short = 'p', default_value_t = 8080)]
+        port: u16,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Subcommand)]
+pub enum ProfileCommand {
+    /// Print the current settings as a profile, without proxy credentials
+    Export {
+        /// Name of the profile
+        name: String,
+    },
+    /// Show what a profile would change
+    Import {
+        /// Profile file to read
+        file: String,
+        /// Replace the config file with the profile, keeping a backup
+        #[arg(long)]
+        apply: bool,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain() {
+        assert!(!Cli::parse_from(["restless"]).plain);
+        assert!(Cli::parse_from(["restless", "--plain"]).plain);
+        assert!(Cli::parse_from(["restless", "--plain", "--fail"]).fail);
+        assert!(Cli::try_parse_from(["restless", "--fail"]).is_err());
+        assert!(Cli::try_parse_from(["restless", "--fancy"]).is_err());
+        assert_eq!(
+            Cli::parse_from(["restless", "--watch", "src"])
+                .watch
+                .as_deref(),
+            Some("src")
+        );
+        assert!(Cli::try_parse_from(["restless", "--plain", "--watch", "src"]).is_err());
+        assert_eq!(
+            Cli::parse_from(["restless", "--poll", "30s"])
+                .poll
+                .as_deref(),
+            Some("30s")
+        );
+        let cli = Cli::parse_from(["restless", "--open", "api.http", "--send-on-save"]);
+        assert_eq!(cli.open.as_deref(), Some("api.http"));
+        assert!(cli.send_on_save);
+        assert!(Cli::try_parse_from(["restless", "--send-on-save"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_target() {
+        let cli = Cli::parse_from(["restless", "https://api.example.com/health"]);
+        assert_eq!(
+            cli.target.as_deref(),
+            Some("https://api.example.com/health")
+        );
+        assert_eq!(cli.command, None);
+        let cli = Cli::parse_from(["restless", "my-collection.json"]);
+        assert_eq!(cli.target.as_deref(), Some("my-collection.json"));
+        // Subcommand names still run the subcommand
+        assert!(Cli::parse_from(["restless", "history"]).target.is_none());
+        assert!(Cli::try_parse_from(["restless", "--plain", "https://a.test/"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_export_script() {
+        let cli = Cli::parse_from(["restless", "export-script", "My API", "--env", "prod"]);
+        assert_eq!(
+            cli.command,
+            Some(Command::ExportScript {
+                collection: "My API".to_string(),
+                env: Some("prod".to_string()),
+                format: "sh".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        assert_eq!(
+            Cli::parse_from(["restless", "profile", "import", "team.toml", "--apply"]).command,
+            Some(Command::Profile(ProfileCommand::Import {
+                file: "team.toml".to_string(),
+                apply: true,
+            }))
+        );
+        assert!(Cli::try_parse_from(["restless", "profile", "export"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_lint() {
+        assert_eq!(
+            Cli::parse_from(["restless", "lint", "My API", "--env", "prod"]).command,
+            Some(Command::Lint {
+                collection: "My API".to_string(),
+                env: Some("prod".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            Cli::parse_from([
+                "restless",
+                "bench",
+                "--url",
+                "http://api/health",
+                "-n",
+                "1000",
+                "-c",
+                "20",
+                "-H",
+                "Accept: application/json",
+            ])
+            .command,
+            Some(Command::Bench {
+                url: "http://api/health".to_string(),
+                method: "GET".to_string(),
+                headers: vec!["Accept: application/json".to_string()],
+                body: None,
+                requests: 1000,
+                concurrency: 20,
+            })
+        );
+        assert!(Cli::try_parse_from(["restless", "bench"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_send() {
+        assert_eq!(
+            Cli::parse_from([
+                "restless",
+                "send",
+                "--method",
+                "POST",
+                "--url",
+                "http://api/users",
+                "--header",
+                "Content-Type: application/json",
+                "--data",
+                "{}",
+            ])
+            .command,
+            Some(Command::Send {
+                url: "http://api/users".to_string(),
+                method: "POST".to_string(),
+                headers: vec!["Content-Type: application/json".to_string()],
+                data: Some("{}".to_string()),
+            })
+        );
+        assert!(Cli::try_parse_from(["restless", "send", "-X", "GET"]).is_err());
+
+        let cli = Cli::parse_from([
+            "restless",
+            "send",
+            "--url",
+            "http://api/",
+            "--log-file",
+            "send.log",
+        ]);
+        assert_eq!(cli.log_file.as_deref(), Some("send.log"));
+    }
+
+    #[test]
+    fn test_parse_mock() {
+        assert_eq!(
+            Cli::parse_from(["restless", "mock"]).command,
+            Some(Command::Mock {
+                file: "restless-mocks.toml".into(),
+                port: 8080,
+            })
+        );
+        assert_eq!(
+            Cli::parse_from(["restless", "mock", "api.toml", "--port", "3001"]).command,
+            Some(Command::Mock {
+                file: "api.toml".into(),
+                port: 3001,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_history() {
+        assert_eq!(
+            Cli::parse_from(["restless", "history", "users", "-n", "5"]).command,
+            Some(Command::History {
+                query: "users".to_string(),
+                limit: 5,
+                stats: false,
+                run: None,
+            })
+        );
+        assert_eq!(
+            Cli::parse_from(["restless", "history", "--stats"]).command,
+            Some(Command::History {
+                query: String::new(),
+                limit: 20,
+                stats: true,
+                run: None,
+            })
+        );
+        assert_eq!(
+            Cli::parse_from(["restless", "history", "--run", "My API", "-n", "3"]).command,
+            Some(Command::History {
+                query: String::new(),
+                limit: 3,
+                stats: false,
+                run: Some("My API".to_string()),
+            })
+        );
+    }
+}
@@ -0,0 +1,33 @@
+//! System clipboard access
+
+use crate::error::{RestlessError, Result};
+use std::sync::Mutex;
+
+// On X11 and Wayland the copied text is served by the clipboard owner, so the
+// handle is kept alive for the lifetime of the app instead of per copy.
+static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+
+fn with_clipboard<T>(
+    f: impl FnOnce(&mut arboard::Clipboard) -> std::result::Result<T, arboard::Error>,
+) -> Result<T> {
+    let mut guard = CLIPBOARD
+        .lock()
+        .map_err(|_| RestlessError::clipboard("Clipboard is unavailable"))?;
+    if guard.is_none() {
+        let clipboard =
+            arboard::Clipboard::new().map_err(|e| RestlessError::clipboard(e.to_string()))?;
+        *guard = Some(clipboard);
+    }
+    f(guard.as_mut().expect("clipboard initialized above"))
+        .map_err(|e| RestlessError::clipboard(e.to_string()))
+}
+
+/// Copies text to the system clipboard
+pub fn copy(text: &str) -> Result<()> {
+    with_clipboard(|clipboard| clipboard.set_text(text.to_string()))
+}
+
+/// The text on the system clipboard
+pub fn paste() -> Result<String> {
+    with_clipboard(|clipboard| clipboard.get_text())
+}
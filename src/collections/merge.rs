@@ -0,0 +1,313 @@
+//! Merging imported requests into an existing collection
+//!
+//! Imports are matched against the collection by method and URL template, so
+//! re-importing an updated export doesn't pile up copies of every request.
+//! Hosts, query strings and path variables are ignored when matching:
+//! `GET {{base_url}}/users/{{id}}`, `GET /users/:id` and
+//! `GET https://api.example.com/users/42?full=1` are the same request.
+
+use super::{Collection, SavedRequest};
+use std::fmt;
+
+/// What to do with an imported request the collection already has
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Resolution {
+    /// Fill in the headers, params and body the existing request lacks
+    Merge,
+    /// Keep the existing request as it is
+    Skip,
+    /// Add the imported request next to the existing one
+    Duplicate,
+}
+
+impl Resolution {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Resolution::Merge => "merge",
+            Resolution::Skip => "skip",
+            Resolution::Duplicate => "duplicate",
+        }
+    }
+}
+
+/// An imported request and how it will be applied
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Incoming {
+    pub request: SavedRequest,
+    /// Index of the matching request already in the collection
+    pub existing: Option<usize>,
+    /// How many requests of the import collapsed into this one
+    pub count: usize,
+    /// Ignored for requests that are new to the collection
+    pub resolution: Resolution,
+}
+
+/// An import waiting for the user to resolve its duplicates
+#[derive(Clone, Debug)]
+pub struct Review {
+    /// File the requests came from
+    pub source: String,
+    /// Collection the requests go into
+    pub collection: Collection,
+    /// Position of the collection in the app's list; `None` when it's new
+    pub index: Option<usize>,
+    pub items: Vec<Incoming>,
+    pub selected: usize,
+}
+
+/// Counts of what an applied import did
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub added: usize,
+    pub merged: usize,
+    pub skipped: usize,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} added, {} merged, {} skipped",
+            self.added, self.merged, self.skipped
+        )
+    }
+}
+
+impl Review {
+    /// Matches the imported requests against the collection
+    ///
+    /// Repeats within the import collapse into their first occurrence, which
+    /// matters for HAR files where the same call shows up dozens of times.
+    /// Requests the collection already has default to [`Resolution::Skip`].
+    pub fn new(
+        source: String,
+        collection: Collection,
+        index: Option<usize>,
+        requests: Vec<SavedRequest>,
+    ) -> Review {
+        let mut items: Vec<(String, Incoming)> = Vec::new();
+        for request in requests {
+            let key = template_key(&request.method, &request.url);
+            if let Some((_, item)) = items.iter_mut().find(|(other, _)| *other == key) {
+                item.count += 1;
+                continue;
+            }
+            let existing = collection
+                .requests
+                .iter()
+                .position(|saved| template_key(&saved.method, &saved.url) == key);
+            items.push((
+                key,
+                Incoming {
+                    request,
+                    existing,
+                    count: 1,
+                    resolution: Resolution::Skip,
+                },
+            ));
+        }
+
+        Review {
+            source,
+            collection,
+            index,
+            items: items.into_iter().map(|(_, item)| item).collect(),
+            selected: 0,
+        }
+    }
+
+    /// How many imported requests match one in the collection
+    pub fn conflicts(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.existing.is_some())
+            .count()
+    }
+
+    /// Sets the resolution of the selected request, or of every conflict
+    pub fn resolve(&mut self, resolution: Resolution, all: bool) {
+        let selected = self.selected;
+        for (index, item) in self.items.iter_mut().enumerate() {
+            if all || index == selected {
+                item.resolution = resolution;
+            }
+        }
+    }
+
+    /// Applies the import to the collection
+    pub fn apply(&mut self) -> Summary {
+        let mut summary = Summary::default();
+        for item in &self.items {
+            match (item.existing, item.resolution) {
+                (None, _) => {
+                    self.collection.requests.push(item.request.clone());
+                    summary.added += 1;
+                }
+                (Some(existing), Resolution::Merge) => {
+                    merge(&mut self.collection.requests[existing], &item.request);
+                    summary.merged += 1;
+                }
+                (Some(_), Resolution::Skip) => summary.skipped += 1,
+                (Some(_), Resolution::Duplicate) => {
+                    self.collection.requests.push(SavedRequest {
+                        name: format!("{} (imported)", item.request.name),
+                        ..item.request.clone()
+                    });
+                    summary.added += 1;
+                }
+            }
+        }
+        summary
+    }
+}
+
+/// Fills in what the existing request lacks; anything it already has wins
+fn merge(existing: &mut SavedRequest, imported: &SavedRequest) {
+    for (key, value) in &imported.headers {
+        if !existing
+            .headers
+            .iter()
+            .any(|(other, _)| other.eq_ignore_ascii_case(key))
+        {
+            existing.headers.push((key.clone(), value.clone()));
+        }
+    }
+    for (key, value) in &imported.params {
+        if !existing.params.iter().any(|(other, _)| other == key) {
+            existing.params.push((key.clone(), value.clone()));
+        }
+    }
+    if existing.body.is_none() && existing.form.is_none() {
+        existing.body = imported.body.clone();
+        existing.form = imported.form.clone();
+    }
+}
+
+/// The method and URL template a request is matched on, e.g. `GET /users/{}`
+pub fn template_key(method: &str, url: &str) -> String {
+    let url = url.split(['?', '#']).next().unwrap_or_default();
+    // Drop the scheme and host, or a leading `{{base_url}}`
+    let path = if let Some(rest) = url.strip_prefix("{{") {
+        rest.split_once("}}").map_or("", |(_, path)| path)
+    } else if let Some((_, rest)) = url.split_once("://") {
+        rest.find('/').map_or("", |index| &rest[index..])
+    } else {
+        url
+    };
+
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| if is_variable(segment) { "{}" } else { segment })
+        .collect();
+    format!("{} /{}", method.to_uppercase(), segments.join("/"))
+}
+
+/// Path segments that stand for a value: `:id`, `{id}`, `{{id}}`, numbers
+/// and UUIDs
+fn is_variable(segment: &str) -> bool {
+    segment.starts_with(':')
+        || (segment.starts_with('{') && segment.ends_with('}'))
+        || segment.chars().all(|c| c.is_ascii_digit())
+        || (segment.len() == 36 && segment.chars().all(|c| c.is_ascii_hexdigit() || c == '-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn saved(name: &str, method: &str, url: &str) -> SavedRequest {
+        SavedRequest {
+            name: name.to_string(),
+            description: String::new(),
+            method: method.to_string(),
+            url: url.to_string(),
+            headers: Vec::new(),
+            params: Vec::new(),
+            disabled_headers: Vec::new(),
+            disabled_params: Vec::new(),
+            tests: String::new(),
+            body: None,
+            form: None,
+            auth: None,
+            options: Default::default(),
+            panel: None,
+        }
+    }
+
+    #[test]
+    fn test_template_key() {
+        let key = template_key("get", "{{base_url}}/users/{{id}}");
+        assert_eq!(key, "GET /users/{}");
+        assert_eq!(template_key("GET", "/users/:id/"), key);
+        assert_eq!(
+            template_key("GET", "https://api.example.com/users/42?full=1"),
+            key
+        );
+        assert_eq!(
+            template_key("GET", "http://x/users/0b6c7f3e-8a4d-4d4e-9c55-2f1d9c1e6a10"),
+            key
+        );
+        assert_ne!(template_key("DELETE", "/users/1"), key);
+        assert_eq!(template_key("GET", "https://example.com"), "GET /");
+    }
+
+    #[test]
+    fn test_review_and_apply() {
+        let mut existing = saved("List users", "GET", "{{base_url}}/users");
+        existing.headers = vec![("Accept".to_string(), "application/json".to_string())];
+        let collection = Collection {
+            name: "API".to_string(),
+            requests: vec![
+                existing,
+                saved("Get user", "GET", "{{base_url}}/users/{{id}}"),
+            ],
+            ..Default::default()
+        };
+
+        let mut listed = saved("GET /users", "GET", "https://api.example.com/users");
+        listed.headers = vec![
+            ("accept".to_string(), "*/*".to_string()),
+            ("X-Trace".to_string(), "1".to_string()),
+        ];
+        let imported = vec![
+            listed,
+            saved("GET /users/1", "GET", "https://api.example.com/users/1"),
+            saved("GET /users/2", "GET", "https://api.example.com/users/2"),
+            saved("POST /users", "POST", "https://api.example.com/users"),
+        ];
+
+        let mut review = Review::new("api.har".to_string(), collection, Some(1), imported);
+        assert_eq!(review.items.len(), 3);
+        assert_eq!(review.conflicts(), 2);
+        assert_eq!(review.items[1].count, 2);
+        assert_eq!(review.items[1].existing, Some(1));
+        assert_eq!(review.items[2].existing, None);
+
+        review.resolve(Resolution::Duplicate, true);
+        review.resolve(Resolution::Merge, false);
+        let summary = review.apply();
+        assert_eq!(
+            summary,
+            Summary {
+                added: 2,
+                merged: 1,
+                skipped: 0
+            }
+        );
+        assert_eq!(summary.to_string(), "2 added, 1 merged, 0 skipped");
+
+        let requests = &review.collection.requests;
+        assert_eq!(requests.len(), 4);
+        // The existing Accept header wins; the new header is filled in
+        assert_eq!(
+            requests[0].headers,
+            vec![
+                ("Accept".to_string(), "application/json".to_string()),
+                ("X-Trace".to_string(), "1".to_string()),
+            ]
+        );
+        assert_eq!(requests[2].name, "GET /users/1 (imported)");
+        assert_eq!(requests[3].name, "POST /users");
+    }
+}
@@ -0,0 +1,272 @@
+//! Request collections
+//!
+//! A collection is a named, ordered list of saved requests. The built-in
+//! tutorial is always available; user collections are JSON files in the
+//! `collections` directory of the config directory:
+//!
+//! ```json
+//! {
+//!   "name": "My API",
+//!   "requests": [
+//!     { "name": "List users", "method": "GET", "url": "{{base_url}}/users" }
+//!   ]
+//! }
+//! ```
+//!
+//! A collection level `auth` applies to every request without an `auth` of its
+//! own; a request opts out with `"auth": {"kind": "none"}`.
+
+pub mod merge;
+pub mod revisions;
+pub mod tutorial;
+
+use crate::error::{RestlessError, Result};
+use crate::logic::auth::Auth;
+use crate::logic::options::RequestOptions;
+use crate::logic::request::Request;
+use crate::logic::HttpMethod;
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A named group of saved requests
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Collection {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Auth shared by the requests that don't set their own
+    #[serde(default)]
+    pub auth: Auth,
+    #[serde(default)]
+    pub requests: Vec<SavedRequest>,
+    /// File the collection was loaded from; `None` for the built-in tutorial
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// A request stored in a collection
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_method")]
+    pub method: String,
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    #[serde(default)]
+    pub params: Vec<(String, String)>,
+    /// Headers and params switched off, see [`Request::disabled_headers`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_headers: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_params: Vec<(String, String)>,
+    /// Assertions checked against every response, see
+    /// [`crate::logic::assertions`]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub tests: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form: Option<Vec<(String, String)>>,
+    /// The request's own auth; `None` inherits the collection's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<Auth>,
+    #[serde(default)]
+    pub options: RequestOptions,
+    /// Template for a custom response tab, see [`crate::logic::panel`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panel: Option<String>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+impl Collection {
+    /// Whether the collection is an opened `.http` file, which is only ever
+    /// read: edits happen in the user's editor
+    pub fn is_http_file(&self) -> bool {
+        self.path
+            .as_ref()
+            .is_some_and(|path| path.extension().is_some_and(|ext| ext == "http"))
+    }
+}
+
+impl SavedRequest {
+    /// Saves an editable request under `name`, with its auth as its own
+    pub fn from_request(name: &str, request: &Request, panel: Option<String>) -> SavedRequest {
+        SavedRequest {
+            name: name.to_string(),
+            description: String::new(),
+            method: request.method.to_string(),
+            url: request.url.clone(),
+            headers: request.headers.clone(),
+            params: request.params.clone(),
+            disabled_headers: request.disabled_headers.clone(),
+            disabled_params: request.disabled_params.clone(),
+            tests: request.tests.clone(),
+            body: request.body.clone(),
+            form: request.form.clone(),
+            auth: Some(request.auth.clone()),
+            options: request.options.clone(),
+            panel,
+        }
+    }
+
+    /// Converts the saved request into an editable request, using the
+    /// collection's auth unless the request has its own
+    pub fn to_request(&self, collection_auth: &Auth) -> Result<Request> {
+        let method = Method::from_bytes(self.method.to_uppercase().as_bytes())
+            .map_err(|_| RestlessError::invalid_http_method(self.method.clone()))?;
+        // Only methods the editor supports can be loaded into a tab
+        HttpMethod::try_from(&method)?;
+
+        Ok(Request {
+            url: self.url.clone(),
+            method,
+            headers: self.headers.clone(),
+            body: self.body.clone(),
+            form: self.form.clone(),
+            params: self.params.clone(),
+            disabled_headers: self.disabled_headers.clone(),
+            disabled_params: self.disabled_params.clone(),
+            tests: self.tests.clone(),
+            auth: self.auth.as_ref().unwrap_or(collection_auth).clone(),
+            options: self.options.clone(),
+            revalidate: false,
+        })
+    }
+}
+
+/// Loads every `*.json` collection in a directory, sorted by file name
+///
+/// A missing directory means no user collections.
+pub fn load_collections(dir: &Path) -> Result<Vec<Collection>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths: Vec<_> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    paths.iter().map(|path| load_collection(path)).collect()
+}
+
+/// Loads one collection file; saving it writes back to the same file
+pub fn load_collection(path: &Path) -> Result<Collection> {
+    let content = std::fs::read_to_string(path)?;
+    let collection: Collection = serde_json::from_str(&content).map_err(|e| {
+        RestlessError::configuration(format!("Invalid collection {}: {}", path.display(), e))
+    })?;
+    Ok(Collection {
+        path: Some(path.to_path_buf()),
+        ..collection
+    })
+}
+
+/// Writes a collection back to the file it belongs to
+pub fn save_collection(collection: &Collection) -> Result<()> {
+    let path = collection.path.as_ref().ok_or_else(|| {
+        RestlessError::app_state(format!("Collection {} has no file", collection.name))
+    })?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(collection)? + "\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saved_request_to_request() {
+        let saved: SavedRequest = serde_json::from_str(
+            r#"{"name": "Create", "method": "post", "url": "http://localhost/items",
+                "headers": [["Content-Type", "application/json"]],
+                "body": "{}", "auth": {"kind": "api_key", "fields": [["key", "X-Key"]]}}"#,
+        )
+        .unwrap();
+
+        let request = saved.to_request(&Auth::default()).unwrap();
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.url, "http://localhost/items");
+        assert_eq!(request.headers.len(), 1);
+        assert_eq!(request.body.as_deref(), Some("{}"));
+        assert_eq!(request.auth.field("key"), Some("X-Key"));
+
+        let saved = SavedRequest {
+            method: "PATCH".to_string(),
+            ..saved
+        };
+        assert!(saved.to_request(&Auth::default()).is_err());
+    }
+
+    #[test]
+    fn test_collection_auth_is_inherited() {
+        let collection: Collection = serde_json::from_str(
+            r#"{"name": "Service",
+                "auth": {"kind": "api_key", "fields": [["key", "X-Key"], ["value", "{{key}}"]]},
+                "requests": [
+                    {"name": "Inherits", "url": "http://x/a"},
+                    {"name": "Own", "url": "http://x/b",
+                     "auth": {"kind": "api_key", "fields": [["key", "X-Other"]]}},
+                    {"name": "Opts out", "url": "http://x/c", "auth": {"kind": "none"}}
+                ]}"#,
+        )
+        .unwrap();
+
+        let requests: Vec<Request> = collection
+            .requests
+            .iter()
+            .map(|saved| saved.to_request(&collection.auth).unwrap())
+            .collect();
+        assert_eq!(requests[0].auth.field("key"), Some("X-Key"));
+        assert_eq!(requests[1].auth.field("key"), Some("X-Other"));
+        assert_eq!(requests[2].auth, Auth::default());
+
+        // Inheriting requests don't write an auth of their own back out
+        let saved = serde_json::to_value(&collection.requests[0]).unwrap();
+        assert!(saved.get("auth").is_none());
+    }
+
+    #[test]
+    fn test_load_collections() {
+        let dir =
+            std::env::temp_dir().join(format!("restless-collections-test-{}", std::process::id()));
+        assert!(load_collections(&dir).unwrap().is_empty());
+
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("b.json"),
+            r#"{"name": "B", "requests": [{"name": "ping", "url": "http://x"}]}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("a.json"), r#"{"name": "A"}"#).unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let mut collections = load_collections(&dir).unwrap();
+        assert_eq!(collections.len(), 2);
+        assert_eq!(collections[0].name, "A");
+        assert_eq!(collections[0].path, Some(dir.join("a.json")));
+        assert_eq!(collections[1].requests[0].method, "GET");
+
+        collections[0].description = "Saved".to_string();
+        save_collection(&collections[0]).unwrap();
+        assert_eq!(load_collections(&dir).unwrap()[0].description, "Saved");
+        assert!(save_collection(&tutorial::tutorial()).is_err());
+
+        std::fs::write(dir.join("c.json"), "not json").unwrap();
+        assert!(load_collections(&dir).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
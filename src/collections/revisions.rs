@@ -0,0 +1,141 @@
+//! Revisions of saved requests
+//!
+//! Collections are edited by hand, so edits are picked up at startup: every
+//! saved request that differs from its last revision gets a new one, dated
+//! when its collection file was last modified. Revisions are kept in the
+//! history storage for as long as it exists, which makes restoring an older
+//! version of a request an undo across sessions.
+
+use super::{Collection, SavedRequest};
+use crate::error::Result;
+use crate::storage::{Revision, Storage};
+use chrono::{DateTime, Utc};
+
+/// One point in the timeline of a saved request
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Version {
+    pub saved_at: DateTime<Utc>,
+    pub request: SavedRequest,
+    /// What changed since the version before; empty for the first one
+    pub changes: Vec<&'static str>,
+}
+
+/// Whether a collection's requests get revisions: only collection files
+/// restless can write back to do
+pub fn is_tracked(collection: &Collection) -> bool {
+    collection.path.is_some() && !collection.is_http_file()
+}
+
+/// Records a revision of each request that changed since its last one,
+/// returning how many were recorded
+pub fn track(
+    storage: &mut dyn Storage,
+    collection: &Collection,
+    saved_at: DateTime<Utc>,
+) -> Result<usize> {
+    if !is_tracked(collection) {
+        return Ok(0);
+    }
+    let mut recorded = 0;
+    for saved in &collection.requests {
+        let content = serde_json::to_string(saved)?;
+        let revisions = storage.revisions(&collection.name, &saved.name)?;
+        if revisions
+            .first()
+            .is_some_and(|last| last.content == content)
+        {
+            continue;
+        }
+        storage.record_revision(&Revision {
+            saved_at,
+            collection: collection.name.clone(),
+            request: saved.name.clone(),
+            content,
+        })?;
+        recorded += 1;
+    }
+    Ok(recorded)
+}
+
+/// The versions of a saved request, newest first
+pub fn timeline(storage: &dyn Storage, collection: &str, request: &str) -> Result<Vec<Version>> {
+    let versions: Vec<(DateTime<Utc>, SavedRequest)> = storage
+        .revisions(collection, request)?
+        .into_iter()
+        .filter_map(|revision| {
+            let saved = serde_json::from_str(&revision.content).ok()?;
+            Some((revision.saved_at, saved))
+        })
+        .collect();
+    Ok(versions
+        .iter()
+        .enumerate()
+        .map(|(i, (saved_at, saved))| Version {
+            saved_at: *saved_at,
+            request: saved.clone(),
+            changes: versions
+                .get(i + 1)
+                .map(|(_, older)| changes(older, saved))
+                .unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// The parts of a saved request that differ between two versions
+pub fn changes(older: &SavedRequest, newer: &SavedRequest) -> Vec<&'static str> {
+    [
+        ("method", older.method != newer.method),
+        ("url", older.url != newer.url),
+        ("headers", older.headers != newer.headers),
+        ("params", older.params != newer.params),
+        ("body", older.body != newer.body || older.form != newer.form),
+        ("auth", older.auth != newer.auth),
+        ("options", older.options != newer.options),
+        ("tests", older.tests != newer.tests),
+        ("description", older.description != newer.description),
+        ("panel", older.panel != newer.panel),
+    ]
+    .into_iter()
+    .filter(|(_, changed)| *changed)
+    .map(|(part, _)| part)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::memory::MemoryStorage;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_track_and_timeline() {
+        let mut storage = MemoryStorage::default();
+        let mut collection: Collection = serde_json::from_str(
+            r#"{"name": "My API", "requests": [
+                {"name": "Users", "url": "http://api/users"},
+                {"name": "Orders", "url": "http://api/orders"}]}"#,
+        )
+        .unwrap();
+        let at = |seconds: i64| Utc.timestamp_opt(1_700_000_000 + seconds, 0).unwrap();
+        assert_eq!(track(&mut storage, &collection, at(0)).unwrap(), 0);
+
+        collection.path = Some("my-api.json".into());
+        assert_eq!(track(&mut storage, &collection, at(0)).unwrap(), 2);
+        assert_eq!(track(&mut storage, &collection, at(5)).unwrap(), 0);
+
+        let users = &mut collection.requests[0];
+        users.url = "https://api/users".to_string();
+        users
+            .headers
+            .push(("Accept".to_string(), "application/json".to_string()));
+        assert_eq!(track(&mut storage, &collection, at(10)).unwrap(), 1);
+
+        let versions = timeline(&storage, "My API", "Users").unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].saved_at, at(10));
+        assert_eq!(versions[0].changes, vec!["url", "headers"]);
+        assert_eq!(versions[1].request.url, "http://api/users");
+        assert!(versions[1].changes.is_empty());
+        assert_eq!(timeline(&storage, "My API", "Orders").unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,142 @@
+//! The built-in "Learn restless" collection
+//!
+//! Every request targets the public httpbin.org echo service, so the lessons
+//! work without any setup beyond a network connection.
+
+use super::{Collection, SavedRequest};
+use crate::logic::auth::{Auth, AuthKind};
+
+const BASE_URL: &str = "https://httpbin.org";
+
+fn lesson(name: &str, method: &str, path: &str, description: &str) -> SavedRequest {
+    SavedRequest {
+        name: name.to_string(),
+        description: description.to_string(),
+        method: method.to_string(),
+        url: format!("{}{}", BASE_URL, path),
+        headers: Vec::new(),
+        params: Vec::new(),
+        disabled_headers: Vec::new(),
+        disabled_params: Vec::new(),
+        tests: String::new(),
+        body: None,
+        form: None,
+        auth: None,
+        options: Default::default(),
+        panel: None,
+    }
+}
+
+fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+    items
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// Builds the tutorial collection
+pub fn tutorial() -> Collection {
+    let mut params = lesson(
+        "2. Query parameters",
+        "GET",
+        "/get",
+        "Parameters live in the Params tab of the Values section instead of the URL. \
+         Press Ctrl+j to reach Values, h/l to switch to Params and i to add one as \
+         key=value. Send again and look for them under \"args\" in the response.",
+    );
+    params.params = pairs(&[("lesson", "2"), ("topic", "params")]);
+
+    let mut body = lesson(
+        "3. Sending a JSON body",
+        "POST",
+        "/post",
+        "POST requests usually carry a body. This one already has a JSON body and a \
+         Content-Type header; open the Body tab and press i to change it. httpbin \
+         echoes the parsed body back under \"json\".",
+    );
+    body.headers = pairs(&[("Content-Type", "application/json")]);
+    body.body = Some("{\n  \"name\": \"Ada\",\n  \"language\": \"Rust\"\n}".to_string());
+
+    let variables = lesson(
+        "4. Variables",
+        "GET",
+        "/anything/{{user}}",
+        "{{user}} is a variable. Create environments.json in the restless config \
+         directory with [{\"name\": \"tutorial\", \"variables\": {\"user\": \"ada\", \
+         \"api_key\": \"s3cr3t\"}}], restart and press e to activate it. Variables are \
+         filled in when the request is sent; unknown ones are left as they are so \
+         typos stay visible.",
+    );
+
+    let mut api_key = lesson(
+        "5. API key auth",
+        "GET",
+        "/headers",
+        "Credentials belong in the Auth tab, not in Headers. This request sends an \
+         X-Api-Key header taken from {{api_key}}: the Headers tab stays empty, but \
+         the echoed headers show the key. Set location=query on the Auth tab to send \
+         it as a parameter instead, or press Space to try the OAuth2 flows.",
+    );
+    api_key.auth = Some(Auth {
+        kind: AuthKind::ApiKey,
+        fields: pairs(&[("key", "X-Api-Key"), ("value", "{{api_key}}")]),
+    });
+
+    let status = lesson(
+        "6. Checking responses",
+        "GET",
+        "/status/418",
+        "The status line is the first thing to check: this endpoint always answers \
+         418. Switch between response headers and body with h/b and scroll with j/k. \
+         Try /status/500 or /delay/2 to see how failures and slow servers look.",
+    );
+
+    let export = lesson(
+        "7. Sharing a request",
+        "PUT",
+        "/put",
+        "Press E to export the current request as a curl command or a HAR file. \
+         Exports note which environment and variables were used, and secrets keep \
+         their {{placeholder}} so the result is safe to paste into a ticket.",
+    );
+
+    Collection {
+        name: "Learn restless".to_string(),
+        description: "A guided tour: open a lesson with Enter, read its notes, then send it."
+            .to_string(),
+        auth: Auth::default(),
+        requests: vec![
+            lesson(
+                "1. Your first request",
+                "GET",
+                "/get",
+                "Press Enter to send this request. Press Ctrl+j to move down to the \
+                 Response section, where h/b switch between headers and body. Press u \
+                 to edit the URL and m to pick another method.",
+            ),
+            params,
+            body,
+            variables,
+            api_key,
+            status,
+            export,
+        ],
+        path: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tutorial_requests_load() {
+        let collection = tutorial();
+        assert!(!collection.requests.is_empty());
+        for saved in &collection.requests {
+            let request = saved.to_request(&collection.auth).unwrap();
+            assert!(request.url.starts_with(BASE_URL));
+            assert!(!saved.description.is_empty());
+        }
+    }
+}
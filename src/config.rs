@@ -0,0 +1,282 @@
+//! Application configuration
+//!
+//! Read from `config.toml` in the config directory at startup:
+//!
+//! ```toml
+//! [connection]
+//! proxy = "socks5h://127.0.0.1:1080"
+//! proxy_auth = "user:password"
+//! ca_cert = "~/certs/internal-ca.pem"
+//! insecure = false
+//! http_version = "1.1"
+//! min_tls_version = "1.2"
+//! timeout = 30
+//!
+//! # Sent with every request that doesn't set them itself
+//! [headers]
+//! User-Agent = "restless"
+//! Accept = "application/json"
+//!
+//! [terminal]
+//! title = true
+//! tmux_status = true
+//!
+//! [history]
+//! backend = "sqlite"
+//! max_entries = 50000
+//!
+//! [ui]
+//! locale = "de"
+//! restore_session = true
+//! theme = "gruvbox"
+//! editor = "code --wait"
+//! layout = "auto"      # or "stacked", "split"
+//!
+//! # Overridden by --log-file
+//! [log]
+//! file = "~/restless.log"
+//! level = "debug"
+//!
+//! # Send again when a 429 Too Many Requests window opens
+//! [rate_limit]
+//! auto_retry = true
+//! max_wait = 60
+//! max_retries = 3
+//!
+//! # Single colors changed from the theme
+//! [theme]
+//! highlight = "#fe8019"
+//! ```
+//!
+//! Without a configured proxy the usual `HTTP_PROXY`, `HTTPS_PROXY`,
+//! `ALL_PROXY` and `NO_PROXY` environment variables apply.
+
+use crate::error::{RestlessError, Result};
+use crate::logic::options::RequestOptions;
+use crate::storage::HistorySettings;
+use crate::ui::layouts::LayoutMode;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Defaults for the per-request options
+    pub connection: RequestOptions,
+    /// Headers added to every request that doesn't set them, by name
+    pub headers: BTreeMap<String, String>,
+    pub terminal: TerminalSettings,
+    pub history: HistorySettings,
+    pub ui: UiSettings,
+    pub log: LogSettings,
+    pub rate_limit: RateLimitSettings,
+    /// Colors changed from the theme, by name
+    pub theme: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TerminalSettings {
+    /// Show the active tab in the terminal window title
+    pub title: bool,
+    /// Also set the `@restless` tmux pane option, for use in the status
+    /// line as `#{@restless}`
+    pub tmux_status: bool,
+}
+
+impl Default for TerminalSettings {
+    fn default() -> Self {
+        TerminalSettings {
+            title: true,
+            tmux_status: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiSettings {
+    /// Language of the interface; without it the system locale is used
+    /// when restless has translations for it
+    pub locale: Option<String>,
+    /// Reopen the tabs of the last run at startup
+    pub restore_session: bool,
+    /// Built-in color scheme: `default`, `gruvbox`, `dracula` or `solarized`
+    pub theme: Option<String>,
+    /// Editor for the body (Ctrl+E), with any arguments; `$VISUAL` or
+    /// `$EDITOR` otherwise
+    pub editor: Option<String>,
+    /// Responses each tab keeps to step through with `[` and `]`
+    pub responses_kept: usize,
+    /// Request and response stacked, side by side, or side by side on wide
+    /// terminals
+    pub layout: LayoutMode,
+}
+
+impl Default for UiSettings {
+    fn default() -> Self {
+        UiSettings {
+            locale: None,
+            restore_session: true,
+            theme: None,
+            editor: None,
+            responses_kept: 10,
+            layout: LayoutMode::Stacked,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogSettings {
+    /// File the tracing logs are appended to; nothing is logged without one
+    pub file: Option<String>,
+    /// Lowest level logged, see [`crate::logging`]
+    pub level: String,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        LogSettings {
+            file: None,
+            level: "info".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitSettings {
+    /// Send again when the window of a `429 Too Many Requests` opens
+    pub auto_retry: bool,
+    /// Longest wait in seconds for an automatic retry
+    pub max_wait: u64,
+    /// Automatic retries of one send before giving up
+    pub max_retries: u32,
+}
+
+impl Default for RateLimitSettings {
+    fn default() -> Self {
+        RateLimitSettings {
+            auto_retry: false,
+            max_wait: 60,
+            max_retries: 3,
+        }
+    }
+}
+
+/// Loads the config file; a missing file means the defaults
+pub fn load_config(path: &Path) -> Result<Config> {
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+    let content = std::fs::read_to_string(path)?;
+    let invalid = |e: &dyn std::fmt::Display| {
+        RestlessError::configuration(format!("Invalid config file {}: {}", path.display(), e))
+    };
+    let config: Config = toml::from_str(&content).map_err(|e| invalid(&e))?;
+    config.connection.validate().map_err(|e| invalid(&e))?;
+    crate::logging::parse_level(&config.log.level).map_err(|e| invalid(&e))?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_config() {
+        let dir = std::env::temp_dir().join(format!("restless-config-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+
+        assert_eq!(load_config(&path).unwrap(), Config::default());
+
+        std::fs::write(&path, "[connection]\nproxy = \"http://proxy:3128\"\n").unwrap();
+        let config = load_config(&path).unwrap();
+        assert_eq!(
+            config.connection.proxy.as_deref(),
+            Some("http://proxy:3128")
+        );
+
+        std::fs::write(
+            &path,
+            "[connection]\ninsecure = true\nhttp_version = 1.1\nmax_tls_version = 1.0\n",
+        )
+        .unwrap();
+        let config = load_config(&path).unwrap();
+        assert!(config.connection.is_insecure());
+        assert_eq!(config.connection.max_tls_version.as_deref(), Some("1.0"));
+        assert_eq!(
+            config.connection.http_version(),
+            Some(crate::logic::options::HttpVersion::Http1)
+        );
+
+        std::fs::write(
+            &path,
+            "[connection]\ntimeout = 90\n[headers]\nUser-Agent = \"restless\"\n",
+        )
+        .unwrap();
+        let config = load_config(&path).unwrap();
+        assert_eq!(
+            config.connection.timeout(),
+            std::time::Duration::from_secs(90)
+        );
+        assert_eq!(config.headers["User-Agent"], "restless");
+        std::fs::write(&path, "[connection]\ntimeout = 0\n").unwrap();
+        assert!(load_config(&path).is_err());
+
+        std::fs::write(&path, "[terminal]\ntmux_status = true\n").unwrap();
+        let config = load_config(&path).unwrap();
+        assert!(config.terminal.title);
+        assert!(config.terminal.tmux_status);
+
+        std::fs::write(&path, "[history]\nbackend = \"memory\"\n").unwrap();
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.history.backend, crate::storage::Backend::Memory);
+
+        std::fs::write(&path, "[ui]\nlocale = \"de\"\n").unwrap();
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.ui.locale.as_deref(), Some("de"));
+        assert!(config.ui.restore_session);
+
+        std::fs::write(
+            &path,
+            "[ui]\ntheme = \"dracula\"\n[theme]\nmuted = \"gray\"\n",
+        )
+        .unwrap();
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.ui.theme.as_deref(), Some("dracula"));
+        assert_eq!(config.theme["muted"], "gray");
+
+        std::fs::write(&path, "[ui]\neditor = \"code --wait\"\n").unwrap();
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.ui.editor.as_deref(), Some("code --wait"));
+        assert_eq!(config.ui.responses_kept, 10);
+        assert_eq!(config.ui.layout, LayoutMode::Stacked);
+        std::fs::write(&path, "[ui]\nlayout = \"split\"\n").unwrap();
+        assert_eq!(load_config(&path).unwrap().ui.layout, LayoutMode::Split);
+        std::fs::write(&path, "[ui]\nlayout = \"sideways\"\n").unwrap();
+        assert!(load_config(&path).is_err());
+
+        std::fs::write(&path, "[log]\nfile = \"/tmp/restless.log\"\n").unwrap();
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.log.file.as_deref(), Some("/tmp/restless.log"));
+        assert_eq!(config.log.level, "info");
+        std::fs::write(&path, "[log]\nlevel = \"loud\"\n").unwrap();
+        assert!(load_config(&path).is_err());
+
+        std::fs::write(&path, "[rate_limit]\nauto_retry = true\n").unwrap();
+        let config = load_config(&path).unwrap();
+        assert!(config.rate_limit.auto_retry);
+        assert_eq!(config.rate_limit.max_wait, 60);
+
+        std::fs::write(&path, "[connection]\nproxy = \"proxy:3128\"\n").unwrap();
+        assert!(load_config(&path).is_err());
+        std::fs::write(&path, "[nonsense]\n").unwrap();
+        assert!(load_config(&path).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,104 @@
+//! Editing text in the user's own editor
+//!
+//! The editor is `editor` under `[ui]` in the config, else `$VISUAL`, else
+//! `$EDITOR`, else `vi`. It may come with arguments (`code --wait`), split
+//! like a shell would so quoted paths with spaces stay whole; the file to edit
+//! is passed after them. The editor takes over the terminal, so
+//! the interface has to be suspended around [`edit`].
+
+use crate::error::{RestlessError, Result};
+use std::io::Write;
+use std::process::Command;
+
+/// The editor command to run
+pub fn command(configured: Option<&str>) -> String {
+    configured
+        .map(str::to_string)
+        .or_else(|| std::env::var("VISUAL").ok())
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|command| !command.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Opens `text` in the editor as a temporary `.extension` file and returns
+/// the text as saved when the editor exits
+///
+/// The file gets a random name and is created fresh, readable only by the
+/// user, so nothing planted at a guessable path in the shared temporary
+/// directory can be written through or read along.
+pub fn edit(command: &str, text: &str, extension: &str) -> Result<String> {
+    let parts = shlex::split(command).ok_or_else(|| {
+        RestlessError::configuration(format!("Unbalanced quotes in the editor {}", command))
+    })?;
+    let (program, args) = parts
+        .split_first()
+        .ok_or_else(|| RestlessError::configuration("No editor configured"))?;
+    let mut file = tempfile::Builder::new()
+        .prefix("restless-body-")
+        .suffix(&format!(".{}", extension))
+        .tempfile()?;
+    file.write_all(text.as_bytes())?;
+    file.flush()?;
+    let path = file.path();
+
+    let status = Command::new(program).args(args).arg(path).status();
+    let edited = match status {
+        Ok(status) if status.success() => std::fs::read_to_string(path).map_err(Into::into),
+        Ok(status) => Err(RestlessError::app_state(format!(
+            "{} exited with {}, the text is unchanged",
+            program, status
+        ))),
+        Err(e) => Err(RestlessError::app_state(format!(
+            "Couldn't start the editor {}: {}",
+            program, e
+        ))),
+    };
+    // Dropping the file removes it
+    edited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command() {
+        assert_eq!(command(Some("nano")), "nano");
+        assert!(!command(None).is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit() {
+        let edited = edit("sed -i s/name/title/", "{\"name\": 1}\n", "json").unwrap();
+        assert_eq!(edited, "{\"title\": 1}\n");
+        assert!(edit("false", "text", "txt").is_err());
+        assert!(edit("restless-no-such-editor", "text", "txt").is_err());
+        assert!(edit("sed -i 's/a/b", "text", "txt").is_err());
+        // Quoted arguments keep their spaces
+        let edited = edit(r#"sh -c 'echo "two words" > "$0"'"#, "text", "txt").unwrap();
+        assert_eq!(edited, "two words\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_uses_a_private_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // An "editor" that replaces the text with the file's path and mode
+        let script =
+            std::env::temp_dir().join(format!("restless-editor-test-{}.sh", std::process::id()));
+        std::fs::write(&script, "#!/bin/sh\necho \"$1\" > \"$1.seen\"\nls -l \"$1\" >> \"$1.seen\"\nmv \"$1.seen\" \"$1\"\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o700)).unwrap();
+        let command = script.display().to_string();
+
+        let first = edit(&command, "text", "txt").unwrap();
+        let second = edit(&command, "text", "txt").unwrap();
+        let (path, listing) = first.split_once('\n').unwrap();
+        assert!(listing.starts_with("-rw-------"));
+        assert!(path.contains("restless-body-") && path.ends_with(".txt"));
+        assert_ne!(first.lines().next(), second.lines().next());
+        assert!(!std::path::Path::new(path).exists());
+        std::fs::remove_file(&script).unwrap();
+    }
+}
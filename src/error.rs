@@ -35,6 +35,9 @@ pub enum RestlessError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[error("Storage error: {0}")]
+    Storage(#[from] rusqlite::Error),
+
     #[error("Terminal error: {0}")]
     Terminal(String),
 
@@ -46,6 +49,12 @@ pub enum RestlessError {
 
     #[error("Application state error: {message}")]
     AppState { message: String },
+
+    #[error("Authentication error: {message}")]
+    Authentication { message: String },
+
+    #[error("Clipboard error: {message}")]
+    Clipboard { message: String },
 }
 
 impl RestlessError {
@@ -102,6 +111,12 @@ impl RestlessError {
             message: message.into(),
         }
     }
+
+    pub fn clipboard<S: Into<String>>(message: S) -> Self {
+        Self::Clipboard {
+            message: message.into(),
+        }
+    }
 }
 
 // Conversion from anyhow::Error to RestlessError
@@ -132,6 +147,15 @@ pub enum RequestError {
 
     #[error("Connection failed: {message}")]
     Connection { message: String },
+
+    #[error("Authentication failed: {message}")]
+    Authentication { message: String },
+
+    #[error("TLS setup failed: {message}")]
+    Tls { message: String },
+
+    #[error("Couldn't decompress the body: {message}")]
+    Decompression { message: String },
 }
 
 impl RequestError {
@@ -159,6 +183,24 @@ impl RequestError {
             message: message.into(),
         }
     }
+
+    pub fn authentication<S: Into<String>>(message: S) -> Self {
+        Self::Authentication {
+            message: message.into(),
+        }
+    }
+
+    pub fn tls<S: Into<String>>(message: S) -> Self {
+        Self::Tls {
+            message: message.into(),
+        }
+    }
+
+    pub fn decompression<S: Into<String>>(message: S) -> Self {
+        Self::Decompression {
+            message: message.into(),
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -242,6 +284,9 @@ impl From<RequestError> for RestlessError {
             },
             RequestError::BodySerialization(msg) => RestlessError::ResponseParsing { message: msg },
             RequestError::Connection { message } => RestlessError::ResponseParsing { message },
+            RequestError::Authentication { message } => RestlessError::Authentication { message },
+            RequestError::Tls { message } => RestlessError::Configuration { message },
+            RequestError::Decompression { message } => RestlessError::ResponseParsing { message },
         }
     }
 }
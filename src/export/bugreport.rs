@@ -0,0 +1,235 @@
+//! Bug report bundle: one exchange as a Markdown file for an issue tracker
+//!
+//! The bundle holds everything needed to understand a single send without
+//! access to the machine it happened on: the reproduction annotation, the
+//! platform, the request, the response with its timings, the recent sends
+//! of the same request from the history and the end of the tracing log.
+//! Secret variables are already redacted in the request it's given;
+//! credential headers are redacted here, as are URLs and credential fields
+//! in the log lines, and long bodies are cut short.
+
+use super::{Annotation, REDACTED};
+use crate::logic::request::{build_url_with_params, Request};
+use crate::logic::response::Response;
+use crate::logic::{form, headers::is_credential, query};
+
+/// Longest body included, in bytes
+const BODY_LIMIT: usize = 16 * 1024;
+
+/// What a report adds to the exchange from elsewhere
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Context {
+    /// Recent sends of the request, one line each
+    pub sends: Vec<String>,
+    /// The last lines of the tracing log, `None` when logging is off
+    pub log: Option<Vec<String>>,
+}
+
+fn headers(lines: &mut Vec<String>, headers: &[(String, String)]) {
+    for (name, value) in headers {
+        let value = if is_credential(name) { REDACTED } else { value };
+        lines.push(format!("{}: {}", name, value));
+    }
+}
+
+/// A log line with the query values and passwords of its URLs and the
+/// values of credential fields like `token=…` redacted
+fn log_line(line: &str) -> String {
+    query::redact_urls(line)
+        .split(' ')
+        .map(|word| match word.split_once('=') {
+            Some((name, _)) if is_field(name) && is_credential(name) => {
+                format!("{}={}", name, REDACTED)
+            }
+            _ => word.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether `name` could be the name of a logged field, not part of a URL
+fn is_field(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+fn body(lines: &mut Vec<String>, body: &str) {
+    if body.len() <= BODY_LIMIT {
+        lines.push(body.to_string());
+        return;
+    }
+    let mut end = BODY_LIMIT;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    lines.push(body[..end].to_string());
+    lines.push(format!(
+        "… (cut short, {} of {} bytes shown)",
+        end,
+        body.len()
+    ));
+}
+
+/// Renders the bundle
+pub fn render(
+    request: &Request,
+    response: Option<&Response>,
+    annotation: &Annotation,
+    context: &Context,
+) -> String {
+    let mut lines = vec![
+        "# restless bug report".to_string(),
+        String::new(),
+        "```".to_string(),
+    ];
+    lines.extend(annotation.to_comment_lines("#"));
+    lines.push(format!(
+        "# platform: {} {}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    ));
+    lines.push("```".to_string());
+
+    let url = build_url_with_params(&request.url, &request.params)
+        .unwrap_or_else(|_| request.url.clone());
+    lines.extend([
+        String::new(),
+        "## Request".to_string(),
+        String::new(),
+        "```http".to_string(),
+        format!("{} {}", request.method, url),
+    ]);
+    headers(&mut lines, &request.headers);
+    if let Some(fields) = &request.form {
+        lines.push(String::new());
+        for (name, value) in fields {
+            match form::file_path(value) {
+                Some(path) => lines.push(format!("{} = file {}", name, path)),
+                None => lines.push(format!("{} = {}", name, value)),
+            }
+        }
+    } else if let Some(request_body) = &request.body {
+        lines.push(String::new());
+        body(&mut lines, request_body);
+    }
+    lines.push("```".to_string());
+    lines.push(format!("Auth: {}", request.auth.kind.label()));
+
+    lines.extend([String::new(), "## Response".to_string(), String::new()]);
+    match response {
+        Some(response) => {
+            lines.push("```http".to_string());
+            lines.push(format!(
+                "{} {}",
+                response.version.as_deref().unwrap_or("HTTP/1.1"),
+                response.status_code
+            ));
+            headers(&mut lines, &response.headers);
+            lines.push(String::new());
+            match &response.bytes {
+                Some(bytes) => lines.push(format!("(binary body, {} bytes)", bytes.len())),
+                None => body(&mut lines, &response.body),
+            }
+            lines.push("```".to_string());
+            if let Some(metrics) = &response.metrics {
+                lines.push(format!("Timing: {}", metrics.summary()));
+            }
+            if let Some(transfer) = &response.transfer {
+                lines.push(format!("Size: {}", transfer.summary()));
+            }
+        }
+        None => lines.push("(not sent yet)".to_string()),
+    }
+
+    if !context.sends.is_empty() {
+        lines.extend([
+            String::new(),
+            "## Recent sends".to_string(),
+            String::new(),
+            "```".to_string(),
+        ]);
+        lines.extend(context.sends.iter().cloned());
+        lines.push("```".to_string());
+    }
+
+    lines.extend([String::new(), "## Log".to_string(), String::new()]);
+    match &context.log {
+        Some(log) => {
+            lines.push("```".to_string());
+            lines.extend(log.iter().map(|line| log_line(line)));
+            lines.push("```".to_string());
+        }
+        None => lines
+            .push("(logging was off; run with `--log-file <path>` to include the log)".to_string()),
+    }
+
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::fixed_time;
+
+    #[test]
+    fn test_render_bug_report() {
+        let request = Request {
+            url: "https://api.test/users".to_string(),
+            method: reqwest::Method::POST,
+            headers: vec![
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Authorization".to_string(), "Bearer abc".to_string()),
+            ],
+            body: Some("{\"name\": \"a\"}".to_string()),
+            params: vec![("dry".to_string(), "1".to_string())],
+            ..Request::default()
+        };
+        let mut response = Response::new_unchecked(
+            500,
+            "Set-Cookie: session=xyz\nContent-Type: text/plain".to_string(),
+            "x".repeat(BODY_LIMIT + 10),
+        );
+        response.version = Some("HTTP/2".to_string());
+        let annotation = Annotation::new(None, &[], fixed_time());
+        let context = Context {
+            sends: vec![
+                "2024-05-01 12:29:00  POST    500    41ms  https://api.test/users".to_string(),
+            ],
+            log: Some(vec![
+                "2024-05-01T12:29:00Z  WARN restless::logic::request: send failed".to_string(),
+                "2024-05-01T12:29:01Z  INFO restless: popup message=Request failed \
+                 (https://api.test/users?api_key=k3y) access_token=t0k"
+                    .to_string(),
+            ]),
+        };
+
+        let report = render(&request, Some(&response), &annotation, &context);
+        assert!(report.starts_with("# restless bug report\n"));
+        assert!(report.contains("# restless-version: "));
+        assert!(report.contains("POST https://api.test/users?dry=1\n"));
+        assert!(report.contains("Authorization: <redacted>\n"));
+        assert!(!report.contains("Bearer abc"));
+        assert!(report.contains("Content-Type: application/json\n"));
+        assert!(report.contains("HTTP/2 500\n"));
+        assert!(report.contains("Set-Cookie: <redacted>\n"));
+        assert!(!report.contains("xyz"));
+        assert!(report.contains(&format!(
+            "(cut short, {} of {} bytes shown)",
+            BODY_LIMIT,
+            BODY_LIMIT + 10
+        )));
+        assert!(report.contains("## Recent sends"));
+        assert!(report.contains(&context.sends[0]));
+        assert!(report.contains("## Log\n\n```\n2024-05-01T12:29:00Z  WARN"));
+        assert!(report.contains("(https://api.test/users?api_key=<redacted>)"));
+        assert!(report.contains(" access_token=<redacted>\n"));
+        assert!(!report.contains("k3y") && !report.contains("t0k"));
+
+        let unsent = render(&request, None, &annotation, &Context::default());
+        assert!(unsent.contains("(not sent yet)"));
+        assert!(!unsent.contains("## Recent sends"));
+        assert!(unsent.contains("(logging was off;"));
+    }
+}
@@ -0,0 +1,166 @@
+//! JavaScript code export: the request sent with `fetch`
+//!
+//! The snippet uses top-level `await`, so it runs as an ES module in Node 18
+//! or later and in a browser console. Only a file upload needs Node, to read
+//! the file.
+
+use super::{header_comments, skipped_options};
+use crate::export::Annotation;
+use crate::logic::form;
+use crate::logic::request::{build_url_with_params, Request};
+
+/// Options `fetch` carries over; TLS and proxy settings are the runtime's
+const SUPPORTED_OPTIONS: &[&str] = &["timeout"];
+
+/// Quotes a value as a JavaScript string literal; JSON string syntax is
+/// valid JavaScript
+fn string_literal(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// Renders the request as an annotated ES module
+pub fn render(request: &Request, annotation: &Annotation) -> String {
+    let mut lines = header_comments(request, annotation, "//");
+    lines.extend(skipped_options(request, SUPPORTED_OPTIONS, "//"));
+
+    if let Some(fields) = &request.form {
+        if fields
+            .iter()
+            .any(|(_, value)| form::file_path(value).is_some())
+        {
+            lines.push("import { readFile } from \"node:fs/promises\";".to_string());
+        }
+        lines.push(String::new());
+        lines.push("const form = new FormData();".to_string());
+        for (name, value) in fields {
+            lines.push(match form::file_path(value) {
+                Some(path) => {
+                    let file_name = std::path::Path::new(path)
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    format!(
+                        "form.append({}, new Blob([await readFile({})]), {});",
+                        string_literal(name),
+                        string_literal(path),
+                        string_literal(&file_name)
+                    )
+                }
+                None => format!(
+                    "form.append({}, {});",
+                    string_literal(name),
+                    string_literal(value)
+                ),
+            });
+        }
+    }
+
+    let url = build_url_with_params(&request.url, &request.params)
+        .unwrap_or_else(|_| request.url.clone());
+    lines.push(String::new());
+    lines.push(format!(
+        "const response = await fetch({}, {{",
+        string_literal(&url)
+    ));
+    lines.push(format!(
+        "  method: {},",
+        string_literal(request.method.as_str())
+    ));
+    let headers: Vec<&(String, String)> = request
+        .headers
+        .iter()
+        // A form brings its own Content-Type with the boundary
+        .filter(|(key, _)| request.form.is_none() || !key.eq_ignore_ascii_case("content-type"))
+        .collect();
+    if !headers.is_empty() {
+        lines.push("  headers: {".to_string());
+        for (key, value) in headers {
+            lines.push(format!(
+                "    {}: {},",
+                string_literal(key),
+                string_literal(value)
+            ));
+        }
+        lines.push("  },".to_string());
+    }
+    if request.form.is_some() {
+        lines.push("  body: form,".to_string());
+    } else if let Some(body) = &request.body {
+        lines.push(format!("  body: {},", string_literal(body)));
+    }
+    if let Some(seconds) = request
+        .options
+        .timeout
+        .as_ref()
+        .and_then(|t| t.parse::<f64>().ok())
+    {
+        lines.push(format!(
+            "  signal: AbortSignal.timeout({}),",
+            (seconds * 1000.0).round()
+        ));
+    }
+    lines.push("});".to_string());
+    lines.push(String::new());
+    lines.push("console.log(response.status);".to_string());
+    lines.push("console.log(await response.text());".to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::fixed_time;
+    use reqwest::Method;
+
+    fn annotation() -> Annotation {
+        Annotation::new(None, &[], fixed_time())
+    }
+
+    #[test]
+    fn test_render() {
+        let mut request = Request {
+            url: "https://api.test/users".to_string(),
+            method: Method::POST,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            params: vec![("q".to_string(), "a b".to_string())],
+            body: Some("{\"name\": \"Ann\"}".to_string()),
+            ..Default::default()
+        };
+        request.options.timeout = Some("2.5".to_string());
+        request.options.insecure = Some("true".to_string());
+        let code = render(&request, &annotation());
+
+        assert!(code.starts_with("// --- restless reproduction ---"));
+        assert!(code.contains("// option insecure is not carried over\n"));
+        assert!(code.contains(
+            "const response = await fetch(\"https://api.test/users?q=a%20b\", {\n  \
+             method: \"POST\",\n  headers: {\n    \"Content-Type\": \"application/json\",\n  },\n  \
+             body: \"{\\\"name\\\": \\\"Ann\\\"}\",\n  \
+             signal: AbortSignal.timeout(2500),\n});"
+        ));
+        assert!(!code.contains("FormData"));
+        assert!(code.ends_with("console.log(await response.text());"));
+    }
+
+    #[test]
+    fn test_render_form() {
+        let request = Request {
+            url: "http://localhost:8080/upload".to_string(),
+            method: Method::PUT,
+            headers: vec![("content-type".to_string(), "text/plain".to_string())],
+            form: Some(vec![
+                ("note".to_string(), "hello".to_string()),
+                ("avatar".to_string(), "@photos/me.png".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let code = render(&request, &annotation());
+
+        assert!(code.contains("import { readFile } from \"node:fs/promises\";\n"));
+        assert!(code.contains(
+            "const form = new FormData();\nform.append(\"note\", \"hello\");\n\
+             form.append(\"avatar\", new Blob([await readFile(\"photos/me.png\")]), \"me.png\");"
+        ));
+        assert!(code.contains("  method: \"PUT\",\n  body: form,\n});"));
+    }
+}
@@ -0,0 +1,106 @@
+//! Requests exported as source code
+//!
+//! Every language has a generator module rendering the request as a snippet
+//! that sends it. [`Language::ALL`] is the registry the export popup offers,
+//! so adding a language takes a variant, its label and extension, and a
+//! module with a `render` function.
+
+pub mod javascript;
+pub mod python;
+pub mod rust;
+
+use super::Annotation;
+use crate::logic::auth::AuthKind;
+use crate::logic::request::Request;
+
+/// Languages requests can be exported to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    Rust,
+    Python,
+    JavaScript,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::Rust, Language::Python, Language::JavaScript];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::Rust => "Rust (reqwest)",
+            Language::Python => "Python (requests)",
+            Language::JavaScript => "JavaScript (fetch)",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Language::Rust => "rs",
+            Language::Python => "py",
+            Language::JavaScript => "js",
+        }
+    }
+
+    /// Renders the request as a snippet in this language
+    pub fn render(&self, request: &Request, annotation: &Annotation) -> String {
+        match self {
+            Language::Rust => rust::render(request, annotation),
+            Language::Python => python::render(request, annotation),
+            Language::JavaScript => javascript::render(request, annotation),
+        }
+    }
+}
+
+/// Comment lines every snippet starts with: the annotation and, when the
+/// request authenticates, a note that the credentials are left out
+fn header_comments(request: &Request, annotation: &Annotation, prefix: &str) -> Vec<String> {
+    let mut lines = annotation.to_comment_lines(prefix);
+    if request.auth.kind != AuthKind::None {
+        lines.push(format!(
+            "{} auth: {} (credentials are resolved at send time and not included)",
+            prefix,
+            request.auth.kind.label()
+        ));
+    }
+    lines
+}
+
+/// Comment lines naming the request's options outside of `supported`, which
+/// the snippet can't carry over
+fn skipped_options(request: &Request, supported: &[&str], prefix: &str) -> Vec<String> {
+    request
+        .options
+        .entries()
+        .into_iter()
+        .filter(|(key, _)| !supported.contains(key))
+        .map(|(key, _)| format!("{} option {} is not carried over", prefix, key))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::fixed_time;
+    use crate::logic::auth::Auth;
+
+    #[test]
+    fn test_header_comments() {
+        let mut request = Request::default();
+        request.options.proxy = Some("http://proxy:3128".to_string());
+        request.options.proxy_auth = Some("user:secret".to_string());
+        let annotation = Annotation::new(None, &[], fixed_time());
+
+        let lines = header_comments(&request, &annotation, "#");
+        assert_eq!(lines, annotation.to_comment_lines("#"));
+        assert_eq!(
+            skipped_options(&request, &["proxy"], "//"),
+            vec!["// option proxy_auth is not carried over"]
+        );
+
+        request.auth = Auth {
+            kind: AuthKind::ApiKey,
+            ..Default::default()
+        };
+        let lines = header_comments(&request, &annotation, "#");
+        assert!(lines.last().unwrap().starts_with("# auth: "));
+    }
+}
@@ -0,0 +1,209 @@
+//! Python code export: the request sent with `requests`
+
+use super::{header_comments, skipped_options};
+use crate::export::Annotation;
+use crate::logic::form;
+use crate::logic::request::Request;
+use reqwest::Method;
+
+/// Options the `requests` call carries over
+const SUPPORTED_OPTIONS: &[&str] = &[
+    "proxy",
+    "insecure",
+    "ca_cert",
+    "client_cert",
+    "client_key",
+    "timeout",
+];
+
+/// Quotes a value as a Python string literal; JSON string syntax is valid
+/// Python
+fn string_literal(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// Renders the request as an annotated script to run with Python 3
+pub fn render(request: &Request, annotation: &Annotation) -> String {
+    let mut lines = header_comments(request, annotation, "#");
+    lines.push("# pip install requests".to_string());
+    lines.extend(skipped_options(request, SUPPORTED_OPTIONS, "#"));
+    lines.push("import requests".to_string());
+    lines.push(String::new());
+
+    let url = string_literal(&request.url);
+    lines.push(match request.method {
+        Method::GET
+        | Method::POST
+        | Method::PUT
+        | Method::PATCH
+        | Method::DELETE
+        | Method::HEAD
+        | Method::OPTIONS => format!(
+            "response = requests.{}({},",
+            request.method.as_str().to_lowercase(),
+            url
+        ),
+        _ => format!(
+            "response = requests.request({}, {},",
+            string_literal(request.method.as_str()),
+            url
+        ),
+    });
+    lines.extend(arguments(request).iter().map(|arg| format!("    {},", arg)));
+    lines.push(")".to_string());
+    lines.push(String::new());
+    lines.push("print(response.status_code)".to_string());
+    lines.push("print(response.text)".to_string());
+    lines.join("\n")
+}
+
+/// The keyword arguments after the URL
+fn arguments(request: &Request) -> Vec<String> {
+    let mut args = Vec::new();
+    if !request.params.is_empty() {
+        let params: Vec<String> = request
+            .params
+            .iter()
+            .map(|(key, value)| format!("({}, {})", string_literal(key), string_literal(value)))
+            .collect();
+        args.push(format!("params=[{}]", params.join(", ")));
+    }
+    let headers: Vec<String> = request
+        .headers
+        .iter()
+        // A form brings its own Content-Type with the boundary
+        .filter(|(key, _)| request.form.is_none() || !key.eq_ignore_ascii_case("content-type"))
+        .map(|(key, value)| format!("{}: {}", string_literal(key), string_literal(value)))
+        .collect();
+    if !headers.is_empty() {
+        args.push(format!("headers={{{}}}", headers.join(", ")));
+    }
+    if let Some(fields) = &request.form {
+        let files: Vec<String> = fields
+            .iter()
+            .map(|(name, value)| match form::file_path(value) {
+                Some(path) => {
+                    let file_name = std::path::Path::new(path)
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    format!(
+                        "({}, ({}, open({}, \"rb\")))",
+                        string_literal(name),
+                        string_literal(&file_name),
+                        string_literal(path)
+                    )
+                }
+                None => format!(
+                    "({}, (None, {}))",
+                    string_literal(name),
+                    string_literal(value)
+                ),
+            })
+            .collect();
+        args.push(format!("files=[{}]", files.join(", ")));
+    } else if let Some(body) = &request.body {
+        // A str body is sent as Latin-1, so anything beyond ASCII goes as UTF-8 bytes
+        let encode = if body.is_ascii() { "" } else { ".encode()" };
+        args.push(format!("data={}{}", string_literal(body), encode));
+    }
+
+    let options = &request.options;
+    match options.proxy.as_deref() {
+        Some("none") => args.push("proxies={\"http\": None, \"https\": None}".to_string()),
+        Some(proxy) => {
+            let proxy = string_literal(proxy);
+            args.push(format!(
+                "proxies={{\"http\": {}, \"https\": {}}}",
+                proxy, proxy
+            ));
+        }
+        None => {}
+    }
+    if options.is_insecure() {
+        args.push("verify=False".to_string());
+    } else if let Some(ca_cert) = &options.ca_cert {
+        args.push(format!("verify={}", string_literal(ca_cert)));
+    }
+    match (&options.client_cert, &options.client_key) {
+        (Some(cert), Some(key)) => args.push(format!(
+            "cert=({}, {})",
+            string_literal(cert),
+            string_literal(key)
+        )),
+        (Some(cert), None) => args.push(format!("cert={}", string_literal(cert))),
+        _ => {}
+    }
+    if let Some(seconds) = options.timeout.as_ref().and_then(|t| t.parse::<f64>().ok()) {
+        args.push(format!("timeout={:?}", seconds));
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::fixed_time;
+
+    fn annotation() -> Annotation {
+        Annotation::new(None, &[], fixed_time())
+    }
+
+    #[test]
+    fn test_render() {
+        let mut request = Request {
+            url: "https://api.test/users".to_string(),
+            method: Method::POST,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            params: vec![
+                ("tag".to_string(), "a".to_string()),
+                ("tag".to_string(), "b".to_string()),
+            ],
+            body: Some("{\"name\": \"Zoë\"}".to_string()),
+            ..Default::default()
+        };
+        request.options.timeout = Some("30".to_string());
+        request.options.http_version = Some("2".to_string());
+        let code = render(&request, &annotation());
+
+        assert!(code.starts_with("# --- restless reproduction ---"));
+        assert!(code.contains("# option http_version is not carried over\nimport requests\n"));
+        assert!(code.contains(
+            "response = requests.post(\"https://api.test/users\",\n    \
+             params=[(\"tag\", \"a\"), (\"tag\", \"b\")],\n    \
+             headers={\"Content-Type\": \"application/json\"},\n    \
+             data=\"{\\\"name\\\": \\\"Zoë\\\"}\".encode(),\n    \
+             timeout=30.0,\n)"
+        ));
+        assert!(code.ends_with("print(response.status_code)\nprint(response.text)"));
+    }
+
+    #[test]
+    fn test_render_form_and_options() {
+        let mut request = Request {
+            url: "http://localhost:8080/upload".to_string(),
+            method: Method::from_bytes(b"PROPFIND").unwrap(),
+            headers: vec![("Content-Type".to_string(), "text/plain".to_string())],
+            form: Some(vec![
+                ("note".to_string(), "hello".to_string()),
+                ("avatar".to_string(), "@photos/me.png".to_string()),
+            ]),
+            ..Default::default()
+        };
+        request.options.proxy = Some("http://proxy:3128".to_string());
+        request.options.insecure = Some("true".to_string());
+        request.options.client_cert = Some("client.pem".to_string());
+        request.options.client_key = Some("client.key".to_string());
+        let code = render(&request, &annotation());
+
+        assert!(code.contains(
+            "response = requests.request(\"PROPFIND\", \"http://localhost:8080/upload\",\n    \
+             files=[(\"note\", (None, \"hello\")), \
+             (\"avatar\", (\"me.png\", open(\"photos/me.png\", \"rb\")))],\n    \
+             proxies={\"http\": \"http://proxy:3128\", \"https\": \"http://proxy:3128\"},\n    \
+             verify=False,\n    cert=(\"client.pem\", \"client.key\"),\n)"
+        ));
+        assert!(!code.contains("text/plain"));
+        assert!(!code.contains("not carried over"));
+    }
+}
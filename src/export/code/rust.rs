@@ -0,0 +1,284 @@
+//! Rust code export: the request as an async `reqwest` program
+
+use super::{header_comments, skipped_options};
+use crate::export::Annotation;
+use crate::logic::form;
+use crate::logic::options::HttpVersion;
+use crate::logic::request::Request;
+use reqwest::Method;
+
+/// Options the client builder carries over
+const SUPPORTED_OPTIONS: &[&str] = &[
+    "proxy",
+    "insecure",
+    "http_version",
+    "min_tls_version",
+    "max_tls_version",
+    "timeout",
+    "ca_cert",
+    "client_cert",
+];
+
+/// Quotes a value as a Rust string literal, raw when it holds quotes,
+/// backslashes or line breaks so bodies stay readable
+pub fn string_literal(value: &str) -> String {
+    if !value.contains(['"', '\\', '\n']) {
+        return format!("{:?}", value);
+    }
+    let mut hashes = 1;
+    while value.contains(&format!("\"{}", "#".repeat(hashes))) {
+        hashes += 1;
+    }
+    let hashes = "#".repeat(hashes);
+    format!("r{}\"{}\"{}", hashes, value, hashes)
+}
+
+/// Renders the request as an annotated `main` to paste into a Rust project
+pub fn render(request: &Request, annotation: &Annotation) -> String {
+    let mut lines = header_comments(request, annotation, "//");
+    let reqwest = if request.form.is_some() {
+        "{ version = \"0.12\", features = [\"multipart\"] }"
+    } else {
+        "\"0.12\""
+    };
+    lines.push(format!(
+        "// [dependencies] reqwest = {}, tokio = {{ version = \"1\", features = [\"full\"] }}",
+        reqwest
+    ));
+    lines.extend(skipped_options(request, SUPPORTED_OPTIONS, "//"));
+    lines.push(String::new());
+    lines.push("#[tokio::main]".to_string());
+    lines.push("async fn main() -> Result<(), Box<dyn std::error::Error>> {".to_string());
+
+    let client = client_options(request);
+    if client.is_empty() {
+        lines.push("    let client = reqwest::Client::new();".to_string());
+    } else {
+        lines.push("    let client = reqwest::Client::builder()".to_string());
+        lines.extend(client.iter().map(|option| format!("        .{}", option)));
+        lines.push("        .build()?;".to_string());
+    }
+
+    if let Some(fields) = &request.form {
+        lines.push("    let form = reqwest::multipart::Form::new()".to_string());
+        for (name, value) in fields {
+            let field = match form::file_path(value) {
+                Some(path) => {
+                    let file_name = std::path::Path::new(path)
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    format!(
+                        ".part({}, reqwest::multipart::Part::bytes(std::fs::read({})?).file_name({}))",
+                        string_literal(name),
+                        string_literal(path),
+                        string_literal(&file_name)
+                    )
+                }
+                None => format!(".text({}, {})", string_literal(name), string_literal(value)),
+            };
+            lines.push(format!("        {}", field));
+        }
+        if let Some(last) = lines.last_mut() {
+            last.push(';');
+        }
+    }
+
+    lines.push("    let response = client".to_string());
+    lines.push(format!(
+        "        .{}",
+        method_call(&request.method, &string_literal(&request.url))
+    ));
+    if !request.params.is_empty() {
+        let params: Vec<String> = request
+            .params
+            .iter()
+            .map(|(key, value)| format!("({}, {})", string_literal(key), string_literal(value)))
+            .collect();
+        lines.push(format!("        .query(&[{}])", params.join(", ")));
+    }
+    for (key, value) in &request.headers {
+        // A form brings its own Content-Type with the boundary
+        if request.form.is_some() && key.eq_ignore_ascii_case("content-type") {
+            continue;
+        }
+        lines.push(format!(
+            "        .header({}, {})",
+            string_literal(key),
+            string_literal(value)
+        ));
+    }
+    if request.form.is_some() {
+        lines.push("        .multipart(form)".to_string());
+    } else if let Some(body) = &request.body {
+        lines.push(format!("        .body({})", string_literal(body)));
+    }
+    lines.push("        .send()".to_string());
+    lines.push("        .await?;".to_string());
+    lines.push(String::new());
+    lines.push("    println!(\"{}\", response.status());".to_string());
+    lines.push("    println!(\"{}\", response.text().await?);".to_string());
+    lines.push("    Ok(())".to_string());
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+/// `get(url)` for the methods with a shorthand, `request(..)` for the rest
+fn method_call(method: &Method, url: &str) -> String {
+    let shorthand = match *method {
+        Method::GET => "get",
+        Method::POST => "post",
+        Method::PUT => "put",
+        Method::PATCH => "patch",
+        Method::DELETE => "delete",
+        Method::HEAD => "head",
+        _ => {
+            let method = match *method {
+                Method::OPTIONS => "reqwest::Method::OPTIONS".to_string(),
+                Method::TRACE => "reqwest::Method::TRACE".to_string(),
+                Method::CONNECT => "reqwest::Method::CONNECT".to_string(),
+                _ => format!(
+                    "reqwest::Method::from_bytes(b{})?",
+                    string_literal(method.as_str())
+                ),
+            };
+            return format!("request({}, {})", method, url);
+        }
+    };
+    format!("{}({})", shorthand, url)
+}
+
+/// The `ClientBuilder` calls the request's options need
+fn client_options(request: &Request) -> Vec<String> {
+    let options = &request.options;
+    let mut calls = Vec::new();
+    match options.proxy.as_deref() {
+        Some("none") => calls.push("no_proxy()".to_string()),
+        Some(proxy) => calls.push(format!(
+            "proxy(reqwest::Proxy::all({})?)",
+            string_literal(proxy)
+        )),
+        None => {}
+    }
+    if options.is_insecure() {
+        calls.push("danger_accept_invalid_certs(true)".to_string());
+    }
+    match options.http_version() {
+        Some(HttpVersion::Http1) => calls.push("http1_only()".to_string()),
+        Some(HttpVersion::Http2) => calls.push("http2_prior_knowledge()".to_string()),
+        None => {}
+    }
+    let tls_version =
+        |version: &str| format!("reqwest::tls::Version::TLS_{}", version.replace('.', "_"));
+    if let Some(version) = &options.min_tls_version {
+        calls.push(format!("min_tls_version({})", tls_version(version)));
+    }
+    if let Some(version) = &options.max_tls_version {
+        calls.push(format!("max_tls_version({})", tls_version(version)));
+    }
+    if let Some(seconds) = options.timeout.as_ref().and_then(|t| t.parse::<f64>().ok()) {
+        calls.push(format!(
+            "timeout(std::time::Duration::from_secs_f64({:?}))",
+            seconds
+        ));
+    }
+    if let Some(ca_cert) = &options.ca_cert {
+        calls.push(format!(
+            "add_root_certificate(reqwest::Certificate::from_pem(&std::fs::read({})?)?)",
+            string_literal(ca_cert)
+        ));
+    }
+    if let Some(client_cert) = &options.client_cert {
+        // The certificate password is a credential and is left out
+        calls.push(format!(
+            "identity(reqwest::Identity::from_pem(&std::fs::read({})?)?)",
+            string_literal(client_cert)
+        ));
+    }
+    calls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::fixed_time;
+    use crate::logic::auth::{Auth, AuthKind};
+
+    fn annotation() -> Annotation {
+        Annotation::new(None, &[], fixed_time())
+    }
+
+    #[test]
+    fn test_string_literal() {
+        assert_eq!(string_literal("http://api/users"), "\"http://api/users\"");
+        assert_eq!(string_literal("tab\there"), "\"tab\\there\"");
+        assert_eq!(
+            string_literal("{\"name\": \"Ann\"}"),
+            "r#\"{\"name\": \"Ann\"}\"#"
+        );
+        assert_eq!(string_literal("a\"#b"), "r##\"a\"#b\"##");
+    }
+
+    #[test]
+    fn test_render() {
+        let request = Request {
+            url: "https://api.test/users".to_string(),
+            method: Method::POST,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            params: vec![("notify".to_string(), "true".to_string())],
+            body: Some("{\"name\": \"Ann\"}".to_string()),
+            ..Default::default()
+        };
+        let code = render(&request, &annotation());
+
+        assert!(code.starts_with("// --- restless reproduction ---"));
+        assert!(code.contains("    let client = reqwest::Client::new();\n"));
+        assert!(code.contains(
+            "    let response = client\n        .post(\"https://api.test/users\")\n        \
+             .query(&[(\"notify\", \"true\")])\n        \
+             .header(\"Content-Type\", \"application/json\")\n        \
+             .body(r#\"{\"name\": \"Ann\"}\"#)\n        .send()\n        .await?;"
+        ));
+        assert!(code.ends_with("    Ok(())\n}"));
+        assert!(!code.contains("// auth:"));
+    }
+
+    #[test]
+    fn test_render_form_and_options() {
+        let mut request = Request {
+            url: "http://localhost:8080/upload".to_string(),
+            method: Method::from_bytes(b"PROPFIND").unwrap(),
+            headers: vec![("Content-Type".to_string(), "text/plain".to_string())],
+            form: Some(vec![
+                ("note".to_string(), "hello".to_string()),
+                ("avatar".to_string(), "@~/me.png".to_string()),
+            ]),
+            auth: Auth {
+                kind: AuthKind::ApiKey,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        request.options.insecure = Some("true".to_string());
+        request.options.timeout = Some("30".to_string());
+        request.options.min_tls_version = Some("1.2".to_string());
+        request.options.raw_wire = Some("true".to_string());
+        let code = render(&request, &annotation());
+
+        assert!(code.contains("features = [\"multipart\"]"));
+        assert!(code.contains("// auth: "));
+        assert!(code.contains("// option raw_wire is not carried over\n"));
+        assert!(code.contains(
+            "        .danger_accept_invalid_certs(true)\n        \
+             .min_tls_version(reqwest::tls::Version::TLS_1_2)\n        \
+             .timeout(std::time::Duration::from_secs_f64(30.0))\n        .build()?;"
+        ));
+        assert!(code.contains(
+            "        .text(\"note\", \"hello\")\n        .part(\"avatar\", \
+             reqwest::multipart::Part::bytes(std::fs::read(\"~/me.png\")?).file_name(\"me.png\"));"
+        ));
+        assert!(code.contains(".request(reqwest::Method::from_bytes(b\"PROPFIND\")?, "));
+        assert!(code.contains("        .multipart(form)\n"));
+        assert!(!code.contains("text/plain"));
+    }
+}
@@ -0,0 +1,114 @@
+//! Clipboard-ready shapes of a response
+//!
+//! Each downstream use wants something different: curl flags to replay
+//! headers, a JSON object for fixtures, a minified body for payloads or an
+//! escaped string literal for source code.
+
+use super::curl::shell_quote;
+use crate::logic::response::Response;
+use serde_json::{Map, Value};
+
+/// Ways a response can be copied
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CopyFormat {
+    HeadersCurl,
+    HeadersJson,
+    BodyMinified,
+    BodyStringLiteral,
+}
+
+impl CopyFormat {
+    pub const ALL: [CopyFormat; 4] = [
+        CopyFormat::HeadersCurl,
+        CopyFormat::HeadersJson,
+        CopyFormat::BodyMinified,
+        CopyFormat::BodyStringLiteral,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CopyFormat::HeadersCurl => "Headers as curl -H flags",
+            CopyFormat::HeadersJson => "Headers as JSON object",
+            CopyFormat::BodyMinified => "Body minified",
+            CopyFormat::BodyStringLiteral => "Body as escaped string literal",
+        }
+    }
+
+    pub fn render(&self, response: &Response) -> String {
+        match self {
+            CopyFormat::HeadersCurl => response
+                .headers
+                .iter()
+                .map(|(key, value)| format!("-H {}", shell_quote(&format!("{}: {}", key, value))))
+                .collect::<Vec<_>>()
+                .join(" \\\n"),
+            CopyFormat::HeadersJson => {
+                let mut object = Map::new();
+                for (key, value) in &response.headers {
+                    // Repeated headers are combined the way HTTP allows
+                    let combined = match object.get(key).and_then(Value::as_str) {
+                        Some(existing) => format!("{}, {}", existing, value),
+                        None => value.clone(),
+                    };
+                    object.insert(key.clone(), Value::String(combined));
+                }
+                serde_json::to_string_pretty(&Value::Object(object)).unwrap_or_default()
+            }
+            CopyFormat::BodyMinified => minify(&response.body),
+            CopyFormat::BodyStringLiteral => {
+                serde_json::to_string(&minify(&response.body)).unwrap_or_default()
+            }
+        }
+    }
+}
+
+/// Minifies JSON bodies; anything else is only trimmed
+fn minify(body: &str) -> String {
+    match serde_json::from_str::<Value>(body.trim()) {
+        Ok(value) => value.to_string(),
+        Err(_) => body.trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response() -> Response {
+        Response::new_unchecked(
+            200,
+            "Content-Type: application/json\nSet-Cookie: a=1\nSet-Cookie: b=it's".to_string(),
+            "{\"name\": \"Ada\", \"quote\": \"say \\\"hi\\\"\"}".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_headers_formats() {
+        let response = response();
+        assert_eq!(
+            CopyFormat::HeadersCurl.render(&response),
+            "-H 'Content-Type: application/json' \\\n-H 'Set-Cookie: a=1' \\\n-H 'Set-Cookie: b=it'\\''s'"
+        );
+
+        let json: Value = serde_json::from_str(&CopyFormat::HeadersJson.render(&response)).unwrap();
+        assert_eq!(json["Content-Type"], "application/json");
+        assert_eq!(json["Set-Cookie"], "a=1, b=it's");
+    }
+
+    #[test]
+    fn test_body_formats() {
+        let response = response();
+        assert_eq!(
+            CopyFormat::BodyMinified.render(&response),
+            r#"{"name":"Ada","quote":"say \"hi\""}"#
+        );
+        assert_eq!(
+            CopyFormat::BodyStringLiteral.render(&response),
+            r#""{\"name\":\"Ada\",\"quote\":\"say \\\"hi\\\"\"}""#
+        );
+
+        let text = Response::new_unchecked(200, String::new(), "  plain\n".to_string());
+        assert_eq!(CopyFormat::BodyMinified.render(&text), "plain");
+        assert_eq!(CopyFormat::BodyStringLiteral.render(&text), "\"plain\"");
+    }
+}
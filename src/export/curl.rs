@@ -0,0 +1,154 @@
+//! curl command export
+
+use super::Annotation;
+use crate::logic::auth::AuthKind;
+use crate::logic::options::HttpVersion;
+use crate::logic::request::{build_url_with_params, Request};
+
+/// Quotes a value for POSIX shells
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Renders the request as an annotated curl command
+pub fn render(request: &Request, annotation: &Annotation) -> String {
+    let mut lines = annotation.to_comment_lines("#");
+
+    if request.auth.kind != AuthKind::None {
+        lines.push(format!(
+            "# auth: {} (credentials are resolved at send time and not included)",
+            request.auth.kind.label()
+        ));
+    }
+
+    let url = build_url_with_params(&request.url, &request.params)
+        .unwrap_or_else(|_| request.url.clone());
+
+    let mut parts = vec![format!("curl -X {} {}", request.method, shell_quote(&url))];
+    for (key, value) in &request.headers {
+        parts.push(format!(
+            "-H {}",
+            shell_quote(&format!("{}: {}", key, value))
+        ));
+    }
+    if let Some(fields) = &request.form {
+        for (name, value) in fields {
+            parts.push(format!(
+                "-F {}",
+                shell_quote(&format!("{}={}", name, value))
+            ));
+        }
+    } else if let Some(body) = &request.body {
+        parts.push(format!("--data-raw {}", shell_quote(body)));
+    }
+    match request.options.proxy.as_deref() {
+        Some("none") => parts.push("--noproxy '*'".to_string()),
+        Some(proxy) => parts.push(format!("--proxy {}", shell_quote(proxy))),
+        None => {}
+    }
+    if request.options.is_insecure() {
+        parts.push("--insecure".to_string());
+    }
+    match request.options.http_version() {
+        Some(HttpVersion::Http1) => parts.push("--http1.1".to_string()),
+        Some(HttpVersion::Http2) => parts.push("--http2-prior-knowledge".to_string()),
+        None => {}
+    }
+    if let Some(version) = &request.options.min_tls_version {
+        parts.push(format!("--tlsv{}", version));
+    }
+    if let Some(version) = &request.options.max_tls_version {
+        parts.push(format!("--tls-max {}", version));
+    }
+    if let Some(timeout) = &request.options.timeout {
+        parts.push(format!("--max-time {}", timeout));
+    }
+    if let Some(ca_cert) = &request.options.ca_cert {
+        parts.push(format!("--cacert {}", shell_quote(ca_cert)));
+    }
+    if let Some(client_cert) = &request.options.client_cert {
+        // The certificate password is a credential and is left out
+        parts.push(format!("--cert {}", shell_quote(client_cert)));
+        if let Some(client_key) = &request.options.client_key {
+            parts.push(format!("--key {}", shell_quote(client_key)));
+        }
+    }
+
+    lines.push(parts.join(" \\\n  "));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::fixed_time;
+    use reqwest::Method;
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("plain"), "'plain'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn test_render_curl() {
+        let request = Request {
+            url: "https://api.example.com/users".to_string(),
+            method: Method::POST,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            params: vec![("page".to_string(), "2".to_string())],
+            body: Some("{\"name\": \"o'neil\"}".to_string()),
+            ..Default::default()
+        };
+        let annotation = Annotation::new(None, &[], fixed_time());
+
+        let output = render(&request, &annotation);
+        assert!(output.starts_with("# --- restless reproduction ---"));
+        assert!(output.contains(
+            "curl -X POST 'https://api.example.com/users?page=2' \\\n  -H 'Content-Type: application/json'"
+        ));
+        assert!(output.contains(r#"--data-raw '{"name": "o'\''neil"}'"#));
+        assert!(!output.contains("# auth:"));
+        assert!(!output.contains("--proxy"));
+    }
+
+    #[test]
+    fn test_render_curl_form() {
+        let request = Request {
+            url: "https://api.example.com/upload".to_string(),
+            method: Method::POST,
+            body: Some("ignored".to_string()),
+            form: Some(vec![
+                ("note".to_string(), "it's".to_string()),
+                ("file".to_string(), "@~/a.png".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let output = render(&request, &Annotation::new(None, &[], fixed_time()));
+        assert!(output.contains(r"-F 'note=it'\''s' \"));
+        assert!(output.contains("-F 'file=@~/a.png'"));
+        assert!(!output.contains("--data-raw"));
+    }
+
+    #[test]
+    fn test_render_curl_proxy() {
+        let mut request = Request {
+            url: "https://api.example.com".to_string(),
+            ..Default::default()
+        };
+        let annotation = Annotation::new(None, &[], fixed_time());
+
+        request.options.proxy = Some("socks5h://127.0.0.1:1080".to_string());
+        assert!(render(&request, &annotation).contains("--proxy 'socks5h://127.0.0.1:1080'"));
+
+        request.options.proxy = Some("none".to_string());
+        assert!(render(&request, &annotation).contains("--noproxy '*'"));
+
+        request.options.min_tls_version = Some("1.0".to_string());
+        request.options.max_tls_version = Some("1.1".to_string());
+        assert!(render(&request, &annotation).contains("--tlsv1.0 \\\n  --tls-max 1.1"));
+
+        request.options.timeout = Some("90".to_string());
+        assert!(render(&request, &annotation).contains("--max-time 90"));
+    }
+}
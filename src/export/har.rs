@@ -0,0 +1,173 @@
+//! HTTP Archive (HAR 1.2) export
+
+use super::Annotation;
+use crate::logic::form;
+use crate::logic::request::{build_url_with_params, Request};
+use crate::logic::response::Response;
+use serde_json::{json, Value};
+
+fn name_values(pairs: &[(String, String)]) -> Vec<Value> {
+    pairs
+        .iter()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect()
+}
+
+fn header_value<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// Renders the request (and its last response, if any) as a HAR document
+///
+/// The annotation is stored both as the log comment and as a structured
+/// `_restless` custom field.
+pub fn render(request: &Request, response: Option<&Response>, annotation: &Annotation) -> String {
+    let url = build_url_with_params(&request.url, &request.params)
+        .unwrap_or_else(|_| request.url.clone());
+
+    let mut har_request = json!({
+        "method": request.method.as_str(),
+        "url": url,
+        "httpVersion": "HTTP/1.1",
+        "cookies": [],
+        "headers": name_values(&request.headers),
+        "queryString": name_values(&request.params),
+        "headersSize": -1,
+        "bodySize": request.body.as_ref().map(|b| b.len() as i64).unwrap_or(0),
+    });
+    if let Some(fields) = &request.form {
+        let params: Vec<Value> = fields
+            .iter()
+            .map(|(name, value)| match form::file_path(value) {
+                Some(path) => json!({"name": name, "fileName": path}),
+                None => json!({"name": name, "value": value}),
+            })
+            .collect();
+        har_request["postData"] = json!({
+            "mimeType": "multipart/form-data",
+            "params": params,
+        });
+    } else if let Some(body) = &request.body {
+        har_request["postData"] = json!({
+            "mimeType": header_value(&request.headers, "content-type").unwrap_or(""),
+            "text": body,
+        });
+    }
+
+    let har_response = match response {
+        Some(response) => json!({
+            "status": response.status_code,
+            "statusText": "",
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": name_values(&response.headers),
+            "content": {
+                "size": response.body.len(),
+                "mimeType": header_value(&response.headers, "content-type").unwrap_or(""),
+                "text": response.body,
+            },
+            "redirectURL": header_value(&response.headers, "location").unwrap_or(""),
+            "headersSize": -1,
+            "bodySize": -1,
+            "comment": response.note.as_deref().unwrap_or(""),
+        }),
+        None => json!({
+            "status": 0,
+            "statusText": "",
+            "httpVersion": "",
+            "cookies": [],
+            "headers": [],
+            "content": { "size": 0, "mimeType": "" },
+            "redirectURL": "",
+            "headersSize": -1,
+            "bodySize": -1,
+            "comment": "Request was not sent",
+        }),
+    };
+
+    let har = json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "restless", "version": annotation.restless_version },
+            "comment": annotation.to_comment_lines("").iter().map(|l| l.trim()).collect::<Vec<_>>().join("\n"),
+            "_restless": annotation.to_json(),
+            "entries": [{
+                "startedDateTime": annotation.timestamp(),
+                "time": -1,
+                "request": har_request,
+                "response": har_response,
+                "cache": {},
+                "timings": { "send": -1, "wait": -1, "receive": -1 },
+            }],
+        }
+    });
+
+    serde_json::to_string_pretty(&har).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::fixed_time;
+    use crate::logic::environment::Environment;
+
+    #[test]
+    fn test_render_har_with_response() {
+        let request = Request {
+            url: "https://api.example.com/items".to_string(),
+            params: vec![("q".to_string(), "a b".to_string())],
+            ..Default::default()
+        };
+        let response = Response::new_unchecked(
+            200,
+            "Content-Type: application/json".to_string(),
+            "{}".to_string(),
+        );
+        let env = Environment::new("dev");
+        let annotation = Annotation::new(Some(&env), &[], fixed_time());
+
+        let output = render(&request, Some(&response), &annotation);
+        let value: Value = serde_json::from_str(&output).unwrap();
+
+        let entry = &value["log"]["entries"][0];
+        assert_eq!(
+            entry["request"]["url"],
+            "https://api.example.com/items?q=a%20b"
+        );
+        assert_eq!(entry["request"]["method"], "GET");
+        assert_eq!(entry["response"]["status"], 200);
+        assert_eq!(entry["response"]["content"]["mimeType"], "application/json");
+        assert_eq!(value["log"]["_restless"]["environment"], "dev");
+        assert!(value["log"]["comment"]
+            .as_str()
+            .unwrap()
+            .contains("environment: dev"));
+    }
+
+    #[test]
+    fn test_render_har_without_response() {
+        let request = Request {
+            url: "https://api.example.com".to_string(),
+            body: Some("payload".to_string()),
+            ..Default::default()
+        };
+        let annotation = Annotation::new(None, &[], fixed_time());
+
+        let value: Value = serde_json::from_str(&render(&request, None, &annotation)).unwrap();
+        let entry = &value["log"]["entries"][0];
+        assert_eq!(entry["request"]["postData"]["text"], "payload");
+        assert_eq!(entry["response"]["status"], 0);
+
+        let request = Request {
+            form: Some(vec![("avatar".to_string(), "@me.png".to_string())]),
+            ..request
+        };
+        let value: Value = serde_json::from_str(&render(&request, None, &annotation)).unwrap();
+        let post_data = &value["log"]["entries"][0]["request"]["postData"];
+        assert_eq!(post_data["mimeType"], "multipart/form-data");
+        assert_eq!(post_data["params"][0]["fileName"], "me.png");
+    }
+}
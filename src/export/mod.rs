@@ -0,0 +1,241 @@
+//! Exporters for sharing requests outside of Restless
+//!
+//! Every export starts with an [`Annotation`] describing where it came from
+//! (Restless version, time, environment and the variables it used), so a
+//! shared reproduction is self-describing.
+
+pub mod bugreport;
+pub mod code;
+pub mod copy;
+pub mod curl;
+pub mod har;
+pub mod script;
+
+use crate::app::tab::Tab;
+use crate::logic::environment::Environment;
+use crate::logic::request::Request;
+use chrono::{DateTime, SecondsFormat, Utc};
+use code::Language;
+use serde_json::{json, Value};
+
+/// Placeholder shown instead of secret variable values
+pub const REDACTED: &str = "<redacted>";
+
+/// Supported export formats
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Curl,
+    Har,
+    /// Markdown bundle of the request and its response for an issue tracker
+    BugReport,
+    /// Source code sending the request
+    Code(Language),
+}
+
+impl ExportFormat {
+    /// Every format, the code languages of the registry last
+    pub fn all() -> Vec<ExportFormat> {
+        [
+            ExportFormat::Curl,
+            ExportFormat::Har,
+            ExportFormat::BugReport,
+        ]
+        .into_iter()
+        .chain(Language::ALL.into_iter().map(ExportFormat::Code))
+        .collect()
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Curl => "curl",
+            ExportFormat::Har => "HAR",
+            ExportFormat::BugReport => "Bug report",
+            ExportFormat::Code(language) => language.label(),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Curl => "sh",
+            ExportFormat::Har => "har",
+            ExportFormat::BugReport => "md",
+            ExportFormat::Code(language) => language.extension(),
+        }
+    }
+
+    /// Renders the tab in this format
+    ///
+    /// `request` must already have its variables resolved (with secrets
+    /// redacted); the tab provides the last response, if any. Only bug
+    /// reports include the `context`.
+    pub fn render(
+        &self,
+        request: &Request,
+        tab: &Tab,
+        annotation: &Annotation,
+        context: &bugreport::Context,
+    ) -> String {
+        match self {
+            ExportFormat::Curl => curl::render(request, annotation),
+            ExportFormat::Har => har::render(request, tab.response.as_ref(), annotation),
+            ExportFormat::BugReport => {
+                bugreport::render(request, tab.response.as_ref(), annotation, context)
+            }
+            ExportFormat::Code(language) => language.render(request, annotation),
+        }
+    }
+}
+
+/// Metadata embedded in every export for reproducibility
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Annotation {
+    pub restless_version: &'static str,
+    pub exported_at: DateTime<Utc>,
+    pub environment: Option<String>,
+    /// Variables used by the request; secret values are replaced by [`REDACTED`]
+    pub variables: Vec<(String, String)>,
+    /// Note attached to the exported response
+    pub note: Option<String>,
+}
+
+impl Annotation {
+    pub fn new(env: Option<&Environment>, used_variables: &[String], now: DateTime<Utc>) -> Self {
+        let variables = match env {
+            Some(env) => used_variables
+                .iter()
+                .filter_map(|name| {
+                    env.get(name).map(|value| {
+                        let value = if env.is_secret(name) {
+                            REDACTED.to_string()
+                        } else {
+                            value.to_string()
+                        };
+                        (name.clone(), value)
+                    })
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Annotation {
+            restless_version: env!("CARGO_PKG_VERSION"),
+            exported_at: now,
+            environment: env.map(|env| env.name.clone()),
+            variables,
+            note: None,
+        }
+    }
+
+    pub fn with_note(mut self, note: Option<String>) -> Self {
+        self.note = note;
+        self
+    }
+
+    pub fn timestamp(&self) -> String {
+        self.exported_at.to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+
+    /// Renders the annotation as comment lines using the given prefix
+    pub fn to_comment_lines(&self, prefix: &str) -> Vec<String> {
+        let mut lines = vec![
+            format!("{} --- restless reproduction ---", prefix),
+            format!("{} restless-version: {}", prefix, self.restless_version),
+            format!("{} exported-at: {}", prefix, self.timestamp()),
+            format!(
+                "{} environment: {}",
+                prefix,
+                self.environment.as_deref().unwrap_or("(none)")
+            ),
+        ];
+        if self.variables.is_empty() {
+            lines.push(format!("{} variables: (none)", prefix));
+        } else {
+            lines.push(format!("{} variables:", prefix));
+            for (name, value) in &self.variables {
+                lines.push(format!("{}   {} = {}", prefix, name, value));
+            }
+        }
+        if let Some(note) = &self.note {
+            lines.push(format!("{} note: {}", prefix, note));
+        }
+        lines.push(format!("{} ---", prefix));
+        lines
+    }
+
+    pub fn to_json(&self) -> Value {
+        let variables: serde_json::Map<String, Value> = self
+            .variables
+            .iter()
+            .map(|(k, v)| (k.clone(), Value::String(v.clone())))
+            .collect();
+        json!({
+            "restlessVersion": self.restless_version,
+            "exportedAt": self.timestamp(),
+            "environment": self.environment,
+            "variables": variables,
+            "note": self.note,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    pub(crate) fn fixed_time() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 1, 12, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn test_annotation_redacts_secrets() {
+        let mut env = Environment::new("staging");
+        env.variables
+            .insert("host".to_string(), "api.test".to_string());
+        env.variables.insert("token".to_string(), "abc".to_string());
+
+        let used = vec![
+            "host".to_string(),
+            "token".to_string(),
+            "unknown".to_string(),
+        ];
+        let annotation = Annotation::new(Some(&env), &used, fixed_time());
+
+        assert_eq!(annotation.environment.as_deref(), Some("staging"));
+        assert_eq!(
+            annotation.variables,
+            vec![
+                ("host".to_string(), "api.test".to_string()),
+                ("token".to_string(), REDACTED.to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_annotation_comment_lines() {
+        let annotation = Annotation::new(None, &[], fixed_time());
+        let lines = annotation.to_comment_lines("#");
+
+        assert_eq!(lines[0], "# --- restless reproduction ---");
+        assert!(lines.contains(&"# exported-at: 2024-05-01T12:30:00Z".to_string()));
+        assert!(!lines.iter().any(|line| line.contains("note:")));
+
+        let annotation = annotation.with_note(Some("500 since deploy 1.4.2".to_string()));
+        let lines = annotation.to_comment_lines("#");
+        assert!(lines.contains(&"# note: 500 since deploy 1.4.2".to_string()));
+        assert!(lines.contains(&"# environment: (none)".to_string()));
+        assert!(lines.contains(&"# variables: (none)".to_string()));
+    }
+
+    #[test]
+    fn test_annotation_json() {
+        let mut env = Environment::new("dev");
+        env.variables.insert("id".to_string(), "7".to_string());
+        let annotation = Annotation::new(Some(&env), &["id".to_string()], fixed_time());
+
+        let value = annotation.to_json();
+        assert_eq!(value["environment"], "dev");
+        assert_eq!(value["variables"]["id"], "7");
+        assert_eq!(value["exportedAt"], "2024-05-01T12:30:00Z");
+    }
+}
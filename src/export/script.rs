@@ -0,0 +1,350 @@
+//! Collection export as a scheduled smoke check
+//!
+//! A whole collection is rendered as a shell script, or a GitHub Actions
+//! workflow, that replays every request through `restless --plain --fail`.
+//! Variables of the chosen environment are filled in, except secrets: those
+//! become `{{secret:env://NAME}}` references so the script can be committed
+//! and the values supplied by the CI system.
+
+use crate::collections::Collection;
+use crate::error::Result;
+use crate::export::Annotation;
+use crate::logic::auth::Auth;
+use crate::logic::environment::{self, Environment};
+use crate::logic::options::RequestOptions;
+use crate::logic::request::Request;
+use crate::logic::secrets;
+use chrono::{DateTime, Utc};
+
+/// Heredoc delimiter around the plain mode commands
+const DELIMITER: &str = "RESTLESS";
+
+/// Supported script flavours
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScriptFormat {
+    #[default]
+    Shell,
+    GithubActions,
+}
+
+impl ScriptFormat {
+    /// Parses the name used on the command line
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sh" | "shell" => Some(ScriptFormat::Shell),
+            "gha" | "github" | "github-actions" => Some(ScriptFormat::GithubActions),
+            _ => None,
+        }
+    }
+}
+
+/// Renders the collection as a script in the given format
+pub fn render(
+    collection: &Collection,
+    env: Option<&Environment>,
+    format: ScriptFormat,
+    now: DateTime<Utc>,
+) -> Result<String> {
+    let mut commands = Vec::new();
+    let mut used = Vec::new();
+    let mut required = Vec::new();
+
+    if let Some(env) = env {
+        commands.push(format!("# environment: {}", env.name));
+    }
+    for saved in &collection.requests {
+        let request = saved.to_request(&collection.auth)?;
+        let (request, names) = resolve(&request, env, &mut required);
+        for name in names {
+            if !used.contains(&name) {
+                used.push(name);
+            }
+        }
+        commands.push(format!("# {}", saved.name));
+        commands.extend(request_commands(&request, &mut required));
+    }
+    commands.push("quit".to_string());
+
+    let annotation = Annotation::new(env, &used, now);
+    Ok(match format {
+        ScriptFormat::Shell => shell(collection, &annotation, &commands, &required),
+        ScriptFormat::GithubActions => workflow(collection, &annotation, &commands, &required),
+    })
+}
+
+/// Name of the CI environment variable holding a secret
+pub fn env_var_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn secret_reference(name: &str) -> String {
+    format!("{{{{{}env://{}}}}}", secrets::PREFIX, env_var_name(name))
+}
+
+fn require(required: &mut Vec<String>, name: &str) {
+    let var = env_var_name(name);
+    if !required.contains(&var) {
+        required.push(var);
+    }
+}
+
+/// Substitutes environment variables, turning secrets into CI references
+fn resolve(
+    request: &Request,
+    env: Option<&Environment>,
+    required: &mut Vec<String>,
+) -> (Request, Vec<String>) {
+    let (request, used) = environment::resolve_request(request, env, true);
+    let Some(env) = env else {
+        return (request, used);
+    };
+
+    let mut references = Environment::default();
+    for name in used.iter().filter(|name| env.is_secret(name)) {
+        require(required, name);
+        references
+            .variables
+            .insert(name.clone(), secret_reference(name));
+    }
+    let (request, _) = environment::resolve_request(&request, Some(&references), false);
+    (request, used)
+}
+
+/// Plain mode commands that rebuild one request and send it
+fn request_commands(request: &Request, required: &mut Vec<String>) -> Vec<String> {
+    let mut commands = vec![
+        "reset".to_string(),
+        format!("method {}", request.method),
+        format!("url {}", request.url),
+    ];
+    for (key, value) in &request.headers {
+        commands.push(format!("header {}: {}", key, value));
+    }
+    for (key, value) in &request.params {
+        commands.push(format!("param {}={}", key, value));
+    }
+    if let Some(fields) = &request.form {
+        for (name, value) in fields {
+            commands.push(format!("form {}={}", name, value));
+        }
+    } else if let Some(body) = &request.body {
+        commands.push("body".to_string());
+        commands.extend(body.lines().map(str::to_string));
+        commands.push(".".to_string());
+    }
+    commands.extend(auth_commands(&request.auth, required));
+    commands.extend(option_commands(&request.options, required));
+    commands.push("send".to_string());
+    commands
+}
+
+fn auth_commands(auth: &Auth, required: &mut Vec<String>) -> Vec<String> {
+    if auth.fields.is_empty() && auth.kind == Default::default() {
+        return Vec::new();
+    }
+    let kind = serde_json::to_value(auth.kind)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default();
+    let mut commands = vec![format!("auth {}", kind)];
+    for (key, value) in &auth.fields {
+        // Literal credentials stay out of the script; placeholders are fine
+        let value = if Auth::is_secret_field(key) && !value.contains("{{") {
+            require(required, key);
+            secret_reference(key)
+        } else {
+            value.clone()
+        };
+        commands.push(format!("auth {}={}", key, value));
+    }
+    commands
+}
+
+fn option_commands(options: &RequestOptions, required: &mut Vec<String>) -> Vec<String> {
+    options
+        .entries()
+        .into_iter()
+        .map(|(key, value)| {
+            if RequestOptions::is_secret(key) {
+                require(required, key);
+                format!("# option {} must be set on the runner; not exported", key)
+            } else {
+                format!("option {}={}", key, value)
+            }
+        })
+        .collect()
+}
+
+fn shell(
+    collection: &Collection,
+    annotation: &Annotation,
+    commands: &[String],
+    required: &[String],
+) -> String {
+    let mut lines = vec!["#!/bin/sh".to_string()];
+    lines.push(format!(
+        "# Smoke checks for the \"{}\" collection",
+        collection.name
+    ));
+    lines.extend(annotation.to_comment_lines("#"));
+    if !required.is_empty() {
+        lines.push(format!(
+            "# Required environment variables: {}",
+            required.join(", ")
+        ));
+    }
+    lines.push("set -eu".to_string());
+    lines.push(String::new());
+    lines.push(format!("restless --plain --fail <<'{}'", DELIMITER));
+    lines.extend(commands.iter().cloned());
+    lines.push(DELIMITER.to_string());
+    lines.join("\n") + "\n"
+}
+
+fn workflow(
+    collection: &Collection,
+    annotation: &Annotation,
+    commands: &[String],
+    required: &[String],
+) -> String {
+    let mut lines = annotation.to_comment_lines("#");
+    lines.extend([
+        format!("name: {} smoke checks", yaml_string(&collection.name)),
+        "on:".to_string(),
+        "  schedule:".to_string(),
+        "    - cron: \"0 * * * *\"".to_string(),
+        "  workflow_dispatch:".to_string(),
+        "jobs:".to_string(),
+        "  smoke:".to_string(),
+        "    runs-on: ubuntu-latest".to_string(),
+        "    steps:".to_string(),
+        "      - name: Install restless".to_string(),
+        "        run: cargo install restless".to_string(),
+        format!("      - name: Run {}", yaml_string(&collection.name)),
+    ]);
+    if !required.is_empty() {
+        lines.push("        env:".to_string());
+        for var in required {
+            lines.push(format!("          {}: ${{{{ secrets.{} }}}}", var, var));
+        }
+    }
+    lines.push("        run: |".to_string());
+    lines.push(format!(
+        "          restless --plain --fail <<'{}'",
+        DELIMITER
+    ));
+    lines.extend(commands.iter().map(|command| {
+        if command.is_empty() {
+            String::new()
+        } else {
+            format!("          {}", command)
+        }
+    }));
+    lines.push(format!("          {}", DELIMITER));
+    lines.join("\n") + "\n"
+}
+
+/// Keeps a name from breaking the YAML it's embedded in
+fn yaml_string(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if matches!(c, ':' | '#' | '"' | '\'') {
+                ' '
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::SavedRequest;
+
+    fn collection() -> Collection {
+        let saved: Vec<SavedRequest> = serde_json::from_str(
+            r#"[{"name": "Health", "url": "{{base_url}}/health"},
+                {"name": "Create", "method": "POST", "url": "{{base_url}}/items",
+                 "headers": [["Authorization", "Bearer {{api_token}}"]],
+                 "body": "{\n  \"a\": 1\n}",
+                 "auth": {"kind": "api_key", "fields": [["key", "X-Key"], ["value", "literal"]]},
+                 "options": {"proxy": "http://proxy:3128", "proxy_auth": "u:p"}}]"#,
+        )
+        .unwrap();
+        Collection {
+            name: "My API".to_string(),
+            requests: saved,
+            ..Default::default()
+        }
+    }
+
+    fn env() -> Environment {
+        let mut env = Environment::new("prod");
+        env.variables.insert(
+            "base_url".to_string(),
+            "https://api.example.com".to_string(),
+        );
+        env.variables
+            .insert("api_token".to_string(), "t0p-secret".to_string());
+        env
+    }
+
+    #[test]
+    fn test_shell_script() {
+        let script = render(&collection(), Some(&env()), ScriptFormat::Shell, Utc::now()).unwrap();
+
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("restless --plain --fail <<'RESTLESS'\n"));
+        assert!(script.contains("url https://api.example.com/health\n"));
+        assert!(script.contains("method POST\n"));
+        assert!(script.contains("header Authorization: Bearer {{secret:env://API_TOKEN}}\n"));
+        assert!(script.contains("body\n{\n  \"a\": 1\n}\n.\n"));
+        assert!(
+            script.contains("auth api_key\nauth key=X-Key\nauth value={{secret:env://VALUE}}\n")
+        );
+        assert!(script.contains("option proxy=http://proxy:3128\n"));
+        assert!(script.contains("# option proxy_auth must be set on the runner"));
+        assert!(script.contains("# Required environment variables: API_TOKEN, VALUE, PROXY_AUTH"));
+        assert!(script.ends_with("send\nquit\nRESTLESS\n"));
+        assert!(!script.contains("t0p-secret"));
+        assert!(!script.contains("literal"));
+        assert!(!script.contains("u:p"));
+    }
+
+    #[test]
+    fn test_github_actions_workflow() {
+        let workflow = render(
+            &collection(),
+            Some(&env()),
+            ScriptFormat::GithubActions,
+            Utc::now(),
+        )
+        .unwrap();
+
+        assert!(workflow.contains("name: My API smoke checks\n"));
+        assert!(workflow.contains("    - cron: \"0 * * * *\"\n"));
+        assert!(workflow.contains("          API_TOKEN: ${{ secrets.API_TOKEN }}\n"));
+        assert!(workflow.contains("          url https://api.example.com/health\n"));
+        assert!(workflow.contains("          RESTLESS\n"));
+    }
+
+    #[test]
+    fn test_without_environment() {
+        let script = render(&collection(), None, ScriptFormat::Shell, Utc::now()).unwrap();
+        assert!(script.contains("url {{base_url}}/health\n"));
+        assert_eq!(
+            ScriptFormat::from_name("gha"),
+            Some(ScriptFormat::GithubActions)
+        );
+        assert_eq!(ScriptFormat::from_name("zip"), None);
+    }
+}
@@ -3,10 +3,28 @@
 //! This module handles keyboard events for the main application screens,
 //! including navigation between sections, method selection, and input handling.
 
+use super::tab::handle_switch_to_tab;
+use crate::app::input::Cursor;
+use crate::app::tab::{InFlight, RunSend};
 use crate::app::{App, CurrentScreen, ValuesScreen};
+use crate::collections::merge::Resolution;
 use crate::error::Result;
-use crate::logic::HttpMethod;
+use crate::export::{copy::CopyFormat, ExportFormat};
+use crate::logic::{
+    assertions::{self, TestResult},
+    auth,
+    environment::{self, Environment},
+    form, lint,
+    load::Load,
+    matrix::{self, Matrix},
+    ratelimit::{format_wait, RateLimit},
+    request::{self, Override, RawResponse, Request},
+    response::Response,
+    runner::{self, Step},
+    HttpMethod,
+};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::time::Duration;
 
 /// Handles keyboard events for the main screens (Url, Values, Response)
 pub async fn handle_main_screen_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
@@ -25,10 +43,16 @@ pub async fn handle_main_screen_keys(app: &mut App, key: KeyEvent) -> Result<Opt
             navigate_section_up(app);
             Ok(None)
         }
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            match app.show_finder() {
+                Ok(()) => Ok(None),
+                Err(e) => Ok(Some(app.get_error_message(&e))),
+            }
+        }
 
         // URL editing
         KeyCode::Char('u') => {
-            app.current_screen = CurrentScreen::EditingUrl;
+            app.start_url_edit();
             Ok(None)
         }
 
@@ -38,14 +62,145 @@ pub async fn handle_main_screen_keys(app: &mut App, key: KeyEvent) -> Result<Opt
             Ok(None)
         }
 
+        // Fold the selected node of the JSON tree instead of sending
+        KeyCode::Enter | KeyCode::Char(' ')
+            if app.current_screen == CurrentScreen::Response && app.tree_active() =>
+        {
+            if let Some(tree) = app.response_tree.as_mut() {
+                tree.toggle();
+            }
+            Ok(None)
+        }
+
         // Send request
         KeyCode::Enter => handle_send_request(app).await,
+        KeyCode::Char('R') => {
+            app.show_resend_with();
+            Ok(None)
+        }
+        KeyCode::Char('!') => {
+            app.toggle_insecure();
+            Ok(None)
+        }
+        KeyCode::Char('\\') => {
+            app.cycle_layout();
+            Ok(None)
+        }
+        KeyCode::Char('W') => {
+            app.show_watch_prompt();
+            Ok(None)
+        }
+        KeyCode::Char('I') => {
+            app.show_poll_prompt();
+            Ok(None)
+        }
+        KeyCode::Char('M') => {
+            app.show_matrix_prompt();
+            Ok(None)
+        }
+        KeyCode::Char('B') => {
+            app.show_load_prompt();
+            Ok(None)
+        }
+        KeyCode::Char('P') => preview_send(app).await,
+        KeyCode::Char('A') => match app.tabs_run() {
+            Ok(requests) => {
+                app.start_run(app.tr("Open tabs").to_string(), requests);
+                Ok(next_run_step(app))
+            }
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+
+        KeyCode::Char('e')
+            if key.modifiers.contains(KeyModifiers::CONTROL)
+                && app.current_screen == CurrentScreen::Values
+                && app.values_screen == ValuesScreen::Body
+                && app.form_input.is_none() =>
+        {
+            app.body_editor_requested = true;
+            Ok(None)
+        }
+
+        // Edit the header or param picked with j/k
+        KeyCode::Char('e')
+            if app.current_screen == CurrentScreen::Values
+                && match app.values_screen {
+                    ValuesScreen::Headers => app.edit_selected_header(),
+                    ValuesScreen::Params => app.edit_selected_param(),
+                    _ => false,
+                } =>
+        {
+            Ok(None)
+        }
+
+        // Environments and export
+        KeyCode::Char('e') => {
+            app.cycle_environment();
+            Ok(None)
+        }
+        KeyCode::Char('E') => {
+            app.show_export();
+            Ok(None)
+        }
+        KeyCode::Char('o') => {
+            app.show_collections();
+            Ok(None)
+        }
+        KeyCode::Char('C') => {
+            app.show_cookies();
+            Ok(None)
+        }
+
+        KeyCode::Char('Z') => {
+            app.dismiss_hint()?;
+            Ok(None)
+        }
+        KeyCode::Char('z') => {
+            app.toggle_response_zoom();
+            Ok(None)
+        }
 
         // Tab management
         KeyCode::Char('t') => handle_new_tab(app),
+        KeyCode::Char('D') => handle_duplicate_tab(app),
         KeyCode::Char('x') => handle_close_tab(app),
         KeyCode::Tab => handle_next_tab(app),
         KeyCode::BackTab => handle_prev_tab(app),
+        // Alt+1..9 as well, for terminals that pass it through
+        KeyCode::Char(digit @ '1'..='9') => {
+            handle_switch_to_tab(app, digit as usize - '1' as usize)
+        }
+        KeyCode::Char('<') => handle_move_tab(app, -1),
+        KeyCode::Char('>') => handle_move_tab(app, 1),
+        KeyCode::Char('H') | KeyCode::Char('h')
+            if key
+                .modifiers
+                .contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT) =>
+        {
+            handle_move_tab(app, -1)
+        }
+        KeyCode::Char('L') | KeyCode::Char('l')
+            if key
+                .modifiers
+                .contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT) =>
+        {
+            handle_move_tab(app, 1)
+        }
+
+        // Step through the tab's kept responses; in the Response section of
+        // a multipart response these step through its parts instead
+        KeyCode::Char('[') | KeyCode::Char(']')
+            if app.current_screen != CurrentScreen::Response
+                || app.tabs[app.selected_tab]
+                    .response
+                    .as_ref()
+                    .is_none_or(|response| response.parts.is_empty()) =>
+        {
+            match app.step_response(key.code == KeyCode::Char(']')) {
+                Ok(()) => Ok(None),
+                Err(e) => Ok(Some(app.get_error_message(&e))),
+            }
+        }
 
         // Screen-specific handlers
         _ => match app.current_screen {
@@ -103,6 +258,9 @@ async fn handle_values_screen_keys(app: &mut App, key: KeyEvent) -> Result<Optio
             app.values_screen = match app.values_screen {
                 ValuesScreen::Headers => ValuesScreen::Body,
                 ValuesScreen::Params => ValuesScreen::Headers,
+                ValuesScreen::Auth => ValuesScreen::Params,
+                ValuesScreen::Options => ValuesScreen::Auth,
+                ValuesScreen::Tests => ValuesScreen::Options,
                 _ => app.values_screen,
             };
             Ok(None)
@@ -111,6 +269,9 @@ async fn handle_values_screen_keys(app: &mut App, key: KeyEvent) -> Result<Optio
             app.values_screen = match app.values_screen {
                 ValuesScreen::Body => ValuesScreen::Headers,
                 ValuesScreen::Headers => ValuesScreen::Params,
+                ValuesScreen::Params => ValuesScreen::Auth,
+                ValuesScreen::Auth => ValuesScreen::Options,
+                ValuesScreen::Options => ValuesScreen::Tests,
                 _ => app.values_screen,
             };
             Ok(None)
@@ -119,18 +280,91 @@ async fn handle_values_screen_keys(app: &mut App, key: KeyEvent) -> Result<Optio
         // Enter editing mode
         KeyCode::Char('i') => {
             match app.values_screen {
-                ValuesScreen::Body => {
-                    app.current_screen = CurrentScreen::EditingBody;
+                ValuesScreen::Body if app.form_input.is_some() => {
+                    app.current_screen = CurrentScreen::EditingForm;
                 }
+                ValuesScreen::Body => app.start_body_edit(),
                 ValuesScreen::Headers => {
                     app.current_screen = CurrentScreen::EditingHeaders;
                 }
                 ValuesScreen::Params => {
                     app.current_screen = CurrentScreen::EditingParams;
                 }
+                ValuesScreen::Auth => {
+                    app.current_screen = CurrentScreen::EditingAuth;
+                }
+                ValuesScreen::Options => {
+                    app.current_screen = CurrentScreen::EditingOptions;
+                }
+                ValuesScreen::Tests => app.start_tests_edit(),
+            }
+            Ok(None)
+        }
+
+        // Pick a header or param to edit ('e') or delete, or scroll the body
+        KeyCode::Char('j') | KeyCode::Down | KeyCode::Char('k') | KeyCode::Up => {
+            let down = matches!(key.code, KeyCode::Char('j') | KeyCode::Down);
+            match app.values_screen {
+                ValuesScreen::Headers => app.select_header(down),
+                ValuesScreen::Params => app.select_param(down),
+                ValuesScreen::Body if app.form_input.is_none() => app.scroll_body(down, 1),
+                _ => {}
+            }
+            Ok(None)
+        }
+        KeyCode::Char('d') => {
+            let deleted = match app.values_screen {
+                ValuesScreen::Headers => app.delete_selected_header(),
+                ValuesScreen::Params => app.delete_selected_param(),
+                _ => Ok(false),
+            };
+            match deleted {
+                Ok(_) => Ok(None),
+                Err(e) => Ok(Some(app.get_error_message(&e))),
             }
+        }
+
+        KeyCode::Char('b')
+            if matches!(
+                app.values_screen,
+                ValuesScreen::Headers | ValuesScreen::Params
+            ) =>
+        {
+            app.start_bulk_edit();
+            Ok(None)
+        }
+
+        // Switch the picked header or param off and on
+        KeyCode::Char(' ') if app.values_screen == ValuesScreen::Headers => {
+            app.toggle_selected_header();
             Ok(None)
         }
+        KeyCode::Char(' ') if app.values_screen == ValuesScreen::Params => {
+            app.toggle_selected_param();
+            Ok(None)
+        }
+
+        // Body type selection
+        KeyCode::Char(' ') if app.values_screen == ValuesScreen::Body => {
+            app.toggle_body_type();
+            Ok(None)
+        }
+
+        // Auth type selection and token reset
+        KeyCode::Char(' ') if app.values_screen == ValuesScreen::Auth => {
+            app.cycle_auth_kind();
+            Ok(None)
+        }
+        KeyCode::Char('p') if app.values_screen == ValuesScreen::Auth => {
+            match app.show_signing_preview() {
+                Ok(()) => Ok(None),
+                Err(e) => Ok(Some(app.get_error_message(&e))),
+            }
+        }
+        KeyCode::Char('r') if app.values_screen == ValuesScreen::Auth => {
+            auth::clear_token_cache();
+            Ok(Some(app.tr("Cached OAuth2 tokens cleared").to_string()))
+        }
 
         _ => Ok(None),
     }
@@ -139,26 +373,173 @@ async fn handle_values_screen_keys(app: &mut App, key: KeyEvent) -> Result<Optio
 /// Handles keys specific to the Response screen
 async fn handle_response_screen_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
     match key.code {
-        // Navigate between response tabs
+        // Navigate between response tabs; on the Body tab long lines scroll
+        // sideways first, and left of the first column is the Headers tab
         KeyCode::Left | KeyCode::Char('h') => {
-            app.response_tab_selected = 0; // Headers
+            if app.response_tab_selected != 1 || !app.scroll_response_columns(false) {
+                app.response_tab_selected = 0; // Headers
+            }
+            Ok(None)
+        }
+        KeyCode::Right | KeyCode::Char('l') if app.response_tab_selected == 1 => {
+            app.scroll_response_columns(true);
             Ok(None)
         }
         KeyCode::Right | KeyCode::Char('b') => {
             app.response_tab_selected = 1; // Body
             Ok(None)
         }
+        KeyCode::Char('T') => {
+            app.show_tls_details();
+            Ok(None)
+        }
+        KeyCode::Char('r') => match app.show_test_results() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Char('K') => match app.toggle_cached_body() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Char('V') => match app.show_sent_request() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Char('p') => match app.show_panel() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Char('v') => match app.toggle_response_table() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Char('H') => match app.toggle_html_preview() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Char('J') => match app.toggle_response_tree() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Char('j') | KeyCode::Char('k') if app.tree_active() => {
+            if let Some(tree) = app.response_tree.as_mut() {
+                tree.move_selection(key.code == KeyCode::Char('j'));
+            }
+            Ok(None)
+        }
+        KeyCode::Char(',') | KeyCode::Char('.') if app.response_table => {
+            app.move_table_column(key.code == KeyCode::Char('.'));
+            Ok(None)
+        }
+        KeyCode::Char('s') if app.response_table => {
+            app.sort_table();
+            Ok(None)
+        }
+        KeyCode::Char('w') if app.response_table => match app.save_table_csv() {
+            Ok(path) => Ok(Some(
+                app.i18n
+                    .format("Table saved to {path}", &[("path", &path.display())]),
+            )),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Char('Y') if app.response_table => {
+            match app.table_csv().and_then(|csv| crate::clipboard::copy(&csv)) {
+                Ok(()) => Ok(Some(app.tr("Table copied as CSV").to_string())),
+                Err(e) => Ok(Some(app.get_error_message(&e))),
+            }
+        }
+        KeyCode::Char('y') => {
+            match app
+                .view_text()
+                .and_then(|(view, text)| crate::clipboard::copy(&text).map(|()| view))
+            {
+                Ok(view) => {
+                    let message = app.i18n.format(
+                        "Copied the {view} to the clipboard",
+                        &[("view", &app.tr(view))],
+                    );
+                    app.show_toast(message);
+                    Ok(None)
+                }
+                Err(e) => Ok(Some(app.get_error_message(&e))),
+            }
+        }
+        KeyCode::Char('S') => {
+            if app.stop_stream() {
+                Ok(Some(app.tr("Event stream closed").to_string()))
+            } else {
+                Ok(None)
+            }
+        }
+        KeyCode::Char('L') => match app.show_latency() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Char('|') => match app.cycle_compare() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+
+        // Step through the parts of a multipart body
+        KeyCode::Char(']') | KeyCode::Char('[') => {
+            let parts = app.tabs[app.selected_tab]
+                .response
+                .as_ref()
+                .map_or(0, |response| response.parts.len());
+            if parts > 0 {
+                let current = app.response_part_selected.min(parts - 1);
+                app.response_part_selected = if key.code == KeyCode::Char(']') {
+                    (current + 1) % parts
+                } else {
+                    (current + parts - 1) % parts
+                };
+                app.response_tab_selected = 1;
+                app.response_scroll = 0;
+                app.response_column.set(0);
+            }
+            Ok(None)
+        }
 
-        // Scroll response content
+        KeyCode::Char('c') => match app.show_copy_menu() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Char('f') => {
+            app.start_filter();
+            Ok(None)
+        }
+        KeyCode::Char('/') => match app.start_search() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        // With a search active, n/N step through the matches
+        KeyCode::Char('n') | KeyCode::Char('N') if app.search.is_some() => {
+            app.step_search(key.code == KeyCode::Char('N'));
+            Ok(None)
+        }
+        KeyCode::Esc if app.search.is_some() => {
+            app.search = None;
+            Ok(None)
+        }
+        KeyCode::Char('n') => match app.start_note_edit() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+
+        // Scroll response content; the headers scroll on their own
         KeyCode::Char('j') => {
-            if app.response_tab_selected == 1 {
-                app.response_scroll = app.response_scroll.saturating_add(1);
+            match app.response_tab_selected {
+                0 => app.scroll_response_headers(true, 1),
+                1 => app.response_scroll = app.response_scroll.saturating_add(1),
+                _ => {}
             }
             Ok(None)
         }
         KeyCode::Char('k') => {
-            if app.response_tab_selected == 1 {
-                app.response_scroll = app.response_scroll.saturating_sub(1);
+            match app.response_tab_selected {
+                0 => app.scroll_response_headers(false, 1),
+                1 => app.response_scroll = app.response_scroll.saturating_sub(1),
+                _ => {}
             }
             Ok(None)
         }
@@ -176,61 +557,206 @@ async fn handle_url_screen_keys(_app: &mut App, _key: KeyEvent) -> Result<Option
 /// Handles URL editing mode
 pub async fn handle_url_editing_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
     match key.code {
+        KeyCode::Enter if app.accept_url_suggestion() => Ok(None),
         KeyCode::Enter => {
+            app.finish_url_edit();
             if let Err(e) = app.save_current_tab_state() {
-                return Ok(Some(format!("Failed to save tab state: {}", e)));
+                return Ok(Some(
+                    app.i18n
+                        .format("Failed to save tab state: {error}", &[("error", &e)]),
+                ));
             }
             app.current_screen = CurrentScreen::Url;
             Ok(None)
         }
-        KeyCode::Backspace => {
-            app.url_input.pop();
+        // The first Esc only closes the suggestions
+        KeyCode::Esc if !app.url_suggestions.is_empty() => {
+            app.url_suggestions.clear();
+            app.url_suggestion_selected = None;
             Ok(None)
         }
         KeyCode::Esc => {
+            app.finish_url_edit();
             app.current_screen = CurrentScreen::Url;
             Ok(None)
         }
-        KeyCode::Char(c) => {
-            app.url_input.push(c);
+        KeyCode::Down | KeyCode::Up => {
+            app.select_url_suggestion(key.code == KeyCode::Down);
+            Ok(None)
+        }
+        code => {
+            let (cursor, url) = (&mut app.url_cursor, &mut app.url_input);
+            let control = key.modifiers.contains(KeyModifiers::CONTROL);
+            match code {
+                KeyCode::Backspace => cursor.backspace(url),
+                KeyCode::Delete => cursor.delete(url),
+                KeyCode::Left => cursor.left(url),
+                KeyCode::Right => cursor.right(url),
+                KeyCode::Home => cursor.home(url),
+                KeyCode::End => cursor.end(url),
+                KeyCode::Char('w') if control => cursor.delete_word(url),
+                KeyCode::Char('u') if control => {
+                    url.clear();
+                    *cursor = Cursor::default();
+                }
+                KeyCode::Char(c) => cursor.insert(url, c.encode_utf8(&mut [0; 4])),
+                _ => return Ok(None),
+            }
+            if !matches!(
+                code,
+                KeyCode::Left | KeyCode::Right | KeyCode::Home | KeyCode::End
+            ) {
+                app.update_url_suggestions();
+            }
             Ok(None)
         }
-        _ => Ok(None),
     }
 }
 
-/// Handles body editing mode
-pub async fn handle_body_editing_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+/// Handles the confirmation shown when a curl command or raw HTTP is pasted
+pub async fn handle_paste_import_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
     match key.code {
-        KeyCode::Enter => {
-            app.body_input.push('\n');
-            Ok(None)
+        KeyCode::Enter | KeyCode::Char('y') => {
+            let label = app
+                .paste_import
+                .as_ref()
+                .map(|(kind, _)| kind.label())
+                .unwrap_or_default();
+            match app.apply_paste_import() {
+                Ok(()) => Ok(Some(
+                    app.i18n
+                        .format("Imported {kind}", &[("kind", &app.tr(label))]),
+                )),
+                Err(e) => {
+                    app.hide_paste_import();
+                    Ok(Some(app.get_error_message(&e)))
+                }
+            }
         }
-        KeyCode::Backspace => {
-            app.body_input.pop();
+        KeyCode::Char('n') => {
+            if let Some((_, text)) = app.paste_import.clone() {
+                app.paste_into_url(&text);
+            }
+            app.hide_paste_import();
             Ok(None)
         }
         KeyCode::Esc => {
-            app.current_screen = CurrentScreen::Values;
-            Ok(None)
-        }
-        KeyCode::Char(c) => {
-            app.body_input.push(c);
+            app.hide_paste_import();
             Ok(None)
         }
         _ => Ok(None),
     }
 }
 
+/// Handles body editing mode
+pub async fn handle_body_editing_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    let (cursor, body) = (&mut app.body_cursor, &mut app.body_input);
+    match key.code {
+        KeyCode::Enter => cursor.insert(body, "\n"),
+        KeyCode::Backspace => cursor.backspace(body),
+        KeyCode::Delete => cursor.delete(body),
+        KeyCode::Left => cursor.left(body),
+        KeyCode::Right => cursor.right(body),
+        KeyCode::Up => cursor.up(body),
+        KeyCode::Down => cursor.down(body),
+        KeyCode::Home => cursor.home(body),
+        KeyCode::End => cursor.end(body),
+        KeyCode::Esc => app.current_screen = CurrentScreen::Values,
+        KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.body_editor_requested = true;
+        }
+        KeyCode::Char(c) => cursor.insert(body, c.encode_utf8(&mut [0; 4])),
+        _ => {}
+    }
+    Ok(None)
+}
+
+/// Handles the headers or params edited as text; Esc applies them
+pub async fn handle_bulk_editing_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    let (cursor, text) = (&mut app.bulk_cursor, &mut app.bulk_input);
+    match key.code {
+        KeyCode::Enter => cursor.insert(text, "\n"),
+        KeyCode::Backspace => cursor.backspace(text),
+        KeyCode::Delete => cursor.delete(text),
+        KeyCode::Left => cursor.left(text),
+        KeyCode::Right => cursor.right(text),
+        KeyCode::Up => cursor.up(text),
+        KeyCode::Down => cursor.down(text),
+        KeyCode::Home => cursor.home(text),
+        KeyCode::End => cursor.end(text),
+        KeyCode::Esc => {
+            if let Err(e) = app.apply_bulk_edit() {
+                return Ok(Some(
+                    app.i18n
+                        .format("Bulk edit error: {error}", &[("error", &e)]),
+                ));
+            }
+        }
+        KeyCode::Char(c) => cursor.insert(text, c.encode_utf8(&mut [0; 4])),
+        _ => {}
+    }
+    Ok(None)
+}
+
+/// Handles the assertions of the Tests tab; Esc checks them
+pub async fn handle_tests_editing_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    let (cursor, text) = (&mut app.tests_cursor, &mut app.tests_input);
+    match key.code {
+        KeyCode::Enter => cursor.insert(text, "\n"),
+        KeyCode::Backspace => cursor.backspace(text),
+        KeyCode::Delete => cursor.delete(text),
+        KeyCode::Left => cursor.left(text),
+        KeyCode::Right => cursor.right(text),
+        KeyCode::Up => cursor.up(text),
+        KeyCode::Down => cursor.down(text),
+        KeyCode::Home => cursor.home(text),
+        KeyCode::End => cursor.end(text),
+        KeyCode::Esc => {
+            if let Err(e) = app.finish_tests_edit() {
+                return Ok(Some(
+                    app.i18n.format("Tests error: {error}", &[("error", &e)]),
+                ));
+            }
+        }
+        KeyCode::Char(c) => cursor.insert(text, c.encode_utf8(&mut [0; 4])),
+        _ => {}
+    }
+    Ok(None)
+}
+
 /// Handles headers editing mode
 pub async fn handle_headers_editing_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    // Keys for the header name suggestions come first
     match key.code {
+        KeyCode::Enter | KeyCode::Tab if app.accept_header_suggestion(key.code == KeyCode::Tab) => {
+            return Ok(None);
+        }
+        KeyCode::Down | KeyCode::Up if !app.header_suggestions.is_empty() => {
+            app.select_header_suggestion(key.code == KeyCode::Down);
+            return Ok(None);
+        }
+        KeyCode::Esc if !app.header_suggestions.is_empty() => {
+            app.header_suggestions.clear();
+            app.header_suggestion_selected = None;
+            return Ok(None);
+        }
+        _ => {}
+    }
+
+    let result = match key.code {
         KeyCode::Enter => {
-            if !app.current_header_key.is_empty() {
+            // An edited header goes back to the list once it's saved
+            let replacing = app.editing_header_index.is_some();
+            let finished = app.current_header_key.is_empty();
+            if !finished {
                 if let Err(e) = app.add_header() {
-                    return Ok(Some(format!("Header error: {}", e)));
+                    return Ok(Some(
+                        app.i18n.format("Header error: {error}", &[("error", &e)]),
+                    ));
                 }
-            } else {
+            }
+            if replacing || finished {
+                app.editing_header_index = None;
                 app.current_screen = CurrentScreen::Values;
             }
             Ok(None)
@@ -254,13 +780,14 @@ pub async fn handle_headers_editing_keys(app: &mut App, key: KeyEvent) -> Result
         KeyCode::Esc => {
             app.current_header_key.clear();
             app.current_header_value.clear();
+            app.editing_header_index = None;
             app.current_screen = CurrentScreen::Values;
             Ok(None)
         }
         KeyCode::Char(':') => {
-            if !app.current_header_key.is_empty() && app.current_header_value.is_empty() {
-                app.current_header_key.push(':');
-            } else if !app.current_header_key.contains(':') {
+            if (!app.current_header_key.is_empty() && app.current_header_value.is_empty())
+                || !app.current_header_key.contains(':')
+            {
                 app.current_header_key.push(':');
             } else {
                 app.current_header_value.push(':');
@@ -288,18 +815,28 @@ pub async fn handle_headers_editing_keys(app: &mut App, key: KeyEvent) -> Result
             Ok(None)
         }
         _ => Ok(None),
-    }
+    };
+    app.update_header_suggestions();
+    result
 }
 
 /// Handles parameters editing mode
 pub async fn handle_params_editing_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
     match key.code {
         KeyCode::Enter => {
-            if !app.current_param_key.is_empty() {
+            // An edited param goes back to the list once it's saved
+            let replacing = app.editing_param_index.is_some();
+            let finished = app.current_param_key.is_empty();
+            if !finished {
                 if let Err(e) = app.add_param() {
-                    return Ok(Some(format!("Parameter error: {}", e)));
+                    return Ok(Some(
+                        app.i18n
+                            .format("Parameter error: {error}", &[("error", &e)]),
+                    ));
                 }
-            } else {
+            }
+            if replacing || finished {
+                app.editing_param_index = None;
                 app.current_screen = CurrentScreen::Values;
             }
             Ok(None)
@@ -323,13 +860,14 @@ pub async fn handle_params_editing_keys(app: &mut App, key: KeyEvent) -> Result<
         KeyCode::Esc => {
             app.current_param_key.clear();
             app.current_param_value.clear();
+            app.editing_param_index = None;
             app.current_screen = CurrentScreen::Values;
             Ok(None)
         }
         KeyCode::Char('=') => {
-            if !app.current_param_key.is_empty() && app.current_param_value.is_empty() {
-                app.current_param_key.push('=');
-            } else if !app.current_param_key.contains('=') {
+            if (!app.current_param_key.is_empty() && app.current_param_value.is_empty())
+                || !app.current_param_key.contains('=')
+            {
                 app.current_param_key.push('=');
             } else {
                 app.current_param_value.push('=');
@@ -348,226 +886,4382 @@ pub async fn handle_params_editing_keys(app: &mut App, key: KeyEvent) -> Result<
     }
 }
 
-/// Handles help screen navigation
-pub async fn handle_help_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+/// Handles auth field editing mode
+pub async fn handle_auth_editing_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
     match key.code {
+        KeyCode::Enter => {
+            if app.current_auth_input.trim().is_empty() {
+                app.current_screen = CurrentScreen::Values;
+            } else if let Err(e) = app.set_auth_field() {
+                return Ok(Some(
+                    app.i18n.format("Auth error: {error}", &[("error", &e)]),
+                ));
+            }
+            Ok(None)
+        }
+        KeyCode::Backspace => {
+            app.current_auth_input.pop();
+            Ok(None)
+        }
         KeyCode::Esc => {
-            app.hide_help();
+            app.current_auth_input.clear();
+            app.current_screen = CurrentScreen::Values;
             Ok(None)
         }
-        KeyCode::Char('j') => {
-            let help_content = app.get_help_content();
-            if app.help_scroll < help_content.len().saturating_sub(1) {
-                app.help_scroll = app.help_scroll.saturating_add(1);
+        KeyCode::Char(c) => {
+            app.current_auth_input.push(c);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles multipart form editing mode
+pub async fn handle_form_editing_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Enter => {
+            if app.current_form_input.trim().is_empty() {
+                app.current_screen = CurrentScreen::Values;
+            } else if let Err(e) = app.set_form_field() {
+                return Ok(Some(
+                    app.i18n.format("Form error: {error}", &[("error", &e)]),
+                ));
             }
             Ok(None)
         }
-        KeyCode::Char('k') => {
-            app.help_scroll = app.help_scroll.saturating_sub(1);
+        KeyCode::Tab => {
+            if let Some(completed) = form::complete_path(&app.current_form_input) {
+                app.current_form_input = completed;
+            }
+            Ok(None)
+        }
+        KeyCode::Backspace => {
+            app.current_form_input.pop();
+            Ok(None)
+        }
+        KeyCode::Esc => {
+            app.current_form_input.clear();
+            app.current_screen = CurrentScreen::Values;
+            Ok(None)
+        }
+        KeyCode::Char(c) => {
+            app.current_form_input.push(c);
             Ok(None)
         }
         _ => Ok(None),
     }
 }
 
-// Helper functions for navigation and actions
-
-fn navigate_section_down(app: &mut App) {
-    app.current_screen = match app.current_screen {
-        CurrentScreen::Url => CurrentScreen::Values,
-        CurrentScreen::Values => CurrentScreen::Response,
-        _ => app.current_screen,
-    };
+/// Handles option editing mode
+pub async fn handle_options_editing_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Enter => {
+            if app.current_options_input.trim().is_empty() {
+                app.current_screen = CurrentScreen::Values;
+            } else if let Err(e) = app.set_option() {
+                return Ok(Some(
+                    app.i18n.format("Option error: {error}", &[("error", &e)]),
+                ));
+            }
+            Ok(None)
+        }
+        KeyCode::Backspace => {
+            app.current_options_input.pop();
+            Ok(None)
+        }
+        KeyCode::Esc => {
+            app.current_options_input.clear();
+            app.current_screen = CurrentScreen::Values;
+            Ok(None)
+        }
+        KeyCode::Char(c) => {
+            app.current_options_input.push(c);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
 }
 
-fn navigate_section_up(app: &mut App) {
-    app.current_screen = match app.current_screen {
-        CurrentScreen::Response => CurrentScreen::Values,
-        CurrentScreen::Values => CurrentScreen::Url,
-        _ => app.current_screen,
-    };
+/// Handles the one-off "resend with" prompt
+pub async fn handle_resend_with_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Enter => {
+            let request_override = match Override::parse(&app.resend_input) {
+                Ok(request_override) => request_override,
+                Err(e) => {
+                    return Ok(Some(
+                        app.i18n.format("Override error: {error}", &[("error", &e)]),
+                    ))
+                }
+            };
+            app.hide_resend_with();
+            let message = send_current_request(app, Some(&request_override)).await?;
+            Ok(message.or_else(|| {
+                Some(app.i18n.format(
+                    "Sent once with {change} (request unchanged)",
+                    &[("change", &request_override.describe())],
+                ))
+            }))
+        }
+        KeyCode::Backspace => {
+            app.resend_input.pop();
+            Ok(None)
+        }
+        KeyCode::Esc => {
+            app.hide_resend_with();
+            Ok(None)
+        }
+        KeyCode::Char(c) => {
+            app.resend_input.push(c);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles the prompt for the variable and values of a matrix send
+pub async fn handle_matrix_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Enter => match Matrix::parse(&app.matrix_input) {
+            Ok(matrix) => send_matrix(app, &matrix).await,
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Backspace => {
+            app.matrix_input.pop();
+            Ok(None)
+        }
+        KeyCode::Esc => {
+            app.hide_matrix();
+            Ok(None)
+        }
+        KeyCode::Char(c) => {
+            app.matrix_input.push(c);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles the matrix results popup
+pub async fn handle_matrix_results_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    if matches!(
+        key.code,
+        KeyCode::Esc | KeyCode::Char('M') | KeyCode::Char('q')
+    ) {
+        app.hide_matrix();
+    }
+    Ok(None)
+}
+
+/// Handles the prompt for the number of requests and concurrency of a load
+/// test
+pub async fn handle_load_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Enter => match Load::parse(&app.load_input) {
+            Ok(load) => start_load_test(app, load),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Backspace => {
+            app.load_input.pop();
+            Ok(None)
+        }
+        KeyCode::Esc => {
+            app.hide_load_test();
+            Ok(None)
+        }
+        KeyCode::Char(c) => {
+            app.load_input.push(c);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles the progress and report of a load test; closing it stops a test
+/// still running
+pub async fn handle_load_results_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('B') | KeyCode::Char('q') => app.hide_load_test(),
+        // Back to the prompt for another run
+        KeyCode::Char('r') if app.load_test.is_none() => {
+            app.current_screen = CurrentScreen::EditingLoad;
+        }
+        _ => {}
+    }
+    Ok(None)
+}
+
+/// Handles the summary of a run
+pub async fn handle_run_results_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            if let Some(run) = app.run.as_ref() {
+                let done = app.run_results.as_ref().map_or(0, |(_, steps)| steps.len());
+                let message = app.i18n.format(
+                    "Run stopped after {done} of {total} requests",
+                    &[("done", &done), ("total", &run.total)],
+                );
+                app.stop_run();
+                return Ok(Some(message));
+            }
+            app.hide_run_results();
+        }
+        KeyCode::Char('j') | KeyCode::Down => app.run_scroll += 1,
+        KeyCode::Char('k') | KeyCode::Up => app.run_scroll = app.run_scroll.saturating_sub(1),
+        _ => {}
+    }
+    Ok(None)
+}
+
+/// Handles the prompt for the path that triggers re-sends
+pub async fn handle_watch_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Enter => {
+            let path = app.watch_input.clone();
+            match app.start_watch(&path) {
+                Ok(()) => {
+                    app.hide_watch_prompt();
+                    Ok(None)
+                }
+                Err(e) => Ok(Some(app.get_error_message(&e))),
+            }
+        }
+        KeyCode::Tab => {
+            if let Some(completed) = crate::paths::complete_path(&app.watch_input) {
+                app.watch_input = completed;
+            }
+            Ok(None)
+        }
+        KeyCode::Backspace => {
+            app.watch_input.pop();
+            Ok(None)
+        }
+        KeyCode::Esc => {
+            app.hide_watch_prompt();
+            Ok(None)
+        }
+        KeyCode::Char(c) => {
+            app.watch_input.push(c);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles the polling interval prompt
+pub async fn handle_poll_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Enter => {
+            let interval = app.poll_input.clone();
+            match app.start_poll(&interval) {
+                Ok(()) => {
+                    app.hide_poll_prompt();
+                    Ok(None)
+                }
+                Err(e) => Ok(Some(app.get_error_message(&e))),
+            }
+        }
+        KeyCode::Backspace => {
+            app.poll_input.pop();
+            Ok(None)
+        }
+        KeyCode::Esc => {
+            app.hide_poll_prompt();
+            Ok(None)
+        }
+        KeyCode::Char(c) => {
+            app.poll_input.push(c);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles the search prompt over the response body
+pub async fn handle_search_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Enter => match app.run_search() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Backspace => {
+            app.search_input.pop();
+            Ok(None)
+        }
+        KeyCode::Esc => {
+            app.search_input.clear();
+            app.current_screen = CurrentScreen::Response;
+            Ok(None)
+        }
+        KeyCode::Char(c) => {
+            app.search_input.push(c);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles the filter prompt over the response body
+pub async fn handle_filter_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Enter => match app.apply_filter() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Backspace => {
+            app.filter_input.pop();
+            Ok(None)
+        }
+        KeyCode::Esc => {
+            app.filter_input.clear();
+            app.current_screen = CurrentScreen::Response;
+            Ok(None)
+        }
+        KeyCode::Char(c) => {
+            app.filter_input.push(c);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles editing the note attached to a response
+pub async fn handle_note_editing_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Enter => {
+            app.save_note();
+            Ok(None)
+        }
+        KeyCode::Backspace => {
+            app.note_input.pop();
+            Ok(None)
+        }
+        KeyCode::Esc => {
+            app.note_input.clear();
+            app.current_screen = CurrentScreen::Response;
+            Ok(None)
+        }
+        KeyCode::Char(c) => {
+            app.note_input.push(c);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles the export popup
+pub async fn handle_export_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    let formats = ExportFormat::all();
+    let index = formats
+        .iter()
+        .position(|f| *f == app.export_format)
+        .unwrap_or(0);
+
+    match key.code {
+        KeyCode::Esc => {
+            app.hide_export();
+            Ok(None)
+        }
+        KeyCode::Char('h') | KeyCode::Left => {
+            app.export_format = formats[(index + formats.len() - 1) % formats.len()];
+            app.export_scroll = 0;
+            Ok(None)
+        }
+        KeyCode::Char('l') | KeyCode::Right => {
+            app.export_format = formats[(index + 1) % formats.len()];
+            app.export_scroll = 0;
+            Ok(None)
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            let lines = app.export_current(app.export_format).lines().count();
+            if app.export_scroll < lines.saturating_sub(1) {
+                app.export_scroll += 1;
+            }
+            Ok(None)
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.export_scroll = app.export_scroll.saturating_sub(1);
+            Ok(None)
+        }
+        KeyCode::Char('s') | KeyCode::Enter => match app.save_export(app.export_format) {
+            Ok(path) => {
+                app.hide_export();
+                Ok(Some(app.i18n.format(
+                    "Exported to {path}",
+                    &[("path", &path.display())],
+                )))
+            }
+            Err(e) => Ok(Some(
+                app.i18n.format("Export failed: {error}", &[("error", &e)]),
+            )),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// Handles the collection browser
+pub async fn handle_collections_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Esc => {
+            app.hide_collections();
+            Ok(None)
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            if app.collection_selected + 1 < app.collection_entries().len() {
+                app.collection_selected += 1;
+            }
+            Ok(None)
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.collection_selected = app.collection_selected.saturating_sub(1);
+            Ok(None)
+        }
+        KeyCode::Enter => match app.open_selected_collection_request() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Char('i') => {
+            app.start_import();
+            Ok(None)
+        }
+        KeyCode::Char('r') => match app.show_timeline() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Char('R') => match app.collection_run() {
+            Ok((name, requests)) => {
+                app.hide_collections();
+                app.start_run(name, requests);
+                Ok(next_run_step(app))
+            }
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Char('a') => {
+            app.send_on_save = !app.send_on_save;
+            let message = if app.send_on_save {
+                "Send on save for .http files: on"
+            } else {
+                "Send on save for .http files: off"
+            };
+            Ok(Some(app.tr(message).to_string()))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles the timeline of a saved request
+pub async fn handle_timeline_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Esc => {
+            app.hide_timeline();
+            Ok(None)
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            if app.timeline_selected + 1 < app.timeline.len() {
+                app.timeline_selected += 1;
+            }
+            Ok(None)
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.timeline_selected = app.timeline_selected.saturating_sub(1);
+            Ok(None)
+        }
+        KeyCode::Enter => match app.restore_revision() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        _ => Ok(None),
+    }
+}
+
+/// Handles the finder: typing narrows it down, Enter jumps to the selection
+pub async fn handle_finder_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    let Some(finder) = app.finder.as_mut() else {
+        app.hide_finder();
+        return Ok(None);
+    };
+    let control = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Enter => {
+            return match app.open_finder_selection() {
+                Ok(()) => Ok(None),
+                Err(e) => Ok(Some(app.get_error_message(&e))),
+            };
+        }
+        KeyCode::Esc => {
+            app.hide_finder();
+            return Ok(None);
+        }
+        KeyCode::Down => finder.select(true),
+        KeyCode::Up => finder.select(false),
+        KeyCode::Char('n' | 'j') if control => finder.select(true),
+        KeyCode::Char('p' | 'k') if control => finder.select(false),
+        KeyCode::Backspace => {
+            finder.input.pop();
+            finder.update();
+        }
+        KeyCode::Char(c) if !control => {
+            finder.input.push(c);
+            finder.update();
+        }
+        _ => {}
+    }
+    Ok(None)
+}
+
+/// Handles typing the path of a file to import into a collection
+pub async fn handle_import_path_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Enter => match app.load_import() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Tab => {
+            if let Some(completed) = crate::paths::complete_path(&app.import_input) {
+                app.import_input = completed;
+            }
+            Ok(None)
+        }
+        KeyCode::Backspace => {
+            app.import_input.pop();
+            Ok(None)
+        }
+        KeyCode::Esc => {
+            app.cancel_import();
+            Ok(None)
+        }
+        KeyCode::Char(c) => {
+            app.import_input.push(c);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
 }
 
-fn open_method_dropdown(app: &mut App) {
-    app.method_dropdown_open = true;
-    app.method_dropdown_selected = match app.selected_method {
-        HttpMethod::GET => 0,
-        HttpMethod::POST => 1,
-        HttpMethod::PUT => 2,
-        HttpMethod::DELETE => 3,
-    };
-}
+/// Handles the review of an import's duplicates
+pub async fn handle_import_review_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.move_import_selection(true);
+            Ok(None)
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.move_import_selection(false);
+            Ok(None)
+        }
+        KeyCode::Char('m') => {
+            app.resolve_import(Resolution::Merge, false);
+            Ok(None)
+        }
+        KeyCode::Char('s') => {
+            app.resolve_import(Resolution::Skip, false);
+            Ok(None)
+        }
+        KeyCode::Char('d') => {
+            app.resolve_import(Resolution::Duplicate, false);
+            Ok(None)
+        }
+        KeyCode::Char('M') => {
+            app.resolve_import(Resolution::Merge, true);
+            Ok(None)
+        }
+        KeyCode::Char('S') => {
+            app.resolve_import(Resolution::Skip, true);
+            Ok(None)
+        }
+        KeyCode::Char('D') => {
+            app.resolve_import(Resolution::Duplicate, true);
+            Ok(None)
+        }
+        KeyCode::Enter => match app.apply_import() {
+            Ok(message) => Ok(Some(message)),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Esc => {
+            app.cancel_import();
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles the copy-format menu
+pub async fn handle_copy_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    let formats = CopyFormat::ALL;
+    let index = formats
+        .iter()
+        .position(|f| *f == app.copy_format)
+        .unwrap_or(0);
+
+    match key.code {
+        KeyCode::Esc => {
+            app.hide_copy_menu();
+            Ok(None)
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.copy_format = formats[(index + 1) % formats.len()];
+            Ok(None)
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.copy_format = formats[(index + formats.len() - 1) % formats.len()];
+            Ok(None)
+        }
+        KeyCode::Enter => {
+            app.hide_copy_menu();
+            match app.copy_response(app.copy_format) {
+                Ok(()) => Ok(Some(app.i18n.format(
+                    "Copied: {format}",
+                    &[("format", &app.tr(app.copy_format.label()))],
+                ))),
+                Err(e) => Ok(Some(app.get_error_message(&e))),
+            }
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles the cookie list
+pub async fn handle_cookies_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Esc => {
+            app.hide_cookies();
+            Ok(None)
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            if app.cookie_selected + 1 < app.cookie_jar.list().len() {
+                app.cookie_selected += 1;
+            }
+            Ok(None)
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.cookie_selected = app.cookie_selected.saturating_sub(1);
+            Ok(None)
+        }
+        KeyCode::Char('a') => {
+            app.start_cookie_edit(true);
+            Ok(None)
+        }
+        KeyCode::Char('i') | KeyCode::Enter => {
+            if app.selected_cookie().is_some() {
+                app.start_cookie_edit(false);
+            }
+            Ok(None)
+        }
+        KeyCode::Char('d') => {
+            app.delete_selected_cookie();
+            Ok(None)
+        }
+        KeyCode::Char('D') => {
+            app.cookie_jar.clear();
+            app.cookie_selected = 0;
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles the cookie input line
+pub async fn handle_cookie_editing_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Esc => {
+            app.cookie_input.clear();
+            app.cookie_editing = None;
+            app.current_screen = CurrentScreen::Cookies;
+            Ok(None)
+        }
+        KeyCode::Enter => match app.save_cookie_edit() {
+            Ok(()) => Ok(None),
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        },
+        KeyCode::Backspace => {
+            app.cookie_input.pop();
+            Ok(None)
+        }
+        KeyCode::Char(c) => {
+            app.cookie_input.push(c);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles the latency histogram popup
+pub async fn handle_latency_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    if matches!(key.code, KeyCode::Esc | KeyCode::Char('L')) {
+        app.hide_latency();
+    }
+    Ok(None)
+}
+
+/// Handles the signature preview popup
+pub async fn handle_signing_preview_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('p') => {
+            app.hide_signing_preview();
+            Ok(None)
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.signing_scroll = app.signing_scroll.saturating_add(1);
+            Ok(None)
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.signing_scroll = app.signing_scroll.saturating_sub(1);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles the dry run popup
+pub async fn handle_dry_run_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('P') => {
+            app.hide_dry_run();
+            Ok(None)
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.dry_run_scroll = app.dry_run_scroll.saturating_add(1);
+            Ok(None)
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.dry_run_scroll = app.dry_run_scroll.saturating_sub(1);
+            Ok(None)
+        }
+        KeyCode::Enter => {
+            app.hide_dry_run();
+            start_send(app, None)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Handles help screen navigation
+pub async fn handle_help_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    match key.code {
+        KeyCode::Esc => {
+            app.hide_help();
+            Ok(None)
+        }
+        KeyCode::Char('j') => {
+            let help_content = app.get_help_content();
+            if app.help_scroll < help_content.len().saturating_sub(1) {
+                app.help_scroll = app.help_scroll.saturating_add(1);
+            }
+            Ok(None)
+        }
+        KeyCode::Char('k') => {
+            app.help_scroll = app.help_scroll.saturating_sub(1);
+            Ok(None)
+        }
+        _ => Ok(None),
+    }
+}
+
+// Helper functions for navigation and actions
+
+fn navigate_section_down(app: &mut App) {
+    app.current_screen = match app.current_screen {
+        CurrentScreen::Url => CurrentScreen::Values,
+        CurrentScreen::Values => CurrentScreen::Response,
+        _ => app.current_screen,
+    };
+}
+
+fn navigate_section_up(app: &mut App) {
+    // The sections above are hidden while zoomed
+    app.response_zoomed = false;
+    app.current_screen = match app.current_screen {
+        CurrentScreen::Response => CurrentScreen::Values,
+        CurrentScreen::Values => CurrentScreen::Url,
+        _ => app.current_screen,
+    };
+}
+
+fn open_method_dropdown(app: &mut App) {
+    app.response_zoomed = false;
+    app.method_dropdown_open = true;
+    app.method_dropdown_selected = match app.selected_method {
+        HttpMethod::GET => 0,
+        HttpMethod::POST => 1,
+        HttpMethod::PUT => 2,
+        HttpMethod::DELETE => 3,
+    };
+}
+
+async fn handle_send_request(app: &mut App) -> Result<Option<String>> {
+    start_send(app, None)
+}
+
+/// Sends the current request and waits for its response, optionally with
+/// one header or param changed
+///
+/// The override only applies to this send; the tab's request is untouched.
+pub async fn send_current_request(
+    app: &mut App,
+    request_override: Option<&Override>,
+) -> Result<Option<String>> {
+    if let Some(message) = start_send(app, request_override)? {
+        return Ok(Some(message));
+    }
+    let index = app.selected_tab;
+    let Some(mut in_flight) = app.tabs[index].in_flight.take() else {
+        return Ok(None);
+    };
+    let outcome = in_flight.finish().await;
+    Ok(finish_send(app, index, in_flight, outcome))
+}
+
+/// Starts sending the current request in the background
+///
+/// The response is picked up by [`poll_sends`]; meanwhile the tab shows how
+/// long it has been waiting.
+pub fn start_send(app: &mut App, request_override: Option<&Override>) -> Result<Option<String>> {
+    if app.tabs[app.selected_tab].in_flight.is_some() {
+        return Ok(Some(
+            app.tr("This tab is still waiting for its response")
+                .to_string(),
+        ));
+    }
+
+    // Validate request before sending
+    if let Err(e) = app.validate_current_request() {
+        return Ok(Some(
+            app.i18n
+                .format("Validation error: {error}", &[("error", &e)]),
+        ));
+    }
+
+    // Make sure unsaved edits are part of the request
+    if let Err(e) = app.save_current_tab_state() {
+        return Ok(Some(
+            app.i18n
+                .format("Failed to save tab state: {error}", &[("error", &e)]),
+        ));
+    }
+
+    app.response_part_selected = 0;
+    app.table_column = 0;
+    spawn_send(app, app.selected_tab, request_override)
+}
+
+/// Starts sending the request of tab `index` in the background, as it was
+/// last saved when it isn't the current tab; for sends nobody asked for
+/// right then, like polling
+pub fn start_tab_send(app: &mut App, index: usize) -> Result<Option<String>> {
+    if index == app.selected_tab {
+        return start_send(app, None);
+    }
+    let tab = &app.tabs[index];
+    if tab.in_flight.is_some() {
+        return Ok(None);
+    }
+    if let Err(e) = tab
+        .request
+        .validate_url()
+        .and_then(|()| tab.request.validate_headers())
+    {
+        return Ok(Some(app.i18n.format(
+            "{tab}: Validation error: {error}",
+            &[("tab", &tab.name), ("error", &e)],
+        )));
+    }
+    spawn_send(app, index, None)
+}
+
+/// Starts the sends of the tabs at `indices`, each once, returning the
+/// first message any of them needs to show
+pub fn start_tab_sends(app: &mut App, mut indices: Vec<usize>) -> Result<Option<String>> {
+    indices.sort_unstable();
+    indices.dedup();
+    let mut message = None;
+    for index in indices {
+        message = message.or(start_tab_send(app, index)?);
+    }
+    Ok(message)
+}
+
+/// Lints, resolves and sends the saved request of tab `index`
+fn spawn_send(
+    app: &mut App,
+    index: usize,
+    request_override: Option<&Override>,
+) -> Result<Option<String>> {
+    // Lint the request as written, with the override but no variables filled in
+    let mut written = app.tabs[index].request.clone();
+    if let Some(request_override) = request_override {
+        request_override.apply(&mut written);
+    }
+    let findings = app.lint(&written);
+    if lint::has_errors(&findings) {
+        return Ok(Some(app.i18n.format(
+            "Not sent, the lint rules found:\n{findings}",
+            &[("findings", &lint::format_findings(&findings))],
+        )));
+    }
+
+    let env = app.send_environment();
+    let (mut request, _) = environment::resolve_request(
+        &app.with_default_headers(&app.tabs[index].request),
+        env.as_ref(),
+        false,
+    );
+    request.options = app.effective_options(&request.options);
+    request.revalidate = true;
+    if let Some(request_override) = request_override {
+        request_override.apply(&mut request);
+    }
+
+    let entry = app.history_entry_for(
+        &app.tabs[index].request,
+        env.as_ref(),
+        Ok(0),
+        Duration::ZERO,
+        None,
+    );
+    let tab = &mut app.tabs[index];
+    tab.retry = None;
+    tab.in_flight = Some(InFlight::spawn(
+        request,
+        tab.request.clone(),
+        env,
+        findings,
+        entry,
+    ));
+    Ok(None)
+}
+
+/// Shows the current request as a send would put it on the wire, without
+/// sending it; `{{secret:…}}` references are left as written, so nothing
+/// is asked of a password manager
+pub async fn preview_send(app: &mut App) -> Result<Option<String>> {
+    if let Err(e) = app.validate_current_request() {
+        return Ok(Some(
+            app.i18n
+                .format("Validation error: {error}", &[("error", &e)]),
+        ));
+    }
+    if let Err(e) = app.save_current_tab_state() {
+        return Ok(Some(
+            app.i18n
+                .format("Failed to save tab state: {error}", &[("error", &e)]),
+        ));
+    }
+
+    let env = app.send_environment();
+    let (mut request, _) = environment::resolve_request(
+        &app.with_default_headers(&app.tabs[app.selected_tab].request),
+        env.as_ref(),
+        false,
+    );
+    request.options = app.effective_options(&request.options);
+    match request::preview_request(&request).await {
+        Ok(sent) => {
+            app.show_dry_run(sent);
+            Ok(None)
+        }
+        Err(e) => Ok(Some(
+            app.i18n.format("Dry run failed: {error}", &[("error", &e)]),
+        )),
+    }
+}
+
+/// Picks up the responses of background sends, returning a message to show
+/// if any of them needs one
+pub fn poll_sends(app: &mut App) -> Option<String> {
+    let mut message = None;
+    for index in 0..app.tabs.len() {
+        let Some(outcome) = app.tabs[index].in_flight.as_mut().and_then(InFlight::poll) else {
+            continue;
+        };
+        if let Some(in_flight) = app.tabs[index].in_flight.take() {
+            let finished = finish_send(app, index, in_flight, outcome).map(|finished| {
+                // Name the tab when its response landed out of sight
+                if index == app.selected_tab {
+                    finished
+                } else {
+                    format!("{}: {}", app.tabs[index].name, finished)
+                }
+            });
+            message = finished.or(message);
+        }
+    }
+    message = poll_run(app).or(message);
+    app.poll_load_test();
+    app.poll_tls_probe();
+    message
+}
+
+/// Records a finished send and shows its response in the tab it came from
+fn finish_send(
+    app: &mut App,
+    index: usize,
+    in_flight: InFlight,
+    outcome: anyhow::Result<RawResponse>,
+) -> Option<String> {
+    let elapsed = in_flight.elapsed();
+    let mut entry = in_flight.entry.clone();
+    match outcome {
+        Ok(mut raw) => {
+            entry.status = Ok(raw.status_code);
+            entry.elapsed = raw.metrics.elapsed;
+            let recorded = app.history.record(&entry);
+            if let Some(tls) = raw.tls.as_mut() {
+                tls.pinned = in_flight
+                    .env
+                    .as_ref()
+                    .and_then(|env| env.pin_for(&tls.host))
+                    .map(str::to_string);
+            }
+            let tab = &mut app.tabs[index];
+            tab.latency.record(&in_flight.url, &raw.metrics);
+            tab.sent = Some(in_flight.written.clone());
+
+            let parsed = match raw.wire_bytes {
+                Some(bytes) => Ok(crate::logic::response::Response::wire(
+                    raw.status_code,
+                    raw.headers.clone(),
+                    bytes,
+                )),
+                None => crate::logic::response::Response::new(
+                    raw.status_code,
+                    raw.headers.clone(),
+                    raw.body.clone(),
+                ),
+            };
+            let (mut response, mut message) = match parsed {
+                Ok(response) => (response, None),
+                Err(e) => (
+                    // Still create response with unchecked method for display
+                    crate::logic::response::Response::new_unchecked(
+                        raw.status_code,
+                        raw.headers,
+                        raw.body,
+                    ),
+                    Some(
+                        app.i18n
+                            .format("Response parsing error: {error}", &[("error", &e)]),
+                    ),
+                ),
+            };
+            response.metrics = Some(raw.metrics);
+            response.transfer = raw.transfer;
+            if let Some(tls) = raw.tls.as_ref().filter(|tls| tls.pin_mismatch()) {
+                message = Some(app.i18n.format(
+                    "Certificate for {host} does not match the pinned fingerprint! \
+                     Wrong cluster or an intercepting proxy? Press T for details.",
+                    &[("host", &tls.host)],
+                ));
+            }
+            if let Err(e) = recorded {
+                message.get_or_insert(
+                    app.i18n
+                        .format("Failed to record history: {error}", &[("error", &e)]),
+                );
+            }
+            if !in_flight.findings.is_empty() {
+                message.get_or_insert(app.i18n.format(
+                    "Lint warnings:\n{findings}",
+                    &[("findings", &lint::format_findings(&in_flight.findings))],
+                ));
+            }
+            response.tls = raw.tls;
+            response.sent = Some(raw.sent);
+            response.cached = raw.cached;
+            response.version = Some(raw.version);
+            response.charset = raw.charset;
+            response.bytes = raw.binary;
+            if let Some(stream) = raw.stream {
+                response.attach_stream(stream);
+            }
+            let now = chrono::Utc::now();
+            response.rate_limit = RateLimit::from_headers(&response.headers, now);
+            if response.status_code == 429 && app.config.rate_limit.auto_retry {
+                let settings = &app.config.rate_limit;
+                let retry_at = response.rate_limit.as_ref().and_then(RateLimit::retry_at);
+                match retry_at.map(|at| (at, (at - now).num_seconds().max(0))) {
+                    None => {
+                        message.get_or_insert(
+                            app.i18n
+                                .tr("Rate limited without Retry-After or a reset time, so not retried")
+                                .to_string(),
+                        );
+                    }
+                    Some((_, wait)) if wait as u64 > settings.max_wait => {
+                        message.get_or_insert(app.i18n.format(
+                            "Rate limited for {wait}, longer than max_wait, so not retried",
+                            &[("wait", &format_wait(wait))],
+                        ));
+                    }
+                    Some(_) if in_flight.retries >= settings.max_retries => {
+                        message.get_or_insert(app.i18n.format(
+                            "Still rate limited after {count} retries",
+                            &[("count", &in_flight.retries)],
+                        ));
+                    }
+                    Some((at, _)) => tab.retry = Some(in_flight.retry(at)),
+                }
+            }
+            // A 304 is checked as the copy it stands for, not its empty body
+            let copy = response.cached_copy();
+            let checked = copy.as_ref().unwrap_or(&response);
+            let tests = assertions::run(&tab.request.tests, checked);
+            let (values, errors) = assertions::extract(&tab.request.tests, checked);
+            let polled = app.poll.as_ref().is_some_and(|(id, _)| *id == tab.id);
+            let changed = polled
+                .then(|| tab.latest_response())
+                .flatten()
+                .map(|previous| match previous.cached_copy() {
+                    Some(previous) => previous.body != checked.body,
+                    None => previous.body != checked.body,
+                });
+            response.tests = tests;
+            response.changed = changed;
+            tab.push_response(response, app.config.ui.responses_kept);
+            if index == app.selected_tab {
+                app.refresh_tree();
+            }
+            if let Some(error) = errors.first() {
+                message.get_or_insert(
+                    app.i18n
+                        .format("Could not set a variable, {error}", &[("error", error)]),
+                );
+            }
+            if !values.is_empty() {
+                let names: Vec<_> = values.iter().map(|(name, _)| name.as_str()).collect();
+                app.show_toast(
+                    app.i18n
+                        .format("Set {names}", &[("names", &names.join(", "))]),
+                );
+                app.extracted.extend(values);
+            }
+            message
+        }
+        Err(e) => {
+            entry.status = Err(e.to_string());
+            entry.elapsed = elapsed;
+            // The failed send is what matters here
+            let _ = app.history.record(&entry);
+            Some(app.i18n.format("Request failed: {error}", &[("error", &e)]))
+        }
+    }
+}
+
+/// Sends the current request once per matrix value, all at the same time
+pub async fn send_matrix(app: &mut App, matrix: &Matrix) -> Result<Option<String>> {
+    if let Err(e) = app.validate_current_request() {
+        return Ok(Some(
+            app.i18n
+                .format("Validation error: {error}", &[("error", &e)]),
+        ));
+    }
+    if let Err(e) = app.save_current_tab_state() {
+        return Ok(Some(
+            app.i18n
+                .format("Failed to save tab state: {error}", &[("error", &e)]),
+        ));
+    }
+    let findings = app.lint(&app.tabs[app.selected_tab].request);
+    if lint::has_errors(&findings) {
+        return Ok(Some(app.i18n.format(
+            "Not sent, the lint rules found:\n{findings}",
+            &[("findings", &lint::format_findings(&findings))],
+        )));
+    }
+
+    let base = app.send_environment();
+    let mut requests = Vec::new();
+    for value in &matrix.values {
+        let env = matrix.environment(base.as_ref(), value);
+        let (mut request, used) = environment::resolve_request(
+            &app.with_default_headers(&app.tabs[app.selected_tab].request),
+            Some(&env),
+            false,
+        );
+        if !used.contains(&matrix.variable) {
+            return Ok(Some(app.i18n.format(
+                "The request doesn't use {{{variable}}}",
+                &[("variable", &matrix.variable)],
+            )));
+        }
+        request.options = app.effective_options(&request.options);
+        requests.push((value.clone(), request));
+    }
+
+    let outcomes = matrix::send_all(requests).await;
+    let mut message = None;
+    for outcome in &outcomes {
+        let env = matrix.environment(base.as_ref(), &outcome.value);
+        let run = format!("matrix {}={}", matrix.variable, outcome.value);
+        let entry = app.history_entry(
+            Some(&env),
+            outcome.status.clone(),
+            outcome.elapsed,
+            Some(run),
+        );
+        if let Err(e) = app.history.record(&entry) {
+            message = Some(
+                app.i18n
+                    .format("Failed to record history: {error}", &[("error", &e)]),
+            );
+        }
+    }
+    app.show_matrix_results(matrix.variable.clone(), outcomes);
+    Ok(message)
+}
+
+/// Starts sending the current request as `load` says, in the background
+pub fn start_load_test(app: &mut App, load: Load) -> Result<Option<String>> {
+    if let Err(e) = app.validate_current_request() {
+        return Ok(Some(
+            app.i18n
+                .format("Validation error: {error}", &[("error", &e)]),
+        ));
+    }
+    if let Err(e) = app.save_current_tab_state() {
+        return Ok(Some(
+            app.i18n
+                .format("Failed to save tab state: {error}", &[("error", &e)]),
+        ));
+    }
+    let findings = app.lint(&app.tabs[app.selected_tab].request);
+    if lint::has_errors(&findings) {
+        return Ok(Some(app.i18n.format(
+            "Not sent, the lint rules found:\n{findings}",
+            &[("findings", &lint::format_findings(&findings))],
+        )));
+    }
+
+    let env = app.send_environment();
+    let (mut request, _) = environment::resolve_request(
+        &app.with_default_headers(&app.tabs[app.selected_tab].request),
+        env.as_ref(),
+        false,
+    );
+    request.options = app.effective_options(&request.options);
+    app.start_load_test(request, load);
+    Ok(None)
+}
+
+/// Starts the next request of the run, recording the ones the lint rules
+/// stop without sending; ends the run after the last. Each request is sent
+/// with the variables set by the ones before it.
+fn next_run_step(app: &mut App) -> Option<String> {
+    let mut message = None;
+    while let Some((name, written)) = app.run.as_mut().and_then(|run| run.pending.pop_front()) {
+        let env = app.send_environment();
+        if lint::has_errors(&app.lint(&written)) {
+            let error = app.tr("Not sent, the lint rules found errors").to_string();
+            let step = (name, written, env);
+            message =
+                record_run_step(app, step, Err(error), Duration::ZERO, Vec::new()).or(message);
+            continue;
+        }
+        let (mut request, _) =
+            environment::resolve_request(&app.with_default_headers(&written), env.as_ref(), false);
+        request.options = app.effective_options(&request.options);
+        if let Some(run) = app.run.as_mut() {
+            run.current = Some(RunSend::spawn(name, request, written, env));
+        }
+        return message;
+    }
+    if let Some(run) = app.run.take() {
+        let steps = app.run_results.as_ref().map_or(&[][..], |(_, steps)| steps);
+        let record = runner::record(run.title, steps, chrono::Utc::now());
+        if let Err(e) = app.history.record_run(&record) {
+            message = Some(
+                app.i18n
+                    .format("Failed to record history: {error}", &[("error", &e)]),
+            );
+        }
+    }
+    message
+}
+
+/// Checks the response of the request of the run being sent once it is
+/// in, and starts the next
+fn poll_run(app: &mut App) -> Option<String> {
+    let outcome = app.run.as_mut()?.current.as_mut()?.poll()?;
+    let send = app.run.as_mut()?.current.take()?;
+    let mut message = None;
+    let mut tests = Vec::new();
+    let (status, elapsed) = match outcome {
+        Ok(raw) => {
+            let mut response =
+                Response::new(raw.status_code, raw.headers.clone(), raw.body.clone())
+                    .unwrap_or_else(|_| {
+                        Response::new_unchecked(raw.status_code, raw.headers, raw.body)
+                    });
+            response.metrics = Some(raw.metrics);
+            tests = assertions::run(&send.written.tests, &response);
+            let (values, errors) = assertions::extract(&send.written.tests, &response);
+            app.extracted.extend(values);
+            if let Some(error) = errors.first() {
+                message = Some(app.i18n.format(
+                    "{request}: could not set a variable, {error}",
+                    &[("request", &send.name), ("error", error)],
+                ));
+            }
+            (Ok(raw.status_code), raw.metrics.elapsed)
+        }
+        Err(e) => (Err(e.to_string()), send.started.elapsed()),
+    };
+    let step = (send.name, send.written, send.env);
+    let recorded = record_run_step(app, step, status, elapsed, tests);
+    let next = next_run_step(app);
+    message.or(recorded).or(next)
+}
+
+/// Records a request of the run in the history and adds it to the results
+fn record_run_step(
+    app: &mut App,
+    (name, written, env): (String, Request, Option<Environment>),
+    status: std::result::Result<u16, String>,
+    elapsed: Duration,
+    tests: Vec<TestResult>,
+) -> Option<String> {
+    let title = app.run.as_ref().map(|run| run.title.clone())?;
+    let entry = app.history_entry_for(
+        &written,
+        env.as_ref(),
+        status.clone(),
+        elapsed,
+        Some(format!("run {}", title)),
+    );
+    let message = app.history.record(&entry).err().map(|e| {
+        app.i18n
+            .format("Failed to record history: {error}", &[("error", &e)])
+    });
+    if let Some((_, steps)) = app.run_results.as_mut() {
+        steps.push(Step {
+            name,
+            method: written.method.to_string(),
+            url: written.url,
+            status,
+            elapsed,
+            tests,
+        });
+    }
+    message
+}
+
+fn handle_new_tab(app: &mut App) -> Result<Option<String>> {
+    if let Err(e) = app.add_new_tab() {
+        Ok(Some(
+            app.i18n.format("Tab error: {error}", &[("error", &e)]),
+        ))
+    } else {
+        Ok(None)
+    }
+}
+
+fn handle_duplicate_tab(app: &mut App) -> Result<Option<String>> {
+    if let Err(e) = app.duplicate_current_tab() {
+        Ok(Some(
+            app.i18n.format("Tab error: {error}", &[("error", &e)]),
+        ))
+    } else {
+        Ok(None)
+    }
+}
+
+fn handle_close_tab(app: &mut App) -> Result<Option<String>> {
+    let tab = &app.tabs[app.selected_tab];
+    if let Some(in_flight) = tab.in_flight.as_ref() {
+        if app.tabs.len() > 1 && app.close_warned != Some(tab.id) {
+            app.close_warned = Some(tab.id);
+            return Ok(Some(app.i18n.format(
+                "Still waiting for the response from {url}. Press x again to cancel the send and close the tab.",
+                &[("url", &in_flight.url)],
+            )));
+        }
+    }
+    if let Err(e) = app.close_current_tab() {
+        Ok(Some(
+            app.i18n.format("Tab error: {error}", &[("error", &e)]),
+        ))
+    } else {
+        Ok(None)
+    }
+}
+
+fn handle_next_tab(app: &mut App) -> Result<Option<String>> {
+    if let Err(e) = app.next_tab() {
+        Ok(Some(
+            app.i18n.format("Tab error: {error}", &[("error", &e)]),
+        ))
+    } else {
+        Ok(None)
+    }
+}
+
+fn handle_prev_tab(app: &mut App) -> Result<Option<String>> {
+    if let Err(e) = app.prev_tab() {
+        Ok(Some(
+            app.i18n.format("Tab error: {error}", &[("error", &e)]),
+        ))
+    } else {
+        Ok(None)
+    }
+}
+
+fn handle_move_tab(app: &mut App, offset: isize) -> Result<Option<String>> {
+    if let Err(e) = app.move_tab(offset) {
+        Ok(Some(
+            app.i18n.format("Tab error: {error}", &[("error", &e)]),
+        ))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::code::Language;
+    use crate::test_server::TestServer;
+    use crate::ui::layouts::LayoutMode;
+    use crossterm::event::{KeyEventKind, KeyEventState};
+
+    fn create_key_event(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    fn create_key_event_with_ctrl(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    fn render_screen(app: &mut App, width: u16, height: u16) -> String {
+        let backend = ratatui::backend::TestBackend::new(width, height);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| crate::ui::ui(f, app, &None)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_navigation_keys() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Url;
+
+        // Test Ctrl+j navigation
+        let key = create_key_event_with_ctrl(KeyCode::Char('j'));
+        let result = handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert!(result.is_none());
+        assert_eq!(app.current_screen, CurrentScreen::Values);
+
+        // Test Ctrl+k navigation
+        let key = create_key_event_with_ctrl(KeyCode::Char('k'));
+        let result = handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert!(result.is_none());
+        assert_eq!(app.current_screen, CurrentScreen::Url);
+    }
+
+    #[tokio::test]
+    async fn test_url_editing() {
+        let mut app = App::new();
+
+        // Start editing
+        let key = create_key_event(KeyCode::Char('u'));
+        let result = handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert!(result.is_none());
+        assert_eq!(app.current_screen, CurrentScreen::EditingUrl);
+
+        // Type some text
+        let key = create_key_event(KeyCode::Char('h'));
+        let result = handle_url_editing_keys(&mut app, key).await.unwrap();
+        assert!(result.is_none());
+        assert_eq!(app.url_input, "h");
+
+        // Exit editing
+        let key = create_key_event(KeyCode::Esc);
+        let result = handle_url_editing_keys(&mut app, key).await.unwrap();
+        assert!(result.is_none());
+        assert_eq!(app.current_screen, CurrentScreen::Url);
+    }
+
+    #[tokio::test]
+    async fn test_url_editing_cursor() {
+        let mut app = App::new();
+        app.url_input = "https://api.test/users/42".to_string();
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('u')))
+            .await
+            .unwrap();
+
+        let ctrl = |c| KeyEvent {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        };
+        handle_url_editing_keys(&mut app, ctrl('w')).await.unwrap();
+        assert_eq!(app.url_input, "https://api.test/users/");
+
+        for code in [
+            KeyCode::Home,
+            KeyCode::Right,
+            KeyCode::Right,
+            KeyCode::Right,
+        ] {
+            handle_url_editing_keys(&mut app, create_key_event(code))
+                .await
+                .unwrap();
+        }
+        handle_url_editing_keys(&mut app, create_key_event(KeyCode::Delete))
+            .await
+            .unwrap();
+        assert_eq!(app.url_input, "htts://api.test/users/");
+        handle_url_editing_keys(&mut app, create_key_event(KeyCode::Char('p')))
+            .await
+            .unwrap();
+        handle_url_editing_keys(&mut app, create_key_event(KeyCode::Backspace))
+            .await
+            .unwrap();
+        handle_url_editing_keys(&mut app, create_key_event(KeyCode::Char('p')))
+            .await
+            .unwrap();
+        handle_url_editing_keys(&mut app, create_key_event(KeyCode::End))
+            .await
+            .unwrap();
+        handle_url_editing_keys(&mut app, create_key_event(KeyCode::Char('7')))
+            .await
+            .unwrap();
+        assert_eq!(app.url_input, "https://api.test/users/7");
+
+        // Pastes land at the cursor too
+        handle_url_editing_keys(&mut app, create_key_event(KeyCode::Left))
+            .await
+            .unwrap();
+        app.paste_into_url("1\n");
+        assert_eq!(app.url_input, "https://api.test/users/17");
+
+        handle_url_editing_keys(&mut app, ctrl('u')).await.unwrap();
+        assert!(app.url_input.is_empty());
+        handle_url_editing_keys(&mut app, create_key_event(KeyCode::Char('x')))
+            .await
+            .unwrap();
+        assert_eq!(app.url_input, "x");
+    }
+
+    #[tokio::test]
+    async fn test_url_suggestions() {
+        use crate::storage::tests::entry;
+
+        let mut app = App::new();
+        for (seconds, url) in [
+            (1, "https://api.test/users"),
+            (2, "https://api.test/orders"),
+            (3, "https://other.test/"),
+            (4, "https://api.test/users"),
+        ] {
+            app.history
+                .record(&entry(seconds, "GET", url, 200))
+                .unwrap();
+        }
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('u')))
+            .await
+            .unwrap();
+        for c in "https://API.test/".chars() {
+            handle_url_editing_keys(&mut app, create_key_event(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        // Newest first, each URL once
+        assert_eq!(
+            app.url_suggestions,
+            vec!["https://api.test/users", "https://api.test/orders"]
+        );
+
+        let key = |code| create_key_event(code);
+        handle_url_editing_keys(&mut app, key(KeyCode::Up))
+            .await
+            .unwrap();
+        assert_eq!(app.url_suggestion_selected, Some(1));
+        handle_url_editing_keys(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(app.url_input, "https://api.test/orders");
+        assert_eq!(app.current_screen, CurrentScreen::EditingUrl);
+        assert!(app.url_suggestions.is_empty());
+
+        // Esc closes the suggestions first, then leaves editing
+        handle_url_editing_keys(&mut app, key(KeyCode::Backspace))
+            .await
+            .unwrap();
+        assert_eq!(app.url_suggestions, vec!["https://api.test/orders"]);
+        handle_url_editing_keys(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert!(app.url_suggestions.is_empty());
+        assert_eq!(app.current_screen, CurrentScreen::EditingUrl);
+        handle_url_editing_keys(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Url);
+    }
+
+    #[tokio::test]
+    async fn test_header_name_completion() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::EditingHeaders;
+        app.headers_input
+            .push(("X-Tenant".to_string(), "acme".to_string()));
+        let key = |code| create_key_event(code);
+
+        for c in "content-t".chars() {
+            handle_headers_editing_keys(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        assert_eq!(app.header_suggestions, vec!["Content-Type"]);
+        handle_headers_editing_keys(&mut app, key(KeyCode::Tab))
+            .await
+            .unwrap();
+        assert_eq!(app.current_header_key, "Content-Type:");
+        // The media types are offered for the value right away
+        assert_eq!(app.header_suggestions.len(), 5);
+        for c in "text/plain".chars() {
+            handle_headers_editing_keys(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        assert!(app.header_suggestions.is_empty());
+        handle_headers_editing_keys(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(
+            app.headers_input[1],
+            ("Content-Type".to_string(), "text/plain".to_string())
+        );
+
+        // Headers used before come first; Enter takes the selected one
+        for c in "x-t".chars() {
+            handle_headers_editing_keys(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        assert_eq!(app.header_suggestions, vec!["X-Tenant"]);
+        handle_headers_editing_keys(&mut app, key(KeyCode::Down))
+            .await
+            .unwrap();
+        handle_headers_editing_keys(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(app.current_header_key, "X-Tenant:");
+        assert!(app.header_suggestions.is_empty());
+
+        // Esc closes the suggestions before it cancels the header
+        handle_headers_editing_keys(&mut app, key(KeyCode::Backspace))
+            .await
+            .unwrap();
+        assert_eq!(app.header_suggestions.len(), 0);
+        handle_headers_editing_keys(&mut app, key(KeyCode::Backspace))
+            .await
+            .unwrap();
+        assert_eq!(app.header_suggestions, vec!["X-Tenant"]);
+        handle_headers_editing_keys(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingHeaders);
+        handle_headers_editing_keys(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Values);
+    }
+
+    #[tokio::test]
+    async fn test_content_type_values() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::EditingHeaders;
+        let key = |code| create_key_event(code);
+
+        for c in "Content-Type:app".chars() {
+            handle_headers_editing_keys(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            app.header_suggestions,
+            vec![
+                "application/json",
+                "application/xml",
+                "application/x-www-form-urlencoded"
+            ]
+        );
+        handle_headers_editing_keys(&mut app, key(KeyCode::Up))
+            .await
+            .unwrap();
+        handle_headers_editing_keys(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(
+            app.current_header_value,
+            "application/x-www-form-urlencoded"
+        );
+        assert!(app.header_suggestions.is_empty());
+        handle_headers_editing_keys(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(
+            app.headers_input,
+            vec![(
+                "Content-Type".to_string(),
+                "application/x-www-form-urlencoded".to_string()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_edit_and_delete_entries() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Values;
+        app.values_screen = ValuesScreen::Headers;
+        for (name, value) in [("Accept", "text/html"), ("X-Trace", "1"), ("X-Debug", "on")] {
+            app.headers_input
+                .push((name.to_string(), value.to_string()));
+        }
+        let key = |code| create_key_event(code);
+        // Without a pick 'd' does nothing
+        handle_main_screen_keys(&mut app, key(KeyCode::Char('d')))
+            .await
+            .unwrap();
+        assert_eq!(app.headers_input.len(), 3);
+        handle_main_screen_keys(&mut app, key(KeyCode::Char('j')))
+            .await
+            .unwrap();
+        handle_main_screen_keys(&mut app, key(KeyCode::Char('j')))
+            .await
+            .unwrap();
+        assert_eq!(app.selected_header, Some(1));
+        handle_main_screen_keys(&mut app, key(KeyCode::Char('e')))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingHeaders);
+        assert_eq!(app.current_header_key, "X-Trace:");
+        assert_eq!(app.current_header_value, "1");
+        handle_headers_editing_keys(&mut app, key(KeyCode::Backspace))
+            .await
+            .unwrap();
+        handle_headers_editing_keys(&mut app, key(KeyCode::Char('2')))
+            .await
+            .unwrap();
+        handle_headers_editing_keys(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        // Replaced in place, back on the list
+        assert_eq!(
+            app.headers_input[1],
+            ("X-Trace".to_string(), "2".to_string())
+        );
+        assert_eq!(app.headers_input.len(), 3);
+        assert_eq!(app.current_screen, CurrentScreen::Values);
+
+        // Deleting the last one picks the one before it
+        handle_main_screen_keys(&mut app, key(KeyCode::Char('j')))
+            .await
+            .unwrap();
+        handle_main_screen_keys(&mut app, key(KeyCode::Char('d')))
+            .await
+            .unwrap();
+        assert_eq!(app.headers_input.len(), 2);
+        assert_eq!(app.selected_header, Some(1));
+
+        // Esc leaves the param as it was
+        app.values_screen = ValuesScreen::Params;
+        app.params_input
+            .push(("limit".to_string(), "10".to_string()));
+        handle_main_screen_keys(&mut app, key(KeyCode::Char('k')))
+            .await
+            .unwrap();
+        handle_main_screen_keys(&mut app, key(KeyCode::Char('e')))
+            .await
+            .unwrap();
+        assert_eq!(app.current_param_key, "limit=");
+        handle_params_editing_keys(&mut app, key(KeyCode::Char('0')))
+            .await
+            .unwrap();
+        handle_params_editing_keys(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert_eq!(app.params_input[0].1, "10");
+        handle_main_screen_keys(&mut app, key(KeyCode::Char('e')))
+            .await
+            .unwrap();
+        handle_params_editing_keys(&mut app, key(KeyCode::Char('0')))
+            .await
+            .unwrap();
+        handle_params_editing_keys(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(
+            app.params_input,
+            vec![("limit".to_string(), "100".to_string())]
+        );
+        handle_main_screen_keys(&mut app, key(KeyCode::Char('d')))
+            .await
+            .unwrap();
+        assert!(app.params_input.is_empty());
+        assert_eq!(app.selected_param, None);
+    }
+
+    #[tokio::test]
+    async fn test_toggle_entries() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Values;
+        app.values_screen = ValuesScreen::Headers;
+        for name in ["Authorization", "Accept"] {
+            app.headers_input.push((name.to_string(), "x".to_string()));
+        }
+        let key = |c| create_key_event(KeyCode::Char(c));
+
+        handle_main_screen_keys(&mut app, key('j')).await.unwrap();
+        handle_main_screen_keys(&mut app, key(' ')).await.unwrap();
+        // Switched off and moved after the others, still picked
+        assert_eq!(app.headers_input.len(), 1);
+        assert_eq!(app.disabled_headers[0].0, "Authorization");
+        assert_eq!(app.selected_header, Some(1));
+        let request = app.current_request();
+        assert_eq!(request.headers.len(), 1);
+        assert_eq!(request.disabled_headers.len(), 1);
+
+        // Kept across tab switches
+        app.add_new_tab().unwrap();
+        app.selected_tab = 0;
+        app.restore_current_tab_state().unwrap();
+        assert_eq!(app.disabled_headers.len(), 1);
+
+        // A disabled header can be edited and switched back on
+        app.selected_header = Some(1);
+        handle_main_screen_keys(&mut app, key('e')).await.unwrap();
+        handle_headers_editing_keys(&mut app, key('y'))
+            .await
+            .unwrap();
+        handle_headers_editing_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(app.disabled_headers[0].1, "xy");
+        handle_main_screen_keys(&mut app, key(' ')).await.unwrap();
+        assert!(app.disabled_headers.is_empty());
+        assert_eq!(app.headers_input[1].0, "Authorization");
+        assert_eq!(app.selected_header, Some(1));
+
+        app.values_screen = ValuesScreen::Params;
+        app.params_input
+            .push(("debug".to_string(), "1".to_string()));
+        handle_main_screen_keys(&mut app, key('j')).await.unwrap();
+        handle_main_screen_keys(&mut app, key(' ')).await.unwrap();
+        assert!(app.current_request().params.is_empty());
+        handle_main_screen_keys(&mut app, key('d')).await.unwrap();
+        assert!(app.disabled_params.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bulk_edit() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Values;
+        app.values_screen = ValuesScreen::Headers;
+        app.headers_input
+            .push(("Accept".to_string(), "*/*".to_string()));
+        app.disabled_headers
+            .push(("Authorization".to_string(), "Bearer t".to_string()));
+
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('b')))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingBulk);
+        assert_eq!(app.bulk_input, "Accept: */*\n//Authorization: Bearer t");
+
+        // A block from the developer tools pastes whole
+        handle_bulk_editing_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        crate::handlers::handle_paste_event(&mut app, ":authority: api.test\r\nX-Trace: 7\r\n")
+            .await
+            .unwrap();
+        handle_bulk_editing_keys(&mut app, create_key_event(KeyCode::Char('x')))
+            .await
+            .unwrap();
+        // A line without a colon keeps the editor open
+        let result = handle_bulk_editing_keys(&mut app, create_key_event(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert!(result.unwrap().contains("Line 5"));
+        assert_eq!(app.current_screen, CurrentScreen::EditingBulk);
+
+        handle_bulk_editing_keys(&mut app, create_key_event(KeyCode::Backspace))
+            .await
+            .unwrap();
+        handle_bulk_editing_keys(&mut app, create_key_event(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Values);
+        assert_eq!(
+            app.headers_input,
+            vec![
+                ("Accept".to_string(), "*/*".to_string()),
+                ("X-Trace".to_string(), "7".to_string())
+            ]
+        );
+        assert_eq!(app.disabled_headers.len(), 1);
+
+        app.values_screen = ValuesScreen::Params;
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('b')))
+            .await
+            .unwrap();
+        for c in "page=2&limit=10".chars() {
+            handle_bulk_editing_keys(&mut app, create_key_event(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        handle_bulk_editing_keys(&mut app, create_key_event(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert_eq!(app.params_input.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_url_query_to_params() {
+        let mut app = App::new();
+        app.url_input = "https://api.test/items".to_string();
+        app.params_input
+            .push(("debug".to_string(), "a b".to_string()));
+
+        // The params are part of the URL while it's edited
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('u')))
+            .await
+            .unwrap();
+        assert_eq!(app.url_input, "https://api.test/items?debug=a%20b");
+
+        let control_u = KeyEvent {
+            code: KeyCode::Char('u'),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        };
+        handle_url_editing_keys(&mut app, control_u).await.unwrap();
+        crate::handlers::handle_paste_event(
+            &mut app,
+            "https://api.example.com/users?limit=10&page=2",
+        )
+        .await
+        .unwrap();
+        handle_url_editing_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(app.url_input, "https://api.example.com/users");
+        assert_eq!(
+            app.params_input,
+            vec![
+                ("limit".to_string(), "10".to_string()),
+                ("page".to_string(), "2".to_string())
+            ]
+        );
+        assert_eq!(app.tabs[0].request.params.len(), 2);
+
+        // Params changed meanwhile show up in the URL next time
+        app.params_input.remove(0);
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('u')))
+            .await
+            .unwrap();
+        assert_eq!(app.url_input, "https://api.example.com/users?page=2");
+        handle_url_editing_keys(&mut app, create_key_event(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert_eq!(app.url_input, "https://api.example.com/users");
+        assert_eq!(app.params_input.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_method_dropdown() {
+        let mut app = App::new();
+
+        // Open dropdown
+        let key = create_key_event(KeyCode::Char('m'));
+        let result = handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert!(result.is_none());
+        assert!(app.method_dropdown_open);
+
+        // Navigate down
+        let key = create_key_event(KeyCode::Down);
+        let result = handle_method_dropdown_keys(&mut app, key).await.unwrap();
+        assert!(result.is_none());
+        assert_eq!(app.method_dropdown_selected, 1);
+
+        // Select method
+        let key = create_key_event(KeyCode::Enter);
+        let result = handle_method_dropdown_keys(&mut app, key).await.unwrap();
+        assert!(result.is_none());
+        assert!(!app.method_dropdown_open);
+        assert_eq!(app.selected_method, HttpMethod::POST);
+    }
+
+    #[tokio::test]
+    async fn test_values_screen_navigation() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Values;
+        app.values_screen = ValuesScreen::Body;
+
+        // Navigate right
+        let key = create_key_event(KeyCode::Char('l'));
+        let result = handle_values_screen_keys(&mut app, key).await.unwrap();
+        assert!(result.is_none());
+        assert_eq!(app.values_screen, ValuesScreen::Headers);
+
+        // Navigate right again
+        let key = create_key_event(KeyCode::Char('l'));
+        let result = handle_values_screen_keys(&mut app, key).await.unwrap();
+        assert!(result.is_none());
+        assert_eq!(app.values_screen, ValuesScreen::Params);
+
+        // Navigate left
+        let key = create_key_event(KeyCode::Char('h'));
+        let result = handle_values_screen_keys(&mut app, key).await.unwrap();
+        assert!(result.is_none());
+        assert_eq!(app.values_screen, ValuesScreen::Headers);
+    }
+
+    #[tokio::test]
+    async fn test_export_popup() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Url;
+
+        let key = create_key_event(KeyCode::Char('E'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Export);
+        assert_eq!(app.export_format, ExportFormat::Curl);
+
+        let key = create_key_event(KeyCode::Char('l'));
+        handle_export_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.export_format, ExportFormat::Har);
+
+        let key = create_key_event(KeyCode::Char('l'));
+        handle_export_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.export_format, ExportFormat::BugReport);
+        assert!(app
+            .export_current(ExportFormat::BugReport)
+            .starts_with("# restless bug report"));
+
+        let key = create_key_event(KeyCode::Char('l'));
+        handle_export_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.export_format, ExportFormat::Code(Language::Rust));
+        assert!(app
+            .export_current(app.export_format)
+            .contains("#[tokio::main]"));
+
+        for language in [Language::Python, Language::JavaScript] {
+            let key = create_key_event(KeyCode::Char('l'));
+            handle_export_keys(&mut app, key).await.unwrap();
+            assert_eq!(app.export_format, ExportFormat::Code(language));
+        }
+
+        let key = create_key_event(KeyCode::Char('l'));
+        handle_export_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.export_format, ExportFormat::Curl);
+
+        let key = create_key_event(KeyCode::Esc);
+        handle_export_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Url);
+    }
+
+    #[tokio::test]
+    async fn test_open_tutorial_request() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Values;
+
+        let key = create_key_event(KeyCode::Char('o'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Collections);
+
+        let key = create_key_event(KeyCode::Char('j'));
+        handle_collections_keys(&mut app, key).await.unwrap();
+        let key = create_key_event(KeyCode::Enter);
+        handle_collections_keys(&mut app, key).await.unwrap();
+
+        // The blank starting tab is reused
+        assert_eq!(app.tabs.len(), 1);
+        assert_eq!(app.current_screen, CurrentScreen::Values);
+        assert_eq!(app.tabs[0].name, "2. Query parameters");
+        assert_eq!(app.url_input, "https://httpbin.org/get");
+        assert_eq!(app.params_input.len(), 2);
+
+        // A tab in use is kept and the request opens in a new one
+        app.show_collections();
+        let key = create_key_event(KeyCode::Enter);
+        handle_collections_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.tabs.len(), 2);
+        assert_eq!(app.selected_tab, 1);
+        assert_eq!(app.tabs[0].request.params.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_import_into_collection() {
+        let dir = std::env::temp_dir().join(format!("restless-import-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let har = dir.join("capture.har");
+        std::fs::write(
+            &har,
+            r#"{"log": {"entries": [
+                {"request": {"method": "GET", "url": "https://api.example.com/users"}},
+                {"request": {"method": "GET", "url": "https://api.example.com/users"}},
+                {"request": {"method": "DELETE", "url": "https://api.example.com/users/7"}}
+            ]}}"#,
+        )
+        .unwrap();
+
+        let mut app = App::new();
+        let collection_path = dir.join("api.json");
+        app.collections.push(crate::collections::Collection {
+            name: "API".to_string(),
+            requests: vec![crate::collections::SavedRequest {
+                name: "List users".to_string(),
+                description: String::new(),
+                method: "GET".to_string(),
+                url: "{{base_url}}/users".to_string(),
+                headers: Vec::new(),
+                params: Vec::new(),
+                disabled_headers: Vec::new(),
+                disabled_params: Vec::new(),
+                tests: String::new(),
+                body: None,
+                form: None,
+                auth: None,
+                options: Default::default(),
+                panel: None,
+            }],
+            path: Some(collection_path.clone()),
+            ..Default::default()
+        });
+        app.show_collections();
+        // Select the saved request so the import targets its collection
+        app.collection_selected = app.collection_entries().len() - 1;
+
+        handle_collections_keys(&mut app, create_key_event(KeyCode::Char('i')))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingImportPath);
+        for c in har.display().to_string().chars() {
+            handle_import_path_keys(&mut app, create_key_event(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        handle_import_path_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::ImportReview);
+        let review = app.import_review.as_ref().unwrap();
+        assert_eq!(review.items.len(), 2);
+        assert_eq!(review.conflicts(), 1);
+
+        let message = handle_import_review_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(
+            message.as_deref(),
+            Some("Imported into API: 1 added, 0 merged, 1 skipped")
+        );
+        assert_eq!(app.current_screen, CurrentScreen::Collections);
+        let saved = crate::collections::load_collections(&dir).unwrap();
+        assert_eq!(saved[0].requests.len(), 2);
+        assert_eq!(saved[0].requests[1].name, "DELETE /users/7");
+        assert_eq!(app.collections.last().unwrap().requests.len(), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_restore_revision() {
+        let dir =
+            std::env::temp_dir().join(format!("restless-revision-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("api.json");
+        let write = |url: &str| {
+            std::fs::write(
+                &path,
+                format!(
+                    r#"{{"name": "API", "requests": [{{"name": "Users", "url": "{}"}}]}}"#,
+                    url
+                ),
+            )
+            .unwrap()
+        };
+
+        let mut app = App::new();
+        write("http://api/users");
+        app.collections = crate::collections::load_collections(&dir).unwrap();
+        app.track_revisions().unwrap();
+        write("http://api/v2/users");
+        app.collections = crate::collections::load_collections(&dir).unwrap();
+        app.track_revisions().unwrap();
+
+        app.show_collections();
+        app.collection_selected = app.collection_entries().len() - 1;
+        handle_collections_keys(&mut app, create_key_event(KeyCode::Char('r')))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Timeline);
+        assert_eq!(app.timeline.len(), 2);
+        assert_eq!(app.timeline[0].changes, vec!["url"]);
+
+        handle_timeline_keys(&mut app, create_key_event(KeyCode::Char('j')))
+            .await
+            .unwrap();
+        handle_timeline_keys(&mut app, create_key_event(KeyCode::Char('j')))
+            .await
+            .unwrap();
+        assert_eq!(app.timeline_selected, 1);
+        handle_timeline_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Values);
+        assert_eq!(app.current_request().url, "http://api/users");
+        let saved = crate::collections::load_collections(&dir).unwrap();
+        assert_eq!(saved[0].requests[0].url, "http://api/users");
+        let timeline =
+            crate::collections::revisions::timeline(app.history.as_ref(), "API", "Users").unwrap();
+        assert_eq!(timeline.len(), 3);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_restore_session() {
+        let dir =
+            std::env::temp_dir().join(format!("restless-session-app-test-{}", std::process::id()));
+        let path = dir.join("session.json");
+
+        let mut app = App::new();
+        app.url_input = "http://api/users".to_string();
+        app.add_new_tab().unwrap();
+        app.url_input = "http://api/orders".to_string();
+        app.selected_method = HttpMethod::DELETE;
+        app.save_session(&path).unwrap();
+
+        let mut restored = App::new();
+        restored.restore_session(&path).unwrap();
+        assert_eq!(restored.tabs.len(), 2);
+        assert_eq!(restored.selected_tab, 1);
+        assert_eq!(restored.url_input, "http://api/orders");
+        assert_eq!(restored.selected_method, HttpMethod::DELETE);
+        assert_eq!(restored.tabs[0].request.url, "http://api/users");
+
+        let mut fresh = App::new();
+        fresh.config.ui.restore_session = false;
+        fresh.restore_session(&path).unwrap();
+        assert_eq!(fresh.tabs.len(), 1);
+        assert!(fresh.url_input.is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_open_http_file() {
+        let dir =
+            std::env::temp_dir().join(format!("restless-http-file-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("api.http");
+        std::fs::write(
+            &file,
+            "### Health\nGET http://localhost/health\n\n### Create\nPOST http://localhost/items\n",
+        )
+        .unwrap();
+
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Collections;
+        let key = create_key_event(KeyCode::Char('i'));
+        handle_collections_keys(&mut app, key).await.unwrap();
+        app.import_input = file.display().to_string();
+        let key = create_key_event(KeyCode::Enter);
+        handle_import_path_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Collections);
+        assert_eq!(app.collections.last().unwrap().name, "api.http");
+        assert!(app.collections.last().unwrap().is_http_file());
+
+        // The first request of the file is selected
+        let key = create_key_event(KeyCode::Enter);
+        handle_collections_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.url_input, "http://localhost/health");
+        let key = create_key_event(KeyCode::Char('a'));
+        app.current_screen = CurrentScreen::Collections;
+        handle_collections_keys(&mut app, key).await.unwrap();
+        assert!(app.send_on_save);
+        app.current_screen = CurrentScreen::Values;
+
+        // Saving the file reloads the tab and asks for a send
+        app.http_files[0].wait_for_scan();
+        std::fs::write(
+            &file,
+            "### Health\nGET http://localhost:8080/health\n\n### Create\nPOST http://localhost/items\n",
+        )
+        .unwrap();
+        app.http_files[0].wait_for_scan();
+        let later = std::time::Instant::now() + std::time::Duration::from_secs(1);
+        assert!(!app.poll_http_files(later).unwrap());
+        assert!(app
+            .poll_http_files(later + crate::logic::watch::DEBOUNCE)
+            .unwrap());
+        assert_eq!(app.url_input, "http://localhost:8080/health");
+        assert_eq!(app.tabs[0].request.url, "http://localhost:8080/health");
+
+        // A request that disappears from the file is reported
+        std::fs::write(&file, "### Renamed\nGET http://localhost/health\n").unwrap();
+        app.http_files[0].wait_for_scan();
+        let later = later + std::time::Duration::from_secs(1);
+        assert!(!app.poll_http_files(later).unwrap());
+        assert!(app
+            .poll_http_files(later + crate::logic::watch::DEBOUNCE)
+            .is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_open_launch_targets() {
+        let mut app = App::new();
+        app.open_url("https://api.example.com/users?page=2")
+            .unwrap();
+        assert_eq!(app.tabs.len(), 1);
+        assert_eq!(app.url_input, "https://api.example.com/users");
+        assert_eq!(
+            app.tabs[0].request.params,
+            vec![("page".to_string(), "2".to_string())]
+        );
+        // A tab in use is kept
+        app.open_url("https://api.example.com/health").unwrap();
+        assert_eq!(app.tabs.len(), 2);
+        assert_eq!(app.tabs[0].request.url, "https://api.example.com/users");
+
+        let dir = std::env::temp_dir().join(format!("restless-launch-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("my-collection.json");
+        std::fs::write(
+            &file,
+            r#"{"name": "Mine", "requests": [{"name": "Health", "url": "http://localhost/health"}]}"#,
+        )
+        .unwrap();
+        app.open_collection_file(&file).unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Collections);
+        let (c, r) = app.collection_entries()[app.collection_selected];
+        assert_eq!(app.collections[c].name, "Mine");
+        assert_eq!(app.collections[c].requests[r].name, "Health");
+        assert!(app.open_collection_file(&dir.join("missing.json")).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_send_in_background() {
+        use std::io::Write;
+
+        let (release, released) = std::sync::mpsc::channel::<()>();
+        let server = TestServer::with(move |_, stream| {
+            released.recv().unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                .unwrap();
+        });
+
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Url;
+        app.url_input = server.url("/slow");
+        let result = handle_main_screen_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(result, None);
+        assert!(app.sending());
+        assert!(app.tabs[0]
+            .in_flight
+            .as_ref()
+            .unwrap()
+            .label()
+            .ends_with('s'));
+        assert_eq!(poll_sends(&mut app), None);
+        assert!(app.tabs[0].response.is_none());
+
+        // A second send waits for the first
+        let result = handle_main_screen_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.as_deref(),
+            Some("This tab is still waiting for its response")
+        );
+
+        release.send(()).unwrap();
+        while app.sending() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            assert_eq!(poll_sends(&mut app), None);
+        }
+        let response = app.tabs[0].response.as_ref().unwrap();
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.body, "ok");
+        assert!(app.tabs[0].sent.is_some());
+        assert_eq!(app.history.search("slow", 10).unwrap()[0].status, Ok(200));
+    }
+
+    #[tokio::test]
+    async fn test_secret_lookup_fails_like_a_send() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Url;
+        app.url_input =
+            "http://127.0.0.1:1/?key={{secret:env://RESTLESS_KEYBOARD_SECRET_MISSING}}".to_string();
+        let result = handle_main_screen_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        // Looked up by the send, not while handling the key
+        assert_eq!(result, None);
+        assert!(app.sending());
+        let mut message = None;
+        while app.sending() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            message = message.or(poll_sends(&mut app));
+        }
+        let message = message.unwrap();
+        assert!(message.starts_with("Request failed: "), "{}", message);
+        assert!(message.contains("RESTLESS_KEYBOARD_SECRET_MISSING"));
+    }
+
+    #[tokio::test]
+    async fn test_tls_lookup_in_background() {
+        let mut app = App::new();
+        let mut response = Response::new(200, String::new(), String::new()).unwrap();
+        response.tls = Some(crate::logic::tls::TlsDetails {
+            host: "127.0.0.1".to_string(),
+            port: 1,
+            ..Default::default()
+        });
+        app.tabs[0].response = Some(response);
+        app.current_screen = CurrentScreen::Response;
+
+        handle_response_screen_keys(&mut app, create_key_event(KeyCode::Char('T')))
+            .await
+            .unwrap();
+        assert_eq!(app.response_tab_selected, 2);
+        let tls = |app: &App| app.tabs[0].response.as_ref().unwrap().tls.clone().unwrap();
+        assert!(tls(&app).probing);
+        while app.tls_probe.is_some() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            poll_sends(&mut app);
+        }
+        let tls = tls(&app);
+        assert!(!tls.probing);
+        assert!(tls.protocol.is_none());
+        assert!(tls.probe_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_tests_tab() {
+        let server = TestServer::new(&[
+            b"HTTP/1.1 200 OK\r\nContent-Length: 8\r\nConnection: close\r\n\r\n{\"id\":1}",
+        ]);
+
+        let mut app = App::new();
+        app.url_input = server.url("/");
+        app.current_screen = CurrentScreen::Values;
+        app.values_screen = ValuesScreen::Options;
+        handle_values_screen_keys(&mut app, create_key_event(KeyCode::Char('l')))
+            .await
+            .unwrap();
+        assert_eq!(app.values_screen, ValuesScreen::Tests);
+        handle_values_screen_keys(&mut app, create_key_event(KeyCode::Char('i')))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingTests);
+        for c in "status == 200\njson.body.id == 2\nstatus is".chars() {
+            let code = if c == '\n' {
+                KeyCode::Enter
+            } else {
+                KeyCode::Char(c)
+            };
+            handle_tests_editing_keys(&mut app, create_key_event(code))
+                .await
+                .unwrap();
+        }
+
+        // A line that can't be read is reported, and kept
+        let result = handle_tests_editing_keys(&mut app, create_key_event(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.as_deref(),
+            Some("Tests error: Configuration error: Line 3: unknown comparison is")
+        );
+        assert_eq!(app.current_screen, CurrentScreen::Values);
+        assert!(app.tabs[0].request.tests.ends_with("status is"));
+
+        // No results before a send
+        app.current_screen = CurrentScreen::Response;
+        let result = handle_response_screen_keys(&mut app, create_key_event(KeyCode::Char('r')))
+            .await
+            .unwrap();
+        assert!(result.is_some());
+
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        while app.sending() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            poll_sends(&mut app);
+        }
+        let results = &app.tabs[0].response.as_ref().unwrap().tests;
+        let passed: Vec<bool> = results.iter().map(|result| result.passed).collect();
+        assert_eq!(passed, vec![true, false, false]);
+        assert_eq!(results[1].detail.as_deref(), Some("got 1"));
+
+        handle_response_screen_keys(&mut app, create_key_event(KeyCode::Char('r')))
+            .await
+            .unwrap();
+        assert_eq!(app.response_tab_selected, 4);
+    }
+
+    #[tokio::test]
+    async fn test_set_variables_from_response() {
+        let server = TestServer::new(&[
+            b"HTTP/1.1 200 OK\r\nContent-Length: 16\r\nConnection: close\r\n\r\n{\"token\":\"abc1\"}",
+            b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n",
+        ]);
+
+        let mut app = App::new();
+        app.url_input = server.url("/login");
+        app.tests_input =
+            "status == 200\nset token = json.body.token\nset id = json.body.id".to_string();
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        let mut message = None;
+        while app.sending() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            message = message.or(poll_sends(&mut app));
+        }
+        server.request();
+        assert!(message
+            .unwrap()
+            .starts_with("Could not set a variable, set id = json.body.id: "));
+        assert_eq!(app.extracted.get("token").map(String::as_str), Some("abc1"));
+        assert_eq!(app.toast.as_ref().unwrap().0, "Set token");
+        assert_eq!(app.tabs[0].response.as_ref().unwrap().tests.len(), 1);
+
+        // Later requests, in any tab, use it like an environment variable
+        app.add_new_tab().unwrap();
+        app.url_input = server.url("/me");
+        app.headers_input = vec![("Authorization".to_string(), "Bearer {{token}}".to_string())];
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        while app.sending() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            poll_sends(&mut app);
+        }
+        let asked = server.request();
+        assert!(asked.to_lowercase().contains("authorization: bearer abc1"));
+
+        // The Request tab shows the header as sent, variable resolved
+        app.current_screen = CurrentScreen::Response;
+        handle_response_screen_keys(&mut app, create_key_event(KeyCode::Char('V')))
+            .await
+            .unwrap();
+        assert_eq!(app.response_tab_selected, 5);
+        let (_, text) = app.view_text().unwrap();
+        assert!(text.contains("> authorization: Bearer abc1"));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_retry() {
+        let server = TestServer::new(&[
+            b"HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            b"HTTP/1.1 429 Too Many Requests\r\nRetry-After: 120\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+
+        async fn settle(app: &mut App) -> Option<String> {
+            let mut message = None;
+            while app.sending() {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                message = message.or(poll_sends(app));
+            }
+            message
+        }
+
+        let mut app = App::new();
+        app.config.rate_limit.auto_retry = true;
+        app.url_input = server.url("/");
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        settle(&mut app).await;
+        assert_eq!(app.tabs[0].response.as_ref().unwrap().status_code, 429);
+        let now = chrono::Utc::now();
+        assert_eq!(app.rate_limit_wait(now), Some((0, true)));
+        assert!(app.rate_limited(now));
+
+        // Sent again once the window opens
+        app.start_due_retries(now);
+        assert!(app.tabs[0].retry.is_none());
+        settle(&mut app).await;
+        assert_eq!(app.tabs[0].response.as_ref().unwrap().status_code, 200);
+        assert_eq!(app.rate_limit_wait(now), None);
+
+        // Too long a wait: counted down, but not retried
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        let message = settle(&mut app).await;
+        assert!(message.unwrap().contains("longer than max_wait"));
+        assert!(app.tabs[0].retry.is_none());
+        let (wait, retrying) = app.rate_limit_wait(chrono::Utc::now()).unwrap();
+        assert!(wait > 100 && !retrying);
+    }
+
+    #[tokio::test]
+    async fn test_poll_marks_changed_bodies() {
+        let server = TestServer::new(&[
+            b"HTTP/1.1 200 OK\r\nETag: \"1\"\r\nContent-Length: 1\r\nConnection: close\r\n\r\n1",
+            b"HTTP/1.1 304 Not Modified\r\nConnection: close\r\n\r\n",
+            b"HTTP/1.1 200 OK\r\nContent-Length: 1\r\nConnection: close\r\n\r\n1",
+            b"HTTP/1.1 200 OK\r\nContent-Length: 1\r\nConnection: close\r\n\r\n2",
+            b"HTTP/1.1 200 OK\r\nContent-Length: 1\r\nConnection: close\r\n\r\n2",
+        ]);
+
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Url;
+        app.url_input = server.url("/");
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('I')))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingPoll);
+        assert_eq!(app.poll_input, "5s");
+        app.poll_input = "fast".to_string();
+        let message = handle_poll_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert!(message.unwrap().contains("not an interval"));
+        app.poll_input = "1s".to_string();
+        handle_poll_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Url);
+
+        let mut changes = Vec::new();
+        let start = std::time::Instant::now();
+        for second in 0..5 {
+            // Polling stays with its tab when another one is selected
+            if second == 3 {
+                app.add_new_tab().unwrap();
+            }
+            let now = start + std::time::Duration::from_secs(second * 2);
+            let due = app.poll_interval(now);
+            assert_eq!(due, Some(0));
+            start_tab_sends(&mut app, due.into_iter().collect()).unwrap();
+            // Held back until the response is in
+            assert_eq!(
+                app.poll_interval(now + std::time::Duration::from_secs(5)),
+                None
+            );
+            while app.sending() {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                poll_sends(&mut app);
+            }
+            changes.push(app.tabs[0].response.as_ref().unwrap().changed);
+        }
+        // The 304 stands for the same body as before
+        assert_eq!(
+            changes,
+            vec![None, Some(false), Some(false), Some(true), Some(false)]
+        );
+        assert!(app.tabs[1].response.is_none());
+        let (_, poll) = app.poll.as_ref().unwrap();
+        assert_eq!(poll.runs, 5);
+
+        // An empty interval stops polling
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('I')))
+            .await
+            .unwrap();
+        app.poll_input.clear();
+        handle_poll_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert!(app.poll.is_none());
+
+        // Closing the polled tab stops polling
+        app.start_poll("1s").unwrap();
+        app.close_current_tab().unwrap();
+        assert_eq!(
+            app.poll_interval(start + std::time::Duration::from_secs(60)),
+            None
+        );
+        assert!(app.poll.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_watch_sends_its_tab_once_idle() {
+        use std::io::Write;
+
+        let (answer, answered) = std::sync::mpsc::channel::<()>();
+        let server = TestServer::with(move |_, stream| {
+            answered.recv().unwrap();
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n")
+                .unwrap();
+        });
+        let dir =
+            std::env::temp_dir().join(format!("restless-watch-tab-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("main.rs"), "fn main() {}").unwrap();
+
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Url;
+        app.url_input = server.url("/");
+        app.start_watch(&dir.display().to_string()).unwrap();
+        app.add_new_tab().unwrap();
+
+        // The change is sent from the tab watching started in
+        app.watch.as_ref().unwrap().1.wait_for_scan();
+        std::fs::write(dir.join("main.rs"), "fn main() { run() }").unwrap();
+        app.watch.as_ref().unwrap().1.wait_for_scan();
+        let now = std::time::Instant::now() + std::time::Duration::from_secs(1);
+        assert_eq!(app.poll_watch(now), None);
+        let due = app.poll_watch(now + crate::logic::watch::DEBOUNCE);
+        assert_eq!(due, Some(0));
+        start_tab_sends(&mut app, due.into_iter().collect()).unwrap();
+        assert!(app.tabs[0].in_flight.is_some());
+        assert!(app.tabs[1].in_flight.is_none());
+
+        // A change during the send waits for its response
+        std::fs::write(dir.join("lib.rs"), "").unwrap();
+        app.watch.as_ref().unwrap().1.wait_for_scan();
+        let later = now + std::time::Duration::from_secs(2);
+        assert_eq!(app.poll_watch(later), None);
+        assert_eq!(app.poll_watch(later + crate::logic::watch::DEBOUNCE), None);
+        answer.send(()).unwrap();
+        while app.sending() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            poll_sends(&mut app);
+        }
+        let later = later + std::time::Duration::from_secs(2);
+        assert_eq!(app.poll_watch(later), None);
+        assert_eq!(
+            app.poll_watch(later + crate::logic::watch::DEBOUNCE),
+            Some(0)
+        );
+        let (_, watch) = app.watch.as_ref().unwrap();
+        assert_eq!(watch.runs, 2);
+
+        // Closing the watching tab stops watching
+        app.prev_tab().unwrap();
+        app.close_current_tab().unwrap();
+        assert_eq!(
+            app.poll_watch(later + std::time::Duration::from_secs(5)),
+            None
+        );
+        assert!(app.watch.is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_run_open_tabs() {
+        let server = TestServer::new(&[
+            b"HTTP/1.1 200 OK\r\nContent-Length: 15\r\nConnection: close\r\n\r\n{\"token\":\"t-9\"}",
+            b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n",
+        ]);
+
+        let mut app = App::new();
+        app.url_input = server.url("/login");
+        app.tests_input = "status == 200\nset token = json.body.token".to_string();
+        app.add_new_tab().unwrap();
+        app.add_new_tab().unwrap();
+        app.url_input = server.url("/me");
+        app.headers_input = vec![("Authorization".to_string(), "Bearer {{token}}".to_string())];
+        app.tests_input = "status == 200".to_string();
+
+        // The blank middle tab is left out
+        let screen = app.current_screen;
+        let result = handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('A')))
+            .await
+            .unwrap();
+        assert_eq!(result, None);
+        assert_eq!(app.current_screen, CurrentScreen::RunResults);
+        assert!(app.sending());
+        while app.sending() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            poll_sends(&mut app);
+        }
+        server.request();
+        assert!(server
+            .request()
+            .to_lowercase()
+            .contains("authorization: bearer t-9"));
+
+        let (title, steps) = app.run_results.as_ref().unwrap();
+        assert_eq!(title, "Open tabs");
+        let statuses: Vec<_> = steps.iter().map(|step| step.status.clone()).collect();
+        assert_eq!(statuses, vec![Ok(200), Ok(204)]);
+        assert!(steps[0].passed());
+        assert!(!steps[1].passed());
+        assert_eq!(steps[1].tests[0].detail.as_deref(), Some("got 204"));
+        assert_eq!(steps[1].url, server.url("/me"));
+        assert_eq!(
+            app.history.search("/me", 10).unwrap()[0].run.as_deref(),
+            Some("run Open tabs")
+        );
+        let runs = app.history.runs("Open tabs", 10).unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].steps.len(), 2);
+        assert!(runs[0].steps[0].passed());
+
+        handle_run_results_keys(&mut app, create_key_event(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, screen);
+    }
+
+    #[tokio::test]
+    async fn test_stop_run() {
+        let server = TestServer::with(|_, _| std::thread::sleep(Duration::from_secs(1)));
+
+        let mut app = App::new();
+        app.url_input = server.url("/slow");
+        app.add_new_tab().unwrap();
+        app.url_input = server.url("/never");
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('A')))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(server.request().contains("/slow"));
+        poll_sends(&mut app);
+        assert!(app.run.as_ref().unwrap().current.is_some());
+        assert_eq!(app.run.as_ref().unwrap().pending.len(), 1);
+
+        // Esc stops the run and leaves the popup open on what finished
+        let result = handle_run_results_keys(&mut app, create_key_event(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert_eq!(result.as_deref(), Some("Run stopped after 0 of 2 requests"));
+        assert!(!app.sending());
+        poll_sends(&mut app);
+        assert_eq!(app.current_screen, CurrentScreen::RunResults);
+        assert!(app.run_results.as_ref().unwrap().1.is_empty());
+        // Only finished runs are kept
+        assert!(app.history.runs("Open tabs", 10).unwrap().is_empty());
+
+        handle_run_results_keys(&mut app, create_key_event(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert_ne!(app.current_screen, CurrentScreen::RunResults);
+    }
+
+    #[tokio::test]
+    async fn test_load_test() {
+        let ok: &[u8] = b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+        let server = TestServer::new(&[ok; 4]);
+
+        let mut app = App::new();
+        let screen = app.current_screen;
+        app.url_input = server.url("/");
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('B')))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingLoad);
+        assert_eq!(app.load_input, "200 x 10");
+        app.load_input = "4 x 0".to_string();
+        let result = handle_load_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert!(result.unwrap().contains("Concurrency must be at least 1"));
+
+        app.load_input = "4 x 2".to_string();
+        handle_load_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::LoadResults);
+        assert!(app.sending());
+        // The throughput and latency so far show while it runs
+        assert!(render_screen(&mut app, 80, 40).contains("req/s"));
+        while app.sending() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            poll_sends(&mut app);
+        }
+        let (target, report) = app.load_report.as_ref().unwrap();
+        assert_eq!(target, &format!("GET {}", server.url("/")));
+        assert_eq!(report.statuses[&200], 4);
+        assert_eq!(report.latency.len(), 4);
+
+        handle_load_results_keys(&mut app, create_key_event(KeyCode::Char('r')))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingLoad);
+        handle_load_keys(&mut app, create_key_event(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, screen);
+    }
+
+    #[test]
+    fn test_collection_run() {
+        let mut app = App::new();
+        app.collections = vec![crate::collections::tutorial::tutorial()];
+        let (name, requests) = app.collection_run().unwrap();
+        assert_eq!(name, app.collections[0].name);
+        assert_eq!(requests.len(), app.collections[0].requests.len());
+        assert_eq!(requests[0].0, app.collections[0].requests[0].name);
+
+        app.collections.clear();
+        assert!(app.collection_run().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_finder() {
+        use crate::storage::tests::entry;
+
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Url;
+        app.url_input = "http://localhost/health".to_string();
+        app.add_new_tab().unwrap();
+        app.collections = vec![crate::collections::tutorial::tutorial()];
+        app.history
+            .record(&entry(1, "POST", "http://localhost/orders?page=2", 201))
+            .unwrap();
+        app.history
+            .record(&entry(2, "POST", "http://localhost/orders?page=2", 201))
+            .unwrap();
+
+        async fn find(app: &mut App, query: &str) {
+            handle_main_screen_keys(app, create_key_event_with_ctrl(KeyCode::Char('p')))
+                .await
+                .unwrap();
+            assert_eq!(app.current_screen, CurrentScreen::Finder);
+            for c in query.chars() {
+                handle_finder_keys(app, create_key_event(KeyCode::Char(c)))
+                    .await
+                    .unwrap();
+            }
+            handle_finder_keys(app, create_key_event(KeyCode::Enter))
+                .await
+                .unwrap();
+            assert!(app.finder.is_none());
+            assert_eq!(app.current_screen, CurrentScreen::Url);
+        }
+
+        handle_main_screen_keys(&mut app, create_key_event_with_ctrl(KeyCode::Char('p')))
+            .await
+            .unwrap();
+        // Both tabs, every saved request and the sent URL once
+        let listed = app.finder.as_ref().unwrap().matches.len();
+        assert_eq!(listed, 2 + app.collections[0].requests.len() + 1);
+        handle_finder_keys(&mut app, create_key_event(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert!(app.finder.is_none());
+
+        find(&mut app, "hlth").await;
+        assert_eq!(app.selected_tab, 0);
+        assert_eq!(app.url_input, "http://localhost/health");
+
+        find(&mut app, "post orders").await;
+        assert_eq!(app.tabs.len(), 3);
+        assert_eq!(app.url_input, "http://localhost/orders");
+        assert_eq!(
+            app.params_input,
+            vec![("page".to_string(), "2".to_string())]
+        );
+        assert_eq!(app.selected_method, HttpMethod::POST);
+
+        find(&mut app, "json body").await;
+        assert_eq!(app.tabs.len(), 4);
+        assert_eq!(app.tabs[3].name, "3. Sending a JSON body");
+        assert_eq!(app.selected_method, HttpMethod::POST);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_tab() {
+        let mut app = App::new();
+        app.add_new_tab().unwrap();
+        app.prev_tab().unwrap();
+        app.url_input = "http://api/users".to_string();
+        app.selected_method = HttpMethod::POST;
+        app.headers_input
+            .push(("Accept".to_string(), "application/json".to_string()));
+        app.params_input.push(("page".to_string(), "2".to_string()));
+        app.body_input = "{\"name\": \"a\"}".to_string();
+        app.tabs[0].http_file = Some(("api.http".into(), "Users".to_string()));
+
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('D')))
+            .await
+            .unwrap();
+        assert_eq!(app.tabs.len(), 3);
+        assert_eq!(app.selected_tab, 1);
+        let copy = &app.tabs[1];
+        assert_eq!(copy.name, format!("{} (copy)", app.tabs[0].name));
+        assert_eq!(copy.request, app.tabs[0].request);
+        assert!(copy.http_file.is_none());
+        assert_eq!(app.url_input, "http://api/users");
+        assert_eq!(app.selected_method, HttpMethod::POST);
+        assert_eq!(app.params_input.len(), 1);
+
+        // Editing the copy leaves the original alone
+        app.url_input = "http://api/orders".to_string();
+        app.prev_tab().unwrap();
+        assert_eq!(app.url_input, "http://api/users");
+        assert_eq!(app.tabs[1].request.url, "http://api/orders");
+    }
+
+    #[tokio::test]
+    async fn test_switch_tab_by_number() {
+        let mut app = App::new();
+        app.add_new_tab().unwrap();
+        app.url_input = "http://api/orders".to_string();
+        app.add_new_tab().unwrap();
+
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('2')))
+            .await
+            .unwrap();
+        assert_eq!(app.selected_tab, 1);
+        assert_eq!(app.url_input, "http://api/orders");
+
+        let alt_one = KeyEvent {
+            code: KeyCode::Char('1'),
+            modifiers: KeyModifiers::ALT,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        };
+        handle_main_screen_keys(&mut app, alt_one).await.unwrap();
+        assert_eq!(app.selected_tab, 0);
+
+        let result = handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('9')))
+            .await
+            .unwrap();
+        assert_eq!(result.as_deref(), Some("There is no tab 9 (only 3 open)"));
+        assert_eq!(app.selected_tab, 0);
+    }
+
+    #[tokio::test]
+    async fn test_move_tab() {
+        let mut app = App::new();
+        app.add_new_tab().unwrap();
+        app.add_new_tab().unwrap();
+        let names: Vec<String> = app.tabs.iter().map(|tab| tab.name.clone()).collect();
+        app.url_input = "http://api/orders".to_string();
+        app.compare_tab = Some(app.tabs[1].id);
+
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('<')))
+            .await
+            .unwrap();
+        assert_eq!(app.selected_tab, 1);
+        assert_eq!(app.tabs[1].name, names[2]);
+        assert_eq!(app.tabs[2].name, names[1]);
+        // The compared tab moved along
+        assert_eq!(app.compare_tab.and_then(|id| app.tab_index(id)), Some(2));
+
+        let ctrl_shift_h = KeyEvent {
+            code: KeyCode::Char('H'),
+            modifiers: KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        };
+        handle_main_screen_keys(&mut app, ctrl_shift_h)
+            .await
+            .unwrap();
+        handle_main_screen_keys(&mut app, ctrl_shift_h)
+            .await
+            .unwrap();
+        assert_eq!(app.selected_tab, 0);
+        assert_eq!(app.tabs[0].name, names[2]);
+        assert_eq!(app.url_input, "http://api/orders");
+
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('>')))
+            .await
+            .unwrap();
+        assert_eq!(app.selected_tab, 1);
+        let order: Vec<&str> = app.tabs.iter().map(|tab| tab.name.as_str()).collect();
+        assert_eq!(order, [&names[0], &names[2], &names[1]]);
+
+        // Switching away and back finds the moved tab as it was left
+        app.next_tab().unwrap();
+        app.prev_tab().unwrap();
+        assert_eq!(app.url_input, "http://api/orders");
+    }
+
+    #[tokio::test]
+    async fn test_config_defaults_apply_to_sends() {
+        let server = TestServer::new(&[b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n"]);
+
+        let mut app = App::new();
+        app.config
+            .headers
+            .insert("User-Agent".to_string(), "restless-test".to_string());
+        app.config
+            .headers
+            .insert("Accept".to_string(), "text/plain".to_string());
+        app.config.connection.timeout = Some("5".to_string());
+        app.url_input = server.url("/");
+        app.headers_input
+            .push(("accept".to_string(), "application/json".to_string()));
+        assert_eq!(
+            app.effective_options(&app.options_input).timeout(),
+            Duration::from_secs(5)
+        );
+
+        assert_eq!(send_current_request(&mut app, None).await.unwrap(), None);
+        let request = server.request().to_lowercase();
+        assert!(request.contains("user-agent: restless-test\r\n"));
+        assert!(request.contains("accept: application/json\r\n"));
+        assert!(!request.contains("text/plain"));
+        // The tab keeps the request as written
+        assert_eq!(app.tabs[0].request.headers.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_leave_tab_while_sending() {
+        // Holds each connection until told to drop it unanswered
+        let (release, released) = std::sync::mpsc::channel::<()>();
+        let server = TestServer::with(move |_, _| {
+            let _ = released.recv();
+        });
+
+        let mut app = App::new();
+        app.add_new_tab().unwrap();
+        app.prev_tab().unwrap();
+        app.current_screen = CurrentScreen::Url;
+        app.url_input = server.url("/slow");
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        app.current_screen = CurrentScreen::Values;
+
+        // Switching away keeps the send going, and its outcome lands in its
+        // own tab, named in the message
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Tab))
+            .await
+            .unwrap();
+        assert_eq!(app.selected_tab, 1);
+        assert!(app.tabs[0].in_flight.is_some());
+        release.send(()).unwrap();
+        let message = loop {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            if let Some(message) = poll_sends(&mut app) {
+                break message;
+            }
+        };
+        assert!(message.starts_with(&format!("{}: Request failed", app.tabs[0].name)));
+        assert!(!app.sending());
+
+        // Closing a tab that is still sending warns first, then cancels
+        app.url_input = server.url("/slow");
+        app.current_screen = CurrentScreen::Url;
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        app.current_screen = CurrentScreen::Values;
+        let warning = handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('x')))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(warning.starts_with("Still waiting for the response"));
+        assert_eq!(app.tabs.len(), 2);
+        assert!(app.tabs[1].in_flight.is_some());
+        let result = handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('x')))
+            .await
+            .unwrap();
+        assert_eq!(result, None);
+        assert_eq!(app.tabs.len(), 1);
+        assert!(!app.sending());
+        assert_eq!(poll_sends(&mut app), None);
+        drop(release);
+    }
+
+    #[tokio::test]
+    async fn test_matrix_send() {
+        use std::io::Write;
+
+        let server = TestServer::with(|request, stream| {
+            let status = if request.starts_with("GET /eu ") {
+                "200 OK"
+            } else {
+                "404 Not Found"
+            };
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Url;
+        app.url_input = server.url("/items");
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('M')))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingMatrix);
+        for c in "region = eu, us".chars() {
+            handle_matrix_keys(&mut app, create_key_event(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        let enter = create_key_event(KeyCode::Enter);
+        let result = handle_matrix_keys(&mut app, enter).await.unwrap();
+        assert_eq!(
+            result.as_deref(),
+            Some("The request doesn't use {{region}}")
+        );
+
+        app.url_input = server.url("/{{region}}");
+        handle_matrix_keys(&mut app, enter).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::MatrixResults);
+        let (variable, outcomes) = app.matrix_results.as_ref().unwrap();
+        assert_eq!(variable, "region");
+        let statuses: Vec<_> = outcomes
+            .iter()
+            .map(|o| (o.value.as_str(), o.status.clone()))
+            .collect();
+        assert_eq!(statuses, vec![("eu", Ok(200)), ("us", Ok(404))]);
+        assert!(app.tabs[0].response.is_none(), "the tab's response is kept");
+        let history: Vec<_> = app
+            .history
+            .search("", 10)
+            .unwrap()
+            .into_iter()
+            .map(|entry| (entry.url, entry.status, entry.run))
+            .collect();
+        assert_eq!(
+            history,
+            vec![
+                (
+                    server.url("/us"),
+                    Ok(404),
+                    Some("matrix region=us".to_string())
+                ),
+                (
+                    server.url("/eu"),
+                    Ok(200),
+                    Some("matrix region=eu".to_string())
+                ),
+            ]
+        );
+
+        handle_matrix_results_keys(&mut app, create_key_event(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Url);
+    }
+
+    #[tokio::test]
+    async fn test_resend_with_override() {
+        let server = TestServer::new(&[
+            b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Url;
+        app.url_input = server.url("/items");
+        app.params_input = vec![("debug".to_string(), "false".to_string())];
+
+        let key = create_key_event(KeyCode::Char('R'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::ResendWith);
+
+        app.resend_input = "oops".to_string();
+        let key = create_key_event(KeyCode::Enter);
+        let result = handle_resend_with_keys(&mut app, key).await.unwrap();
+        assert!(result.unwrap().starts_with("Override error"));
+        assert_eq!(app.current_screen, CurrentScreen::ResendWith);
+
+        app.resend_input = "debug=true".to_string();
+        let result = handle_resend_with_keys(&mut app, key).await.unwrap();
+        assert_eq!(
+            result.as_deref(),
+            Some("Sent once with ?debug=true (request unchanged)")
+        );
+        assert_eq!(app.current_screen, CurrentScreen::Url);
+        assert!(server.request().starts_with("GET /items?debug=true "));
+        assert_eq!(app.tabs[0].request.params[0].1, "false");
+        assert_eq!(app.tabs[0].response.as_ref().unwrap().status_code, 200);
+    }
+
+    #[tokio::test]
+    async fn test_response_note() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Response;
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            500,
+            String::new(),
+            String::new(),
+        ));
+
+        let key = create_key_event(KeyCode::Char('n'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingNote);
+
+        for c in "after deploy 1.4.2".chars() {
+            let key = create_key_event(KeyCode::Char(c));
+            handle_note_editing_keys(&mut app, key).await.unwrap();
+        }
+        let key = create_key_event(KeyCode::Enter);
+        handle_note_editing_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Response);
+        let response = app.tabs[0].response.as_ref().unwrap();
+        assert_eq!(response.note.as_deref(), Some("after deploy 1.4.2"));
+
+        app.url_input = "https://example.com".to_string();
+        let export = app.export_current(ExportFormat::Curl);
+        assert!(export.contains("# note: after deploy 1.4.2"));
+
+        // Clearing the text removes the note
+        let key = create_key_event(KeyCode::Char('n'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.note_input, "after deploy 1.4.2");
+        app.note_input.clear();
+        let key = create_key_event(KeyCode::Enter);
+        handle_note_editing_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.tabs[0].response.as_ref().unwrap().note, None);
+    }
+
+    #[tokio::test]
+    async fn test_response_search() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Response;
+        let body: String = (0..20)
+            .map(|i| format!("line {}{}\n", i, if i % 8 == 2 { " token" } else { "" }))
+            .collect();
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            200,
+            String::new(),
+            body,
+        ));
+
+        let key = create_key_event(KeyCode::Char('/'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Searching);
+        for c in "token".chars() {
+            let key = create_key_event(KeyCode::Char(c));
+            handle_search_keys(&mut app, key).await.unwrap();
+        }
+        let key = create_key_event(KeyCode::Enter);
+        handle_search_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Response);
+        // Matches on lines 2, 10 and 18; scrolling keeps a little context
+        assert_eq!(app.response_scroll, 0);
+
+        let key = create_key_event(KeyCode::Char('n'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.response_scroll, 7);
+        assert_eq!(app.current_screen, CurrentScreen::Response);
+        let key = create_key_event(KeyCode::Char('N'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.response_scroll, 15);
+
+        // Esc clears the search and n goes back to attaching a note
+        let key = create_key_event(KeyCode::Esc);
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert!(app.search.is_none());
+        let key = create_key_event(KeyCode::Char('n'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingNote);
+
+        app.current_screen = CurrentScreen::Response;
+        app.search_input = "missing".to_string();
+        let error = app.run_search().unwrap_err();
+        assert!(error.to_string().contains("Pattern not found: missing"));
+    }
+
+    #[tokio::test]
+    async fn test_copy_menu() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Response;
+
+        let key = create_key_event(KeyCode::Char('c'));
+        let result = handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert!(result.is_some());
+        assert_eq!(app.current_screen, CurrentScreen::Response);
+
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            200,
+            String::new(),
+            "{}".to_string(),
+        ));
+        let key = create_key_event(KeyCode::Char('c'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Copy);
+
+        let key = create_key_event(KeyCode::Char('k'));
+        handle_copy_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.copy_format, CopyFormat::BodyStringLiteral);
+
+        let key = create_key_event(KeyCode::Esc);
+        handle_copy_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Response);
+    }
+
+    #[tokio::test]
+    async fn test_multipart_part_navigation() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Response;
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            200,
+            "Content-Type: multipart/mixed; boundary=b".to_string(),
+            "--b\n\none\n--b\n\ntwo\n--b--".to_string(),
+        ));
+
+        let key = create_key_event(KeyCode::Char(']'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.response_part_selected, 1);
+
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.response_part_selected, 0);
+
+        let key = create_key_event(KeyCode::Char('['));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.response_part_selected, 1);
+    }
+
+    #[tokio::test]
+    async fn test_response_history() {
+        let response = |body: &str| {
+            crate::logic::response::Response::new_unchecked(200, String::new(), body.to_string())
+        };
+        let body = |app: &App| app.tabs[0].response.as_ref().unwrap().body.clone();
+        let older = create_key_event(KeyCode::Char('['));
+        let newer = create_key_event(KeyCode::Char(']'));
+
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Response;
+        for n in 1..=4 {
+            app.tabs[0].push_response(response(&n.to_string()), 3);
+        }
+        assert_eq!(app.tabs[0].response_position(), (3, 3));
+
+        handle_main_screen_keys(&mut app, older).await.unwrap();
+        handle_main_screen_keys(&mut app, older).await.unwrap();
+        assert_eq!(body(&app), "2");
+        assert_eq!(app.tabs[0].response_position(), (1, 3));
+        let result = handle_main_screen_keys(&mut app, older).await.unwrap();
+        assert!(result.unwrap().contains("No older response kept"));
+
+        handle_main_screen_keys(&mut app, newer).await.unwrap();
+        assert_eq!(body(&app), "3");
+
+        // A new response comes in last and is shown, the oldest dropped
+        app.tabs[0].push_response(response("5"), 3);
+        assert_eq!(body(&app), "5");
+        assert_eq!(app.tabs[0].response_position(), (3, 3));
+        handle_main_screen_keys(&mut app, older).await.unwrap();
+        assert_eq!(body(&app), "4");
+        handle_main_screen_keys(&mut app, older).await.unwrap();
+        assert_eq!(body(&app), "3");
+        handle_main_screen_keys(&mut app, newer).await.unwrap();
+        handle_main_screen_keys(&mut app, newer).await.unwrap();
+        let result = handle_main_screen_keys(&mut app, newer).await.unwrap();
+        assert!(result.unwrap().contains("This is the latest response"));
+
+        // Outside the Response section a multipart body doesn't take the keys
+        app.tabs[0].push_response(
+            crate::logic::response::Response::new_unchecked(
+                200,
+                "Content-Type: multipart/mixed; boundary=b".to_string(),
+                "--b\n\none\n--b\n\ntwo\n--b--".to_string(),
+            ),
+            3,
+        );
+        app.current_screen = CurrentScreen::Values;
+        handle_main_screen_keys(&mut app, older).await.unwrap();
+        assert_eq!(body(&app), "5");
+    }
+
+    #[tokio::test]
+    async fn test_cookie_screen() {
+        let mut app = App::new();
+        app.cookie_jar = std::sync::Arc::new(crate::logic::cookies::CookieJar::default());
+        app.current_screen = CurrentScreen::Url;
+
+        let key = create_key_event(KeyCode::Char('C'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Cookies);
+
+        // Add a cookie by hand
+        let key = create_key_event(KeyCode::Char('a'));
+        handle_cookies_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingCookie);
+        for c in "sid=1; Domain=example.com".chars() {
+            let key = create_key_event(KeyCode::Char(c));
+            handle_cookie_editing_keys(&mut app, key).await.unwrap();
+        }
+        let key = create_key_event(KeyCode::Enter);
+        let result = handle_cookie_editing_keys(&mut app, key).await.unwrap();
+        assert!(result.is_none());
+        assert_eq!(app.current_screen, CurrentScreen::Cookies);
+        assert_eq!(app.cookie_jar.list()[0].value, "1");
+
+        // Edit its value
+        let key = create_key_event(KeyCode::Char('i'));
+        handle_cookies_keys(&mut app, key).await.unwrap();
+        assert!(app.cookie_input.starts_with("sid=1; Domain=example.com"));
+        app.cookie_input = app.cookie_input.replacen("sid=1", "sid=2", 1);
+        let key = create_key_event(KeyCode::Enter);
+        handle_cookie_editing_keys(&mut app, key).await.unwrap();
+        let cookies = app.cookie_jar.list();
+        assert_eq!(cookies.len(), 1);
+        assert_eq!(cookies[0].value, "2");
+
+        // Delete it
+        let key = create_key_event(KeyCode::Char('d'));
+        handle_cookies_keys(&mut app, key).await.unwrap();
+        assert!(app.cookie_jar.list().is_empty());
+
+        let key = create_key_event(KeyCode::Esc);
+        handle_cookies_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Url);
+    }
+
+    #[tokio::test]
+    async fn test_signing_preview() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Values;
+        app.values_screen = ValuesScreen::Auth;
+        app.url_input = "https://api.example.com/items".to_string();
+
+        // No signing auth: explain instead of opening
+        let key = create_key_event(KeyCode::Char('p'));
+        let result = handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert!(result.is_some());
+        assert_eq!(app.current_screen, CurrentScreen::Values);
+
+        app.auth_input.kind = crate::logic::auth::AuthKind::HmacSignature;
+        app.auth_input.set_field("secret", "s3cr3t");
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::SigningPreview);
+
+        let key = create_key_event(KeyCode::Esc);
+        handle_signing_preview_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Values);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Url;
+        app.url_input = "http://127.0.0.1:9/items".to_string();
+        app.params_input = vec![("q".to_string(), "a b".to_string())];
+        app.headers_input = vec![("X-Token".to_string(), "{{token}}".to_string())];
+        app.extracted
+            .insert("token".to_string(), "abc1".to_string());
+        app.auth_input.kind = crate::logic::auth::AuthKind::ApiKey;
+        app.auth_input.set_field("key", "api_key");
+        app.auth_input.set_field("value", "k3y");
+        app.auth_input.set_field("location", "query");
+
+        let key = create_key_event(KeyCode::Char('P'));
+        let result = handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert!(result.is_none());
+        assert_eq!(app.current_screen, CurrentScreen::DryRun);
+        assert!(app.tabs[0].in_flight.is_none());
+        let lines = app.dry_run.as_ref().unwrap().lines(None);
+        assert_eq!(lines[1], "> GET /items?q=a%20b&api_key=k3y HTTP/1.1");
+        assert!(lines.contains(&"> x-token: abc1".to_string()));
+
+        handle_dry_run_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Url);
+        assert!(app.tabs[0].response.is_none());
+    }
 
-async fn handle_send_request(app: &mut App) -> Result<Option<String>> {
-    // Validate request before sending
-    if let Err(e) = app.validate_current_request() {
-        return Ok(Some(format!("Validation error: {}", e)));
+    #[tokio::test]
+    async fn test_compare_tabs() {
+        use crate::app::tab::Tab;
+        use crate::logic::response::Response;
+
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Response;
+        let compare = create_key_event(KeyCode::Char('|'));
+        let result = handle_main_screen_keys(&mut app, compare).await.unwrap();
+        assert!(result.is_some(), "no second response yet");
+
+        app.tabs.push(Tab::new("Tab 2".to_string(), String::new()));
+        app.tabs.push(Tab::new("Tab 3".to_string(), String::new()));
+        for (tab, body) in
+            app.tabs
+                .iter_mut()
+                .zip([r#"{"a": 1, "b": 2}"#, r#"{"b": 2, "a": 1}"#, "plain"])
+        {
+            tab.response = Some(Response::new_unchecked(
+                200,
+                "Content-Type: application/json".to_string(),
+                body.to_string(),
+            ));
+        }
+        app.response_tab_selected = 1;
+        app.response_scroll = 3;
+
+        handle_main_screen_keys(&mut app, compare).await.unwrap();
+        assert_eq!(app.compare_tab, Some(app.tabs[1].id));
+        assert_eq!(app.response_scroll, 0);
+        handle_main_screen_keys(&mut app, compare).await.unwrap();
+        assert_eq!(app.compare_tab, Some(app.tabs[2].id));
+
+        let screen = render_screen(&mut app, 100, 40);
+        assert!(screen.contains("Compare - Tab 3 Status: 200"));
+        assert!(screen.contains("plain"));
+
+        handle_main_screen_keys(&mut app, compare).await.unwrap();
+        assert_eq!(app.compare_tab, None);
+
+        // Opening and closing tabs around it keeps comparing the same tab
+        handle_main_screen_keys(&mut app, compare).await.unwrap();
+        app.duplicate_current_tab().unwrap();
+        assert_eq!(app.compared_tab().unwrap().name, "Tab 2");
+        app.close_current_tab().unwrap();
+        app.selected_tab = 2;
+        app.close_current_tab().unwrap();
+        assert_eq!(app.selected_tab, 1);
+        assert!(app.compared_tab().is_none());
+        app.selected_tab = 0;
+        assert_eq!(app.compared_tab().unwrap().name, "Tab 2");
     }
 
-    // Send request with error handling
-    match app.tabs[app.selected_tab].request.send().await {
-        Ok((status_code, headers, body)) => {
-            match crate::logic::response::Response::new(status_code, headers.clone(), body.clone())
-            {
-                Ok(response) => {
-                    app.tabs[app.selected_tab].response = Some(response);
-                    Ok(None)
-                }
-                Err(e) => {
-                    // Still create response with unchecked method for display
-                    let response =
-                        crate::logic::response::Response::new_unchecked(status_code, headers, body);
-                    app.tabs[app.selected_tab].response = Some(response);
-                    Ok(Some(format!("Response parsing error: {}", e)))
-                }
-            }
+    #[tokio::test]
+    async fn test_cycle_layout() {
+        // Rows of the URL field and of the response title
+        fn rows(app: &mut App, width: u16) -> (usize, usize) {
+            let screen: Vec<char> = render_screen(app, width, 40).chars().collect();
+            let lines: Vec<String> = screen
+                .chunks(width as usize)
+                .map(|line| line.iter().collect())
+                .collect();
+            let row = |text: &str| lines.iter().position(|line| line.contains(text)).unwrap();
+            (row("Enter URL"), row("Response"))
         }
-        Err(e) => Ok(Some(format!("Request failed: {}", e))),
+
+        let mut app = App::new();
+        let (url, response) = rows(&mut app, 200);
+        assert!(response > url + 5);
+
+        let key = create_key_event(KeyCode::Char('\\'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.layout, LayoutMode::Split);
+        assert_eq!(app.toast.as_ref().unwrap().0, "Layout: split");
+        let (url, response) = rows(&mut app, 200);
+        assert!(response <= url + 1);
+
+        // Auto splits wide terminals only
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.layout, LayoutMode::Auto);
+        let (url, response) = rows(&mut app, 200);
+        assert!(response <= url + 1);
+        let (url, response) = rows(&mut app, 100);
+        assert!(response > url + 5);
+
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.layout, LayoutMode::Stacked);
+
+        app.i18n = crate::i18n::load("de", None).unwrap();
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.toast.as_ref().unwrap().0, "Layout: geteilt");
     }
-}
 
-fn handle_new_tab(app: &mut App) -> Result<Option<String>> {
-    if let Err(e) = app.add_new_tab() {
-        Ok(Some(format!("Tab error: {}", e)))
-    } else {
-        Ok(None)
+    #[tokio::test]
+    async fn test_response_zoom() {
+        use crate::logic::response::Response;
+        let mut app = App::new();
+        let z = create_key_event(KeyCode::Char('z'));
+        handle_main_screen_keys(&mut app, z).await.unwrap();
+        assert!(app.response_zoomed);
+        assert_eq!(app.current_screen, CurrentScreen::Response);
+        let zoomed = render_screen(&mut app, 80, 40);
+        assert!(!zoomed.contains("Enter URL"));
+        assert!(zoomed.contains("Zoomed, z restores"));
+
+        handle_main_screen_keys(&mut app, z).await.unwrap();
+        assert!(!app.response_zoomed);
+        assert!(render_screen(&mut app, 80, 40).contains("Enter URL"));
+
+        // Moving up to the hidden sections restores the layout
+        handle_main_screen_keys(&mut app, z).await.unwrap();
+        handle_main_screen_keys(&mut app, create_key_event_with_ctrl(KeyCode::Char('k')))
+            .await
+            .unwrap();
+        assert!(!app.response_zoomed);
+        assert_eq!(app.current_screen, CurrentScreen::Values);
+
+        // z zooms even while a hint shows, Z dismisses the hint
+        app.tabs[0].response = Some(Response::new_unchecked(
+            200,
+            "Content-Type: text/html".to_string(),
+            "<p>hi</p>".to_string(),
+        ));
+        assert!(app.hint().is_some());
+        handle_main_screen_keys(&mut app, z).await.unwrap();
+        assert!(app.response_zoomed);
+        assert!(app.hint().is_some());
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('Z')))
+            .await
+            .unwrap();
+        assert!(app.response_zoomed);
+        assert!(app.hint().is_none());
     }
-}
 
-fn handle_close_tab(app: &mut App) -> Result<Option<String>> {
-    if let Err(e) = app.close_current_tab() {
-        Ok(Some(format!("Tab error: {}", e)))
-    } else {
-        Ok(None)
+    #[tokio::test]
+    async fn test_response_headers_scroll() {
+        use crate::logic::response::Response;
+        let mut app = App::new();
+        let headers = (0..60)
+            .map(|n| format!("X-Item-{:02}: {}", n, n))
+            .collect::<Vec<_>>()
+            .join("\n");
+        app.tabs[0].response = Some(Response::new_unchecked(200, headers, "{}".to_string()));
+        app.current_screen = CurrentScreen::Response;
+        app.response_tab_selected = 0;
+
+        let j = create_key_event(KeyCode::Char('j'));
+        for _ in 0..4 {
+            handle_main_screen_keys(&mut app, j).await.unwrap();
+        }
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('k')))
+            .await
+            .unwrap();
+        assert_eq!(app.response_headers_scroll.get(), 3);
+        assert_eq!(app.response_scroll, 0);
+        let shown = render_screen(&mut app, 80, 40);
+        assert!(shown.contains("-03"));
+        assert!(!shown.contains("-02"));
+
+        // No further than the last page
+        app.response_headers_scroll.set(1000);
+        assert!(render_screen(&mut app, 80, 40).contains("-59"));
+        assert!(app.response_headers_scroll.get() < 60);
+
+        // The body keeps its own offset
+        app.response_tab_selected = 1;
+        handle_main_screen_keys(&mut app, j).await.unwrap();
+        assert_eq!(app.response_scroll, 1);
     }
-}
 
-fn handle_next_tab(app: &mut App) -> Result<Option<String>> {
-    if let Err(e) = app.next_tab() {
-        Ok(Some(format!("Tab error: {}", e)))
-    } else {
-        Ok(None)
+    #[tokio::test]
+    async fn test_response_column_scroll() {
+        use crate::logic::response::Response;
+        let mut app = App::new();
+        let line = (0..20).map(|n| format!("<{:03}>", n)).collect::<String>();
+        app.tabs[0].response = Some(Response::new_unchecked(
+            200,
+            "Content-Type: text/plain".to_string(),
+            line,
+        ));
+        app.current_screen = CurrentScreen::Response;
+        app.response_tab_selected = 1;
+        assert!(!render_screen(&mut app, 80, 40).contains("<019>"));
+
+        let l = create_key_event(KeyCode::Char('l'));
+        let h = create_key_event(KeyCode::Char('h'));
+        handle_main_screen_keys(&mut app, l).await.unwrap();
+        handle_main_screen_keys(&mut app, l).await.unwrap();
+        assert_eq!(app.response_column.get(), 16);
+        // Starting inside "<003>"
+        let shown = render_screen(&mut app, 80, 40);
+        assert!(shown.contains("003><004>"));
+        assert!(!shown.contains("<003>"));
+
+        // No further than the end of the widest line
+        for _ in 0..20 {
+            handle_main_screen_keys(&mut app, l).await.unwrap();
+        }
+        assert!(render_screen(&mut app, 80, 40).contains("<019>"));
+        assert!(app.response_column.get() < 100);
+
+        // h scrolls back, and past the first column goes to the headers
+        app.response_column.set(8);
+        handle_main_screen_keys(&mut app, h).await.unwrap();
+        assert_eq!(app.response_column.get(), 0);
+        assert_eq!(app.response_tab_selected, 1);
+        handle_main_screen_keys(&mut app, h).await.unwrap();
+        assert_eq!(app.response_tab_selected, 0);
     }
-}
 
-fn handle_prev_tab(app: &mut App) -> Result<Option<String>> {
-    if let Err(e) = app.prev_tab() {
-        Ok(Some(format!("Tab error: {}", e)))
-    } else {
-        Ok(None)
+    #[tokio::test]
+    async fn test_cached_copy_key() {
+        use crate::logic::cache::Cached;
+        use crate::logic::response::Response;
+
+        let mut app = App::new();
+        let mut response = Response::new_unchecked(304, String::new(), String::new());
+        response.cached = Cached::new("ETag: \"v1\"", "kept", chrono::Utc::now());
+        app.tabs[0].response = Some(response);
+        app.current_screen = CurrentScreen::Response;
+
+        let key = create_key_event(KeyCode::Char('K'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        let shown = app.tabs[0].response.as_ref().unwrap();
+        assert!(shown.cached_shown);
+        assert_eq!(shown.body, "kept");
+        assert_eq!(app.current_screen, CurrentScreen::Response);
+
+        // C stays the cookie jar, here too
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('C')))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Cookies);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crossterm::event::{KeyEventKind, KeyEventState};
+    #[tokio::test]
+    async fn test_latency_popup() {
+        use crate::logic::metrics::Metrics;
 
-    fn create_key_event(code: KeyCode) -> KeyEvent {
-        KeyEvent {
-            code,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: KeyEventState::NONE,
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Response;
+        let key = create_key_event(KeyCode::Char('L'));
+        let result = handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert!(result.is_some(), "nothing sent yet");
+        assert_eq!(app.current_screen, CurrentScreen::Response);
+
+        app.tabs[0].latency.record(
+            "http://a",
+            &Metrics {
+                elapsed: std::time::Duration::from_millis(30),
+                reused_connection: false,
+                phases: Default::default(),
+            },
+        );
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Latency);
+
+        handle_latency_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Response);
+    }
+
+    #[tokio::test]
+    async fn test_body_editing_cursor() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Values;
+        app.values_screen = ValuesScreen::Body;
+        app.body_input = "{\n  \"a\": 1\n}".to_string();
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('i')))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingBody);
+
+        for code in [
+            KeyCode::Up,
+            KeyCode::End,
+            KeyCode::Left,
+            KeyCode::Left,
+            KeyCode::Backspace,
+        ] {
+            handle_body_editing_keys(&mut app, create_key_event(code))
+                .await
+                .unwrap();
         }
+        assert_eq!(app.body_input, "{\n  \"a\" 1\n}");
+        for c in ":2".chars() {
+            handle_body_editing_keys(&mut app, create_key_event(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        for code in [KeyCode::Delete, KeyCode::Delete, KeyCode::Enter] {
+            handle_body_editing_keys(&mut app, create_key_event(code))
+                .await
+                .unwrap();
+        }
+        assert_eq!(app.body_input, "{\n  \"a\":2\n\n}");
+
+        handle_body_editing_keys(&mut app, create_key_event(KeyCode::Home))
+            .await
+            .unwrap();
+        handle_body_editing_keys(&mut app, create_key_event(KeyCode::Char('x')))
+            .await
+            .unwrap();
+        assert_eq!(app.body_input, "{\n  \"a\":2\nx\n}");
+        assert_eq!(app.body_cursor.position(&app.body_input), (2, 1));
     }
 
-    fn create_key_event_with_ctrl(code: KeyCode) -> KeyEvent {
-        KeyEvent {
-            code,
+    #[tokio::test]
+    async fn test_body_scroll() {
+        let mut app = App::new();
+        app.body_input = (0..100)
+            .map(|n| format!("line {}", n))
+            .collect::<Vec<_>>()
+            .join("\n");
+        app.current_screen = CurrentScreen::Values;
+        app.values_screen = ValuesScreen::Body;
+
+        let j = create_key_event(KeyCode::Char('j'));
+        for _ in 0..3 {
+            handle_values_screen_keys(&mut app, j).await.unwrap();
+        }
+        handle_values_screen_keys(&mut app, create_key_event(KeyCode::Char('k')))
+            .await
+            .unwrap();
+        assert_eq!(app.body_scroll.get(), 2);
+        let shown = render_screen(&mut app, 80, 40);
+        assert!(shown.contains("line 2"));
+        assert!(!shown.contains("line 1 "));
+
+        // No further than the last page
+        app.body_scroll.set(1000);
+        assert!(render_screen(&mut app, 80, 40).contains("line 99"));
+        assert!(app.body_scroll.get() < 100);
+
+        // Another tab starts at the top
+        app.add_new_tab().unwrap();
+        assert_eq!(app.body_scroll.get(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_edit_body_in_editor() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::EditingBody;
+        app.body_input = "{\"a\": 1}".to_string();
+        let ctrl_e = KeyEvent {
+            code: KeyCode::Char('e'),
             modifiers: KeyModifiers::CONTROL,
             kind: KeyEventKind::Press,
             state: KeyEventState::NONE,
-        }
+        };
+        handle_body_editing_keys(&mut app, ctrl_e).await.unwrap();
+        assert!(app.body_editor_requested);
+        assert_eq!(app.body_input, "{\"a\": 1}");
+        assert_eq!(app.body_extension(), "json");
+
+        // The editor's final newline is dropped again
+        app.apply_body_edit("{\"a\": 2}\n".to_string());
+        assert_eq!(app.body_input, "{\"a\": 2}");
+        app.body_input = "<a/>\n".to_string();
+        assert_eq!(app.body_extension(), "xml");
+        app.apply_body_edit("<b/>\n".to_string());
+        assert_eq!(app.body_input, "<b/>\n");
+
+        app.body_editor_requested = false;
+        app.current_screen = CurrentScreen::Values;
+        app.values_screen = ValuesScreen::Body;
+        handle_main_screen_keys(&mut app, ctrl_e).await.unwrap();
+        assert!(app.body_editor_requested);
+
+        // A multipart body has no text to edit
+        app.body_editor_requested = false;
+        app.form_input = Some(Vec::new());
+        handle_main_screen_keys(&mut app, ctrl_e).await.unwrap();
+        assert!(!app.body_editor_requested);
     }
 
     #[tokio::test]
-    async fn test_navigation_keys() {
+    async fn test_multipart_body_editing() {
         let mut app = App::new();
-        app.current_screen = CurrentScreen::Url;
+        app.current_screen = CurrentScreen::Values;
+        app.values_screen = ValuesScreen::Body;
+        app.body_input = "raw".to_string();
 
-        // Test Ctrl+j navigation
-        let key = create_key_event_with_ctrl(KeyCode::Char('j'));
-        let result = handle_main_screen_keys(&mut app, key).await.unwrap();
-        assert!(result.is_none());
+        let space = create_key_event(KeyCode::Char(' '));
+        handle_main_screen_keys(&mut app, space).await.unwrap();
+        assert_eq!(app.form_input, Some(Vec::new()));
+
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('i')))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingForm);
+        for c in "note=hi".chars() {
+            handle_form_editing_keys(&mut app, create_key_event(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        let enter = create_key_event(KeyCode::Enter);
+        handle_form_editing_keys(&mut app, enter).await.unwrap();
+        assert_eq!(
+            app.form_input,
+            Some(vec![("note".to_string(), "hi".to_string())])
+        );
+
+        app.current_form_input = "oops".to_string();
+        let result = handle_form_editing_keys(&mut app, enter).await.unwrap();
+        assert!(result.unwrap().starts_with("Form error"));
+        app.current_form_input = "note=".to_string();
+        handle_form_editing_keys(&mut app, enter).await.unwrap();
+        assert_eq!(app.form_input, Some(Vec::new()));
+
+        handle_form_editing_keys(&mut app, enter).await.unwrap();
         assert_eq!(app.current_screen, CurrentScreen::Values);
+        handle_main_screen_keys(&mut app, space).await.unwrap();
+        assert_eq!(app.current_request().form, None);
+        assert_eq!(app.current_request().body.as_deref(), Some("raw"));
+    }
 
-        // Test Ctrl+k navigation
-        let key = create_key_event_with_ctrl(KeyCode::Char('k'));
-        let result = handle_main_screen_keys(&mut app, key).await.unwrap();
-        assert!(result.is_none());
-        assert_eq!(app.current_screen, CurrentScreen::Url);
+    #[tokio::test]
+    async fn test_copy_view() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Response;
+        let copy = create_key_event(KeyCode::Char('y'));
+        let result = handle_main_screen_keys(&mut app, copy).await.unwrap();
+        assert!(result.is_some_and(|message| message.contains("No response to copy yet")));
+
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            200,
+            "Content-Type: application/json".to_string(),
+            r#"[{"id": 1}]"#.to_string(),
+        ));
+        app.response_tab_selected = 0;
+        assert_eq!(
+            app.view_text().unwrap(),
+            ("headers", "Content-Type: application/json".to_string())
+        );
+        app.response_tab_selected = 1;
+        assert_eq!(
+            app.view_text().unwrap(),
+            ("body", "[\n  {\n    \"id\": 1\n  }\n]".to_string())
+        );
+        app.toggle_response_table().unwrap();
+        assert_eq!(
+            app.view_text().unwrap(),
+            ("table", "id\r\n1\r\n".to_string())
+        );
+        app.response_tab_selected = 2;
+        assert!(app.view_text().is_err());
+
+        app.show_toast("Copied the body to the clipboard");
+        let shown = app.toast.as_ref().unwrap().1;
+        assert!(app.expire_toast(shown + std::time::Duration::from_millis(500)));
+        assert!(!app.expire_toast(shown + std::time::Duration::from_secs(2)));
+        assert_eq!(app.toast, None);
     }
 
     #[tokio::test]
-    async fn test_url_editing() {
+    async fn test_response_table_view() {
         let mut app = App::new();
+        app.current_screen = CurrentScreen::Response;
+        let toggle = create_key_event(KeyCode::Char('v'));
+        let result = handle_main_screen_keys(&mut app, toggle).await.unwrap();
+        assert!(result.is_some(), "no response yet");
 
-        // Start editing
-        let key = create_key_event(KeyCode::Char('u'));
-        let result = handle_main_screen_keys(&mut app, key).await.unwrap();
-        assert!(result.is_none());
-        assert_eq!(app.current_screen, CurrentScreen::EditingUrl);
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            200,
+            String::new(),
+            r#"[{"id": 2, "name": "b"}, {"id": 1, "name": "a"}]"#.to_string(),
+        ));
+        handle_main_screen_keys(&mut app, toggle).await.unwrap();
+        assert!(app.response_table);
 
-        // Type some text
-        let key = create_key_event(KeyCode::Char('h'));
-        let result = handle_url_editing_keys(&mut app, key).await.unwrap();
-        assert!(result.is_none());
-        assert_eq!(app.url_input, "h");
+        let next = create_key_event(KeyCode::Char('.'));
+        handle_main_screen_keys(&mut app, next).await.unwrap();
+        handle_main_screen_keys(&mut app, next).await.unwrap();
+        assert_eq!(app.table_column, 1, "stops at the last column");
 
-        // Exit editing
-        let key = create_key_event(KeyCode::Esc);
-        let result = handle_url_editing_keys(&mut app, key).await.unwrap();
-        assert!(result.is_none());
-        assert_eq!(app.current_screen, CurrentScreen::Url);
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('s')))
+            .await
+            .unwrap();
+        let table = app.tabs[0]
+            .response
+            .as_ref()
+            .unwrap()
+            .table
+            .as_ref()
+            .unwrap();
+        assert_eq!(table.rows[0], vec!["1", "a"]);
+        assert_eq!(app.table_csv().unwrap(), "id,name\r\n1,a\r\n2,b\r\n");
+
+        handle_main_screen_keys(&mut app, toggle).await.unwrap();
+        assert!(!app.response_table);
     }
 
     #[tokio::test]
-    async fn test_method_dropdown() {
+    async fn test_response_tree_view() {
         let mut app = App::new();
+        app.current_screen = CurrentScreen::Response;
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            200,
+            String::new(),
+            r#"{"user": {"name": "Ada", "roles": ["admin"]}, "id": 7}"#.to_string(),
+        ));
+        let toggle = create_key_event(KeyCode::Char('J'));
+        handle_main_screen_keys(&mut app, toggle).await.unwrap();
+        let rows = |app: &App| app.response_tree.as_ref().unwrap().rows().len();
+        // Root, id, user and its two children
+        assert_eq!(rows(&app), 5);
 
-        // Open dropdown
-        let key = create_key_event(KeyCode::Char('m'));
-        let result = handle_main_screen_keys(&mut app, key).await.unwrap();
-        assert!(result.is_none());
-        assert!(app.method_dropdown_open);
+        // j moves the selection; Enter folds instead of sending
+        for _ in 0..2 {
+            handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('j')))
+                .await
+                .unwrap();
+        }
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(rows(&app), 3);
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char(' ')))
+            .await
+            .unwrap();
+        assert_eq!(rows(&app), 5);
+        assert_eq!(app.response_scroll, 0);
 
-        // Navigate down
-        let key = create_key_event(KeyCode::Down);
-        let result = handle_method_dropdown_keys(&mut app, key).await.unwrap();
-        assert!(result.is_none());
-        assert_eq!(app.method_dropdown_selected, 1);
+        // A new response keeps the tree open; a non-JSON one closes it
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            200,
+            String::new(),
+            r#"{"user": {"name": "Grace"}}"#.to_string(),
+        ));
+        app.refresh_tree();
+        assert_eq!(rows(&app), 3);
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            200,
+            String::new(),
+            "plain".to_string(),
+        ));
+        app.refresh_tree();
+        assert!(app.response_tree.is_none());
+        let result = handle_main_screen_keys(&mut app, toggle).await.unwrap();
+        assert!(result.is_some(), "not JSON");
+    }
 
-        // Select method
-        let key = create_key_event(KeyCode::Enter);
-        let result = handle_method_dropdown_keys(&mut app, key).await.unwrap();
-        assert!(result.is_none());
-        assert!(!app.method_dropdown_open);
-        assert_eq!(app.selected_method, HttpMethod::POST);
+    #[tokio::test]
+    async fn test_html_preview() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Response;
+        let preview = create_key_event(KeyCode::Char('H'));
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            200,
+            "Content-Type: application/json".to_string(),
+            "{}".to_string(),
+        ));
+        let result = handle_main_screen_keys(&mut app, preview).await.unwrap();
+        assert!(result.is_some(), "not HTML");
+
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            500,
+            "Content-Type: text/html; charset=utf-8".to_string(),
+            "<html><body><h1>Oops</h1><p>It broke.</p></body></html>".to_string(),
+        ));
+        handle_main_screen_keys(&mut app, preview).await.unwrap();
+        assert!(app.html_preview_active());
+        assert_eq!(app.body_text(), "# Oops\n\nIt broke.");
+
+        handle_main_screen_keys(&mut app, preview).await.unwrap();
+        assert!(app.body_text().starts_with("<html>"));
     }
 
     #[tokio::test]
-    async fn test_values_screen_navigation() {
+    async fn test_first_use_hints() {
+        use crate::app::hints::Hint;
+
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Response;
+        app.url_input = "http://localhost/page".to_string();
+        app.save_current_tab_state().unwrap();
+        assert_eq!(app.hint(), None);
+
+        app.tabs[0].sent = Some(app.tabs[0].request.clone());
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            200,
+            "Content-Type: text/html".to_string(),
+            "<p>hi</p>".to_string(),
+        ));
+        assert_eq!(app.hint(), Some(Hint::HtmlPreview));
+        app.url_input.push_str("?page=2");
+        assert_eq!(app.hint(), Some(Hint::UnsentEdits));
+
+        // Dismissing shows the next hint that applies, for good
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('Z')))
+            .await
+            .unwrap();
+        assert_eq!(app.hint(), Some(Hint::HtmlPreview));
+        app.url_input.push_str("&page=3");
+        assert_eq!(app.hint(), Some(Hint::HtmlPreview));
+
+        app.html_preview = true;
+        assert_eq!(app.hint(), None);
+        app.current_screen = CurrentScreen::Help;
+        app.html_preview = false;
+        assert_eq!(app.hint(), None, "no hints over popups");
+    }
+
+    #[tokio::test]
+    async fn test_binary_response_hex_dump() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Response;
+        let mut response = crate::logic::response::Response::new_unchecked(
+            200,
+            "Content-Type: image/png".to_string(),
+            String::new(),
+        );
+        response.bytes = Some((0..=255).collect());
+        app.tabs[0].response = Some(response);
+        assert_eq!(
+            app.tabs[0]
+                .response
+                .as_ref()
+                .and_then(|r| r.hex_bytes())
+                .map(<[u8]>::len),
+            Some(256)
+        );
+
+        let result = handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('/')))
+            .await
+            .unwrap();
+        assert!(result.is_some(), "binary bodies can't be searched");
+        assert_eq!(app.current_screen, CurrentScreen::Response);
+
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('j')))
+            .await
+            .unwrap();
+        assert_eq!(app.response_scroll, 1);
+    }
+
+    #[tokio::test]
+    async fn test_response_filter() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::Response;
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            200,
+            String::new(),
+            r#"{"items": [{"id": 1}, {"id": 2}]}"#.to_string(),
+        ));
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('f')))
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingFilter);
+        for c in "$.items[".chars() {
+            handle_filter_keys(&mut app, create_key_event(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        let enter = create_key_event(KeyCode::Enter);
+        let result = handle_filter_keys(&mut app, enter).await.unwrap();
+        assert!(result.is_some(), "invalid filters are rejected");
+        assert_eq!(app.current_screen, CurrentScreen::EditingFilter);
+        for c in "*].id".chars() {
+            handle_filter_keys(&mut app, create_key_event(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        handle_filter_keys(&mut app, enter).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Response);
+        assert_eq!(app.tabs[0].filter.as_deref(), Some("$.items[*].id"));
+        assert_eq!(app.body_text(), "[\n  1,\n  2\n]");
+
+        // The filter stays with the tab and applies to the next response
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            200,
+            String::new(),
+            r#"{"items": [{"id": 3}]}"#.to_string(),
+        ));
+        assert_eq!(app.body_text(), "3");
+
+        // Reopening starts from the current filter; clearing it shows the body
+        handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('f')))
+            .await
+            .unwrap();
+        assert_eq!(app.filter_input, "$.items[*].id");
+        app.filter_input.clear();
+        handle_filter_keys(&mut app, enter).await.unwrap();
+        assert!(app.tabs[0].filter.is_none());
+        assert!(app.filtered_body().is_none());
+        assert_eq!(app.body_text(), app.tabs[0].response.as_ref().unwrap().body);
+    }
+
+    #[tokio::test]
+    async fn test_cycle_environment() {
+        let mut app = App::new();
+        app.environments = vec![
+            crate::logic::environment::Environment::new("dev"),
+            crate::logic::environment::Environment::new("prod"),
+        ];
+
+        let key = create_key_event(KeyCode::Char('e'));
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.active_environment().unwrap().name, "dev");
+
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.active_environment().unwrap().name, "prod");
+
+        handle_main_screen_keys(&mut app, key).await.unwrap();
+        assert!(app.active_environment().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_auth_tab_editing() {
         let mut app = App::new();
         app.current_screen = CurrentScreen::Values;
-        app.values_screen = ValuesScreen::Body;
+        app.values_screen = ValuesScreen::Auth;
 
-        // Navigate right
-        let key = create_key_event(KeyCode::Char('l'));
-        let result = handle_values_screen_keys(&mut app, key).await.unwrap();
-        assert!(result.is_none());
-        assert_eq!(app.values_screen, ValuesScreen::Headers);
+        // Cycle auth type
+        let key = create_key_event(KeyCode::Char(' '));
+        handle_values_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.auth_input.kind, crate::logic::auth::AuthKind::ApiKey);
 
-        // Navigate right again
-        let key = create_key_event(KeyCode::Char('l'));
-        let result = handle_values_screen_keys(&mut app, key).await.unwrap();
-        assert!(result.is_none());
-        assert_eq!(app.values_screen, ValuesScreen::Params);
+        // Enter editing mode and set a field
+        let key = create_key_event(KeyCode::Char('i'));
+        handle_values_screen_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingAuth);
 
-        // Navigate left
-        let key = create_key_event(KeyCode::Char('h'));
-        let result = handle_values_screen_keys(&mut app, key).await.unwrap();
+        for c in "client_id=abc".chars() {
+            let key = create_key_event(KeyCode::Char(c));
+            handle_auth_editing_keys(&mut app, key).await.unwrap();
+        }
+        let key = create_key_event(KeyCode::Enter);
+        let result = handle_auth_editing_keys(&mut app, key).await.unwrap();
         assert!(result.is_none());
-        assert_eq!(app.values_screen, ValuesScreen::Headers);
+        assert_eq!(app.auth_input.field("client_id"), Some("abc"));
+        assert!(app.current_auth_input.is_empty());
+
+        // Enter on an empty line leaves editing mode
+        let key = create_key_event(KeyCode::Enter);
+        handle_auth_editing_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Values);
+    }
+
+    #[tokio::test]
+    async fn test_paste_import_keys() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::EditingUrl;
+        app.show_paste_import(
+            crate::import::PasteKind::RawHttp,
+            "DELETE /items/1 HTTP/1.1\nHost: api.example.com\nX-Trace: 1\n\n".to_string(),
+        );
+
+        let key = create_key_event(KeyCode::Enter);
+        let result = handle_paste_import_keys(&mut app, key).await.unwrap();
+        assert_eq!(result.as_deref(), Some("Imported raw HTTP request"));
+        assert_eq!(app.current_screen, CurrentScreen::Url);
+        assert_eq!(app.url_input, "https://api.example.com/items/1");
+        assert_eq!(app.selected_method, HttpMethod::DELETE);
+        assert_eq!(app.tabs[0].request.headers.len(), 1);
+        assert!(app.paste_import.is_none());
+
+        // Declining pastes the text into the URL field on one line
+        app.url_input.clear();
+        app.current_screen = CurrentScreen::EditingUrl;
+        app.show_paste_import(
+            crate::import::PasteKind::Curl,
+            "curl \\\n https://x.test".to_string(),
+        );
+        let key = create_key_event(KeyCode::Char('n'));
+        handle_paste_import_keys(&mut app, key).await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::EditingUrl);
+        assert_eq!(app.url_input, "curl \\ https://x.test");
+    }
+
+    #[tokio::test]
+    async fn test_messages_are_translated() {
+        let mut app = App::new();
+        app.i18n = crate::i18n::load("de", None).unwrap();
+        app.current_screen = CurrentScreen::Values;
+        app.values_screen = ValuesScreen::Auth;
+        let message = handle_main_screen_keys(&mut app, create_key_event(KeyCode::Char('r')))
+            .await
+            .unwrap();
+        assert_eq!(
+            message.as_deref(),
+            Some("Zwischengespeicherte OAuth2-Tokens verworfen")
+        );
+
+        app.current_screen = CurrentScreen::Url;
+        app.url_input = "not a url".to_string();
+        let message = start_send(&mut app, None).unwrap().unwrap();
+        assert!(message.starts_with("Ungültige Anfrage: "));
     }
 }
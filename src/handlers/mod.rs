@@ -7,37 +7,159 @@
 #![allow(dead_code)]
 
 pub mod keyboard;
+pub mod mouse;
 pub mod navigation;
 pub mod request;
 pub mod tab;
 
 pub use keyboard::*;
+pub use mouse::handle_mouse_event;
 
 use crate::app::{App, CurrentScreen};
 use crate::error::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::import::{self, PasteKind};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 /// Main event handler that routes events to appropriate sub-handlers
 pub async fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
+    tracing::trace!(code = ?key.code, modifiers = ?key.modifiers, screen = ?app.current_screen, "key");
     // Global key handlers that work in any screen
     if let Some(result) = handle_global_keys(app, key).await? {
         return Ok(result);
     }
 
-    // Screen-specific handlers
+    // Ctrl+V for terminals that don't pass pastes through
+    if key.code == KeyCode::Char('v')
+        && key.modifiers.contains(KeyModifiers::CONTROL)
+        && is_editing_mode(app)
+    {
+        return match crate::clipboard::paste() {
+            Ok(text) => handle_paste_event(app, &text).await,
+            Err(e) => Ok(Some(app.get_error_message(&e))),
+        };
+    }
+
+    handle_screen_keys(app, key).await
+}
+
+/// Routes a key to the handler of the current screen
+async fn handle_screen_keys(app: &mut App, key: KeyEvent) -> Result<Option<String>> {
     match app.current_screen {
         CurrentScreen::Url | CurrentScreen::Values | CurrentScreen::Response => {
             handle_main_screen_keys(app, key).await
         }
         CurrentScreen::EditingUrl => handle_url_editing_keys(app, key).await,
         CurrentScreen::EditingBody => handle_body_editing_keys(app, key).await,
+        CurrentScreen::EditingForm => handle_form_editing_keys(app, key).await,
         CurrentScreen::EditingHeaders => handle_headers_editing_keys(app, key).await,
         CurrentScreen::EditingParams => handle_params_editing_keys(app, key).await,
+        CurrentScreen::EditingBulk => handle_bulk_editing_keys(app, key).await,
+        CurrentScreen::EditingAuth => handle_auth_editing_keys(app, key).await,
+        CurrentScreen::EditingOptions => handle_options_editing_keys(app, key).await,
+        CurrentScreen::EditingTests => handle_tests_editing_keys(app, key).await,
+        CurrentScreen::EditingNote => handle_note_editing_keys(app, key).await,
+        CurrentScreen::Searching => handle_search_keys(app, key).await,
+        CurrentScreen::EditingFilter => handle_filter_keys(app, key).await,
+        CurrentScreen::ResendWith => handle_resend_with_keys(app, key).await,
+        CurrentScreen::EditingWatch => handle_watch_keys(app, key).await,
+        CurrentScreen::EditingPoll => handle_poll_keys(app, key).await,
         CurrentScreen::Help => handle_help_keys(app, key).await,
+        CurrentScreen::Export => handle_export_keys(app, key).await,
+        CurrentScreen::Collections => handle_collections_keys(app, key).await,
+        CurrentScreen::Timeline => handle_timeline_keys(app, key).await,
+        CurrentScreen::Finder => handle_finder_keys(app, key).await,
+        CurrentScreen::EditingImportPath => handle_import_path_keys(app, key).await,
+        CurrentScreen::ImportReview => handle_import_review_keys(app, key).await,
+        CurrentScreen::Copy => handle_copy_keys(app, key).await,
+        CurrentScreen::Cookies => handle_cookies_keys(app, key).await,
+        CurrentScreen::EditingCookie => handle_cookie_editing_keys(app, key).await,
+        CurrentScreen::SigningPreview => handle_signing_preview_keys(app, key).await,
+        CurrentScreen::DryRun => handle_dry_run_keys(app, key).await,
+        CurrentScreen::Latency => handle_latency_keys(app, key).await,
+        CurrentScreen::EditingMatrix => handle_matrix_keys(app, key).await,
+        CurrentScreen::MatrixResults => handle_matrix_results_keys(app, key).await,
+        CurrentScreen::RunResults => handle_run_results_keys(app, key).await,
+        CurrentScreen::EditingLoad => handle_load_keys(app, key).await,
+        CurrentScreen::LoadResults => handle_load_results_keys(app, key).await,
+        CurrentScreen::PasteImport => handle_paste_import_keys(app, key).await,
         CurrentScreen::Exiting => Ok(Some("Application exiting".to_string())),
     }
 }
 
+/// Handles a bracketed paste, or Ctrl+V
+///
+/// In the URL field a pasted curl command or raw HTTP request is offered for
+/// import; the body, the bulk editor and the tests take the text as it is at
+/// the cursor,
+/// and headers and params one per line. Other editing screens receive the
+/// text as if it had been typed.
+pub async fn handle_paste_event(app: &mut App, text: &str) -> Result<Option<String>> {
+    match app.current_screen {
+        CurrentScreen::EditingUrl => {
+            match import::detect(text) {
+                kind @ (PasteKind::Curl | PasteKind::RawHttp) => {
+                    app.show_paste_import(kind, text.to_string())
+                }
+                PasteKind::Url | PasteKind::Text => app.paste_into_url(text),
+            }
+            Ok(None)
+        }
+        CurrentScreen::EditingBody => {
+            let text = text.replace("\r\n", "\n").replace('\r', "\n");
+            app.body_cursor.insert(&mut app.body_input, &text);
+            Ok(None)
+        }
+        CurrentScreen::EditingBulk => {
+            let text = text.replace("\r\n", "\n").replace('\r', "\n");
+            app.bulk_cursor.insert(&mut app.bulk_input, &text);
+            Ok(None)
+        }
+        CurrentScreen::EditingTests => {
+            let text = text.replace("\r\n", "\n").replace('\r', "\n");
+            app.tests_cursor.insert(&mut app.tests_input, &text);
+            Ok(None)
+        }
+        CurrentScreen::EditingHeaders | CurrentScreen::EditingParams => {
+            // Every line but the last is finished with Enter, so a pasted
+            // block of headers adds them all
+            let mut lines = text
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .peekable();
+            while let Some(line) = lines.next() {
+                if let Some(msg) = replay(app, line).await? {
+                    return Ok(Some(msg));
+                }
+                if lines.peek().is_some() || text.ends_with('\n') {
+                    let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+                    if let Some(msg) = handle_screen_keys(app, enter).await? {
+                        return Ok(Some(msg));
+                    }
+                }
+            }
+            Ok(None)
+        }
+        _ if is_editing_mode(app) => replay(app, text).await,
+        // Pasting into a non-editing screen would run every character as a command
+        _ => Ok(None),
+    }
+}
+
+/// Types `text` into the current screen a key at a time, on one line
+async fn replay(app: &mut App, text: &str) -> Result<Option<String>> {
+    for c in text.chars() {
+        let code = match c {
+            '\n' | '\r' => continue,
+            '\t' => KeyCode::Char(' '),
+            c => KeyCode::Char(c),
+        };
+        if let Some(msg) = handle_screen_keys(app, KeyEvent::new(code, KeyModifiers::NONE)).await? {
+            return Ok(Some(msg));
+        }
+    }
+    Ok(None)
+}
+
 /// Handles global keys that work in any screen
 async fn handle_global_keys(app: &mut App, key: KeyEvent) -> Result<Option<Option<String>>> {
     match key.code {
@@ -63,8 +185,24 @@ fn is_editing_mode(app: &App) -> bool {
         app.current_screen,
         CurrentScreen::EditingUrl
             | CurrentScreen::EditingBody
+            | CurrentScreen::EditingForm
+            | CurrentScreen::EditingImportPath
             | CurrentScreen::EditingHeaders
             | CurrentScreen::EditingParams
+            | CurrentScreen::EditingBulk
+            | CurrentScreen::EditingAuth
+            | CurrentScreen::EditingOptions
+            | CurrentScreen::EditingTests
+            | CurrentScreen::EditingNote
+            | CurrentScreen::Searching
+            | CurrentScreen::EditingFilter
+            | CurrentScreen::EditingMatrix
+            | CurrentScreen::EditingLoad
+            | CurrentScreen::ResendWith
+            | CurrentScreen::EditingWatch
+            | CurrentScreen::EditingPoll
+            | CurrentScreen::Finder
+            | CurrentScreen::EditingCookie
     )
 }
 
@@ -159,6 +297,64 @@ mod tests {
         assert!(!app.help_visible);
     }
 
+    #[tokio::test]
+    async fn test_paste_into_url() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::EditingUrl;
+
+        handle_paste_event(&mut app, "https://example.com/a\n")
+            .await
+            .unwrap();
+        assert_eq!(app.url_input, "https://example.com/a");
+        assert_eq!(app.current_screen, CurrentScreen::EditingUrl);
+
+        handle_paste_event(&mut app, "curl -X PUT https://example.com/b -d '{}'")
+            .await
+            .unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::PasteImport);
+        assert_eq!(app.url_input, "https://example.com/a");
+    }
+
+    #[tokio::test]
+    async fn test_paste_replays_in_other_editors() {
+        let mut app = App::new();
+        app.current_screen = CurrentScreen::EditingBody;
+        handle_paste_event(&mut app, "{\n  \"a\": 1\r\n}")
+            .await
+            .unwrap();
+        assert_eq!(app.body_input, "{\n  \"a\": 1\n}");
+
+        // The body takes a paste at the cursor in one piece
+        app.body_cursor.up(&app.body_input);
+        app.body_cursor.end(&app.body_input);
+        handle_paste_event(&mut app, ",\n\t\"b\": 2").await.unwrap();
+        assert_eq!(app.body_input, "{\n  \"a\": 1,\n\t\"b\": 2\n}");
+
+        // Headers are added a line at a time; the last line stays open
+        app.current_screen = CurrentScreen::EditingHeaders;
+        handle_paste_event(
+            &mut app,
+            "Accept: application/json\r\n\r\nX-Trace: 1\nX-Open: o",
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            app.headers_input,
+            vec![
+                ("Accept".to_string(), "application/json".to_string()),
+                ("X-Trace".to_string(), "1".to_string()),
+            ]
+        );
+        assert_eq!(app.current_header_key, "X-Open:");
+        assert_eq!(app.current_header_value, "o");
+        assert_eq!(app.current_screen, CurrentScreen::EditingHeaders);
+
+        // Outside an editor a paste is ignored rather than run as commands
+        app.current_screen = CurrentScreen::Values;
+        handle_paste_event(&mut app, "q").await.unwrap();
+        assert_eq!(app.current_screen, CurrentScreen::Values);
+    }
+
     #[tokio::test]
     async fn test_global_keys_ignored_in_editing_mode() {
         let mut app = App::new();
@@ -0,0 +1,213 @@
+//! Mouse handling
+//!
+//! A left click focuses the section it lands in, and on a tab bar selects
+//! the tab under it; the wheel scrolls the help, the request body and the
+//! response body or headers.
+//! Clicks are hit-tested against [`App::screen_layout`], where the sections
+//! were drawn last. Mouse events over other popups are ignored.
+
+use super::tab::handle_switch_to_tab;
+use crate::app::{App, CurrentScreen, ValuesScreen};
+use crate::error::Result;
+use crate::ui::components::{response_tab_titles, tab_titles, values_tab_titles};
+use crate::ui::layouts::tab_at;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
+use ratatui::text::Line;
+
+/// Lines one notch of the wheel scrolls
+const WHEEL_LINES: usize = 3;
+
+/// Handles a mouse event
+pub async fn handle_mouse_event(app: &mut App, mouse: MouseEvent) -> Result<Option<String>> {
+    let position = Position::new(mouse.column, mouse.row);
+    match (app.current_screen, mouse.kind) {
+        (CurrentScreen::Help, MouseEventKind::ScrollDown) => {
+            let last = app.get_help_content().len().saturating_sub(1);
+            app.help_scroll = (app.help_scroll + WHEEL_LINES).min(last);
+            Ok(None)
+        }
+        (CurrentScreen::Help, MouseEventKind::ScrollUp) => {
+            app.help_scroll = app.help_scroll.saturating_sub(WHEEL_LINES);
+            Ok(None)
+        }
+        (CurrentScreen::Url | CurrentScreen::Values | CurrentScreen::Response, _)
+            if app.method_dropdown_open =>
+        {
+            Ok(None)
+        }
+        (
+            CurrentScreen::Url | CurrentScreen::Values | CurrentScreen::Response,
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp,
+        ) if app.screen_layout.values_area.contains(position) => {
+            if app.values_screen == ValuesScreen::Body && app.form_input.is_none() {
+                app.scroll_body(mouse.kind == MouseEventKind::ScrollDown, WHEEL_LINES);
+            }
+            Ok(None)
+        }
+        (
+            CurrentScreen::Url | CurrentScreen::Values | CurrentScreen::Response,
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollUp,
+        ) if app.screen_layout.response_area.contains(position) => {
+            // Only the body and the headers scroll, as with j/k
+            let down = mouse.kind == MouseEventKind::ScrollDown;
+            match app.response_tab_selected {
+                0 => app.scroll_response_headers(down, WHEEL_LINES),
+                1 => {
+                    app.response_scroll = if down {
+                        app.response_scroll.saturating_add(WHEEL_LINES)
+                    } else {
+                        app.response_scroll.saturating_sub(WHEEL_LINES)
+                    }
+                }
+                _ => {}
+            }
+            Ok(None)
+        }
+        (
+            CurrentScreen::Url | CurrentScreen::Values | CurrentScreen::Response,
+            MouseEventKind::Down(MouseButton::Left),
+        ) => click(app, position),
+        _ => Ok(None),
+    }
+}
+
+/// Focuses what was clicked at `position`
+fn click(app: &mut App, position: Position) -> Result<Option<String>> {
+    let layout = app.screen_layout.clone();
+    if layout.tabs_area.contains(position) {
+        // Inside the border
+        let offset = position.x.saturating_sub(layout.tabs_area.x + 1);
+        return match tab_at(&widths(&tab_titles(app)), 1, 1, offset) {
+            Some(index) if index != app.selected_tab => handle_switch_to_tab(app, index),
+            _ => Ok(None),
+        };
+    }
+    if layout.url_area.contains(position) {
+        app.current_screen = CurrentScreen::Url;
+    } else if layout.values_area.contains(position) {
+        app.current_screen = CurrentScreen::Values;
+        if position.y == layout.values_area.y {
+            let offset = position.x - layout.values_area.x;
+            if let Some(index) = tab_at(&widths(&values_tab_titles(app)), 0, 1, offset) {
+                app.values_screen = ValuesScreen::ALL[index];
+            }
+        }
+    } else if layout.response_area.contains(position) {
+        app.current_screen = CurrentScreen::Response;
+        if position.y == layout.response_area.y && shows_response_tabs(app) {
+            let (indices, titles): (Vec<usize>, Vec<Line>) =
+                response_tab_titles(app).into_iter().unzip();
+            let offset = position.x - layout.response_area.x;
+            if let Some(shown) = tab_at(&widths(&titles), 0, 1, offset) {
+                app.response_tab_selected = indices[shown];
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn widths(titles: &[Line]) -> Vec<usize> {
+    titles.iter().map(Line::width).collect()
+}
+
+/// Whether the response section starts with its tab bar, rather than the
+/// progress of a send or the empty placeholder
+fn shows_response_tabs(app: &App) -> bool {
+    let tab = &app.tabs[app.selected_tab];
+    tab.in_flight.is_none() && tab.response.is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::response::Response;
+    use crossterm::event::KeyModifiers;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn draw(app: &mut App) {
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal.draw(|f| crate::ui::ui(f, app, &None)).unwrap();
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    async fn click_at(app: &mut App, column: u16, row: u16) {
+        let left = MouseEventKind::Down(MouseButton::Left);
+        handle_mouse_event(app, mouse(left, column, row))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_click_focuses() {
+        let mut app = App::new();
+        app.add_new_tab().unwrap();
+        app.tabs[1].response = Some(Response::new_unchecked(
+            200,
+            "Content-Type: text/plain".to_string(),
+            "hi\n".repeat(100),
+        ));
+        draw(&mut app);
+        let layout = app.screen_layout.clone();
+
+        // " Tab 1 │ Tab 2 "
+        click_at(&mut app, layout.tabs_area.x + 2, layout.tabs_area.y + 1).await;
+        assert_eq!(app.selected_tab, 0);
+        click_at(&mut app, layout.tabs_area.x + 10, layout.tabs_area.y + 1).await;
+        assert_eq!(app.selected_tab, 1);
+
+        click_at(&mut app, layout.url_area.x + 30, layout.url_area.y + 1).await;
+        assert_eq!(app.current_screen, CurrentScreen::Url);
+
+        // "Body Headers Params"
+        click_at(&mut app, layout.values_area.x + 14, layout.values_area.y).await;
+        assert_eq!(app.current_screen, CurrentScreen::Values);
+        assert_eq!(app.values_screen, ValuesScreen::Params);
+
+        // "Headers Body TLS"
+        app.response_tab_selected = 1;
+        click_at(&mut app, layout.response_area.x + 2, layout.response_area.y).await;
+        assert_eq!(app.current_screen, CurrentScreen::Response);
+        assert_eq!(app.response_tab_selected, 0);
+        click_at(&mut app, layout.response_area.x + 9, layout.response_area.y).await;
+        assert_eq!(app.response_tab_selected, 1);
+    }
+
+    #[tokio::test]
+    async fn test_wheel_scrolls() {
+        let mut app = App::new();
+        draw(&mut app);
+        let response = app.screen_layout.response_area;
+        app.response_tab_selected = 1;
+
+        let down = mouse(MouseEventKind::ScrollDown, response.x + 5, response.y + 5);
+        handle_mouse_event(&mut app, down).await.unwrap();
+        handle_mouse_event(&mut app, down).await.unwrap();
+        assert_eq!(app.response_scroll, 6);
+        let up = mouse(MouseEventKind::ScrollUp, response.x + 5, response.y + 5);
+        handle_mouse_event(&mut app, up).await.unwrap();
+        assert_eq!(app.response_scroll, 3);
+
+        // Over the values the request body scrolls instead
+        let values = app.screen_layout.values_area;
+        let elsewhere = mouse(MouseEventKind::ScrollDown, values.x + 5, values.y + 5);
+        handle_mouse_event(&mut app, elsewhere).await.unwrap();
+        assert_eq!(app.response_scroll, 3);
+        assert_eq!(app.body_scroll.get(), 3);
+
+        app.show_help();
+        handle_mouse_event(&mut app, down).await.unwrap();
+        assert_eq!(app.help_scroll, 3);
+        handle_mouse_event(&mut app, up).await.unwrap();
+        handle_mouse_event(&mut app, up).await.unwrap();
+        assert_eq!(app.help_scroll, 0);
+    }
+}
@@ -38,6 +38,9 @@ pub fn navigate_values_left(app: &mut App) -> Result<Option<String>> {
         app.values_screen = match app.values_screen {
             ValuesScreen::Headers => ValuesScreen::Body,
             ValuesScreen::Params => ValuesScreen::Headers,
+            ValuesScreen::Auth => ValuesScreen::Params,
+            ValuesScreen::Options => ValuesScreen::Auth,
+            ValuesScreen::Tests => ValuesScreen::Options,
             _ => app.values_screen,
         };
     }
@@ -51,6 +54,9 @@ pub fn navigate_values_right(app: &mut App) -> Result<Option<String>> {
         app.values_screen = match app.values_screen {
             ValuesScreen::Body => ValuesScreen::Headers,
             ValuesScreen::Headers => ValuesScreen::Params,
+            ValuesScreen::Params => ValuesScreen::Auth,
+            ValuesScreen::Auth => ValuesScreen::Options,
+            ValuesScreen::Options => ValuesScreen::Tests,
             _ => app.values_screen,
         };
     }
@@ -60,10 +66,8 @@ pub fn navigate_values_right(app: &mut App) -> Result<Option<String>> {
 /// Handles navigation between response tabs
 #[cfg(test)]
 pub fn navigate_response_tabs(app: &mut App, tab_index: usize) -> Result<Option<String>> {
-    if matches!(app.current_screen, CurrentScreen::Response) {
-        if tab_index < 2 {
-            app.response_tab_selected = tab_index;
-        }
+    if matches!(app.current_screen, CurrentScreen::Response) && tab_index < 2 {
+        app.response_tab_selected = tab_index;
     }
     Ok(None)
 }
@@ -107,19 +111,25 @@ pub fn scroll_help(app: &mut App, direction: ScrollDirection) -> Result<Option<S
 #[cfg(test)]
 pub fn enter_edit_mode(app: &mut App) -> Result<Option<String>> {
     match app.current_screen {
-        CurrentScreen::Url => {
-            app.current_screen = CurrentScreen::EditingUrl;
-        }
+        CurrentScreen::Url => app.start_url_edit(),
         CurrentScreen::Values => match app.values_screen {
-            ValuesScreen::Body => {
-                app.current_screen = CurrentScreen::EditingBody;
+            ValuesScreen::Body if app.form_input.is_some() => {
+                app.current_screen = CurrentScreen::EditingForm;
             }
+            ValuesScreen::Body => app.start_body_edit(),
             ValuesScreen::Headers => {
                 app.current_screen = CurrentScreen::EditingHeaders;
             }
             ValuesScreen::Params => {
                 app.current_screen = CurrentScreen::EditingParams;
             }
+            ValuesScreen::Auth => {
+                app.current_screen = CurrentScreen::EditingAuth;
+            }
+            ValuesScreen::Options => {
+                app.current_screen = CurrentScreen::EditingOptions;
+            }
+            ValuesScreen::Tests => app.start_tests_edit(),
         },
         _ => {
             return Ok(Some(
@@ -138,8 +148,13 @@ pub fn exit_edit_mode(app: &mut App) -> Result<Option<String>> {
             app.current_screen = CurrentScreen::Url;
         }
         CurrentScreen::EditingBody
+        | CurrentScreen::EditingForm
         | CurrentScreen::EditingHeaders
-        | CurrentScreen::EditingParams => {
+        | CurrentScreen::EditingParams
+        | CurrentScreen::EditingBulk
+        | CurrentScreen::EditingAuth
+        | CurrentScreen::EditingOptions
+        | CurrentScreen::EditingTests => {
             app.current_screen = CurrentScreen::Values;
         }
         _ => {
@@ -178,22 +193,58 @@ pub fn get_navigation_context(app: &App) -> String {
                 ValuesScreen::Body => "Body",
                 ValuesScreen::Headers => "Headers",
                 ValuesScreen::Params => "Params",
+                ValuesScreen::Auth => "Auth",
+                ValuesScreen::Options => "Options",
+                ValuesScreen::Tests => "Tests",
             };
             format!("Values - {}", tab)
         }
         CurrentScreen::Response => {
-            let tab = if app.response_tab_selected == 0 {
-                "Headers"
-            } else {
-                "Body"
+            let tab = match app.response_tab_selected {
+                0 => "Headers",
+                1 if app.response_table => "Table",
+                1 if app.response_tree.is_some() => "Tree",
+                1 if app.html_preview_active() => "Preview",
+                1 => "Body",
+                2 => "TLS",
+                3 => "Panel",
+                4 => "Tests",
+                _ => "Request",
             };
             format!("Response - {}", tab)
         }
         CurrentScreen::EditingUrl => "Editing URL".to_string(),
         CurrentScreen::EditingBody => "Editing Body".to_string(),
+        CurrentScreen::EditingForm => "Editing Form".to_string(),
         CurrentScreen::EditingHeaders => "Editing Headers".to_string(),
         CurrentScreen::EditingParams => "Editing Params".to_string(),
+        CurrentScreen::EditingBulk => "Bulk Edit".to_string(),
+        CurrentScreen::EditingTests => "Editing Tests".to_string(),
+        CurrentScreen::EditingAuth => "Editing Auth".to_string(),
+        CurrentScreen::EditingOptions => "Editing Options".to_string(),
+        CurrentScreen::EditingNote => "Editing Note".to_string(),
+        CurrentScreen::Searching => "Search".to_string(),
+        CurrentScreen::EditingFilter => "Filter".to_string(),
+        CurrentScreen::ResendWith => "Resend With".to_string(),
+        CurrentScreen::EditingWatch => "Watch".to_string(),
+        CurrentScreen::EditingPoll => "Poll".to_string(),
         CurrentScreen::Help => "Help".to_string(),
+        CurrentScreen::Export => "Export".to_string(),
+        CurrentScreen::Collections => "Collections".to_string(),
+        CurrentScreen::Timeline => "Timeline".to_string(),
+        CurrentScreen::Finder => "Find".to_string(),
+        CurrentScreen::EditingImportPath => "Import File".to_string(),
+        CurrentScreen::ImportReview => "Review Import".to_string(),
+        CurrentScreen::Copy => "Copy".to_string(),
+        CurrentScreen::PasteImport => "Import Paste".to_string(),
+        CurrentScreen::Cookies => "Cookies".to_string(),
+        CurrentScreen::EditingCookie => "Editing Cookie".to_string(),
+        CurrentScreen::SigningPreview => "Signature Preview".to_string(),
+        CurrentScreen::DryRun => "Dry Run".to_string(),
+        CurrentScreen::Latency => "Latency".to_string(),
+        CurrentScreen::EditingMatrix | CurrentScreen::MatrixResults => "Matrix".to_string(),
+        CurrentScreen::RunResults => "Run".to_string(),
+        CurrentScreen::EditingLoad | CurrentScreen::LoadResults => "Load Test".to_string(),
         CurrentScreen::Exiting => "Exiting".to_string(),
     }
 }
@@ -246,8 +297,26 @@ mod tests {
         navigate_values_right(&mut app).unwrap();
         assert_eq!(app.values_screen, ValuesScreen::Params);
 
-        // Should stay at Params
         navigate_values_right(&mut app).unwrap();
+        assert_eq!(app.values_screen, ValuesScreen::Auth);
+
+        navigate_values_right(&mut app).unwrap();
+        assert_eq!(app.values_screen, ValuesScreen::Options);
+
+        navigate_values_right(&mut app).unwrap();
+        assert_eq!(app.values_screen, ValuesScreen::Tests);
+
+        // Should stay at Tests
+        navigate_values_right(&mut app).unwrap();
+        assert_eq!(app.values_screen, ValuesScreen::Tests);
+
+        navigate_values_left(&mut app).unwrap();
+        assert_eq!(app.values_screen, ValuesScreen::Options);
+
+        navigate_values_left(&mut app).unwrap();
+        assert_eq!(app.values_screen, ValuesScreen::Auth);
+
+        navigate_values_left(&mut app).unwrap();
         assert_eq!(app.values_screen, ValuesScreen::Params);
 
         navigate_values_left(&mut app).unwrap();
@@ -30,8 +30,15 @@ pub async fn send_current_request(app: &mut App) -> Result<Option<String>> {
 
     // Send the request
     match app.tabs[current_tab_index].request.send().await {
-        Ok((status_code, headers, body)) => {
-            handle_successful_response(app, current_tab_index, status_code, headers, body).await
+        Ok(raw) => {
+            handle_successful_response(
+                app,
+                current_tab_index,
+                raw.status_code,
+                raw.headers,
+                raw.body,
+            )
+            .await
         }
         Err(e) => handle_request_error(e),
     }
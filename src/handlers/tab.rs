@@ -49,11 +49,14 @@ pub fn handle_prev_tab(app: &mut App) -> Result<Option<String>> {
     }
 }
 
-/// Handles switching to a specific tab by index
-#[cfg(test)]
+/// Handles switching to a specific tab by index, as the number keys do
 pub fn handle_switch_to_tab(app: &mut App, index: usize) -> Result<Option<String>> {
     if index >= app.tabs.len() {
-        return Ok(Some(format!("Invalid tab index: {}", index)));
+        return Ok(Some(format!(
+            "There is no tab {} (only {} open)",
+            index + 1,
+            app.tabs.len()
+        )));
     }
 
     if let Err(e) = app.save_current_tab_state() {
@@ -61,6 +64,7 @@ pub fn handle_switch_to_tab(app: &mut App, index: usize) -> Result<Option<String
     }
 
     app.selected_tab = index;
+    app.close_warned = None;
 
     if let Err(e) = app.restore_current_tab_state() {
         return Ok(Some(format!("Failed to restore tab: {}", e)));
@@ -101,4 +105,21 @@ mod tests {
         assert!(result.is_some());
         assert_eq!(app.tabs.len(), 1);
     }
+
+    #[test]
+    fn test_window_title_follows_tab() {
+        let mut app = App::new();
+        assert_eq!(app.window_title(), "restless: Tab 1 GET");
+
+        app.tabs[0].response = Some(crate::logic::response::Response::new_unchecked(
+            404,
+            String::new(),
+            String::new(),
+        ));
+        app.selected_method = crate::logic::HttpMethod::POST;
+        assert_eq!(app.window_title(), "restless: Tab 1 POST 404");
+
+        handle_new_tab(&mut app).unwrap();
+        assert_eq!(app.window_title(), "restless: Tab 2 GET");
+    }
 }
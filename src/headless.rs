@@ -0,0 +1,119 @@
+//! One request sent without the interface (`restless send`)
+//!
+//! The response is printed the way it came in: the status line, the headers,
+//! a blank line and the body, so it can be piped on in scripts and CI. The
+//! exit code tells how it went, see [`run`].
+
+use crate::error::Result;
+use crate::logic::request::{send_request, RawResponse, Request};
+use std::io::Write;
+
+/// Exit code for a 4xx or 5xx response
+pub const EXIT_HTTP_ERROR: i32 = 1;
+/// Exit code when no response came back at all
+pub const EXIT_SEND_FAILED: i32 = 2;
+
+/// Sends `request` and prints its response to `output`, returning the exit
+/// code: 0 for a response below 400, [`EXIT_HTTP_ERROR`] from 400 on and
+/// [`EXIT_SEND_FAILED`] when the request failed, with the reason on stderr
+pub async fn run<W: Write>(request: &Request, output: &mut W) -> Result<i32> {
+    let mut raw = match send_request(request).await {
+        Ok(raw) => raw,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return Ok(EXIT_SEND_FAILED);
+        }
+    };
+    write_head(&raw, output)?;
+    if let Some(mut stream) = raw.stream.take() {
+        // Events are printed as they arrive until the server closes the stream
+        while let Some(item) = stream.next().await {
+            match item {
+                Ok(event) => {
+                    write!(output, "{}", event.to_text())?;
+                    output.flush()?;
+                }
+                Err(error) => eprintln!("Stream error: {}", error),
+            }
+        }
+    } else {
+        match raw.wire_bytes.as_ref().or(raw.binary.as_ref()) {
+            Some(bytes) => output.write_all(bytes)?,
+            None => output.write_all(raw.body.as_bytes())?,
+        }
+    }
+    output.flush()?;
+    Ok(if raw.status_code >= 400 {
+        EXIT_HTTP_ERROR
+    } else {
+        0
+    })
+}
+
+/// The status line and headers, followed by the blank line before the body
+fn write_head<W: Write>(raw: &RawResponse, output: &mut W) -> Result<()> {
+    let reason = reqwest::StatusCode::from_u16(raw.status_code)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .map(|reason| format!(" {}", reason))
+        .unwrap_or_default();
+    writeln!(output, "{} {}{}", raw.version, raw.status_code, reason)?;
+    for line in raw.headers.lines() {
+        writeln!(output, "{}", line)?;
+    }
+    writeln!(output)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::TestServer;
+
+    #[tokio::test]
+    async fn test_send_prints_response() {
+        let server = TestServer::new(&[
+            b"HTTP/1.1 201 Created\r\nContent-Length: 9\r\nConnection: close\r\n\r\n{\"id\":7}\n",
+        ]);
+        let request = Request {
+            url: server.url("/users"),
+            method: reqwest::Method::POST,
+            headers: vec![("X-Test".to_string(), "1".to_string())],
+            body: Some("{\"name\":\"a\"}".to_string()),
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        let code = run(&request, &mut output).await.unwrap();
+        let asked = server.request();
+
+        assert_eq!(code, 0);
+        assert!(asked.starts_with("POST /users HTTP/1.1"));
+        assert!(asked.to_lowercase().contains("x-test: 1"));
+        assert!(asked.ends_with("{\"name\":\"a\"}"));
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("HTTP/1.1 201 Created\n"));
+        assert!(output.contains("content-length: 9\n"));
+        assert!(output.ends_with("\n\n{\"id\":7}\n"));
+    }
+
+    #[tokio::test]
+    async fn test_exit_codes() {
+        let server = TestServer::new(&[
+            b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        ]);
+        let request = Request {
+            url: server.url("/"),
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        assert_eq!(run(&request, &mut output).await.unwrap(), EXIT_HTTP_ERROR);
+
+        let request = Request {
+            url: "http://127.0.0.1:1/".to_string(),
+            ..Default::default()
+        };
+        let mut output = Vec::new();
+        assert_eq!(run(&request, &mut output).await.unwrap(), EXIT_SEND_FAILED);
+        assert!(output.is_empty());
+    }
+}
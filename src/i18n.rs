@@ -0,0 +1,201 @@
+//! Translations of the interface
+//!
+//! User-facing text is written in English in the code and looked up in the
+//! catalog of the active locale, keyed by that English text. A catalog is a
+//! TOML file of translations:
+//!
+//! ```toml
+//! "Quit application" = "Anwendung beenden"
+//! "Network Error: {error}" = "Netzwerkfehler: {error}"
+//! ```
+//!
+//! `{name}` placeholders are filled in after the lookup, so a translation
+//! must keep the placeholders of its text. Text without a translation stays
+//! English. German ships with restless; other locales, or changes to German,
+//! go in `locales/<locale>.toml` in the config directory.
+
+use crate::error::{RestlessError, Result};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// Locale of the text in the code
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Catalogs built into restless
+const BUILT_IN: &[(&str, &str)] = &[("de", include_str!("locales/de.toml"))];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Catalog {
+    pub locale: String,
+    translations: HashMap<String, String>,
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Catalog {
+            locale: DEFAULT_LOCALE.to_string(),
+            translations: HashMap::new(),
+        }
+    }
+}
+
+impl Catalog {
+    /// Reads a catalog file, checking every translation keeps the
+    /// placeholders of its text
+    pub fn parse(locale: &str, content: &str) -> Result<Catalog> {
+        let invalid = |message: String| {
+            RestlessError::configuration(format!("Invalid {} translations: {}", locale, message))
+        };
+        let translations: HashMap<String, String> =
+            toml::from_str(content).map_err(|e| invalid(e.to_string()))?;
+        for (text, translation) in &translations {
+            if placeholders(text) != placeholders(translation) {
+                return Err(invalid(format!(
+                    "'{}' must keep the placeholders of '{}'",
+                    translation, text
+                )));
+            }
+        }
+        Ok(Catalog {
+            locale: locale.to_string(),
+            translations,
+        })
+    }
+
+    /// The translation of `text`, or `text` itself without one
+    pub fn tr<'a>(&'a self, text: &'a str) -> &'a str {
+        self.translations
+            .get(text)
+            .map(String::as_str)
+            .unwrap_or(text)
+    }
+
+    /// Translates `text` and fills in its `{name}` placeholders
+    pub fn format(&self, text: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        args.iter()
+            .fold(self.tr(text).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{}}}", name), &value.to_string())
+            })
+    }
+}
+
+/// The `{name}` placeholders in a text, sorted
+fn placeholders(text: &str) -> Vec<&str> {
+    let mut found: Vec<&str> = text
+        .match_indices('{')
+        .filter_map(|(start, _)| {
+            let rest = &text[start + 1..];
+            let end = rest.find('}')?;
+            let name = &rest[..end];
+            (!name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_'))
+                .then_some(name)
+        })
+        .collect();
+    found.sort_unstable();
+    found
+}
+
+/// Loads the catalog of a locale: the built-in one, if any, with the
+/// translations of `locales/<locale>.toml` in `dir` on top
+pub fn load(locale: &str, dir: Option<&Path>) -> Result<Catalog> {
+    let locale = locale.trim().to_lowercase();
+    if locale == DEFAULT_LOCALE {
+        return Ok(Catalog::default());
+    }
+    let built_in = BUILT_IN
+        .iter()
+        .find(|(name, _)| *name == locale)
+        .map(|(_, content)| Catalog::parse(&locale, content))
+        .transpose()?;
+    let file = dir
+        .map(|dir| dir.join(format!("{}.toml", locale)))
+        .filter(|path| path.is_file());
+    let user = match file {
+        Some(path) => Some(Catalog::parse(&locale, &std::fs::read_to_string(path)?)?),
+        None => None,
+    };
+    match (built_in, user) {
+        (None, None) => Err(RestlessError::configuration(format!(
+            "No translations for locale '{}'",
+            locale
+        ))),
+        (Some(catalog), None) | (None, Some(catalog)) => Ok(catalog),
+        (Some(mut catalog), Some(user)) => {
+            catalog.translations.extend(user.translations);
+            Ok(catalog)
+        }
+    }
+}
+
+/// The language of the system locale, from `LC_ALL`, `LC_MESSAGES` or
+/// `LANG`, e.g. `de` for `de_DE.UTF-8`
+pub fn system_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| language(&value))
+}
+
+fn language(value: &str) -> Option<String> {
+    let language = value.split(['_', '.', '@', '-']).next()?.to_lowercase();
+    (!language.is_empty() && language != "c" && language != "posix").then_some(language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate() {
+        let catalog = Catalog::parse(
+            "de",
+            "\"Quit application\" = \"Anwendung beenden\"\n\"Tab {current}/{count}\" = \"Tab {current} von {count}\"\n",
+        )
+        .unwrap();
+        assert_eq!(catalog.tr("Quit application"), "Anwendung beenden");
+        assert_eq!(catalog.tr("Next tab"), "Next tab");
+        assert_eq!(
+            catalog.format("Tab {current}/{count}", &[("current", &2), ("count", &3)]),
+            "Tab 2 von 3"
+        );
+        assert_eq!(
+            Catalog::default().format("Tab {current}/{count}", &[("current", &2), ("count", &3)]),
+            "Tab 2/3"
+        );
+
+        assert!(Catalog::parse("de", "\"Error: {error}\" = \"Fehler\"\n").is_err());
+        assert!(Catalog::parse("de", "\"Error: {error}\" = \"Fehler: {fehler}\"\n").is_err());
+        assert!(Catalog::parse("de", "translations = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_load_catalogs() {
+        // Every built-in catalog is valid
+        for (locale, content) in BUILT_IN {
+            Catalog::parse(locale, content).unwrap();
+        }
+        assert_eq!(load("en", None).unwrap(), Catalog::default());
+        assert_eq!(
+            load("DE", None).unwrap().tr("Quit application"),
+            "Anwendung beenden"
+        );
+        assert!(load("xx", None).is_err());
+
+        let dir = std::env::temp_dir().join(format!("restless-i18n-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("de.toml"), "\"Quit application\" = \"Beenden\"\n").unwrap();
+        std::fs::write(dir.join("xx.toml"), "\"Next tab\" = \"Nekst tab\"\n").unwrap();
+        let german = load("de", Some(&dir)).unwrap();
+        assert_eq!(german.tr("Quit application"), "Beenden");
+        assert_eq!(german.tr("Next tab"), "Nächster Tab");
+        assert_eq!(load("xx", Some(&dir)).unwrap().tr("Next tab"), "Nekst tab");
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(language("de_DE.UTF-8").as_deref(), Some("de"));
+        assert_eq!(language("pt-BR").as_deref(), Some("pt"));
+        assert_eq!(language("C.UTF-8"), None);
+        assert_eq!(language("POSIX"), None);
+    }
+}
@@ -0,0 +1,370 @@
+//! curl command import
+//!
+//! Understands the options that describe a request (method, headers, data,
+//! basic auth, cookies, user agent, `-G`) and ignores transport options such
+//! as `-s`, `-L` or `--compressed`.
+
+use crate::error::{RestlessError, Result};
+use crate::logic::form;
+use crate::logic::options::RequestOptions;
+use crate::logic::request::Request;
+use crate::logic::HttpMethod;
+use base64::Engine;
+use reqwest::Method;
+
+/// Options that take a value but don't affect the request itself
+const IGNORED_WITH_VALUE: &[&str] = &[
+    "-o",
+    "--output",
+    "--connect-timeout",
+    "-w",
+    "--write-out",
+    "--retry",
+    "-c",
+    "--cookie-jar",
+];
+
+/// Splits a shell command line into words (quotes, escapes, line continuations)
+pub fn split_words(command: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err(unterminated("'")),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => current.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => return Err(unterminated("\"")),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err(unterminated("\"")),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                // Line continuation
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(c) => {
+                    in_word = true;
+                    current.push(c);
+                }
+                None => {}
+            },
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+fn unterminated(quote: &str) -> RestlessError {
+    RestlessError::configuration(format!("Unterminated {} in curl command", quote))
+}
+
+/// Parses a curl command into a request
+pub fn parse(command: &str) -> Result<Request> {
+    let words = split_words(command.trim())?;
+    let mut args = words.into_iter().skip(1);
+
+    let mut method: Option<String> = None;
+    let mut url: Option<String> = None;
+    let mut headers: Vec<(String, String)> = Vec::new();
+    let mut data: Vec<String> = Vec::new();
+    let mut form_fields: Vec<(String, String)> = Vec::new();
+    let mut as_query = false;
+    let mut request_options = RequestOptions::default();
+
+    while let Some(arg) = args.next() {
+        // Support both `-X POST` and `-XPOST` / `--request=POST`
+        let (flag, inline) = if let Some((flag, value)) =
+            arg.strip_prefix("--").and_then(|rest| rest.split_once('='))
+        {
+            (format!("--{}", flag), Some(value.to_string()))
+        } else if arg.len() > 2
+            && arg.starts_with('-')
+            && !arg.starts_with("--")
+            && arg.is_char_boundary(2)
+        {
+            let (flag, value) = arg.split_at(2);
+            if "XHdubAexUEF".contains(&flag[1..]) {
+                (flag.to_string(), Some(value.to_string()))
+            } else {
+                (arg.clone(), None)
+            }
+        } else {
+            (arg.clone(), None)
+        };
+        let mut value = |name: &str| {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| RestlessError::configuration(format!("{} needs a value", name)))
+        };
+
+        match flag.as_str() {
+            "-X" | "--request" => method = Some(value(&flag)?.to_uppercase()),
+            "-H" | "--header" => {
+                let header = value(&flag)?;
+                if let Some((key, val)) = header.split_once(':') {
+                    headers.push((key.trim().to_string(), val.trim().to_string()));
+                }
+            }
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii"
+            | "--data-urlencode" => data.push(value(&flag)?),
+            "-F" | "--form" => {
+                let (name, field) = form::parse_field(&value(&flag)?)?;
+                // restless picks the file's content type itself
+                let field = match field.split_once(";type=") {
+                    Some((path, _)) if field.starts_with('@') => path.to_string(),
+                    _ => field,
+                };
+                form_fields.push((name, field));
+            }
+            "--json" => {
+                data.push(value(&flag)?);
+                headers.push(("Content-Type".to_string(), "application/json".to_string()));
+                headers.push(("Accept".to_string(), "application/json".to_string()));
+            }
+            "-u" | "--user" => {
+                let credentials = base64::engine::general_purpose::STANDARD.encode(value(&flag)?);
+                headers.push((
+                    "Authorization".to_string(),
+                    format!("Basic {}", credentials),
+                ));
+            }
+            "-b" | "--cookie" => headers.push(("Cookie".to_string(), value(&flag)?)),
+            "-A" | "--user-agent" => headers.push(("User-Agent".to_string(), value(&flag)?)),
+            "-e" | "--referer" => headers.push(("Referer".to_string(), value(&flag)?)),
+            "-E" | "--cert" => {
+                // curl allows `file:password`
+                let cert = value(&flag)?;
+                match cert.split_once(':').filter(|(file, _)| file.len() > 1) {
+                    Some((file, password)) => {
+                        request_options.client_cert = Some(file.to_string());
+                        request_options.client_cert_password = Some(password.to_string());
+                    }
+                    None => request_options.client_cert = Some(cert),
+                }
+            }
+            "--key" => request_options.client_key = Some(value(&flag)?),
+            "--cacert" => request_options.ca_cert = Some(value(&flag)?),
+            "-x" | "--proxy" => request_options.proxy = Some(value(&flag)?),
+            "-U" | "--proxy-user" => request_options.proxy_auth = Some(value(&flag)?),
+            "--noproxy" if value(&flag)? == "*" => request_options.proxy = Some("none".to_string()),
+            "-k" | "--insecure" => request_options.insecure = Some("true".to_string()),
+            "--http1.1" => request_options.http_version = Some("1.1".to_string()),
+            "--tlsv1.0" | "--tlsv1.1" | "--tlsv1.2" => {
+                request_options.min_tls_version = Some(flag["--tlsv".len()..].to_string())
+            }
+            "--tls-max" => request_options.max_tls_version = Some(value(&flag)?),
+            "-m" | "--max-time" => {
+                // curl takes fractions of a second; restless counts whole ones
+                let seconds = value(&flag)?;
+                let seconds: f64 = seconds.parse().map_err(|_| {
+                    RestlessError::configuration(format!("Invalid {} '{}'", flag, seconds))
+                })?;
+                request_options.timeout = Some((seconds.ceil() as u64).max(1).to_string());
+            }
+            "--http2" | "--http2-prior-knowledge" => {
+                request_options.http_version = Some("2".to_string())
+            }
+            "-G" | "--get" => as_query = true,
+            "-I" | "--head" => method = Some("HEAD".to_string()),
+            "--url" => url = Some(value(&flag)?),
+            flag if IGNORED_WITH_VALUE.contains(&flag) => {
+                value(flag)?;
+            }
+            flag if flag.starts_with('-') => {}
+            _ => url = Some(arg),
+        }
+    }
+
+    let url = url.ok_or_else(|| RestlessError::configuration("curl command has no URL"))?;
+    request_options.validate()?;
+    let mut request = Request {
+        url,
+        options: request_options,
+        ..Default::default()
+    };
+
+    let body = (!data.is_empty()).then(|| data.join("&"));
+    if as_query {
+        if let Some(body) = body {
+            for pair in body.split('&').filter(|pair| !pair.is_empty()) {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                request.params.push((key.to_string(), value.to_string()));
+            }
+        }
+    } else {
+        request.body = body;
+    }
+    if !form_fields.is_empty() {
+        request.form = Some(form_fields);
+    }
+
+    let method = method.unwrap_or_else(|| {
+        if request.body.is_some() || request.form.is_some() {
+            "POST".to_string()
+        } else {
+            "GET".to_string()
+        }
+    });
+    request.method = Method::from_bytes(method.as_bytes())
+        .map_err(|_| RestlessError::invalid_http_method(method.clone()))?;
+    HttpMethod::try_from(&request.method)?;
+
+    // curl adds this for -d; restless doesn't, so keep the request equivalent
+    if request.body.is_some()
+        && !headers
+            .iter()
+            .any(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+    {
+        headers.push((
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        ));
+    }
+    request.headers = headers;
+
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_words() {
+        let words = split_words("curl 'a b' \"c \\\"d\\\"\" e\\ f \\\n  --flag").unwrap();
+        assert_eq!(words, vec!["curl", "a b", "c \"d\"", "e f", "--flag"]);
+        assert!(split_words("curl 'open").is_err());
+    }
+
+    #[test]
+    fn test_parse_post_with_headers() {
+        let request = parse(
+            "curl -X POST 'https://api.example.com/users' \\\n  \
+             -H 'Content-Type: application/json' -H 'X-Trace: 1' \\\n  \
+             --data-raw '{\"name\": \"Ada\"}' -sSL --compressed",
+        )
+        .unwrap();
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.url, "https://api.example.com/users");
+        assert_eq!(request.headers.len(), 2);
+        assert_eq!(request.headers[1], ("X-Trace".to_string(), "1".to_string()));
+        assert_eq!(request.body.as_deref(), Some("{\"name\": \"Ada\"}"));
+    }
+
+    #[test]
+    fn test_parse_form() {
+        let request =
+            parse("curl https://x.test/upload -F note=hi -F 'file=@./a.png;type=image/png'")
+                .unwrap();
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(
+            request.form,
+            Some(vec![
+                ("note".to_string(), "hi".to_string()),
+                ("file".to_string(), "@./a.png".to_string()),
+            ])
+        );
+        assert_eq!(request.body, None);
+        assert!(request.headers.is_empty());
+    }
+
+    #[test]
+    fn test_parse_implied_method_and_options() {
+        let request = parse("curl https://x.test -d a=1 -d b=2 -u user:pass").unwrap();
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.body.as_deref(), Some("a=1&b=2"));
+        assert!(request.headers.contains(&(
+            "Authorization".to_string(),
+            "Basic dXNlcjpwYXNz".to_string()
+        )));
+        assert!(request
+            .headers
+            .iter()
+            .any(|(k, v)| k == "Content-Type" && v == "application/x-www-form-urlencoded"));
+
+        let request = parse("curl -G https://x.test -d q=rust -XDELETE -o out.json").unwrap();
+        assert_eq!(request.method, Method::DELETE);
+        assert_eq!(request.params, vec![("q".to_string(), "rust".to_string())]);
+        assert_eq!(request.body, None);
+
+        let request = parse("curl -x socks5h://localhost:1080 -U me:pw https://x.test").unwrap();
+        assert_eq!(
+            request.options.proxy.as_deref(),
+            Some("socks5h://localhost:1080")
+        );
+        assert_eq!(request.options.proxy_auth.as_deref(), Some("me:pw"));
+
+        let request = parse("curl --cert client.p12:secret --key k.pem https://x.test").unwrap();
+        assert_eq!(request.options.client_cert.as_deref(), Some("client.p12"));
+        assert_eq!(
+            request.options.client_cert_password.as_deref(),
+            Some("secret")
+        );
+        assert_eq!(request.options.client_key.as_deref(), Some("k.pem"));
+        assert!(parse("curl -k https://x.test")
+            .unwrap()
+            .options
+            .is_insecure());
+        let request = parse("curl --http1.1 https://x.test").unwrap();
+        assert_eq!(request.options.http_version.as_deref(), Some("1.1"));
+        let request = parse("curl --tlsv1.2 --tls-max 1.2 https://x.test").unwrap();
+        assert_eq!(request.options.min_tls_version.as_deref(), Some("1.2"));
+        assert_eq!(request.options.max_tls_version.as_deref(), Some("1.2"));
+        assert!(parse("curl --tls-max 1.3 https://x.test").is_err());
+        let request = parse("curl -m 2.5 https://x.test").unwrap();
+        assert_eq!(request.options.timeout.as_deref(), Some("3"));
+        assert!(parse("curl --max-time soon https://x.test").is_err());
+
+        assert!(parse("curl -X PATCH https://x.test").is_err());
+        assert!(parse("curl -s").is_err());
+    }
+
+    #[test]
+    fn test_parse_multibyte_flag() {
+        // An en dash pasted from a document instead of a second hyphen
+        let request = parse("curl -\u{2013}data x=1 https://x.test").unwrap();
+        assert_eq!(request.url, "https://x.test");
+        assert_eq!(request.body, None);
+        let request = parse("curl -\u{e9} https://x.test").unwrap();
+        assert_eq!(request.url, "https://x.test");
+    }
+}
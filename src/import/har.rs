@@ -0,0 +1,158 @@
+//! HTTP Archive (HAR) import
+//!
+//! Every entry of `log.entries` becomes a saved request named after its
+//! method and path. Headers the browser adds on its own (HTTP/2 pseudo
+//! headers, `Host`, `Content-Length` and cookies) are left out.
+
+use crate::collections::SavedRequest;
+use crate::error::{RestlessError, Result};
+use serde_json::Value;
+
+const SKIPPED_HEADERS: &[&str] = &["host", "content-length", "cookie", "connection"];
+
+/// Parses the entries of a HAR document
+pub fn parse(har: &Value) -> Result<Vec<SavedRequest>> {
+    let entries = har
+        .pointer("/log/entries")
+        .and_then(Value::as_array)
+        .ok_or_else(|| RestlessError::configuration("HAR file has no log.entries"))?;
+    Ok(entries
+        .iter()
+        .filter_map(|entry| entry.get("request"))
+        .map(request)
+        .collect())
+}
+
+fn request(request: &Value) -> SavedRequest {
+    let method = text(request, "method").unwrap_or("GET").to_uppercase();
+    let full_url = text(request, "url").unwrap_or_default();
+    let url = full_url.split(['?', '#']).next().unwrap_or_default();
+    let path = url
+        .split_once("://")
+        .and_then(|(_, rest)| rest.find('/').map(|index| &rest[index..]))
+        .unwrap_or("/");
+
+    let headers = name_values(request.get("headers"))
+        .into_iter()
+        .filter(|(name, _)| {
+            !name.starts_with(':') && !SKIPPED_HEADERS.contains(&name.to_lowercase().as_str())
+        })
+        .collect();
+
+    let post_data = request.get("postData");
+    let params = post_data
+        .and_then(|data| data.get("params"))
+        .and_then(Value::as_array);
+    let is_multipart = post_data
+        .and_then(|data| text(data, "mimeType"))
+        .is_some_and(|mime| mime.starts_with("multipart/form-data"));
+    let form = params.filter(|_| is_multipart).map(|params| {
+        params
+            .iter()
+            .map(|param| {
+                let name = text(param, "name").unwrap_or_default().to_string();
+                let value = match text(param, "fileName") {
+                    Some(file) => format!("@{}", file),
+                    None => text(param, "value").unwrap_or_default().to_string(),
+                };
+                (name, value)
+            })
+            .collect()
+    });
+    let body = post_data
+        .filter(|_| form.is_none())
+        .and_then(|data| text(data, "text"))
+        .filter(|body| !body.is_empty())
+        .map(str::to_string);
+
+    SavedRequest {
+        name: format!("{} {}", method, path),
+        description: String::new(),
+        method,
+        url: url.to_string(),
+        headers,
+        params: name_values(request.get("queryString")),
+        disabled_headers: Vec::new(),
+        disabled_params: Vec::new(),
+        tests: String::new(),
+        body,
+        form,
+        auth: None,
+        options: Default::default(),
+        panel: None,
+    }
+}
+
+fn text<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+fn name_values(list: Option<&Value>) -> Vec<(String, String)> {
+    list.and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    Some((
+                        text(item, "name")?.to_string(),
+                        text(item, "value").unwrap_or_default().to_string(),
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_har() {
+        let har: Value = serde_json::from_str(
+            r#"{"log": {"entries": [
+                {"request": {"method": "GET", "url": "https://api.example.com/users?page=2",
+                    "headers": [{"name": ":authority", "value": "api.example.com"},
+                                {"name": "Accept", "value": "application/json"},
+                                {"name": "Host", "value": "api.example.com"}],
+                    "queryString": [{"name": "page", "value": "2"}]}},
+                {"request": {"method": "post", "url": "https://api.example.com/upload",
+                    "headers": [],
+                    "postData": {"mimeType": "multipart/form-data; boundary=x",
+                        "params": [{"name": "note", "value": "hi"},
+                                   {"name": "file", "fileName": "a.png"}]}}},
+                {"request": {"method": "PUT", "url": "https://api.example.com",
+                    "postData": {"mimeType": "application/json", "text": "{}"}}}
+            ]}}"#,
+        )
+        .unwrap();
+
+        let requests = parse(&har).unwrap();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].name, "GET /users");
+        assert_eq!(requests[0].url, "https://api.example.com/users");
+        assert_eq!(
+            requests[0].headers,
+            vec![("Accept".to_string(), "application/json".to_string())]
+        );
+        assert_eq!(
+            requests[0].params,
+            vec![("page".to_string(), "2".to_string())]
+        );
+
+        assert_eq!(requests[1].method, "POST");
+        assert_eq!(
+            requests[1].form,
+            Some(vec![
+                ("note".to_string(), "hi".to_string()),
+                ("file".to_string(), "@a.png".to_string()),
+            ])
+        );
+        assert_eq!(requests[1].body, None);
+
+        assert_eq!(requests[2].name, "PUT /");
+        assert_eq!(requests[2].body.as_deref(), Some("{}"));
+
+        assert!(parse(&serde_json::json!({"log": {}})).is_err());
+    }
+}
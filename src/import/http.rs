@@ -0,0 +1,271 @@
+//! Raw HTTP request import
+//!
+//! Accepts the text of an HTTP/1.x request as copied from browser dev tools,
+//! proxies or `.http` files:
+//!
+//! ```text
+//! POST /users HTTP/1.1
+//! Host: api.example.com
+//! Content-Type: application/json
+//!
+//! {"name": "Ada"}
+//! ```
+//!
+//! Whole `.http` files, as used by editor REST clients, are read by
+//! [`parse_file`]: requests are separated by `###` lines, the HTTP version
+//! is optional, and `#` or `//` lines are comments. A `### Name` separator or
+//! a `# @name Name` comment names the request that follows. File-level
+//! `@variable = value` definitions aren't supported; `{{variables}}` come
+//! from the active environment as everywhere else.
+
+use crate::collections::SavedRequest;
+use crate::error::{RestlessError, Result};
+use crate::logic::request::Request;
+use crate::logic::HttpMethod;
+use reqwest::Method;
+
+const METHODS: &[&str] = &[
+    "GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+];
+
+/// Whether a line looks like `METHOD target HTTP/x`
+pub fn is_request_line(line: &str) -> bool {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    matches!(parts.as_slice(), [method, _, version]
+        if METHODS.contains(method) && version.starts_with("HTTP/"))
+}
+
+/// Parses a raw HTTP request; relative targets are resolved against `Host`
+pub fn parse(text: &str) -> Result<Request> {
+    let text = text.trim_start().replace("\r\n", "\n");
+    let (head, body) = text.split_once("\n\n").unwrap_or((&text, ""));
+    let mut lines = head.lines();
+
+    let request_line = lines.next().unwrap_or_default();
+    if !is_request_line(request_line) {
+        return Err(RestlessError::configuration(format!(
+            "Not an HTTP request line: {}",
+            request_line
+        )));
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let target = parts.next().unwrap_or_default();
+
+    let mut host = None;
+    let mut headers = Vec::new();
+    for line in lines {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if key.eq_ignore_ascii_case("host") {
+            host = Some(value.to_string());
+        } else if !key.eq_ignore_ascii_case("content-length") {
+            // Content-Length is recomputed when the request is sent
+            headers.push((key.to_string(), value.to_string()));
+        }
+    }
+
+    let url = resolve_target(target, host.as_deref())?;
+
+    let method = Method::from_bytes(method.as_bytes())
+        .map_err(|_| RestlessError::invalid_http_method(method))?;
+    HttpMethod::try_from(&method)?;
+
+    let body = body.trim_end_matches('\n');
+    Ok(Request {
+        url,
+        method,
+        headers,
+        body: (!body.is_empty()).then(|| body.to_string()),
+        ..Default::default()
+    })
+}
+
+/// Makes a relative request target absolute using the `Host` header
+fn resolve_target(target: &str, host: Option<&str>) -> Result<String> {
+    if !target.starts_with('/') {
+        return Ok(target.to_string());
+    }
+    let host = host.ok_or_else(|| {
+        RestlessError::configuration("HTTP request has a relative target but no Host header")
+    })?;
+    let scheme = if host.ends_with(":80") {
+        "http"
+    } else {
+        "https"
+    };
+    Ok(format!("{}://{}{}", scheme, host, target))
+}
+
+/// Parses every request in a `.http` file
+pub fn parse_file(text: &str) -> Result<Vec<SavedRequest>> {
+    let text = text.replace("\r\n", "\n");
+    let mut requests = Vec::new();
+    let mut name = None;
+    let mut block = Vec::new();
+    for line in text.lines() {
+        if let Some(separator) = line.strip_prefix("###") {
+            requests.extend(parse_block(&block, name.take())?);
+            block.clear();
+            let separator = separator.trim();
+            name = (!separator.is_empty()).then(|| separator.to_string());
+        } else {
+            block.push(line);
+        }
+    }
+    requests.extend(parse_block(&block, name)?);
+    Ok(requests)
+}
+
+/// Parses the lines between two `###` separators; `None` when there is no
+/// request among them, e.g. a file's leading comments
+fn parse_block(lines: &[&str], mut name: Option<String>) -> Result<Option<SavedRequest>> {
+    let mut lines = lines.iter().map(|line| line.trim_end());
+    let request_line = loop {
+        let Some(line) = lines.next() else {
+            return Ok(None);
+        };
+        let line = line.trim_start();
+        if let Some(comment) = line.strip_prefix('#').or_else(|| line.strip_prefix("//")) {
+            if let Some(tag) = comment.trim().strip_prefix("@name") {
+                name = Some(tag.trim().to_string());
+            }
+        } else if !line.is_empty() {
+            break line;
+        }
+    };
+
+    let mut parts: Vec<&str> = request_line.split_whitespace().collect();
+    if parts.len() > 1 && parts[parts.len() - 1].starts_with("HTTP/") {
+        parts.pop();
+    }
+    let (method, target) = match parts.as_slice() {
+        [method, target] if METHODS.contains(method) => (*method, *target),
+        [target] => ("GET", *target),
+        _ => {
+            return Err(RestlessError::configuration(format!(
+                "Not an HTTP request line: {}",
+                request_line
+            )))
+        }
+    };
+
+    let mut host = None;
+    let mut headers = Vec::new();
+    for line in lines.by_ref() {
+        if line.is_empty() {
+            break;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            return Err(RestlessError::configuration(format!(
+                "Not a header in request {}: {}",
+                request_line, line
+            )));
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if key.eq_ignore_ascii_case("host") {
+            host = Some(value.to_string());
+        } else if !key.eq_ignore_ascii_case("content-length") {
+            headers.push((key.to_string(), value.to_string()));
+        }
+    }
+    let url = resolve_target(target, host.as_deref())?;
+
+    let body = lines.collect::<Vec<_>>().join("\n");
+    let body = body.trim();
+    Ok(Some(SavedRequest {
+        name: name.unwrap_or_else(|| format!("{} {}", method, url)),
+        description: String::new(),
+        method: method.to_string(),
+        url,
+        headers,
+        params: Vec::new(),
+        disabled_headers: Vec::new(),
+        disabled_params: Vec::new(),
+        tests: String::new(),
+        body: (!body.is_empty()).then(|| body.to_string()),
+        form: None,
+        auth: None,
+        options: Default::default(),
+        panel: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_raw_request() {
+        let request = parse(
+            "POST /users?x=1 HTTP/1.1\r\nHost: api.example.com\r\nContent-Type: application/json\r\n\
+             Content-Length: 15\r\n\r\n{\"name\":\"Ada\"}\r\n",
+        )
+        .unwrap();
+        assert_eq!(request.method, Method::POST);
+        assert_eq!(request.url, "https://api.example.com/users?x=1");
+        assert_eq!(
+            request.headers,
+            vec![("Content-Type".to_string(), "application/json".to_string())]
+        );
+        assert_eq!(request.body.as_deref(), Some("{\"name\":\"Ada\"}"));
+    }
+
+    #[test]
+    fn test_parse_absolute_and_errors() {
+        let request = parse("GET http://localhost:8080/health HTTP/1.1\n").unwrap();
+        assert_eq!(request.url, "http://localhost:8080/health");
+        assert_eq!(request.body, None);
+
+        let request = parse("GET / HTTP/1.1\nHost: example.com:80\n\n").unwrap();
+        assert_eq!(request.url, "http://example.com:80/");
+
+        assert!(parse("GET /no-host HTTP/1.1\n\n").is_err());
+        assert!(parse("hello world").is_err());
+    }
+
+    #[test]
+    fn test_parse_http_file() {
+        let requests = parse_file(
+            "# Requests for the users service\n\
+             \n\
+             ### List users\n\
+             GET {{base_url}}/users?limit=10 HTTP/1.1\n\
+             Accept: application/json\n\
+             \n\
+             ###\n\
+             # @name create-user\n\
+             POST /users\n\
+             Host: api.example.com\n\
+             Content-Type: application/json\n\
+             \n\
+             {\"name\": \"Ada\"}\n\
+             \n\
+             ###\n\
+             https://example.com/health\n",
+        )
+        .unwrap();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].name, "List users");
+        assert_eq!(requests[0].url, "{{base_url}}/users?limit=10");
+        assert_eq!(
+            requests[0].headers,
+            vec![("Accept".to_string(), "application/json".to_string())]
+        );
+        assert_eq!(requests[0].body, None);
+
+        assert_eq!(requests[1].name, "create-user");
+        assert_eq!(requests[1].method, "POST");
+        assert_eq!(requests[1].url, "https://api.example.com/users");
+        assert_eq!(requests[1].body.as_deref(), Some("{\"name\": \"Ada\"}"));
+
+        assert_eq!(requests[2].name, "GET https://example.com/health");
+        assert_eq!(requests[2].method, "GET");
+
+        assert!(parse_file("").unwrap().is_empty());
+        assert!(parse_file("GET /users\nnot a header\n").is_err());
+        assert!(parse_file("FETCH a b\n").is_err());
+    }
+}
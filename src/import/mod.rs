@@ -0,0 +1,155 @@
+//! Importers that turn text from elsewhere into requests
+//!
+//! Pasted text is classified by [`detect`] so a curl command or a raw HTTP
+//! message can be imported as a whole instead of landing in the URL field.
+//! HAR files, Postman collections and OpenAPI documents (JSON or YAML) are
+//! imported into a collection with [`load_file`]; `.http` files are opened as collections of their own with
+//! [`load_http_file`] and re-read whenever they change.
+
+pub mod curl;
+pub mod har;
+pub mod http;
+pub mod openapi;
+pub mod postman;
+
+use crate::collections::{Collection, SavedRequest};
+use crate::error::{RestlessError, Result};
+use crate::logic::request::Request;
+use serde_json::Value;
+use std::path::Path;
+
+/// What a piece of pasted text looks like
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PasteKind {
+    /// A single `http(s)://` URL
+    Url,
+    /// A `curl ...` command line
+    Curl,
+    /// A raw HTTP request (`GET /path HTTP/1.1` plus headers)
+    RawHttp,
+    /// Anything else
+    Text,
+}
+
+impl PasteKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PasteKind::Url => "URL",
+            PasteKind::Curl => "curl command",
+            PasteKind::RawHttp => "raw HTTP request",
+            PasteKind::Text => "text",
+        }
+    }
+
+    /// Imports the text as a request, for the kinds that have an importer
+    pub fn import(&self, text: &str) -> Option<Result<Request>> {
+        match self {
+            PasteKind::Curl => Some(curl::parse(text)),
+            PasteKind::RawHttp => Some(http::parse(text)),
+            PasteKind::Url | PasteKind::Text => None,
+        }
+    }
+}
+
+/// Classifies pasted text
+pub fn detect(text: &str) -> PasteKind {
+    let trimmed = text.trim();
+    if trimmed.starts_with("curl ") || trimmed.starts_with("curl.exe ") {
+        PasteKind::Curl
+    } else if http::is_request_line(trimmed.lines().next().unwrap_or_default()) {
+        PasteKind::RawHttp
+    } else if (trimmed.starts_with("http://") || trimmed.starts_with("https://"))
+        && !trimmed.contains(char::is_whitespace)
+    {
+        PasteKind::Url
+    } else {
+        PasteKind::Text
+    }
+}
+
+/// Reads a HAR file, Postman collection or OpenAPI document, returning a
+/// name for the collection it makes and its requests
+pub fn load_file(path: &Path) -> Result<(String, Vec<SavedRequest>)> {
+    let content = std::fs::read_to_string(path)?;
+    let cannot_import = |e: &dyn std::fmt::Display| {
+        RestlessError::configuration(format!("Cannot import {}: {}", path.display(), e))
+    };
+    let value: Value = if path
+        .extension()
+        .is_some_and(|ext| ext == "yaml" || ext == "yml")
+    {
+        serde_yaml_ng::from_str(&content).map_err(|e| cannot_import(&e))?
+    } else {
+        serde_json::from_str(&content).map_err(|e| cannot_import(&e))?
+    };
+
+    if value.pointer("/log/entries").is_some() {
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Imported".to_string());
+        Ok((name, har::parse(&value)?))
+    } else if value.get("info").is_some() && value.get("item").is_some() {
+        postman::parse(&value)
+    } else if openapi::is_openapi(&value) {
+        openapi::parse(&value)
+    } else {
+        Err(RestlessError::configuration(format!(
+            "{} is not a HAR file, Postman collection or OpenAPI document",
+            path.display()
+        )))
+    }
+}
+
+/// Reads a `.http` file as a collection named after the file
+pub fn load_http_file(path: &Path) -> Result<Collection> {
+    let content = std::fs::read_to_string(path)?;
+    let requests = http::parse_file(&content).map_err(|e| {
+        RestlessError::configuration(format!("Cannot open {}: {}", path.display(), e))
+    })?;
+    Ok(Collection {
+        name: path.file_name().map_or_else(
+            || path.display().to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        ),
+        requests,
+        path: Some(path.to_path_buf()),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(detect("  https://example.com/a?b=1\n"), PasteKind::Url);
+        assert_eq!(detect("curl -X POST https://example.com"), PasteKind::Curl);
+        assert_eq!(
+            detect("GET /users HTTP/1.1\r\nHost: example.com\r\n\r\n"),
+            PasteKind::RawHttp
+        );
+        assert_eq!(detect("https://example.com and more"), PasteKind::Text);
+        assert_eq!(detect("hello"), PasteKind::Text);
+    }
+
+    #[test]
+    fn test_load_openapi_yaml() {
+        let path =
+            std::env::temp_dir().join(format!("restless-import-test-{}.yaml", std::process::id()));
+        std::fs::write(
+            &path,
+            "openapi: 3.0.0\ninfo:\n  title: Todos\npaths:\n  /todos:\n    get:\n      summary: List todos\n",
+        )
+        .unwrap();
+        let (name, requests) = load_file(&path).unwrap();
+        assert_eq!(name, "Todos");
+        assert_eq!(requests[0].name, "List todos");
+        assert_eq!(requests[0].url, "{{base_url}}/todos");
+
+        std::fs::write(&path, "title: nothing to import\n").unwrap();
+        assert!(load_file(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,562 @@
+//! OpenAPI 3 and Swagger 2 import
+//!
+//! Every operation under `paths` becomes a saved request named after its
+//! summary, else its operation id. Path templates like `/users/{id}` turn
+//! into `{{id}}` variables for an environment to fill in, and the URL starts
+//! at the first server, its variables set to their defaults; without an
+//! absolute server URL it starts at `{{base_url}}`. Required query
+//! parameters and headers are enabled, optional ones are kept disabled.
+//! Values and bodies come from the examples in the document, else from the
+//! schemas' defaults and types. Local `$ref`s are followed.
+
+use crate::collections::SavedRequest;
+use crate::error::{RestlessError, Result};
+use serde_json::{Map, Value};
+
+const METHODS: &[&str] = &[
+    "get", "post", "put", "patch", "delete", "head", "options", "trace",
+];
+
+/// Headers OpenAPI describes elsewhere, which parameters can't set
+const SKIPPED_HEADERS: &[&str] = &["accept", "content-type", "authorization"];
+
+/// How deep `$ref`s and schemas are followed, so cycles end
+const MAX_DEPTH: usize = 8;
+
+/// Whether a JSON or YAML document is an OpenAPI or Swagger description
+pub fn is_openapi(document: &Value) -> bool {
+    (document.get("openapi").is_some() || document.get("swagger").is_some())
+        && document.get("paths").is_some()
+}
+
+/// Parses an OpenAPI document into its title and requests
+pub fn parse(document: &Value) -> Result<(String, Vec<SavedRequest>)> {
+    let paths = document
+        .get("paths")
+        .and_then(Value::as_object)
+        .ok_or_else(|| RestlessError::configuration("OpenAPI document has no paths"))?;
+    let name = document
+        .pointer("/info/title")
+        .and_then(Value::as_str)
+        .unwrap_or("OpenAPI")
+        .to_string();
+    let api = Api {
+        document,
+        base_url: base_url(document),
+    };
+
+    let mut requests = Vec::new();
+    for (path, item) in paths {
+        let item = api.resolve(item);
+        for method in METHODS {
+            if let Some(operation) = item.get(*method) {
+                requests.push(api.request(method, path, item, operation));
+            }
+        }
+    }
+    Ok((name, requests))
+}
+
+struct Api<'a> {
+    document: &'a Value,
+    base_url: String,
+}
+
+impl<'a> Api<'a> {
+    /// Follows local `$ref`s to the value they point at
+    fn resolve(&self, mut value: &'a Value) -> &'a Value {
+        for _ in 0..MAX_DEPTH {
+            let Some(target) = value
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| reference.strip_prefix('#'))
+                .and_then(|pointer| self.document.pointer(pointer))
+            else {
+                break;
+            };
+            value = target;
+        }
+        value
+    }
+
+    fn request(
+        &self,
+        method: &str,
+        path: &str,
+        item: &'a Value,
+        operation: &'a Value,
+    ) -> SavedRequest {
+        let method = method.to_uppercase();
+        let name = text(operation, "summary")
+            .or_else(|| text(operation, "operationId"))
+            .map_or_else(|| format!("{} {}", method, path), str::to_string);
+        let description = text(operation, "description")
+            .unwrap_or_default()
+            .to_string();
+
+        // Operation parameters override the path's of the same name and place
+        let mut parameters: Vec<&Value> = Vec::new();
+        for parameter in [item, operation]
+            .into_iter()
+            .filter_map(|value| value.get("parameters").and_then(Value::as_array))
+            .flatten()
+            .map(|parameter| self.resolve(parameter))
+        {
+            parameters.retain(|known| {
+                text(known, "name") != text(parameter, "name")
+                    || text(known, "in") != text(parameter, "in")
+            });
+            parameters.push(parameter);
+        }
+
+        let mut request = SavedRequest {
+            name,
+            description,
+            method,
+            url: format!("{}{}", self.base_url, template(path)),
+            headers: Vec::new(),
+            params: Vec::new(),
+            disabled_headers: Vec::new(),
+            disabled_params: Vec::new(),
+            tests: String::new(),
+            body: None,
+            form: None,
+            auth: None,
+            options: Default::default(),
+            panel: None,
+        };
+        for parameter in &parameters {
+            let Some(name) = text(parameter, "name") else {
+                continue;
+            };
+            let pair = (name.to_string(), self.parameter_value(parameter));
+            let required = parameter.get("required").and_then(Value::as_bool) == Some(true);
+            match text(parameter, "in") {
+                Some("query") if required => request.params.push(pair),
+                Some("query") => request.disabled_params.push(pair),
+                Some("header") if SKIPPED_HEADERS.contains(&name.to_lowercase().as_str()) => {}
+                Some("header") if required => request.headers.push(pair),
+                Some("header") => request.disabled_headers.push(pair),
+                _ => {}
+            }
+        }
+
+        match operation.get("requestBody").map(|body| self.resolve(body)) {
+            Some(body) => self.add_body(&mut request, body.get("content")),
+            None => self.add_swagger_body(&mut request, operation, &parameters),
+        }
+        request
+    }
+
+    /// Sets the body from an OpenAPI 3 `content` map
+    fn add_body(&self, request: &mut SavedRequest, content: Option<&'a Value>) {
+        let Some(content) = content.and_then(Value::as_object) else {
+            return;
+        };
+        let Some((media_type, media)) = preferred(content) else {
+            return;
+        };
+        let schema = media.get("schema").map(|schema| self.resolve(schema));
+        let example = media
+            .get("example")
+            .cloned()
+            .or_else(|| {
+                media
+                    .get("examples")
+                    .and_then(Value::as_object)
+                    .and_then(|examples| examples.values().next())
+                    .and_then(|example| self.resolve(example).get("value").cloned())
+            })
+            .or_else(|| schema.map(|schema| self.example(schema, 0)));
+        let example = example.unwrap_or(Value::Null);
+
+        if media_type.starts_with("multipart/form-data") {
+            let properties = schema.map(|schema| self.properties(schema, 0));
+            request.form = Some(
+                properties
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(name, schema)| {
+                        let value = if text(schema, "format") == Some("binary") {
+                            "@".to_string()
+                        } else {
+                            example.get(&name).map(plain).unwrap_or_default()
+                        };
+                        (name, value)
+                    })
+                    .collect(),
+            );
+            return;
+        }
+
+        let body = if media_type == "application/x-www-form-urlencoded" {
+            example
+                .as_object()
+                .map(|fields| {
+                    fields
+                        .iter()
+                        .map(|(key, value)| format!("{}={}", key, plain(value)))
+                        .collect::<Vec<_>>()
+                        .join("&")
+                })
+                .unwrap_or_default()
+        } else if media_type.contains("json") {
+            serde_json::to_string_pretty(&example).unwrap_or_default()
+        } else {
+            plain(&example)
+        };
+        if !body.is_empty() {
+            request
+                .headers
+                .push(("Content-Type".to_string(), media_type.to_string()));
+            request.body = Some(body);
+        }
+    }
+
+    /// Sets the body from Swagger 2 `body` or `formData` parameters
+    fn add_swagger_body(
+        &self,
+        request: &mut SavedRequest,
+        operation: &'a Value,
+        parameters: &[&'a Value],
+    ) {
+        if let Some(schema) = parameters
+            .iter()
+            .find(|parameter| text(parameter, "in") == Some("body"))
+            .and_then(|parameter| parameter.get("schema"))
+        {
+            let example = self.example(self.resolve(schema), 0);
+            request
+                .headers
+                .push(("Content-Type".to_string(), "application/json".to_string()));
+            request.body = Some(serde_json::to_string_pretty(&example).unwrap_or_default());
+            return;
+        }
+
+        let fields: Vec<(String, String)> = parameters
+            .iter()
+            .filter(|parameter| text(parameter, "in") == Some("formData"))
+            .filter_map(|parameter| {
+                let value = if text(parameter, "type") == Some("file") {
+                    "@".to_string()
+                } else {
+                    self.parameter_value(parameter)
+                };
+                Some((text(parameter, "name")?.to_string(), value))
+            })
+            .collect();
+        if fields.is_empty() {
+            return;
+        }
+        let multipart = operation
+            .get("consumes")
+            .and_then(Value::as_array)
+            .is_some_and(|types| {
+                types
+                    .iter()
+                    .any(|kind| kind.as_str() == Some("multipart/form-data"))
+            })
+            || fields.iter().any(|(_, value)| value == "@");
+        if multipart {
+            request.form = Some(fields);
+        } else {
+            request.headers.push((
+                "Content-Type".to_string(),
+                "application/x-www-form-urlencoded".to_string(),
+            ));
+            request.body = Some(
+                fields
+                    .iter()
+                    .map(|(key, value)| format!("{}={}", key, value))
+                    .collect::<Vec<_>>()
+                    .join("&"),
+            );
+        }
+    }
+
+    /// A parameter's example, else its schema's, as text; empty without one
+    fn parameter_value(&self, parameter: &'a Value) -> String {
+        // Swagger 2 puts the schema on the parameter itself
+        let schema = parameter
+            .get("schema")
+            .map_or(parameter, |schema| self.resolve(schema));
+        parameter
+            .get("example")
+            .or_else(|| given(schema))
+            .map(plain)
+            .unwrap_or_default()
+    }
+
+    /// An example value for a schema
+    fn example(&self, schema: &'a Value, depth: usize) -> Value {
+        let schema = self.resolve(schema);
+        if let Some(given) = given(schema) {
+            return given.clone();
+        }
+        if depth >= MAX_DEPTH {
+            return Value::Null;
+        }
+        if let Some(first) = ["oneOf", "anyOf"]
+            .iter()
+            .find_map(|key| schema.get(*key).and_then(Value::as_array)?.first())
+        {
+            return self.example(first, depth + 1);
+        }
+
+        let kind = text(schema, "type").or_else(|| {
+            (schema.get("properties").is_some() || schema.get("allOf").is_some())
+                .then_some("object")
+        });
+        match kind {
+            Some("object") => Value::Object(
+                self.properties(schema, depth)
+                    .into_iter()
+                    .map(|(name, property)| (name, self.example(property, depth + 1)))
+                    .collect::<Map<_, _>>(),
+            ),
+            Some("array") => Value::Array(
+                schema
+                    .get("items")
+                    .map(|items| vec![self.example(items, depth + 1)])
+                    .unwrap_or_default(),
+            ),
+            Some("integer") | Some("number") => Value::from(0),
+            Some("boolean") => Value::Bool(false),
+            Some("string") => Value::String(String::new()),
+            _ => Value::Null,
+        }
+    }
+
+    /// An object schema's properties, including those of its `allOf` parts
+    fn properties(&self, schema: &'a Value, depth: usize) -> Vec<(String, &'a Value)> {
+        let schema = self.resolve(schema);
+        let mut properties: Vec<(String, &Value)> = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .map(|properties| {
+                properties
+                    .iter()
+                    .map(|(name, property)| (name.clone(), property))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if depth < MAX_DEPTH {
+            for part in schema
+                .get("allOf")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                properties.extend(self.properties(part, depth + 1));
+            }
+        }
+        properties
+    }
+}
+
+/// Where every request's URL starts
+fn base_url(document: &Value) -> String {
+    let url = if let Some(server) = document
+        .get("servers")
+        .and_then(Value::as_array)
+        .and_then(|servers| servers.first())
+    {
+        let mut url = text(server, "url").unwrap_or_default().to_string();
+        if let Some(variables) = server.get("variables").and_then(Value::as_object) {
+            for (name, variable) in variables {
+                let default = text(variable, "default").unwrap_or_default();
+                url = url.replace(&format!("{{{}}}", name), default);
+            }
+        }
+        url
+    } else if let Some(host) = text(document, "host") {
+        let scheme = document
+            .get("schemes")
+            .and_then(Value::as_array)
+            .and_then(|schemes| schemes.first())
+            .and_then(Value::as_str)
+            .unwrap_or("https");
+        format!(
+            "{}://{}{}",
+            scheme,
+            host,
+            text(document, "basePath").unwrap_or_default()
+        )
+    } else {
+        text(document, "basePath").unwrap_or_default().to_string()
+    };
+
+    let url = url.trim_end_matches('/');
+    if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("{{{{base_url}}}}{}", url)
+    }
+}
+
+/// `/users/{id}` as `/users/{{id}}`
+fn template(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix('{') {
+            Some(name) if name.ends_with('}') && !name.starts_with('{') => {
+                format!("{{{{{}}}", name)
+            }
+            _ => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// The media type to send: JSON if there is one, else the first
+fn preferred(content: &Map<String, Value>) -> Option<(&str, &Value)> {
+    content
+        .iter()
+        .find(|(media_type, _)| media_type.as_str() == "application/json")
+        .or_else(|| {
+            content
+                .iter()
+                .find(|(media_type, _)| media_type.ends_with("+json"))
+        })
+        .or_else(|| content.iter().next())
+        .map(|(media_type, media)| (media_type.as_str(), media))
+}
+
+/// The example, default or first allowed value a schema gives
+fn given(schema: &Value) -> Option<&Value> {
+    schema
+        .get("example")
+        .or_else(|| schema.get("default"))
+        .or_else(|| schema.get("enum")?.as_array()?.first())
+}
+
+fn text<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+/// A value as it's typed into a field: strings without quotes, nothing for null
+fn plain(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_openapi() {
+        let document: Value = serde_json::from_str(
+            r##"{"openapi": "3.0.3", "info": {"title": "Pets"},
+                "servers": [{"url": "https://{region}.pets.test/v1",
+                             "variables": {"region": {"default": "eu"}}}],
+                "paths": {
+                    "/pets/{petId}": {
+                        "parameters": [{"$ref": "#/components/parameters/PetId"}],
+                        "get": {"summary": "Get a pet", "description": "One pet",
+                            "parameters": [
+                                {"name": "fields", "in": "query", "example": "name,tag"},
+                                {"name": "X-Trace", "in": "header", "required": true,
+                                 "schema": {"type": "string", "default": "on"}},
+                                {"name": "Accept", "in": "header"}]},
+                        "put": {"operationId": "updatePet",
+                            "requestBody": {"$ref": "#/components/requestBodies/Pet"}}
+                    },
+                    "/pets": {
+                        "get": {"parameters": [
+                            {"name": "limit", "in": "query", "required": true,
+                             "schema": {"type": "integer", "example": 20}}]},
+                        "post": {"summary": "Add a photo", "requestBody": {"content": {
+                            "multipart/form-data": {"schema": {"properties": {
+                                "photo": {"type": "string", "format": "binary"},
+                                "caption": {"type": "string"}}}}}}}
+                    }
+                },
+                "components": {
+                    "parameters": {"PetId": {"name": "petId", "in": "path", "required": true}},
+                    "requestBodies": {"Pet": {"content": {
+                        "application/xml": {},
+                        "application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}}},
+                    "schemas": {"Pet": {"allOf": [
+                        {"properties": {"name": {"type": "string", "example": "Rex"}}},
+                        {"properties": {"tags": {"type": "array", "items": {"type": "string"}},
+                                        "age": {"type": "integer"}}}]}}
+                }}"##,
+        )
+        .unwrap();
+        assert!(is_openapi(&document));
+
+        let (name, requests) = parse(&document).unwrap();
+        assert_eq!(name, "Pets");
+        assert_eq!(requests.len(), 4);
+
+        // Paths come in order, then methods
+        let list = &requests[0];
+        assert_eq!(list.name, "GET /pets");
+        assert_eq!(list.url, "https://eu.pets.test/v1/pets");
+        assert_eq!(list.params, vec![("limit".to_string(), "20".to_string())]);
+
+        let photo = &requests[1];
+        assert_eq!(photo.method, "POST");
+        assert_eq!(
+            photo.form,
+            Some(vec![
+                ("caption".to_string(), String::new()),
+                ("photo".to_string(), "@".to_string()),
+            ])
+        );
+        assert!(photo.headers.is_empty());
+
+        let get = &requests[2];
+        assert_eq!(get.name, "Get a pet");
+        assert_eq!(get.description, "One pet");
+        assert_eq!(get.url, "https://eu.pets.test/v1/pets/{{petId}}");
+        assert!(get.params.is_empty());
+        assert_eq!(
+            get.disabled_params,
+            vec![("fields".to_string(), "name,tag".to_string())]
+        );
+        assert_eq!(get.headers, vec![("X-Trace".to_string(), "on".to_string())]);
+        assert!(get.disabled_headers.is_empty());
+
+        let put = &requests[3];
+        assert_eq!(put.name, "updatePet");
+        assert_eq!(
+            put.headers,
+            vec![("Content-Type".to_string(), "application/json".to_string())]
+        );
+        let body: Value = serde_json::from_str(put.body.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"name": "Rex", "tags": [""], "age": 0})
+        );
+
+        assert!(parse(&serde_json::json!({"openapi": "3.1.0"})).is_err());
+    }
+
+    #[test]
+    fn test_parse_swagger() {
+        let document: Value = serde_json::from_str(
+            r#"{"swagger": "2.0", "info": {"title": "Store"}, "basePath": "/api/",
+                "paths": {
+                    "/orders": {"post": {"parameters": [{"name": "order", "in": "body",
+                        "schema": {"type": "object", "properties": {"id": {"type": "integer"}}}}]}},
+                    "/login": {"post": {"parameters": [
+                        {"name": "user", "in": "formData", "type": "string", "default": "ada"}]}}
+                }}"#,
+        )
+        .unwrap();
+        let (name, requests) = parse(&document).unwrap();
+        assert_eq!(name, "Store");
+
+        let login = &requests[0];
+        assert_eq!(login.url, "{{base_url}}/api/login");
+        assert_eq!(login.body.as_deref(), Some("user=ada"));
+
+        let order = &requests[1];
+        assert_eq!(order.url, "{{base_url}}/api/orders");
+        assert_eq!(order.body.as_deref(), Some("{\n  \"id\": 0\n}"));
+    }
+}
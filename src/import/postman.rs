@@ -0,0 +1,240 @@
+//! Postman collection (v2.0 and v2.1) import
+//!
+//! Folders are flattened, keeping their requests in order. Postman's
+//! `{{variable}}` syntax is the same as restless environments use, so
+//! variables carry over as they are.
+
+use crate::collections::SavedRequest;
+use crate::error::{RestlessError, Result};
+use serde_json::Value;
+
+/// Parses a Postman collection into its name and requests
+pub fn parse(collection: &Value) -> Result<(String, Vec<SavedRequest>)> {
+    let items = collection
+        .get("item")
+        .and_then(Value::as_array)
+        .ok_or_else(|| RestlessError::configuration("Postman collection has no items"))?;
+    let name = collection
+        .pointer("/info/name")
+        .and_then(Value::as_str)
+        .unwrap_or("Postman")
+        .to_string();
+
+    let mut requests = Vec::new();
+    collect(items, &mut requests);
+    Ok((name, requests))
+}
+
+fn collect(items: &[Value], requests: &mut Vec<SavedRequest>) {
+    for item in items {
+        if let Some(children) = item.get("item").and_then(Value::as_array) {
+            collect(children, requests);
+        } else if let Some(request) = item.get("request") {
+            let name = text(item, "name").unwrap_or_default();
+            requests.push(saved_request(name, request));
+        }
+    }
+}
+
+fn saved_request(name: &str, request: &Value) -> SavedRequest {
+    // A request can be just its URL
+    let (method, url) = match request {
+        Value::String(url) => ("GET".to_string(), url.clone()),
+        _ => (
+            text(request, "method").unwrap_or("GET").to_uppercase(),
+            request.get("url").map(raw_url).unwrap_or_default(),
+        ),
+    };
+    let (url, query) = match url.split_once('?') {
+        Some((url, query)) => (url.to_string(), query),
+        None => (url, ""),
+    };
+    let params = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key.to_string(), value.to_string())
+        })
+        .collect();
+
+    let mut headers = key_values(request.get("header"));
+    let (body, form) = match request.get("body") {
+        Some(body) => match text(body, "mode") {
+            Some("raw") => (text(body, "raw").map(str::to_string), None),
+            Some("formdata") => (None, Some(form_fields(body.get("formdata")))),
+            Some("urlencoded") => {
+                let pairs = key_values(body.get("urlencoded"));
+                if !headers
+                    .iter()
+                    .any(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+                {
+                    headers.push((
+                        "Content-Type".to_string(),
+                        "application/x-www-form-urlencoded".to_string(),
+                    ));
+                }
+                let encoded = pairs
+                    .iter()
+                    .map(|(key, value)| format!("{}={}", key, value))
+                    .collect::<Vec<_>>()
+                    .join("&");
+                (Some(encoded), None)
+            }
+            _ => (None, None),
+        },
+        None => (None, None),
+    };
+
+    let description = match request.get("description") {
+        Some(Value::String(description)) => description.clone(),
+        Some(description) => text(description, "content").unwrap_or_default().to_string(),
+        None => String::new(),
+    };
+
+    SavedRequest {
+        name: name.to_string(),
+        description,
+        method,
+        url,
+        headers,
+        params,
+        disabled_headers: disabled_key_values(request.get("header")),
+        disabled_params: Vec::new(),
+        tests: String::new(),
+        body: body.filter(|body| !body.is_empty()),
+        form,
+        auth: None,
+        options: Default::default(),
+        panel: None,
+    }
+}
+
+/// The URL as typed in Postman, from either the string or the object form
+fn raw_url(url: &Value) -> String {
+    match url {
+        Value::String(url) => url.clone(),
+        _ => text(url, "raw").unwrap_or_default().to_string(),
+    }
+}
+
+fn text<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+/// Enabled `key`/`value` pairs; Postman keeps disabled ones around
+fn key_values(list: Option<&Value>) -> Vec<(String, String)> {
+    pairs(enabled(list))
+}
+
+/// The `key`/`value` pairs Postman keeps around disabled
+fn disabled_key_values(list: Option<&Value>) -> Vec<(String, String)> {
+    pairs(
+        list.and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter(|item| item.get("disabled").and_then(Value::as_bool) == Some(true)),
+    )
+}
+
+fn pairs<'a>(items: impl Iterator<Item = &'a Value>) -> Vec<(String, String)> {
+    items
+        .filter_map(|item| {
+            Some((
+                text(item, "key")?.to_string(),
+                text(item, "value").unwrap_or_default().to_string(),
+            ))
+        })
+        .collect()
+}
+
+/// Form fields, with file fields written as `@path` like the form editor
+fn form_fields(list: Option<&Value>) -> Vec<(String, String)> {
+    enabled(list)
+        .filter_map(|item| {
+            let key = text(item, "key")?.to_string();
+            let value = if text(item, "type") == Some("file") {
+                format!("@{}", text(item, "src").unwrap_or_default())
+            } else {
+                text(item, "value").unwrap_or_default().to_string()
+            };
+            Some((key, value))
+        })
+        .collect()
+}
+
+fn enabled(list: Option<&Value>) -> impl Iterator<Item = &Value> {
+    list.and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|item| item.get("disabled").and_then(Value::as_bool) != Some(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_postman() {
+        let collection: Value = serde_json::from_str(
+            r#"{"info": {"name": "Shop", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"},
+                "item": [
+                    {"name": "Orders", "item": [
+                        {"name": "Get order", "request": {"method": "GET",
+                            "url": {"raw": "{{base_url}}/orders/:id?expand=items"},
+                            "header": [{"key": "Accept", "value": "application/json"},
+                                       {"key": "X-Debug", "value": "1", "disabled": true}],
+                            "description": "Fetches one order"}}
+                    ]},
+                    {"name": "Upload", "request": {"method": "post", "url": "{{base_url}}/files",
+                        "body": {"mode": "formdata", "formdata": [
+                            {"key": "file", "type": "file", "src": "/tmp/a.png"},
+                            {"key": "note", "value": "hi", "type": "text"}]}}},
+                    {"name": "Login", "request": {"method": "POST", "url": "{{base_url}}/login",
+                        "body": {"mode": "urlencoded", "urlencoded": [
+                            {"key": "user", "value": "ada"}]}}},
+                    {"name": "Ping", "request": "https://example.com/ping"}
+                ]}"#,
+        )
+        .unwrap();
+
+        let (name, requests) = parse(&collection).unwrap();
+        assert_eq!(name, "Shop");
+        assert_eq!(requests.len(), 4);
+
+        let order = &requests[0];
+        assert_eq!(order.name, "Get order");
+        assert_eq!(order.url, "{{base_url}}/orders/:id");
+        assert_eq!(
+            order.params,
+            vec![("expand".to_string(), "items".to_string())]
+        );
+        assert_eq!(order.headers.len(), 1);
+        assert_eq!(
+            order.disabled_headers,
+            vec![("X-Debug".to_string(), "1".to_string())]
+        );
+        assert_eq!(order.description, "Fetches one order");
+
+        assert_eq!(requests[1].method, "POST");
+        assert_eq!(
+            requests[1].form,
+            Some(vec![
+                ("file".to_string(), "@/tmp/a.png".to_string()),
+                ("note".to_string(), "hi".to_string()),
+            ])
+        );
+
+        assert_eq!(requests[2].body.as_deref(), Some("user=ada"));
+        assert_eq!(
+            requests[2].headers[0],
+            (
+                "Content-Type".to_string(),
+                "application/x-www-form-urlencoded".to_string()
+            )
+        );
+
+        assert_eq!(requests[3].method, "GET");
+        assert_eq!(requests[3].url, "https://example.com/ping");
+    }
+}
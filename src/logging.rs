@@ -0,0 +1,146 @@
+//! Tracing logs written to a file (`--log-file` or `[log] file`)
+//!
+//! The interface owns the terminal, so nothing can go to stderr while it
+//! runs. The levels build on each other:
+//!
+//! | Level | Logged |
+//! |-------|--------|
+//! | `error`, `warn` | Failed handlers and sends |
+//! | `info` | Every send with its status and time, and the messages shown in popups |
+//! | `debug` | Connection details from the HTTP client |
+//! | `trace` | Every key press, typed text included, so the file may hold secrets |
+
+use crate::error::{RestlessError, Result};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tracing::{Level, Subscriber};
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Level names accepted in the config file
+pub const LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+/// How much of the end of the file [`tail`] reads
+const TAIL_BYTES: u64 = 64 * 1024;
+
+/// The file this run logs to, once logging is set up
+static FILE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Parses a level name from the config file
+pub fn parse_level(level: &str) -> Result<Level> {
+    level.parse().map_err(|_| {
+        RestlessError::configuration(format!(
+            "Unknown log level '{}' (expected one of: {})",
+            level,
+            LEVELS.join(", ")
+        ))
+    })
+}
+
+/// Appends the logs of this run at `level` and above to `path`
+pub fn init(path: &Path, level: Level) -> Result<()> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| {
+            RestlessError::configuration(format!("Cannot open log file {}: {}", path.display(), e))
+        })?;
+    // Also takes in the `log` records of the HTTP client
+    subscriber(file, level)
+        .try_init()
+        .map_err(|e| RestlessError::app_state(format!("Logging is already set up: {}", e)))?;
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), "restless started");
+    let _ = FILE.set(path.to_path_buf());
+    Ok(())
+}
+
+/// The file this run logs to, if logging is on
+pub fn file() -> Option<&'static Path> {
+    FILE.get().map(PathBuf::as_path)
+}
+
+/// The last `count` lines of a log file
+///
+/// `trace` lines are left out: they hold every key press, typed secrets
+/// included.
+pub fn tail(path: &Path, count: usize) -> Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let start = file.metadata()?.len().saturating_sub(TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let text = String::from_utf8_lossy(&bytes);
+
+    let mut lines: Vec<&str> = text.lines().collect();
+    // Reading from the middle of the file starts within a line
+    if start > 0 && !lines.is_empty() {
+        lines.remove(0);
+    }
+    lines.retain(|line| !line.contains(" TRACE "));
+    let skip = lines.len().saturating_sub(count);
+    Ok(lines[skip..].iter().map(|line| line.to_string()).collect())
+}
+
+/// Plain text lines, without colors, written to `file`
+fn subscriber(file: File, level: Level) -> impl Subscriber + Send + Sync {
+    tracing_subscriber::fmt()
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .with_max_level(level)
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!(parse_level("debug").unwrap(), Level::DEBUG);
+        assert_eq!(parse_level("WARN").unwrap(), Level::WARN);
+        let error = parse_level("verbose").unwrap_err();
+        assert!(error.to_string().contains("expected one of: error, warn"));
+    }
+
+    #[test]
+    fn test_subscriber_writes_file() {
+        let path = std::env::temp_dir().join(format!("restless-log-{}.log", std::process::id()));
+        let file = File::create(&path).unwrap();
+        tracing::subscriber::with_default(subscriber(file, Level::INFO), || {
+            tracing::info!(status = 201, "response received");
+            tracing::debug!("left out below the level");
+        });
+        let log = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(log.contains(" INFO "));
+        assert!(log.contains("response received status=201"));
+        assert!(!log.contains("left out"));
+        assert!(!log.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_tail() {
+        let path = std::env::temp_dir().join(format!("restless-tail-{}.log", std::process::id()));
+        let mut log = "x".repeat(TAIL_BYTES as usize);
+        for n in 0..5 {
+            log.push_str(&format!(
+                "\n2024-05-01T12:00:0{}Z  INFO restless: send {}",
+                n, n
+            ));
+        }
+        log.push_str("\n2024-05-01T12:00:06Z TRACE restless::handlers: key code=Char('p')\n");
+        std::fs::write(&path, log).unwrap();
+
+        let lines = tail(&path, 3).unwrap();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with("send 2"));
+        assert!(lines[2].ends_with("send 4"));
+        // The cut-off first line is dropped, not shown in part
+        assert_eq!(tail(&path, 100).unwrap().len(), 5);
+        std::fs::remove_file(&path).unwrap();
+        assert!(tail(&path, 3).is_err());
+    }
+}
@@ -0,0 +1,361 @@
+//! Assertions checked against every response (the Tests tab)
+//!
+//! A request's tests are one assertion per line; blank lines and lines
+//! starting with `#` are skipped:
+//!
+//! | Assertion | Passes when |
+//! |-----------|-------------|
+//! | `status == 200` | The status code is 200 |
+//! | `time < 500` | The response took less than 500 ms |
+//! | `header Content-Type contains json` | The header's value contains `json` |
+//! | `json.body.id exists` | The JSON body has a value at `$.id` |
+//! | `json.body.items[0].name == "Ann"` | The value there is `Ann` |
+//! | `body contains ok` | The body contains `ok` |
+//!
+//! The comparisons are `==`, `!=`, `<`, `<=`, `>`, `>=` and `contains`, or
+//! `exists` on its own. Numbers compare as numbers; quotes around a value
+//! are dropped.
+//!
+//! A `set <name> = <subject>` line is no assertion: it keeps the subject's
+//! value from the response as `{{name}}` for the requests sent after it, e.g.
+//! `set token = json.body.access_token` after logging in.
+
+use crate::error::{RestlessError, Result};
+use crate::logic::jsonpath;
+use crate::logic::response::Response;
+use serde_json::Value;
+
+/// How one assertion went
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestResult {
+    /// The assertion as written
+    pub assertion: String,
+    pub passed: bool,
+    /// What was found instead, for a failure
+    pub detail: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Subject {
+    Status,
+    /// Milliseconds the response took
+    Time,
+    Header(String),
+    Body,
+    /// A JSONPath into the body
+    Json(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Contains,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Check {
+    Exists,
+    Compare(Operator, String),
+}
+
+/// Checks every line of `tests` without running it, naming the first one
+/// that can't be read
+pub fn validate(tests: &str) -> Result<()> {
+    for (number, line) in lines(tests) {
+        let outcome = match set_line(line) {
+            Some(set) => set.map(|_| ()),
+            None => parse(line).map(|_| ()),
+        };
+        outcome.map_err(|reason| {
+            RestlessError::configuration(format!("Line {}: {}", number, reason))
+        })?;
+    }
+    Ok(())
+}
+
+/// Runs the assertions of `tests` against `response`; one that can't be
+/// read fails with the reason
+pub fn run(tests: &str, response: &Response) -> Vec<TestResult> {
+    lines(tests)
+        .filter(|(_, line)| set_line(line).is_none())
+        .map(|(_, line)| {
+            let outcome = parse(line).and_then(|(subject, check)| {
+                let actual = actual(&subject, response);
+                evaluate(&check, actual)
+            });
+            TestResult {
+                assertion: line.to_string(),
+                passed: outcome.is_ok(),
+                detail: outcome.err(),
+            }
+        })
+        .collect()
+}
+
+/// The variables the `set` lines of `tests` take from `response`, and why
+/// each one that found no value was left out
+pub fn extract(tests: &str, response: &Response) -> (Vec<(String, String)>, Vec<String>) {
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+    for (_, line) in lines(tests) {
+        let Some(set) = set_line(line) else {
+            continue;
+        };
+        match set.and_then(|(name, subject)| Ok((name, actual(&subject, response)?))) {
+            Ok((name, value)) => values.push((name, value)),
+            Err(reason) => errors.push(format!("{}: {}", line, reason)),
+        }
+    }
+    (values, errors)
+}
+
+/// How many of `results` passed, e.g. `3/4 tests passed`
+pub fn summary(results: &[TestResult]) -> String {
+    let passed = results.iter().filter(|result| result.passed).count();
+    format!("{}/{} tests passed", passed, results.len())
+}
+
+/// The assertions with their line number from 1
+fn lines(tests: &str) -> impl Iterator<Item = (usize, &str)> {
+    tests
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// The first word of `text` and what follows it
+fn next_word(text: &str) -> (&str, &str) {
+    match text.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim_start()),
+        None => (text, ""),
+    }
+}
+
+/// The variable name and subject of a `set` line, or `None` for an assertion
+fn set_line(line: &str) -> Option<std::result::Result<(String, Subject), String>> {
+    let (word, rest) = next_word(line);
+    if word != "set" {
+        return None;
+    }
+    let (name, rest) = next_word(rest);
+    let (equals, rest) = next_word(rest);
+    Some(if name.is_empty() || equals != "=" {
+        Err("expected set <name> = <value>".to_string())
+    } else if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        Err(format!("{} is not a variable name", name))
+    } else {
+        parse_subject(rest).and_then(|(subject, rest)| match rest {
+            "" => Ok((name.to_string(), subject)),
+            _ => Err(format!("nothing may follow the value, found {}", rest)),
+        })
+    })
+}
+
+fn parse(line: &str) -> std::result::Result<(Subject, Check), String> {
+    let (subject, rest) = parse_subject(line)?;
+    let (word, value) = next_word(rest);
+    let operator = match word {
+        "exists" if value.is_empty() => return Ok((subject, Check::Exists)),
+        "exists" => return Err("nothing may follow exists".to_string()),
+        "==" => Operator::Equal,
+        "!=" => Operator::NotEqual,
+        "<" => Operator::Less,
+        "<=" => Operator::LessOrEqual,
+        ">" => Operator::Greater,
+        ">=" => Operator::GreaterOrEqual,
+        "contains" => Operator::Contains,
+        "" => return Err("expected a comparison or exists".to_string()),
+        _ => return Err(format!("unknown comparison {}", word)),
+    };
+    if value.is_empty() {
+        return Err(format!("expected a value after {}", word));
+    }
+    Ok((
+        subject,
+        Check::Compare(operator, unquote(value).to_string()),
+    ))
+}
+
+/// The subject `text` starts with and what follows it
+fn parse_subject(text: &str) -> std::result::Result<(Subject, &str), String> {
+    let (word, rest) = next_word(text);
+    Ok(match word {
+        "status" => (Subject::Status, rest),
+        "time" => (Subject::Time, rest),
+        "body" => (Subject::Body, rest),
+        "header" => {
+            let (name, rest) = next_word(rest);
+            if name.is_empty() {
+                return Err("header needs a name".to_string());
+            }
+            (Subject::Header(name.to_string()), rest)
+        }
+        _ => match word.strip_prefix("json.body") {
+            Some(path) => {
+                let path = format!("${}", path);
+                jsonpath::validate(&path).map_err(|e| e.to_string())?;
+                (Subject::Json(path), rest)
+            }
+            None => {
+                return Err(format!(
+                    "{} is not status, time, header, body or json.body",
+                    word
+                ))
+            }
+        },
+    })
+}
+
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .iter()
+        .find_map(|&quote| value.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(value)
+}
+
+/// The value `subject` has in `response`, or why there is none
+fn actual(subject: &Subject, response: &Response) -> std::result::Result<String, String> {
+    match subject {
+        Subject::Status => Ok(response.status_code.to_string()),
+        Subject::Time => response
+            .metrics
+            .as_ref()
+            .map(|metrics| metrics.elapsed.as_millis().to_string())
+            .ok_or_else(|| "no timing for this response".to_string()),
+        Subject::Header(name) => response
+            .headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.clone())
+            .ok_or_else(|| format!("no {} header", name)),
+        Subject::Body => Ok(response.body.clone()),
+        Subject::Json(path) => {
+            let found = jsonpath::filter(&response.body, path).map_err(|e| e.to_string())?;
+            Ok(match serde_json::from_str(&found) {
+                Ok(Value::String(text)) => text,
+                Ok(value) => value.to_string(),
+                Err(_) => found,
+            })
+        }
+    }
+}
+
+fn evaluate(
+    check: &Check,
+    actual: std::result::Result<String, String>,
+) -> std::result::Result<(), String> {
+    let actual = actual?;
+    let Check::Compare(operator, expected) = check else {
+        return Ok(());
+    };
+    let numbers = actual.parse::<f64>().ok().zip(expected.parse::<f64>().ok());
+    let passed = match operator {
+        Operator::Contains => actual.contains(expected.as_str()),
+        Operator::Equal => numbers.map_or(actual == *expected, |(a, b)| a == b),
+        Operator::NotEqual => numbers.map_or(actual != *expected, |(a, b)| a != b),
+        _ => {
+            let (a, b) = numbers.ok_or_else(|| format!("{} is not a number", actual))?;
+            match operator {
+                Operator::Less => a < b,
+                Operator::LessOrEqual => a <= b,
+                Operator::Greater => a > b,
+                _ => a >= b,
+            }
+        }
+    };
+    if passed {
+        Ok(())
+    } else {
+        Err(format!("got {}", actual))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response() -> Response {
+        Response::new(
+            201,
+            "Content-Type: application/json; charset=utf-8".to_string(),
+            r#"{"id": 7, "name": "Ann", "tags": ["a", "b"]}"#.to_string(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_run() {
+        let tests = "# created\nstatus == 201\nstatus < 300\n\nheader content-type contains json\n\
+                     json.body.id exists\njson.body.name == \"Ann\"\njson.body.tags[1] != a\n\
+                     body contains Ann";
+        let results = run(tests, &response());
+        assert_eq!(results.len(), 7);
+        assert!(results.iter().all(|result| result.passed), "{:?}", results);
+        assert_eq!(summary(&results), "7/7 tests passed");
+    }
+
+    #[test]
+    fn test_failures() {
+        let results = run(
+            "status == 200\njson.body.email exists\nheader ETag exists\njson.body.name > 3\n\
+             time < 500\nstatus is 201",
+            &response(),
+        );
+        assert!(results.iter().all(|result| !result.passed));
+        assert_eq!(results[0].detail.as_deref(), Some("got 201"));
+        assert_eq!(results[2].detail.as_deref(), Some("no ETag header"));
+        assert_eq!(results[3].detail.as_deref(), Some("Ann is not a number"));
+        assert_eq!(
+            results[4].detail.as_deref(),
+            Some("no timing for this response")
+        );
+        assert_eq!(results[5].detail.as_deref(), Some("unknown comparison is"));
+        assert_eq!(summary(&results), "0/6 tests passed");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("status == 200\n# note\n\njson.body.items[0] exists").is_ok());
+        let error = validate("status == 200\nstatus ==").unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Line 2: expected a value after =="));
+        assert!(validate("cookie x exists").is_err());
+        assert!(validate("json.body..[ exists").is_err());
+        assert!(validate("set token = json.body.token\nset id = header X-Id").is_ok());
+        assert!(validate("set token json.body.token").is_err());
+        assert!(validate("set a.b = status").is_err());
+        assert!(validate("set id = status == 200").is_err());
+    }
+
+    #[test]
+    fn test_extract() {
+        let tests = "status == 201\nset user_id = json.body.id\nset name = json.body.name\n\
+                     set type = header content-type\nset email = json.body.email";
+        let (values, errors) = extract(tests, &response());
+        assert_eq!(
+            values,
+            vec![
+                ("user_id".to_string(), "7".to_string()),
+                ("name".to_string(), "Ann".to_string()),
+                (
+                    "type".to_string(),
+                    "application/json; charset=utf-8".to_string()
+                ),
+            ]
+        );
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("set email = json.body.email: "));
+        // set lines are not counted as tests
+        assert_eq!(run(tests, &response()).len(), 1);
+    }
+}
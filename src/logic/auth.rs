@@ -0,0 +1,773 @@
+//! Authentication helpers for outgoing requests
+//!
+//! An [`Auth`] is stored on every request and resolved right before sending.
+//! Resolution turns the configured auth type into concrete headers/params,
+//! fetching and caching OAuth2 tokens when needed.
+
+use crate::error::RequestError;
+use crate::logic::request::REQUEST_TIMEOUT;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Tokens are refreshed this long before they actually expire
+const EXPIRY_SKEW: Duration = Duration::from_secs(30);
+
+/// How long the authorization code flow waits for the browser redirect
+const AUTHORIZATION_TIMEOUT: Duration = Duration::from_secs(120);
+
+const DEFAULT_REDIRECT_PORT: u16 = 8765;
+
+/// Supported authentication types
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuthKind {
+    #[default]
+    #[serde(rename = "none")]
+    None,
+    #[serde(rename = "api_key")]
+    ApiKey,
+    #[serde(rename = "hmac")]
+    HmacSignature,
+    #[serde(rename = "aws_sigv4")]
+    AwsSigV4,
+    #[serde(rename = "oauth2_client_credentials")]
+    OAuth2ClientCredentials,
+    #[serde(rename = "oauth2_authorization_code")]
+    OAuth2AuthorizationCode,
+}
+
+/// Where an API key is placed on the request
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiKeyLocation {
+    Header,
+    Query,
+}
+
+impl ApiKeyLocation {
+    fn parse(value: Option<&str>) -> Result<Self, RequestError> {
+        match value.map(|v| v.to_lowercase()).as_deref() {
+            None | Some("header") => Ok(ApiKeyLocation::Header),
+            Some("query") | Some("param") => Ok(ApiKeyLocation::Query),
+            Some(other) => Err(RequestError::authentication(format!(
+                "Unknown API key location '{}' (use header or query)",
+                other
+            ))),
+        }
+    }
+}
+
+impl AuthKind {
+    pub const ALL: [AuthKind; 6] = [
+        AuthKind::None,
+        AuthKind::ApiKey,
+        AuthKind::HmacSignature,
+        AuthKind::AwsSigV4,
+        AuthKind::OAuth2ClientCredentials,
+        AuthKind::OAuth2AuthorizationCode,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AuthKind::None => "No Auth",
+            AuthKind::ApiKey => "API Key",
+            AuthKind::HmacSignature => "HMAC Signature",
+            AuthKind::AwsSigV4 => "AWS SigV4",
+            AuthKind::OAuth2ClientCredentials => "OAuth2 (client credentials)",
+            AuthKind::OAuth2AuthorizationCode => "OAuth2 (authorization code)",
+        }
+    }
+
+    /// Fields that must be set before a request can be sent
+    pub fn required_fields(&self) -> &'static [&'static str] {
+        match self {
+            AuthKind::None => &[],
+            AuthKind::ApiKey => &["key", "value"],
+            AuthKind::HmacSignature => &["secret"],
+            AuthKind::AwsSigV4 => &["access_key", "secret_key", "region", "service"],
+            AuthKind::OAuth2ClientCredentials => &["token_url", "client_id", "client_secret"],
+            AuthKind::OAuth2AuthorizationCode => &["auth_url", "token_url", "client_id"],
+        }
+    }
+
+    /// Fields that are understood but may be left empty
+    pub fn optional_fields(&self) -> &'static [&'static str] {
+        match self {
+            AuthKind::None => &[],
+            AuthKind::ApiKey => &["location"],
+            AuthKind::HmacSignature => &["key_id", "header", "algorithm", "encoding"],
+            AuthKind::AwsSigV4 => &["session_token"],
+            AuthKind::OAuth2ClientCredentials => &["scope"],
+            AuthKind::OAuth2AuthorizationCode => &["client_secret", "scope", "redirect_port"],
+        }
+    }
+
+    /// Returns the next auth type, wrapping around
+    pub fn next(&self) -> AuthKind {
+        let index = Self::ALL.iter().position(|k| k == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Authentication configuration attached to a request
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Auth {
+    #[serde(default)]
+    pub kind: AuthKind,
+    #[serde(default)]
+    pub fields: Vec<(String, String)>,
+}
+
+/// Headers and query parameters produced by resolving an [`Auth`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuthInjection {
+    pub headers: Vec<(String, String)>,
+    pub params: Vec<(String, String)>,
+}
+
+impl Auth {
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Sets a field, replacing any existing value for the same key
+    pub fn set_field(&mut self, name: &str, value: &str) {
+        match self.fields.iter_mut().find(|(key, _)| key == name) {
+            Some(entry) => entry.1 = value.to_string(),
+            None => self.fields.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    pub fn remove_field(&mut self, name: &str) {
+        self.fields.retain(|(key, _)| key != name);
+    }
+
+    /// Whether a field holds a secret and should be masked in the UI
+    pub fn is_secret_field(name: &str) -> bool {
+        let name = name.to_lowercase();
+        name == "value"
+            || name.contains("secret")
+            || name.contains("password")
+            || name.contains("token")
+    }
+
+    pub fn missing_fields(&self) -> Vec<&'static str> {
+        self.kind
+            .required_fields()
+            .iter()
+            .filter(|name| self.field(name).is_none())
+            .copied()
+            .collect()
+    }
+
+    pub fn validate(&self) -> Result<(), RequestError> {
+        let missing = self.missing_fields();
+        if !missing.is_empty() {
+            return Err(RequestError::authentication(format!(
+                "{} is missing required fields: {}",
+                self.kind.label(),
+                missing.join(", ")
+            )));
+        }
+        if self.kind == AuthKind::ApiKey {
+            ApiKeyLocation::parse(self.field("location"))?;
+        }
+        Ok(())
+    }
+
+    /// Resolves the auth configuration into headers/params for a request
+    pub async fn resolve(&self, client: &Client) -> Result<AuthInjection, RequestError> {
+        match self.kind {
+            AuthKind::None => Ok(AuthInjection::default()),
+            // Signatures cover the whole request and are added by `signing::sign`
+            AuthKind::HmacSignature | AuthKind::AwsSigV4 => {
+                self.validate()?;
+                Ok(AuthInjection::default())
+            }
+            AuthKind::ApiKey => {
+                self.validate()?;
+                let pair = vec![(
+                    self.field("key").unwrap_or_default().to_string(),
+                    self.field("value").unwrap_or_default().to_string(),
+                )];
+                match ApiKeyLocation::parse(self.field("location"))? {
+                    ApiKeyLocation::Header => Ok(AuthInjection {
+                        headers: pair,
+                        params: vec![],
+                    }),
+                    ApiKeyLocation::Query => Ok(AuthInjection {
+                        headers: vec![],
+                        params: pair,
+                    }),
+                }
+            }
+            AuthKind::OAuth2ClientCredentials | AuthKind::OAuth2AuthorizationCode => {
+                self.validate()?;
+                let token = oauth2_token(self, client).await?;
+                Ok(AuthInjection {
+                    headers: vec![(
+                        "Authorization".to_string(),
+                        format!("{} {}", token.token_type, token.access_token),
+                    )],
+                    params: vec![],
+                })
+            }
+        }
+    }
+}
+
+/// Access token as cached between requests
+#[derive(Clone, Debug)]
+struct CachedToken {
+    access_token: String,
+    token_type: String,
+    refresh_token: Option<String>,
+    expires_at: Option<Instant>,
+}
+
+impl CachedToken {
+    fn from_response(response: TokenResponse, now: Instant) -> Self {
+        CachedToken {
+            access_token: response.access_token,
+            token_type: normalize_token_type(response.token_type.as_deref()),
+            refresh_token: response.refresh_token,
+            expires_at: response
+                .expires_in
+                .map(|secs| now + Duration::from_secs(secs)),
+        }
+    }
+
+    fn is_expired(&self, now: Instant) -> bool {
+        match self.expires_at {
+            Some(expires_at) => now + EXPIRY_SKEW >= expires_at,
+            None => false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    token_type: Option<String>,
+    expires_in: Option<u64>,
+    refresh_token: Option<String>,
+}
+
+fn token_cache() -> &'static Mutex<HashMap<String, CachedToken>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, CachedToken>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn cache_key(auth: &Auth) -> String {
+    format!(
+        "{:?}|{}|{}|{}",
+        auth.kind,
+        auth.field("token_url").unwrap_or_default(),
+        auth.field("client_id").unwrap_or_default(),
+        auth.field("scope").unwrap_or_default()
+    )
+}
+
+/// Servers send "bearer", "Bearer" or nothing at all; normalize for the header
+fn normalize_token_type(token_type: Option<&str>) -> String {
+    match token_type {
+        Some(t) if !t.is_empty() && !t.eq_ignore_ascii_case("bearer") => t.to_string(),
+        _ => "Bearer".to_string(),
+    }
+}
+
+/// Drops every cached OAuth2 token, forcing the next send to re-authenticate
+pub fn clear_token_cache() {
+    if let Ok(mut cache) = token_cache().lock() {
+        cache.clear();
+    }
+}
+
+async fn oauth2_token(auth: &Auth, client: &Client) -> Result<CachedToken, RequestError> {
+    let key = cache_key(auth);
+    let cached = token_cache()
+        .lock()
+        .map_err(|_| RequestError::authentication("Token cache is poisoned"))?
+        .get(&key)
+        .cloned();
+
+    let now = Instant::now();
+    if let Some(token) = &cached {
+        if !token.is_expired(now) {
+            return Ok(token.clone());
+        }
+    }
+
+    // Prefer the refresh token when the cached one expired, fall back to a full grant
+    let refreshed = match cached.and_then(|t| t.refresh_token) {
+        Some(refresh_token) => refresh(auth, client, &refresh_token).await.ok(),
+        None => None,
+    };
+
+    let token = match refreshed {
+        Some(token) => token,
+        None => match auth.kind {
+            AuthKind::OAuth2AuthorizationCode => authorization_code_grant(auth, client).await?,
+            _ => client_credentials_grant(auth, client).await?,
+        },
+    };
+
+    token_cache()
+        .lock()
+        .map_err(|_| RequestError::authentication("Token cache is poisoned"))?
+        .insert(key, token.clone());
+
+    Ok(token)
+}
+
+async fn client_credentials_grant(
+    auth: &Auth,
+    client: &Client,
+) -> Result<CachedToken, RequestError> {
+    let mut form = vec![
+        ("grant_type", "client_credentials".to_string()),
+        (
+            "client_id",
+            auth.field("client_id").unwrap_or_default().to_string(),
+        ),
+        (
+            "client_secret",
+            auth.field("client_secret").unwrap_or_default().to_string(),
+        ),
+    ];
+    if let Some(scope) = auth.field("scope") {
+        form.push(("scope", scope.to_string()));
+    }
+    request_token(auth, client, &form).await
+}
+
+async fn refresh(
+    auth: &Auth,
+    client: &Client,
+    refresh_token: &str,
+) -> Result<CachedToken, RequestError> {
+    let mut form = vec![
+        ("grant_type", "refresh_token".to_string()),
+        ("refresh_token", refresh_token.to_string()),
+        (
+            "client_id",
+            auth.field("client_id").unwrap_or_default().to_string(),
+        ),
+    ];
+    if let Some(secret) = auth.field("client_secret") {
+        form.push(("client_secret", secret.to_string()));
+    }
+    let mut token = request_token(auth, client, &form).await?;
+    // Some servers only return a new refresh token when rotating it
+    if token.refresh_token.is_none() {
+        token.refresh_token = Some(refresh_token.to_string());
+    }
+    Ok(token)
+}
+
+async fn authorization_code_grant(
+    auth: &Auth,
+    client: &Client,
+) -> Result<CachedToken, RequestError> {
+    let port = match auth.field("redirect_port") {
+        Some(port) => port.parse::<u16>().map_err(|_| {
+            RequestError::authentication(format!("Invalid redirect_port: {}", port))
+        })?,
+        None => DEFAULT_REDIRECT_PORT,
+    };
+    let redirect_uri = format!("http://127.0.0.1:{}/callback", port);
+    let state = random_token(16)?;
+    let verifier = random_token(32)?;
+    let authorize_url =
+        build_authorize_url(auth, &redirect_uri, &state, &code_challenge(&verifier));
+
+    let listener = TcpListener::bind(("127.0.0.1", port)).await.map_err(|e| {
+        RequestError::authentication(format!("Failed to listen on port {}: {}", port, e))
+    })?;
+
+    open_in_browser(&authorize_url);
+
+    let code = tokio::time::timeout(AUTHORIZATION_TIMEOUT, wait_for_code(&listener, &state))
+        .await
+        .map_err(|_| {
+            RequestError::authentication(format!(
+                "Timed out waiting for authorization. Open this URL to log in: {}",
+                authorize_url
+            ))
+        })??;
+
+    let mut form = vec![
+        ("grant_type", "authorization_code".to_string()),
+        ("code", code),
+        ("redirect_uri", redirect_uri),
+        (
+            "client_id",
+            auth.field("client_id").unwrap_or_default().to_string(),
+        ),
+        ("code_verifier", verifier),
+    ];
+    if let Some(secret) = auth.field("client_secret") {
+        form.push(("client_secret", secret.to_string()));
+    }
+    request_token(auth, client, &form).await
+}
+
+async fn request_token(
+    auth: &Auth,
+    client: &Client,
+    form: &[(&str, String)],
+) -> Result<CachedToken, RequestError> {
+    let token_url = auth.field("token_url").unwrap_or_default();
+    let response = client
+        .post(token_url)
+        .header("Accept", "application/json")
+        .form(form)
+        .timeout(REQUEST_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| RequestError::authentication(format!("Token request failed: {}", e)))?;
+
+    let status = response.status();
+    let body = response.text().await.map_err(RequestError::Http)?;
+    if !status.is_success() {
+        return Err(RequestError::authentication(format!(
+            "Token endpoint returned {}: {}",
+            status.as_u16(),
+            body
+        )));
+    }
+
+    parse_token_response(&body, Instant::now())
+}
+
+fn parse_token_response(body: &str, now: Instant) -> Result<CachedToken, RequestError> {
+    let response: TokenResponse = serde_json::from_str(body)
+        .map_err(|e| RequestError::authentication(format!("Invalid token response: {}", e)))?;
+    Ok(CachedToken::from_response(response, now))
+}
+
+fn build_authorize_url(auth: &Auth, redirect_uri: &str, state: &str, challenge: &str) -> String {
+    let auth_url = auth.field("auth_url").unwrap_or_default();
+    let mut query = vec![
+        ("response_type", "code"),
+        ("client_id", auth.field("client_id").unwrap_or_default()),
+        ("redirect_uri", redirect_uri),
+        ("state", state),
+        ("code_challenge", challenge),
+        ("code_challenge_method", "S256"),
+    ];
+    if let Some(scope) = auth.field("scope") {
+        query.push(("scope", scope));
+    }
+
+    let query_string = query
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+    let separator = if auth_url.contains('?') { '&' } else { '?' };
+    format!("{}{}{}", auth_url, separator, query_string)
+}
+
+/// Accepts loopback connections until the redirect carrying the code arrives
+async fn wait_for_code(
+    listener: &TcpListener,
+    expected_state: &str,
+) -> Result<String, RequestError> {
+    loop {
+        let (mut stream, _) = listener.accept().await.map_err(|e| {
+            RequestError::authentication(format!("Failed to accept redirect: {}", e))
+        })?;
+
+        let mut buffer = vec![0u8; 8192];
+        let read = stream.read(&mut buffer).await.unwrap_or(0);
+        let request = String::from_utf8_lossy(&buffer[..read]);
+
+        let outcome = parse_callback(&request, expected_state);
+        let page = match &outcome {
+            Some(Ok(_)) => {
+                "Authorization complete. You can close this window and return to restless."
+            }
+            Some(Err(_)) => "Authorization failed. Check restless for details.",
+            None => "Waiting for authorization...",
+        };
+        let reply = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            page.len(),
+            page
+        );
+        let _ = stream.write_all(reply.as_bytes()).await;
+
+        // Browsers also ask for /favicon.ico; keep waiting for the real callback
+        if let Some(result) = outcome {
+            return result;
+        }
+    }
+}
+
+/// Extracts the authorization code from a raw redirect request
+fn parse_callback(request: &str, expected_state: &str) -> Option<Result<String, RequestError>> {
+    let target = request.lines().next()?.split_whitespace().nth(1)?;
+    let (path, query) = target.split_once('?')?;
+    if path != "/callback" {
+        return None;
+    }
+
+    let params: HashMap<String, String> = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| {
+            let value = urlencoding::decode(&v.replace('+', " "))
+                .map(|v| v.into_owned())
+                .unwrap_or_else(|_| v.to_string());
+            (k.to_string(), value)
+        })
+        .collect();
+
+    if let Some(error) = params.get("error") {
+        return Some(Err(RequestError::authentication(format!(
+            "Authorization server returned error: {}",
+            error
+        ))));
+    }
+    if params.get("state").map(String::as_str) != Some(expected_state) {
+        return Some(Err(RequestError::authentication(
+            "Authorization state mismatch",
+        )));
+    }
+    params.get("code").cloned().map(Ok).or_else(|| {
+        Some(Err(RequestError::authentication(
+            "Redirect did not contain a code",
+        )))
+    })
+}
+
+/// `len` random bytes from the operating system, for the `state` and PKCE
+/// code verifier, base64url encoded
+fn random_token(len: usize) -> Result<String, RequestError> {
+    let mut bytes = vec![0; len];
+    getrandom::fill(&mut bytes).map_err(|e| {
+        RequestError::authentication(format!("Failed to generate a random value: {}", e))
+    })?;
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// The PKCE `S256` challenge sent for a code verifier (RFC 7636)
+fn code_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// Best effort attempt to open a URL in the user's browser
+fn open_in_browser(url: &str) {
+    #[cfg(target_os = "macos")]
+    let command = std::process::Command::new("open").arg(url).spawn();
+    #[cfg(target_os = "windows")]
+    let command = std::process::Command::new("cmd")
+        .args(["/C", "start", "", url])
+        .spawn();
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let command = std::process::Command::new("xdg-open")
+        .arg(url)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn();
+
+    let _ = command;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client_credentials_auth() -> Auth {
+        Auth {
+            kind: AuthKind::OAuth2ClientCredentials,
+            fields: vec![
+                (
+                    "token_url".to_string(),
+                    "https://auth.example.com/token".to_string(),
+                ),
+                ("client_id".to_string(), "my-client".to_string()),
+                ("client_secret".to_string(), "s3cret".to_string()),
+            ],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_api_key_header_and_query() {
+        let mut auth = Auth {
+            kind: AuthKind::ApiKey,
+            fields: vec![
+                ("key".to_string(), "X-Api-Key".to_string()),
+                ("value".to_string(), "abc123".to_string()),
+            ],
+        };
+
+        let injection = auth.resolve(&Client::new()).await.unwrap();
+        assert_eq!(
+            injection.headers,
+            vec![("X-Api-Key".to_string(), "abc123".to_string())]
+        );
+        assert!(injection.params.is_empty());
+
+        auth.set_field("location", "query");
+        let injection = auth.resolve(&Client::new()).await.unwrap();
+        assert!(injection.headers.is_empty());
+        assert_eq!(
+            injection.params,
+            vec![("X-Api-Key".to_string(), "abc123".to_string())]
+        );
+
+        auth.set_field("location", "cookie");
+        assert!(auth.validate().is_err());
+    }
+
+    #[test]
+    fn test_auth_kind_cycles() {
+        assert_eq!(AuthKind::None.next(), AuthKind::ApiKey);
+        assert_eq!(AuthKind::ApiKey.next(), AuthKind::HmacSignature);
+        assert_eq!(AuthKind::OAuth2AuthorizationCode.next(), AuthKind::None);
+    }
+
+    #[test]
+    fn test_set_field_replaces_existing() {
+        let mut auth = client_credentials_auth();
+        auth.set_field("client_id", "other");
+        assert_eq!(auth.field("client_id"), Some("other"));
+        assert_eq!(auth.fields.len(), 3);
+
+        auth.remove_field("client_id");
+        assert_eq!(auth.field("client_id"), None);
+    }
+
+    #[test]
+    fn test_validate_reports_missing_fields() {
+        let mut auth = client_credentials_auth();
+        assert!(auth.validate().is_ok());
+
+        auth.set_field("client_secret", "");
+        assert_eq!(auth.missing_fields(), vec!["client_secret"]);
+        assert!(auth.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_token_response() {
+        let now = Instant::now();
+        let token = parse_token_response(
+            r#"{"access_token":"abc","token_type":"bearer","expires_in":3600,"refresh_token":"r1"}"#,
+            now,
+        )
+        .unwrap();
+        assert_eq!(token.access_token, "abc");
+        assert_eq!(token.token_type, "Bearer");
+        assert_eq!(token.refresh_token.as_deref(), Some("r1"));
+        assert!(!token.is_expired(now));
+        assert!(token.is_expired(now + Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_token_expiry_includes_skew() {
+        let now = Instant::now();
+        let token = parse_token_response(r#"{"access_token":"abc","expires_in":10}"#, now).unwrap();
+        // Expiring within the skew window counts as expired
+        assert!(token.is_expired(now));
+
+        let token = parse_token_response(r#"{"access_token":"abc"}"#, now).unwrap();
+        assert!(!token.is_expired(now + Duration::from_secs(86400)));
+    }
+
+    #[test]
+    fn test_parse_token_response_invalid() {
+        assert!(parse_token_response("not json", Instant::now()).is_err());
+    }
+
+    #[test]
+    fn test_build_authorize_url() {
+        let auth = Auth {
+            kind: AuthKind::OAuth2AuthorizationCode,
+            fields: vec![
+                (
+                    "auth_url".to_string(),
+                    "https://auth.example.com/authorize".to_string(),
+                ),
+                ("client_id".to_string(), "my client".to_string()),
+                ("scope".to_string(), "read write".to_string()),
+            ],
+        };
+        let url = build_authorize_url(&auth, "http://127.0.0.1:8765/callback", "xyz", "c-1");
+        assert_eq!(
+            url,
+            "https://auth.example.com/authorize?response_type=code&client_id=my%20client\
+             &redirect_uri=http%3A%2F%2F127.0.0.1%3A8765%2Fcallback&state=xyz\
+             &code_challenge=c-1&code_challenge_method=S256&scope=read%20write"
+        );
+    }
+
+    #[test]
+    fn test_pkce() {
+        // The example of RFC 7636, appendix B
+        assert_eq!(
+            code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        let verifier = random_token(32).unwrap();
+        assert_eq!(verifier.len(), 43);
+        assert_ne!(verifier, random_token(32).unwrap());
+    }
+
+    #[test]
+    fn test_parse_callback() {
+        let request = "GET /callback?code=abc%2F123&state=xyz HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n";
+        assert_eq!(parse_callback(request, "xyz").unwrap().unwrap(), "abc/123");
+
+        let wrong_state = "GET /callback?code=abc&state=nope HTTP/1.1\r\n\r\n";
+        assert!(parse_callback(wrong_state, "xyz").unwrap().is_err());
+
+        let denied = "GET /callback?error=access_denied&state=xyz HTTP/1.1\r\n\r\n";
+        assert!(parse_callback(denied, "xyz").unwrap().is_err());
+
+        let favicon = "GET /favicon.ico HTTP/1.1\r\n\r\n";
+        assert!(parse_callback(favicon, "xyz").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_without_auth_is_empty() {
+        let auth = Auth::default();
+        let injection = auth.resolve(&Client::new()).await.unwrap();
+        assert_eq!(injection, AuthInjection::default());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_uses_cached_token() {
+        let mut auth = client_credentials_auth();
+        auth.set_field("token_url", "https://cached.example.com/token");
+        let token = parse_token_response(
+            r#"{"access_token":"cached-token","expires_in":3600}"#,
+            Instant::now(),
+        )
+        .unwrap();
+        token_cache()
+            .lock()
+            .unwrap()
+            .insert(cache_key(&auth), token);
+
+        let injection = auth.resolve(&Client::new()).await.unwrap();
+        assert_eq!(
+            injection.headers,
+            vec![(
+                "Authorization".to_string(),
+                "Bearer cached-token".to_string()
+            )]
+        );
+    }
+}
@@ -0,0 +1,153 @@
+//! Headers and params edited as one block of text
+//!
+//! Every line is a `Name: value` header or a `name=value` param, and a line
+//! starting with `//` is switched off. Blocks copied from the browser's
+//! developer tools paste as they are: HTTP/2 pseudo-headers (`:authority`)
+//! and the request line are left out, and a query string may put several
+//! params on one line joined with `&`.
+
+use crate::error::{RestlessError, Result};
+
+/// Entries kept and switched off, as parsed from a block
+pub type Entries = (Vec<(String, String)>, Vec<(String, String)>);
+
+const DISABLED: &str = "//";
+
+/// The block for `enabled` and then `disabled` entries, with `separator`
+/// between name and value
+pub fn format(
+    enabled: &[(String, String)],
+    disabled: &[(String, String)],
+    separator: &str,
+) -> String {
+    let on = enabled
+        .iter()
+        .map(|(name, value)| format!("{}{}{}", name, separator, value));
+    let off = disabled
+        .iter()
+        .map(|(name, value)| format!("{}{}{}{}", DISABLED, name, separator, value));
+    on.chain(off).collect::<Vec<_>>().join("\n")
+}
+
+pub fn parse_headers(text: &str) -> Result<Entries> {
+    let mut entries: Entries = Default::default();
+    for (number, line, disabled) in lines(text) {
+        // Pseudo-headers and request lines don't go into a request
+        if line.starts_with(':') || is_request_line(line) {
+            continue;
+        }
+        let (name, value) = line.split_once(':').ok_or_else(|| {
+            RestlessError::invalid_header(format!(
+                "Line {} has no ':' between name and value",
+                number
+            ))
+        })?;
+        let list = if disabled {
+            &mut entries.1
+        } else {
+            &mut entries.0
+        };
+        list.push((name.trim().to_string(), value.trim().to_string()));
+    }
+    Ok(entries)
+}
+
+pub fn parse_params(text: &str) -> Result<Entries> {
+    let mut entries: Entries = Default::default();
+    for (number, line, disabled) in lines(text) {
+        let line = line.strip_prefix('?').unwrap_or(line);
+        for pair in line.split('&').filter(|pair| !pair.trim().is_empty()) {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            if name.trim().is_empty() {
+                return Err(RestlessError::invalid_parameter(format!(
+                    "Line {} has no parameter name",
+                    number
+                )));
+            }
+            let list = if disabled {
+                &mut entries.1
+            } else {
+                &mut entries.0
+            };
+            list.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    Ok(entries)
+}
+
+/// The non-blank lines with their number from 1, without the `//` of
+/// disabled ones
+fn lines(text: &str) -> impl Iterator<Item = (usize, &str, bool)> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(number, line)| match line.strip_prefix(DISABLED) {
+            Some(rest) => (number, rest.trim_start(), true),
+            None => (number, line, false),
+        })
+}
+
+/// `GET /path HTTP/1.1` as copied along with the headers
+fn is_request_line(line: &str) -> bool {
+    line.split_whitespace()
+        .last()
+        .is_some_and(|last| last.starts_with("HTTP/"))
+        && !line.contains(": ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let enabled = pairs(&[("Accept", "application/json"), ("X-Time", "12:30")]);
+        let disabled = pairs(&[("Authorization", "Bearer abc")]);
+        let text = format(&enabled, &disabled, ": ");
+        assert_eq!(
+            text,
+            "Accept: application/json\nX-Time: 12:30\n//Authorization: Bearer abc"
+        );
+        assert_eq!(parse_headers(&text).unwrap(), (enabled, disabled));
+
+        let params = pairs(&[("page", "2"), ("q", "")]);
+        let text = format(&params, &[], "=");
+        assert_eq!(parse_params(&text).unwrap(), (params, Vec::new()));
+    }
+
+    #[test]
+    fn test_parse_devtools_block() {
+        let block = "GET /api/users?page=2 HTTP/1.1\n:authority: api.test\n:method: GET\n\
+                     accept: */*\n\n// x-debug: 1\nuser-agent: Mozilla/5.0 (X11; Linux)\n";
+        let (enabled, disabled) = parse_headers(block).unwrap();
+        assert_eq!(
+            enabled,
+            pairs(&[
+                ("accept", "*/*"),
+                ("user-agent", "Mozilla/5.0 (X11; Linux)")
+            ])
+        );
+        assert_eq!(disabled, pairs(&[("x-debug", "1")]));
+
+        assert!(parse_headers("Accept application/json").is_err());
+    }
+
+    #[test]
+    fn test_parse_params() {
+        let (enabled, disabled) = parse_params("?page=2&sort=name\n//debug\nq=a=b").unwrap();
+        assert_eq!(
+            enabled,
+            pairs(&[("page", "2"), ("sort", "name"), ("q", "a=b")])
+        );
+        assert_eq!(disabled, pairs(&[("debug", "")]));
+        assert!(parse_params("=1").is_err());
+    }
+}
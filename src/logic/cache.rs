@@ -0,0 +1,254 @@
+//! Validators of earlier responses, for conditional requests
+//!
+//! A `GET` whose response carried an `ETag` or `Last-Modified` header is
+//! remembered for the session, by its URL and by the credential headers and
+//! the headers named in its `Vary` that the request was sent with. The next
+//! `GET` of that URL with the same headers asks with `If-None-Match` /
+//! `If-Modified-Since` whether it changed, and a `304 Not Modified` answer
+//! comes with the copy stored here, so the body can be shown instead of
+//! nothing. Switching token or environment asks without validators instead
+//! of showing another user's copy. The `cache=false` option opts a request
+//! out, and only the [`MAX_ENTRIES`] most recently used copies are kept.
+
+use crate::logic::headers;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+/// Most responses kept at once; the least recently used goes first
+pub const MAX_ENTRIES: usize = 256;
+
+/// A response kept for its validators
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cached {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    /// Headers as `Name: value` lines, like [`crate::logic::request::RawResponse`]
+    pub headers: String,
+    pub body: String,
+    pub stored: DateTime<Utc>,
+    /// Request headers named in `Vary`, in lowercase
+    pub vary: Vec<String>,
+}
+
+impl Cached {
+    /// Keeps a response with `Name: value` header lines, if it has a
+    /// validator and may be stored
+    pub fn new(headers: &str, body: &str, stored: DateTime<Utc>) -> Option<Self> {
+        let header = |name: &str| {
+            headers.lines().find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                key.trim()
+                    .eq_ignore_ascii_case(name)
+                    .then(|| value.trim().to_string())
+            })
+        };
+        if header("cache-control").is_some_and(|value| value.to_lowercase().contains("no-store")) {
+            return None;
+        }
+        let vary: Vec<String> = header("vary")
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(|name| name.trim().to_lowercase())
+                    .filter(|name| !name.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        // Varies with something other than the request headers
+        if vary.iter().any(|name| name == "*") {
+            return None;
+        }
+        let etag = header("etag").filter(|etag| !etag.is_empty());
+        let last_modified = header("last-modified").filter(|date| !date.is_empty());
+        if etag.is_none() && last_modified.is_none() {
+            return None;
+        }
+        Some(Cached {
+            etag,
+            last_modified,
+            headers: headers.to_string(),
+            body: body.to_string(),
+            stored,
+            vary,
+        })
+    }
+
+    /// The headers asking the server whether this copy is still current
+    pub fn conditional_headers(&self) -> Vec<(String, String)> {
+        let mut headers = Vec::new();
+        if let Some(etag) = &self.etag {
+            headers.push(("If-None-Match".to_string(), etag.clone()));
+        }
+        if let Some(last_modified) = &self.last_modified {
+            headers.push(("If-Modified-Since".to_string(), last_modified.clone()));
+        }
+        headers
+    }
+}
+
+/// Whether the request already asks conditionally on its own
+pub fn is_conditional(headers: &[(String, String)]) -> bool {
+    headers.iter().any(|(key, _)| {
+        key.eq_ignore_ascii_case("if-none-match") || key.eq_ignore_ascii_case("if-modified-since")
+    })
+}
+
+/// The request headers a copy was stored for: the credentials and those
+/// named in `vary`, as sorted lowercase `(name, value)` pairs
+fn keyed_headers(request_headers: &[(String, String)], vary: &[String]) -> Vec<(String, String)> {
+    let mut keyed: Vec<(String, String)> = request_headers
+        .iter()
+        .map(|(name, value)| (name.to_lowercase(), value.clone()))
+        .filter(|(name, _)| headers::is_credential(name) || vary.contains(name))
+        .collect();
+    keyed.sort();
+    keyed
+}
+
+#[derive(Debug)]
+struct Entry {
+    url: String,
+    request_headers: Vec<(String, String)>,
+    cached: Cached,
+}
+
+impl Entry {
+    fn matches(&self, url: &str, request_headers: &[(String, String)]) -> bool {
+        self.url == url && self.request_headers == keyed_headers(request_headers, &self.cached.vary)
+    }
+}
+
+/// Responses with validators, by request URL and headers, most recently
+/// used last
+#[derive(Debug, Default)]
+pub struct ResponseCache {
+    entries: Mutex<VecDeque<Entry>>,
+}
+
+impl ResponseCache {
+    fn lock(&self) -> MutexGuard<'_, VecDeque<Entry>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// The copy stored for a request of `url` with `request_headers`
+    pub fn get(&self, url: &str, request_headers: &[(String, String)]) -> Option<Cached> {
+        let mut entries = self.lock();
+        let index = entries
+            .iter()
+            .position(|entry| entry.matches(url, request_headers))?;
+        let entry = entries.remove(index)?;
+        let cached = entry.cached.clone();
+        entries.push_back(entry);
+        Some(cached)
+    }
+
+    /// Keeps `cached` for requests of `url` with `request_headers`, in place
+    /// of any copy stored for the same ones
+    pub fn insert(&self, url: &str, request_headers: &[(String, String)], cached: Cached) {
+        let mut entries = self.lock();
+        entries.retain(|entry| !entry.matches(url, request_headers));
+        if entries.len() >= MAX_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(Entry {
+            url: url.to_string(),
+            request_headers: keyed_headers(request_headers, &cached.vary),
+            cached,
+        });
+    }
+}
+
+/// The cache every send uses
+pub fn shared_cache() -> Arc<ResponseCache> {
+    static CACHE: OnceLock<Arc<ResponseCache>> = OnceLock::new();
+    CACHE
+        .get_or_init(|| Arc::new(ResponseCache::default()))
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_validators() {
+        let now = Utc::now();
+        let cached = Cached::new(
+            "content-type: application/json\nETag: \"v1\"\nlast-modified: Tue, 01 Sep 2026 10:00:00 GMT",
+            "{}",
+            now,
+        )
+        .unwrap();
+        assert_eq!(
+            cached.conditional_headers(),
+            vec![
+                ("If-None-Match".to_string(), "\"v1\"".to_string()),
+                (
+                    "If-Modified-Since".to_string(),
+                    "Tue, 01 Sep 2026 10:00:00 GMT".to_string()
+                ),
+            ]
+        );
+
+        // Nothing to revalidate with, or not to be stored
+        assert!(Cached::new("content-type: text/plain", "hi", now).is_none());
+        assert!(
+            Cached::new("etag: W/\"1\"\ncache-control: private, no-store", "hi", now).is_none()
+        );
+        // no-cache still allows storing, it only demands revalidation
+        assert!(Cached::new("etag: W/\"1\"\ncache-control: no-cache", "hi", now).is_some());
+
+        assert!(is_conditional(&[(
+            "if-none-match".to_string(),
+            "*".to_string()
+        )]));
+        assert!(!is_conditional(&[(
+            "Accept".to_string(),
+            "*/*".to_string()
+        )]));
+        assert!(Cached::new("etag: \"1\"\nvary: *", "hi", now).is_none());
+    }
+
+    #[test]
+    fn test_cache_keys_and_limit() {
+        let header = |name: &str, value: &str| (name.to_string(), value.to_string());
+        let now = Utc::now();
+        let cache = ResponseCache::default();
+        let alice = [header("Authorization", "Bearer a"), header("Accept", "*/*")];
+        let bob = [header("Authorization", "Bearer b"), header("Accept", "*/*")];
+        let copy = Cached::new("etag: \"1\"", "alice's", now).unwrap();
+        cache.insert("https://api.test/me", &alice, copy);
+
+        // Another token, or none, doesn't get the copy
+        assert!(cache.get("https://api.test/me", &alice).is_some());
+        assert!(cache.get("https://api.test/me", &bob).is_none());
+        assert!(cache.get("https://api.test/me", &[]).is_none());
+        assert!(cache.get("https://api.test/other", &alice).is_none());
+        // Headers that aren't credentials and aren't named in Vary don't count
+        let other_accept = [
+            header("authorization", "Bearer a"),
+            header("Accept", "text/*"),
+        ];
+        assert!(cache.get("https://api.test/me", &other_accept).is_some());
+
+        // Headers named in Vary do
+        let copy = Cached::new("etag: \"2\"\nvary: Accept-Language", "de", now).unwrap();
+        let german = [header("Accept-Language", "de")];
+        cache.insert("https://api.test/greeting", &german, copy);
+        assert!(cache.get("https://api.test/greeting", &german).is_some());
+        let english = [header("Accept-Language", "en")];
+        assert!(cache.get("https://api.test/greeting", &english).is_none());
+
+        // The least recently used copy goes first
+        for i in 0..MAX_ENTRIES - 1 {
+            let copy = Cached::new("etag: \"3\"", "", now).unwrap();
+            cache.insert(&format!("https://api.test/{}", i), &[], copy);
+        }
+        assert!(cache.get("https://api.test/me", &alice).is_none());
+        assert!(cache.get("https://api.test/greeting", &german).is_some());
+        assert_eq!(cache.lock().len(), MAX_ENTRIES);
+    }
+}
@@ -0,0 +1,133 @@
+//! Character set detection and transcoding of response bodies
+//!
+//! The encoding comes from a byte order mark, then the `charset` parameter of
+//! `Content-Type`. Without either, bytes that aren't valid UTF-8 are read as
+//! windows-1252, the superset of Latin-1 that browsers fall back to.
+
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+
+/// Where the encoding of a body was found
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharsetSource {
+    Bom,
+    ContentType,
+    /// Nothing declared and the body wasn't valid UTF-8
+    Guessed,
+}
+
+/// The encoding a body was transcoded from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Charset {
+    pub encoding: &'static Encoding,
+    pub source: CharsetSource,
+}
+
+impl Charset {
+    /// Encoding name and where it came from, e.g. `ISO-8859-2 (Content-Type)`
+    pub fn summary(&self) -> String {
+        let source = match self.source {
+            CharsetSource::Bom => "byte order mark",
+            CharsetSource::ContentType => "Content-Type",
+            CharsetSource::Guessed => "guessed",
+        };
+        format!("{} ({})", self.encoding.name(), source)
+    }
+
+    /// Whether the body had to be converted to display it
+    pub fn is_transcoded(&self) -> bool {
+        self.encoding != UTF_8
+    }
+}
+
+/// The `charset` parameter of a `Content-Type` value
+pub fn from_content_type(content_type: &str) -> Option<&'static Encoding> {
+    content_type.split(';').skip(1).find_map(|parameter| {
+        let (key, value) = parameter.split_once('=')?;
+        if !key.trim().eq_ignore_ascii_case("charset") {
+            return None;
+        }
+        Encoding::for_label(value.trim().trim_matches('"').as_bytes())
+    })
+}
+
+/// Decodes a body to UTF-8, reporting the encoding it was in
+///
+/// Plain UTF-8 without a BOM reports no charset, since there's nothing
+/// worth pointing out.
+pub fn decode(bytes: &[u8], content_type: Option<&str>) -> (String, Option<Charset>) {
+    let detected = match Encoding::for_bom(bytes) {
+        Some((encoding, _)) => Some(Charset {
+            encoding,
+            source: CharsetSource::Bom,
+        }),
+        None => content_type
+            .and_then(from_content_type)
+            .map(|encoding| Charset {
+                encoding,
+                source: CharsetSource::ContentType,
+            }),
+    };
+
+    let charset = match detected {
+        Some(charset) => charset,
+        None if std::str::from_utf8(bytes).is_ok() => {
+            return (String::from_utf8_lossy(bytes).into_owned(), None);
+        }
+        None => Charset {
+            encoding: WINDOWS_1252,
+            source: CharsetSource::Guessed,
+        },
+    };
+
+    // `decode` strips the BOM itself
+    let (text, _, _) = charset.encoding.decode(bytes);
+    let charset =
+        (charset.is_transcoded() || charset.source == CharsetSource::Bom).then_some(charset);
+    (text.into_owned(), charset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_charset_from_content_type() {
+        let encoding = from_content_type("text/plain; charset=\"ISO-8859-1\"").unwrap();
+        assert_eq!(encoding, WINDOWS_1252);
+        assert_eq!(
+            from_content_type("text/html;Charset=shift_jis"),
+            Some(encoding_rs::SHIFT_JIS)
+        );
+        assert_eq!(from_content_type("application/json"), None);
+        assert_eq!(from_content_type("text/plain; charset=bogus"), None);
+    }
+
+    #[test]
+    fn test_decode_latin1() {
+        let body = b"caf\xe9 cr\xe8me";
+        let (text, charset) = decode(body, Some("text/plain; charset=iso-8859-1"));
+        assert_eq!(text, "café crème");
+        let charset = charset.unwrap();
+        assert_eq!(charset.source, CharsetSource::ContentType);
+        assert_eq!(charset.summary(), "windows-1252 (Content-Type)");
+
+        let (text, charset) = decode(body, Some("text/plain"));
+        assert_eq!(text, "café crème");
+        assert_eq!(charset.unwrap().source, CharsetSource::Guessed);
+    }
+
+    #[test]
+    fn test_decode_bom_and_utf8() {
+        let (text, charset) = decode(b"\xff\xfeh\x00i\x00", None);
+        assert_eq!(text, "hi");
+        assert_eq!(charset.unwrap().summary(), "UTF-16LE (byte order mark)");
+
+        let (text, charset) = decode(b"\xef\xbb\xbf{}", Some("application/json"));
+        assert_eq!(text, "{}");
+        assert_eq!(charset.unwrap().source, CharsetSource::Bom);
+
+        let (text, charset) = decode("naïve".as_bytes(), Some("text/plain; charset=utf-8"));
+        assert_eq!(text, "naïve");
+        assert_eq!(charset, None);
+    }
+}
@@ -0,0 +1,210 @@
+//! Decompression of response bodies
+//!
+//! Restless asks for compressed bodies and undoes the compression itself
+//! rather than leaving it to the HTTP client, which would drop the
+//! `Content-Encoding` header and the size the body had on the wire.
+
+use std::io::{self, Write};
+
+/// Encodings offered in `Accept-Encoding`
+pub const ACCEPT_ENCODING: &str = "gzip, deflate, br, zstd";
+
+/// Most a compressed body, or one chunk of a stream, decodes to; anything
+/// past it is dropped, so a small body can't expand to fill the memory
+pub const MAX_DECODED: usize = 64 * 1024 * 1024;
+
+/// Undoes one `Content-Encoding`, a chunk at a time
+pub enum Decoder {
+    Identity,
+    Gzip(flate2::write::GzDecoder<Output>),
+    Deflate(flate2::write::ZlibDecoder<Output>),
+    Brotli(Box<brotli_decompressor::DecompressorWriter<Output>>),
+    Zstd(zstd::stream::write::Decoder<'static, Output>),
+}
+
+/// Where a decoder writes to, taking at most [`MAX_DECODED`] bytes between
+/// two drains
+#[derive(Default)]
+pub struct Output {
+    bytes: Vec<u8>,
+    /// Whether bytes past the cap were refused
+    truncated: bool,
+}
+
+impl Write for Output {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        let room = MAX_DECODED - self.bytes.len();
+        if room == 0 && !data.is_empty() {
+            self.truncated = true;
+            return Err(io::Error::other("body too large"));
+        }
+        let taken = data.len().min(room);
+        self.bytes.extend_from_slice(&data[..taken]);
+        Ok(taken)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Decoder {
+    /// The decoder for a `Content-Encoding` value; `None` for encodings
+    /// restless can't undo, including several stacked ones
+    pub fn for_encoding(encoding: Option<&str>) -> Option<Decoder> {
+        let encoding = encoding.map(str::trim).unwrap_or_default();
+        Some(match encoding.to_ascii_lowercase().as_str() {
+            "" | "identity" => Decoder::Identity,
+            "gzip" | "x-gzip" => Decoder::Gzip(flate2::write::GzDecoder::new(Output::default())),
+            "deflate" => Decoder::Deflate(flate2::write::ZlibDecoder::new(Output::default())),
+            "br" => Decoder::Brotli(Box::new(brotli_decompressor::DecompressorWriter::new(
+                Output::default(),
+                4096,
+            ))),
+            "zstd" => Decoder::Zstd(zstd::stream::write::Decoder::new(Output::default()).ok()?),
+            _ => return None,
+        })
+    }
+
+    /// Whether the body decoded past [`MAX_DECODED`] and was cut short
+    pub fn truncated(&mut self) -> bool {
+        self.output().is_some_and(|output| output.truncated)
+    }
+
+    /// Decodes a chunk, returning what it decoded to; after a chunk was cut
+    /// short, the stream can't go on
+    pub fn push(&mut self, chunk: &[u8]) -> io::Result<Vec<u8>> {
+        if self.truncated() {
+            return Err(io::Error::other(format!(
+                "decoded to more than {} bytes",
+                MAX_DECODED
+            )));
+        }
+        let written = match self {
+            Decoder::Identity => return Ok(chunk.to_vec()),
+            Decoder::Gzip(decoder) => decoder.write_all(chunk).and_then(|_| decoder.flush()),
+            Decoder::Deflate(decoder) => decoder.write_all(chunk).and_then(|_| decoder.flush()),
+            Decoder::Brotli(decoder) => decoder.write_all(chunk).and_then(|_| decoder.flush()),
+            Decoder::Zstd(decoder) => decoder.write_all(chunk).and_then(|_| decoder.flush()),
+        };
+        self.drain(written)
+    }
+
+    /// Decodes the end of the body, failing on a truncated one
+    pub fn finish(mut self) -> io::Result<Vec<u8>> {
+        if self.truncated() {
+            return Ok(Vec::new());
+        }
+        let finished = match &mut self {
+            Decoder::Identity => return Ok(Vec::new()),
+            Decoder::Gzip(decoder) => decoder.try_finish(),
+            Decoder::Deflate(decoder) => decoder.try_finish(),
+            Decoder::Brotli(decoder) => decoder.close(),
+            Decoder::Zstd(decoder) => decoder.flush(),
+        };
+        self.drain(finished)
+    }
+
+    fn output(&mut self) -> Option<&mut Output> {
+        match self {
+            Decoder::Identity => None,
+            Decoder::Gzip(decoder) => Some(decoder.get_mut()),
+            Decoder::Deflate(decoder) => Some(decoder.get_mut()),
+            Decoder::Brotli(decoder) => Some(decoder.get_mut()),
+            Decoder::Zstd(decoder) => Some(decoder.get_mut()),
+        }
+    }
+
+    /// Takes what was decoded; an error from running into the cap isn't one
+    fn drain(&mut self, written: io::Result<()>) -> io::Result<Vec<u8>> {
+        let Some(output) = self.output() else {
+            return written.map(|_| Vec::new());
+        };
+        if !output.truncated {
+            written?;
+        }
+        Ok(std::mem::take(&mut output.bytes))
+    }
+}
+
+/// Decodes a whole body, and whether it was cut short at [`MAX_DECODED`]
+pub fn decode(mut decoder: Decoder, bytes: &[u8]) -> io::Result<(Vec<u8>, bool)> {
+    let mut body = decoder.push(bytes)?;
+    let mut truncated = decoder.truncated();
+    body.extend(decoder.finish()?);
+    if body.len() > MAX_DECODED {
+        body.truncate(MAX_DECODED);
+        truncated = true;
+    }
+    Ok((body, truncated))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &[u8] = b"{\"message\": \"hello hello hello hello\"}";
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_decode_encodings() {
+        let decode_as = |encoding: &str, bytes: &[u8]| {
+            decode(Decoder::for_encoding(Some(encoding)).unwrap(), bytes)
+                .unwrap()
+                .0
+        };
+        assert_eq!(decode_as("gzip", &gzip(TEXT)), TEXT);
+        assert_eq!(decode_as(" X-GZIP ", &gzip(TEXT)), TEXT);
+
+        let mut zlib = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        zlib.write_all(TEXT).unwrap();
+        assert_eq!(decode_as("deflate", &zlib.finish().unwrap()), TEXT);
+
+        let zstd = zstd::encode_all(TEXT, 0).unwrap();
+        assert_eq!(decode_as("zstd", &zstd), TEXT);
+
+        // "hello" as a brotli stream
+        let brotli = [0x0b, 0x02, 0x80, 0x68, 0x65, 0x6c, 0x6c, 0x6f, 0x03];
+        assert_eq!(decode_as("br", &brotli), b"hello");
+
+        assert_eq!(decode_as("identity", TEXT), TEXT);
+        assert!(Decoder::for_encoding(Some("gzip, br")).is_none());
+        assert!(Decoder::for_encoding(Some("compress")).is_none());
+    }
+
+    #[test]
+    fn test_decode_in_chunks() {
+        let compressed = gzip(TEXT);
+        let mut decoder = Decoder::for_encoding(Some("gzip")).unwrap();
+        let mut body = Vec::new();
+        for chunk in compressed.chunks(5) {
+            body.extend(decoder.push(chunk).unwrap());
+        }
+        body.extend(decoder.finish().unwrap());
+        assert_eq!(body, TEXT);
+
+        let truncated = &compressed[..compressed.len() - 4];
+        let decoder = Decoder::for_encoding(Some("gzip")).unwrap();
+        assert!(decode(decoder, truncated).is_err());
+    }
+
+    #[test]
+    fn test_decode_stops_at_the_cap() {
+        let zeros = vec![0; MAX_DECODED + 1000];
+        let decoder = Decoder::for_encoding(Some("gzip")).unwrap();
+        let (body, truncated) = decode(decoder, &gzip(&zeros)).unwrap();
+        assert_eq!(body.len(), MAX_DECODED);
+        assert!(truncated);
+
+        let decoder = Decoder::for_encoding(Some("gzip")).unwrap();
+        assert_eq!(
+            decode(decoder, &gzip(TEXT)).unwrap(),
+            (TEXT.to_vec(), false)
+        );
+    }
+}
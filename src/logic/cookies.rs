@@ -0,0 +1,350 @@
+//! Cookie jar shared by every send
+//!
+//! Cookies set by responses are stored here and sent back on matching
+//! requests. Unlike reqwest's built-in jar it can be listed and edited, which
+//! the Cookies screen relies on. Matching follows the essentials of RFC 6265:
+//! host-only vs domain cookies, path prefixes, `Secure` and expiry.
+
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderValue;
+use reqwest::Url;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+/// A stored cookie
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+    pub domain: String,
+    pub path: String,
+    /// Only sent to exactly `domain`, not its subdomains
+    pub host_only: bool,
+    pub secure: bool,
+    pub http_only: bool,
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl Cookie {
+    /// Parses a `Set-Cookie` value received from `url`
+    ///
+    /// Returns `None` for malformed cookies or ones the URL may not set.
+    pub fn parse(set_cookie: &str, url: &Url) -> Option<Cookie> {
+        Self::parse_at(set_cookie, url.host_str()?, url.path(), Utc::now())
+    }
+
+    /// Parses a cookie entered by hand, e.g. `session=abc; Domain=example.com`
+    ///
+    /// The `Domain` attribute is required since there is no request to take it from.
+    pub fn parse_manual(input: &str) -> Option<Cookie> {
+        let domain = attributes(input)
+            .find(|(key, _)| key.eq_ignore_ascii_case("domain"))
+            .map(|(_, value)| value.trim_start_matches('.').to_lowercase())?;
+        let mut cookie = Self::parse_at(input, &domain, "/", Utc::now())?;
+        // Hand-written domains mean "exactly this host" unless prefixed with a dot
+        cookie.host_only = !input.to_lowercase().contains("domain=.");
+        Some(cookie)
+    }
+
+    fn parse_at(
+        set_cookie: &str,
+        host: &str,
+        request_path: &str,
+        now: DateTime<Utc>,
+    ) -> Option<Cookie> {
+        let (pair, _) = set_cookie.split_once(';').unwrap_or((set_cookie, ""));
+        let (name, value) = pair.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let host = host.to_lowercase();
+        let mut cookie = Cookie {
+            name: name.to_string(),
+            value: value.trim().trim_matches('"').to_string(),
+            domain: host.clone(),
+            path: default_path(request_path),
+            host_only: true,
+            secure: false,
+            http_only: false,
+            expires: None,
+        };
+
+        let mut max_age = None;
+        for (key, value) in attributes(set_cookie) {
+            match key.to_lowercase().as_str() {
+                "domain" if !value.is_empty() => {
+                    let domain = value.trim_start_matches('.').to_lowercase();
+                    if !domain_matches(&host, &domain) {
+                        return None;
+                    }
+                    cookie.domain = domain;
+                    cookie.host_only = false;
+                }
+                "path" if value.starts_with('/') => cookie.path = value.to_string(),
+                "secure" => cookie.secure = true,
+                "httponly" => cookie.http_only = true,
+                "max-age" => max_age = value.parse::<i64>().ok(),
+                "expires" => {
+                    if let Ok(date) = DateTime::parse_from_rfc2822(value) {
+                        cookie.expires = Some(date.with_timezone(&Utc));
+                    }
+                }
+                _ => {}
+            }
+        }
+        // Max-Age wins over Expires
+        if let Some(seconds) = max_age {
+            cookie.expires = Some(now + chrono::Duration::seconds(seconds));
+        }
+
+        Some(cookie)
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    fn matches(&self, url: &Url, now: DateTime<Utc>) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        let host = host.to_lowercase();
+        let domain_ok = if self.host_only {
+            host == self.domain
+        } else {
+            domain_matches(&host, &self.domain)
+        };
+        domain_ok
+            && path_matches(url.path(), &self.path)
+            && (!self.secure || url.scheme() == "https")
+            && !self.is_expired(now)
+    }
+
+    fn same_identity(&self, other: &Cookie) -> bool {
+        self.name == other.name && self.domain == other.domain && self.path == other.path
+    }
+
+    /// The cookie written back in `Set-Cookie` syntax, used for editing
+    pub fn to_set_cookie(&self) -> String {
+        let mut text = format!("{}={}", self.name, self.value);
+        if self.host_only {
+            text.push_str(&format!("; Domain={}", self.domain));
+        } else {
+            text.push_str(&format!("; Domain=.{}", self.domain));
+        }
+        text.push_str(&format!("; Path={}", self.path));
+        if let Some(expires) = self.expires {
+            text.push_str(&format!(
+                "; Expires={}",
+                expires.format("%a, %d %b %Y %H:%M:%S GMT")
+            ));
+        }
+        if self.secure {
+            text.push_str("; Secure");
+        }
+        if self.http_only {
+            text.push_str("; HttpOnly");
+        }
+        text
+    }
+}
+
+fn attributes(set_cookie: &str) -> impl Iterator<Item = (&str, &str)> {
+    set_cookie.split(';').skip(1).map(|attribute| {
+        let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+        (key.trim(), value.trim())
+    })
+}
+
+fn default_path(request_path: &str) -> String {
+    match request_path.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(index) => request_path[..index].to_string(),
+    }
+}
+
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain || host.ends_with(&format!(".{}", domain))
+}
+
+fn path_matches(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || (request_path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || request_path[cookie_path.len()..].starts_with('/')))
+}
+
+/// Listable, editable cookie storage
+#[derive(Debug, Default)]
+pub struct CookieJar {
+    cookies: Mutex<Vec<Cookie>>,
+}
+
+impl CookieJar {
+    fn lock(&self) -> MutexGuard<'_, Vec<Cookie>> {
+        self.cookies
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Stores a cookie, replacing one with the same name, domain and path
+    ///
+    /// An already expired cookie deletes its stored counterpart instead.
+    pub fn insert(&self, cookie: Cookie) {
+        let mut cookies = self.lock();
+        cookies.retain(|existing| !existing.same_identity(&cookie));
+        if !cookie.is_expired(Utc::now()) {
+            cookies.push(cookie);
+        }
+    }
+
+    pub fn remove(&self, cookie: &Cookie) {
+        self.lock()
+            .retain(|existing| !existing.same_identity(cookie));
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Live cookies sorted by domain, then path and name
+    pub fn list(&self) -> Vec<Cookie> {
+        let now = Utc::now();
+        let mut cookies = self.lock();
+        cookies.retain(|cookie| !cookie.is_expired(now));
+        let mut list = cookies.clone();
+        list.sort_by(|a, b| (&a.domain, &a.path, &a.name).cmp(&(&b.domain, &b.path, &b.name)));
+        list
+    }
+
+    /// The `Cookie` header value for a request to `url`
+    pub fn header_for(&self, url: &Url) -> Option<String> {
+        let now = Utc::now();
+        let mut matching: Vec<Cookie> = self
+            .lock()
+            .iter()
+            .filter(|cookie| cookie.matches(url, now))
+            .cloned()
+            .collect();
+        if matching.is_empty() {
+            return None;
+        }
+        // More specific paths first, as RFC 6265 recommends
+        matching.sort_by_key(|cookie| std::cmp::Reverse(cookie.path.len()));
+        Some(
+            matching
+                .iter()
+                .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+                .collect::<Vec<_>>()
+                .join("; "),
+        )
+    }
+}
+
+impl reqwest::cookie::CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        for header in cookie_headers {
+            if let Some(cookie) = header.to_str().ok().and_then(|h| Cookie::parse(h, url)) {
+                self.insert(cookie);
+            }
+        }
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        self.header_for(url)
+            .and_then(|header| HeaderValue::from_str(&header).ok())
+    }
+}
+
+/// The jar used by the shared HTTP client
+pub fn shared_jar() -> Arc<CookieJar> {
+    static JAR: OnceLock<Arc<CookieJar>> = OnceLock::new();
+    JAR.get_or_init(|| Arc::new(CookieJar::default())).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_set_cookie() {
+        let cookie = Cookie::parse(
+            "sid=abc; Domain=.example.com; Path=/api; Secure; HttpOnly; Max-Age=60",
+            &url("https://www.example.com/login"),
+        )
+        .unwrap();
+        assert_eq!(cookie.name, "sid");
+        assert_eq!(cookie.value, "abc");
+        assert_eq!(cookie.domain, "example.com");
+        assert!(!cookie.host_only);
+        assert_eq!(cookie.path, "/api");
+        assert!(cookie.secure && cookie.http_only);
+        assert!(cookie.expires.is_some());
+
+        let cookie = Cookie::parse("a=1", &url("http://example.com/docs/page")).unwrap();
+        assert!(cookie.host_only);
+        assert_eq!(cookie.path, "/docs");
+
+        // Cookies for unrelated domains are rejected
+        assert!(Cookie::parse("a=1; Domain=other.com", &url("http://example.com/")).is_none());
+        assert!(Cookie::parse("=1", &url("http://example.com/")).is_none());
+    }
+
+    #[test]
+    fn test_jar_matching() {
+        let jar = CookieJar::default();
+        let origin = url("https://example.com/");
+        for header in [
+            "host=1",
+            "wide=2; Domain=example.com",
+            "api=3; Path=/api",
+            "secure=4; Secure",
+        ] {
+            jar.insert(Cookie::parse(header, &origin).unwrap());
+        }
+
+        assert_eq!(
+            jar.header_for(&url("https://example.com/api/users"))
+                .as_deref(),
+            Some("api=3; host=1; wide=2; secure=4")
+        );
+        assert_eq!(
+            jar.header_for(&url("http://sub.example.com/apiary"))
+                .as_deref(),
+            Some("wide=2")
+        );
+        assert_eq!(jar.header_for(&url("https://other.com/")), None);
+    }
+
+    #[test]
+    fn test_jar_replace_and_expire() {
+        let jar = CookieJar::default();
+        let origin = url("http://example.com/");
+        jar.insert(Cookie::parse("a=1", &origin).unwrap());
+        jar.insert(Cookie::parse("a=2", &origin).unwrap());
+        assert_eq!(jar.list().len(), 1);
+        assert_eq!(jar.list()[0].value, "2");
+
+        jar.insert(Cookie::parse("a=gone; Max-Age=0", &origin).unwrap());
+        assert!(jar.list().is_empty());
+    }
+
+    #[test]
+    fn test_manual_cookie_round_trip() {
+        let cookie = Cookie::parse_manual("token=xyz; Domain=api.test; Path=/v1").unwrap();
+        assert!(cookie.host_only);
+        assert_eq!(cookie.domain, "api.test");
+        assert_eq!(cookie.path, "/v1");
+        assert_eq!(Cookie::parse_manual(&cookie.to_set_cookie()), Some(cookie));
+
+        let wide = Cookie::parse_manual("t=1; Domain=.api.test").unwrap();
+        assert!(!wide.host_only);
+        assert_eq!(Cookie::parse_manual(&wide.to_set_cookie()), Some(wide));
+
+        assert!(Cookie::parse_manual("token=xyz").is_none());
+    }
+}
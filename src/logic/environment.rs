@@ -0,0 +1,300 @@
+//! Environments and `{{variable}}` substitution
+//!
+//! Environments are loaded from `environments.json` in the config directory:
+//!
+//! ```json
+//! [
+//!   { "name": "dev", "variables": { "base_url": "http://localhost:8080" }, "secrets": ["api_key"],
+//!     "options": { "client_cert": "~/certs/dev-client.pem" },
+//!     "pins": { "api.dev.example.com": "AB:CD:…" } }
+//! ]
+//! ```
+//!
+//! Variables are substituted into the URL, headers, params, body and auth
+//! fields right before a request is sent. A value can itself be a
+//! `{{secret:op://vault/item/field}}` reference, which is fetched from a
+//! password manager at send time (see [`crate::logic::secrets`]).
+
+use crate::error::{RestlessError, Result};
+use crate::logic::options::RequestOptions;
+use crate::logic::request::Request;
+use crate::logic::tls;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A named set of variables
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct Environment {
+    pub name: String,
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+    /// Variables that must never be shown or exported in clear text
+    #[serde(default)]
+    pub secrets: Vec<String>,
+    /// Connection options for every request sent in this environment
+    #[serde(default)]
+    pub options: RequestOptions,
+    /// Expected SHA-256 certificate fingerprint per host
+    #[serde(default)]
+    pub pins: BTreeMap<String, String>,
+}
+
+impl Environment {
+    #[cfg(test)]
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Environment {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.variables.get(name).map(String::as_str)
+    }
+
+    /// The pinned certificate fingerprint for a host, if any
+    pub fn pin_for(&self, host: &str) -> Option<&str> {
+        self.pins
+            .iter()
+            .find(|(pinned_host, _)| pinned_host.eq_ignore_ascii_case(host))
+            .map(|(_, fingerprint)| fingerprint.as_str())
+    }
+
+    /// Whether a variable is secret, either declared as such or by its name
+    pub fn is_secret(&self, name: &str) -> bool {
+        if self.secrets.iter().any(|s| s == name) {
+            return true;
+        }
+        let name = name.to_lowercase();
+        [
+            "secret", "password", "token", "apikey", "api_key", "private",
+        ]
+        .iter()
+        .any(|marker| name.contains(marker))
+    }
+}
+
+/// Loads environments from a JSON file; a missing file means no environments
+pub fn load_environments(path: &Path) -> Result<Vec<Environment>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(path)?;
+    let invalid = |e: &dyn std::fmt::Display| {
+        RestlessError::configuration(format!(
+            "Invalid environments file {}: {}",
+            path.display(),
+            e
+        ))
+    };
+    let environments: Vec<Environment> = serde_json::from_str(&content).map_err(|e| invalid(&e))?;
+    for env in &environments {
+        env.options.validate().map_err(|e| invalid(&e))?;
+        for pin in env.pins.values() {
+            tls::validate_pin(pin).map_err(|e| invalid(&e))?;
+        }
+    }
+    Ok(environments)
+}
+
+/// Result of substituting variables into a piece of text
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Substitution {
+    pub text: String,
+    /// Variable names referenced by the text, in order of first use
+    pub used: Vec<String>,
+}
+
+/// Replaces `{{name}}` placeholders with values from the environment
+///
+/// Unknown variables are left untouched so the problem stays visible. When
+/// `redact_secrets` is set, secret variables keep their placeholder, which is
+/// what exports use so shared snippets never contain credentials.
+pub fn substitute(text: &str, env: Option<&Environment>, redact_secrets: bool) -> Substitution {
+    let mut result = Substitution::default();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let name = rest[start + 2..start + 2 + end].trim();
+        let placeholder = &rest[start..start + 2 + end + 2];
+        result.text.push_str(&rest[..start]);
+
+        match env.and_then(|env| env.get(name).map(|value| (env, value))) {
+            Some((env, value)) => {
+                if redact_secrets && env.is_secret(name) {
+                    result.text.push_str(placeholder);
+                } else {
+                    result.text.push_str(value);
+                }
+                if !result.used.iter().any(|used| used == name) {
+                    result.used.push(name.to_string());
+                }
+            }
+            None => result.text.push_str(placeholder),
+        }
+        rest = &rest[start + 2 + end + 2..];
+    }
+
+    result.text.push_str(rest);
+    result
+}
+
+/// Produces a copy of the request with all variables substituted
+///
+/// Returns the resolved request and the names of every variable used.
+pub fn resolve_request(
+    request: &Request,
+    env: Option<&Environment>,
+    redact_secrets: bool,
+) -> (Request, Vec<String>) {
+    let mut used = Vec::new();
+    let mut apply = |text: &str| {
+        let substitution = substitute(text, env, redact_secrets);
+        for name in substitution.used {
+            if !used.contains(&name) {
+                used.push(name);
+            }
+        }
+        substitution.text
+    };
+
+    let mut resolved = request.clone();
+    resolved.url = apply(&request.url);
+    resolved.headers = request
+        .headers
+        .iter()
+        .map(|(k, v)| (apply(k), apply(v)))
+        .collect();
+    resolved.params = request
+        .params
+        .iter()
+        .map(|(k, v)| (apply(k), apply(v)))
+        .collect();
+    resolved.body = request.body.as_deref().map(&mut apply);
+    resolved.form = request
+        .form
+        .as_ref()
+        .map(|fields| fields.iter().map(|(k, v)| (apply(k), apply(v))).collect());
+    resolved.auth.fields = request
+        .auth
+        .fields
+        .iter()
+        .map(|(k, v)| (k.clone(), apply(v)))
+        .collect();
+
+    (resolved, used)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_env() -> Environment {
+        let mut env = Environment::new("dev");
+        env.variables
+            .insert("base_url".to_string(), "http://localhost:8080".to_string());
+        env.variables
+            .insert("user".to_string(), "alice".to_string());
+        env.variables
+            .insert("api_key".to_string(), "k-123".to_string());
+        env
+    }
+
+    #[test]
+    fn test_substitute_known_variables() {
+        let env = test_env();
+        let result = substitute("{{base_url}}/users/{{ user }}", Some(&env), false);
+        assert_eq!(result.text, "http://localhost:8080/users/alice");
+        assert_eq!(result.used, vec!["base_url", "user"]);
+    }
+
+    #[test]
+    fn test_substitute_leaves_unknown_and_unclosed() {
+        let env = test_env();
+        let result = substitute("{{missing}}/{{user", Some(&env), false);
+        assert_eq!(result.text, "{{missing}}/{{user");
+        assert!(result.used.is_empty());
+
+        let result = substitute("{{user}}", None, false);
+        assert_eq!(result.text, "{{user}}");
+    }
+
+    #[test]
+    fn test_substitute_redacts_secrets() {
+        let env = test_env();
+        let result = substitute("key={{api_key}}&u={{user}}", Some(&env), true);
+        assert_eq!(result.text, "key={{api_key}}&u=alice");
+        assert_eq!(result.used, vec!["api_key", "user"]);
+    }
+
+    #[test]
+    fn test_is_secret() {
+        let mut env = test_env();
+        assert!(env.is_secret("api_key"));
+        assert!(env.is_secret("AUTH_TOKEN"));
+        assert!(!env.is_secret("user"));
+
+        env.secrets.push("user".to_string());
+        assert!(env.is_secret("user"));
+    }
+
+    #[test]
+    fn test_resolve_request() {
+        let env = test_env();
+        let request = Request {
+            url: "{{base_url}}/items".to_string(),
+            headers: vec![("X-User".to_string(), "{{user}}".to_string())],
+            params: vec![("key".to_string(), "{{api_key}}".to_string())],
+            body: Some("{\"owner\": \"{{user}}\"}".to_string()),
+            ..Default::default()
+        };
+
+        let (resolved, used) = resolve_request(&request, Some(&env), false);
+        assert_eq!(resolved.url, "http://localhost:8080/items");
+        assert_eq!(resolved.headers[0].1, "alice");
+        assert_eq!(resolved.params[0].1, "k-123");
+        assert_eq!(resolved.body.as_deref(), Some("{\"owner\": \"alice\"}"));
+        assert_eq!(used, vec!["base_url", "user", "api_key"]);
+    }
+
+    #[test]
+    fn test_load_environments() {
+        let dir = std::env::temp_dir().join(format!("restless-env-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("environments.json");
+
+        assert!(load_environments(&path).unwrap().is_empty());
+
+        std::fs::write(
+            &path,
+            r#"[{"name": "dev", "variables": {"a": "1"}, "secrets": ["a"]},
+                {"name": "prod", "options": {"client_cert": "prod.p12"},
+                 "pins": {"API.example.com": "{pin}"}}]"#
+                .replace("{pin}", &"ab".repeat(32)),
+        )
+        .unwrap();
+        let envs = load_environments(&path).unwrap();
+        assert_eq!(envs.len(), 2);
+        assert_eq!(envs[0].get("a"), Some("1"));
+        assert!(envs[0].is_secret("a"));
+        assert!(envs[1].variables.is_empty());
+        assert_eq!(envs[1].options.client_cert.as_deref(), Some("prod.p12"));
+        assert_eq!(
+            envs[1].pin_for("api.example.com"),
+            Some("ab".repeat(32).as_str())
+        );
+        assert_eq!(envs[0].pin_for("api.example.com"), None);
+
+        std::fs::write(&path, r#"[{"name": "dev", "pins": {"a.test": "nope"}}]"#).unwrap();
+        assert!(load_environments(&path).is_err());
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(load_environments(&path).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,167 @@
+//! `multipart/form-data` request bodies
+//!
+//! Fields are `(name, value)` pairs like headers. Following curl's `-F`, a
+//! value starting with `@` names a file to upload, so `avatar=@~/me.png`
+//! attaches a file and `note=hello` is a plain text field.
+
+use crate::error::RequestError;
+use crate::paths::{self, expand_home};
+use reqwest::multipart::{Form, Part};
+use std::path::Path;
+
+/// The file path of a field value, if the field uploads a file
+pub fn file_path(value: &str) -> Option<&str> {
+    value.strip_prefix('@').filter(|path| !path.is_empty())
+}
+
+/// Parses `name=value` or `name=@path` as typed into the form editor
+pub fn parse_field(input: &str) -> Result<(String, String), RequestError> {
+    let (name, value) = input
+        .split_once('=')
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .ok_or_else(|| {
+            RequestError::body_serialization("Form fields use name=value or name=@path")
+        })?;
+    if name.is_empty() {
+        return Err(RequestError::body_serialization(
+            "Form field name cannot be empty",
+        ));
+    }
+    Ok((name.to_string(), value.to_string()))
+}
+
+/// Builds the multipart form, reading any files it references
+pub fn build(fields: &[(String, String)]) -> Result<Form, RequestError> {
+    let mut form = Form::new();
+    for (name, value) in fields {
+        form = match file_path(value) {
+            Some(path) => form.part(name.clone(), file_part(path)?),
+            None => form.text(name.clone(), value.clone()),
+        };
+    }
+    Ok(form)
+}
+
+fn file_part(path: &str) -> Result<Part, RequestError> {
+    let full_path = expand_home(path);
+    let data = std::fs::read(&full_path).map_err(|e| {
+        RequestError::body_serialization(format!("Cannot read form file {}: {}", path, e))
+    })?;
+    let file_name = full_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string());
+    Part::bytes(data)
+        .file_name(file_name)
+        .mime_str(mime_type(&full_path))
+        .map_err(|e| RequestError::body_serialization(e.to_string()))
+}
+
+/// Content type for an uploaded file, guessed from its extension
+pub fn mime_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "txt" | "log" => "text/plain",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Completes the path of a `name=@partial` field input
+///
+/// Returns `None` when nothing matches or the input isn't a file field.
+pub fn complete_path(input: &str) -> Option<String> {
+    let (name, partial) = input.split_once("=@")?;
+    Some(format!("{}=@{}", name, paths::complete_path(partial)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_field() {
+        assert_eq!(
+            parse_field("avatar = @me.png").unwrap(),
+            ("avatar".to_string(), "@me.png".to_string())
+        );
+        assert_eq!(file_path("@me.png"), Some("me.png"));
+        assert_eq!(file_path("me.png"), None);
+        assert_eq!(file_path("@"), None);
+        assert!(parse_field("no separator").is_err());
+        assert!(parse_field("=value").is_err());
+    }
+
+    #[test]
+    fn test_mime_type() {
+        assert_eq!(mime_type(Path::new("a/b.JSON")), "application/json");
+        assert_eq!(mime_type(Path::new("photo.jpeg")), "image/jpeg");
+        assert_eq!(mime_type(Path::new("blob")), "application/octet-stream");
+    }
+
+    #[test]
+    fn test_build_reads_files() {
+        let dir = std::env::temp_dir().join(format!("restless-form-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("data.csv");
+        std::fs::write(&file, "a,b\n").unwrap();
+
+        let fields = vec![
+            ("note".to_string(), "hello".to_string()),
+            ("upload".to_string(), format!("@{}", file.display())),
+        ];
+        let form = build(&fields).unwrap();
+        assert!(!form.boundary().is_empty());
+
+        let missing = vec![("upload".to_string(), "@/definitely/not/here".to_string())];
+        let error = build(&missing).unwrap_err().to_string();
+        assert!(error.contains("Cannot read form file"), "{}", error);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_complete_path() {
+        let dir =
+            std::env::temp_dir().join(format!("restless-complete-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("reports")).unwrap();
+        std::fs::write(dir.join("report-2024.csv"), "").unwrap();
+        std::fs::write(dir.join("report-2025.csv"), "").unwrap();
+        std::fs::write(dir.join("photo.png"), "").unwrap();
+        let base = format!("f=@{}/", dir.display());
+
+        assert_eq!(
+            complete_path(&format!("{}ph", base)),
+            Some(format!("{}photo.png", base))
+        );
+        assert_eq!(
+            complete_path(&format!("{}report-", base)),
+            Some(format!("{}report-202", base))
+        );
+        assert_eq!(
+            complete_path(&format!("{}reports", base)),
+            Some(format!("{}reports/", base))
+        );
+        assert_eq!(
+            complete_path(&format!("{}reports/", base)),
+            None,
+            "empty directory"
+        );
+        assert_eq!(complete_path(&format!("{}zzz", base)), None);
+        assert_eq!(complete_path("f=value"), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
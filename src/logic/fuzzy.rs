@@ -0,0 +1,112 @@
+//! Fuzzy matching, for the finder
+//!
+//! Every word of the query has to appear in the text with its letters in
+//! order, though not necessarily next to each other, ignoring case: `usr ls`
+//! matches `users / List users`. Matches on word starts and runs of adjacent
+//! letters rank higher than letters scattered over the text.
+
+/// Score for every matched letter
+const MATCHED: i64 = 1;
+/// Extra for a letter right after the previous matched one
+const ADJACENT: i64 = 5;
+/// Extra for a letter starting a word, like the `l` of `/list`
+const WORD_START: i64 = 8;
+/// Most a gap between two matched letters costs
+const MAX_GAP_PENALTY: i64 = 3;
+
+/// Where and how well a query matched
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Match {
+    pub score: i64,
+    /// Indices of the matched chars of the text, ascending
+    pub positions: Vec<usize>,
+}
+
+/// Matches `query` against `text`; `None` when a word of the query isn't in
+/// it. An empty query matches everything with a score of 0.
+pub fn score(query: &str, text: &str) -> Option<Match> {
+    let text: Vec<char> = text.chars().map(lowercase).collect();
+    let mut result = Match::default();
+    for word in query.split_whitespace() {
+        let word: Vec<char> = word.chars().map(lowercase).collect();
+        let (score, positions) = best_match(&word, &text)?;
+        result.score += score;
+        result.positions.extend(positions);
+    }
+    result.positions.sort_unstable();
+    result.positions.dedup();
+    Some(result)
+}
+
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// The best scoring placement of `word` in `text`, trying every place its
+/// first letter appears and matching the rest as early as possible
+fn best_match(word: &[char], text: &[char]) -> Option<(i64, Vec<usize>)> {
+    let mut best: Option<(i64, Vec<usize>)> = None;
+    for start in (0..text.len()).filter(|&i| text[i] == word[0]) {
+        let Some(positions) = placement(word, text, start) else {
+            // Starting later won't fit the rest either
+            break;
+        };
+        let score = placement_score(&positions, text);
+        if best.as_ref().is_none_or(|(best, _)| score > *best) {
+            best = Some((score, positions));
+        }
+    }
+    best
+}
+
+/// Matches `word` from `start` on, each letter as early as possible
+fn placement(word: &[char], text: &[char], start: usize) -> Option<Vec<usize>> {
+    let mut positions = vec![start];
+    for &letter in &word[1..] {
+        let next = positions[positions.len() - 1] + 1;
+        positions.push((next..text.len()).find(|&i| text[i] == letter)?);
+    }
+    Some(positions)
+}
+
+fn placement_score(positions: &[usize], text: &[char]) -> i64 {
+    let mut score = 0;
+    for (n, &i) in positions.iter().enumerate() {
+        score += MATCHED;
+        if i == 0 || !text[i - 1].is_alphanumeric() {
+            score += WORD_START;
+        }
+        if n > 0 {
+            let gap = (i - positions[n - 1] - 1) as i64;
+            score += if gap == 0 {
+                ADJACENT
+            } else {
+                -gap.min(MAX_GAP_PENALTY)
+            };
+        }
+    }
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        let matched = score("usr LS", "users / List users").unwrap();
+        assert_eq!(matched.positions, vec![0, 1, 3, 8, 10]);
+        assert!(score("usr xyz", "users / List users").is_none());
+        assert!(score("sru", "users").is_none());
+        // The last `h` can't start a match, the others can
+        assert!(score("hlth", "http://localhost/health").is_some());
+        assert_eq!(score("  ", "anything"), Some(Match::default()));
+
+        // Word starts and adjacent letters beat scattered ones
+        let word_start = score("lu", "GET /list/users").unwrap();
+        assert_eq!(word_start.positions, vec![5, 10]);
+        let adjacent = score("user", "GET /users").unwrap().score;
+        let scattered = score("user", "GET /uploads/service").unwrap().score;
+        assert!(adjacent > scattered);
+    }
+}
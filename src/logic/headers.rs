@@ -0,0 +1,165 @@
+//! Request header names for completion
+//!
+//! Names are suggested from the ones already used in open tabs and saved
+//! requests first, then from the well-known headers below. Headers with a
+//! small set of usual values (`Content-Type`) get those offered too.
+//!
+//! Headers carrying credentials are also told apart here, so what's shared
+//! (bug reports, exported profiles) can leave their values out.
+
+/// Headers commonly set on requests
+pub const COMMON: &[&str] = &[
+    "Accept",
+    "Accept-Charset",
+    "Accept-Encoding",
+    "Accept-Language",
+    "Authorization",
+    "Cache-Control",
+    "Connection",
+    "Content-Disposition",
+    "Content-Encoding",
+    "Content-Length",
+    "Content-Type",
+    "Cookie",
+    "DNT",
+    "Expect",
+    "Forwarded",
+    "From",
+    "Host",
+    "If-Match",
+    "If-Modified-Since",
+    "If-None-Match",
+    "If-Range",
+    "If-Unmodified-Since",
+    "Origin",
+    "Pragma",
+    "Prefer",
+    "Proxy-Authorization",
+    "Range",
+    "Referer",
+    "TE",
+    "Upgrade",
+    "User-Agent",
+    "Via",
+    "X-API-Key",
+    "X-Correlation-ID",
+    "X-Forwarded-For",
+    "X-Forwarded-Host",
+    "X-Forwarded-Proto",
+    "X-Request-ID",
+    "X-Requested-With",
+];
+
+/// Media types offered for `Content-Type`
+pub const CONTENT_TYPES: &[&str] = &[
+    "application/json",
+    "application/xml",
+    "application/x-www-form-urlencoded",
+    "text/plain",
+    "multipart/form-data",
+];
+
+/// Most names suggested at once
+pub const MAX_SUGGESTIONS: usize = 8;
+
+/// Headers whose values are always credentials
+const CREDENTIALS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+];
+
+/// Parts of names that give a header away as a credential, like
+/// `X-API-Key` or `X-Auth-Token`
+const CREDENTIAL_PARTS: &[&str] = &["api-key", "api_key", "apikey", "token", "secret"];
+
+/// Whether the header `name` carries a credential, ignoring case
+pub fn is_credential(name: &str) -> bool {
+    let name = name.trim().to_lowercase();
+    CREDENTIALS.contains(&name.as_str()) || CREDENTIAL_PARTS.iter().any(|part| name.contains(part))
+}
+
+/// Header names starting with `prefix`, ignoring case, from `used` and then
+/// [`COMMON`]; each name once and none equal to `prefix`
+pub fn complete<'a>(prefix: &str, used: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let prefix = prefix.trim().to_lowercase();
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+    let mut names: Vec<String> = Vec::new();
+    for name in used.into_iter().chain(COMMON.iter().copied()) {
+        let lower = name.to_lowercase();
+        if lower.starts_with(&prefix)
+            && lower != prefix
+            && !names.iter().any(|known| known.eq_ignore_ascii_case(name))
+        {
+            names.push(name.to_string());
+            if names.len() == MAX_SUGGESTIONS {
+                break;
+            }
+        }
+    }
+    names
+}
+
+/// Usual values of the header `name` starting with `prefix`, ignoring case;
+/// all of them for an empty prefix, none equal to it
+pub fn values(name: &str, prefix: &str) -> Vec<String> {
+    let known: &[&str] = if name.trim().eq_ignore_ascii_case("Content-Type") {
+        CONTENT_TYPES
+    } else {
+        &[]
+    };
+    let prefix = prefix.trim().to_lowercase();
+    known
+        .iter()
+        .filter(|value| value.starts_with(&prefix) && **value != prefix)
+        .map(|value| value.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complete() {
+        assert_eq!(complete("content-t", []), vec!["Content-Type".to_string()]);
+        assert_eq!(
+            complete("X-Req", ["X-Request-Source", "x-request-id"]),
+            vec!["X-Request-Source", "x-request-id", "X-Requested-With"]
+        );
+        assert!(complete("accept", []).iter().all(|name| name != "Accept"));
+        assert_eq!(complete("A", []).len(), 5);
+        assert_eq!(complete("", []), Vec::<String>::new());
+        assert_eq!(complete("X-Nothing", []), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_values() {
+        assert_eq!(values("content-type", "").len(), CONTENT_TYPES.len());
+        assert_eq!(
+            values("Content-Type", "Application/X"),
+            vec!["application/xml", "application/x-www-form-urlencoded"]
+        );
+        assert!(values("Content-Type", "text/plain").is_empty());
+        assert!(values("Accept", "").is_empty());
+    }
+
+    #[test]
+    fn test_is_credential() {
+        for name in [
+            "Authorization",
+            "cookie",
+            "X-API-Key",
+            "apikey",
+            "X-Auth-Token",
+        ] {
+            assert!(is_credential(name), "{}", name);
+        }
+        for name in ["Accept", "Content-Type", "X-Request-ID"] {
+            assert!(!is_credential(name), "{}", name);
+        }
+    }
+}
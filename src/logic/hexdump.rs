@@ -0,0 +1,165 @@
+//! Hex dumps of binary response bodies
+//!
+//! A body counts as binary when its Content-Type says so (images, archives,
+//! `application/octet-stream` and the like) or when its bytes can't be text:
+//! a NUL byte, or invalid UTF-8 with control characters in it. Invalid
+//! UTF-8 without control characters is still read as Latin-1 text, see
+//! [`crate::logic::charset`].
+
+use crate::logic::charset;
+use encoding_rs::Encoding;
+
+/// Bytes shown on each line of the dump
+pub const BYTES_PER_LINE: usize = 16;
+
+/// `application/` types that are never text
+const BINARY_APPLICATION_TYPES: &[&str] = &[
+    "octet-stream",
+    "pdf",
+    "zip",
+    "gzip",
+    "x-gzip",
+    "x-tar",
+    "x-7z-compressed",
+    "x-bzip2",
+    "zstd",
+    "wasm",
+    "protobuf",
+    "x-protobuf",
+    "vnd.google.protobuf",
+    "msgpack",
+    "x-msgpack",
+    "cbor",
+];
+
+/// Whether a body should be shown as a hex dump rather than text
+pub fn is_binary(bytes: &[u8], content_type: Option<&str>) -> bool {
+    if content_type.is_some_and(is_binary_type) {
+        return true;
+    }
+    // Declared text, like UTF-16 with all its NUL bytes
+    if Encoding::for_bom(bytes).is_some()
+        || content_type.and_then(charset::from_content_type).is_some()
+    {
+        return false;
+    }
+    if bytes.contains(&0) {
+        return true;
+    }
+    std::str::from_utf8(bytes).is_err()
+        && bytes
+            .iter()
+            .any(|&b| (b < 0x20 && !b"\t\n\r\x0c\x1b".contains(&b)) || b == 0x7f)
+}
+
+fn is_binary_type(content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    // SVG and the like are XML text
+    if mime.ends_with("+xml") || mime.ends_with("+json") {
+        return false;
+    }
+    match mime.split_once('/') {
+        Some(("image" | "audio" | "video" | "font", _)) => true,
+        Some(("application", subtype)) => BINARY_APPLICATION_TYPES.contains(&subtype),
+        _ => false,
+    }
+}
+
+/// Number of lines the dump of `len` bytes takes
+pub fn line_count(len: usize) -> usize {
+    len.div_ceil(BYTES_PER_LINE)
+}
+
+/// One line of the dump: offset, hex bytes and their printable characters
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Line {
+    pub offset: String,
+    pub hex: String,
+    pub ascii: String,
+}
+
+/// The dump line starting at byte `BYTES_PER_LINE * index`, if any
+pub fn line(bytes: &[u8], index: usize) -> Option<Line> {
+    let start = index.checked_mul(BYTES_PER_LINE)?;
+    if start >= bytes.len() {
+        return None;
+    }
+    let chunk = &bytes[start..bytes.len().min(start + BYTES_PER_LINE)];
+    let mut hex = String::with_capacity(BYTES_PER_LINE * 3 + 1);
+    for position in 0..BYTES_PER_LINE {
+        // A wider gap between the two halves of the line
+        if position == BYTES_PER_LINE / 2 {
+            hex.push(' ');
+        }
+        match chunk.get(position) {
+            Some(byte) => hex.push_str(&format!("{:02x} ", byte)),
+            None => hex.push_str("   "),
+        }
+    }
+    let ascii = chunk
+        .iter()
+        .map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                b as char
+            } else {
+                '.'
+            }
+        })
+        .collect();
+    Some(Line {
+        offset: format!("{:08x}", start),
+        hex,
+        ascii,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_binary() {
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        assert!(is_binary(png, None));
+        assert!(is_binary(b"text", Some("application/octet-stream")));
+        assert!(is_binary(b"GIF89a", Some("image/gif")));
+        assert!(!is_binary(b"<svg/>", Some("image/svg+xml")));
+        assert!(!is_binary(b"{}", Some("application/json")));
+        // Latin-1 text stays text
+        assert!(!is_binary(b"caf\xe9 cr\xe8me\n", Some("text/plain")));
+        assert!(is_binary(b"\x1f\x8b\x08\x00\xff", Some("application/json")));
+        assert!(is_binary(b"a\0b", None));
+        assert!(!is_binary(b"\xff\xfeh\x00i\x00", None));
+        assert!(!is_binary(
+            b"h\x00i\x00",
+            Some("text/plain; charset=utf-16le")
+        ));
+    }
+
+    #[test]
+    fn test_dump_lines() {
+        let bytes: Vec<u8> = (0x41..0x41 + 18).chain([0x00, 0xff]).collect();
+        assert_eq!(line_count(bytes.len()), 2);
+        assert_eq!(line_count(0), 0);
+        assert_eq!(
+            line(&bytes, 0).unwrap(),
+            Line {
+                offset: "00000000".to_string(),
+                hex: "41 42 43 44 45 46 47 48  49 4a 4b 4c 4d 4e 4f 50 ".to_string(),
+                ascii: "ABCDEFGHIJKLMNOP".to_string(),
+            }
+        );
+        let last = line(&bytes, 1).unwrap();
+        assert_eq!(last.offset, "00000010");
+        assert_eq!(last.hex.trim_end(), "51 52 00 ff");
+        assert_eq!(last.hex.len(), BYTES_PER_LINE * 3 + 1);
+        assert_eq!(last.ascii, "QR..");
+        assert_eq!(line(&bytes, 2), None);
+        assert_eq!(line(&[], 0), None);
+    }
+}
@@ -0,0 +1,264 @@
+//! Syntax highlighting of response bodies
+//!
+//! The tokenizers work a line at a time, which is all the renderer needs:
+//! JSON strings can't contain raw line breaks, so no token spans lines, and
+//! indented XML has its tags on one line each. Anything they don't
+//! recognize comes back as [`Token::Other`] rather than an error, so
+//! truncated or not-quite-valid bodies still render.
+
+/// What a piece of a JSON or XML line is
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Token {
+    /// An object key, including its quotes
+    Key,
+    String,
+    Number,
+    /// `true`, `false` or `null`
+    Literal,
+    /// Braces, brackets, colons and commas; the `<`, `>`, `/` and `=` of
+    /// XML tags
+    Punctuation,
+    /// An XML element name
+    Tag,
+    /// An XML attribute name
+    Attribute,
+    /// An XML comment
+    Comment,
+    /// Whitespace and anything unrecognized
+    Other,
+}
+
+/// Splits a line of JSON into tokens; the pieces add up to the whole line
+pub fn json_line(line: &str) -> Vec<(Token, &str)> {
+    let bytes = line.as_bytes();
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    while start < bytes.len() {
+        let (token, end) = match bytes[start] {
+            b'"' => {
+                let end = string_end(bytes, start);
+                // A string followed by a colon is a key
+                let rest = line[end..].trim_start();
+                let token = if rest.starts_with(':') {
+                    Token::Key
+                } else {
+                    Token::String
+                };
+                (token, end)
+            }
+            b'{' | b'}' | b'[' | b']' | b':' | b',' => (Token::Punctuation, start + 1),
+            b'-' | b'0'..=b'9' => (
+                Token::Number,
+                scan(bytes, start, |b| {
+                    b.is_ascii_digit() || matches!(b, b'-' | b'+' | b'.' | b'e' | b'E')
+                }),
+            ),
+            b if b.is_ascii_alphabetic() => {
+                let end = scan(bytes, start, |b| b.is_ascii_alphabetic());
+                let token = match &line[start..end] {
+                    "true" | "false" | "null" => Token::Literal,
+                    _ => Token::Other,
+                };
+                (token, end)
+            }
+            _ => (
+                Token::Other,
+                scan(bytes, start, |b| {
+                    !b.is_ascii_alphanumeric() && !b"\"{}[]:,-".contains(&b)
+                }),
+            ),
+        };
+        tokens.push((token, &line[start..end]));
+        start = end;
+    }
+    tokens
+}
+
+/// Splits a line of XML into tokens; the pieces add up to the whole line
+///
+/// Attribute values come back as [`Token::String`] and text between tags as
+/// [`Token::Other`].
+pub fn xml_line(line: &str) -> Vec<(Token, &str)> {
+    let bytes = line.as_bytes();
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut in_tag = false;
+    // The first name in a tag is the element, the rest are attributes
+    let mut expect_name = false;
+    while start < bytes.len() {
+        let rest = &line[start..];
+        let (token, end) = if rest.starts_with("<!--") {
+            let end = rest.find("-->").map_or(line.len(), |end| start + end + 3);
+            (Token::Comment, end)
+        } else if !in_tag {
+            match rest.find('<') {
+                Some(0) => {
+                    in_tag = true;
+                    expect_name = true;
+                    let end = scan(bytes, start, |b| matches!(b, b'<' | b'/' | b'?' | b'!'));
+                    (Token::Punctuation, end)
+                }
+                Some(next) => (Token::Other, start + next),
+                None => (Token::Other, line.len()),
+            }
+        } else {
+            let name = std::mem::take(&mut expect_name);
+            match bytes[start] {
+                b'>' => {
+                    in_tag = false;
+                    (Token::Punctuation, start + 1)
+                }
+                b'/' | b'?' | b'=' => (Token::Punctuation, start + 1),
+                quote @ (b'"' | b'\'') => {
+                    let end = bytes[start + 1..]
+                        .iter()
+                        .position(|&b| b == quote)
+                        .map_or(bytes.len(), |offset| start + offset + 2);
+                    (Token::String, end)
+                }
+                b if is_name_byte(b) => {
+                    let token = if name { Token::Tag } else { Token::Attribute };
+                    (token, scan(bytes, start, is_name_byte))
+                }
+                _ => (
+                    Token::Other,
+                    scan(bytes, start, |b| {
+                        !is_name_byte(b) && !b"<>/?=\"'".contains(&b)
+                    }),
+                ),
+            }
+        };
+        tokens.push((token, &line[start..end]));
+        start = end;
+    }
+    tokens
+}
+
+/// Bytes of element and attribute names, namespace prefixes included
+fn is_name_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b':' | b'_' | b'-' | b'.') || !b.is_ascii()
+}
+
+/// Index just past the closing quote of the string starting at `start`, or
+/// the end of the line for an unterminated one
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let mut index = start + 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            b'"' => return index + 1,
+            _ => index += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Index of the first byte from `start` on that doesn't match
+fn scan(bytes: &[u8], start: usize, matches: impl Fn(u8) -> bool) -> usize {
+    bytes[start..]
+        .iter()
+        .position(|&b| !matches(b))
+        .map_or(bytes.len(), |offset| start + offset)
+        .max(start + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_line_tokens() {
+        assert_eq!(
+            json_line(r#"  "name": "Ada \"the\" first","#),
+            vec![
+                (Token::Other, "  "),
+                (Token::Key, r#""name""#),
+                (Token::Punctuation, ":"),
+                (Token::Other, " "),
+                (Token::String, r#""Ada \"the\" first""#),
+                (Token::Punctuation, ","),
+            ]
+        );
+        assert_eq!(
+            json_line(r#"[-1.5e3, true, null, {}]"#),
+            vec![
+                (Token::Punctuation, "["),
+                (Token::Number, "-1.5e3"),
+                (Token::Punctuation, ","),
+                (Token::Other, " "),
+                (Token::Literal, "true"),
+                (Token::Punctuation, ","),
+                (Token::Other, " "),
+                (Token::Literal, "null"),
+                (Token::Punctuation, ","),
+                (Token::Other, " "),
+                (Token::Punctuation, "{"),
+                (Token::Punctuation, "}"),
+                (Token::Punctuation, "]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_xml_line_tokens() {
+        assert_eq!(
+            xml_line(r#"  <ns:item id="a>1" x='2'>text</ns:item>"#),
+            vec![
+                (Token::Other, "  "),
+                (Token::Punctuation, "<"),
+                (Token::Tag, "ns:item"),
+                (Token::Other, " "),
+                (Token::Attribute, "id"),
+                (Token::Punctuation, "="),
+                (Token::String, r#""a>1""#),
+                (Token::Other, " "),
+                (Token::Attribute, "x"),
+                (Token::Punctuation, "="),
+                (Token::String, "'2'"),
+                (Token::Punctuation, ">"),
+                (Token::Other, "text"),
+                (Token::Punctuation, "</"),
+                (Token::Tag, "ns:item"),
+                (Token::Punctuation, ">"),
+            ]
+        );
+        assert_eq!(
+            xml_line("<?xml version=\"1.0\"?><!-- hi --><br/>"),
+            vec![
+                (Token::Punctuation, "<?"),
+                (Token::Tag, "xml"),
+                (Token::Other, " "),
+                (Token::Attribute, "version"),
+                (Token::Punctuation, "="),
+                (Token::String, "\"1.0\""),
+                (Token::Punctuation, "?"),
+                (Token::Punctuation, ">"),
+                (Token::Comment, "<!-- hi -->"),
+                (Token::Punctuation, "<"),
+                (Token::Tag, "br"),
+                (Token::Punctuation, "/"),
+                (Token::Punctuation, ">"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_line_is_lossless() {
+        for line in [
+            r#"  "unterminated: "#,
+            "not json at all — ünïcode",
+            r#"{"é": "ß", "k" : 1}"#,
+            "",
+            "<a attr=\"open <",
+            "<",
+            "< >",
+            "<!-- unterminated",
+        ] {
+            let joined: String = json_line(line).iter().map(|(_, text)| *text).collect();
+            assert_eq!(joined, line);
+            let joined: String = xml_line(line).iter().map(|(_, text)| *text).collect();
+            assert_eq!(joined, line);
+        }
+        assert_eq!(json_line("nope")[0], (Token::Other, "nope"));
+    }
+}
@@ -0,0 +1,400 @@
+//! Reading HTML responses as text
+//!
+//! A deliberately small renderer for error pages and documentation: block
+//! elements start new lines, list items get bullets, headings are marked
+//! with `#`, links show their target, and scripts, styles and comments are
+//! dropped. Whitespace collapses as in a browser except inside `<pre>`.
+//! There's no layout beyond that, so tables come out one cell per line.
+
+/// Elements that start and end on their own lines
+const BLOCKS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "html",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "td",
+    "th",
+    "title",
+    "tr",
+    "ul",
+];
+
+/// Elements whose content is never shown
+const HIDDEN: &[&str] = &["script", "style", "head", "noscript", "template"];
+
+/// Renders an HTML document as plain text
+pub fn to_text(html: &str) -> String {
+    let mut out = Renderer::default();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        out.text(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(end) = tag_end(rest) else {
+            out.text(rest);
+            rest = "";
+            break;
+        };
+        let tag = &rest[1..end - 1];
+        rest = &rest[end..];
+        let (closing, name) = match tag.strip_prefix('/') {
+            Some(name) => (true, tag_name(name)),
+            None => (false, tag_name(tag)),
+        };
+        // Skip over hidden elements, the <head> with its <title> included
+        if !closing && HIDDEN.contains(&name.as_str()) && !tag.ends_with('/') {
+            rest = skip_element(rest, &name);
+            continue;
+        }
+        out.tag(&name, tag, closing);
+    }
+    out.text(rest);
+    out.finish()
+}
+
+#[derive(Default)]
+struct Renderer {
+    lines: Vec<String>,
+    line: String,
+    /// Nesting depth of `<pre>` elements
+    pre: usize,
+    /// Targets of the links currently open
+    links: Vec<Option<String>>,
+    /// Ordered lists open, with the number of the next item
+    lists: Vec<Option<usize>>,
+}
+
+impl Renderer {
+    fn text(&mut self, text: &str) {
+        let text = decode_entities(text);
+        if self.pre > 0 {
+            let mut pieces = text.split('\n');
+            if let Some(first) = pieces.next() {
+                self.line.push_str(first);
+            }
+            for piece in pieces {
+                self.break_line();
+                self.line.push_str(piece);
+            }
+            return;
+        }
+        // Only ASCII whitespace collapses, so &nbsp; stays put
+        for (index, word) in text.split_ascii_whitespace().enumerate() {
+            let starts_with_space =
+                index > 0 || text.starts_with(|c: char| c.is_ascii_whitespace());
+            if starts_with_space && !self.line.is_empty() && !self.line.ends_with(' ') {
+                self.line.push(' ');
+            }
+            self.line.push_str(word);
+        }
+        if text.ends_with(|c: char| c.is_ascii_whitespace()) && !self.line.is_empty() {
+            self.line.push(' ');
+        }
+    }
+
+    fn tag(&mut self, name: &str, tag: &str, closing: bool) {
+        match (name, closing) {
+            ("br", _) => self.break_line(),
+            ("hr", _) => {
+                self.paragraph();
+                self.lines.push("─".repeat(40));
+                self.paragraph();
+            }
+            ("pre", false) => {
+                self.paragraph();
+                self.pre += 1;
+            }
+            ("pre", true) => {
+                self.pre = self.pre.saturating_sub(1);
+                self.paragraph();
+            }
+            ("ul" | "ol", false) => {
+                // Only the outermost list is set apart from the text around it
+                if self.lists.is_empty() {
+                    self.paragraph();
+                } else {
+                    self.block();
+                }
+                self.lists.push((name == "ol").then_some(1));
+            }
+            ("ul" | "ol", true) => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.paragraph();
+                } else {
+                    self.block();
+                }
+            }
+            ("p" | "blockquote" | "table", _) => self.paragraph(),
+            ("li", false) => {
+                self.block();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "• ".to_string(),
+                };
+                self.line = format!("{}{}", indent, marker);
+            }
+            ("a", false) => self.links.push(attribute(tag, "href")),
+            ("a", true) => {
+                if let Some(Some(href)) = self.links.pop() {
+                    if !href.starts_with('#') && !self.line.trim_end().ends_with(&href) {
+                        let line = self.line.trim_end().to_string();
+                        self.line = format!("{} <{}>", line, href);
+                    }
+                }
+            }
+            ("img", _) => {
+                if let Some(alt) = attribute(tag, "alt").filter(|alt| !alt.is_empty()) {
+                    self.text(&format!("[{}]", alt));
+                }
+            }
+            (heading, false) if is_heading(heading) => {
+                self.paragraph();
+                let level = heading[1..].parse().unwrap_or(1);
+                self.line = format!("{} ", "#".repeat(level));
+            }
+            (heading, true) if is_heading(heading) => self.paragraph(),
+            (name, _) if BLOCKS.contains(&name) => self.block(),
+            _ => {}
+        }
+    }
+
+    /// Ends the current line
+    fn break_line(&mut self) {
+        let line = std::mem::take(&mut self.line);
+        self.lines.push(line.trim_end().to_string());
+    }
+
+    /// Ends the current line, if it has text, for a block boundary
+    fn block(&mut self) {
+        if !self.line.trim().is_empty() {
+            self.break_line();
+        } else {
+            self.line.clear();
+        }
+    }
+
+    /// Ends the current line and leaves a blank one, for paragraph-like
+    /// elements
+    fn paragraph(&mut self) {
+        self.block();
+        self.lines.push(String::new());
+    }
+
+    fn finish(mut self) -> String {
+        self.block();
+        // At most one blank line in a row, none at the ends
+        let mut text = String::new();
+        let mut blank = false;
+        for line in self.lines {
+            if line.trim().is_empty() {
+                blank = !text.is_empty();
+                continue;
+            }
+            if blank {
+                text.push('\n');
+                blank = false;
+            }
+            text.push_str(&line);
+            text.push('\n');
+        }
+        text.truncate(text.trim_end().len());
+        text
+    }
+}
+
+fn is_heading(name: &str) -> bool {
+    matches!(name, "h1" | "h2" | "h3" | "h4" | "h5" | "h6")
+}
+
+/// Length of the tag at the start of `text`, quoted `>` included
+fn tag_end(text: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, c) in text.char_indices().skip(1) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn tag_name(tag: &str) -> String {
+    tag.split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Rest of the document after the closing tag of a hidden element
+fn skip_element<'a>(rest: &'a str, name: &str) -> &'a str {
+    let lower = rest.to_ascii_lowercase();
+    match lower.find(&format!("</{}", name)) {
+        Some(start) => {
+            let after = &rest[start..];
+            after.find('>').map_or("", |end| &after[end + 1..])
+        }
+        None => "",
+    }
+}
+
+/// Value of an attribute, quoted or not
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search = 0;
+    while let Some(found) = lower[search..].find(name) {
+        let start = search + found;
+        search = start + name.len();
+        let preceded = lower[..start].ends_with(char::is_whitespace);
+        let rest = tag[search..].trim_start();
+        let (true, Some(value)) = (preceded, rest.strip_prefix('=')) else {
+            continue;
+        };
+        let value = value.trim_start();
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+            _ => value.split(char::is_whitespace).next().unwrap_or_default(),
+        };
+        return Some(decode_entities(value));
+    }
+    None
+}
+
+/// Replaces the common named entities and all numeric ones
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                "copy" => Some('©'),
+                "hellip" => Some('…'),
+                "mdash" => Some('—'),
+                "ndash" => Some('–'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|n| n.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_page() {
+        let html = r##"<!DOCTYPE html>
+<html><head><title>502 Bad Gateway</title>
+<style>body { color: red }</style></head>
+<body>
+  <h1>Bad   Gateway</h1>
+  <!-- upstream: 10.0.0.7 -->
+  <p>The server got an <b>invalid</b> response &amp; gave up.<br>Try again&nbsp;later.</p>
+  <script>alert("<p>no</p>")</script>
+  <hr>
+  <p>See <a href="https://status.example.com">the status page</a> or <a href="#top">top</a>.</p>
+</body></html>"##;
+        assert_eq!(
+            to_text(html),
+            [
+                "# Bad Gateway",
+                "",
+                "The server got an invalid response & gave up.",
+                "Try again\u{a0}later.",
+                "",
+                &"─".repeat(40),
+                "",
+                "See the status page <https://status.example.com> or top.",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_lists_and_pre() {
+        let html = "<ul><li>one</li><li>two<ol><li>a</li><li>b</li></ol></li></ul>\
+                    <pre>  let x = 1;\n  x &lt; 2</pre><p>after <img src=x alt='logo'></p>";
+        assert_eq!(
+            to_text(html),
+            "• one\n• two\n  1. a\n  2. b\n\n  let x = 1;\n  x < 2\n\nafter [logo]"
+        );
+    }
+
+    #[test]
+    fn test_entities_and_broken_markup() {
+        assert_eq!(
+            decode_entities("&#65;&#x42;&unknown; & &lt"),
+            "AB&unknown; & &lt"
+        );
+        assert_eq!(to_text("plain <b>text"), "plain text");
+        assert_eq!(to_text("a < b"), "a < b");
+        assert_eq!(
+            attribute(r#"a class="x" href='/y'"#, "href").as_deref(),
+            Some("/y")
+        );
+        assert_eq!(attribute("a data-href=1", "href"), None);
+    }
+}
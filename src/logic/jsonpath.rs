@@ -0,0 +1,399 @@
+//! Filtering JSON responses with JSONPath
+//!
+//! Supports the commonly used part of JSONPath, plus jq's leading-dot
+//! spelling so `.items[0].sku` works as well as `$.items[0].sku`:
+//!
+//! | Syntax | Selects |
+//! |--------|---------|
+//! | `.key`, `['key']` | An object member |
+//! | `[2]`, `[-1]` | An array element, counting from the end when negative |
+//! | `[1:3]` | A slice of an array |
+//! | `.*`, `[*]`, `[]` | Every member or element |
+//! | `..key` | `key` at any depth |
+//! | `[?(@.price < 10)]` | Elements whose value passes a comparison |
+//! | `[?(@.isbn)]` | Elements that have a value there |
+//!
+//! One match is shown as it is; several are shown as an array.
+
+use crate::error::{RestlessError, Result};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Step {
+    Child(String),
+    Index(i64),
+    Slice(Option<i64>, Option<i64>),
+    Wildcard,
+    /// The current nodes and everything below them, for `..`
+    Descendants,
+    Filter(Condition),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Condition {
+    path: Vec<Step>,
+    /// Comparison and literal; `None` only tests that the path exists
+    comparison: Option<(Operator, Value)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+/// Applies an expression to a JSON body, returning the matches as pretty
+/// printed JSON
+pub fn filter(body: &str, expression: &str) -> Result<String> {
+    let steps = parse(expression)?;
+    let json: Value = serde_json::from_str(body.trim())
+        .map_err(|_| RestlessError::response_parsing("The filter needs a JSON response"))?;
+    let mut matches = select(&json, &steps);
+    let result = match matches.len() {
+        0 => {
+            return Err(RestlessError::response_parsing(format!(
+                "Nothing matches {}",
+                expression.trim()
+            )))
+        }
+        1 => matches.remove(0).clone(),
+        _ => Value::Array(matches.into_iter().cloned().collect()),
+    };
+    Ok(serde_json::to_string_pretty(&result)?)
+}
+
+/// Checks that an expression is valid without applying it
+pub fn validate(expression: &str) -> Result<()> {
+    parse(expression).map(|_| ())
+}
+
+fn invalid(expression: &str, reason: &str) -> RestlessError {
+    RestlessError::configuration(format!("Invalid filter {}: {}", expression.trim(), reason))
+}
+
+fn parse(expression: &str) -> Result<Vec<Step>> {
+    let trimmed = expression.trim();
+    let rest = trimmed.strip_prefix(['$', '@']).unwrap_or(trimmed);
+    if rest.len() == trimmed.len() && !rest.starts_with(['.', '[']) {
+        return Err(invalid(expression, "start with $ or ."));
+    }
+    parse_steps(rest).map_err(|reason| invalid(expression, &reason))
+}
+
+fn parse_steps(mut rest: &str) -> std::result::Result<Vec<Step>, String> {
+    let mut steps = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("..") {
+            steps.push(Step::Descendants);
+            rest = after;
+            if !rest.starts_with('[') {
+                let (name, after) = split_name(rest);
+                if name.is_empty() {
+                    return Err("expected a name after ..".to_string());
+                }
+                steps.push(name_step(name));
+                rest = after;
+            }
+        } else if let Some(after) = rest.strip_prefix('.') {
+            // jq spells iteration and indexing `.[]` and `.[0]`
+            if after.starts_with('[') || after.is_empty() {
+                rest = after;
+                continue;
+            }
+            let (name, after) = split_name(after);
+            if name.is_empty() {
+                return Err(format!("expected a name at {}", after));
+            }
+            steps.push(name_step(name));
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = closing_bracket(after).ok_or("unclosed [")?;
+            steps.push(bracket_step(after[..end].trim())?);
+            rest = &after[end + 1..];
+        } else {
+            return Err(format!("unexpected {}", rest));
+        }
+    }
+    Ok(steps)
+}
+
+/// A member name up to the next `.` or `[`
+fn split_name(rest: &str) -> (&str, &str) {
+    let end = rest.find(['.', '[']).unwrap_or(rest.len());
+    (rest[..end].trim(), &rest[end..])
+}
+
+fn name_step(name: &str) -> Step {
+    if name == "*" {
+        Step::Wildcard
+    } else {
+        Step::Child(name.to_string())
+    }
+}
+
+/// Index of the `]` closing a bracket, skipping quoted text and nested
+/// brackets inside filters
+fn closing_bracket(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    for (index, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '[') => depth += 1,
+            (None, ']') if depth == 0 => return Some(index),
+            (None, ']') => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn bracket_step(inside: &str) -> std::result::Result<Step, String> {
+    if inside.is_empty() || inside == "*" {
+        return Ok(Step::Wildcard);
+    }
+    if let Some(condition) = inside.strip_prefix('?') {
+        let condition = condition.trim();
+        let condition = condition
+            .strip_prefix('(')
+            .and_then(|c| c.strip_suffix(')'))
+            .unwrap_or(condition);
+        return parse_condition(condition.trim()).map(Step::Filter);
+    }
+    if let Some(name) = unquote(inside) {
+        return Ok(Step::Child(name.to_string()));
+    }
+    if let Some((start, end)) = inside.split_once(':') {
+        let bound = |text: &str| -> std::result::Result<Option<i64>, String> {
+            let text = text.trim();
+            if text.is_empty() {
+                return Ok(None);
+            }
+            text.parse()
+                .map(Some)
+                .map_err(|_| format!("bad slice bound {}", text))
+        };
+        return Ok(Step::Slice(bound(start)?, bound(end)?));
+    }
+    inside
+        .parse()
+        .map(Step::Index)
+        .map_err(|_| format!("bad index [{}]", inside))
+}
+
+fn unquote(text: &str) -> Option<&str> {
+    ['\'', '"'].iter().find_map(|&quote| {
+        text.strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+    })
+}
+
+fn parse_condition(condition: &str) -> std::result::Result<Condition, String> {
+    const OPERATORS: &[(&str, Operator)] = &[
+        ("==", Operator::Equal),
+        ("!=", Operator::NotEqual),
+        ("<=", Operator::LessOrEqual),
+        (">=", Operator::GreaterOrEqual),
+        ("<", Operator::Less),
+        (">", Operator::Greater),
+    ];
+    let Some(relative) = condition.strip_prefix('@') else {
+        return Err(format!("filters start with @, got {}", condition));
+    };
+    let found = OPERATORS
+        .iter()
+        .filter_map(|&(symbol, operator)| {
+            relative.find(symbol).map(|index| (index, symbol, operator))
+        })
+        .min_by_key(|&(index, symbol, _)| (index, std::cmp::Reverse(symbol.len())));
+    let Some((index, symbol, operator)) = found else {
+        return Ok(Condition {
+            path: parse_steps(relative.trim())?,
+            comparison: None,
+        });
+    };
+    let literal = relative[index + symbol.len()..].trim();
+    let value = match unquote(literal) {
+        Some(text) if literal.starts_with('\'') => Value::String(text.to_string()),
+        _ => serde_json::from_str(literal).map_err(|_| format!("bad value {}", literal))?,
+    };
+    Ok(Condition {
+        path: parse_steps(relative[..index].trim())?,
+        comparison: Some((operator, value)),
+    })
+}
+
+fn select<'a>(root: &'a Value, steps: &[Step]) -> Vec<&'a Value> {
+    let mut nodes = vec![root];
+    for step in steps {
+        nodes = nodes
+            .into_iter()
+            .flat_map(|node| apply(node, step))
+            .collect();
+    }
+    nodes
+}
+
+fn apply<'a>(node: &'a Value, step: &Step) -> Vec<&'a Value> {
+    match (step, node) {
+        (Step::Child(name), Value::Object(fields)) => fields.get(name).into_iter().collect(),
+        (Step::Index(index), Value::Array(items)) => {
+            let index = if *index < 0 {
+                items.len() as i64 + index
+            } else {
+                *index
+            };
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| items.get(index))
+                .into_iter()
+                .collect()
+        }
+        (Step::Slice(start, end), Value::Array(items)) => {
+            let len = items.len() as i64;
+            let clamp = |bound: i64| {
+                let bound = if bound < 0 { len + bound } else { bound };
+                bound.clamp(0, len) as usize
+            };
+            let start = clamp(start.unwrap_or(0));
+            let end = clamp(end.unwrap_or(len));
+            items
+                .get(start..end.max(start))
+                .unwrap_or_default()
+                .iter()
+                .collect()
+        }
+        (Step::Wildcard, _) => children(node),
+        (Step::Descendants, _) => {
+            let mut nodes = vec![node];
+            let mut index = 0;
+            while index < nodes.len() {
+                nodes.extend(children(nodes[index]));
+                index += 1;
+            }
+            nodes
+        }
+        (Step::Filter(condition), _) => children(node)
+            .into_iter()
+            .filter(|child| matches(child, condition))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn children(node: &Value) -> Vec<&Value> {
+    match node {
+        Value::Object(fields) => fields.values().collect(),
+        Value::Array(items) => items.iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn matches(node: &Value, condition: &Condition) -> bool {
+    let values = select(node, &condition.path);
+    let Some((operator, literal)) = &condition.comparison else {
+        return !values.is_empty();
+    };
+    values.into_iter().any(|value| {
+        let ordering = match (value, literal) {
+            (Value::Number(a), Value::Number(b)) => a
+                .as_f64()
+                .partial_cmp(&b.as_f64())
+                .unwrap_or(Ordering::Equal),
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            _ => {
+                return match operator {
+                    Operator::Equal => value == literal,
+                    Operator::NotEqual => value != literal,
+                    _ => false,
+                }
+            }
+        };
+        match operator {
+            Operator::Equal => ordering == Ordering::Equal,
+            Operator::NotEqual => ordering != Ordering::Equal,
+            Operator::Less => ordering == Ordering::Less,
+            Operator::LessOrEqual => ordering != Ordering::Greater,
+            Operator::Greater => ordering == Ordering::Greater,
+            Operator::GreaterOrEqual => ordering != Ordering::Less,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STORE: &str = r#"{"store": {
+        "book": [
+            {"title": "Sayings", "price": 8.95, "tags": ["old"]},
+            {"title": "Sword", "price": 12.99, "isbn": "0-553"},
+            {"title": "Moby Dick", "price": 8.99, "isbn": "0-395"}
+        ],
+        "bicycle": {"color": "red", "price": 19.95},
+        "a.b": 1
+    }}"#;
+
+    fn compact(expression: &str) -> String {
+        let pretty = filter(STORE, expression).unwrap();
+        serde_json::from_str::<Value>(&pretty).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_paths() {
+        assert_eq!(compact("$.store.bicycle.color"), r#""red""#);
+        assert_eq!(compact(".store.book[0].title"), r#""Sayings""#);
+        assert_eq!(compact("$['store']['a.b']"), "1");
+        assert_eq!(compact("$.store.book[-1].title"), r#""Moby Dick""#);
+        assert_eq!(
+            compact("$.store.book[1:].title"),
+            r#"["Sword","Moby Dick"]"#
+        );
+        assert_eq!(compact(".store.book[].price"), "[8.95,12.99,8.99]");
+        assert_eq!(compact("$.store.book[*].tags[0]"), r#""old""#);
+        assert_eq!(compact("$..price"), "[19.95,8.95,12.99,8.99]");
+        assert_eq!(compact("$.store.bicycle.*"), r#"["red",19.95]"#);
+        assert_eq!(compact("$").len(), compact(".").len());
+    }
+
+    #[test]
+    fn test_filters() {
+        assert_eq!(
+            compact("$.store.book[?(@.price < 10)].title"),
+            r#"["Sayings","Moby Dick"]"#
+        );
+        assert_eq!(
+            compact("$.store.book[?(@.isbn)].isbn"),
+            r#"["0-553","0-395"]"#
+        );
+        assert_eq!(compact("$..book[?(@.title == 'Sword')].price"), "12.99");
+        assert_eq!(
+            compact(r#"$..book[?(@.title != "Sword")].price"#),
+            "[8.95,8.99]"
+        );
+        assert_eq!(compact("$..book[?(@.price >= 12.99)].title"), r#""Sword""#);
+        assert_eq!(
+            compact("$..book[?(@.tags[0] == 'old')].title"),
+            r#""Sayings""#
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(validate("store.book").is_err());
+        assert!(validate("$.store[").is_err());
+        assert!(validate("$.store[abc]").is_err());
+        assert!(validate("$..").is_err());
+        assert!(validate("$[?(@.a == nope)]").is_err());
+        assert!(validate("$[?(price > 1)]").is_err());
+        assert!(filter(STORE, "$.missing").is_err());
+        assert!(filter("not json", "$").is_err());
+    }
+}
@@ -0,0 +1,330 @@
+//! Lint rules checked before a request is sent
+//!
+//! The rules of a workspace live in `restless-lint.toml`, looked up from the
+//! working directory upwards so a project can keep them next to its code:
+//!
+//! ```toml
+//! require_accept = "warn"
+//! no_plain_http = "error"
+//! no_secret_params = "error"
+//! require_idempotency_key = "off"
+//!
+//! prod_environments = ["prod", "production"]
+//! secret_params = ["token", "secret", "password", "api_key", "apikey"]
+//! idempotency_header = "Idempotency-Key"
+//! ```
+//!
+//! Each rule is `off`, `warn` or `error`. Errors stop the send, warnings are
+//! shown with the response. Without the file nothing is checked.
+
+use crate::error::{RestlessError, Result};
+use crate::logic::environment::{self, Environment};
+use crate::logic::request::Request;
+use crate::logic::secrets;
+use reqwest::Method;
+use serde::Deserialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Name of the rules file
+pub const FILE_NAME: &str = "restless-lint.toml";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Off,
+    Warn,
+    Error,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Level::Off => "off",
+            Level::Warn => "warning",
+            Level::Error => "error",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Rules {
+    /// Every request names the content it accepts
+    pub require_accept: Level,
+    /// No `http://` URLs in the production environments
+    pub no_plain_http: Level,
+    /// No secrets in the query string, where logs and proxies keep them
+    pub no_secret_params: Level,
+    /// Every POST carries an idempotency key, so retries are safe
+    pub require_idempotency_key: Level,
+    pub prod_environments: Vec<String>,
+    /// Query parameter names containing any of these count as secret
+    pub secret_params: Vec<String>,
+    pub idempotency_header: String,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            require_accept: Level::Warn,
+            no_plain_http: Level::Error,
+            no_secret_params: Level::Error,
+            require_idempotency_key: Level::Off,
+            prod_environments: vec!["prod".to_string(), "production".to_string()],
+            secret_params: ["token", "secret", "password", "api_key", "apikey"]
+                .map(str::to_string)
+                .to_vec(),
+            idempotency_header: "Idempotency-Key".to_string(),
+        }
+    }
+}
+
+/// One broken rule
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding {
+    pub rule: &'static str,
+    pub level: Level,
+    pub message: String,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]: {}", self.level, self.rule, self.message)
+    }
+}
+
+impl Rules {
+    /// Checks a request as saved, before variables are filled in, for
+    /// sending in `env`
+    pub fn check(&self, request: &Request, env: Option<&Environment>) -> Vec<Finding> {
+        let mut findings = Vec::new();
+        let mut report = |rule, level, message: String| {
+            if level != Level::Off {
+                findings.push(Finding {
+                    rule,
+                    level,
+                    message,
+                });
+            }
+        };
+        let has_header = |name: &str| {
+            request
+                .headers
+                .iter()
+                .any(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+        };
+
+        if !has_header("accept") {
+            report(
+                "require_accept",
+                self.require_accept,
+                "no Accept header".to_string(),
+            );
+        }
+
+        let url = environment::substitute(&request.url, env, true).text;
+        if let Some(env) = env.filter(|env| self.is_prod(&env.name)) {
+            if url.trim().to_lowercase().starts_with("http://") {
+                report(
+                    "no_plain_http",
+                    self.no_plain_http,
+                    format!("plain http:// URL in the {} environment", env.name),
+                );
+            }
+        }
+
+        for name in self.secret_params_in(request, env) {
+            report(
+                "no_secret_params",
+                self.no_secret_params,
+                format!("query parameter '{}' carries a secret", name),
+            );
+        }
+
+        if request.method == Method::POST && !has_header(&self.idempotency_header) {
+            report(
+                "require_idempotency_key",
+                self.require_idempotency_key,
+                format!("POST without an {} header", self.idempotency_header),
+            );
+        }
+        findings
+    }
+
+    fn is_prod(&self, env_name: &str) -> bool {
+        self.prod_environments
+            .iter()
+            .any(|name| name.eq_ignore_ascii_case(env_name))
+    }
+
+    /// Query parameters, including those written into the URL, whose name
+    /// looks secret or whose value uses a secret
+    fn secret_params_in(&self, request: &Request, env: Option<&Environment>) -> Vec<String> {
+        let in_url = request
+            .url
+            .split_once('?')
+            .map(|(_, query)| query)
+            .unwrap_or_default()
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| pair.split_once('=').unwrap_or((pair, "")));
+        let params = request
+            .params
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()));
+        params
+            .chain(in_url)
+            .filter(|(name, value)| {
+                let name = name.to_lowercase();
+                let secret_name = self
+                    .secret_params
+                    .iter()
+                    .any(|marker| name.contains(&marker.to_lowercase()));
+                let used = environment::substitute(value, env, true).used;
+                let secret_value = value.contains(&format!("{{{{{}", secrets::PREFIX))
+                    || env.is_some_and(|env| used.iter().any(|var| env.is_secret(var)));
+                secret_name || secret_value
+            })
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+}
+
+/// Whether any finding stops the send
+pub fn has_errors(findings: &[Finding]) -> bool {
+    findings.iter().any(|f| f.level == Level::Error)
+}
+
+/// One finding per line
+pub fn format_findings(findings: &[Finding]) -> String {
+    findings
+        .iter()
+        .map(Finding::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The rules file of the workspace around `dir`, if there is one
+pub fn find_rules_file(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join(FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// Reads a rules file
+pub fn load_rules(path: &Path) -> Result<Rules> {
+    let content = std::fs::read_to_string(path)?;
+    toml::from_str(&content).map_err(|e| {
+        RestlessError::configuration(format!("Invalid lint rules {}: {}", path.display(), e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: Method, url: &str) -> Request {
+        Request {
+            url: url.to_string(),
+            method,
+            headers: vec![("Accept".to_string(), "application/json".to_string())],
+            ..Default::default()
+        }
+    }
+
+    fn rules_broken(
+        rules: &Rules,
+        request: &Request,
+        env: Option<&Environment>,
+    ) -> Vec<&'static str> {
+        rules.check(request, env).iter().map(|f| f.rule).collect()
+    }
+
+    #[test]
+    fn test_lint_rules() {
+        let rules = Rules {
+            require_idempotency_key: Level::Warn,
+            ..Default::default()
+        };
+        let mut prod = Environment::new("Prod");
+        prod.variables
+            .insert("host".to_string(), "http://api".to_string());
+        prod.variables
+            .insert("api_token".to_string(), "s3cret".to_string());
+
+        let clean = request(Method::GET, "https://api/users");
+        assert!(rules.check(&clean, Some(&prod)).is_empty());
+
+        let mut no_accept = clean.clone();
+        no_accept.headers.clear();
+        let findings = rules.check(&no_accept, None);
+        assert_eq!(
+            findings[0].to_string(),
+            "warning [require_accept]: no Accept header"
+        );
+        assert!(!has_errors(&findings));
+
+        let plain = request(Method::GET, "{{host}}/users");
+        assert_eq!(
+            rules_broken(&rules, &plain, Some(&prod)),
+            vec!["no_plain_http"]
+        );
+        assert!(rules_broken(&rules, &plain, Some(&Environment::new("dev"))).is_empty());
+        assert!(has_errors(&rules.check(&plain, Some(&prod))));
+
+        let mut secret = request(Method::GET, "https://api/users?access_token=abc&page=2");
+        secret
+            .params
+            .push(("auth".to_string(), "{{api_token}}".to_string()));
+        secret
+            .params
+            .push(("key".to_string(), "{{secret:env://KEY}}".to_string()));
+        let findings = rules.check(&secret, Some(&prod));
+        let names: Vec<&str> = findings.iter().map(|f| f.message.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "query parameter 'auth' carries a secret",
+                "query parameter 'key' carries a secret",
+                "query parameter 'access_token' carries a secret",
+            ]
+        );
+
+        let mut post = request(Method::POST, "https://api/orders");
+        assert_eq!(
+            rules_broken(&rules, &post, None),
+            vec!["require_idempotency_key"]
+        );
+        post.headers
+            .push(("idempotency-key".to_string(), "1".to_string()));
+        assert!(rules.check(&post, None).is_empty());
+        assert!(Rules::default()
+            .check(&request(Method::POST, "https://a"), None)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_load_rules() {
+        let dir = std::env::temp_dir().join(format!("restless-lint-test-{}", std::process::id()));
+        let nested = dir.join("api").join("v1");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_rules_file(&nested), None);
+
+        let path = dir.join(FILE_NAME);
+        std::fs::write(
+            &path,
+            "require_accept = \"error\"\nprod_environments = [\"live\"]\n",
+        )
+        .unwrap();
+        assert_eq!(find_rules_file(&nested), Some(path.clone()));
+        let rules = load_rules(&path).unwrap();
+        assert_eq!(rules.require_accept, Level::Error);
+        assert_eq!(rules.no_plain_http, Level::Error);
+        assert_eq!(rules.prod_environments, vec!["live"]);
+
+        std::fs::write(&path, "require_accept = \"sometimes\"\n").unwrap();
+        assert!(load_rules(&path).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,301 @@
+//! Sending one request many times over several connections at once
+//!
+//! `concurrency` workers share the `total` sends between them, each sending
+//! its next one as soon as the last is answered, so there are never more
+//! than `concurrency` requests in flight. Statuses of 4xx and 5xx count as
+//! responses; only sends that got no response at all are errors.
+
+use crate::error::{RestlessError, Result};
+use crate::logic::metrics::{format_duration, LatencyHistogram};
+use crate::logic::request::Request;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+
+/// Requests in flight at once when no concurrency is given
+pub const DEFAULT_CONCURRENCY: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Load {
+    /// Number of sends in all
+    pub total: usize,
+    /// Number of sends in flight at once
+    pub concurrency: usize,
+}
+
+impl Load {
+    pub fn new(total: usize, concurrency: usize) -> Result<Load> {
+        if total == 0 {
+            return Err(RestlessError::configuration("Send at least one request"));
+        }
+        if concurrency == 0 {
+            return Err(RestlessError::configuration(
+                "Concurrency must be at least 1",
+            ));
+        }
+        Ok(Load {
+            total,
+            concurrency: concurrency.min(total),
+        })
+    }
+
+    /// Parses `requests x concurrency`, e.g. `200 x 10`; the concurrency
+    /// defaults to [`DEFAULT_CONCURRENCY`]
+    pub fn parse(input: &str) -> Result<Load> {
+        let numbers = input
+            .split(|c: char| c == 'x' || c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                part.parse::<usize>().map_err(|_| {
+                    RestlessError::configuration(format!("'{}' is not a number", part))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        match numbers[..] {
+            [total] => Load::new(total, DEFAULT_CONCURRENCY),
+            [total, concurrency] => Load::new(total, concurrency),
+            _ => Err(RestlessError::configuration(
+                "Use 'requests x concurrency', e.g. 200 x 10",
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Time of every send that got a response
+    pub latency: LatencyHistogram,
+    /// How many responses came with each status code
+    pub statuses: BTreeMap<u16, usize>,
+    /// How many sends failed with each error
+    pub errors: BTreeMap<String, usize>,
+    /// Wall-clock time of the whole run
+    pub elapsed: Duration,
+}
+
+impl Report {
+    pub fn sent(&self) -> usize {
+        self.statuses.values().sum::<usize>() + self.errors.values().sum::<usize>()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.errors.values().sum()
+    }
+
+    pub fn requests_per_second(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds == 0.0 {
+            0.0
+        } else {
+            self.sent() as f64 / seconds
+        }
+    }
+
+    /// The report as printed by `restless bench`
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "{} requests in {} ({:.1} req/s)",
+            self.sent(),
+            format_duration(self.elapsed),
+            self.requests_per_second()
+        )];
+        if !self.latency.is_empty() {
+            lines.push(format!("Latency: {}", self.latency.summary()));
+        }
+        if !self.statuses.is_empty() {
+            let statuses: Vec<String> = self
+                .statuses
+                .iter()
+                .map(|(status, count)| format!("{} ×{}", status, count))
+                .collect();
+            lines.push(format!("Status: {}", statuses.join(", ")));
+        }
+        if !self.errors.is_empty() {
+            lines.push(format!("Errors: {}", self.failed()));
+            lines.extend(
+                self.errors
+                    .iter()
+                    .map(|(error, count)| format!("  {} ×{}", error, count)),
+            );
+        }
+        lines
+    }
+}
+
+/// How far a load test has got, updated by its workers as sends finish
+#[derive(Debug, Default)]
+pub struct Progress {
+    /// Sends done, answered or not
+    pub done: AtomicUsize,
+    /// Time of every send answered so far
+    latency: Mutex<Vec<Duration>>,
+}
+
+impl Progress {
+    pub fn done(&self) -> usize {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    /// Latency of the sends answered so far
+    pub fn latency(&self) -> LatencyHistogram {
+        let samples = self.latency.lock().unwrap_or_else(|e| e.into_inner());
+        LatencyHistogram::from_samples(samples.iter().copied())
+    }
+
+    fn record(&self, elapsed: Option<Duration>) {
+        if let Some(elapsed) = elapsed {
+            self.latency
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(elapsed);
+        }
+        self.done.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Sends `request` as often and as concurrently as `load` says
+pub async fn run(request: &Request, load: Load) -> Report {
+    run_counted(request, load, Arc::default()).await
+}
+
+/// Like [`run`], keeping `progress` up to date as sends finish
+///
+/// The workers belong to the returned future: dropping it before it's done
+/// aborts them, so no more requests go out.
+pub async fn run_counted(request: &Request, load: Load, progress: Arc<Progress>) -> Report {
+    let next = Arc::new(AtomicUsize::new(0));
+    let started = Instant::now();
+    let mut workers = JoinSet::new();
+    for _ in 0..load.concurrency {
+        let request = request.clone();
+        let next = next.clone();
+        let progress = progress.clone();
+        workers.spawn(async move {
+            let mut outcomes = Vec::new();
+            while next.fetch_add(1, Ordering::Relaxed) < load.total {
+                let outcome = match request.send().await {
+                    Ok(raw) => Ok((raw.status_code, raw.metrics.elapsed)),
+                    Err(e) => Err(e.to_string()),
+                };
+                progress.record(outcome.as_ref().ok().map(|&(_, elapsed)| elapsed));
+                outcomes.push(outcome);
+            }
+            outcomes
+        });
+    }
+
+    let mut report = Report::default();
+    let mut samples = Vec::with_capacity(load.total);
+    while let Some(worker) = workers.join_next().await {
+        let outcomes = worker.unwrap_or_else(|e| vec![Err(e.to_string())]);
+        for outcome in outcomes {
+            match outcome {
+                Ok((status, elapsed)) => {
+                    *report.statuses.entry(status).or_default() += 1;
+                    samples.push(elapsed);
+                }
+                Err(error) => *report.errors.entry(error).or_default() += 1,
+            }
+        }
+    }
+    report.latency = LatencyHistogram::from_samples(samples);
+    report.elapsed = started.elapsed();
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::TestServer;
+
+    #[tokio::test]
+    async fn test_run_load() {
+        use std::io::Write;
+
+        assert!(Load::new(0, 1).is_err());
+        assert!(Load::new(1, 0).is_err());
+        assert_eq!(Load::new(3, 10).unwrap().concurrency, 3);
+        assert_eq!(
+            Load::parse("200 x 20").unwrap(),
+            Load::new(200, 20).unwrap()
+        );
+        assert_eq!(Load::parse(" 50x5 ").unwrap(), Load::new(50, 5).unwrap());
+        assert_eq!(Load::parse("100").unwrap().concurrency, DEFAULT_CONCURRENCY);
+        assert!(Load::parse("").is_err());
+        assert!(Load::parse("ten x 2").is_err());
+        assert!(Load::parse("1 x 2 x 3").is_err());
+
+        let mut answered = 0;
+        let server = TestServer::with(move |_, stream| {
+            let status = if answered % 3 == 2 {
+                "503 Service Unavailable"
+            } else {
+                "200 OK"
+            };
+            answered += 1;
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status
+            );
+            let _ = stream.write_all(response.as_bytes());
+        });
+
+        let request = Request {
+            url: server.url("/"),
+            ..Request::default()
+        };
+        let progress = Arc::new(Progress::default());
+        let report = run_counted(&request, Load::new(9, 3).unwrap(), progress.clone()).await;
+        assert_eq!(progress.done(), 9);
+        assert_eq!(progress.latency(), report.latency);
+        assert_eq!(report.sent(), 9);
+        assert_eq!(report.failed(), 0);
+        assert_eq!(report.statuses[&200], 6);
+        assert_eq!(report.statuses[&503], 3);
+        assert_eq!(report.latency.len(), 9);
+        let lines = report.lines();
+        assert!(lines[0].starts_with("9 requests in "));
+        assert!(lines[1].starts_with("Latency: min "));
+        assert_eq!(lines[2], "Status: 200 ×6, 503 ×3");
+
+        let unreachable = Request {
+            url: "http://127.0.0.1:1/".to_string(),
+            ..Request::default()
+        };
+        let report = run(&unreachable, Load::new(2, 2).unwrap()).await;
+        assert_eq!(report.failed(), 2);
+        assert!(report.latency.is_empty());
+        assert_eq!(report.lines()[1], "Errors: 2");
+    }
+
+    #[tokio::test]
+    async fn test_dropping_a_run_stops_the_workers() {
+        use std::io::Write;
+
+        let received = Arc::new(AtomicUsize::new(0));
+        let counted = received.clone();
+        let server = TestServer::with(move |_, stream| {
+            counted.fetch_add(1, Ordering::Relaxed);
+            std::thread::sleep(Duration::from_millis(10));
+            let _ = stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+        });
+        let request = Request {
+            url: server.url("/"),
+            ..Request::default()
+        };
+
+        let running = run(&request, Load::new(10_000, 4).unwrap());
+        assert!(tokio::time::timeout(Duration::from_millis(200), running)
+            .await
+            .is_err());
+        // Requests already on their way may still arrive, then nothing more
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let stopped = received.load(Ordering::Relaxed);
+        assert!(stopped > 0);
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(received.load(Ordering::Relaxed), stopped);
+    }
+}
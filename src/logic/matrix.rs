@@ -0,0 +1,148 @@
+//! Sending a request once per value of a variable
+//!
+//! `region = eu, us, ap` sends the current request three times at once,
+//! with `{{region}}` standing for each value in turn on top of the active
+//! environment. The results are kept in input order whatever order the
+//! responses arrive in.
+
+use crate::error::{RestlessError, Result};
+use crate::logic::environment::Environment;
+use crate::logic::request::Request;
+use crate::logic::secrets;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matrix {
+    pub variable: String,
+    pub values: Vec<String>,
+}
+
+/// How the send for one value went
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    pub value: String,
+    /// Status code, or why no response arrived
+    pub status: std::result::Result<u16, String>,
+    pub elapsed: Duration,
+}
+
+impl Matrix {
+    /// Parses `name = value, value, ...`
+    pub fn parse(input: &str) -> Result<Matrix> {
+        let (variable, values) = input
+            .split_once('=')
+            .ok_or_else(|| RestlessError::configuration("Use 'name = value, value, ...'"))?;
+        let variable = variable.trim();
+        if variable.is_empty() || variable.contains(|c: char| c.is_whitespace() || "{}".contains(c))
+        {
+            return Err(RestlessError::configuration(format!(
+                "Invalid variable name '{}'",
+                variable
+            )));
+        }
+        let mut unique: Vec<String> = Vec::new();
+        for value in values.split(',').map(str::trim).filter(|v| !v.is_empty()) {
+            if !unique.iter().any(|seen| seen == value) {
+                unique.push(value.to_string());
+            }
+        }
+        if unique.is_empty() {
+            return Err(RestlessError::configuration(format!(
+                "No values for {}",
+                variable
+            )));
+        }
+        Ok(Matrix {
+            variable: variable.to_string(),
+            values: unique,
+        })
+    }
+
+    /// The active environment, if any, with the variable set to `value`
+    pub fn environment(&self, base: Option<&Environment>, value: &str) -> Environment {
+        let mut env = base.cloned().unwrap_or_default();
+        env.variables
+            .insert(self.variable.clone(), value.to_string());
+        env
+    }
+}
+
+/// Sends every request concurrently, each after looking up its secrets,
+/// returning one outcome per value
+pub async fn send_all(requests: Vec<(String, Request)>) -> Vec<Outcome> {
+    let sends: Vec<_> = requests
+        .into_iter()
+        .map(|(value, request)| {
+            let send = tokio::spawn(async move {
+                let started = Instant::now();
+                let sent = match secrets::resolve(request).await {
+                    Ok(request) => request.send().await,
+                    Err(e) => Err(e.into()),
+                };
+                match sent {
+                    Ok(raw) => (Ok(raw.status_code), raw.metrics.elapsed),
+                    Err(e) => (Err(e.to_string()), started.elapsed()),
+                }
+            });
+            (value, send)
+        })
+        .collect();
+    let mut outcomes = Vec::with_capacity(sends.len());
+    for (value, send) in sends {
+        let (status, elapsed) = send
+            .await
+            .unwrap_or_else(|e| (Err(e.to_string()), Duration::ZERO));
+        outcomes.push(Outcome {
+            value,
+            status,
+            elapsed,
+        });
+    }
+    outcomes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_matrix() {
+        let matrix = Matrix::parse(" region = eu, us,, ap, eu ").unwrap();
+        assert_eq!(matrix.variable, "region");
+        assert_eq!(matrix.values, vec!["eu", "us", "ap"]);
+        assert!(Matrix::parse("region").is_err());
+        assert!(Matrix::parse("region = , ").is_err());
+        assert!(Matrix::parse("{{region}} = eu").is_err());
+        assert!(Matrix::parse("= eu").is_err());
+    }
+
+    #[test]
+    fn test_matrix_environment() {
+        let matrix = Matrix::parse("tenant = a, b").unwrap();
+        let mut base = Environment::new("dev");
+        base.variables
+            .insert("tenant".to_string(), "default".to_string());
+        base.variables
+            .insert("host".to_string(), "localhost".to_string());
+        let env = matrix.environment(Some(&base), "b");
+        assert_eq!(env.get("tenant"), Some("b"));
+        assert_eq!(env.get("host"), Some("localhost"));
+        assert_eq!(matrix.environment(None, "a").get("tenant"), Some("a"));
+    }
+
+    #[tokio::test]
+    async fn test_send_all_keeps_order() {
+        let request = |port: &str| Request {
+            url: format!("http://127.0.0.1:{}/", port),
+            ..Request::default()
+        };
+        let outcomes = send_all(vec![
+            ("one".to_string(), request("1")),
+            ("two".to_string(), request("2")),
+        ])
+        .await;
+        let values: Vec<_> = outcomes.iter().map(|o| o.value.as_str()).collect();
+        assert_eq!(values, vec!["one", "two"]);
+        assert!(outcomes.iter().all(|o| o.status.is_err()));
+    }
+}
@@ -0,0 +1,510 @@
+//! Per-send metrics and latency aggregates
+//!
+//! Requests go through one shared client so connections are pooled. A
+//! connector layer counts and times the connections opened while a send is
+//! in flight, which tells whether the send reused a warm connection or paid
+//! for a new one, and a resolver times the name lookups. The connector does
+//! the TCP and TLS handshakes in one step, so they are timed together.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+tokio::task_local! {
+    static TRACKER: Arc<Tracker>;
+}
+
+/// Measurements taken for a single send
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Metrics {
+    pub elapsed: Duration,
+    pub reused_connection: bool,
+    pub phases: Phases,
+}
+
+/// Where the time of a send went
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Phases {
+    /// Name lookups; `None` without one, like on a reused connection
+    pub dns: Option<Duration>,
+    /// Opening the connection once the address is known, TLS included
+    pub connect: Option<Duration>,
+    /// Whether `connect` includes a TLS handshake
+    pub tls: bool,
+    /// From the connection being ready to the response headers
+    pub first_byte: Duration,
+    /// Reading the body
+    pub download: Duration,
+}
+
+impl Metrics {
+    /// Short description for the response title, e.g.
+    /// `123 ms, reused connection (TTFB 100 ms, download 23 ms)`
+    pub fn summary(&self) -> String {
+        format!(
+            "{}, {}{}",
+            format_duration(self.elapsed),
+            if self.reused_connection {
+                "reused connection"
+            } else {
+                "new connection"
+            },
+            self.phases
+                .summary()
+                .map(|phases| format!(" ({})", phases))
+                .unwrap_or_default()
+        )
+    }
+}
+
+impl Phases {
+    /// `DNS 2 ms, TCP+TLS 40 ms, TTFB 100 ms, download 23 ms`, leaving out
+    /// phases that didn't happen; `None` when nothing was measured
+    pub fn summary(&self) -> Option<String> {
+        if *self == Phases::default() {
+            return None;
+        }
+        let mut parts = Vec::new();
+        if let Some(dns) = self.dns {
+            parts.push(format!("DNS {}", format_duration(dns)));
+        }
+        if let Some(connect) = self.connect {
+            let label = if self.tls { "TCP+TLS" } else { "TCP" };
+            parts.push(format!("{} {}", label, format_duration(connect)));
+        }
+        parts.push(format!("TTFB {}", format_duration(self.first_byte)));
+        parts.push(format!("download {}", format_duration(self.download)));
+        Some(parts.join(", "))
+    }
+}
+
+/// Connections and lookups of the send in flight
+#[derive(Debug, Default)]
+struct Tracker {
+    opened: AtomicUsize,
+    dns: Mutex<Option<Duration>>,
+    /// Time spent connecting, lookups included
+    connect: Mutex<Option<Duration>>,
+}
+
+impl Tracker {
+    fn add(total: &Mutex<Option<Duration>>, duration: Duration) {
+        let mut total = total.lock().unwrap_or_else(|e| e.into_inner());
+        *total = Some(total.unwrap_or_default() + duration);
+    }
+
+    fn current() -> Option<Arc<Tracker>> {
+        // Sends outside of track_connections simply aren't tracked
+        TRACKER.try_with(Arc::clone).ok()
+    }
+}
+
+/// What [`track_connections`] saw during a send
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Tracked {
+    pub opened: usize,
+    pub dns: Option<Duration>,
+    /// Time spent connecting, lookups included
+    pub connect: Option<Duration>,
+}
+
+impl Tracked {
+    /// Metrics of a send that had its headers after `headers` and was done
+    /// after `elapsed`
+    pub fn metrics(&self, headers: Duration, elapsed: Duration, tls: bool) -> Metrics {
+        let dns = self.dns.unwrap_or_default();
+        let connect = self.connect.map(|connect| connect.saturating_sub(dns));
+        Metrics {
+            elapsed,
+            reused_connection: self.opened == 0,
+            phases: Phases {
+                dns: self.dns,
+                connect,
+                tls: tls && connect.is_some(),
+                first_byte: headers.saturating_sub(dns + connect.unwrap_or_default()),
+                download: elapsed.saturating_sub(headers),
+            },
+        }
+    }
+}
+
+/// Runs a send and reports the connections opened and lookups made for it
+pub async fn track_connections<F: Future>(future: F) -> (F::Output, Tracked) {
+    let tracker = Arc::new(Tracker::default());
+    let output = TRACKER.scope(tracker.clone(), future).await;
+    let read = |total: &Mutex<Option<Duration>>| *total.lock().unwrap_or_else(|e| e.into_inner());
+    let tracked = Tracked {
+        opened: tracker.opened.load(Ordering::Relaxed),
+        dns: read(&tracker.dns),
+        connect: read(&tracker.connect),
+    };
+    (output, tracked)
+}
+
+/// Name resolution through the system resolver, timed for
+/// [`track_connections`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TimedResolver;
+
+impl reqwest::dns::Resolve for TimedResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let tracker = Tracker::current();
+        Box::pin(async move {
+            let started = Instant::now();
+            let addrs: Vec<_> = tokio::net::lookup_host((name.as_str(), 0)).await?.collect();
+            if let Some(tracker) = tracker {
+                Tracker::add(&tracker.dns, started.elapsed());
+            }
+            Ok(Box::new(addrs.into_iter()) as reqwest::dns::Addrs)
+        })
+    }
+}
+
+/// Connector layer that counts and times new connections for
+/// [`track_connections`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ConnectionCounter;
+
+impl<S> tower_layer::Layer<S> for ConnectionCounter {
+    type Service = CountConnections<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CountConnections { inner }
+    }
+}
+
+/// Connector service wrapper produced by [`ConnectionCounter`]
+#[derive(Clone)]
+pub struct CountConnections<S> {
+    inner: S,
+}
+
+impl<S, R> tower_service::Service<R> for CountConnections<S>
+where
+    S: tower_service::Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: R) -> Self::Future {
+        let tracker = Tracker::current();
+        if let Some(tracker) = &tracker {
+            tracker.opened.fetch_add(1, Ordering::Relaxed);
+        }
+        let connecting = self.inner.call(request);
+        Box::pin(async move {
+            let started = Instant::now();
+            let connection = connecting.await;
+            if let Some(tracker) = tracker {
+                Tracker::add(&tracker.connect, started.elapsed());
+            }
+            connection
+        })
+    }
+}
+
+/// Latency of repeated sends to one URL, split by connection state
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LatencyStats {
+    pub target: String,
+    pub cold: Vec<Duration>,
+    pub warm: Vec<Duration>,
+}
+
+impl LatencyStats {
+    /// Records a send; sending somewhere else starts a fresh comparison
+    pub fn record(&mut self, target: &str, metrics: &Metrics) {
+        if self.target != target {
+            *self = LatencyStats {
+                target: target.to_string(),
+                ..Default::default()
+            };
+        }
+        if metrics.reused_connection {
+            self.warm.push(metrics.elapsed);
+        } else {
+            self.cold.push(metrics.elapsed);
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.cold.len() + self.warm.len()
+    }
+
+    /// Every recorded send, regardless of connection state
+    pub fn histogram(&self) -> LatencyHistogram {
+        LatencyHistogram::from_samples(self.cold.iter().chain(&self.warm).copied())
+    }
+
+    /// Cold vs warm averages, once there is more than one send to compare
+    pub fn summary(&self) -> Option<String> {
+        if self.count() < 2 {
+            return None;
+        }
+        let describe = |label: &str, samples: &[Duration]| match average(samples) {
+            Some(avg) => format!(
+                "{} avg {} (n={})",
+                label,
+                format_duration(avg),
+                samples.len()
+            ),
+            None => format!("{} -", label),
+        };
+        Some(format!(
+            "{} / {}",
+            describe("cold", &self.cold),
+            describe("warm", &self.warm)
+        ))
+    }
+}
+
+/// Latency distribution of many sends, for percentiles and bar charts
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    /// Kept sorted so percentiles are a lookup
+    samples: Vec<Duration>,
+}
+
+impl LatencyHistogram {
+    pub fn from_samples<I: IntoIterator<Item = Duration>>(samples: I) -> Self {
+        let mut samples: Vec<Duration> = samples.into_iter().collect();
+        samples.sort();
+        LatencyHistogram { samples }
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Nearest-rank percentile, `p` between 0 and 100
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let rank = ((p / 100.0) * self.samples.len() as f64).ceil() as usize;
+        Some(self.samples[rank.clamp(1, self.samples.len()) - 1])
+    }
+
+    pub fn min(&self) -> Option<Duration> {
+        self.samples.first().copied()
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.samples.last().copied()
+    }
+
+    pub fn mean(&self) -> Option<Duration> {
+        average(&self.samples)
+    }
+
+    /// Splits min..=max into equal-width buckets
+    ///
+    /// Returns each bucket's lower bound and how many samples fell into it.
+    pub fn buckets(&self, count: usize) -> Vec<(Duration, u64)> {
+        let (Some(min), Some(max)) = (self.min(), self.max()) else {
+            return Vec::new();
+        };
+        let count = count.max(1);
+        let width = (max - min).as_secs_f64() / count as f64;
+        let mut buckets: Vec<(Duration, u64)> = (0..count)
+            .map(|i| (min + Duration::from_secs_f64(width * i as f64), 0))
+            .collect();
+        for sample in &self.samples {
+            let index = if width == 0.0 {
+                0
+            } else {
+                (((*sample - min).as_secs_f64() / width) as usize).min(count - 1)
+            };
+            buckets[index].1 += 1;
+        }
+        buckets
+    }
+
+    /// One-line percentile summary, e.g. `p50 40 ms  p90 …`
+    pub fn summary(&self) -> String {
+        [
+            ("min", 0.0),
+            ("p50", 50.0),
+            ("p90", 90.0),
+            ("p95", 95.0),
+            ("p99", 99.0),
+            ("max", 100.0),
+        ]
+        .iter()
+        .filter_map(|(label, p)| {
+            self.percentile(*p)
+                .map(|value| format!("{} {}", label, format_duration(value)))
+        })
+        .collect::<Vec<_>>()
+        .join("  ")
+    }
+}
+
+fn average(samples: &[Duration]) -> Option<Duration> {
+    if samples.is_empty() {
+        None
+    } else {
+        Some(samples.iter().sum::<Duration>() / samples.len() as u32)
+    }
+}
+
+/// Formats a duration as milliseconds, or seconds once it gets long
+pub fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_secs_f64() * 1000.0;
+    if millis >= 1000.0 {
+        format!("{:.2} s", millis / 1000.0)
+    } else {
+        format!("{:.0} ms", millis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(ms: u64, reused: bool) -> Metrics {
+        Metrics {
+            elapsed: Duration::from_millis(ms),
+            reused_connection: reused,
+            phases: Phases::default(),
+        }
+    }
+
+    #[test]
+    fn test_metrics_summary() {
+        assert_eq!(metrics(123, true).summary(), "123 ms, reused connection");
+        assert_eq!(metrics(1500, false).summary(), "1.50 s, new connection");
+    }
+
+    #[test]
+    fn test_phases() {
+        let ms = Duration::from_millis;
+        let tracked = Tracked {
+            opened: 1,
+            dns: Some(ms(5)),
+            connect: Some(ms(45)),
+        };
+        let metrics = tracked.metrics(ms(110), ms(130), true);
+        assert_eq!(
+            metrics.summary(),
+            "130 ms, new connection (DNS 5 ms, TCP+TLS 40 ms, TTFB 65 ms, download 20 ms)"
+        );
+
+        let reused = Tracked::default().metrics(ms(100), ms(123), true);
+        assert!(reused.reused_connection);
+        assert_eq!(
+            reused.summary(),
+            "123 ms, reused connection (TTFB 100 ms, download 23 ms)"
+        );
+    }
+
+    #[test]
+    fn test_histogram_percentiles() {
+        let histogram = LatencyHistogram::default();
+        assert_eq!(histogram.percentile(50.0), None);
+        assert!(histogram.buckets(4).is_empty());
+
+        let histogram = LatencyHistogram::from_samples(
+            [50, 10, 40, 30, 20, 60, 70, 80, 90, 100].map(Duration::from_millis),
+        );
+        assert_eq!(histogram.len(), 10);
+        assert_eq!(histogram.min(), Some(Duration::from_millis(10)));
+        assert_eq!(histogram.percentile(50.0), Some(Duration::from_millis(50)));
+        assert_eq!(histogram.percentile(90.0), Some(Duration::from_millis(90)));
+        assert_eq!(histogram.percentile(99.0), Some(Duration::from_millis(100)));
+        assert_eq!(histogram.mean(), Some(Duration::from_millis(55)));
+        assert!(histogram.summary().starts_with("min 10 ms  p50 50 ms"));
+    }
+
+    #[test]
+    fn test_histogram_buckets() {
+        let histogram =
+            LatencyHistogram::from_samples([10, 12, 14, 50, 90, 100].map(Duration::from_millis));
+        let buckets = histogram.buckets(3);
+        assert_eq!(buckets.len(), 3);
+        assert_eq!(buckets[0], (Duration::from_millis(10), 3));
+        assert_eq!(buckets[1], (Duration::from_millis(40), 1));
+        assert_eq!(buckets[2], (Duration::from_millis(70), 2));
+
+        let same = LatencyHistogram::from_samples([Duration::from_millis(5); 4]);
+        assert_eq!(same.buckets(3)[0].1, 4);
+    }
+
+    #[test]
+    fn test_latency_stats() {
+        let mut stats = LatencyStats::default();
+        stats.record("http://a", &metrics(200, false));
+        assert_eq!(stats.summary(), None);
+
+        stats.record("http://a", &metrics(40, true));
+        stats.record("http://a", &metrics(60, true));
+        assert_eq!(
+            stats.summary().as_deref(),
+            Some("cold avg 200 ms (n=1) / warm avg 50 ms (n=2)")
+        );
+
+        stats.record("http://b", &metrics(10, true));
+        assert_eq!(stats.count(), 1);
+        stats.record("http://b", &metrics(10, true));
+        assert_eq!(
+            stats.summary().as_deref(),
+            Some("cold - / warm avg 10 ms (n=2)")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shared_client_reuses_connections() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // A keep-alive server that answers every request on a connection
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    while let Ok(n) = stream.read(&mut buf).await {
+                        if n == 0 {
+                            return;
+                        }
+                        let reply = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok";
+                        if stream.write_all(reply.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        let client = reqwest::Client::builder()
+            .connector_layer(ConnectionCounter)
+            .build()
+            .unwrap();
+        let url = format!("http://{}/", addr);
+
+        let (result, tracked) = track_connections(client.get(&url).send()).await;
+        assert!(result.is_ok());
+        assert_eq!(tracked.opened, 1);
+        assert!(tracked.connect.is_some());
+
+        let (result, tracked) = track_connections(client.get(&url).send()).await;
+        assert_eq!(result.unwrap().text().await.unwrap(), "ok");
+        assert_eq!(tracked, Tracked::default());
+    }
+}
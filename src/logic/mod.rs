@@ -1,4 +1,39 @@
+pub mod assertions;
+pub mod auth;
+pub mod bulk;
+pub mod cache;
+pub mod charset;
+pub mod compression;
+pub mod cookies;
+pub mod environment;
+pub mod form;
+pub mod fuzzy;
+pub mod headers;
+pub mod hexdump;
+pub mod highlight;
+pub mod html;
+pub mod jsonpath;
+pub mod lint;
+pub mod load;
+pub mod matrix;
+pub mod metrics;
+pub mod multipart;
+pub mod options;
+pub mod panel;
+pub mod poll;
+pub mod query;
+pub mod ratelimit;
 pub mod request;
 pub mod response;
+pub mod runner;
+pub mod search;
+pub mod secrets;
+pub mod signing;
+pub mod sse;
+pub mod table;
+pub mod tls;
+pub mod tree;
+pub mod watch;
+pub mod xml;
 
 pub use request::HttpMethod;
@@ -0,0 +1,156 @@
+//! Parsing of `multipart/*` response bodies
+//!
+//! Parts are split on the boundary from the `Content-Type` header, each with
+//! its own headers, so the UI can show them one at a time instead of the raw
+//! boundary noise.
+
+/// A single part of a multipart body
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Part {
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Part {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.header("content-type")
+    }
+
+    /// The part's name or file name from `Content-Disposition`
+    pub fn name(&self) -> Option<String> {
+        let disposition = self.header("content-disposition")?;
+        ["filename", "name"]
+            .iter()
+            .find_map(|key| parameter(disposition, key))
+    }
+
+    /// The body formatted for its content type (JSON is pretty-printed)
+    pub fn rendered_body(&self) -> String {
+        let is_json = self
+            .content_type()
+            .is_some_and(|ct| ct.to_lowercase().contains("json"));
+        if is_json {
+            if let Ok(value) = serde_json::from_str::<serde_json::Value>(self.body.trim()) {
+                if let Ok(pretty) = serde_json::to_string_pretty(&value) {
+                    return pretty;
+                }
+            }
+        }
+        self.body.clone()
+    }
+}
+
+/// Extracts a `key=value` parameter from a header value, unquoting it
+fn parameter(header: &str, key: &str) -> Option<String> {
+    header.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        if name.trim().eq_ignore_ascii_case(key) {
+            Some(value.trim().trim_matches('"').to_string())
+        } else {
+            None
+        }
+    })
+}
+
+/// Returns the boundary if the content type is `multipart/*`
+pub fn boundary(content_type: &str) -> Option<String> {
+    let mime = content_type.split(';').next()?.trim().to_lowercase();
+    if !mime.starts_with("multipart/") {
+        return None;
+    }
+    parameter(content_type, "boundary").filter(|b| !b.is_empty())
+}
+
+/// Splits a multipart body into parts; preamble and epilogue are dropped
+pub fn parse(body: &str, boundary: &str) -> Vec<Part> {
+    let delimiter = format!("--{}", boundary);
+    let mut parts = Vec::new();
+
+    for section in body.split(&delimiter).skip(1) {
+        if section.starts_with("--") {
+            break;
+        }
+        // The delimiter line ends with CRLF; the part ends with the CRLF before the next one
+        let section = section
+            .strip_prefix("\r\n")
+            .or_else(|| section.strip_prefix('\n'))
+            .unwrap_or(section);
+        let section = section
+            .strip_suffix("\r\n")
+            .or_else(|| section.strip_suffix('\n'))
+            .unwrap_or(section);
+
+        let (head, body) = match section.find("\r\n\r\n") {
+            Some(index) => (&section[..index], &section[index + 4..]),
+            None => match section.find("\n\n") {
+                Some(index) => (&section[..index], &section[index + 2..]),
+                None if section.contains(':') => (section, ""),
+                None => ("", section),
+            },
+        };
+
+        let headers = head
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
+
+        parts.push(Part {
+            headers,
+            body: body.to_string(),
+        });
+    }
+
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boundary() {
+        assert_eq!(
+            boundary("multipart/mixed; boundary=\"abc 123\""),
+            Some("abc 123".to_string())
+        );
+        assert_eq!(
+            boundary("Multipart/Form-Data; charset=utf-8; Boundary=xyz"),
+            Some("xyz".to_string())
+        );
+        assert_eq!(boundary("application/json; boundary=xyz"), None);
+        assert_eq!(boundary("multipart/mixed"), None);
+    }
+
+    #[test]
+    fn test_parse_parts() {
+        let body = "preamble\r\n--b1\r\nContent-Type: application/json\r\n\
+                    Content-Disposition: form-data; name=\"meta\"\r\n\r\n{\"a\":1}\r\n\
+                    --b1\r\nContent-Type: text/plain\r\n\r\nline one\r\nline two\r\n\
+                    --b1--\r\nepilogue";
+        let parts = parse(body, "b1");
+
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].content_type(), Some("application/json"));
+        assert_eq!(parts[0].name().as_deref(), Some("meta"));
+        assert_eq!(parts[0].rendered_body(), "{\n  \"a\": 1\n}");
+        assert_eq!(parts[1].body, "line one\r\nline two");
+        assert_eq!(parts[1].name(), None);
+    }
+
+    #[test]
+    fn test_parse_lf_only_and_filename() {
+        let body = "--x\nContent-Disposition: attachment; name=\"f\"; filename=\"a.txt\"\n\nhello\n--x--\n";
+        let parts = parse(body, "x");
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].name().as_deref(), Some("a.txt"));
+        assert_eq!(parts[0].body, "hello");
+    }
+}
@@ -0,0 +1,345 @@
+//! Per-request connection options
+//!
+//! Options are edited as `key=value` lines on the Options tab. Anything left
+//! unset falls back to the active environment's `options`, then to the
+//! `[connection]` section of the config file.
+
+use crate::error::{RestlessError, Result};
+use crate::logic::request::REQUEST_TIMEOUT;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Connection settings that need a dedicated HTTP client
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RequestOptions {
+    /// `http://`, `https://`, `socks5://` or `socks5h://` proxy URL, or `none`
+    /// to bypass the configured and environment proxies
+    pub proxy: Option<String>,
+    /// Proxy credentials as `user:password`
+    pub proxy_auth: Option<String>,
+    /// PEM file with extra CA certificates to trust
+    pub ca_cert: Option<String>,
+    /// Client certificate for mutual TLS: a PEM file, or a PKCS#12 bundle
+    /// (`.p12`/`.pfx`) holding both certificate and key
+    pub client_cert: Option<String>,
+    /// PKCS#8 PEM key for a PEM client certificate, when not in the same file
+    pub client_key: Option<String>,
+    /// Password of a PKCS#12 client certificate
+    pub client_cert_password: Option<String>,
+    /// `true` to accept invalid TLS certificates (self-signed dev servers)
+    #[serde(deserialize_with = "scalar")]
+    pub insecure: Option<String>,
+    /// `1.1` to force HTTP/1.1, `2` to force HTTP/2; unset negotiates
+    #[serde(deserialize_with = "scalar")]
+    pub http_version: Option<String>,
+    /// `true` to keep the body exactly as sent: no decompression or decoding
+    #[serde(deserialize_with = "scalar")]
+    pub raw_wire: Option<String>,
+    /// Oldest TLS version to offer: `1.0`, `1.1` or `1.2`
+    #[serde(deserialize_with = "scalar")]
+    pub min_tls_version: Option<String>,
+    /// Newest TLS version to offer: `1.0`, `1.1` or `1.2`
+    #[serde(deserialize_with = "scalar")]
+    pub max_tls_version: Option<String>,
+    /// Seconds a send may take, reading the body included
+    #[serde(deserialize_with = "scalar")]
+    pub timeout: Option<String>,
+    /// `false` to send a `GET` without the validators of its last response
+    #[serde(deserialize_with = "scalar")]
+    pub cache: Option<String>,
+}
+
+/// TLS versions that can bound a handshake; the native TLS backend can't
+/// pin 1.3 as either end
+const TLS_VERSIONS: &[&str] = &["1.0", "1.1", "1.2"];
+
+/// Accepts `insecure = true` or `http_version = 2` as well as quoted values
+fn scalar<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Scalar {
+        Bool(bool),
+        Integer(i64),
+        Float(f64),
+        Text(String),
+    }
+    Ok(
+        Option::<Scalar>::deserialize(deserializer)?.map(|scalar| match scalar {
+            Scalar::Bool(value) => value.to_string(),
+            Scalar::Integer(value) => value.to_string(),
+            // `1.0` would otherwise come out as `1`
+            Scalar::Float(value) if value.fract() == 0.0 => format!("{:.1}", value),
+            Scalar::Float(value) => value.to_string(),
+            Scalar::Text(value) => value,
+        }),
+    )
+}
+
+/// HTTP version a request is restricted to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HttpVersion {
+    Http1,
+    Http2,
+}
+
+impl RequestOptions {
+    /// Option names accepted by [`RequestOptions::set`]
+    pub const KEYS: &'static [&'static str] = &[
+        "proxy",
+        "proxy_auth",
+        "ca_cert",
+        "client_cert",
+        "client_key",
+        "client_cert_password",
+        "insecure",
+        "http_version",
+        "raw_wire",
+        "min_tls_version",
+        "max_tls_version",
+        "timeout",
+        "cache",
+    ];
+
+    fn slot(&mut self, key: &str) -> Option<&mut Option<String>> {
+        match key {
+            "proxy" => Some(&mut self.proxy),
+            "proxy_auth" => Some(&mut self.proxy_auth),
+            "ca_cert" => Some(&mut self.ca_cert),
+            "client_cert" => Some(&mut self.client_cert),
+            "client_key" => Some(&mut self.client_key),
+            "client_cert_password" => Some(&mut self.client_cert_password),
+            "insecure" => Some(&mut self.insecure),
+            "http_version" => Some(&mut self.http_version),
+            "raw_wire" => Some(&mut self.raw_wire),
+            "min_tls_version" => Some(&mut self.min_tls_version),
+            "max_tls_version" => Some(&mut self.max_tls_version),
+            "timeout" => Some(&mut self.timeout),
+            "cache" => Some(&mut self.cache),
+            _ => None,
+        }
+    }
+
+    fn values(&self) -> [(&'static str, &Option<String>); 13] {
+        [
+            ("proxy", &self.proxy),
+            ("proxy_auth", &self.proxy_auth),
+            ("ca_cert", &self.ca_cert),
+            ("client_cert", &self.client_cert),
+            ("client_key", &self.client_key),
+            ("client_cert_password", &self.client_cert_password),
+            ("insecure", &self.insecure),
+            ("http_version", &self.http_version),
+            ("raw_wire", &self.raw_wire),
+            ("min_tls_version", &self.min_tls_version),
+            ("max_tls_version", &self.max_tls_version),
+            ("timeout", &self.timeout),
+            ("cache", &self.cache),
+        ]
+    }
+
+    /// Sets an option by name; an empty value unsets it
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        if !value.is_empty() {
+            validate(key, value)?;
+        }
+        let slot = self.slot(key).ok_or_else(|| {
+            RestlessError::configuration(format!(
+                "Unknown option '{}' (expected one of: {})",
+                key,
+                Self::KEYS.join(", ")
+            ))
+        })?;
+        *slot = (!value.is_empty()).then(|| value.to_string());
+        Ok(())
+    }
+
+    /// The options that are set, as `(key, value)` pairs for display
+    pub fn entries(&self) -> Vec<(&'static str, String)> {
+        self.values()
+            .into_iter()
+            .filter_map(|(key, value)| value.clone().map(|value| (key, value)))
+            .collect()
+    }
+
+    /// Whether certificate verification is turned off
+    pub fn is_insecure(&self) -> bool {
+        self.insecure.as_deref() == Some("true")
+    }
+
+    /// Whether the body is shown as received, without decompression
+    pub fn is_raw_wire(&self) -> bool {
+        self.raw_wire.as_deref() == Some("true")
+    }
+
+    /// Whether a `GET` revalidates the copy of its last response; on unless
+    /// turned off
+    pub fn is_cached(&self) -> bool {
+        self.cache.as_deref() != Some("false")
+    }
+
+    /// The HTTP version the request must use, if restricted
+    pub fn http_version(&self) -> Option<HttpVersion> {
+        match self.http_version.as_deref() {
+            Some("1.1") => Some(HttpVersion::Http1),
+            Some("2") => Some(HttpVersion::Http2),
+            _ => None,
+        }
+    }
+
+    /// The oldest and newest TLS versions the handshake may use
+    pub fn tls_versions(&self) -> (Option<reqwest::tls::Version>, Option<reqwest::tls::Version>) {
+        (
+            tls_version(self.min_tls_version.as_deref()),
+            tls_version(self.max_tls_version.as_deref()),
+        )
+    }
+
+    /// How long a send may take, [`REQUEST_TIMEOUT`] unless set
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+            .as_deref()
+            .and_then(|seconds| seconds.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(REQUEST_TIMEOUT)
+    }
+
+    /// Whether the value of an option should be masked when shown
+    pub fn is_secret(key: &str) -> bool {
+        matches!(key, "proxy_auth" | "client_cert_password")
+    }
+
+    /// These options with unset values taken from `defaults`
+    pub fn merged(&self, defaults: &RequestOptions) -> RequestOptions {
+        let mut merged = self.clone();
+        for (key, value) in defaults.entries() {
+            let slot = merged.slot(key).expect("entries only yields known keys");
+            if slot.is_none() {
+                *slot = Some(value);
+            }
+        }
+        merged
+    }
+
+    /// Checks values that may come from a hand-edited file
+    pub fn validate(&self) -> Result<()> {
+        self.entries()
+            .into_iter()
+            .try_for_each(|(key, value)| validate(key, &value))
+    }
+}
+
+fn tls_version(value: Option<&str>) -> Option<reqwest::tls::Version> {
+    match value? {
+        "1.0" => Some(reqwest::tls::Version::TLS_1_0),
+        "1.1" => Some(reqwest::tls::Version::TLS_1_1),
+        "1.2" => Some(reqwest::tls::Version::TLS_1_2),
+        _ => None,
+    }
+}
+
+fn validate(key: &str, value: &str) -> Result<()> {
+    match key {
+        "proxy" => validate_proxy(value),
+        "proxy_auth" if !value.contains(':') => Err(RestlessError::configuration(
+            "proxy_auth uses the format user:password",
+        )),
+        "insecure" | "raw_wire" | "cache" if value != "true" && value != "false" => Err(
+            RestlessError::configuration(format!("{} must be true or false", key)),
+        ),
+        "http_version" if value != "1.1" && value != "2" => Err(RestlessError::configuration(
+            "http_version must be 1.1 or 2",
+        )),
+        "timeout" if value.parse::<u64>().map_or(true, |seconds| seconds == 0) => Err(
+            RestlessError::configuration("timeout must be a whole number of seconds, at least 1"),
+        ),
+        "min_tls_version" | "max_tls_version" if !TLS_VERSIONS.contains(&value) => {
+            Err(RestlessError::configuration(format!(
+                "{} must be 1.0, 1.1 or 1.2 (1.3 can't be pinned; leave it unset to allow it)",
+                key
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_proxy(proxy: &str) -> Result<()> {
+    const SCHEMES: &[&str] = &["http://", "https://", "socks5://", "socks5h://"];
+    if proxy == "none" || SCHEMES.iter().any(|scheme| proxy.starts_with(scheme)) {
+        Ok(())
+    } else {
+        Err(RestlessError::configuration(format!(
+            "Proxy must start with http://, https://, socks5:// or socks5h:// (or be 'none'), got: {}",
+            proxy
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_entries() {
+        let mut options = RequestOptions::default();
+        options.set("proxy", "socks5h://127.0.0.1:1080").unwrap();
+        options.set("proxy_auth", "me:pw").unwrap();
+        assert_eq!(
+            options.entries(),
+            vec![
+                ("proxy", "socks5h://127.0.0.1:1080".to_string()),
+                ("proxy_auth", "me:pw".to_string())
+            ]
+        );
+
+        options.set("proxy_auth", "").unwrap();
+        assert_eq!(options.proxy_auth, None);
+
+        assert!(options.set("proxy", "ftp://host").is_err());
+        assert!(options.set("proxy_auth", "nocolon").is_err());
+        assert!(options.set("colour", "red").is_err());
+        assert!(options.set("insecure", "yes").is_err());
+        options.set("insecure", "true").unwrap();
+        assert!(options.is_insecure());
+        assert!(options.set("http_version", "3").is_err());
+        options.set("http_version", "2").unwrap();
+        assert!(options.set("raw_wire", "1").is_err());
+        options.set("raw_wire", "true").unwrap();
+        assert!(options.is_raw_wire());
+        assert!(options.is_cached());
+        options.set("cache", "false").unwrap();
+        assert!(!options.is_cached());
+        assert_eq!(options.http_version(), Some(HttpVersion::Http2));
+        options.set("proxy", "none").unwrap();
+        assert!(options.set("max_tls_version", "1.3").is_err());
+        options.set("max_tls_version", "1.1").unwrap();
+        assert_eq!(
+            options.tls_versions(),
+            (None, Some(reqwest::tls::Version::TLS_1_1))
+        );
+        assert_eq!(options.timeout(), REQUEST_TIMEOUT);
+        assert!(options.set("timeout", "0").is_err());
+        assert!(options.set("timeout", "1.5").is_err());
+        options.set("timeout", "90").unwrap();
+        assert_eq!(options.timeout(), Duration::from_secs(90));
+    }
+
+    #[test]
+    fn test_merged_prefers_request_values() {
+        let defaults = RequestOptions {
+            proxy: Some("http://corp:3128".to_string()),
+            proxy_auth: Some("a:b".to_string()),
+            ..Default::default()
+        };
+        let request = RequestOptions {
+            proxy: Some("none".to_string()),
+            ..Default::default()
+        };
+        let merged = request.merged(&defaults);
+        assert_eq!(merged.proxy.as_deref(), Some("none"));
+        assert_eq!(merged.proxy_auth.as_deref(), Some("a:b"));
+        assert_eq!(RequestOptions::default().merged(&defaults), defaults);
+    }
+}
@@ -0,0 +1,187 @@
+//! Custom response panels
+//!
+//! A saved request can carry a small template that is filled in from its
+//! JSON response and shown as an extra response tab, for endpoints that are
+//! checked often enough to deserve a summary:
+//!
+//! ```text
+//! Order {{id}} — {{status}} — {{total}}
+//! {{#each items}}
+//!   {{quantity}} × {{sku}}
+//! {{/each}}
+//! ```
+//!
+//! Paths use dots and indexes (`customer.name`, `items[0].sku`); inside
+//! `#each` they start at the current element, which `{{.}}` stands for.
+//! Missing values render as `-`.
+
+use crate::error::{RestlessError, Result};
+use serde_json::Value;
+
+/// Shown for paths that don't exist in the response
+const MISSING: &str = "-";
+
+#[derive(Debug, PartialEq, Eq)]
+enum Node {
+    Text(String),
+    Value(String),
+    Each(String, Vec<Node>),
+}
+
+/// Fills in a template from a JSON body
+pub fn render(template: &str, body: &str) -> Result<String> {
+    let nodes = parse_nodes(&mut &*template, None)?;
+    let json: Value = serde_json::from_str(body.trim())
+        .map_err(|_| RestlessError::response_parsing("The panel needs a JSON response"))?;
+    let mut output = String::new();
+    render_nodes(&nodes, &json, &mut output);
+    Ok(output)
+}
+
+/// Parses until the end of the template, or the `{{/each}}` closing `each`
+fn parse_nodes(rest: &mut &str, each: Option<&str>) -> Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    loop {
+        let Some(start) = rest.find("{{") else {
+            if let Some(path) = each {
+                return Err(RestlessError::configuration(format!(
+                    "Panel template: {{{{#each {}}}}} is never closed",
+                    path
+                )));
+            }
+            if !rest.is_empty() {
+                nodes.push(Node::Text(rest.to_string()));
+            }
+            return Ok(nodes);
+        };
+        if start > 0 {
+            nodes.push(Node::Text(rest[..start].to_string()));
+        }
+        let end = rest[start..]
+            .find("}}")
+            .ok_or_else(|| RestlessError::configuration("Panel template: unclosed {{ tag"))?
+            + start;
+        let tag = rest[start + 2..end].trim();
+        *rest = &rest[end + 2..];
+
+        if let Some(path) = tag.strip_prefix("#each") {
+            skip_newline(rest);
+            let path = path.trim();
+            let children = parse_nodes(rest, Some(path))?;
+            nodes.push(Node::Each(path.to_string(), children));
+        } else if tag == "/each" {
+            if each.is_none() {
+                return Err(RestlessError::configuration(
+                    "Panel template: {{/each}} without {{#each}}",
+                ));
+            }
+            skip_newline(rest);
+            return Ok(nodes);
+        } else {
+            nodes.push(Node::Value(tag.to_string()));
+        }
+    }
+}
+
+/// Block tags on a line of their own shouldn't leave an empty line behind
+fn skip_newline(rest: &mut &str) {
+    if let Some(after) = rest.strip_prefix('\n') {
+        *rest = after;
+    }
+}
+
+fn render_nodes(nodes: &[Node], scope: &Value, output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Value(path) => match lookup(scope, path) {
+                Some(value) => output.push_str(&display(value)),
+                None => output.push_str(MISSING),
+            },
+            Node::Each(path, children) => match lookup(scope, path) {
+                Some(Value::Array(items)) => {
+                    for item in items {
+                        render_nodes(children, item, output);
+                    }
+                }
+                Some(Value::Object(fields)) => {
+                    for value in fields.values() {
+                        render_nodes(children, value, output);
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+}
+
+/// Follows a path like `items[0].sku` or `items.0.sku`
+fn lookup<'a>(scope: &'a Value, path: &str) -> Option<&'a Value> {
+    if path == "." {
+        return Some(scope);
+    }
+    path.replace('[', ".")
+        .replace(']', "")
+        .split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(scope, |value, segment| match value {
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => value.get(segment),
+        })
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDER: &str = r#"{"id": 42, "status": "shipped", "total": 19.5,
+        "customer": {"name": "Ada"},
+        "items": [{"sku": "A-1", "quantity": 2}, {"sku": "B-7", "quantity": 1}],
+        "tags": ["gift", "express"]}"#;
+
+    #[test]
+    fn test_render_values() {
+        assert_eq!(
+            render("Order {{id}} — {{ status }} — {{total}}", ORDER).unwrap(),
+            "Order 42 — shipped — 19.5"
+        );
+        assert_eq!(
+            render(
+                "{{customer.name}} / {{items[1].sku}} / {{items.0.quantity}}",
+                ORDER
+            )
+            .unwrap(),
+            "Ada / B-7 / 2"
+        );
+        assert_eq!(
+            render("{{missing}} {{items[9].sku}}", ORDER).unwrap(),
+            "- -"
+        );
+        assert_eq!(render("{{customer}}", ORDER).unwrap(), r#"{"name":"Ada"}"#);
+    }
+
+    #[test]
+    fn test_render_each() {
+        let template = "Items:\n{{#each items}}\n  {{quantity}} × {{sku}}\n{{/each}}\nTags: {{#each tags}}{{.}} {{/each}}";
+        assert_eq!(
+            render(template, ORDER).unwrap(),
+            "Items:\n  2 × A-1\n  1 × B-7\nTags: gift express "
+        );
+        assert_eq!(render("{{#each nope}}x{{/each}}", ORDER).unwrap(), "");
+    }
+
+    #[test]
+    fn test_render_errors() {
+        assert!(render("{{#each items}}", ORDER).is_err());
+        assert!(render("{{/each}}", ORDER).is_err());
+        assert!(render("{{id", ORDER).is_err());
+        assert!(render("{{id}}", "not json").is_err());
+    }
+}
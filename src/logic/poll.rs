@@ -0,0 +1,121 @@
+//! Re-sending a request on a fixed interval
+//!
+//! The interval counts from the previous send, and a send still waiting for
+//! its response holds the next one back instead of piling sends up.
+
+use crate::error::{RestlessError, Result};
+use crate::i18n::Catalog;
+use std::time::{Duration, Instant};
+
+/// Interval the prompt offers when nothing was polled yet
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub struct Poller {
+    pub interval: Duration,
+    /// Sends triggered so far
+    pub runs: usize,
+    next: Instant,
+}
+
+impl Poller {
+    /// Polls every `interval`, the first send right away
+    pub fn new(interval: Duration, now: Instant) -> Poller {
+        Poller {
+            interval,
+            runs: 0,
+            next: now,
+        }
+    }
+
+    /// Whether the next send is due, counting it as sent when it is
+    pub fn poll(&mut self, now: Instant) -> bool {
+        if now < self.next {
+            return false;
+        }
+        self.next = now + self.interval;
+        self.runs += 1;
+        true
+    }
+
+    /// Status bar text, e.g. `Polling every 5s (3 sends, next in 2s)`
+    pub fn summary(&self, now: Instant, i18n: &Catalog) -> String {
+        let text = if self.runs == 1 {
+            "Polling every {interval} ({count} send, next in {next}s)"
+        } else {
+            "Polling every {interval} ({count} sends, next in {next}s)"
+        };
+        let next = self
+            .next
+            .saturating_duration_since(now)
+            .as_secs_f64()
+            .ceil();
+        i18n.format(
+            text,
+            &[
+                ("interval", &format_interval(self.interval)),
+                ("count", &self.runs),
+                ("next", &next),
+            ],
+        )
+    }
+}
+
+/// Parses an interval such as `5`, `30s` or `2m`
+pub fn parse_interval(input: &str) -> Result<Duration> {
+    let input = input.trim();
+    let (number, unit) = match input.strip_suffix('m') {
+        Some(minutes) => (minutes, 60),
+        None => (input.strip_suffix('s').unwrap_or(input), 1),
+    };
+    match number.trim().parse::<u64>() {
+        Ok(count) if count > 0 => Ok(Duration::from_secs(count * unit)),
+        _ => Err(RestlessError::invalid_parameter(format!(
+            "'{}' is not an interval; use whole seconds like 5, 30s or 2m",
+            input
+        ))),
+    }
+}
+
+/// `5s`, or `2m` for whole minutes
+pub fn format_interval(interval: Duration) -> String {
+    let seconds = interval.as_secs();
+    if seconds >= 60 && seconds.is_multiple_of(60) {
+        format!("{}m", seconds / 60)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("5").unwrap(), Duration::from_secs(5));
+        assert_eq!(parse_interval(" 30s ").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_interval("2m").unwrap(), Duration::from_secs(120));
+        assert!(parse_interval("0").is_err());
+        assert!(parse_interval("fast").is_err());
+        assert_eq!(format_interval(Duration::from_secs(120)), "2m");
+        assert_eq!(format_interval(Duration::from_secs(90)), "90s");
+    }
+
+    #[test]
+    fn test_poller() {
+        let start = Instant::now();
+        let mut poller = Poller::new(Duration::from_secs(5), start);
+        assert!(poller.poll(start));
+        assert!(!poller.poll(start + Duration::from_secs(4)));
+        assert_eq!(
+            poller.summary(start + Duration::from_millis(1500), &Catalog::default()),
+            "Polling every 5s (1 send, next in 4s)"
+        );
+        assert!(poller.poll(start + Duration::from_secs(6)));
+        // Counted from the send, not from the schedule
+        assert!(!poller.poll(start + Duration::from_secs(10)));
+        assert!(poller.poll(start + Duration::from_secs(11)));
+        assert_eq!(poller.runs, 3);
+    }
+}
@@ -0,0 +1,205 @@
+//! Query strings written into the URL
+//!
+//! Params are kept as a list next to the URL, but a URL is often pasted or
+//! typed with its query. [`split`] moves the query out of a URL into params
+//! and [`join`] writes params back into one, so the URL can be edited whole.
+//! Names and values are stored decoded; `join` only escapes what would
+//! otherwise be read back differently, leaving `{{variables}}` readable.
+//! [`redact`] hides the values of a URL that's about to be logged.
+
+/// What a redacted value is replaced by
+const REDACTED: &str = "<redacted>";
+
+/// The URL without its query, and the params of the query in order
+///
+/// A `#fragment` stays with the URL.
+pub fn split(url: &str) -> (String, Vec<(String, String)>) {
+    let (rest, fragment) = match url.split_once('#') {
+        Some((rest, fragment)) => (rest, Some(fragment)),
+        None => (url, None),
+    };
+    let Some((base, query)) = rest.split_once('?') else {
+        return (url.to_string(), Vec::new());
+    };
+    let params = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(name), decode(value))
+        })
+        .collect();
+    let base = match fragment {
+        Some(fragment) => format!("{}#{}", base, fragment),
+        None => base.to_string(),
+    };
+    (base, params)
+}
+
+/// `url` with `params` added to its query
+pub fn join(url: &str, params: &[(String, String)]) -> String {
+    if params.is_empty() {
+        return url.to_string();
+    }
+    let (rest, fragment) = match url.split_once('#') {
+        Some((rest, fragment)) => (rest, Some(fragment)),
+        None => (url, None),
+    };
+    let query = params
+        .iter()
+        .map(|(name, value)| format!("{}={}", escape(name, true), escape(value, false)))
+        .collect::<Vec<_>>()
+        .join("&");
+    let separator = if rest.contains('?') { '&' } else { '?' };
+    let mut joined = format!("{}{}{}", rest, separator, query);
+    if let Some(fragment) = fragment {
+        joined.push('#');
+        joined.push_str(fragment);
+    }
+    joined
+}
+
+/// The URL with its password and every query value replaced, since a sent
+/// URL may hold resolved secrets such as `?api_key=`
+pub fn redact(url: &str) -> String {
+    let (rest, fragment) = match url.split_once('#') {
+        Some((rest, fragment)) => (rest, Some(fragment)),
+        None => (url, None),
+    };
+    let (base, query) = match rest.split_once('?') {
+        Some((base, query)) => (base, Some(query)),
+        None => (rest, None),
+    };
+
+    let mut redacted = match base.split_once("://") {
+        Some((scheme, after)) => {
+            let end = after.find('/').unwrap_or(after.len());
+            let (authority, path) = after.split_at(end);
+            let authority = match authority.rsplit_once('@') {
+                Some((user_info, host)) => match user_info.split_once(':') {
+                    Some((user, _)) => format!("{}:{}@{}", user, REDACTED, host),
+                    None => authority.to_string(),
+                },
+                None => authority.to_string(),
+            };
+            format!("{}://{}{}", scheme, authority, path)
+        }
+        None => base.to_string(),
+    };
+    if let Some(query) = query {
+        let pairs: Vec<String> = query
+            .split('&')
+            .map(|pair| match pair.split_once('=') {
+                Some((name, _)) => format!("{}={}", name, REDACTED),
+                None => pair.to_string(),
+            })
+            .collect();
+        redacted.push('?');
+        redacted.push_str(&pairs.join("&"));
+    }
+    if let Some(fragment) = fragment {
+        redacted.push('#');
+        redacted.push_str(fragment);
+    }
+    redacted
+}
+
+/// The text with every `http://` or `https://` URL in it redacted as
+/// [`redact`] does, for messages such as errors that quote the URL
+pub fn redact_urls(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = ["http://", "https://"]
+        .iter()
+        .filter_map(|scheme| rest.find(scheme))
+        .min()
+    {
+        let end = rest[start..]
+            .find(|c: char| c.is_whitespace() || matches!(c, ')' | '"' | '\'' | '>' | ']'))
+            .map_or(rest.len(), |end| start + end);
+        result.push_str(&rest[..start]);
+        result.push_str(&redact(&rest[start..end]));
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+    result
+}
+
+fn decode(text: &str) -> String {
+    let text = text.replace('+', " ");
+    urlencoding::decode(&text)
+        .map(|decoded| decoded.into_owned())
+        .unwrap_or(text)
+}
+
+/// Percent-encodes the characters that would split or change the pair
+fn escape(text: &str, name: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '%' | '&' | '#' | '+' => escaped.push_str(&format!("%{:02X}", c as u8)),
+            '=' if name => escaped.push_str("%3D"),
+            ' ' => escaped.push_str("%20"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_split() {
+        let (url, params) = split("https://api.example.com/users?limit=10&page=2");
+        assert_eq!(url, "https://api.example.com/users");
+        assert_eq!(params, pairs(&[("limit", "10"), ("page", "2")]));
+
+        let (url, params) = split("{{base}}/search?q=a+b%26c&flag&tag=x=y#top");
+        assert_eq!(url, "{{base}}/search#top");
+        assert_eq!(
+            params,
+            pairs(&[("q", "a b&c"), ("flag", ""), ("tag", "x=y")])
+        );
+
+        assert_eq!(split("https://a.test/").1, Vec::new());
+        assert_eq!(split("https://a.test/?").0, "https://a.test/");
+    }
+
+    #[test]
+    fn test_join_round_trip() {
+        let params = pairs(&[("q", "a b&c"), ("id", "{{user_id}}"), ("x=1", "50%")]);
+        let url = join("https://a.test/find#top", &params);
+        assert_eq!(
+            url,
+            "https://a.test/find?q=a%20b%26c&id={{user_id}}&x%3D1=50%25#top"
+        );
+        assert_eq!(split(&url), ("https://a.test/find#top".to_string(), params));
+        assert_eq!(join("https://a.test/", &[]), "https://a.test/");
+    }
+
+    #[test]
+    fn test_redact() {
+        assert_eq!(
+            redact("https://me:pw@api.test/v1/items?api_key=s3cret&flag&page=2#top"),
+            "https://me:<redacted>@api.test/v1/items?api_key=<redacted>&flag&page=<redacted>#top"
+        );
+        assert_eq!(redact("https://api.test/users"), "https://api.test/users");
+        assert_eq!(
+            redact_urls("error sending request for url (http://a.test/?k=v): refused"),
+            "error sending request for url (http://a.test/?k=<redacted>): refused"
+        );
+        assert_eq!(
+            redact_urls("GET https://a.test/?t=1 then https://b.test/x?u=2"),
+            "GET https://a.test/?t=<redacted> then https://b.test/x?u=<redacted>"
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! Rate limit and `Retry-After` headers
+//!
+//! Understands the common `X-RateLimit-*` headers, the IETF `RateLimit-*`
+//! headers (including the combined `RateLimit: limit=.., remaining=..` form)
+//! and `Retry-After`, so quota information doesn't have to be dug out of the
+//! raw header list.
+
+use chrono::{DateTime, Duration, Local, Utc};
+
+/// Reset values above this are epoch seconds rather than a delay
+const EPOCH_THRESHOLD: i64 = 1_000_000_000;
+
+/// Quota information reported by a response
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    /// When the quota resets
+    pub reset: Option<DateTime<Utc>>,
+    /// When the server asked the client to retry
+    pub retry_after: Option<DateTime<Utc>>,
+}
+
+impl RateLimit {
+    /// Reads rate limit headers; `None` when the response has none
+    pub fn from_headers(headers: &[(String, String)], received: DateTime<Utc>) -> Option<Self> {
+        let mut rate_limit = RateLimit::default();
+
+        for (key, value) in headers {
+            let key = key.to_ascii_lowercase();
+            let value = value.trim();
+            let name = key
+                .strip_prefix("x-ratelimit-")
+                .or_else(|| key.strip_prefix("ratelimit-"))
+                .or_else(|| key.strip_prefix("x-rate-limit-"));
+            match name {
+                Some("limit") => rate_limit.limit = first_number(value),
+                Some("remaining") => rate_limit.remaining = first_number(value),
+                Some("reset") => rate_limit.reset = parse_reset(value, received),
+                _ if key == "ratelimit" => rate_limit.apply_structured(value, received),
+                _ if key == "retry-after" => {
+                    rate_limit.retry_after = parse_retry_after(value, received)
+                }
+                _ => {}
+            }
+        }
+
+        (rate_limit != RateLimit::default()).then_some(rate_limit)
+    }
+
+    /// Applies `limit=100, remaining=50, reset=30` (or `;r=50;t=30`) values
+    fn apply_structured(&mut self, value: &str, received: DateTime<Utc>) {
+        for item in value.split([',', ';']) {
+            let Some((key, value)) = item.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('"');
+            match key.trim() {
+                "limit" | "l" => self.limit = first_number(value),
+                "remaining" | "r" => self.remaining = first_number(value),
+                "reset" | "t" => self.reset = parse_reset(value, received),
+                _ => {}
+            }
+        }
+    }
+
+    /// When the server takes requests again: the `Retry-After` time, or
+    /// else the reset of a quota that's used up
+    pub fn retry_at(&self) -> Option<DateTime<Utc>> {
+        self.retry_after
+            .or(self.reset.filter(|_| self.remaining.unwrap_or(0) == 0))
+    }
+
+    /// Whether the remaining quota is low (at most 10%, or nothing left)
+    pub fn is_low(&self) -> bool {
+        if self.retry_after.is_some() {
+            return true;
+        }
+        match (self.remaining, self.limit) {
+            (Some(remaining), Some(limit)) => remaining.saturating_mul(10) <= limit,
+            (Some(remaining), None) => remaining == 0,
+            _ => false,
+        }
+    }
+
+    /// One-line summary such as `quota 5/100, resets 14:30:05`
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        match (self.remaining, self.limit) {
+            (Some(remaining), Some(limit)) => parts.push(format!("quota {}/{}", remaining, limit)),
+            (Some(remaining), None) => parts.push(format!("quota {} left", remaining)),
+            (None, Some(limit)) => parts.push(format!("limit {}", limit)),
+            (None, None) => {}
+        }
+        if let Some(reset) = self.reset {
+            parts.push(format!("resets {}", clock(reset)));
+        }
+        if let Some(retry_after) = self.retry_after {
+            parts.push(format!("retry after {}", clock(retry_after)));
+        }
+        parts.join(", ")
+    }
+}
+
+/// A wait as counted down in the status bar: `45s`, `2m 05s`, `1h 02m`
+pub fn format_wait(seconds: i64) -> String {
+    let seconds = seconds.max(0);
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m {:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {:02}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+fn clock(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format("%H:%M:%S").to_string()
+}
+
+/// Some servers send a list such as `100, 100;w=60`; the first value counts
+fn first_number(value: &str) -> Option<u64> {
+    value
+        .split([',', ';'])
+        .next()
+        .and_then(|n| n.trim().parse().ok())
+}
+
+fn parse_reset(value: &str, received: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let seconds: i64 = value
+        .split([',', ';'])
+        .next()?
+        .trim()
+        .split('.')
+        .next()?
+        .parse()
+        .ok()?;
+    if seconds >= EPOCH_THRESHOLD {
+        DateTime::from_timestamp(seconds, 0)
+    } else {
+        Some(received + Duration::seconds(seconds))
+    }
+}
+
+fn parse_retry_after(value: &str, received: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    match value.parse::<i64>() {
+        Ok(seconds) => Some(received + Duration::seconds(seconds)),
+        Err(_) => DateTime::parse_from_rfc2822(value)
+            .ok()
+            .map(|date| date.with_timezone(&Utc)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn received() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap()
+    }
+
+    fn headers(items: &[(&str, &str)]) -> Vec<(String, String)> {
+        items
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_x_ratelimit_headers() {
+        let reset = received().timestamp() + 600;
+        let rate_limit = RateLimit::from_headers(
+            &headers(&[
+                ("X-RateLimit-Limit", "5000"),
+                ("X-RateLimit-Remaining", "4990"),
+                ("X-RateLimit-Reset", &reset.to_string()),
+            ]),
+            received(),
+        )
+        .unwrap();
+        assert_eq!(rate_limit.limit, Some(5000));
+        assert_eq!(rate_limit.remaining, Some(4990));
+        assert_eq!(rate_limit.reset, Some(received() + Duration::seconds(600)));
+        assert!(!rate_limit.is_low());
+        assert!(rate_limit.summary().starts_with("quota 4990/5000, resets "));
+    }
+
+    #[test]
+    fn test_ietf_headers() {
+        let rate_limit = RateLimit::from_headers(
+            &headers(&[
+                ("RateLimit-Limit", "100, 100;w=60"),
+                ("RateLimit-Remaining", "3"),
+                ("RateLimit-Reset", "30"),
+            ]),
+            received(),
+        )
+        .unwrap();
+        assert_eq!(rate_limit.limit, Some(100));
+        assert_eq!(rate_limit.reset, Some(received() + Duration::seconds(30)));
+        assert!(rate_limit.is_low());
+
+        let rate_limit = RateLimit::from_headers(
+            &headers(&[("RateLimit", "limit=10, remaining=9, reset=5")]),
+            received(),
+        )
+        .unwrap();
+        assert_eq!(
+            (rate_limit.limit, rate_limit.remaining),
+            (Some(10), Some(9))
+        );
+        assert_eq!(rate_limit.reset, Some(received() + Duration::seconds(5)));
+
+        // A huge remaining quota doesn't overflow
+        let rate_limit = RateLimit::from_headers(
+            &headers(&[
+                ("RateLimit-Limit", &(u64::MAX / 2).to_string()),
+                ("RateLimit-Remaining", &(u64::MAX / 4).to_string()),
+            ]),
+            received(),
+        )
+        .unwrap();
+        assert_eq!(rate_limit.remaining, Some(u64::MAX / 4));
+        assert!(!rate_limit.is_low());
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let rate_limit =
+            RateLimit::from_headers(&headers(&[("Retry-After", "120")]), received()).unwrap();
+        assert_eq!(
+            rate_limit.retry_after,
+            Some(received() + Duration::seconds(120))
+        );
+        assert!(rate_limit.is_low());
+
+        let rate_limit = RateLimit::from_headers(
+            &headers(&[("Retry-After", "Wed, 01 May 2024 12:05:00 GMT")]),
+            received(),
+        )
+        .unwrap();
+        assert_eq!(
+            rate_limit.retry_after,
+            Some(received() + Duration::minutes(5))
+        );
+        assert_eq!(rate_limit.retry_at(), rate_limit.retry_after);
+
+        // Without Retry-After the reset counts once the quota is used up
+        let rate_limit = RateLimit::from_headers(
+            &headers(&[("X-RateLimit-Remaining", "0"), ("X-RateLimit-Reset", "30")]),
+            received(),
+        )
+        .unwrap();
+        assert_eq!(
+            rate_limit.retry_at(),
+            Some(received() + Duration::seconds(30))
+        );
+        let rate_limit = RateLimit::from_headers(
+            &headers(&[("X-RateLimit-Remaining", "5"), ("X-RateLimit-Reset", "30")]),
+            received(),
+        )
+        .unwrap();
+        assert_eq!(rate_limit.retry_at(), None);
+    }
+
+    #[test]
+    fn test_format_wait() {
+        assert_eq!(format_wait(-3), "0s");
+        assert_eq!(format_wait(45), "45s");
+        assert_eq!(format_wait(125), "2m 05s");
+        assert_eq!(format_wait(3720), "1h 02m");
+    }
+
+    #[test]
+    fn test_no_rate_limit_headers() {
+        let result =
+            RateLimit::from_headers(&headers(&[("Content-Type", "text/plain")]), received());
+        assert_eq!(result, None);
+    }
+}
@@ -1,32 +1,346 @@
 use crate::error::ResponseError;
+use crate::logic::assertions::TestResult;
+use crate::logic::cache::Cached;
+use crate::logic::charset::Charset;
+use crate::logic::hexdump;
+use crate::logic::metrics::Metrics;
+use crate::logic::multipart::{self, Part};
+use crate::logic::ratelimit::RateLimit;
+use crate::logic::request::SentRequest;
+use crate::logic::sse::{Event, EventStream};
+use crate::logic::table::Table;
+use crate::logic::tls::TlsDetails;
+use crate::logic::xml;
 use serde_json::{to_string_pretty, Value};
 
 pub struct Response {
     pub status_code: u16,
     pub headers: Vec<(String, String)>,
     pub body: String,
+    /// Parts of a `multipart/*` body; empty for everything else
+    pub parts: Vec<Part>,
+    /// Rows and columns of a body that's a JSON array of objects
+    pub table: Option<Table>,
+    /// Timing and connection details, when the response came from a send
+    pub metrics: Option<Metrics>,
+    /// Triage note the user attached to this particular response
+    pub note: Option<String>,
+    /// Quota and retry information from the rate limit headers
+    pub rate_limit: Option<RateLimit>,
+    /// Protocol, cipher and server certificate of an HTTPS response
+    pub tls: Option<TlsDetails>,
+    /// Protocol the response came over, e.g. `HTTP/2`
+    pub version: Option<String>,
+    /// Original encoding of a body that wasn't plain UTF-8
+    pub charset: Option<Charset>,
+    /// Body bytes exactly as sent by the server, in raw wire mode
+    pub wire_bytes: Option<Vec<u8>>,
+    /// Body bytes of a binary response, shown as a hex dump
+    pub bytes: Option<Vec<u8>>,
+    /// Open event stream whose events are appended to the body
+    pub stream: Option<EventStream>,
+    /// Events received so far, for event stream responses
+    pub events: Option<usize>,
+    /// Header and body sizes, when the response came from a send
+    pub transfer: Option<Transfer>,
+    /// Results of the request's assertions, see [`crate::logic::assertions`]
+    pub tests: Vec<TestResult>,
+    /// The request as it went out, when the response came from a send
+    pub sent: Option<SentRequest>,
+    /// The copy a `304 Not Modified` stands for, see [`crate::logic::cache`]
+    pub cached: Option<Cached>,
+    /// Whether the body is the cached copy's rather than the empty one sent
+    pub cached_shown: bool,
+    /// Whether the body differs from the response before, while polling
+    pub changed: Option<bool>,
+}
+
+/// How big a response was, and how its body was compressed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transfer {
+    /// Size of the header block, counting each as `Name: value\r\n`
+    pub header_size: usize,
+    /// Body size as received
+    pub wire_size: usize,
+    /// Body size after decompression
+    pub body_size: usize,
+    /// `Content-Encoding` of a compressed body
+    pub encoding: Option<String>,
+    /// Whether the body decompressed to more than
+    /// [`MAX_DECODED`](crate::logic::compression::MAX_DECODED) and was cut short
+    pub truncated: bool,
+}
+
+impl Transfer {
+    /// Sizes of a response with `Name: value` header lines
+    pub fn new(headers: &str, wire_size: usize, body_size: usize, encoding: Option<&str>) -> Self {
+        Transfer {
+            header_size: headers.lines().map(|line| line.len() + 2).sum(),
+            wire_size,
+            body_size,
+            encoding: encoding
+                .map(str::trim)
+                .filter(|e| !e.is_empty() && !e.eq_ignore_ascii_case("identity"))
+                .map(str::to_string),
+            truncated: false,
+        }
+    }
+
+    /// Summary for the status line, e.g.
+    /// `body 12.3 KB (2.1 KB gzip), headers 412 B`
+    pub fn summary(&self) -> String {
+        let mut summary = format!("body {}", format_size(self.body_size));
+        if let Some(encoding) = &self.encoding {
+            summary.push_str(&format!(" ({} {})", format_size(self.wire_size), encoding));
+        }
+        if self.truncated {
+            summary.push_str(", cut short");
+        }
+        summary.push_str(&format!(", headers {}", format_size(self.header_size)));
+        summary
+    }
+}
+
+/// `412 B`, `2.1 KB`, `3.4 MB`
+pub fn format_size(bytes: usize) -> String {
+    const KB: f64 = 1024.0;
+    let size = bytes as f64;
+    if size < KB {
+        format!("{} B", bytes)
+    } else if size < KB * KB {
+        format!("{:.1} KB", size / KB)
+    } else {
+        format!("{:.1} MB", size / (KB * KB))
+    }
 }
 
 impl Response {
+    /// The response a `304 Not Modified` stands for: the cached copy, with
+    /// the `200` it was stored from
+    pub fn cached_copy(&self) -> Option<Response> {
+        let cached = self.cached.as_ref().filter(|_| self.status_code == 304)?;
+        Some(
+            Response::new(200, cached.headers.clone(), cached.body.clone()).unwrap_or_else(|_| {
+                Response::new_unchecked(200, cached.headers.clone(), cached.body.clone())
+            }),
+        )
+    }
+
+    /// Swaps the empty body of a `304 Not Modified` for the cached copy's,
+    /// formatted like any body, and back
+    pub fn toggle_cached(&mut self) -> bool {
+        let Some(cached) = &self.cached else {
+            return false;
+        };
+        if self.cached_shown {
+            self.body.clear();
+            self.parts.clear();
+            self.table = None;
+        } else {
+            let copy = Response::new_unchecked(
+                self.status_code,
+                cached.headers.clone(),
+                cached.body.clone(),
+            );
+            self.body = copy.body;
+            self.parts = copy.parts;
+            self.table = copy.table;
+        }
+        self.cached_shown = !self.cached_shown;
+        true
+    }
+
     pub fn new(status_code: u16, headers: String, body: String) -> Result<Self, ResponseError> {
         let parsed_headers = Self::split_headers(&headers)?;
-        let formatted_body = Self::pretty_print_json(&body)?;
+        let parts = Self::parse_parts(&parsed_headers, &body);
+        let table = Table::from_json(&body);
+        let formatted_body = if Self::xml_type(&parsed_headers) {
+            xml::pretty(&body).unwrap_or(body)
+        } else {
+            Self::pretty_print_json(&body)?
+        };
 
         Ok(Response {
             status_code,
             headers: parsed_headers,
             body: formatted_body,
+            parts,
+            table,
+            metrics: None,
+            note: None,
+            rate_limit: None,
+            tls: None,
+            version: None,
+            charset: None,
+            wire_bytes: None,
+            bytes: None,
+            stream: None,
+            events: None,
+            transfer: None,
+            tests: Vec::new(),
+            sent: None,
+            cached: None,
+            cached_shown: false,
+            changed: None,
         })
     }
 
     pub fn new_unchecked(status_code: u16, headers: String, body: String) -> Self {
+        let headers = Self::split_headers(&headers).unwrap_or_default();
+        let parts = Self::parse_parts(&headers, &body);
+        let table = Table::from_json(&body);
+        let body = if Self::xml_type(&headers) {
+            xml::pretty(&body).unwrap_or(body)
+        } else {
+            Self::pretty_print_json(&body).unwrap_or(body)
+        };
+        Response {
+            status_code,
+            headers,
+            body,
+            parts,
+            table,
+            metrics: None,
+            note: None,
+            rate_limit: None,
+            tls: None,
+            version: None,
+            charset: None,
+            wire_bytes: None,
+            bytes: None,
+            stream: None,
+            events: None,
+            transfer: None,
+            tests: Vec::new(),
+            sent: None,
+            cached: None,
+            cached_shown: false,
+            changed: None,
+        }
+    }
+
+    /// A response shown as it came off the wire: no decompression, decoding,
+    /// pretty printing or multipart splitting
+    pub fn wire(status_code: u16, headers: String, bytes: Vec<u8>) -> Self {
         Response {
             status_code,
             headers: Self::split_headers(&headers).unwrap_or_default(),
-            body: Self::pretty_print_json(&body).unwrap_or_else(|_| body),
+            body: String::from_utf8_lossy(&bytes).into_owned(),
+            parts: Vec::new(),
+            table: None,
+            metrics: None,
+            note: None,
+            rate_limit: None,
+            tls: None,
+            version: None,
+            charset: None,
+            wire_bytes: Some(bytes),
+            bytes: None,
+            stream: None,
+            events: None,
+            transfer: None,
+            tests: Vec::new(),
+            sent: None,
+            cached: None,
+            cached_shown: false,
+            changed: None,
+        }
+    }
+
+    /// The status code with its reason phrase, e.g. `404 Not Found`
+    pub fn status_text(&self) -> String {
+        match reqwest::StatusCode::from_u16(self.status_code)
+            .ok()
+            .and_then(|status| status.canonical_reason())
+        {
+            Some(reason) => format!("{} {}", self.status_code, reason),
+            None => self.status_code.to_string(),
         }
     }
 
+    /// Summary for the response title in raw wire mode, e.g.
+    /// `raw wire: 312 bytes, gzip`
+    pub fn wire_summary(&self) -> Option<String> {
+        let bytes = self.wire_bytes.as_ref()?;
+        let encoding = self
+            .headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("content-encoding"))
+            .map(|(_, value)| format!(", {}", value))
+            .unwrap_or_default();
+        Some(format!("raw wire: {} bytes{}", bytes.len(), encoding))
+    }
+
+    /// Bytes to show as a hex dump: a binary body, or raw wire bytes that
+    /// aren't text, such as a compressed body
+    pub fn hex_bytes(&self) -> Option<&[u8]> {
+        self.bytes.as_deref().or_else(|| {
+            self.wire_bytes
+                .as_deref()
+                .filter(|bytes| hexdump::is_binary(bytes, self.content_type()))
+        })
+    }
+
+    /// Keeps the response open, appending events as they arrive
+    pub fn attach_stream(&mut self, stream: EventStream) {
+        self.stream = Some(stream);
+        self.events = Some(0);
+    }
+
+    /// Appends an event to the body
+    pub fn push_event(&mut self, event: &Event) {
+        self.body.push_str(&event.to_text());
+        *self.events.get_or_insert(0) += 1;
+    }
+
+    /// Appends the events that arrived since the last poll, returning whether
+    /// the stream is still open
+    pub fn poll_stream(&mut self) -> bool {
+        let Some(stream) = self.stream.as_mut() else {
+            return false;
+        };
+        let poll = stream.poll();
+        for event in &poll.events {
+            self.push_event(event);
+        }
+        match poll.closed {
+            None => true,
+            Some(error) => {
+                self.stream = None;
+                if let Some(error) = error {
+                    self.body.push_str(&format!("[stream error: {}]\n", error));
+                }
+                false
+            }
+        }
+    }
+
+    /// Closes the event stream, returning whether one was open
+    pub fn stop_stream(&mut self) -> bool {
+        self.stream.take().is_some()
+    }
+
+    /// Summary for the response title, e.g. `streaming, 3 events`
+    pub fn stream_summary(&self) -> Option<String> {
+        let events = self.events?;
+        let state = if self.stream.is_some() {
+            "streaming"
+        } else {
+            "stream closed"
+        };
+        let plural = if events == 1 { "" } else { "s" };
+        Some(format!("{}, {} event{}", state, events, plural))
+    }
+
+    fn parse_parts(headers: &[(String, String)], body: &str) -> Vec<Part> {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+            .and_then(|(_, value)| multipart::boundary(value))
+            .map(|boundary| multipart::parse(body, &boundary))
+            .unwrap_or_default()
+    }
+
     fn pretty_print_json(raw_json: &str) -> Result<String, ResponseError> {
         if raw_json.trim().is_empty() {
             return Ok(String::new());
@@ -85,24 +399,37 @@ impl Response {
         Ok(headers)
     }
 
-    #[cfg(test)]
+    /// Whether the Content-Type is JSON, including `+json` types like
+    /// `application/problem+json`
     pub fn is_json(&self) -> bool {
         self.headers.iter().any(|(key, value)| {
+            let value = value.to_lowercase();
             key.to_lowercase() == "content-type"
-                && value.to_lowercase().contains("application/json")
+                && (value.contains("application/json") || value.contains("+json"))
         })
     }
 
-    #[cfg(test)]
+    pub fn is_html(&self) -> bool {
+        self.content_type()
+            .is_some_and(|value| value.to_lowercase().contains("text/html"))
+    }
+
+    /// Whether the Content-Type is XML, including `+xml` types like
+    /// `application/atom+xml`
     pub fn is_xml(&self) -> bool {
-        self.headers.iter().any(|(key, value)| {
+        Self::xml_type(&self.headers)
+    }
+
+    fn xml_type(headers: &[(String, String)]) -> bool {
+        headers.iter().any(|(key, value)| {
+            let value = value.to_lowercase();
             key.to_lowercase() == "content-type"
-                && (value.to_lowercase().contains("application/xml")
-                    || value.to_lowercase().contains("text/xml"))
+                && (value.contains("application/xml")
+                    || value.contains("text/xml")
+                    || value.contains("+xml"))
         })
     }
 
-    #[cfg(test)]
     pub fn content_type(&self) -> Option<&str> {
         self.headers
             .iter()
@@ -145,6 +472,43 @@ mod tests {
         assert!(response.is_json());
     }
 
+    #[test]
+    fn test_wire_response_is_left_alone() {
+        let response = Response::wire(
+            200,
+            "Content-Type: application/json\nContent-Encoding: gzip".to_string(),
+            br#"{"a":1}"#.to_vec(),
+        );
+        assert_eq!(response.body, r#"{"a":1}"#);
+        assert_eq!(
+            response.wire_summary().as_deref(),
+            Some("raw wire: 7 bytes, gzip")
+        );
+        assert_eq!(
+            Response::new_unchecked(200, String::new(), String::new()).wire_summary(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_stream_events_append_to_body() {
+        let mut response = Response::new_unchecked(200, String::new(), String::new());
+        assert_eq!(response.stream_summary(), None);
+        assert!(!response.poll_stream());
+
+        response.push_event(&Event {
+            event: None,
+            id: Some("1".to_string()),
+            data: "{\"n\": 1}".to_string(),
+        });
+        assert_eq!(response.body, "[message #1]\n{\"n\": 1}\n");
+        assert_eq!(
+            response.stream_summary().as_deref(),
+            Some("stream closed, 1 event")
+        );
+        assert!(!response.stop_stream());
+    }
+
     #[test]
     fn test_split_headers_with_empty_string() {
         let headers = Response::split_headers("").expect("Should handle empty string");
@@ -211,6 +575,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_xml_body_is_indented() {
+        let response = Response::new_unchecked(
+            200,
+            "Content-Type: application/soap+xml".to_string(),
+            "<env><body>ok</body></env>".to_string(),
+        );
+        assert!(response.is_xml());
+        assert_eq!(response.body, "<env>\n  <body>ok</body>\n</env>");
+
+        let broken = "<env><body>ok</env>".to_string();
+        let response =
+            Response::new(200, "Content-Type: text/xml".to_string(), broken.clone()).unwrap();
+        assert_eq!(response.body, broken);
+    }
+
+    #[test]
+    fn test_multipart_response_parts() {
+        let response = Response::new_unchecked(
+            200,
+            "Content-Type: multipart/mixed; boundary=sep".to_string(),
+            "--sep\r\nContent-Type: text/plain\r\n\r\nhi\r\n--sep--".to_string(),
+        );
+        assert_eq!(response.parts.len(), 1);
+        assert_eq!(response.parts[0].body, "hi");
+
+        let response = Response::new_unchecked(200, String::new(), "--sep".to_string());
+        assert!(response.parts.is_empty());
+    }
+
     #[test]
     fn test_content_length() {
         let response = Response::new_unchecked(
@@ -220,4 +614,65 @@ mod tests {
         );
         assert_eq!(response.content_length(), Some(123));
     }
+
+    #[test]
+    fn test_status_text() {
+        let status = |code| Response::new_unchecked(code, String::new(), String::new());
+        assert_eq!(status(200).status_text(), "200 OK");
+        assert_eq!(status(404).status_text(), "404 Not Found");
+        assert_eq!(status(599).status_text(), "599");
+    }
+
+    #[test]
+    fn test_toggle_cached() {
+        let mut response = Response::new(304, String::new(), String::new()).unwrap();
+        assert!(!response.toggle_cached());
+
+        response.cached = Cached::new(
+            "ETag: \"v1\"\nContent-Type: application/json",
+            "[{\"id\":1}]",
+            chrono::Utc::now(),
+        );
+        // Tests see the copy as the 200 it was stored from
+        let copy = response.cached_copy().unwrap();
+        assert_eq!(copy.status_code, 200);
+        assert!(copy.table.is_some());
+
+        assert!(response.toggle_cached());
+        assert!(response.cached_shown);
+        assert_eq!(response.status_code, 304);
+        assert!(response.body.contains("\"id\": 1"));
+        assert!(response.table.is_some());
+
+        assert!(response.toggle_cached());
+        assert!(!response.cached_shown);
+        assert!(response.body.is_empty());
+        assert!(response.table.is_none());
+    }
+
+    #[test]
+    fn test_transfer_summary() {
+        let transfer = Transfer::new(
+            "content-type: application/json\ncontent-encoding: gzip",
+            2150,
+            12_600,
+            Some("gzip"),
+        );
+        assert_eq!(transfer.header_size, 56);
+        assert_eq!(
+            transfer.summary(),
+            "body 12.3 KB (2.1 KB gzip), headers 56 B"
+        );
+
+        let mut bomb = Transfer::new("", 1024, 64 * 1024 * 1024, Some("gzip"));
+        bomb.truncated = true;
+        assert_eq!(
+            bomb.summary(),
+            "body 64.0 MB (1.0 KB gzip), cut short, headers 0 B"
+        );
+
+        let plain = Transfer::new("", 10, 10, Some("identity"));
+        assert_eq!(plain.summary(), "body 10 B, headers 0 B");
+        assert_eq!(format_size(3 * 1024 * 1024 + 400_000), "3.4 MB");
+    }
 }
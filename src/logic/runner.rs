@@ -0,0 +1,103 @@
+//! Sending a list of requests one after another (the runner)
+//!
+//! Every request of a collection, or of the open tabs, is sent in order and
+//! waits for the one before it, so a `set` line in one request's tests can
+//! hand a value to the requests after it. Each step keeps its status, time
+//! and test results for the summary.
+
+use crate::i18n::Catalog;
+use crate::logic::assertions::TestResult;
+use crate::storage::{RunRecord, RunStep};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// How one request of a run went
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    /// Status code, or why no response arrived
+    pub status: std::result::Result<u16, String>,
+    pub elapsed: Duration,
+    pub tests: Vec<TestResult>,
+}
+
+impl Step {
+    /// Whether a response below 400 came back and all its tests passed
+    pub fn passed(&self) -> bool {
+        matches!(self.status, Ok(code) if code < 400)
+            && self.tests.iter().all(|result| result.passed)
+    }
+}
+
+/// How many of `steps` passed, e.g. `3/4 requests passed`
+pub fn summary(steps: &[Step], i18n: &Catalog) -> String {
+    let passed = steps.iter().filter(|step| step.passed()).count();
+    i18n.format(
+        "{passed}/{total} requests passed",
+        &[("passed", &passed), ("total", &steps.len())],
+    )
+}
+
+/// The results of a finished run as kept in the history
+pub fn record(title: String, steps: &[Step], finished_at: DateTime<Utc>) -> RunRecord {
+    RunRecord {
+        finished_at,
+        title,
+        steps: steps
+            .iter()
+            .map(|step| RunStep {
+                name: step.name.clone(),
+                method: step.method.clone(),
+                url: step.url.clone(),
+                status: step.status.clone(),
+                elapsed: step.elapsed,
+                tests: step.tests.len(),
+                passed_tests: step.tests.iter().filter(|result| result.passed).count(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(status: std::result::Result<u16, String>, tests: &[bool]) -> Step {
+        Step {
+            name: "step".to_string(),
+            method: "GET".to_string(),
+            url: "http://localhost/".to_string(),
+            status,
+            elapsed: Duration::from_millis(5),
+            tests: tests
+                .iter()
+                .map(|&passed| TestResult {
+                    assertion: "status == 200".to_string(),
+                    passed,
+                    detail: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_summary() {
+        let steps = vec![
+            step(Ok(200), &[true, true]),
+            step(Ok(201), &[]),
+            step(Ok(200), &[true, false]),
+            step(Ok(404), &[]),
+            step(Err("connection refused".to_string()), &[]),
+        ];
+        let passed: Vec<bool> = steps.iter().map(Step::passed).collect();
+        assert_eq!(passed, vec![true, true, false, false, false]);
+        assert_eq!(summary(&steps, &Catalog::default()), "2/5 requests passed");
+
+        let record = record("My API".to_string(), &steps, Utc::now());
+        let recorded: Vec<bool> = record.steps.iter().map(RunStep::passed).collect();
+        assert_eq!(recorded, passed);
+        assert_eq!(record.steps[2].passed_tests, 1);
+    }
+}
@@ -0,0 +1,158 @@
+//! Searching the response body
+//!
+//! Matching is vim's smartcase: a query in lowercase matches regardless of
+//! case, one with an uppercase letter matches exactly. Matches never span
+//! lines and don't overlap.
+
+/// Lines kept above the current match when scrolling to it
+pub const CONTEXT_LINES: usize = 3;
+
+/// A match of the search query, as a byte range within one line
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Search {
+    pub query: String,
+    /// Index of the current match
+    pub current: usize,
+}
+
+impl Search {
+    pub fn new(query: String) -> Search {
+        Search { query, current: 0 }
+    }
+
+    /// Every match in the text, in order
+    pub fn matches(&self, text: &str) -> Vec<Match> {
+        text.lines()
+            .enumerate()
+            .flat_map(|(line, content)| {
+                self.line_matches(content)
+                    .into_iter()
+                    .map(move |(start, end)| Match { line, start, end })
+            })
+            .collect()
+    }
+
+    /// Byte ranges of the matches within a single line
+    fn line_matches(&self, line: &str) -> Vec<(usize, usize)> {
+        if self.query.is_empty() {
+            return Vec::new();
+        }
+        let exact = self.query.chars().any(char::is_uppercase);
+        // ASCII-only folding keeps byte offsets the same in both strings
+        let (haystack, needle) = if exact {
+            (line.to_string(), self.query.clone())
+        } else {
+            (line.to_ascii_lowercase(), self.query.to_ascii_lowercase())
+        };
+        haystack
+            .match_indices(&needle)
+            .map(|(start, found)| (start, start + found.len()))
+            .collect()
+    }
+
+    /// Moves to the first match at or after a line, wrapping around
+    pub fn jump_from(&mut self, text: &str, line: usize) -> Option<Match> {
+        let matches = self.matches(text);
+        self.current = matches
+            .iter()
+            .position(|found| found.line >= line)
+            .unwrap_or(0);
+        matches.get(self.current).copied()
+    }
+
+    /// Moves to the next match, or the previous one when `back` is set,
+    /// wrapping around at either end
+    pub fn step(&mut self, text: &str, back: bool) -> Option<Match> {
+        let matches = self.matches(text);
+        if matches.is_empty() {
+            return None;
+        }
+        let current = self.current.min(matches.len() - 1);
+        self.current = if back {
+            (current + matches.len() - 1) % matches.len()
+        } else {
+            (current + 1) % matches.len()
+        };
+        matches.get(self.current).copied()
+    }
+
+    /// Position among the matches, e.g. `/token 3/12`
+    pub fn summary(&self, text: &str) -> String {
+        let total = self.matches(text).len();
+        if total == 0 {
+            format!("/{} no matches", self.query)
+        } else {
+            format!(
+                "/{} {}/{}",
+                self.query,
+                self.current.min(total - 1) + 1,
+                total
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = "{\n  \"token\": \"abc\",\n  \"Token_type\": \"bearer\",\n  \"id\": 1\n}";
+
+    #[test]
+    fn test_smartcase_matches() {
+        let search = Search::new("token".to_string());
+        let matches = search.matches(BODY);
+        assert_eq!(
+            matches,
+            vec![
+                Match {
+                    line: 1,
+                    start: 3,
+                    end: 8
+                },
+                Match {
+                    line: 2,
+                    start: 3,
+                    end: 8
+                },
+            ]
+        );
+        assert_eq!(Search::new("Token".to_string()).matches(BODY).len(), 1);
+        assert!(Search::new(String::new()).matches(BODY).is_empty());
+        assert_eq!(
+            Search::new("é".to_string()).line_matches("café, CAFÉ"),
+            vec![(3, 5)]
+        );
+    }
+
+    #[test]
+    fn test_jump_and_step() {
+        let mut search = Search::new("\"".to_string());
+        // Five quoted strings, two quotes each
+        assert_eq!(search.matches(BODY).len(), 10);
+        assert_eq!(search.jump_from(BODY, 2).unwrap().line, 2);
+        assert_eq!(search.current, 4);
+        assert_eq!(search.summary(BODY), "/\" 5/10");
+
+        assert_eq!(search.step(BODY, true).unwrap().line, 1);
+        search.current = 9;
+        assert_eq!(search.step(BODY, false).unwrap().line, 1);
+        assert_eq!(search.current, 0);
+        assert_eq!(search.step(BODY, true).unwrap().line, 3);
+
+        // Past the last match wraps to the first
+        assert_eq!(search.jump_from(BODY, 4).unwrap().line, 1);
+
+        let mut missing = Search::new("nope".to_string());
+        assert_eq!(missing.jump_from(BODY, 0), None);
+        assert_eq!(missing.step(BODY, false), None);
+        assert_eq!(missing.summary(BODY), "/nope no matches");
+    }
+}
@@ -0,0 +1,291 @@
+//! Secrets fetched from password managers at send time
+//!
+//! A `{{secret:<reference>}}` placeholder, typically used as the value of an
+//! environment variable, is replaced by asking a provider for the secret:
+//!
+//! - `op://vault/item/field` — 1Password CLI (`op read`)
+//! - `pass://path/to/entry` — `pass show`, first line of the entry
+//! - `env://NAME` — a process environment variable
+//!
+//! Secrets are only looked up right before a request is sent and only live in
+//! the request copy that goes out; they're never written to tabs or disk.
+//! The lookup runs in the background send, never on the interface thread,
+//! since a provider may take a while or wait for its own unlock prompt.
+
+use crate::error::{RestlessError, Result};
+use crate::logic::request::Request;
+use std::collections::HashMap;
+use std::process::{Command, Stdio};
+
+/// Placeholder prefix marking a secret reference
+pub const PREFIX: &str = "secret:";
+
+/// A backend that can fetch secrets for one reference scheme
+pub trait SecretProvider {
+    /// Scheme handled by this provider, e.g. `op` for `op://…`
+    fn scheme(&self) -> &'static str;
+
+    /// Fetches the secret for a full reference such as `op://vault/item/field`
+    fn fetch(&self, reference: &str) -> Result<String>;
+}
+
+/// 1Password via its CLI
+pub struct OnePassword;
+
+impl SecretProvider for OnePassword {
+    fn scheme(&self) -> &'static str {
+        "op"
+    }
+
+    fn fetch(&self, reference: &str) -> Result<String> {
+        run("op", &["read", "--no-newline", reference])
+    }
+}
+
+/// The standard unix password manager
+pub struct Pass;
+
+impl SecretProvider for Pass {
+    fn scheme(&self) -> &'static str {
+        "pass"
+    }
+
+    fn fetch(&self, reference: &str) -> Result<String> {
+        let entry = strip_scheme(reference, "pass");
+        let output = run("pass", &["show", entry])?;
+        Ok(output.lines().next().unwrap_or_default().to_string())
+    }
+}
+
+/// Variables of the restless process itself
+pub struct EnvVar;
+
+impl SecretProvider for EnvVar {
+    fn scheme(&self) -> &'static str {
+        "env"
+    }
+
+    fn fetch(&self, reference: &str) -> Result<String> {
+        let name = strip_scheme(reference, "env");
+        std::env::var(name).map_err(|_| {
+            RestlessError::configuration(format!("Environment variable {} is not set", name))
+        })
+    }
+}
+
+/// The built-in providers
+pub fn providers() -> Vec<Box<dyn SecretProvider>> {
+    vec![Box::new(OnePassword), Box::new(Pass), Box::new(EnvVar)]
+}
+
+/// Fetches one secret with whichever provider handles its scheme
+pub fn fetch(reference: &str, providers: &[Box<dyn SecretProvider>]) -> Result<String> {
+    let scheme = reference.split_once("://").map(|(scheme, _)| scheme);
+    let provider = providers
+        .iter()
+        .find(|provider| Some(provider.scheme()) == scheme)
+        .ok_or_else(|| {
+            let schemes: Vec<_> = providers
+                .iter()
+                .map(|p| format!("{}://", p.scheme()))
+                .collect();
+            RestlessError::configuration(format!(
+                "Unknown secret reference {} (expected {})",
+                reference,
+                schemes.join(", ")
+            ))
+        })?;
+    provider.fetch(reference).map_err(|e| {
+        RestlessError::configuration(format!("Could not read secret {}: {}", reference, e))
+    })
+}
+
+/// Replaces every `{{secret:…}}` placeholder in the text using `lookup`
+pub fn substitute<F>(text: &str, lookup: &mut F) -> Result<String>
+where
+    F: FnMut(&str) -> Result<String>,
+{
+    let mut result = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let inner = rest[start + 2..start + 2 + end].trim();
+        match inner.strip_prefix(PREFIX) {
+            Some(reference) => {
+                result.push_str(&rest[..start]);
+                result.push_str(&lookup(reference.trim())?);
+            }
+            None => result.push_str(&rest[..start + 2 + end + 2]),
+        }
+        rest = &rest[start + 2 + end + 2..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Resolves the secrets of a request that's about to be sent
+///
+/// Each reference is fetched once, even if it's used in several places.
+pub fn resolve_request(request: &mut Request, providers: &[Box<dyn SecretProvider>]) -> Result<()> {
+    let mut cache: HashMap<String, String> = HashMap::new();
+    let mut lookup = |reference: &str| -> Result<String> {
+        if let Some(value) = cache.get(reference) {
+            return Ok(value.clone());
+        }
+        let value = fetch(reference, providers)?;
+        cache.insert(reference.to_string(), value.clone());
+        Ok(value)
+    };
+
+    request.url = substitute(&request.url, &mut lookup)?;
+    for (key, value) in request.headers.iter_mut().chain(request.params.iter_mut()) {
+        *key = substitute(key, &mut lookup)?;
+        *value = substitute(value, &mut lookup)?;
+    }
+    if let Some(body) = &request.body {
+        request.body = Some(substitute(body, &mut lookup)?);
+    }
+    for (_, value) in request.form.iter_mut().flatten() {
+        *value = substitute(value, &mut lookup)?;
+    }
+    for (_, value) in request.auth.fields.iter_mut() {
+        *value = substitute(value, &mut lookup)?;
+    }
+    Ok(())
+}
+
+/// Resolves the secrets of `request` with the built-in providers on a
+/// blocking thread, for use inside a send task
+pub async fn resolve(mut request: Request) -> Result<Request> {
+    tokio::task::spawn_blocking(move || {
+        resolve_request(&mut request, &providers())?;
+        Ok(request)
+    })
+    .await
+    .map_err(|e| RestlessError::configuration(format!("Could not read secrets: {}", e)))?
+}
+
+fn strip_scheme<'a>(reference: &'a str, scheme: &str) -> &'a str {
+    reference
+        .strip_prefix(scheme)
+        .and_then(|rest| rest.strip_prefix("://"))
+        .unwrap_or(reference)
+}
+
+fn run(program: &str, args: &[&str]) -> Result<String> {
+    // Nothing to type into: the terminal belongs to the interface
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| RestlessError::configuration(format!("Could not run {}: {}", program, e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(RestlessError::configuration(format!(
+            "{} failed: {}",
+            program,
+            stderr.trim()
+        )));
+    }
+    String::from_utf8(output.stdout)
+        .map_err(|_| RestlessError::configuration(format!("{} returned non-UTF-8 output", program)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fake;
+
+    impl SecretProvider for Fake {
+        fn scheme(&self) -> &'static str {
+            "fake"
+        }
+
+        fn fetch(&self, reference: &str) -> Result<String> {
+            Ok(format!("<{}>", strip_scheme(reference, "fake")))
+        }
+    }
+
+    #[test]
+    fn test_substitute_only_touches_secrets() {
+        let mut lookup = |reference: &str| Ok(format!("[{}]", reference));
+        let text = "Bearer {{ secret:op://dev/api/token }} for {{user}}";
+        assert_eq!(
+            substitute(text, &mut lookup).unwrap(),
+            "Bearer [op://dev/api/token] for {{user}}"
+        );
+    }
+
+    #[test]
+    fn test_fetch_picks_provider_by_scheme() {
+        let providers: Vec<Box<dyn SecretProvider>> = vec![Box::new(Fake), Box::new(EnvVar)];
+        assert_eq!(fetch("fake://a/b", &providers).unwrap(), "<a/b>");
+
+        std::env::set_var("RESTLESS_SECRET_TEST", "s3cret");
+        assert_eq!(
+            fetch("env://RESTLESS_SECRET_TEST", &providers).unwrap(),
+            "s3cret"
+        );
+        assert!(fetch("env://RESTLESS_SECRET_TEST_MISSING", &providers).is_err());
+
+        let error = fetch("vault://x", &providers).unwrap_err().to_string();
+        assert!(error.contains("fake://, env://"), "{}", error);
+    }
+
+    #[test]
+    fn test_resolve_request_fetches_each_reference_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        struct Counting(Arc<AtomicUsize>);
+
+        impl SecretProvider for Counting {
+            fn scheme(&self) -> &'static str {
+                "count"
+            }
+
+            fn fetch(&self, reference: &str) -> Result<String> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Ok(strip_scheme(reference, "count").to_uppercase())
+            }
+        }
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let providers: Vec<Box<dyn SecretProvider>> = vec![Box::new(Counting(calls.clone()))];
+        let mut request = Request {
+            url: "https://api.test/?k={{secret:count://k}}".to_string(),
+            headers: vec![("X-Key".to_string(), "{{secret:count://k}}".to_string())],
+            body: Some("{\"token\": \"{{secret:count://other}}\"}".to_string()),
+            ..Default::default()
+        };
+
+        resolve_request(&mut request, &providers).unwrap();
+        assert_eq!(request.url, "https://api.test/?k=K");
+        assert_eq!(request.headers[0].1, "K");
+        assert_eq!(request.body.as_deref(), Some("{\"token\": \"OTHER\"}"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_in_background() {
+        std::env::set_var("RESTLESS_SECRET_RESOLVE_TEST", "t0ken");
+        let request = Request {
+            url: "https://api.test/?k={{secret:env://RESTLESS_SECRET_RESOLVE_TEST}}".to_string(),
+            ..Default::default()
+        };
+        let resolved = resolve(request).await.unwrap();
+        assert_eq!(resolved.url, "https://api.test/?k=t0ken");
+
+        let missing = Request {
+            url: "https://api.test/?k={{secret:env://RESTLESS_SECRET_RESOLVE_MISSING}}".to_string(),
+            ..Default::default()
+        };
+        let error = resolve(missing).await.unwrap_err().to_string();
+        assert!(error.contains("Could not read secret"), "{}", error);
+    }
+}
@@ -0,0 +1,372 @@
+//! Request signing for HMAC and AWS Signature Version 4 auth
+//!
+//! Signatures depend on the final method, URL and body, so they are computed
+//! right before sending rather than in [`Auth::resolve`]. The intermediate
+//! strings are kept in a [`Signature`] so they can be previewed when a server
+//! rejects a signature and the canonical forms need comparing.
+//!
+//! The HMAC scheme signs `METHOD\npath?query\ntimestamp\nhex(sha256(body))`
+//! and sends the result in `X-Signature` (configurable with `header`) along
+//! with `X-Timestamp`.
+
+use crate::error::RequestError;
+use crate::logic::auth::{Auth, AuthKind};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::{Method, Url};
+use sha2::{Digest, Sha256, Sha512};
+
+/// The result of signing a request, with the inputs that produced it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Signature {
+    /// Only SigV4 has a separate canonical request
+    pub canonical_request: Option<String>,
+    pub string_to_sign: String,
+    pub headers: Vec<(String, String)>,
+}
+
+/// Signs a request whose auth type requires it; other auth types yield `None`
+///
+/// `url` must already contain every query parameter that will be sent.
+pub fn sign(
+    auth: &Auth,
+    method: &Method,
+    url: &str,
+    body: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<Option<Signature>, RequestError> {
+    match auth.kind {
+        AuthKind::HmacSignature => {
+            auth.validate()?;
+            sign_hmac(auth, method, &parse_url(url)?, body, now).map(Some)
+        }
+        AuthKind::AwsSigV4 => {
+            auth.validate()?;
+            Ok(Some(sign_sigv4(auth, method, &parse_url(url)?, body, now)))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn parse_url(url: &str) -> Result<Url, RequestError> {
+    Url::parse(url).map_err(|e| RequestError::invalid_url(format!("{}: {}", url, e)))
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn sign_hmac(
+    auth: &Auth,
+    method: &Method,
+    url: &Url,
+    body: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<Signature, RequestError> {
+    let secret = auth.field("secret").unwrap_or_default().as_bytes();
+    let timestamp = now.timestamp().to_string();
+    let mut target = url.path().to_string();
+    if let Some(query) = url.query() {
+        target.push('?');
+        target.push_str(query);
+    }
+    let string_to_sign = format!(
+        "{}\n{}\n{}\n{}",
+        method,
+        target,
+        timestamp,
+        sha256_hex(body.unwrap_or_default().as_bytes())
+    );
+
+    let digest = match auth.field("algorithm").map(str::to_lowercase).as_deref() {
+        None | Some("sha256") => hmac_sha256(secret, string_to_sign.as_bytes()),
+        Some("sha512") => hmac_sha512(secret, string_to_sign.as_bytes()),
+        Some(other) => {
+            return Err(RequestError::authentication(format!(
+                "Unknown HMAC algorithm '{}' (use sha256 or sha512)",
+                other
+            )))
+        }
+    };
+    let signature = match auth.field("encoding").map(str::to_lowercase).as_deref() {
+        None | Some("hex") => hex::encode(digest),
+        Some("base64") => base64::engine::general_purpose::STANDARD.encode(digest),
+        Some(other) => {
+            return Err(RequestError::authentication(format!(
+                "Unknown signature encoding '{}' (use hex or base64)",
+                other
+            )))
+        }
+    };
+
+    let mut headers = vec![("X-Timestamp".to_string(), timestamp)];
+    if let Some(key_id) = auth.field("key_id") {
+        headers.push(("X-Key-Id".to_string(), key_id.to_string()));
+    }
+    headers.push((
+        auth.field("header").unwrap_or("X-Signature").to_string(),
+        signature,
+    ));
+
+    Ok(Signature {
+        canonical_request: None,
+        string_to_sign,
+        headers,
+    })
+}
+
+/// Derives the SigV4 signing key for a date, region and service
+fn sigv4_signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let k_date = hmac_sha256(format!("AWS4{}", secret).as_bytes(), date.as_bytes());
+    let k_region = hmac_sha256(&k_date, region.as_bytes());
+    let k_service = hmac_sha256(&k_region, service.as_bytes());
+    hmac_sha256(&k_service, b"aws4_request")
+}
+
+fn sign_sigv4(
+    auth: &Auth,
+    method: &Method,
+    url: &Url,
+    body: Option<&str>,
+    now: DateTime<Utc>,
+) -> Signature {
+    let access_key = auth.field("access_key").unwrap_or_default();
+    let secret_key = auth.field("secret_key").unwrap_or_default();
+    let region = auth.field("region").unwrap_or_default();
+    let service = auth.field("service").unwrap_or_default();
+
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let payload_hash = sha256_hex(body.unwrap_or_default().as_bytes());
+
+    let mut host = url.host_str().unwrap_or_default().to_string();
+    if let Some(port) = url.port() {
+        host.push_str(&format!(":{}", port));
+    }
+
+    // Each path segment is URI-encoded once, from its decoded form
+    let canonical_uri = url
+        .path()
+        .split('/')
+        .map(|segment| {
+            let decoded = urlencoding::decode(segment)
+                .map(|s| s.into_owned())
+                .unwrap_or_else(|_| segment.to_string());
+            urlencoding::encode(&decoded).into_owned()
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let mut query: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| {
+            (
+                urlencoding::encode(&k).into_owned(),
+                urlencoding::encode(&v).into_owned(),
+            )
+        })
+        .collect();
+    query.sort();
+    let canonical_query = query
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let mut signed: Vec<(&str, String)> = vec![
+        ("host", host),
+        ("x-amz-content-sha256", payload_hash.clone()),
+        ("x-amz-date", amz_date.clone()),
+    ];
+    if let Some(token) = auth.field("session_token") {
+        signed.push(("x-amz-security-token", token.to_string()));
+    }
+    let canonical_headers: String = signed
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = signed
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(";");
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        method, canonical_uri, canonical_query, canonical_headers, signed_headers, payload_hash
+    );
+    let scope = format!("{}/{}/{}/aws4_request", date, region, service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        sha256_hex(canonical_request.as_bytes())
+    );
+    let signature = hex::encode(hmac_sha256(
+        &sigv4_signing_key(secret_key, &date, region, service),
+        string_to_sign.as_bytes(),
+    ));
+
+    let mut headers = vec![
+        (
+            "Authorization".to_string(),
+            format!(
+                "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+                access_key, scope, signed_headers, signature
+            ),
+        ),
+        ("X-Amz-Date".to_string(), amz_date),
+        ("X-Amz-Content-Sha256".to_string(), payload_hash),
+    ];
+    if let Some(token) = auth.field("session_token") {
+        headers.push(("X-Amz-Security-Token".to_string(), token.to_string()));
+    }
+
+    Signature {
+        canonical_request: Some(canonical_request),
+        string_to_sign,
+        headers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn auth(kind: AuthKind, fields: &[(&str, &str)]) -> Auth {
+        Auth {
+            kind,
+            fields: fields
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap()
+    }
+
+    #[test]
+    fn test_sigv4_signing_key() {
+        // Example from the AWS Signature Version 4 documentation
+        let key = sigv4_signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex::encode(key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn test_sigv4_canonical_forms() {
+        let auth = auth(
+            AuthKind::AwsSigV4,
+            &[
+                ("access_key", "AKIDEXAMPLE"),
+                ("secret_key", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY"),
+                ("region", "us-east-1"),
+                ("service", "iam"),
+            ],
+        );
+        let signature = sign(
+            &auth,
+            &Method::GET,
+            "https://iam.amazonaws.com/a b/?Version=2010-05-08&Action=ListUsers",
+            None,
+            now(),
+        )
+        .unwrap()
+        .unwrap();
+
+        let empty_hash = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        assert_eq!(
+            signature.canonical_request.as_deref(),
+            Some(
+                format!(
+                    "GET\n/a%20b/\nAction=ListUsers&Version=2010-05-08\nhost:iam.amazonaws.com\n\
+                     x-amz-content-sha256:{h}\nx-amz-date:20150830T123600Z\n\n\
+                     host;x-amz-content-sha256;x-amz-date\n{h}",
+                    h = empty_hash
+                )
+                .as_str()
+            )
+        );
+        assert!(signature.string_to_sign.starts_with(
+            "AWS4-HMAC-SHA256\n20150830T123600Z\n20150830/us-east-1/iam/aws4_request\n"
+        ));
+        assert!(signature.headers[0].1.starts_with(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/iam/aws4_request, \
+             SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature="
+        ));
+    }
+
+    #[test]
+    fn test_hmac_signature() {
+        let auth = auth(
+            AuthKind::HmacSignature,
+            &[("secret", "key"), ("key_id", "client-1")],
+        );
+        let signature = sign(
+            &auth,
+            &Method::POST,
+            "https://api.example.com/orders?x=1",
+            Some("{}"),
+            now(),
+        )
+        .unwrap()
+        .unwrap();
+
+        let expected_sts = format!("POST\n/orders?x=1\n1440938160\n{}", sha256_hex(b"{}"));
+        assert_eq!(signature.string_to_sign, expected_sts);
+        assert_eq!(
+            signature.headers,
+            vec![
+                ("X-Timestamp".to_string(), "1440938160".to_string()),
+                ("X-Key-Id".to_string(), "client-1".to_string()),
+                (
+                    "X-Signature".to_string(),
+                    hex::encode(hmac_sha256(b"key", expected_sts.as_bytes()))
+                ),
+            ]
+        );
+
+        let mut auth = auth;
+        auth.set_field("encoding", "base64");
+        auth.set_field("algorithm", "sha512");
+        let signature = sign(&auth, &Method::GET, "https://a.test/", None, now())
+            .unwrap()
+            .unwrap();
+        assert_eq!(signature.headers[2].1.len(), 88);
+
+        auth.set_field("algorithm", "md5");
+        assert!(sign(&auth, &Method::GET, "https://a.test/", None, now()).is_err());
+    }
+
+    #[test]
+    fn test_non_signing_auth_is_skipped() {
+        let auth = Auth::default();
+        assert_eq!(
+            sign(&auth, &Method::GET, "https://a.test/", None, now()).unwrap(),
+            None
+        );
+    }
+}
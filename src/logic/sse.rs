@@ -0,0 +1,227 @@
+//! Server-Sent Events (`text/event-stream`) responses
+//!
+//! An event stream never really ends, so instead of waiting for the body the
+//! connection is handed to a background task that parses events as they
+//! arrive and passes them on through a channel. The response view drains it
+//! on every redraw.
+
+use crate::logic::compression::Decoder;
+use tokio::sync::mpsc::{self, error::TryRecvError, UnboundedReceiver};
+use tokio::task::JoinHandle;
+
+/// One dispatched event
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Event {
+    /// The `event:` field; `None` means the default `message` type
+    pub event: Option<String>,
+    pub id: Option<String>,
+    pub data: String,
+}
+
+impl Event {
+    /// The event as shown in the response body: a `[type #id]` line followed
+    /// by the data
+    pub fn to_text(&self) -> String {
+        let mut header = format!("[{}", self.event.as_deref().unwrap_or("message"));
+        if let Some(id) = &self.id {
+            header.push_str(&format!(" #{}", id));
+        }
+        format!("{}]\n{}\n", header, self.data)
+    }
+}
+
+/// Whether a `Content-Type` value announces an event stream
+pub fn is_event_stream(content_type: &str) -> bool {
+    content_type
+        .split(';')
+        .next()
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("text/event-stream"))
+}
+
+/// Incremental parser for the event stream format
+///
+/// Chunks may end anywhere, even inside a UTF-8 sequence; only complete lines
+/// are parsed.
+#[derive(Debug, Default)]
+pub struct Parser {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Option<String>,
+    /// The last event ID persists across events, as in the spec
+    last_id: Option<String>,
+}
+
+impl Parser {
+    /// Parses a chunk, returning the events it completed
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<Event> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line[..end]);
+            if let Some(event) = self.line(line.strip_suffix('\r').unwrap_or(&line)) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    fn line(&mut self, line: &str) -> Option<Event> {
+        if line.is_empty() {
+            let event = self.event.take();
+            let mut data = self.data.take()?;
+            if data.ends_with('\n') {
+                data.pop();
+            }
+            return Some(Event {
+                event,
+                id: self.last_id.clone(),
+                data,
+            });
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "data" => {
+                let data = self.data.get_or_insert_with(String::new);
+                data.push_str(value);
+                data.push('\n');
+            }
+            "event" => self.event = Some(value.to_string()),
+            "id" if !value.contains('\0') => self.last_id = Some(value.to_string()),
+            _ => {}
+        }
+        None
+    }
+}
+
+/// An event stream being read in the background
+///
+/// Dropping it closes the connection.
+#[derive(Debug)]
+pub struct EventStream {
+    receiver: UnboundedReceiver<Result<Event, String>>,
+    task: JoinHandle<()>,
+}
+
+/// What [`EventStream::poll`] found
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Poll {
+    pub events: Vec<Event>,
+    /// Why the stream ended, if it did: `None` for a clean close
+    pub closed: Option<Option<String>>,
+}
+
+impl EventStream {
+    /// Starts reading events from a response in the background
+    pub fn spawn(mut response: reqwest::Response, mut decoder: Decoder) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let task = tokio::spawn(async move {
+            let mut parser = Parser::default();
+            loop {
+                match response.chunk().await {
+                    Ok(Some(chunk)) => {
+                        let chunk = match decoder.push(&chunk) {
+                            Ok(chunk) => chunk,
+                            Err(e) => {
+                                let _ = sender.send(Err(e.to_string()));
+                                return;
+                            }
+                        };
+                        for event in parser.feed(&chunk) {
+                            if sender.send(Ok(event)).is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    Ok(None) => return,
+                    Err(e) => {
+                        let _ = sender.send(Err(e.to_string()));
+                        return;
+                    }
+                }
+            }
+        });
+        EventStream { receiver, task }
+    }
+
+    /// Takes the events that arrived since the last poll without waiting
+    pub fn poll(&mut self) -> Poll {
+        let mut poll = Poll::default();
+        loop {
+            match self.receiver.try_recv() {
+                Ok(Ok(event)) => poll.events.push(event),
+                Ok(Err(error)) => poll.closed = Some(Some(error)),
+                Err(TryRecvError::Empty) => return poll,
+                Err(TryRecvError::Disconnected) => {
+                    poll.closed.get_or_insert(None);
+                    return poll;
+                }
+            }
+        }
+    }
+
+    /// Waits for the next event; `None` once the stream has ended
+    pub async fn next(&mut self) -> Option<Result<Event, String>> {
+        self.receiver.recv().await
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        let mut parser = Parser::default();
+        assert!(parser.feed(b": keep-alive\n\ndata: first").is_empty());
+        let events = parser.feed(b"\ndata:  second line\r\n\nevent: update\nid: 7\ndata: {}\n\n");
+        assert_eq!(
+            events,
+            vec![
+                Event {
+                    event: None,
+                    id: None,
+                    data: "first\n second line".to_string(),
+                },
+                Event {
+                    event: Some("update".to_string()),
+                    id: Some("7".to_string()),
+                    data: "{}".to_string(),
+                },
+            ]
+        );
+
+        // The ID carries over, a split UTF-8 character survives and an event
+        // without data isn't dispatched
+        let mut events = parser.feed(b"event: empty\n\ndata: caf\xc3");
+        events.extend(parser.feed(b"\xa9\n\n"));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "café");
+        assert_eq!(events[0].event, None);
+        assert_eq!(events[0].id.as_deref(), Some("7"));
+    }
+
+    #[test]
+    fn test_event_text() {
+        let event = Event {
+            event: Some("update".to_string()),
+            id: Some("3".to_string()),
+            data: "a\nb".to_string(),
+        };
+        assert_eq!(event.to_text(), "[update #3]\na\nb\n");
+        assert_eq!(Event::default().to_text(), "[message]\n\n");
+        assert!(is_event_stream("text/event-stream; charset=utf-8"));
+        assert!(!is_event_stream("text/plain"));
+    }
+}
@@ -0,0 +1,200 @@
+//! Table view of JSON arrays of objects
+//!
+//! Result lists like `[{"id": 1, "name": "a"}, …]` read far better as rows
+//! and columns than as pretty printed JSON. Columns are the object keys in
+//! the order they first appear; nested values are shown as compact JSON.
+
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// Widest a column gets before its cells are cut off
+pub const MAX_COLUMN_WIDTH: usize = 40;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Table {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// Column the rows are sorted by and whether descending
+    pub sorted_by: Option<(usize, bool)>,
+}
+
+impl Table {
+    /// Builds a table when the body is a non-empty JSON array of objects
+    pub fn from_json(body: &str) -> Option<Table> {
+        let Value::Array(items) = serde_json::from_str::<Value>(body.trim()).ok()? else {
+            return None;
+        };
+        if items.is_empty() || !items.iter().all(Value::is_object) {
+            return None;
+        }
+
+        let mut columns: Vec<String> = Vec::new();
+        for item in &items {
+            for key in item.as_object()?.keys() {
+                if !columns.contains(key) {
+                    columns.push(key.clone());
+                }
+            }
+        }
+        let rows = items
+            .iter()
+            .map(|item| {
+                columns
+                    .iter()
+                    .map(|column| item.get(column).map(cell).unwrap_or_default())
+                    .collect()
+            })
+            .collect();
+
+        Some(Table {
+            columns,
+            rows,
+            sorted_by: None,
+        })
+    }
+
+    /// Sorts by a column, ascending first and descending when sorted again
+    ///
+    /// Numbers compare by value and come before text; empty cells go last.
+    pub fn sort_by(&mut self, column: usize) {
+        if column >= self.columns.len() {
+            return;
+        }
+        let descending = self.sorted_by == Some((column, false));
+        self.rows.sort_by(|a, b| {
+            let (a, b) = (&a[column], &b[column]);
+            if a.is_empty() || b.is_empty() {
+                return b.is_empty().cmp(&a.is_empty()).reverse();
+            }
+            let ordering = compare(a, b);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        self.sorted_by = Some((column, descending));
+    }
+
+    /// The table as CSV, in the current row order
+    pub fn to_csv(&self) -> String {
+        std::iter::once(&self.columns)
+            .chain(&self.rows)
+            .map(|row| {
+                row.iter()
+                    .map(|cell| csv_field(cell))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .map(|line| line + "\r\n")
+            .collect()
+    }
+
+    /// Display width of each column, capped at [`MAX_COLUMN_WIDTH`]
+    pub fn widths(&self) -> Vec<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                self.rows
+                    .iter()
+                    .map(|row| row[index].chars().count())
+                    .chain([column.chars().count() + 2])
+                    .max()
+                    .unwrap_or_default()
+                    .min(MAX_COLUMN_WIDTH)
+            })
+            .collect()
+    }
+}
+
+fn compare(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_json() {
+        let table = Table::from_json(
+            r#"[{"id": 2, "name": "b", "tags": ["x"]}, {"id": 1, "name": null, "extra": true}]"#,
+        )
+        .unwrap();
+        assert_eq!(table.columns, vec!["id", "name", "tags", "extra"]);
+        assert_eq!(table.rows[0], vec!["2", "b", "[\"x\"]", ""]);
+        assert_eq!(table.rows[1], vec!["1", "", "", "true"]);
+
+        assert!(Table::from_json("[]").is_none());
+        assert!(Table::from_json("[1, 2]").is_none());
+        assert!(Table::from_json(r#"{"id": 1}"#).is_none());
+        assert!(Table::from_json("not json").is_none());
+    }
+
+    #[test]
+    fn test_sort_and_widths() {
+        let mut table =
+            Table::from_json(r#"[{"n": "b"}, {"n": "a"}, {"n": "a very long value"}]"#).unwrap();
+        table.sort_by(0);
+        assert_eq!(table.rows[0][0], "a");
+        assert_eq!(table.sorted_by, Some((0, false)));
+        table.sort_by(0);
+        assert_eq!(table.rows[0][0], "b");
+        assert_eq!(table.sorted_by, Some((0, true)));
+        table.sort_by(5);
+        assert_eq!(table.sorted_by, Some((0, true)));
+
+        assert_eq!(table.widths(), vec![17]);
+    }
+
+    #[test]
+    fn test_sort_numbers_before_text() {
+        let mut table = Table::from_json(
+            r#"[{"v": 10}, {"v": "x"}, {"v": null}, {"v": 9.5}, {"v": -1}, {"v": "b"}]"#,
+        )
+        .unwrap();
+        let column = |table: &Table| -> Vec<String> {
+            table.rows.iter().map(|row| row[0].clone()).collect()
+        };
+
+        table.sort_by(0);
+        assert_eq!(column(&table), vec!["-1", "9.5", "10", "b", "x", ""]);
+        table.sort_by(0);
+        assert_eq!(column(&table), vec!["x", "b", "10", "9.5", "-1", ""]);
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let table = Table::from_json(
+            r#"[{"id": 1, "note": "plain"}, {"id": 2, "note": "has, comma and \"quotes\""}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            table.to_csv(),
+            "id,note\r\n1,plain\r\n2,\"has, comma and \"\"quotes\"\"\"\r\n"
+        );
+    }
+}
@@ -0,0 +1,622 @@
+//! TLS connection details of an HTTPS response
+//!
+//! The server certificate comes from the connection the request actually
+//! used. The negotiated protocol and cipher suite aren't exposed by the HTTP
+//! client, so they're looked up on demand with a separate handshake against
+//! the same host (only possible where the system TLS library is OpenSSL).
+//! The handshake runs in the background with the connection settings of the
+//! send: through the same proxy, trusting the same CA bundle, presenting the
+//! same client certificate and within the same TLS versions.
+
+use crate::logic::options::RequestOptions;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use tokio::sync::oneshot::{self, error::TryRecvError};
+
+/// What the TLS tab of the response shows
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TlsDetails {
+    /// Host and port the request connected to
+    pub host: String,
+    pub port: u16,
+    /// Negotiated protocol, e.g. `TLSv1.3`, once probed
+    pub protocol: Option<String>,
+    /// Negotiated cipher suite, once probed
+    pub cipher: Option<String>,
+    /// Why the protocol and cipher are unknown, if probing failed
+    pub probe_error: Option<String>,
+    pub certificate: Option<CertificateInfo>,
+    /// Fingerprint the active environment pins for this host
+    pub pinned: Option<String>,
+    /// Connection settings of the send, for the probe to use as well
+    pub options: RequestOptions,
+    /// Whether the protocol and cipher are being looked up
+    pub probing: bool,
+}
+
+/// The parts of the server certificate worth looking at when debugging
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: DateTime<Utc>,
+    pub not_after: DateTime<Utc>,
+    /// DNS names from the subject alternative name extension
+    pub alt_names: Vec<String>,
+    /// SHA-256 of the DER encoding, as colon separated hex
+    pub fingerprint: String,
+}
+
+impl CertificateInfo {
+    /// Parses a DER encoded certificate
+    pub fn from_der(der: &[u8]) -> Option<Self> {
+        use x509_parser::prelude::*;
+
+        let (_, cert) = X509Certificate::from_der(der).ok()?;
+        let timestamp = |time: ASN1Time| DateTime::from_timestamp(time.timestamp(), 0);
+        let alt_names = cert
+            .subject_alternative_name()
+            .ok()
+            .flatten()
+            .map(|san| {
+                san.value
+                    .general_names
+                    .iter()
+                    .filter_map(|name| match name {
+                        GeneralName::DNSName(dns) => Some(dns.to_string()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(CertificateInfo {
+            subject: cert.subject().to_string(),
+            issuer: cert.issuer().to_string(),
+            not_before: timestamp(cert.validity().not_before)?,
+            not_after: timestamp(cert.validity().not_after)?,
+            alt_names,
+            fingerprint: fingerprint(der),
+        })
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now > self.not_after
+    }
+
+    /// Expiry in words, e.g. `expires in 42 days` or `expired 3 days ago`
+    pub fn expiry_summary(&self, now: DateTime<Utc>) -> String {
+        let days = (self.not_after - now).num_days();
+        if self.is_expired(now) {
+            format!("expired {} days ago", -days)
+        } else {
+            format!("expires in {} days", days)
+        }
+    }
+}
+
+impl TlsDetails {
+    /// Details for a response received from `url` with the given certificate
+    /// over a connection made with `options`
+    pub fn new(
+        url: &reqwest::Url,
+        certificate_der: Option<&[u8]>,
+        options: &RequestOptions,
+    ) -> Option<Self> {
+        if url.scheme() != "https" {
+            return None;
+        }
+        Some(TlsDetails {
+            host: url.host_str()?.to_string(),
+            port: url.port_or_known_default()?,
+            certificate: certificate_der.and_then(CertificateInfo::from_der),
+            options: options.clone(),
+            ..Default::default()
+        })
+    }
+
+    /// Whether a pin is set and the presented certificate doesn't match it
+    ///
+    /// A missing certificate counts as a mismatch, since nothing was verified.
+    pub fn pin_mismatch(&self) -> bool {
+        match (&self.pinned, &self.certificate) {
+            (Some(pinned), Some(cert)) => !same_fingerprint(pinned, &cert.fingerprint),
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Whether the protocol and cipher still need to be looked up
+    pub fn needs_probe(&self) -> bool {
+        self.protocol.is_none() && self.probe_error.is_none()
+    }
+
+    /// Starts looking up the negotiated protocol and cipher suite in the
+    /// background
+    pub fn start_probe(&mut self) -> Probe {
+        self.probing = true;
+        Probe::spawn(self.host.clone(), self.port, self.options.clone())
+    }
+
+    /// Keeps what a probe found out
+    pub fn finish_probe(&mut self, outcome: Result<(String, String), String>) {
+        self.probing = false;
+        match outcome {
+            Ok((protocol, cipher)) => {
+                self.protocol = Some(protocol);
+                self.cipher = Some(cipher);
+            }
+            Err(e) => self.probe_error = Some(e),
+        }
+    }
+}
+
+/// A handshake looking up the protocol and cipher of `host`
+pub struct Probe {
+    pub host: String,
+    pub port: u16,
+    outcome: oneshot::Receiver<Result<(String, String), String>>,
+}
+
+impl Probe {
+    fn spawn(host: String, port: u16, options: RequestOptions) -> Probe {
+        let (sender, outcome) = oneshot::channel();
+        let (target, target_port) = (host.clone(), port);
+        tokio::task::spawn_blocking(move || {
+            let _ = sender.send(probe(&target, target_port, &options));
+        });
+        Probe {
+            host,
+            port,
+            outcome,
+        }
+    }
+
+    /// The protocol and cipher, once the handshake is done
+    pub fn poll(&mut self) -> Option<Result<(String, String), String>> {
+        match self.outcome.try_recv() {
+            Ok(outcome) => Some(outcome),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Closed) => Some(Err("the lookup stopped".to_string())),
+        }
+    }
+}
+
+/// SHA-256 fingerprint of a DER encoded certificate, as `AB:CD:…`
+pub fn fingerprint(der: &[u8]) -> String {
+    Sha256::digest(der)
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Compares fingerprints regardless of case and `:` separators
+pub fn same_fingerprint(a: &str, b: &str) -> bool {
+    let normalize = |fingerprint: &str| {
+        fingerprint
+            .chars()
+            .filter(|c| *c != ':')
+            .map(|c| c.to_ascii_lowercase())
+            .collect::<String>()
+    };
+    normalize(a) == normalize(b)
+}
+
+/// Checks that a pin looks like a SHA-256 fingerprint
+pub fn validate_pin(pin: &str) -> Result<(), String> {
+    let digits: Vec<char> = pin.chars().filter(|c| *c != ':').collect();
+    if digits.len() == 64 && digits.iter().all(char::is_ascii_hexdigit) {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} is not a SHA-256 fingerprint (64 hex digits, optionally separated by :)",
+            pin
+        ))
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_vendor = "apple")))]
+fn probe(host: &str, port: u16, options: &RequestOptions) -> Result<(String, String), String> {
+    use openssl::ssl::{SslConnector, SslFiletype, SslMethod, SslVerifyMode, SslVersion};
+
+    let timeout = options.timeout();
+    let stream = connect(host, port, options, timeout)?;
+
+    let mut builder = SslConnector::builder(SslMethod::tls()).map_err(|e| e.to_string())?;
+    if options.is_insecure() {
+        builder.set_verify(SslVerifyMode::NONE);
+    } else if let Some(path) = &options.ca_cert {
+        builder
+            .set_ca_file(crate::paths::expand_home(path))
+            .map_err(|e| format!("Invalid CA bundle {}: {}", path, e))?;
+    }
+    let version = |version: Option<&str>| match version.map(str::trim) {
+        Some("1.0") => Some(SslVersion::TLS1),
+        Some("1.1") => Some(SslVersion::TLS1_1),
+        Some("1.2") => Some(SslVersion::TLS1_2),
+        _ => None,
+    };
+    builder
+        .set_min_proto_version(version(options.min_tls_version.as_deref()))
+        .map_err(|e| e.to_string())?;
+    builder
+        .set_max_proto_version(version(options.max_tls_version.as_deref()))
+        .map_err(|e| e.to_string())?;
+    if let Some(path) = &options.client_cert {
+        let lower = path.to_lowercase();
+        if lower.ends_with(".p12") || lower.ends_with(".pfx") {
+            let der = std::fs::read(crate::paths::expand_home(path))
+                .map_err(|e| format!("Cannot read {}: {}", path, e))?;
+            let password = options.client_cert_password.as_deref().unwrap_or_default();
+            let bundle = openssl::pkcs12::Pkcs12::from_der(&der)
+                .and_then(|bundle| bundle.parse2(password))
+                .map_err(|e| format!("Invalid client certificate {}: {}", path, e))?;
+            if let Some(cert) = &bundle.cert {
+                builder.set_certificate(cert).map_err(|e| e.to_string())?;
+            }
+            if let Some(key) = &bundle.pkey {
+                builder.set_private_key(key).map_err(|e| e.to_string())?;
+            }
+            for cert in bundle.ca.into_iter().flatten() {
+                builder
+                    .add_extra_chain_cert(cert)
+                    .map_err(|e| e.to_string())?;
+            }
+        } else {
+            let key = options.client_key.as_deref().unwrap_or(path);
+            builder
+                .set_certificate_chain_file(crate::paths::expand_home(path))
+                .map_err(|e| format!("Invalid client certificate {}: {}", path, e))?;
+            builder
+                .set_private_key_file(crate::paths::expand_home(key), SslFiletype::PEM)
+                .map_err(|e| format!("Invalid client key {}: {}", key, e))?;
+        }
+    }
+    // Offer what the send offers, so the server picks the same
+    let alpn: &[u8] = match options.http_version() {
+        Some(crate::logic::options::HttpVersion::Http1) => b"\x08http/1.1",
+        _ => b"\x02h2\x08http/1.1",
+    };
+    builder.set_alpn_protos(alpn).map_err(|e| e.to_string())?;
+
+    let mut config = builder.build().configure().map_err(|e| e.to_string())?;
+    if options.is_insecure() {
+        config.set_verify_hostname(false);
+    }
+    let stream = config.connect(host, stream).map_err(|e| e.to_string())?;
+    let ssl = stream.ssl();
+    let cipher = ssl
+        .current_cipher()
+        .map(|cipher| cipher.standard_name().unwrap_or(cipher.name()).to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    Ok((ssl.version_str().to_string(), cipher))
+}
+
+#[cfg(any(target_os = "windows", target_vendor = "apple"))]
+fn probe(_host: &str, _port: u16, _options: &RequestOptions) -> Result<(String, String), String> {
+    Err("not reported by the system TLS library".to_string())
+}
+
+/// The proxy a send to `host` goes through: the `proxy` option, or else
+/// `HTTPS_PROXY` / `ALL_PROXY`, unless `NO_PROXY` leaves the host out
+#[cfg(not(any(target_os = "windows", target_vendor = "apple")))]
+fn proxy_for(host: &str, options: &RequestOptions) -> Option<String> {
+    let env = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+    };
+    let proxy = match options.proxy.as_deref() {
+        Some("none") => return None,
+        Some(proxy) => proxy.to_string(),
+        None => env(&["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"])?,
+    };
+    let bypassed = env(&["NO_PROXY", "no_proxy"]).is_some_and(|hosts| {
+        hosts.split(',').map(str::trim).any(|entry| {
+            let entry = entry.trim_start_matches('.');
+            entry == "*"
+                || (!entry.is_empty() && (host == entry || host.ends_with(&format!(".{}", entry))))
+        })
+    });
+    (!bypassed).then_some(proxy)
+}
+
+/// Opens a connection to `host`, tunnelled through the proxy a send would use
+#[cfg(not(any(target_os = "windows", target_vendor = "apple")))]
+fn connect(
+    host: &str,
+    port: u16,
+    options: &RequestOptions,
+    timeout: std::time::Duration,
+) -> Result<std::net::TcpStream, String> {
+    use std::io::{Read, Write};
+    use std::net::{TcpStream, ToSocketAddrs};
+
+    let open = |host: &str, port: u16| -> Result<TcpStream, String> {
+        let address = (host, port)
+            .to_socket_addrs()
+            .map_err(|e| e.to_string())?
+            .next()
+            .ok_or_else(|| format!("{} did not resolve", host))?;
+        let stream = TcpStream::connect_timeout(&address, timeout).map_err(|e| e.to_string())?;
+        stream
+            .set_read_timeout(Some(timeout))
+            .map_err(|e| e.to_string())?;
+        stream
+            .set_write_timeout(Some(timeout))
+            .map_err(|e| e.to_string())?;
+        Ok(stream)
+    };
+
+    let Some(proxy) = proxy_for(host, options) else {
+        return open(host, port);
+    };
+    let url = reqwest::Url::parse(&proxy).map_err(|e| format!("Invalid proxy {}: {}", proxy, e))?;
+    let proxy_host = url
+        .host_str()
+        .ok_or_else(|| format!("Invalid proxy {}", proxy))?;
+    let credentials = options
+        .proxy_auth
+        .as_deref()
+        .and_then(|auth| auth.split_once(':'))
+        .map(|(user, password)| (user.to_string(), password.to_string()))
+        .or_else(|| {
+            (!url.username().is_empty()).then(|| {
+                (
+                    url.username().to_string(),
+                    url.password().unwrap_or_default().to_string(),
+                )
+            })
+        });
+    let io = |e: std::io::Error| format!("Proxy {}: {}", proxy, e);
+
+    match url.scheme() {
+        "http" => {
+            let mut stream = open(proxy_host, url.port_or_known_default().unwrap_or(80))?;
+            let mut connect = format!(
+                "CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n",
+                host = host,
+                port = port
+            );
+            if let Some((user, password)) = &credentials {
+                use base64::Engine;
+                let token = base64::engine::general_purpose::STANDARD
+                    .encode(format!("{}:{}", user, password));
+                connect.push_str(&format!("Proxy-Authorization: Basic {}\r\n", token));
+            }
+            connect.push_str("\r\n");
+            stream.write_all(connect.as_bytes()).map_err(io)?;
+            // Up to the end of the proxy's answer, and not a byte further
+            let mut head = Vec::new();
+            let mut byte = [0u8; 1];
+            while !head.ends_with(b"\r\n\r\n") {
+                if stream.read(&mut byte).map_err(io)? == 0 {
+                    return Err(format!("Proxy {} closed the connection", proxy));
+                }
+                head.push(byte[0]);
+            }
+            let head = String::from_utf8_lossy(&head);
+            let status = head.lines().next().unwrap_or_default();
+            if status.split_whitespace().nth(1) != Some("200") {
+                return Err(format!("Proxy {} answered {}", proxy, status.trim()));
+            }
+            Ok(stream)
+        }
+        "socks5" | "socks5h" => {
+            let mut stream = open(proxy_host, url.port_or_known_default().unwrap_or(1080))?;
+            let methods: &[u8] = if credentials.is_some() {
+                &[5, 2, 0, 2]
+            } else {
+                &[5, 1, 0]
+            };
+            stream.write_all(methods).map_err(io)?;
+            let mut choice = [0u8; 2];
+            stream.read_exact(&mut choice).map_err(io)?;
+            match (choice[1], &credentials) {
+                (0, _) => {}
+                (2, Some((user, password))) => {
+                    let mut login = vec![1, user.len() as u8];
+                    login.extend_from_slice(user.as_bytes());
+                    login.push(password.len() as u8);
+                    login.extend_from_slice(password.as_bytes());
+                    stream.write_all(&login).map_err(io)?;
+                    let mut status = [0u8; 2];
+                    stream.read_exact(&mut status).map_err(io)?;
+                    if status[1] != 0 {
+                        return Err(format!("Proxy {} refused the credentials", proxy));
+                    }
+                }
+                _ => return Err(format!("Proxy {} wants an unsupported login", proxy)),
+            }
+            let mut request = vec![5, 1, 0, 3, host.len() as u8];
+            request.extend_from_slice(host.as_bytes());
+            request.extend_from_slice(&port.to_be_bytes());
+            stream.write_all(&request).map_err(io)?;
+            let mut reply = [0u8; 4];
+            stream.read_exact(&mut reply).map_err(io)?;
+            if reply[1] != 0 {
+                return Err(format!(
+                    "Proxy {} could not connect (code {})",
+                    proxy, reply[1]
+                ));
+            }
+            // The bound address the proxy reports, then the port
+            let skip = match reply[3] {
+                1 => 4,
+                4 => 16,
+                _ => {
+                    let mut length = [0u8; 1];
+                    stream.read_exact(&mut length).map_err(io)?;
+                    length[0] as usize
+                }
+            };
+            let mut bound = vec![0u8; skip + 2];
+            stream.read_exact(&mut bound).map_err(io)?;
+            Ok(stream)
+        }
+        scheme => Err(format!(
+            "Looking up the protocol through a {}:// proxy isn't supported",
+            scheme
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_format() {
+        let fingerprint = fingerprint(b"not really a certificate");
+        assert_eq!(fingerprint.len(), 32 * 3 - 1);
+        assert!(fingerprint
+            .split(':')
+            .all(|byte| byte.len() == 2 && byte.chars().all(|c| c.is_ascii_hexdigit())));
+    }
+
+    #[cfg(not(any(target_os = "windows", target_vendor = "apple")))]
+    #[test]
+    fn test_certificate_from_der() {
+        use openssl::asn1::Asn1Time;
+        use openssl::hash::MessageDigest;
+        use openssl::pkey::PKey;
+        use openssl::rsa::Rsa;
+        use openssl::x509::extension::SubjectAlternativeName;
+        use openssl::x509::{X509NameBuilder, X509};
+
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", "dev.example.test").unwrap();
+        let name = name.build();
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::from_unix(1_700_000_000).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::from_unix(1_800_000_000).unwrap())
+            .unwrap();
+        let san = SubjectAlternativeName::new()
+            .dns("dev.example.test")
+            .dns("api.example.test")
+            .build(&builder.x509v3_context(None, None))
+            .unwrap();
+        builder.append_extension(san).unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        let der = builder.build().to_der().unwrap();
+
+        let cert = CertificateInfo::from_der(&der).unwrap();
+        assert_eq!(cert.subject, "CN=dev.example.test");
+        assert_eq!(cert.issuer, "CN=dev.example.test");
+        assert_eq!(cert.not_before.timestamp(), 1_700_000_000);
+        assert_eq!(cert.not_after.timestamp(), 1_800_000_000);
+        assert_eq!(cert.alt_names, vec!["dev.example.test", "api.example.test"]);
+        assert_eq!(cert.fingerprint, fingerprint(&der));
+    }
+
+    #[test]
+    fn test_pin_mismatch() {
+        let der = b"presented certificate";
+        let mut tls = TlsDetails {
+            host: "api.example.com".to_string(),
+            port: 443,
+            ..Default::default()
+        };
+        assert!(!tls.pin_mismatch());
+
+        tls.pinned = Some(fingerprint(der).to_lowercase().replace(':', ""));
+        assert!(tls.pin_mismatch(), "no certificate to compare against");
+
+        tls.certificate = Some(CertificateInfo {
+            subject: String::new(),
+            issuer: String::new(),
+            not_before: Utc::now(),
+            not_after: Utc::now(),
+            alt_names: Vec::new(),
+            fingerprint: fingerprint(der),
+        });
+        assert!(!tls.pin_mismatch());
+
+        tls.pinned = Some(fingerprint(b"another certificate"));
+        assert!(tls.pin_mismatch());
+    }
+
+    #[cfg(not(any(target_os = "windows", target_vendor = "apple")))]
+    #[test]
+    fn test_connect_through_proxy() {
+        use crate::test_server::TestServer;
+        use std::time::Duration;
+
+        let proxy = TestServer::new(&[
+            b"HTTP/1.1 200 Connection established\r\n\r\n",
+            b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n",
+        ]);
+        let options = RequestOptions {
+            proxy: Some(proxy.url("")),
+            proxy_auth: Some("user:pass".to_string()),
+            ..Default::default()
+        };
+        let timeout = Duration::from_secs(5);
+        assert!(connect("api.example.test", 8443, &options, timeout).is_ok());
+        let asked = proxy.request();
+        assert!(asked.starts_with("CONNECT api.example.test:8443 HTTP/1.1\r\n"));
+        assert!(asked.contains("Proxy-Authorization: Basic dXNlcjpwYXNz\r\n"));
+
+        let error = connect("api.example.test", 8443, &options, timeout).unwrap_err();
+        assert!(
+            error.contains("407 Proxy Authentication Required"),
+            "{}",
+            error
+        );
+
+        let bypass = RequestOptions {
+            proxy: Some("none".to_string()),
+            ..options
+        };
+        assert_eq!(proxy_for("api.example.test", &bypass), None);
+    }
+
+    #[test]
+    fn test_validate_pin() {
+        assert!(validate_pin(&fingerprint(b"x")).is_ok());
+        assert!(validate_pin(&"ab".repeat(32)).is_ok());
+        assert!(validate_pin("AB:CD").is_err());
+        assert!(validate_pin(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn test_details_only_for_https() {
+        let http = reqwest::Url::parse("http://example.com/").unwrap();
+        let options = RequestOptions::default();
+        assert!(TlsDetails::new(&http, None, &options).is_none());
+
+        let https = reqwest::Url::parse("https://example.com/path").unwrap();
+        let details = TlsDetails::new(&https, Some(b"garbage"), &options).unwrap();
+        assert_eq!(details.host, "example.com");
+        assert_eq!(details.port, 443);
+        assert!(details.certificate.is_none());
+        assert!(details.needs_probe());
+    }
+
+    #[test]
+    fn test_expiry_summary() {
+        let now = Utc::now();
+        let cert = CertificateInfo {
+            subject: "CN=example.com".to_string(),
+            issuer: "CN=Example CA".to_string(),
+            not_before: now - chrono::Duration::days(30),
+            not_after: now + chrono::Duration::days(10) + chrono::Duration::hours(1),
+            alt_names: Vec::new(),
+            fingerprint: String::new(),
+        };
+        assert!(!cert.is_expired(now));
+        assert_eq!(cert.expiry_summary(now), "expires in 10 days");
+        let later = now + chrono::Duration::days(13);
+        assert!(cert.is_expired(later));
+        assert_eq!(cert.expiry_summary(later), "expired 2 days ago");
+    }
+}
@@ -0,0 +1,275 @@
+//! Collapsible tree view of JSON responses
+//!
+//! Objects and arrays are rows that fold and unfold; everything else is a
+//! leaf showing its value. Nodes are identified by their JSON pointer
+//! (`/items/0/sku`), so which nodes are open carries over when a new
+//! response with the same shape arrives. The root and its direct children
+//! start open.
+
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// One visible line of the tree
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Row {
+    pub depth: usize,
+    /// Object key or array index; `None` for the root
+    pub key: Option<String>,
+    /// The value of a leaf as JSON, or a summary like `{3 keys}`
+    pub value: String,
+    /// Whether a container is open; `None` for leaves
+    pub open: Option<bool>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Tree {
+    root: Value,
+    /// Pointers of the open containers
+    open: HashSet<String>,
+    pub selected: usize,
+}
+
+impl Tree {
+    /// Builds a tree when the body is a JSON object or array
+    pub fn from_json(body: &str) -> Option<Tree> {
+        let root = parse(body)?;
+        let mut open = HashSet::from([String::new()]);
+        if let Some(children) = children(&root) {
+            open.extend(
+                children
+                    .into_iter()
+                    .map(|(key, _)| format!("/{}", escape(&key))),
+            );
+        }
+        Some(Tree {
+            root,
+            open,
+            selected: 0,
+        })
+    }
+
+    /// Shows another body, keeping open whatever nodes it shares with the
+    /// current one; false when the body isn't a JSON object or array
+    pub fn reload(&mut self, body: &str) -> bool {
+        match parse(body) {
+            Some(root) => {
+                self.root = root;
+                self.selected = self.selected.min(self.rows().len().saturating_sub(1));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The visible rows, in order
+    pub fn rows(&self) -> Vec<Row> {
+        let mut rows = Vec::new();
+        self.push_rows(&self.root, None, String::new(), 0, &mut rows);
+        rows
+    }
+
+    fn push_rows(
+        &self,
+        value: &Value,
+        key: Option<String>,
+        pointer: String,
+        depth: usize,
+        rows: &mut Vec<Row>,
+    ) {
+        let Some(children) = children(value) else {
+            rows.push(Row {
+                depth,
+                key,
+                value: value.to_string(),
+                open: None,
+            });
+            return;
+        };
+        let open = self.open.contains(&pointer);
+        rows.push(Row {
+            depth,
+            key,
+            value: summary(value),
+            open: Some(open),
+        });
+        if open {
+            for (child_key, child) in children {
+                let child_pointer = format!("{}/{}", pointer, escape(&child_key));
+                self.push_rows(child, Some(child_key), child_pointer, depth + 1, rows);
+            }
+        }
+    }
+
+    /// Moves the selection one row
+    pub fn move_selection(&mut self, down: bool) {
+        self.selected = if down {
+            (self.selected + 1).min(self.rows().len().saturating_sub(1))
+        } else {
+            self.selected.saturating_sub(1)
+        };
+    }
+
+    /// Folds or unfolds the selected row when it is an object or array
+    pub fn toggle(&mut self) {
+        if let Some(pointer) = self.pointer_of(self.selected) {
+            if !self.open.remove(&pointer) {
+                self.open.insert(pointer);
+            }
+        }
+    }
+
+    /// JSON pointer of a visible container row
+    fn pointer_of(&self, row: usize) -> Option<String> {
+        let mut index = 0;
+        find_pointer(&self.root, String::new(), &self.open, row, &mut index)
+    }
+}
+
+/// Walks the visible rows in order until reaching `target`, returning its
+/// pointer if it is a container
+fn find_pointer(
+    value: &Value,
+    pointer: String,
+    open: &HashSet<String>,
+    target: usize,
+    index: &mut usize,
+) -> Option<String> {
+    let children = children(value);
+    *index += 1;
+    if *index > target {
+        return children.map(|_| pointer);
+    }
+    if !open.contains(&pointer) {
+        return None;
+    }
+    for (key, child) in children? {
+        let child_pointer = format!("{}/{}", pointer, escape(&key));
+        let found = find_pointer(child, child_pointer, open, target, index);
+        if *index > target {
+            return found;
+        }
+    }
+    None
+}
+
+fn parse(body: &str) -> Option<Value> {
+    serde_json::from_str::<Value>(body.trim())
+        .ok()
+        .filter(|value| value.is_object() || value.is_array())
+}
+
+/// Keys and values of an object or array
+fn children(value: &Value) -> Option<Vec<(String, &Value)>> {
+    match value {
+        Value::Object(fields) => Some(fields.iter().map(|(k, v)| (k.clone(), v)).collect()),
+        Value::Array(items) => Some(
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (i.to_string(), v))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Summary of a container, e.g. `{3 keys}` or `[1 item]`
+fn summary(value: &Value) -> String {
+    let (count, noun, open, close) = match value {
+        Value::Object(fields) => (fields.len(), "key", '{', '}'),
+        Value::Array(items) => (items.len(), "item", '[', ']'),
+        _ => return value.to_string(),
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{}{} {}{}{}", open, count, noun, plural, close)
+}
+
+/// Escapes a key for use in a JSON pointer
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDER: &str = r#"{"id": 42, "customer": {"name": "Ada", "address": {"city": "Paris"}},
+        "items": [{"sku": "A-1"}], "a/b": {"c": true}}"#;
+
+    fn labels(tree: &Tree) -> Vec<String> {
+        tree.rows()
+            .iter()
+            .map(|row| {
+                format!(
+                    "{}{}{}",
+                    "  ".repeat(row.depth),
+                    row.key
+                        .as_deref()
+                        .map_or(String::new(), |k| format!("{}: ", k)),
+                    row.value
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tree_rows_and_folding() {
+        let mut tree = Tree::from_json(ORDER).unwrap();
+        assert_eq!(
+            labels(&tree),
+            vec![
+                "{4 keys}",
+                "  a/b: {1 key}",
+                "    c: true",
+                "  customer: {2 keys}",
+                "    address: {1 key}",
+                "    name: \"Ada\"",
+                "  id: 42",
+                "  items: [1 item]",
+                "    0: {1 key}",
+            ]
+        );
+        assert_eq!(tree.rows()[4].open, Some(false));
+        assert_eq!(tree.rows()[5].open, None);
+
+        // Unfold customer.address
+        tree.selected = 4;
+        tree.toggle();
+        assert_eq!(tree.rows()[5].value, "\"Paris\"");
+        assert_eq!(tree.rows().len(), 10);
+
+        // Fold the escaped a/b key; leaves don't toggle
+        tree.selected = 1;
+        tree.toggle();
+        assert_eq!(tree.rows().len(), 9);
+        tree.selected = 6;
+        tree.toggle();
+        assert_eq!(tree.rows().len(), 9);
+
+        tree.move_selection(true);
+        tree.move_selection(true);
+        tree.move_selection(true);
+        assert_eq!(tree.selected, 8);
+    }
+
+    #[test]
+    fn test_tree_reload_keeps_open_nodes() {
+        let mut tree = Tree::from_json(ORDER).unwrap();
+        tree.selected = 8;
+        tree.toggle();
+        assert!(tree.reload(r#"{"items": [{"sku": "B-7"}, {"sku": "C-3"}]}"#));
+        assert_eq!(
+            labels(&tree),
+            vec![
+                "{1 key}",
+                "  items: [2 items]",
+                "    0: {1 key}",
+                "      sku: \"B-7\"",
+                "    1: {1 key}",
+            ]
+        );
+        assert_eq!(tree.selected, 4);
+        assert!(!tree.reload("42"));
+        assert!(Tree::from_json("not json").is_none());
+    }
+}
@@ -0,0 +1,236 @@
+//! Re-sending a request when files change
+//!
+//! The watched file or directory tree is polled rather than subscribed to:
+//! every scan fingerprints the modification time and size of each file, so
+//! edits, new files and deletions all count as changes. Scans run on a
+//! thread of their own, so a large tree never holds up the UI. Hidden files
+//! and directories (`.git` and friends) and build output (`target`,
+//! `node_modules`) are skipped, and symlinked directories aren't followed,
+//! so a link back up the tree can't loop. A change only fires once things
+//! have been quiet for [`DEBOUNCE`], so a save that touches several files or
+//! a build that writes a binary in chunks triggers a single send.
+
+use crate::error::{RestlessError, Result};
+use crate::i18n::Catalog;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+/// How long the files must stay unchanged before a send
+pub const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Time between two scans of the tree
+const SCAN_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Directories of build output, skipped wherever they are in the tree
+const SKIPPED: &[&str] = &["node_modules", "target"];
+
+#[derive(Debug)]
+pub struct Watcher {
+    pub path: PathBuf,
+    /// Sends triggered so far
+    pub runs: usize,
+    /// Fingerprint of the last scan seen, none before the first
+    fingerprint: Option<u64>,
+    scans: Arc<Scans>,
+    /// When the last unsettled change was seen
+    changed_at: Option<Instant>,
+}
+
+/// What the scanning thread found, shared with the watcher
+#[derive(Debug, Default)]
+struct Scans {
+    /// Scans finished so far
+    count: AtomicUsize,
+    first: AtomicU64,
+    latest: AtomicU64,
+}
+
+impl Watcher {
+    /// Starts scanning `path`; the scans stop once the watcher is dropped
+    pub fn new(path: PathBuf) -> Result<Watcher> {
+        if !path.exists() {
+            return Err(RestlessError::configuration(format!(
+                "Cannot watch {}: no such file or directory",
+                path.display()
+            )));
+        }
+        let scans = Arc::new(Scans::default());
+        let shared = Arc::downgrade(&scans);
+        let scanned = path.clone();
+        std::thread::spawn(move || scan(&scanned, shared));
+        Ok(Watcher {
+            path,
+            runs: 0,
+            fingerprint: None,
+            scans,
+            changed_at: None,
+        })
+    }
+
+    /// Looks at the latest scan, returning true once a change has settled
+    pub fn poll(&mut self, now: Instant) -> bool {
+        if self.scans.count.load(Ordering::Acquire) > 0 {
+            let baseline = self
+                .fingerprint
+                .unwrap_or_else(|| self.scans.first.load(Ordering::Relaxed));
+            let fingerprint = self.scans.latest.load(Ordering::Relaxed);
+            if fingerprint != baseline {
+                self.changed_at = Some(now);
+            }
+            self.fingerprint = Some(fingerprint);
+        }
+        match self.changed_at {
+            Some(changed_at) if now.duration_since(changed_at) >= DEBOUNCE => {
+                self.changed_at = None;
+                self.runs += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Blocks until a scan started after this call has finished
+    #[cfg(test)]
+    pub fn wait_for_scan(&self) {
+        let after = self.scans.count.load(Ordering::Acquire) + 2;
+        while self.scans.count.load(Ordering::Acquire) < after {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Status bar text, e.g. `Watching src (3 sends)`
+    pub fn summary(&self, i18n: &Catalog) -> String {
+        let text = if self.runs == 1 {
+            "Watching {path} ({count} send)"
+        } else {
+            "Watching {path} ({count} sends)"
+        };
+        i18n.format(
+            text,
+            &[("path", &self.path.display()), ("count", &self.runs)],
+        )
+    }
+}
+
+/// Fingerprints `path` every [`SCAN_INTERVAL`] for as long as the watcher
+/// holding `scans` is around
+fn scan(path: &Path, scans: Weak<Scans>) {
+    loop {
+        let fingerprint = fingerprint(path);
+        let Some(scans) = scans.upgrade() else {
+            return;
+        };
+        if scans.count.load(Ordering::Relaxed) == 0 {
+            scans.first.store(fingerprint, Ordering::Relaxed);
+        }
+        scans.latest.store(fingerprint, Ordering::Relaxed);
+        scans.count.fetch_add(1, Ordering::Release);
+        drop(scans);
+        std::thread::sleep(SCAN_INTERVAL);
+    }
+}
+
+/// Hash of the name, size and modification time of every file under a path
+///
+/// Symlinks to files count as the file; symlinks to directories count by
+/// their target's path, without going in.
+fn fingerprint(path: &Path) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    let mut pending = vec![path.to_path_buf()];
+    let mut root = true;
+    while let Some(path) = pending.pop() {
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            continue;
+        };
+        path.hash(&mut hasher);
+        let linked = !root
+            && std::fs::symlink_metadata(&path).is_ok_and(|link| link.file_type().is_symlink());
+        root = false;
+        if metadata.is_dir() && linked {
+            std::fs::read_link(&path).ok().hash(&mut hasher);
+        } else if metadata.is_dir() {
+            let Ok(entries) = std::fs::read_dir(&path) else {
+                continue;
+            };
+            let mut children: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| !skipped(entry))
+                .map(|entry| entry.path())
+                .collect();
+            // Directory order isn't stable, the fingerprint must be
+            children.sort();
+            pending.extend(children.into_iter().rev());
+        } else {
+            metadata.len().hash(&mut hasher);
+            metadata.modified().ok().hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// Whether an entry is hidden or a directory of build output
+fn skipped(entry: &std::fs::DirEntry) -> bool {
+    let name = entry.file_name();
+    let name = name.to_string_lossy();
+    name.starts_with('.')
+        || SKIPPED.contains(&name.as_ref()) && entry.file_type().is_ok_and(|kind| kind.is_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watcher_debounces_changes() {
+        let dir = std::env::temp_dir().join(format!("restless-watch-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
+
+        let mut watcher = Watcher::new(dir.clone()).unwrap();
+        watcher.wait_for_scan();
+        let start = Instant::now();
+        assert!(!watcher.poll(start));
+
+        // Hidden files and build output don't count
+        std::fs::write(dir.join(".swap"), "x").unwrap();
+        std::fs::create_dir_all(dir.join("target/debug")).unwrap();
+        std::fs::write(dir.join("target/debug/app"), "x").unwrap();
+        watcher.wait_for_scan();
+        assert!(!watcher.poll(start + DEBOUNCE));
+
+        std::fs::write(dir.join("src/lib.rs"), "").unwrap();
+        watcher.wait_for_scan();
+        let changed = start + DEBOUNCE * 2;
+        assert!(!watcher.poll(changed));
+        // Still settling
+        assert!(!watcher.poll(changed + DEBOUNCE / 2));
+        assert!(watcher.poll(changed + DEBOUNCE));
+        assert!(!watcher.poll(changed + DEBOUNCE * 2));
+        assert_eq!(watcher.runs, 1);
+        assert!(watcher.summary(&Catalog::default()).ends_with("(1 send)"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(Watcher::new(dir).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_loop() {
+        let dir = std::env::temp_dir().join(format!("restless-watch-loop-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("main.rs"), "fn main() {}").unwrap();
+        std::os::unix::fs::symlink(".", dir.join("a")).unwrap();
+
+        // The link back up isn't followed, so the scan ends
+        let before = fingerprint(&dir);
+        assert_eq!(fingerprint(&dir), before);
+        std::fs::write(dir.join("main.rs"), "fn main() { run() }").unwrap();
+        assert_ne!(fingerprint(&dir), before);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
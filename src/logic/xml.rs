@@ -0,0 +1,190 @@
+//! Indenting XML response bodies
+//!
+//! Each element goes on its own line, two spaces deeper than its parent;
+//! an element holding nothing but text stays on one line. This is a
+//! formatter, not a validating parser: anything it can't make sense of,
+//! like unbalanced tags, leaves the body exactly as it arrived.
+
+/// One piece of an XML document
+#[derive(Debug, PartialEq, Eq)]
+enum Node<'a> {
+    Open {
+        name: &'a str,
+        tag: &'a str,
+    },
+    Close {
+        name: &'a str,
+        tag: &'a str,
+    },
+    /// Self-closing elements, comments, declarations and doctypes
+    Single(&'a str),
+    Text(&'a str),
+}
+
+/// Indents an XML document, or returns `None` when it isn't well formed
+pub fn pretty(text: &str) -> Option<String> {
+    let nodes = parse(text.trim())?;
+    let mut lines: Vec<String> = Vec::new();
+    let mut open: Vec<&str> = Vec::new();
+    let mut index = 0;
+    while index < nodes.len() {
+        let indent = "  ".repeat(open.len());
+        match nodes[index] {
+            Node::Open { name, tag } => {
+                // Keep `<a>text</a>` and `<a></a>` on one line
+                let (text, after) = match nodes.get(index + 1) {
+                    Some(Node::Text(text)) => (*text, index + 2),
+                    _ => ("", index + 1),
+                };
+                if let Some(Node::Close {
+                    name: closing,
+                    tag: close,
+                }) = nodes.get(after)
+                {
+                    if *closing != name {
+                        return None;
+                    }
+                    lines.push(format!("{}{}{}{}", indent, tag, text, close));
+                    index = after + 1;
+                    continue;
+                }
+                lines.push(format!("{}{}", indent, tag));
+                open.push(name);
+            }
+            Node::Close { name, tag } => {
+                if open.pop() != Some(name) {
+                    return None;
+                }
+                lines.push(format!("{}{}", "  ".repeat(open.len()), tag));
+            }
+            Node::Single(tag) => lines.push(format!("{}{}", indent, tag)),
+            Node::Text(text) => lines.push(format!("{}{}", indent, text)),
+        }
+        index += 1;
+    }
+    (open.is_empty() && !lines.is_empty()).then(|| lines.join("\n"))
+}
+
+fn parse(mut rest: &str) -> Option<Vec<Node<'_>>> {
+    let mut nodes = Vec::new();
+    while !rest.is_empty() {
+        if !rest.starts_with('<') || rest.starts_with("<![CDATA[") {
+            let end = text_end(rest)?;
+            let text = rest[..end].trim();
+            if !text.is_empty() {
+                nodes.push(Node::Text(text));
+            }
+            rest = &rest[end..];
+            continue;
+        }
+        let end = if rest.starts_with("<!--") {
+            rest.find("-->")? + 3
+        } else {
+            tag_end(rest)?
+        };
+        let tag = &rest[..end];
+        rest = &rest[end..];
+        let node = if let Some(closing) = tag.strip_prefix("</") {
+            Node::Close {
+                name: tag_name(closing),
+                tag,
+            }
+        } else if tag.starts_with("<?") || tag.starts_with("<!") || tag.ends_with("/>") {
+            Node::Single(tag)
+        } else {
+            Node::Open {
+                name: tag_name(&tag[1..]),
+                tag,
+            }
+        };
+        if matches!(
+            node,
+            Node::Open { name: "", .. } | Node::Close { name: "", .. }
+        ) {
+            return None;
+        }
+        nodes.push(node);
+    }
+    Some(nodes)
+}
+
+/// Length of the text up to the next tag, counting CDATA sections as text
+fn text_end(text: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(next) = text[offset..].find('<') {
+        let at = offset + next;
+        if !text[at..].starts_with("<![CDATA[") {
+            return Some(at);
+        }
+        offset = at + text[at..].find("]]>")? + 3;
+    }
+    Some(text.len())
+}
+
+/// Length of the tag at the start of `text`, quotes in attributes included
+fn tag_end(text: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, c) in text.char_indices().skip(1) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(index + 1),
+            (None, '<') => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn tag_name(text: &str) -> &str {
+    let end = text
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .unwrap_or(text.len());
+    &text[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pretty_xml() {
+        let xml = r#"<?xml version="1.0"?><!-- orders --><orders count="2"><order id="a>b"><sku>A-1</sku><gift/><note></note></order><order><![CDATA[<raw>]]></order></orders>"#;
+        assert_eq!(
+            pretty(xml).unwrap(),
+            [
+                r#"<?xml version="1.0"?>"#,
+                "<!-- orders -->",
+                r#"<orders count="2">"#,
+                r#"  <order id="a>b">"#,
+                "    <sku>A-1</sku>",
+                "    <gift/>",
+                "    <note></note>",
+                "  </order>",
+                "  <order><![CDATA[<raw>]]></order>",
+                "</orders>",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_pretty_xml_reindents_and_keeps_text() {
+        let xml = "<a>\n      <b>  one two </b>\n  text\n<c>x</c></a>";
+        assert_eq!(
+            pretty(xml).unwrap(),
+            "<a>\n  <b>one two</b>\n  text\n  <c>x</c>\n</a>"
+        );
+    }
+
+    #[test]
+    fn test_malformed_xml_is_left_alone() {
+        assert!(pretty("<a><b></a></b>").is_none());
+        assert!(pretty("<a>").is_none());
+        assert!(pretty("</a>").is_none());
+        assert!(pretty("<a attr=\"unterminated></a>").is_none());
+        assert!(pretty("<a><!-- open </a>").is_none());
+        assert!(pretty("").is_none());
+    }
+}
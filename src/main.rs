@@ -1,23 +1,166 @@
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyEventKind};
+use clap::Parser;
+use crossterm::event::{self, Event, KeyEventKind, MouseEventKind};
 
 mod app;
+mod cli;
+mod clipboard;
+mod collections;
+mod config;
+mod editor;
 use app::App;
 
 mod ui;
 use ui::ui;
 
 mod error;
+mod export;
 mod handlers;
+mod headless;
+mod i18n;
+mod import;
+mod logging;
 mod logic;
+mod mock;
+mod paths;
+mod plain;
+mod profile;
+mod session;
+mod storage;
 mod terminal;
+#[cfg(test)]
+mod test_server;
 
+use crate::collections::Collection;
 use crate::error::RestlessError;
-use crate::handlers::handle_key_event;
+use crate::export::script::ScriptFormat;
+use crate::handlers::{
+    handle_key_event, handle_mouse_event, handle_paste_event, poll_sends, start_tab_sends,
+};
+use crate::logic::environment::Environment;
+use crate::logic::query;
 use crate::terminal::TerminalManager;
+use std::time::{Duration, Instant};
+
+/// How often the UI wakes up without input while a request is in flight, a
+/// response is streaming events or files are watched, including opened
+/// `.http` files
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = cli::Cli::parse();
+    init_logging(&cli)?;
+    if let Some(cli::Command::ExportScript {
+        collection,
+        env,
+        format,
+    }) = &cli.command
+    {
+        let (app, startup_error) = init_app();
+        if let Some(error) = startup_error {
+            eprintln!("{}", error);
+        }
+        print!(
+            "{}",
+            export_script(&app, collection, env.as_deref(), format)?
+        );
+        return Ok(());
+    }
+    if let Some(cli::Command::History {
+        query,
+        limit,
+        stats,
+        run,
+    }) = &cli.command
+    {
+        let (app, startup_error) = init_app();
+        if let Some(error) = startup_error {
+            eprintln!("{}", error);
+        }
+        if let Some(title) = run {
+            for run in app.history.runs(title, *limit)? {
+                println!("{}", storage::format_run(&run));
+            }
+        } else if *stats {
+            for url in app.history.stats()? {
+                println!("{}", storage::format_stats(&url));
+            }
+        } else {
+            for entry in app.history.search(query, *limit)? {
+                println!("{}", storage::format_entry(&entry));
+            }
+        }
+        return Ok(());
+    }
+    if let Some(cli::Command::Profile(command)) = &cli.command {
+        return run_profile_command(command);
+    }
+    if let Some(cli::Command::Lint { collection, env }) = &cli.command {
+        let (app, startup_error) = init_app();
+        if let Some(error) = startup_error {
+            eprintln!("{}", error);
+        }
+        if !lint_collection(&app, collection, env.as_deref())? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    if let Some(cli::Command::Bench {
+        url,
+        method,
+        headers,
+        body,
+        requests,
+        concurrency,
+    }) = &cli.command
+    {
+        let request = request_from_args(url, method, headers, body.as_deref())?;
+        let load = logic::load::Load::new(*requests, *concurrency)?;
+        println!(
+            "Sending {} {} {} times, {} at a time",
+            request.method, request.url, load.total, load.concurrency
+        );
+        for line in logic::load::run(&request, load).await.lines() {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+    if let Some(cli::Command::Mock { file, port }) = &cli.command {
+        mock::run(file, *port).await?;
+        return Ok(());
+    }
+    if let Some(cli::Command::Send {
+        url,
+        method,
+        headers,
+        data,
+    }) = &cli.command
+    {
+        let request = request_from_args(url, method, headers, data.as_deref())?;
+        let code = headless::run(&request, &mut std::io::stdout().lock()).await?;
+        if code != 0 {
+            std::process::exit(code);
+        }
+        return Ok(());
+    }
+    if cli.plain {
+        let (mut app, startup_error) = init_app();
+        let mut stdout = std::io::stdout();
+        let failures = plain::run(
+            &mut app,
+            std::io::stdin().lock(),
+            &mut stdout,
+            startup_error,
+        )
+        .await?;
+        if cli.fail && failures > 0 {
+            eprintln!("{} request(s) failed", failures);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Initialize terminal
     let mut terminal_manager = TerminalManager::new().map_err(|e| {
         eprintln!("Failed to initialize terminal: {}", e);
@@ -31,58 +174,377 @@ async fn main() -> Result<()> {
     }
 
     // Initialize application
-    let mut app = App::new();
+    let (mut app, mut startup_error) = init_app();
+    let mut report = |error: String| {
+        startup_error = Some(match startup_error.take() {
+            Some(errors) => format!("{}\n{}", errors, error),
+            None => error,
+        });
+    };
+    if let Some(path) = paths::session_file() {
+        if let Err(e) = app.restore_session(&path) {
+            report(format!("Failed to restore the last session: {}", e));
+        }
+    }
+    if let Some(path) = &cli.watch {
+        if let Err(e) = app.start_watch(path) {
+            report(format!("Failed to watch: {}", e));
+        }
+    }
+    if let Some(interval) = &cli.poll {
+        if let Err(e) = app.start_poll(interval) {
+            report(format!("Failed to poll: {}", e));
+        }
+    }
+    if let Some(path) = &cli.open {
+        app.send_on_save = cli.send_on_save;
+        let opened = app
+            .open_http_file(&paths::expand_home(path))
+            .and_then(|()| app.open_selected_collection_request());
+        if let Err(e) = opened {
+            report(format!("Failed to open {}: {}", path, e));
+        }
+    }
+    if let Some(target) = &cli.target {
+        let opened = if target.starts_with("http://") || target.starts_with("https://") {
+            app.open_url(target)
+        } else {
+            app.open_collection_file(&paths::expand_home(target))
+        };
+        if let Err(e) = opened {
+            report(format!("Failed to open {}: {}", target, e));
+        }
+    }
 
     // Run the application
-    let result = run_app(&mut terminal_manager, &mut app).await;
+    let result = run_app(&mut terminal_manager, &mut app, startup_error).await;
 
     // Cleanup is handled by the TerminalManager's Drop implementation
     // but we can also explicitly cleanup for better error handling
     if let Err(cleanup_error) = terminal_manager.cleanup() {
         eprintln!("Warning: Failed to cleanup terminal: {}", cleanup_error);
     }
+    if let Some(path) = paths::session_file() {
+        if let Err(e) = app.save_session(&path) {
+            eprintln!("Warning: Failed to save the session: {}", e);
+        }
+    }
 
     result
 }
 
-async fn run_app(terminal_manager: &mut TerminalManager, app: &mut App) -> Result<()> {
+/// Starts logging to the file given on the command line or in the config;
+/// a broken config is left for `init_app` to report
+fn init_logging(cli: &cli::Cli) -> Result<()> {
+    let log = paths::config_file()
+        .and_then(|path| config::load_config(&path).ok())
+        .map(|config| config.log)
+        .unwrap_or_default();
+    let Some(file) = cli.log_file.as_ref().or(log.file.as_ref()) else {
+        return Ok(());
+    };
+    logging::init(&paths::expand_home(file), logging::parse_level(&log.level)?)?;
+    Ok(())
+}
+
+/// Creates the app and loads user files, collecting any load errors
+fn init_app() -> (App, Option<String>) {
+    let mut app = App::new();
+    let startup_errors: Vec<String> = [
+        app.load_config()
+            .err()
+            .map(|e| format!("Failed to load config: {}", e)),
+        app.load_locale()
+            .err()
+            .map(|e| format!("Failed to load translations: {}", e)),
+        app.load_environments()
+            .err()
+            .map(|e| format!("Failed to load environments: {}", e)),
+        app.load_collections()
+            .err()
+            .map(|e| format!("Failed to load collections: {}", e)),
+        app.load_hints()
+            .err()
+            .map(|e| format!("Failed to load hints: {}", e)),
+        app.load_history()
+            .err()
+            .map(|e| format!("Failed to open history: {}", e)),
+        app.track_revisions()
+            .err()
+            .map(|e| format!("Failed to record revisions: {}", e)),
+        app.load_lint()
+            .err()
+            .map(|e| format!("Failed to load lint rules: {}", e)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let startup_error = (!startup_errors.is_empty()).then(|| startup_errors.join("\n"));
+    (app, startup_error)
+}
+
+/// Exports or imports a settings profile
+fn run_profile_command(command: &cli::ProfileCommand) -> Result<()> {
+    let path = paths::config_file()
+        .ok_or_else(|| RestlessError::configuration("No config directory on this system"))?;
+    let current = profile::read_config(&path)?;
+    match command {
+        cli::ProfileCommand::Export { name } => {
+            let profile = profile::Profile {
+                name: name.clone(),
+                settings: current,
+            };
+            print!("{}", profile.to_file()?);
+        }
+        cli::ProfileCommand::Import { file, apply } => {
+            let content = std::fs::read_to_string(paths::expand_home(file))?;
+            let profile = profile::Profile::parse(&content)?;
+            let changes = profile.diff(&current);
+            if changes.is_empty() {
+                println!("Profile '{}' matches the current settings", profile.name);
+                return Ok(());
+            }
+            println!("Profile '{}' changes:", profile.name);
+            for change in &changes {
+                println!("  {}", change);
+            }
+            if *apply {
+                profile::apply(&profile, &current, &path)?;
+                println!("Applied to {}", path.display());
+            } else {
+                println!("Run again with --apply to use it");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Prints what the lint rules find in each request of a collection
+/// (`restless lint`), returning whether no rule at `error` level is broken
+fn lint_collection(app: &App, collection: &str, env: Option<&str>) -> Result<bool> {
+    let rules = app.lint.as_ref().ok_or_else(|| {
+        RestlessError::configuration(format!(
+            "No {} in this directory or above",
+            logic::lint::FILE_NAME
+        ))
+    })?;
+    let (collection, env) = find_collection(app, collection, env)?;
+    let mut passed = true;
+    for saved in &collection.requests {
+        let request = saved.to_request(&collection.auth)?;
+        let findings = rules.check(&request, env);
+        for finding in &findings {
+            println!("{}: {}", saved.name, finding);
+        }
+        passed &= !logic::lint::has_errors(&findings);
+    }
+    Ok(passed)
+}
+
+/// Looks up a collection and optionally an environment by name
+fn find_collection<'a>(
+    app: &'a App,
+    collection: &str,
+    env: Option<&str>,
+) -> Result<(&'a Collection, Option<&'a Environment>)> {
+    let collection = app
+        .collections
+        .iter()
+        .find(|c| c.name == collection)
+        .ok_or_else(|| {
+            RestlessError::configuration(format!("No collection named '{}'", collection))
+        })?;
+    let env = env
+        .map(|name| {
+            app.environments
+                .iter()
+                .find(|env| env.name == name)
+                .ok_or_else(|| {
+                    RestlessError::configuration(format!("No environment named '{}'", name))
+                })
+        })
+        .transpose()?;
+    Ok((collection, env))
+}
+
+/// Builds the request `restless bench` and `restless send` send from their
+/// arguments
+fn request_from_args(
+    url: &str,
+    method: &str,
+    headers: &[String],
+    body: Option<&str>,
+) -> Result<logic::request::Request> {
+    let method = reqwest::Method::from_bytes(method.to_uppercase().as_bytes())
+        .map_err(|_| RestlessError::invalid_http_method(method))?;
+    let headers = headers
+        .iter()
+        .map(|header| {
+            let (key, value) = header.split_once(':').ok_or_else(|| {
+                RestlessError::invalid_header("Headers use the format Name: value")
+            })?;
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let request = logic::request::Request {
+        url: url.to_string(),
+        method,
+        headers,
+        body: body.map(str::to_string),
+        ..Default::default()
+    };
+    request.validate_url()?;
+    request.validate_headers()?;
+    Ok(request)
+}
+
+/// Renders a collection as a smoke check script (`restless export-script`)
+fn export_script(app: &App, collection: &str, env: Option<&str>, format: &str) -> Result<String> {
+    let (collection, env) = find_collection(app, collection, env)?;
+    let format = ScriptFormat::from_name(format).ok_or_else(|| {
+        RestlessError::configuration(format!(
+            "Unknown script format '{}' (use sh or gha)",
+            format
+        ))
+    })?;
+    Ok(export::script::render(
+        collection,
+        env,
+        format,
+        chrono::Utc::now(),
+    )?)
+}
+
+async fn run_app(
+    terminal_manager: &mut TerminalManager,
+    app: &mut App,
+    startup_error: Option<String>,
+) -> Result<()> {
     // Store any error message to display to the user
-    let mut error_message: Option<String> = None;
+    let mut error_message: Option<String> = startup_error;
+    let mut title = String::new();
 
     loop {
+        // Pick up events from any streaming response before drawing
+        let streaming = app.poll_streams();
+        let toasting = app.expire_toast(Instant::now());
+        if let Some(message) = poll_sends(app) {
+            tracing::info!(message = %query::redact_urls(&message), "popup");
+            error_message.get_or_insert(message);
+        }
+
         // Draw the UI
         terminal_manager
             .terminal_mut()
             .draw(|f| ui(f, app, &error_message))
             .map_err(|e| RestlessError::terminal(format!("Failed to draw UI: {}", e)))?;
+        let settings = &app.config.terminal;
+        if settings.title && app.window_title() != title {
+            title = app.window_title();
+            terminal_manager.set_title(&title, settings.tmux_status)?;
+        }
 
-        // Handle events
-        if let Event::Key(key) = event::read()? {
-            if key.kind != KeyEventKind::Press {
-                continue;
-            }
-
-            // If there's an error message, any key press dismisses it
+        // While a send or stream is open, a toast shows or files are watched,
+        // wake up every so often even without input
+        let ticking = streaming
+            || toasting
+            || app.sending()
+            || app.watch.is_some()
+            || app.poll.is_some()
+            || app.rate_limited(chrono::Utc::now())
+            || !app.http_files.is_empty();
+        let result = if ticking && !event::poll(TICK_INTERVAL)? {
+            // A pending change waits until any popup is dismissed
             if error_message.is_some() {
-                error_message = None;
                 continue;
             }
+            app.start_due_retries(chrono::Utc::now());
+            let now = Instant::now();
+            match app.poll_http_files(now) {
+                Ok(reloaded) => {
+                    let mut due = Vec::new();
+                    if reloaded {
+                        due.push(app.selected_tab);
+                    }
+                    due.extend(app.poll_watch(now));
+                    due.extend(app.poll_interval(now));
+                    if due.is_empty() {
+                        continue;
+                    }
+                    start_tab_sends(app, due)
+                }
+                Err(e) => Err(e),
+            }
+        } else {
+            // Handle events
+            match event::read()? {
+                Event::Key(key) => {
+                    if key.kind != KeyEventKind::Press {
+                        continue;
+                    }
+
+                    // If there's an error message, any key press dismisses it
+                    if error_message.is_some() {
+                        error_message = None;
+                        continue;
+                    }
 
-            // Handle the key event using the modular handler
-            match handle_key_event(app, key).await {
-                Ok(Some(msg)) => {
-                    error_message = Some(msg);
+                    // Handle the key event using the modular handler
+                    handle_key_event(app, key).await
                 }
-                Ok(None) => {
-                    // Check if we should exit
-                    if matches!(app.current_screen, app::CurrentScreen::Exiting) {
-                        return Ok(());
+                Event::Paste(text) if error_message.is_none() => {
+                    handle_paste_event(app, &text).await
+                }
+                // Moves are reported too, but change nothing
+                Event::Mouse(mouse) if mouse.kind == MouseEventKind::Moved => continue,
+                // A click dismisses the error popup like a key press
+                Event::Mouse(mouse) if error_message.is_some() => {
+                    if matches!(mouse.kind, MouseEventKind::Down(_)) {
+                        error_message = None;
                     }
+                    continue;
                 }
-                Err(e) => {
-                    error_message = Some(format!("Error: {}", e));
+                Event::Mouse(mouse) => handle_mouse_event(app, mouse).await,
+                _ => continue,
+            }
+        };
+
+        match result {
+            Ok(Some(msg)) => {
+                tracing::info!(message = %query::redact_urls(&msg), "popup");
+                error_message = Some(msg);
+            }
+            Ok(None) => {
+                // Check if we should exit
+                if matches!(app.current_screen, app::CurrentScreen::Exiting) {
+                    return Ok(());
                 }
             }
+            Err(e) => {
+                tracing::error!(
+                    error = %query::redact_urls(&e.to_string()),
+                    screen = ?app.current_screen,
+                    "handler failed"
+                );
+                error_message = Some(format!("Error: {}", e));
+            }
+        }
+
+        if std::mem::take(&mut app.body_editor_requested) {
+            if let Err(e) = edit_body(terminal_manager, app) {
+                error_message = Some(app.get_error_message(&e));
+            }
         }
     }
 }
+
+/// Opens the body in the external editor, with the interface suspended
+/// until the editor exits
+fn edit_body(terminal_manager: &mut TerminalManager, app: &mut App) -> Result<(), RestlessError> {
+    let command = editor::command(app.config.ui.editor.as_deref());
+    terminal_manager.suspend()?;
+    let edited = editor::edit(&command, &app.body_input, app.body_extension());
+    terminal_manager.resume()?;
+    app.apply_body_edit(edited?);
+    Ok(())
+}
@@ -0,0 +1,364 @@
+//! A local HTTP server answering with canned responses (`restless mock`)
+//!
+//! Routes are read from a TOML file, by default `restless-mocks.toml` in the
+//! working directory:
+//!
+//! ```toml
+//! [[route]]
+//! method = "GET"            # any method when left out
+//! path = "/users/{id}"      # {name} matches one segment, a trailing * the rest
+//! status = 200              # the default
+//! headers = { Content-Type = "application/json" }
+//! body = '{"id": 1, "name": "Ann"}'
+//! delay_ms = 300
+//!
+//! [[route]]
+//! method = "POST"
+//! path = "/users"
+//! status = 201
+//! body_file = "fixtures/user.json"  # relative to the routes file
+//! ```
+//!
+//! The first route that matches answers; a request no route matches gets a
+//! 404 naming it, and one with a body over [`MAX_BODY`] a 413. Every response allows any origin, and preflight `OPTIONS`
+//! requests without a route of their own are answered, so a frontend running
+//! in the browser can use the mocks directly.
+
+use crate::error::{RestlessError, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Name of the routes file looked for when none is given
+pub const FILE_NAME: &str = "restless-mocks.toml";
+
+/// Largest request body read; bigger ones get `413 Payload Too Large`
+const MAX_BODY: usize = 4 * 1024 * 1024;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Mocks {
+    #[serde(default, rename = "route")]
+    pub routes: Vec<Route>,
+}
+
+/// One canned response and the requests it answers
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Route {
+    pub method: Option<String>,
+    pub path: String,
+    #[serde(default = "default_status")]
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub body: String,
+    /// File the body is read from instead, relative to the routes file
+    pub body_file: Option<PathBuf>,
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+fn default_status() -> u16 {
+    200
+}
+
+impl Route {
+    /// Whether the route answers `method` on `path` (without the query)
+    pub fn matches(&self, method: &str, path: &str) -> bool {
+        if self
+            .method
+            .as_ref()
+            .is_some_and(|own| !own.eq_ignore_ascii_case(method))
+        {
+            return false;
+        }
+        let mut pattern = self.path.trim_matches('/').split('/');
+        let mut segments = path.trim_matches('/').split('/');
+        loop {
+            match (pattern.next(), segments.next()) {
+                (Some("*"), _) => return true,
+                (Some(own), Some(segment)) => {
+                    let placeholder = own.starts_with('{') && own.ends_with('}');
+                    if !placeholder && own != segment {
+                        return false;
+                    }
+                }
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+}
+
+/// Reads a routes file, with the `body_file` of each route read in
+pub fn load(path: &Path) -> Result<Mocks> {
+    let invalid = |reason: String| {
+        RestlessError::configuration(format!("Invalid mocks {}: {}", path.display(), reason))
+    };
+    let content = std::fs::read_to_string(path)?;
+    let mut mocks: Mocks = toml::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    for route in &mut mocks.routes {
+        if !route.path.starts_with('/') {
+            return Err(invalid(format!("path {} must start with /", route.path)));
+        }
+        if !(100..=999).contains(&route.status) {
+            return Err(invalid(format!("{} is not a status code", route.status)));
+        }
+        if let Some(file) = &route.body_file {
+            let file = dir.join(file);
+            route.body = std::fs::read_to_string(&file)
+                .map_err(|e| invalid(format!("{}: {}", file.display(), e)))?;
+        }
+    }
+    Ok(mocks)
+}
+
+/// Answers requests on `listener` with `mocks` until the task is dropped,
+/// logging each one to stdout
+pub async fn serve(mocks: Mocks, listener: TcpListener) -> Result<()> {
+    let mocks = Arc::new(mocks);
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let mocks = mocks.clone();
+        tokio::spawn(async move {
+            if let Err(e) = answer(&mocks, stream).await {
+                eprintln!("Mock error: {}", e);
+            }
+        });
+    }
+}
+
+/// Starts the mock server for the routes in `path` on `port`
+pub async fn run(path: &Path, port: u16) -> Result<()> {
+    let mocks = load(path)?;
+    let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+    println!(
+        "Mocking {} routes from {} on http://{} (Ctrl+C stops)",
+        mocks.routes.len(),
+        path.display(),
+        listener.local_addr()?
+    );
+    serve(mocks, listener).await
+}
+
+/// Reads one request from `stream` and writes the response of its route
+async fn answer(mocks: &Mocks, stream: TcpStream) -> Result<()> {
+    let started = Instant::now();
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(());
+    };
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    // The body is read so the client isn't cut off mid-send, then ignored
+    let too_large = content_length > MAX_BODY;
+    if !too_large {
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).await?;
+    }
+
+    let path = target.split(['?', '#']).next().unwrap_or(target);
+    let route = mocks
+        .routes
+        .iter()
+        .find(|route| route.matches(method, path));
+    let (status, headers, body) = match route {
+        _ if too_large => (
+            413,
+            BTreeMap::new(),
+            format!("Request body over {} bytes\n", MAX_BODY),
+        ),
+        Some(route) => {
+            tokio::time::sleep(Duration::from_millis(route.delay_ms)).await;
+            (route.status, route.headers.clone(), route.body.clone())
+        }
+        None if method.eq_ignore_ascii_case("OPTIONS") => {
+            let headers = [
+                ("Access-Control-Allow-Methods", "*"),
+                ("Access-Control-Allow-Headers", "*"),
+            ];
+            let headers = headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect();
+            (204, headers, String::new())
+        }
+        None => (
+            404,
+            BTreeMap::new(),
+            format!("No mock route for {} {}\n", method, path),
+        ),
+    };
+
+    let reason = reqwest::StatusCode::from_u16(status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("");
+    let mut head = format!("HTTP/1.1 {} {}\r\n", status, reason);
+    let has = |name: &str| headers.keys().any(|own| own.eq_ignore_ascii_case(name));
+    if !has("Access-Control-Allow-Origin") {
+        head.push_str("Access-Control-Allow-Origin: *\r\n");
+    }
+    for (name, value) in &headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    ));
+    let mut stream = reader.into_inner();
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.flush().await?;
+    println!(
+        "{} {} -> {} ({} ms)",
+        method,
+        target,
+        status,
+        started.elapsed().as_millis()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::request::Request;
+
+    fn route(method: Option<&str>, path: &str) -> Route {
+        Route {
+            method: method.map(str::to_string),
+            path: path.to_string(),
+            status: 200,
+            headers: BTreeMap::new(),
+            body: String::new(),
+            body_file: None,
+            delay_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_route_matches() {
+        let user = route(Some("get"), "/users/{id}");
+        assert!(user.matches("GET", "/users/7"));
+        assert!(user.matches("GET", "/users/7/"));
+        assert!(!user.matches("POST", "/users/7"));
+        assert!(!user.matches("GET", "/users"));
+        assert!(!user.matches("GET", "/users/7/posts"));
+
+        let files = route(None, "/files/*");
+        assert!(files.matches("PUT", "/files/a/b.txt"));
+        assert!(files.matches("GET", "/files"));
+        assert!(route(None, "/").matches("DELETE", "/"));
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = std::env::temp_dir().join(format!("restless-mock-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("fixtures")).unwrap();
+        std::fs::write(dir.join("fixtures/user.json"), "{\"id\": 1}").unwrap();
+        let path = dir.join(FILE_NAME);
+        std::fs::write(
+            &path,
+            "[[route]]\npath = \"/health\"\nbody = \"ok\"\n\n\
+             [[route]]\nmethod = \"POST\"\npath = \"/users\"\nstatus = 201\n\
+             body_file = \"fixtures/user.json\"\ndelay_ms = 50\n",
+        )
+        .unwrap();
+        let mocks = load(&path).unwrap();
+        assert_eq!(mocks.routes.len(), 2);
+        assert_eq!(mocks.routes[0].status, 200);
+        assert_eq!(mocks.routes[1].body, "{\"id\": 1}");
+        assert_eq!(mocks.routes[1].delay_ms, 50);
+
+        std::fs::write(&path, "[[route]]\npath = \"users\"\n").unwrap();
+        assert!(load(&path).is_err());
+        std::fs::write(&path, "[[route]]\npath = \"/\"\nstatus = 42\n").unwrap();
+        assert!(load(&path).is_err());
+        std::fs::write(&path, "[[route]]\npath = \"/\"\nbody_file = \"missing\"\n").unwrap();
+        assert!(load(&path).is_err());
+        std::fs::write(&path, "[[route]]\npath = \"/\"\ncolor = \"red\"\n").unwrap();
+        assert!(load(&path).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_serve() {
+        let mut created = route(Some("POST"), "/users");
+        created.status = 201;
+        created
+            .headers
+            .insert("Content-Type".to_string(), "application/json".to_string());
+        created.body = "{\"id\": 7}".to_string();
+        created.delay_ms = 100;
+        let mocks = Mocks {
+            routes: vec![created],
+        };
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(serve(mocks, listener));
+
+        let request = Request {
+            url: format!("{}/users?notify=1", url),
+            method: reqwest::Method::POST,
+            body: Some("{\"name\": \"Ann\"}".to_string()),
+            ..Default::default()
+        };
+        let started = Instant::now();
+        let raw = request.send().await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(100));
+        assert_eq!(raw.status_code, 201);
+        assert_eq!(raw.body, "{\"id\": 7}");
+        assert!(raw.headers.contains("content-type: application/json"));
+        assert!(raw.headers.contains("access-control-allow-origin: *"));
+
+        let missing = Request {
+            url: format!("{}/orders", url),
+            ..Default::default()
+        };
+        let raw = missing.send().await.unwrap();
+        assert_eq!(raw.status_code, 404);
+        assert_eq!(raw.body, "No mock route for GET /orders\n");
+
+        let preflight = Request {
+            url: format!("{}/users", url),
+            method: reqwest::Method::OPTIONS,
+            ..Default::default()
+        };
+        assert_eq!(preflight.send().await.unwrap().status_code, 204);
+
+        // A body over the cap isn't read, whatever length is claimed
+        let mut stream = TcpStream::connect(url.trim_start_matches("http://"))
+            .await
+            .unwrap();
+        stream
+            .write_all(b"POST /users HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 413 Payload Too Large\r\n"));
+        server.abort();
+    }
+}
@@ -0,0 +1,98 @@
+//! Well-known file locations used by Restless
+//!
+//! Everything user-editable lives in the platform config directory
+//! (e.g. `~/.config/restless` on Linux).
+
+use std::path::{Path, PathBuf};
+
+/// Expands a leading `~/` to the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Directory holding Restless configuration files
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("restless"))
+}
+
+/// Directory holding user collections
+pub fn collections_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("collections"))
+}
+
+/// Path of the application config file
+pub fn config_file() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("config.toml"))
+}
+
+/// Path of the environments file
+pub fn environments_file() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("environments.json"))
+}
+
+/// Path of the SQLite database holding the send history
+pub fn history_file() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("history.db"))
+}
+
+/// Directory holding translations of the interface
+pub fn locales_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("locales"))
+}
+
+/// Path of the file holding the tabs of the last run
+pub fn session_file() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("session.json"))
+}
+
+/// Path of the file remembering dismissed first-use hints
+pub fn hints_file() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("hints.json"))
+}
+
+/// Completes a partly typed file path
+///
+/// Completes as far as every match agrees, adding `/` after a directory.
+/// Returns `None` when nothing matches.
+pub fn complete_path(partial: &str) -> Option<String> {
+    let (dir, prefix) = match partial.rfind('/') {
+        Some(index) => (&partial[..=index], &partial[index + 1..]),
+        None => ("", partial),
+    };
+    let search_dir = if dir.is_empty() {
+        Path::new(".").to_path_buf()
+    } else {
+        expand_home(dir)
+    };
+
+    let mut matches: Vec<(String, bool)> = std::fs::read_dir(search_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let hidden = file_name.starts_with('.') && !prefix.starts_with('.');
+            (file_name.starts_with(prefix) && !hidden).then(|| (file_name, entry.path().is_dir()))
+        })
+        .collect();
+    matches.sort();
+
+    let completed = match matches.as_slice() {
+        [] => return None,
+        [(file_name, is_dir)] => {
+            let suffix = if *is_dir { "/" } else { "" };
+            format!("{}{}", file_name, suffix)
+        }
+        [(first, _), rest @ ..] => rest.iter().fold(first.clone(), |common, (file_name, _)| {
+            common
+                .chars()
+                .zip(file_name.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect()
+        }),
+    };
+    Some(format!("{}{}", dir, completed))
+}
@@ -0,0 +1,490 @@
+//! Linear output mode (`--plain`)
+//!
+//! Reads one command per line and prints plain text, without the alternate
+//! screen or any cursor movement, so the request workflow works with screen
+//! readers and in dumb terminals. Commands drive the same [`App`] state and
+//! send path as the full-screen interface.
+
+use crate::app::App;
+use crate::error::{RestlessError, Result};
+use crate::handlers::send_current_request;
+use crate::logic::assertions;
+use crate::logic::HttpMethod;
+use std::io::{BufRead, Write};
+
+const HELP: &str = "\
+Commands:
+  url <url>             Set the request URL
+  method <method>       Set the method (GET, POST, PUT, DELETE)
+  header <Name: value>  Add a header
+  param <name=value>    Add a query parameter
+  test <assertion>      Add a check run after each send, e.g. status == 200,
+                        or keep a value: test set token = json.body.token
+  body                  Set the body; end it with a line containing only '.'
+  body <text>           Set a one-line body
+  form <name=value>     Add a multipart form field; name=@path uploads a file
+  auth <kind>           Set the auth type (none, api_key, hmac, aws_sigv4,
+                        oauth2_client_credentials, oauth2_authorization_code)
+  auth <field=value>    Set an auth field
+  option <key=value>    Set a connection option (proxy, ca_cert, insecure, ...)
+  env [name|none]       List environments, or select one
+  show                  Print the current request
+  send                  Send the request and print the response
+  reset                 Clear the request
+  help                  Show this help
+  quit                  Exit
+Lines starting with '#' are ignored.";
+
+/// Whether the session should keep reading commands
+#[derive(Debug, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    Quit,
+}
+
+/// Runs the linear mode until `quit` or end of input
+///
+/// Returns the number of sends that failed or got a 4xx/5xx response, which
+/// `--fail` turns into the exit status.
+pub async fn run<R: BufRead, W: Write>(
+    app: &mut App,
+    mut input: R,
+    output: &mut W,
+    startup_error: Option<String>,
+) -> anyhow::Result<usize> {
+    writeln!(
+        output,
+        "restless {} (plain mode). Type 'help' for commands.",
+        env!("CARGO_PKG_VERSION")
+    )?;
+    if let Some(error) = startup_error {
+        writeln!(output, "{}", error)?;
+    }
+
+    let mut failures = 0;
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(failures);
+        }
+        match execute(app, line.trim(), &mut input, output, &mut failures).await {
+            Ok(Flow::Quit) => return Ok(failures),
+            Ok(Flow::Continue) => {}
+            Err(e) => writeln!(output, "Error: {}", app.get_error_message(&e))?,
+        }
+    }
+}
+
+async fn execute<R: BufRead, W: Write>(
+    app: &mut App,
+    line: &str,
+    input: &mut R,
+    output: &mut W,
+    failures: &mut usize,
+) -> Result<Flow> {
+    let (command, argument) = line
+        .split_once(char::is_whitespace)
+        .map(|(command, argument)| (command, argument.trim()))
+        .unwrap_or((line, ""));
+
+    match command {
+        "" => {}
+        _ if command.starts_with('#') => {}
+        "url" => {
+            app.url_input = argument.to_string();
+            writeln!(output, "URL set")?;
+        }
+        "method" => {
+            app.selected_method = match argument.to_uppercase().as_str() {
+                "GET" => HttpMethod::GET,
+                "POST" => HttpMethod::POST,
+                "PUT" => HttpMethod::PUT,
+                "DELETE" => HttpMethod::DELETE,
+                _ => return Err(RestlessError::invalid_http_method(argument)),
+            };
+            writeln!(output, "Method set")?;
+        }
+        "header" => {
+            let (key, value) = argument.split_once(':').ok_or_else(|| {
+                RestlessError::invalid_header("Headers use the format Name: value")
+            })?;
+            app.headers_input
+                .push((key.trim().to_string(), value.trim().to_string()));
+            writeln!(output, "Header added")?;
+        }
+        "param" => {
+            let (key, value) = argument.split_once('=').ok_or_else(|| {
+                RestlessError::configuration("Parameters use the format name=value")
+            })?;
+            app.params_input
+                .push((key.trim().to_string(), value.trim().to_string()));
+            writeln!(output, "Parameter added")?;
+        }
+        "test" => {
+            assertions::validate(argument)?;
+            if !app.tests_input.is_empty() {
+                app.tests_input.push('\n');
+            }
+            app.tests_input.push_str(argument);
+            writeln!(output, "Test added")?;
+        }
+        "body" if argument.is_empty() => {
+            writeln!(output, "Enter the body, then a line with only '.'")?;
+            let mut body = Vec::new();
+            loop {
+                let mut line = String::new();
+                if input.read_line(&mut line)? == 0 || line.trim_end() == "." {
+                    break;
+                }
+                body.push(line.trim_end_matches(['\n', '\r']).to_string());
+            }
+            app.body_input = body.join("\n");
+            writeln!(output, "Body set, {} lines", body.len())?;
+        }
+        "body" => {
+            app.body_input = argument.to_string();
+            writeln!(output, "Body set")?;
+        }
+        "form" => {
+            app.current_form_input = argument.to_string();
+            app.set_form_field()?;
+            writeln!(output, "Form field set")?;
+        }
+        "auth" => match argument.split_once('=') {
+            Some((key, value)) => {
+                let key = key.trim();
+                if value.trim().is_empty() {
+                    app.auth_input.remove_field(key);
+                } else {
+                    app.auth_input.set_field(key, value.trim());
+                }
+                writeln!(output, "Auth field set")?;
+            }
+            None => {
+                app.auth_input.kind = serde_json::from_value(argument.into()).map_err(|_| {
+                    RestlessError::configuration(format!("Unknown auth type '{}'", argument))
+                })?;
+                writeln!(output, "Auth type {}", app.auth_input.kind.label())?;
+            }
+        },
+        "option" => {
+            let (key, value) = argument
+                .split_once('=')
+                .ok_or_else(|| RestlessError::configuration("Options use the format key=value"))?;
+            app.options_input.set(key.trim(), value.trim())?;
+            writeln!(output, "Option set")?;
+        }
+        "env" => select_environment(app, argument, output)?,
+        "show" => show_request(app, output)?,
+        "send" => {
+            app.save_current_tab_state()?;
+            writeln!(output, "Sending...")?;
+            output.flush()?;
+            app.tabs[app.selected_tab].response = None;
+            let message = send_current_request(app, None).await?;
+            if let Some(message) = &message {
+                writeln!(output, "{}", message)?;
+            }
+            show_response(app, output)?;
+            show_events(app, output).await?;
+            let status = app.tabs[app.selected_tab]
+                .response
+                .as_ref()
+                .map(|response| response.status_code);
+            if message.is_some() || status.is_none_or(|status| status >= 400) {
+                *failures += 1;
+            }
+        }
+        "reset" => {
+            app.url_input.clear();
+            app.selected_method = HttpMethod::GET;
+            app.headers_input.clear();
+            app.params_input.clear();
+            app.disabled_headers.clear();
+            app.disabled_params.clear();
+            app.tests_input.clear();
+            app.body_input.clear();
+            app.form_input = None;
+            app.auth_input = Default::default();
+            app.options_input = Default::default();
+            app.save_current_tab_state()?;
+            writeln!(output, "Request cleared")?;
+        }
+        "help" | "?" => writeln!(output, "{}", HELP)?,
+        "quit" | "exit" | "q" => return Ok(Flow::Quit),
+        _ => writeln!(
+            output,
+            "Unknown command '{}'. Type 'help' for commands.",
+            command
+        )?,
+    }
+    Ok(Flow::Continue)
+}
+
+fn select_environment<W: Write>(app: &mut App, name: &str, output: &mut W) -> Result<()> {
+    if name.is_empty() {
+        if app.environments.is_empty() {
+            writeln!(output, "No environments defined")?;
+        }
+        for (index, env) in app.environments.iter().enumerate() {
+            let active = if app.active_environment == Some(index) {
+                " (active)"
+            } else {
+                ""
+            };
+            writeln!(output, "{}{}", env.name, active)?;
+        }
+        return Ok(());
+    }
+    if name == "none" {
+        app.active_environment = None;
+        writeln!(output, "No environment")?;
+        return Ok(());
+    }
+    let index = app
+        .environments
+        .iter()
+        .position(|env| env.name == name)
+        .ok_or_else(|| RestlessError::configuration(format!("No environment named '{}'", name)))?;
+    app.active_environment = Some(index);
+    writeln!(output, "Environment {}", name)?;
+    Ok(())
+}
+
+fn show_request<W: Write>(app: &App, output: &mut W) -> Result<()> {
+    let request = app.current_request();
+    writeln!(output, "{} {}", request.method, request.url)?;
+    for (key, value) in &request.headers {
+        writeln!(output, "Header {}: {}", key, value)?;
+    }
+    for (key, value) in &request.params {
+        writeln!(output, "Parameter {}={}", key, value)?;
+    }
+    for assertion in request.tests.lines() {
+        writeln!(output, "Test {}", assertion)?;
+    }
+    if let Some(fields) = &request.form {
+        for (name, value) in fields {
+            writeln!(output, "Form {}={}", name, value)?;
+        }
+    } else if let Some(body) = &request.body {
+        writeln!(output, "Body:\n{}", body)?;
+    }
+    if let Some(env) = app.active_environment() {
+        writeln!(output, "Environment {}", env.name)?;
+    }
+    Ok(())
+}
+
+fn show_response<W: Write>(app: &App, output: &mut W) -> Result<()> {
+    let Some(response) = &app.tabs[app.selected_tab].response else {
+        return Ok(());
+    };
+    let mut status = format!("Status {}", response.status_code);
+    if let Some(version) = &response.version {
+        status.push_str(&format!(", {}", version));
+    }
+    if let Some(transfer) = &response.transfer {
+        status.push_str(&format!(", {}", transfer.summary()));
+    }
+    if let Some(charset) = &response.charset {
+        status.push_str(&format!(", {}", charset.summary()));
+    }
+    if let Some(wire) = response.wire_summary() {
+        status.push_str(&format!(", {}", wire));
+    }
+    if response.stream.is_some() {
+        status.push_str(", event stream");
+    }
+    if let Some(metrics) = &response.metrics {
+        status.push_str(&format!(", {}", metrics.summary()));
+    }
+    writeln!(output, "{}", status)?;
+    if let Some(rate_limit) = &response.rate_limit {
+        let warning = if rate_limit.is_low() { "Warning: " } else { "" };
+        writeln!(output, "{}Rate limit {}", warning, rate_limit.summary())?;
+    }
+    writeln!(output, "Headers:")?;
+    for (key, value) in &response.headers {
+        writeln!(output, "{}: {}", key, value)?;
+    }
+    for result in &response.tests {
+        let mark = if result.passed { "Passed" } else { "Failed" };
+        match &result.detail {
+            Some(detail) => writeln!(output, "{}: {} ({})", mark, result.assertion, detail)?,
+            None => writeln!(output, "{}: {}", mark, result.assertion)?,
+        }
+    }
+    if !response.tests.is_empty() {
+        writeln!(output, "{}", assertions::summary(&response.tests))?;
+    }
+    writeln!(output, "Body:")?;
+    if response.stream.is_some() {
+        // The events follow as they arrive, see `show_events`
+        return Ok(());
+    }
+    if response.body.is_empty() {
+        writeln!(output, "(empty)")?;
+    } else {
+        writeln!(output, "{}", response.body)?;
+    }
+    writeln!(output, "End of response")?;
+    Ok(())
+}
+
+/// Prints the events of a streaming response until the server closes it
+async fn show_events<W: Write>(app: &mut App, output: &mut W) -> Result<()> {
+    let Some(response) = app.tabs[app.selected_tab].response.as_mut() else {
+        return Ok(());
+    };
+    let Some(mut stream) = response.stream.take() else {
+        return Ok(());
+    };
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(event) => {
+                write!(output, "{}", event.to_text())?;
+                output.flush()?;
+                response.push_event(&event);
+            }
+            Err(error) => writeln!(output, "Stream error: {}", error)?,
+        }
+    }
+    writeln!(output, "End of response")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::TestServer;
+    use std::io::Cursor;
+
+    async fn run_script(app: &mut App, script: &str) -> String {
+        let mut output = Vec::new();
+        run(app, Cursor::new(script.to_string()), &mut output, None)
+            .await
+            .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_auth_options_and_comments() {
+        let mut app = App::new();
+        let output = run_script(
+            &mut app,
+            "# a comment\nauth api_key\nauth key=X-Key\nauth bogus\noption insecure=true\n\
+             option insecure=maybe\n",
+        )
+        .await;
+
+        assert!(!output.contains("Unknown command"));
+        assert!(output.contains("Unknown auth type 'bogus'"));
+        assert!(output.contains("insecure must be true or false"));
+        assert_eq!(app.auth_input.kind, crate::logic::auth::AuthKind::ApiKey);
+        assert_eq!(app.auth_input.field("key"), Some("X-Key"));
+        assert!(app.options_input.is_insecure());
+    }
+
+    #[tokio::test]
+    async fn test_form_fields() {
+        let mut app = App::new();
+        let output = run_script(
+            &mut app,
+            "url https://example.com/upload\nform note=hi\nform file=@./a.png\nform nope\nshow\n",
+        )
+        .await;
+
+        assert!(output.contains("Form note=hi\nForm file=@./a.png"));
+        assert!(output.contains("Form fields use name=value"));
+        assert_eq!(
+            app.current_request().form.map(|fields| fields.len()),
+            Some(2)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_sends_are_counted() {
+        let mut app = App::new();
+        let mut output = Vec::new();
+        let failures = run(
+            &mut app,
+            Cursor::new("url http://127.0.0.1:1/\nsend\nsend\n".to_string()),
+            &mut output,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(failures, 2);
+    }
+
+    #[tokio::test]
+    async fn test_lint_errors_stop_sends() {
+        use crate::logic::lint::{Level, Rules};
+
+        let mut app = App::new();
+        app.lint = Some(Rules {
+            require_accept: Level::Error,
+            ..Default::default()
+        });
+        let mut output = Vec::new();
+        let failures = run(
+            &mut app,
+            Cursor::new("url http://127.0.0.1:1/\nsend\n".to_string()),
+            &mut output,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(failures, 1);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Not sent, the lint rules found:\nerror [require_accept]"));
+        assert!(app.history.search("", 10).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_build_request() {
+        let mut app = App::new();
+        let output = run_script(
+            &mut app,
+            "url https://example.com/items\nmethod post\nheader Accept: text/plain\n\
+             param page=2\nbody\n{\n  \"a\": 1\n}\n.\nshow\nbogus\nmethod PATCH\nquit\nurl ignored\n",
+        )
+        .await;
+
+        assert!(output.contains("POST https://example.com/items"));
+        assert!(output.contains("Header Accept: text/plain"));
+        assert!(output.contains("Parameter page=2"));
+        assert!(output.contains("Body set, 3 lines"));
+        assert!(output.contains("Unknown command 'bogus'"));
+        assert!(output.contains("Error: "));
+        assert_eq!(app.body_input, "{\n  \"a\": 1\n}");
+        assert_eq!(app.url_input, "https://example.com/items");
+    }
+
+    #[tokio::test]
+    async fn test_send_prints_response() {
+        let server = TestServer::new(&[
+            b"HTTP/1.1 201 Created\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+        ]);
+
+        let mut app = App::new();
+        let output = run_script(
+            &mut app,
+            &format!(
+                "url {}\ntest status == 201\ntest body contains no\nsend\n",
+                server.url("/")
+            ),
+        )
+        .await;
+
+        assert!(output.contains("Status 201, HTTP/1.1"));
+        assert!(output.contains("Passed: status == 201\nFailed: body contains no (got ok)"));
+        assert!(output.contains("1/2 tests passed"));
+        assert!(output.contains("content-length: 2"));
+        assert!(output.contains("Body:\nok\nEnd of response"));
+    }
+}
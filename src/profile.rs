@@ -0,0 +1,286 @@
+//! Sharing settings between machines as profiles
+//!
+//! A profile is a config file with a `[profile]` table naming it:
+//!
+//! ```toml
+//! [profile]
+//! name = "team"
+//!
+//! [connection]
+//! ca_cert = "~/certs/internal-ca.pem"
+//! ```
+//!
+//! `restless profile export <name>` prints the current config as a profile,
+//! without its secrets so it can be shared: the secret connection options
+//! (`proxy_auth`, `client_cert_password`) and default headers carrying
+//! credentials, like `Authorization` or `X-API-Key`. `restless profile import
+//! <file>` validates a profile and lists what it would change; with
+//! `--apply` it replaces `config.toml`, keeping the old file as
+//! `config.toml.bak`. Private settings the profile leaves out stay as they
+//! were.
+
+use crate::config::Config;
+use crate::error::{RestlessError, Result};
+use crate::logic::headers;
+use crate::logic::options::RequestOptions;
+use std::fmt;
+use std::path::Path;
+
+/// Whether the setting `key` of `section` is left out of exported profiles
+fn is_private(section: &str, key: &str) -> bool {
+    match section {
+        "connection" => RequestOptions::is_secret(key),
+        "headers" => headers::is_credential(key),
+        _ => false,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    /// The config file content, without the `[profile]` table
+    pub settings: toml::Table,
+}
+
+/// One setting a profile would change
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    Added(String, toml::Value),
+    Removed(String, toml::Value),
+    Changed(String, toml::Value, toml::Value),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added(key, value) => write!(f, "+ {} = {}", key, value),
+            Change::Removed(key, value) => write!(f, "- {} = {}", key, value),
+            Change::Changed(key, old, new) => write!(f, "~ {} = {} -> {}", key, old, new),
+        }
+    }
+}
+
+impl Profile {
+    /// Reads and validates a profile
+    pub fn parse(content: &str) -> Result<Profile> {
+        let invalid =
+            |e: &dyn fmt::Display| RestlessError::configuration(format!("Invalid profile: {}", e));
+        let mut settings: toml::Table = toml::from_str(content).map_err(|e| invalid(&e))?;
+        let name = settings
+            .remove("profile")
+            .and_then(|profile| profile.get("name")?.as_str().map(str::to_string))
+            .filter(|name| !name.trim().is_empty())
+            .ok_or_else(|| invalid(&"missing [profile] name"))?;
+        let config: Config = settings.clone().try_into().map_err(|e| invalid(&e))?;
+        config.connection.validate().map_err(|e| invalid(&e))?;
+        Ok(Profile { name, settings })
+    }
+
+    /// The profile as a file, private settings left out
+    pub fn to_file(&self) -> Result<String> {
+        let mut settings = self.settings.clone();
+        for (section, value) in settings.iter_mut() {
+            if let toml::Value::Table(table) = value {
+                table.retain(|key, _| !is_private(section, key));
+            }
+        }
+        let mut header = toml::Table::new();
+        header.insert("name".to_string(), self.name.clone().into());
+        let mut file = toml::Table::new();
+        file.insert("profile".to_string(), header.into());
+        let content =
+            toml::to_string(&file).map_err(|e| RestlessError::configuration(e.to_string()))?;
+        let settings =
+            toml::to_string(&settings).map_err(|e| RestlessError::configuration(e.to_string()))?;
+        Ok(format!("{}\n{}", content, settings))
+    }
+
+    /// The settings to apply over `current`: the profile's, plus the
+    /// private ones it leaves out
+    pub fn merged(&self, current: &toml::Table) -> toml::Table {
+        let mut settings = self.settings.clone();
+        for (section, value) in current {
+            let toml::Value::Table(own) = value else {
+                continue;
+            };
+            for (key, value) in own.iter().filter(|(key, _)| is_private(section, key)) {
+                let table = settings
+                    .entry(section.clone())
+                    .or_insert_with(|| toml::Table::new().into());
+                if let toml::Value::Table(table) = table {
+                    table.entry(key.clone()).or_insert_with(|| value.clone());
+                }
+            }
+        }
+        settings
+    }
+
+    /// What applying the profile would change in `current`
+    pub fn diff(&self, current: &toml::Table) -> Vec<Change> {
+        let incoming = flatten(&self.merged(current));
+        let current = flatten(current);
+        let mut changes: Vec<Change> = current
+            .iter()
+            .filter_map(|(key, old)| match incoming.iter().find(|(k, _)| k == key) {
+                None => Some(Change::Removed(key.clone(), old.clone())),
+                Some((_, new)) if new != old => {
+                    Some(Change::Changed(key.clone(), old.clone(), new.clone()))
+                }
+                Some(_) => None,
+            })
+            .collect();
+        changes.extend(
+            incoming
+                .into_iter()
+                .filter(|(key, _)| !current.iter().any(|(k, _)| k == key))
+                .map(|(key, value)| Change::Added(key, value)),
+        );
+        changes
+    }
+}
+
+/// Settings as `section.key` and value pairs
+fn flatten(table: &toml::Table) -> Vec<(String, toml::Value)> {
+    let mut settings = Vec::new();
+    for (key, value) in table {
+        match value {
+            toml::Value::Table(inner) => settings.extend(
+                flatten(inner)
+                    .into_iter()
+                    .map(|(inner_key, value)| (format!("{}.{}", key, inner_key), value)),
+            ),
+            value => settings.push((key.clone(), value.clone())),
+        }
+    }
+    settings
+}
+
+/// The current config file as a table; a missing file is an empty one
+pub fn read_config(path: &Path) -> Result<toml::Table> {
+    if !path.exists() {
+        return Ok(toml::Table::new());
+    }
+    let content = std::fs::read_to_string(path)?;
+    toml::from_str(&content).map_err(|e| {
+        RestlessError::configuration(format!("Invalid config file {}: {}", path.display(), e))
+    })
+}
+
+/// Replaces the config file at `path`, currently `current`, with the
+/// profile's settings, keeping a backup
+pub fn apply(profile: &Profile, current: &toml::Table, path: &Path) -> Result<()> {
+    if path.exists() {
+        std::fs::copy(path, path.with_extension("toml.bak"))?;
+    } else if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let settings = toml::to_string(&profile.merged(current))
+        .map_err(|e| RestlessError::configuration(e.to_string()))?;
+    std::fs::write(path, settings)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_round_trip() {
+        let settings: toml::Table = toml::from_str(
+            "[connection]\nproxy = \"http://proxy:3128\"\nproxy_auth = \"me:secret\"\n\n[terminal]\ntmux_status = true\n",
+        )
+        .unwrap();
+        let profile = Profile {
+            name: "team".to_string(),
+            settings,
+        };
+        let file = profile.to_file().unwrap();
+        assert!(file.starts_with("[profile]\nname = \"team\"\n"));
+        assert!(!file.contains("secret"));
+        assert!(file.contains("tmux_status"));
+
+        let imported = Profile::parse(&file).unwrap();
+        assert_eq!(imported.name, "team");
+        assert_eq!(
+            imported.settings["connection"]["proxy"].as_str(),
+            Some("http://proxy:3128")
+        );
+    }
+
+    #[test]
+    fn test_profile_leaves_out_secret_options() {
+        let settings: toml::Table = toml::from_str(
+            "[connection]\nclient_cert = \"me.pem\"\nclient_cert_password = \"hunter2\"\nproxy_auth = \"me:pw\"\n",
+        )
+        .unwrap();
+        let profile = Profile {
+            name: "team".to_string(),
+            settings: settings.clone(),
+        };
+        let file = profile.to_file().unwrap();
+        assert!(!file.contains("hunter2"));
+        assert!(!file.contains("me:pw"));
+        assert!(file.contains("client_cert = \"me.pem\""));
+
+        // Applying it keeps the ones already set here
+        let imported = Profile::parse(&file).unwrap();
+        assert_eq!(imported.merged(&settings), settings);
+    }
+
+    #[test]
+    fn test_profile_leaves_out_credential_headers() {
+        let settings: toml::Table = toml::from_str(
+            "[headers]\nAccept = \"application/json\"\nAuthorization = \"Bearer abc\"\nCookie = \"session=1\"\nX-API-Key = \"k-123\"\n",
+        )
+        .unwrap();
+        let profile = Profile {
+            name: "team".to_string(),
+            settings: settings.clone(),
+        };
+        let file = profile.to_file().unwrap();
+        for secret in ["Bearer abc", "session=1", "k-123"] {
+            assert!(!file.contains(secret), "{}", secret);
+        }
+        assert!(file.contains("Accept = \"application/json\""));
+
+        let imported = Profile::parse(&file).unwrap();
+        assert_eq!(imported.merged(&settings), settings);
+        assert!(imported.diff(&settings).is_empty());
+    }
+
+    #[test]
+    fn test_profile_validation() {
+        assert!(Profile::parse("[connection]\nproxy = \"http://proxy\"\n").is_err());
+        assert!(Profile::parse("[profile]\nname = \"x\"\n[nonsense]\n").is_err());
+        assert!(
+            Profile::parse("[profile]\nname = \"x\"\n[connection]\nproxy = \"proxy:3128\"\n")
+                .is_err()
+        );
+        assert!(Profile::parse("[profile]\nname = \"x\"\n").is_ok());
+    }
+
+    #[test]
+    fn test_profile_diff() {
+        let current: toml::Table = toml::from_str(
+            "[connection]\nproxy = \"http://old\"\ninsecure = true\nproxy_auth = \"me:pw\"\n[terminal]\ntitle = true\n",
+        )
+        .unwrap();
+        let profile = Profile::parse(
+            "[profile]\nname = \"x\"\n[connection]\nproxy = \"http://new\"\n[terminal]\ntitle = true\ntmux_status = true\n",
+        )
+        .unwrap();
+        let changes: Vec<String> = profile
+            .diff(&current)
+            .iter()
+            .map(Change::to_string)
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                "- connection.insecure = true",
+                "~ connection.proxy = \"http://old\" -> \"http://new\"",
+                "+ terminal.tmux_status = true",
+            ]
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Reopening the tabs of the last run
+//!
+//! On exit the open tabs are written to `session.json` in the config
+//! directory, and the next launch starts with them again. Only the requests
+//! are kept: responses are not, so a restored tab starts out unsent. With
+//! `restore_session = false` under `[ui]` restless starts with one empty
+//! tab and leaves the file alone.
+
+use crate::app::tab::Tab;
+use crate::collections::SavedRequest;
+use crate::error::{RestlessError, Result};
+use crate::logic::auth::Auth;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    /// Index of the tab that was selected
+    #[serde(default)]
+    pub selected: usize,
+    pub tabs: Vec<SessionTab>,
+}
+
+/// A tab's request under the tab's name, and where it came from
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SessionTab {
+    #[serde(flatten)]
+    pub request: SavedRequest,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_file: Option<(PathBuf, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+}
+
+impl Session {
+    pub fn capture(tabs: &[Tab], selected: usize) -> Session {
+        Session {
+            selected,
+            tabs: tabs
+                .iter()
+                .map(|tab| SessionTab {
+                    request: SavedRequest::from_request(&tab.name, &tab.request, tab.panel.clone()),
+                    http_file: tab.http_file.clone(),
+                    filter: tab.filter.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// The tabs to reopen, skipping any whose request can't be loaded
+    pub fn tabs(&self) -> Vec<Tab> {
+        self.tabs
+            .iter()
+            .filter_map(|saved| {
+                let request = saved.request.to_request(&Auth::default()).ok()?;
+                let mut tab = Tab::new(saved.request.name.clone(), String::new());
+                tab.request = request;
+                tab.panel = saved.request.panel.clone();
+                tab.http_file = saved.http_file.clone();
+                tab.filter = saved.filter.clone();
+                Some(tab)
+            })
+            .collect()
+    }
+}
+
+/// Reads the session file; `None` when there is none
+pub fn load(path: &Path) -> Result<Option<Session>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)?;
+    let session = serde_json::from_str(&content).map_err(|e| {
+        RestlessError::configuration(format!("Invalid session file {}: {}", path.display(), e))
+    })?;
+    Ok(Some(session))
+}
+
+pub fn save(path: &Path, session: &Session) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(session)? + "\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::Method;
+
+    #[test]
+    fn test_session_round_trip() {
+        let mut users = Tab::new("Users".to_string(), "http://api/users".to_string());
+        users.request.method = Method::POST;
+        users
+            .request
+            .headers
+            .push(("Accept".to_string(), "application/json".to_string()));
+        users.request.body = Some("{}".to_string());
+        users.filter = Some("$.items".to_string());
+        let mut health = Tab::new("Health".to_string(), "http://api/health".to_string());
+        health.http_file = Some(("api.http".into(), "Health".to_string()));
+
+        let dir =
+            std::env::temp_dir().join(format!("restless-session-test-{}", std::process::id()));
+        let path = dir.join("session.json");
+        assert_eq!(load(&path).unwrap(), None);
+        save(&path, &Session::capture(&[users, health], 1)).unwrap();
+
+        let session = load(&path).unwrap().unwrap();
+        assert_eq!(session.selected, 1);
+        let tabs = session.tabs();
+        assert_eq!(tabs.len(), 2);
+        assert_eq!(tabs[0].name, "Users");
+        assert_eq!(tabs[0].request.method, Method::POST);
+        assert_eq!(tabs[0].request.body.as_deref(), Some("{}"));
+        assert_eq!(tabs[0].request.headers.len(), 1);
+        assert_eq!(tabs[0].filter.as_deref(), Some("$.items"));
+        assert_eq!(tabs[1].request.url, "http://api/health");
+        assert!(tabs[1].http_file.is_some());
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(load(&path).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,117 @@
+//! History kept in memory for the current run only
+
+use super::{Entry, Retention, Revision, RunRecord, Storage, UrlStats};
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    /// Oldest first
+    entries: Vec<Entry>,
+    /// Oldest first
+    revisions: Vec<Revision>,
+    /// Oldest first
+    runs: Vec<RunRecord>,
+}
+
+impl Storage for MemoryStorage {
+    fn record(&mut self, entry: &Entry) -> Result<()> {
+        self.entries.push(entry.clone());
+        Ok(())
+    }
+
+    fn search(&self, query: &str, limit: usize) -> Result<Vec<Entry>> {
+        let query = query.to_lowercase();
+        Ok(self
+            .entries
+            .iter()
+            .rev()
+            .filter(|entry| entry.url.to_lowercase().contains(&query))
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    fn stats(&self) -> Result<Vec<UrlStats>> {
+        let mut stats: Vec<UrlStats> = Vec::new();
+        for entry in &self.entries {
+            let found = stats
+                .iter_mut()
+                .find(|s| s.method == entry.method && s.url == entry.url);
+            let Some(found) = found else {
+                stats.push(UrlStats {
+                    method: entry.method.clone(),
+                    url: entry.url.clone(),
+                    count: 1,
+                    errors: usize::from(entry.is_error()),
+                    // The sum for now, divided below
+                    average: entry.elapsed,
+                    last_sent: entry.sent_at,
+                });
+                continue;
+            };
+            found.count += 1;
+            found.errors += usize::from(entry.is_error());
+            found.average += entry.elapsed;
+            found.last_sent = found.last_sent.max(entry.sent_at);
+        }
+        for s in &mut stats {
+            s.average /= s.count as u32;
+        }
+        stats.sort_by(|a, b| b.count.cmp(&a.count).then(b.last_sent.cmp(&a.last_sent)));
+        Ok(stats)
+    }
+
+    fn prune(&mut self, retention: &Retention, now: DateTime<Utc>) -> Result<usize> {
+        let before = self.entries.len();
+        if let Some(cutoff) = retention.cutoff(now) {
+            self.entries.retain(|entry| entry.sent_at >= cutoff);
+        }
+        if let Some(max) = retention.max_entries {
+            let excess = self.entries.len().saturating_sub(max);
+            self.entries.drain(..excess);
+        }
+        Ok(before - self.entries.len())
+    }
+
+    fn record_revision(&mut self, revision: &Revision) -> Result<()> {
+        self.revisions.push(revision.clone());
+        Ok(())
+    }
+
+    fn revisions(&self, collection: &str, request: &str) -> Result<Vec<Revision>> {
+        Ok(self
+            .revisions
+            .iter()
+            .rev()
+            .filter(|r| r.collection == collection && r.request == request)
+            .cloned()
+            .collect())
+    }
+
+    fn record_run(&mut self, run: &RunRecord) -> Result<()> {
+        self.runs.push(run.clone());
+        Ok(())
+    }
+
+    fn runs(&self, title: &str, limit: usize) -> Result<Vec<RunRecord>> {
+        Ok(self
+            .runs
+            .iter()
+            .rev()
+            .filter(|run| run.title == title)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_storage() {
+        crate::storage::tests::check_storage(&mut MemoryStorage::default());
+    }
+}
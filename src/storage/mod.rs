@@ -0,0 +1,398 @@
+//! History of sent requests
+//!
+//! Every send, and every send of a run such as a matrix send, is recorded
+//! through the [`Storage`] trait, as are the revisions of saved requests and
+//! the results of finished runs of a collection or the open tabs.
+//! The default backend is a SQLite database, `history.db` in the config
+//! directory, which keeps searching and aggregating fast with tens of
+//! thousands of entries; the memory backend keeps nothing between runs. Old
+//! sends are pruned at startup, revisions and run results are kept:
+//!
+//! ```toml
+//! [history]
+//! backend = "sqlite"   # or "memory"
+//! max_entries = 50000
+//! max_age_days = 90
+//! ```
+
+pub mod memory;
+pub mod sqlite;
+
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::path::Path;
+use std::time::Duration;
+
+/// One recorded send
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub sent_at: DateTime<Utc>,
+    pub method: String,
+    /// URL with its query, secrets redacted
+    pub url: String,
+    /// Status code, or why no response arrived
+    pub status: std::result::Result<u16, String>,
+    pub elapsed: Duration,
+    /// Run the send was part of, like `matrix region=eu`; `None` for a
+    /// send of its own
+    pub run: Option<String>,
+}
+
+impl Entry {
+    /// Whether the send failed or got a 4xx/5xx
+    pub fn is_error(&self) -> bool {
+        self.status.as_ref().map_or(true, |&status| status >= 400)
+    }
+}
+
+/// A saved request as it was at one point
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Revision {
+    pub saved_at: DateTime<Utc>,
+    pub collection: String,
+    pub request: String,
+    /// The saved request as JSON
+    pub content: String,
+}
+
+/// How a finished run went
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunRecord {
+    pub finished_at: DateTime<Utc>,
+    /// What ran: a collection's name, or the open tabs
+    pub title: String,
+    /// Its requests in the order they were sent
+    pub steps: Vec<RunStep>,
+}
+
+/// How one request of a run went
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunStep {
+    pub name: String,
+    pub method: String,
+    /// URL as written, variables not filled in
+    pub url: String,
+    /// Status code, or why no response arrived
+    pub status: std::result::Result<u16, String>,
+    pub elapsed: Duration,
+    /// Assertions run on the response, and how many of them passed
+    pub tests: usize,
+    pub passed_tests: usize,
+}
+
+impl RunStep {
+    /// Whether a response below 400 came back and all its tests passed
+    pub fn passed(&self) -> bool {
+        matches!(self.status, Ok(code) if code < 400) && self.passed_tests == self.tests
+    }
+}
+
+/// Sends of one method and URL, summed up
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UrlStats {
+    pub method: String,
+    pub url: String,
+    pub count: usize,
+    /// Sends that failed or got a 4xx/5xx
+    pub errors: usize,
+    pub average: Duration,
+    pub last_sent: DateTime<Utc>,
+}
+
+/// Where history is kept
+pub trait Storage {
+    fn record(&mut self, entry: &Entry) -> Result<()>;
+
+    /// Entries whose URL contains `query`, ignoring case, newest first
+    fn search(&self, query: &str, limit: usize) -> Result<Vec<Entry>>;
+
+    /// Statistics per method and URL, most sent first
+    fn stats(&self) -> Result<Vec<UrlStats>>;
+
+    /// Drops entries beyond the retention limits, returning how many
+    fn prune(&mut self, retention: &Retention, now: DateTime<Utc>) -> Result<usize>;
+
+    fn record_revision(&mut self, revision: &Revision) -> Result<()>;
+
+    /// Revisions of one saved request, newest first
+    fn revisions(&self, collection: &str, request: &str) -> Result<Vec<Revision>>;
+
+    fn record_run(&mut self, run: &RunRecord) -> Result<()>;
+
+    /// Results of the runs titled `title`, newest first
+    fn runs(&self, title: &str, limit: usize) -> Result<Vec<RunRecord>>;
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    #[default]
+    Sqlite,
+    Memory,
+}
+
+/// The `[history]` section of the config file
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HistorySettings {
+    pub backend: Backend,
+    pub max_entries: Option<usize>,
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for HistorySettings {
+    fn default() -> Self {
+        HistorySettings {
+            backend: Backend::default(),
+            max_entries: Some(50_000),
+            max_age_days: None,
+        }
+    }
+}
+
+impl HistorySettings {
+    pub fn retention(&self) -> Retention {
+        Retention {
+            max_entries: self.max_entries,
+            max_age_days: self.max_age_days,
+        }
+    }
+}
+
+/// How much history to keep; `None` means no limit
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Retention {
+    pub max_entries: Option<usize>,
+    pub max_age_days: Option<u32>,
+}
+
+impl Retention {
+    /// Entries sent before this are dropped
+    pub fn cutoff(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.max_age_days
+            .map(|days| now - chrono::Duration::days(i64::from(days)))
+    }
+}
+
+/// Opens the configured backend, SQLite at `path` when there is one
+pub fn open(settings: &HistorySettings, path: Option<&Path>) -> Result<Box<dyn Storage>> {
+    match (settings.backend, path) {
+        (Backend::Sqlite, Some(path)) => Ok(Box::new(sqlite::SqliteStorage::open(path)?)),
+        _ => Ok(Box::<memory::MemoryStorage>::default()),
+    }
+}
+
+/// One line per entry: time, method, status, duration and URL
+pub fn format_entry(entry: &Entry) -> String {
+    let status = match &entry.status {
+        Ok(status) => status.to_string(),
+        Err(_) => "ERR".to_string(),
+    };
+    let mut line = format!(
+        "{}  {:<7} {:>3} {:>7}  {}",
+        entry
+            .sent_at
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S"),
+        entry.method,
+        status,
+        format!("{}ms", entry.elapsed.as_millis()),
+        entry.url
+    );
+    if let Some(run) = &entry.run {
+        line.push_str(&format!("  ({})", run));
+    }
+    line
+}
+
+/// A line with when the run finished and how many requests passed, then a
+/// line per request like [`format_entry`] with its passed tests
+pub fn format_run(run: &RunRecord) -> String {
+    let passed = run.steps.iter().filter(|step| step.passed()).count();
+    let mut text = format!(
+        "{}  {}: {}/{} requests passed",
+        run.finished_at
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S"),
+        run.title,
+        passed,
+        run.steps.len()
+    );
+    for step in &run.steps {
+        let status = match &step.status {
+            Ok(status) => status.to_string(),
+            Err(_) => "ERR".to_string(),
+        };
+        text.push_str(&format!(
+            "\n  {:<7} {:>3} {:>7}  {:>5}  {}  ({})",
+            step.method,
+            status,
+            format!("{}ms", step.elapsed.as_millis()),
+            format!("{}/{}", step.passed_tests, step.tests),
+            step.url,
+            step.name
+        ));
+        if let Err(error) = &step.status {
+            text.push_str(&format!("\n    {}", error));
+        }
+    }
+    text
+}
+
+/// One line per method and URL: sends, errors, average time and URL
+pub fn format_stats(stats: &UrlStats) -> String {
+    format!(
+        "{:>6} sends {:>5} errors {:>7} avg  {:<7} {}",
+        stats.count,
+        stats.errors,
+        format!("{}ms", stats.average.as_millis()),
+        stats.method,
+        stats.url
+    )
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    pub fn entry(seconds: i64, method: &str, url: &str, status: u16) -> Entry {
+        Entry {
+            sent_at: Utc.timestamp_opt(1_700_000_000 + seconds, 0).unwrap(),
+            method: method.to_string(),
+            url: url.to_string(),
+            status: Ok(status),
+            elapsed: Duration::from_millis(100 + seconds as u64),
+            run: None,
+        }
+    }
+
+    /// The behaviour every backend shares
+    pub fn check_storage(storage: &mut dyn Storage) {
+        storage
+            .record(&entry(0, "GET", "http://api/Users", 200))
+            .unwrap();
+        storage
+            .record(&entry(10, "POST", "http://api/users", 201))
+            .unwrap();
+        storage
+            .record(&entry(20, "GET", "http://api/users", 500))
+            .unwrap();
+        let mut failed = entry(30, "GET", "http://api/orders", 0);
+        failed.status = Err("connection refused".to_string());
+        failed.run = Some("matrix region=eu".to_string());
+        storage.record(&failed).unwrap();
+
+        let found = storage.search("USERS", 10).unwrap();
+        let urls: Vec<_> = found.iter().map(|e| e.sent_at.timestamp() % 100).collect();
+        assert_eq!(urls, vec![20, 10, 0]);
+        assert_eq!(storage.search("", 2).unwrap().len(), 2);
+        assert_eq!(storage.search("", 1).unwrap(), vec![failed.clone()]);
+
+        let stats = storage.stats().unwrap();
+        assert_eq!(stats.len(), 4);
+        assert_eq!(stats[0].url, "http://api/orders");
+        let users = stats
+            .iter()
+            .find(|s| s.method == "GET" && s.url == "http://api/users")
+            .unwrap();
+        assert_eq!((users.count, users.errors), (1, 1));
+        assert_eq!(users.average, Duration::from_millis(120));
+
+        let now = Utc.timestamp_opt(1_700_000_000 + 86_400 + 15, 0).unwrap();
+        let by_age = Retention {
+            max_entries: None,
+            max_age_days: Some(1),
+        };
+        assert_eq!(storage.prune(&by_age, now).unwrap(), 2);
+        let by_count = Retention {
+            max_entries: Some(1),
+            max_age_days: None,
+        };
+        assert_eq!(storage.prune(&by_count, now).unwrap(), 1);
+        assert_eq!(storage.search("", 10).unwrap(), vec![failed]);
+
+        let revision = |seconds: i64, request: &str, content: &str| Revision {
+            saved_at: Utc.timestamp_opt(1_700_000_000 + seconds, 0).unwrap(),
+            collection: "My API".to_string(),
+            request: request.to_string(),
+            content: content.to_string(),
+        };
+        storage
+            .record_revision(&revision(0, "Users", "v1"))
+            .unwrap();
+        storage
+            .record_revision(&revision(5, "Orders", "v1"))
+            .unwrap();
+        storage
+            .record_revision(&revision(9, "Users", "v2"))
+            .unwrap();
+        assert_eq!(
+            storage.revisions("My API", "Users").unwrap(),
+            vec![revision(9, "Users", "v2"), revision(0, "Users", "v1")]
+        );
+        assert!(storage.revisions("Other", "Users").unwrap().is_empty());
+
+        let run = |seconds: i64, title: &str, status| RunRecord {
+            finished_at: Utc.timestamp_opt(1_700_000_000 + seconds, 0).unwrap(),
+            title: title.to_string(),
+            steps: vec![
+                RunStep {
+                    name: "Login".to_string(),
+                    method: "POST".to_string(),
+                    url: "{{base}}/login".to_string(),
+                    status: Ok(200),
+                    elapsed: Duration::from_millis(40),
+                    tests: 2,
+                    passed_tests: 2,
+                },
+                RunStep {
+                    name: "Me".to_string(),
+                    method: "GET".to_string(),
+                    url: "{{base}}/me".to_string(),
+                    status,
+                    elapsed: Duration::from_millis(7),
+                    tests: 0,
+                    passed_tests: 0,
+                },
+            ],
+        };
+        storage.record_run(&run(0, "My API", Ok(200))).unwrap();
+        storage.record_run(&run(5, "Open tabs", Ok(200))).unwrap();
+        let refused = Err("connection refused".to_string());
+        storage
+            .record_run(&run(9, "My API", refused.clone()))
+            .unwrap();
+        assert_eq!(
+            storage.runs("My API", 10).unwrap(),
+            vec![run(9, "My API", refused), run(0, "My API", Ok(200))]
+        );
+        assert_eq!(storage.runs("My API", 1).unwrap().len(), 1);
+        let text = format_run(&run(9, "My API", Err("refused".to_string())));
+        assert!(text.contains(
+            "My API: 1/2 requests passed\n  POST    200    40ms    2/2  {{base}}/login  (Login)\n"
+        ));
+        assert!(text.ends_with("GET     ERR     7ms    0/0  {{base}}/me  (Me)\n    refused"));
+        assert!(storage.runs("Other", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_history_settings() {
+        let settings: HistorySettings =
+            toml::from_str("backend = \"memory\"\nmax_age_days = 30\n").unwrap();
+        assert_eq!(settings.backend, Backend::Memory);
+        assert_eq!(
+            settings.retention(),
+            Retention {
+                max_entries: Some(50_000),
+                max_age_days: Some(30),
+            }
+        );
+        assert_eq!(
+            toml::from_str::<HistorySettings>("").unwrap(),
+            HistorySettings::default()
+        );
+        assert!(toml::from_str::<HistorySettings>("backend = \"csv\"").is_err());
+    }
+}
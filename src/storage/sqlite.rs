@@ -0,0 +1,297 @@
+//! History in a SQLite database
+
+use super::{Entry, Retention, Revision, RunRecord, RunStep, Storage, UrlStats};
+use crate::error::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Row};
+use std::path::Path;
+use std::time::Duration;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS history (
+    id INTEGER PRIMARY KEY,
+    sent_at INTEGER NOT NULL,
+    method TEXT NOT NULL,
+    url TEXT NOT NULL,
+    status INTEGER,
+    error TEXT,
+    elapsed_us INTEGER NOT NULL,
+    run TEXT
+);
+CREATE INDEX IF NOT EXISTS history_sent_at ON history (sent_at);
+CREATE INDEX IF NOT EXISTS history_method_url ON history (method, url);
+CREATE TABLE IF NOT EXISTS revisions (
+    id INTEGER PRIMARY KEY,
+    saved_at INTEGER NOT NULL,
+    collection TEXT NOT NULL,
+    request TEXT NOT NULL,
+    content TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS revisions_request ON revisions (collection, request);
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    finished_at INTEGER NOT NULL,
+    title TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS runs_title ON runs (title);
+CREATE TABLE IF NOT EXISTS run_steps (
+    run_id INTEGER NOT NULL REFERENCES runs (id),
+    position INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    method TEXT NOT NULL,
+    url TEXT NOT NULL,
+    status INTEGER,
+    error TEXT,
+    elapsed_us INTEGER NOT NULL,
+    tests INTEGER NOT NULL,
+    passed_tests INTEGER NOT NULL,
+    PRIMARY KEY (run_id, position)
+);
+";
+
+/// Entries are ordered by `id`, which grows with every send
+pub struct SqliteStorage {
+    connection: Connection,
+}
+
+impl SqliteStorage {
+    /// Opens the database, creating it and its directory if needed
+    pub fn open(path: &Path) -> Result<SqliteStorage> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        SqliteStorage::with_connection(Connection::open(path)?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<SqliteStorage> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(SqliteStorage { connection })
+    }
+}
+
+fn millis_to_time(millis: i64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(millis).unwrap_or_default()
+}
+
+fn entry_from_row(row: &Row) -> rusqlite::Result<Entry> {
+    let status: Option<u16> = row.get("status")?;
+    let error: Option<String> = row.get("error")?;
+    Ok(Entry {
+        sent_at: millis_to_time(row.get("sent_at")?),
+        method: row.get("method")?,
+        url: row.get("url")?,
+        status: status.ok_or_else(|| error.unwrap_or_default()),
+        elapsed: Duration::from_micros(row.get("elapsed_us")?),
+        run: row.get("run")?,
+    })
+}
+
+fn step_from_row(row: &Row) -> rusqlite::Result<RunStep> {
+    let status: Option<u16> = row.get("status")?;
+    let error: Option<String> = row.get("error")?;
+    Ok(RunStep {
+        name: row.get("name")?,
+        method: row.get("method")?,
+        url: row.get("url")?,
+        status: status.ok_or_else(|| error.unwrap_or_default()),
+        elapsed: Duration::from_micros(row.get("elapsed_us")?),
+        tests: row.get("tests")?,
+        passed_tests: row.get("passed_tests")?,
+    })
+}
+
+impl Storage for SqliteStorage {
+    fn record(&mut self, entry: &Entry) -> Result<()> {
+        let (status, error) = match &entry.status {
+            Ok(status) => (Some(*status), None),
+            Err(error) => (None, Some(error.as_str())),
+        };
+        self.connection.execute(
+            "INSERT INTO history (sent_at, method, url, status, error, elapsed_us, run)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                entry.sent_at.timestamp_millis(),
+                entry.method,
+                entry.url,
+                status,
+                error,
+                entry.elapsed.as_micros() as i64,
+                entry.run,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn search(&self, query: &str, limit: usize) -> Result<Vec<Entry>> {
+        let mut statement = self.connection.prepare_cached(
+            "SELECT * FROM history WHERE instr(lower(url), lower(?1)) > 0
+             ORDER BY id DESC LIMIT ?2",
+        )?;
+        let entries = statement
+            .query_map(params![query, limit as i64], entry_from_row)?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(entries)
+    }
+
+    fn stats(&self) -> Result<Vec<UrlStats>> {
+        let mut statement = self.connection.prepare_cached(
+            "SELECT method, url, COUNT(*),
+                    SUM(status IS NULL OR status >= 400),
+                    AVG(elapsed_us), MAX(sent_at)
+             FROM history GROUP BY method, url
+             ORDER BY COUNT(*) DESC, MAX(sent_at) DESC",
+        )?;
+        let stats = statement
+            .query_map([], |row| {
+                Ok(UrlStats {
+                    method: row.get(0)?,
+                    url: row.get(1)?,
+                    count: row.get(2)?,
+                    errors: row.get(3)?,
+                    average: Duration::from_micros(row.get::<_, f64>(4)?.round() as u64),
+                    last_sent: millis_to_time(row.get(5)?),
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(stats)
+    }
+
+    fn prune(&mut self, retention: &Retention, now: DateTime<Utc>) -> Result<usize> {
+        let mut pruned = 0;
+        if let Some(cutoff) = retention.cutoff(now) {
+            pruned += self.connection.execute(
+                "DELETE FROM history WHERE sent_at < ?1",
+                params![cutoff.timestamp_millis()],
+            )?;
+        }
+        if let Some(max) = retention.max_entries {
+            pruned += self.connection.execute(
+                "DELETE FROM history WHERE id NOT IN
+                 (SELECT id FROM history ORDER BY id DESC LIMIT ?1)",
+                params![max as i64],
+            )?;
+        }
+        Ok(pruned)
+    }
+
+    fn record_revision(&mut self, revision: &Revision) -> Result<()> {
+        self.connection.execute(
+            "INSERT INTO revisions (saved_at, collection, request, content)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                revision.saved_at.timestamp_millis(),
+                revision.collection,
+                revision.request,
+                revision.content,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn revisions(&self, collection: &str, request: &str) -> Result<Vec<Revision>> {
+        let mut statement = self.connection.prepare_cached(
+            "SELECT saved_at, content FROM revisions WHERE collection = ?1 AND request = ?2
+             ORDER BY id DESC",
+        )?;
+        let revisions = statement
+            .query_map(params![collection, request], |row| {
+                Ok(Revision {
+                    saved_at: millis_to_time(row.get(0)?),
+                    collection: collection.to_string(),
+                    request: request.to_string(),
+                    content: row.get(1)?,
+                })
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        Ok(revisions)
+    }
+
+    fn record_run(&mut self, run: &RunRecord) -> Result<()> {
+        let transaction = self.connection.transaction()?;
+        transaction.execute(
+            "INSERT INTO runs (finished_at, title) VALUES (?1, ?2)",
+            params![run.finished_at.timestamp_millis(), run.title],
+        )?;
+        let run_id = transaction.last_insert_rowid();
+        for (position, step) in run.steps.iter().enumerate() {
+            let (status, error) = match &step.status {
+                Ok(status) => (Some(*status), None),
+                Err(error) => (None, Some(error.as_str())),
+            };
+            transaction.execute(
+                "INSERT INTO run_steps (run_id, position, name, method, url, status, error,
+                                        elapsed_us, tests, passed_tests)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    run_id,
+                    position as i64,
+                    step.name,
+                    step.method,
+                    step.url,
+                    status,
+                    error,
+                    step.elapsed.as_micros() as i64,
+                    step.tests as i64,
+                    step.passed_tests as i64,
+                ],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn runs(&self, title: &str, limit: usize) -> Result<Vec<RunRecord>> {
+        let mut statement = self.connection.prepare_cached(
+            "SELECT id, finished_at FROM runs WHERE title = ?1 ORDER BY id DESC LIMIT ?2",
+        )?;
+        let found: Vec<(i64, i64)> = statement
+            .query_map(params![title, limit as i64], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        let mut steps = self
+            .connection
+            .prepare_cached("SELECT * FROM run_steps WHERE run_id = ?1 ORDER BY position")?;
+        let mut runs = Vec::with_capacity(found.len());
+        for (id, finished_at) in found {
+            runs.push(RunRecord {
+                finished_at: millis_to_time(finished_at),
+                title: title.to_string(),
+                steps: steps
+                    .query_map(params![id], step_from_row)?
+                    .collect::<rusqlite::Result<_>>()?,
+            });
+        }
+        Ok(runs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::tests::{check_storage, entry};
+
+    #[test]
+    fn test_sqlite_storage() {
+        let mut storage =
+            SqliteStorage::with_connection(Connection::open_in_memory().unwrap()).unwrap();
+        check_storage(&mut storage);
+    }
+
+    #[test]
+    fn test_sqlite_storage_persists() {
+        let dir =
+            std::env::temp_dir().join(format!("restless-history-test-{}", std::process::id()));
+        let path = dir.join("history.db");
+        SqliteStorage::open(&path)
+            .unwrap()
+            .record(&entry(0, "GET", "http://api/users", 200))
+            .unwrap();
+        let storage = SqliteStorage::open(&path).unwrap();
+        assert_eq!(
+            storage.search("users", 10).unwrap(),
+            vec![entry(0, "GET", "http://api/users", 200)]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -6,25 +6,33 @@
 
 use anyhow::Result;
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
+    },
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io::{self, Stderr};
+use std::process::{Command, Stdio};
 
 use crate::error::RestlessError;
 
 /// Terminal manager that handles setup and cleanup
 pub struct TerminalManager {
     terminal: Terminal<CrosstermBackend<Stderr>>,
+    /// Whether the `@restless` tmux option was set and needs unsetting
+    tmux_status: bool,
 }
 
 impl TerminalManager {
     /// Creates a new terminal manager and initializes the terminal
     pub fn new() -> Result<Self, RestlessError> {
         let terminal = Self::setup_terminal()?;
-        Ok(Self { terminal })
+        Ok(Self {
+            terminal,
+            tmux_status: false,
+        })
     }
 
     /// Sets up the terminal for the application
@@ -35,8 +43,13 @@ impl TerminalManager {
 
         // Setup terminal backend
         let mut stderr = io::stderr();
-        execute!(stderr, EnterAlternateScreen, EnableMouseCapture)
-            .map_err(|e| RestlessError::terminal(format!("Failed to setup terminal: {}", e)))?;
+        execute!(
+            stderr,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableBracketedPaste
+        )
+        .map_err(|e| RestlessError::terminal(format!("Failed to setup terminal: {}", e)))?;
 
         // Create terminal instance
         let backend = CrosstermBackend::new(stderr);
@@ -57,6 +70,53 @@ impl TerminalManager {
         &self.terminal
     }
 
+    /// Sets the window title, and the `@restless` tmux pane option when
+    /// `tmux` is set and restless runs inside tmux
+    pub fn set_title(&mut self, title: &str, tmux: bool) -> Result<(), RestlessError> {
+        execute!(self.terminal.backend_mut(), SetTitle(title))
+            .map_err(|e| RestlessError::terminal(format!("Failed to set title: {}", e)))?;
+        if tmux && std::env::var_os("TMUX").is_some() {
+            self.tmux_status = true;
+            set_tmux_option(&["set-option", "-p", "@restless", title]);
+        }
+        Ok(())
+    }
+
+    /// Hands the terminal back to the shell for an external program, like
+    /// on exit; [`TerminalManager::resume`] takes it over again
+    pub fn suspend(&mut self) -> Result<(), RestlessError> {
+        disable_raw_mode()
+            .map_err(|e| RestlessError::terminal(format!("Failed to disable raw mode: {}", e)))?;
+        execute!(
+            self.terminal.backend_mut(),
+            DisableBracketedPaste,
+            DisableMouseCapture,
+            LeaveAlternateScreen
+        )
+        .map_err(|e| RestlessError::terminal(format!("Failed to suspend terminal: {}", e)))?;
+        self.terminal
+            .show_cursor()
+            .map_err(|e| RestlessError::terminal(format!("Failed to show cursor: {}", e)))
+    }
+
+    /// Sets the terminal up again after [`TerminalManager::suspend`]
+    pub fn resume(&mut self) -> Result<(), RestlessError> {
+        enable_raw_mode()
+            .map_err(|e| RestlessError::terminal(format!("Failed to enable raw mode: {}", e)))?;
+        execute!(
+            self.terminal.backend_mut(),
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableBracketedPaste
+        )
+        .map_err(|e| RestlessError::terminal(format!("Failed to resume terminal: {}", e)))?;
+        // The screen is redrawn from scratch, not diffed against what was
+        // there before the suspend
+        self.terminal
+            .clear()
+            .map_err(|e| RestlessError::terminal(format!("Failed to clear terminal: {}", e)))
+    }
+
     /// Validates that the terminal size is adequate for the application
     pub fn validate_size(&self) -> Result<(), RestlessError> {
         let size = self
@@ -98,10 +158,14 @@ impl TerminalManager {
         // Restore terminal
         execute!(
             self.terminal.backend_mut(),
+            DisableBracketedPaste,
             DisableMouseCapture,
             LeaveAlternateScreen
         )
         .map_err(|e| RestlessError::terminal(format!("Failed to cleanup terminal: {}", e)))?;
+        if std::mem::take(&mut self.tmux_status) {
+            set_tmux_option(&["set-option", "-pu", "@restless"]);
+        }
 
         // Show cursor
         self.terminal
@@ -121,6 +185,16 @@ impl Drop for TerminalManager {
     }
 }
 
+/// Runs a tmux command, ignoring failures: the status line is a nicety
+fn set_tmux_option(args: &[&str]) {
+    let _ = Command::new("tmux")
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
 /// Configuration for terminal setup
 #[derive(Debug, Clone)]
 #[cfg(test)]
@@ -302,7 +376,12 @@ pub mod utils {
     #[allow(dead_code)]
     pub fn emergency_cleanup() {
         let _ = disable_raw_mode();
-        let _ = execute!(io::stderr(), DisableMouseCapture, LeaveAlternateScreen);
+        let _ = execute!(
+            io::stderr(),
+            DisableBracketedPaste,
+            DisableMouseCapture,
+            LeaveAlternateScreen
+        );
     }
 }
 
@@ -0,0 +1,100 @@
+//! A local HTTP server for tests
+//!
+//! Every connection is read up to the end of its request, which is recorded
+//! for [`TestServer::request`], and then answered. [`TestServer::new`] gives
+//! canned responses in turn, one per connection; [`TestServer::with`] leaves
+//! the answer to the test, for responses that depend on the request or have
+//! to wait for something.
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+/// How long [`TestServer::request`] waits for a request to come in
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct TestServer {
+    pub port: u16,
+    requests: Receiver<String>,
+}
+
+impl TestServer {
+    /// Answers the first connections with `responses`, in order; later ones
+    /// are closed unanswered
+    pub fn new(responses: &[&[u8]]) -> TestServer {
+        let mut responses = responses
+            .iter()
+            .map(|response| response.to_vec())
+            .collect::<Vec<_>>()
+            .into_iter();
+        TestServer::with(move |_, stream| {
+            if let Some(response) = responses.next() {
+                let _ = stream.write_all(&response);
+            }
+        })
+    }
+
+    /// Answers every connection with `answer`, given the request as text
+    pub fn with<F>(mut answer: F) -> TestServer
+    where
+        F: FnMut(&str, &mut TcpStream) + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (received, requests) = mpsc::channel();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    return;
+                };
+                let request = read_request(&mut stream);
+                let _ = received.send(request.clone());
+                answer(&request, &mut stream);
+            }
+        });
+        TestServer { port, requests }
+    }
+
+    /// `http://127.0.0.1:{port}` followed by `path`
+    pub fn url(&self, path: &str) -> String {
+        format!("http://127.0.0.1:{}{}", self.port, path)
+    }
+
+    /// The next request received, as sent
+    pub fn request(&self) -> String {
+        self.requests
+            .recv_timeout(REQUEST_TIMEOUT)
+            .expect("no request reached the test server")
+    }
+}
+
+/// Reads the head and then the body, as long as `Content-Length` says or up
+/// to the last chunk
+fn read_request(stream: &mut TcpStream) -> String {
+    let mut seen = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let text = String::from_utf8_lossy(&seen);
+        if let Some(end) = text.find("\r\n\r\n") {
+            let head = text[..end].to_lowercase();
+            let length = head.lines().find_map(|line| {
+                line.strip_prefix("content-length:")
+                    .and_then(|value| value.trim().parse::<usize>().ok())
+            });
+            let complete = match length {
+                Some(length) => seen.len() >= end + 4 + length,
+                None if head.contains("transfer-encoding: chunked") => text.ends_with("0\r\n\r\n"),
+                None => true,
+            };
+            if complete {
+                break;
+            }
+        }
+        match stream.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => seen.extend_from_slice(&buf[..n]),
+        }
+    }
+    String::from_utf8_lossy(&seen).into_owned()
+}
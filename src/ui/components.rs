@@ -5,40 +5,70 @@
 //! makes the code more maintainable and testable.
 
 use ratatui::{
-    layout::{Alignment, Position, Rect},
+    layout::{Alignment, Constraint, Layout, Position, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{
-        Block, Borders, Clear, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation, Tabs,
+        Bar, BarChart, BarGroup, Block, Borders, Clear, List, ListItem, Paragraph, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Tabs,
     },
     Frame,
 };
 
 use super::{
-    create_block, create_response_layout, create_url_layout, create_values_layout, method_text,
-    truncate_text, TEXT_COLOR_HIGHLIGHT, TEXT_COLOR_MUTED,
-    layouts::create_method_dropdown_layout,
+    create_block, create_compare_layout, create_response_layout, create_url_layout,
+    create_values_layout, layouts::create_method_dropdown_layout, method_text, truncate_text,
+    Theme,
 };
+use crate::app::input::Cursor;
+use crate::app::tab::{InFlight, Tab};
 use crate::app::{App, CurrentScreen, ValuesScreen};
+use crate::logic::assertions::{self, TestResult};
+use crate::logic::auth::{Auth, AuthKind};
+use crate::logic::form;
+use crate::logic::hexdump;
+use crate::logic::highlight::{self, Token};
+use crate::logic::metrics::{format_duration, LatencyHistogram};
+use crate::logic::options::RequestOptions;
+use crate::logic::panel;
+use crate::logic::query;
+use crate::logic::ratelimit::format_wait;
+use crate::logic::request::SentRequest;
+use crate::logic::search::Search;
+use crate::logic::table::Table;
+use crate::logic::tls::TlsDetails;
+use crate::logic::tree::Tree;
+use std::cell::Cell;
 
 /// Renders the tab bar at the top of the application
 pub fn render_tabs(f: &mut Frame, app: &App, area: Rect) {
     let block = Block::default().borders(Borders::ALL);
 
-    let tab_titles: Vec<Line> = app
-        .tabs
-        .iter()
-        .map(|tab| Line::from(tab.name.clone()))
-        .collect();
-
-    let tabs_widget = Tabs::new(tab_titles)
+    let tabs_widget = Tabs::new(tab_titles(app))
         .block(block)
         .select(app.selected_tab)
-        .highlight_style(Style::default().fg(TEXT_COLOR_HIGHLIGHT));
+        .highlight_style(Style::default().fg(app.theme.highlight));
 
     f.render_widget(tabs_widget, area);
 }
 
+/// The titles of the tab bar; tabs waiting for a response show for how long
+pub fn tab_titles(app: &App) -> Vec<Line<'_>> {
+    app.tabs
+        .iter()
+        .map(|tab| match &tab.in_flight {
+            Some(in_flight) => Line::from(vec![
+                Span::raw(format!("{} ", tab.name)),
+                Span::styled(
+                    format!("[{}]", in_flight.label()),
+                    Style::default().fg(app.theme.highlight),
+                ),
+            ]),
+            None => Line::from(tab.name.clone()),
+        })
+        .collect()
+}
+
 /// Renders the URL input section with method selector
 pub fn render_url_input(f: &mut Frame, app: &App, area: Rect) {
     let (method_area, url_area) = create_url_layout(area);
@@ -56,11 +86,12 @@ pub fn render_url_input(f: &mut Frame, app: &App, area: Rect) {
 
     // Set cursor position when editing URL
     if let CurrentScreen::EditingUrl = app.current_screen {
-        let cursor_x = url_area.x + 6 + app.url_input.len() as u16; // "URL: " = 5 chars + space
-        let cursor_y = url_area.y + 1;
+        // Past the border and "URL: "
+        let (_, column) = app.url_cursor.position(&app.url_input);
+        let right = url_area.x + url_area.width.saturating_sub(2);
         f.set_cursor_position(Position {
-            x: cursor_x,
-            y: cursor_y,
+            x: (url_area.x + 6 + column as u16).min(right),
+            y: url_area.y + 1,
         });
     }
 }
@@ -68,9 +99,9 @@ pub fn render_url_input(f: &mut Frame, app: &App, area: Rect) {
 /// Renders the HTTP method selector
 fn render_method_selector(f: &mut Frame, app: &App, area: Rect) {
     let is_active = matches!(app.current_screen, CurrentScreen::Url);
-    let block = create_block("Method", is_active, false);
+    let block = create_block(&app.theme, app.tr("Method"), is_active, false);
 
-    let method_paragraph = Paragraph::new(method_text(&app.selected_method))
+    let method_paragraph = Paragraph::new(method_text(&app.theme, &app.selected_method))
         .block(block)
         .alignment(Alignment::Center);
 
@@ -81,22 +112,139 @@ fn render_method_selector(f: &mut Frame, app: &App, area: Rect) {
 fn render_url_field(f: &mut Frame, app: &App, area: Rect) {
     let is_active = matches!(app.current_screen, CurrentScreen::Url);
     let is_editing = matches!(app.current_screen, CurrentScreen::EditingUrl);
-    let block = create_block("URL", is_active, is_editing);
+    let mut block = create_block(&app.theme, app.tr("URL"), is_active, is_editing);
+    if app.effective_options(&app.options_input).is_insecure() {
+        block = block
+            .border_style(Style::default().fg(app.theme.error))
+            .title(
+                Line::from(Span::styled(
+                    format!(" {} ", app.tr("INSECURE TLS: certificates not verified")),
+                    Style::default()
+                        .fg(app.theme.text)
+                        .bg(app.theme.error)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .right_aligned(),
+            );
+    }
 
     let url_text = if app.url_input.is_empty() && !is_editing {
-        "Enter URL (press 'u' to edit)".to_string()
+        Line::from(app.tr("Enter URL (press 'u' to edit)"))
+    } else if is_editing {
+        Line::from(format!("URL: {}", app.url_input))
     } else {
-        format!("URL: {}", app.url_input)
+        // The query the params make up, as the URL will be sent
+        let full = query::join(&app.url_input, &app.params_input);
+        let (url, query) = match full.strip_prefix(app.url_input.as_str()) {
+            Some(query) => (app.url_input.as_str(), query),
+            // Before a #fragment
+            None => (full.as_str(), ""),
+        };
+        Line::from(vec![
+            Span::raw(format!("URL: {}", url)),
+            Span::styled(query.to_string(), Style::default().fg(app.theme.muted)),
+        ])
     };
 
     let url_paragraph = Paragraph::new(url_text).block(block);
     f.render_widget(url_paragraph, area);
 }
 
+/// Renders the sent URLs matching the URL being typed in a dropdown below
+/// the URL field
+pub fn render_url_suggestions(f: &mut Frame, app: &App, area: Rect) {
+    if app.current_screen != CurrentScreen::EditingUrl || app.url_suggestions.is_empty() {
+        return;
+    }
+    let (_, url_area) = create_url_layout(area);
+    render_suggestions(
+        f,
+        app,
+        Rect {
+            y: url_area.y + url_area.height,
+            ..url_area
+        },
+        app.tr("Sent before (↑/↓, Enter)"),
+        &app.url_suggestions,
+        app.url_suggestion_selected,
+    );
+}
+
+/// Renders the header names (or values) completing the one being typed below
+/// its line
+pub fn render_header_suggestions(f: &mut Frame, app: &App, area: Rect) {
+    if app.current_screen != CurrentScreen::EditingHeaders || app.header_suggestions.is_empty() {
+        return;
+    }
+    let (_, content_area) = create_values_layout(area);
+    // Below the border and the line being typed, which is the header edited
+    // or the one after the others
+    let line = app.editing_header_index.unwrap_or(app.header_count());
+    let below = content_area.y + 2 + line as u16;
+    render_suggestions(
+        f,
+        app,
+        Rect {
+            x: content_area.x + 1,
+            y: below,
+            width: 40.min(content_area.width.saturating_sub(2)),
+            height: 0,
+        },
+        app.tr("Tab completes"),
+        &app.header_suggestions,
+        app.header_suggestion_selected,
+    );
+}
+
+/// A dropdown of `items` with its top left corner and width from `at`
+fn render_suggestions(
+    f: &mut Frame,
+    app: &App,
+    at: Rect,
+    title: &str,
+    items: &[String],
+    selected: Option<usize>,
+) {
+    let dropdown_area = Rect {
+        height: items.len() as u16 + 2,
+        ..at
+    }
+    .intersection(f.area());
+
+    let lines: Vec<Line> = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let style = if selected == Some(index) {
+                Style::default()
+                    .fg(app.theme.highlight)
+                    .bg(app.theme.selection)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.theme.text)
+            };
+            Line::from(Span::styled(item.clone(), style))
+        })
+        .collect();
+    let block = Block::default()
+        .title(title.to_string())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.text))
+        .style(Style::default().bg(app.theme.surface));
+
+    f.render_widget(Clear, dropdown_area);
+    f.render_widget(Paragraph::new(lines).block(block), dropdown_area);
+}
+
 /// Renders the method dropdown menu
 fn render_method_dropdown(f: &mut Frame, app: &App, method_area: Rect) {
     let methods = ["GET", "POST", "PUT", "DELETE"];
-    let method_colors = [Color::Green, Color::Blue, Color::Yellow, Color::Red];
+    let method_colors = [
+        app.theme.success,
+        app.theme.info,
+        app.theme.highlight,
+        app.theme.error,
+    ];
 
     let dropdown_area = create_method_dropdown_layout(method_area);
 
@@ -106,17 +254,17 @@ fn render_method_dropdown(f: &mut Frame, app: &App, method_area: Rect) {
     // Render dropdown container
     let dropdown_block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::White))
-        .style(Style::default().bg(Color::Rgb(30, 30, 30)));
+        .border_style(Style::default().fg(app.theme.text))
+        .style(Style::default().bg(app.theme.surface));
     f.render_widget(dropdown_block, dropdown_area);
 
     // Render method options
     for (i, method) in methods.iter().enumerate() {
         let is_selected = i == app.method_dropdown_selected;
         let bg_color = if is_selected {
-            Color::Rgb(60, 60, 60)
+            app.theme.selection
         } else {
-            Color::Rgb(30, 30, 30)
+            app.theme.surface
         };
 
         let item_area = Rect {
@@ -155,89 +303,221 @@ pub fn render_values_section(f: &mut Frame, app: &App, area: Rect) {
         ValuesScreen::Body => render_body_content(f, app, content_area),
         ValuesScreen::Headers => render_headers_content(f, app, content_area),
         ValuesScreen::Params => render_params_content(f, app, content_area),
+        ValuesScreen::Auth => render_auth_content(f, app, content_area),
+        ValuesScreen::Options => render_options_content(f, app, content_area),
+        ValuesScreen::Tests => render_tests_content(f, app, content_area),
     }
 }
 
 /// Renders the tabs for the values section
 fn render_values_tabs(f: &mut Frame, app: &App, area: Rect) {
-    let tab_titles = vec![
-        Line::from("Body"),
-        Line::from("Headers"),
-        Line::from("Params"),
-    ];
-
-    let selected_tab = match app.values_screen {
-        ValuesScreen::Body => 0,
-        ValuesScreen::Headers => 1,
-        ValuesScreen::Params => 2,
-    };
+    let selected_tab = ValuesScreen::ALL
+        .iter()
+        .position(|screen| *screen == app.values_screen);
 
-    let tabs = Tabs::new(tab_titles)
+    let tabs = Tabs::new(values_tab_titles(app))
         .select(selected_tab)
-        .highlight_style(Style::default().fg(TEXT_COLOR_HIGHLIGHT))
+        .highlight_style(Style::default().fg(app.theme.highlight))
         .divider(" ")
         .padding("", "");
 
     f.render_widget(tabs, area);
 }
 
+/// The titles of the values tabs, in the order of [`ValuesScreen::ALL`]
+pub fn values_tab_titles(app: &App) -> Vec<Line<'_>> {
+    ["Body", "Headers", "Params", "Auth", "Options", "Tests"]
+        .into_iter()
+        .map(|title| Line::from(app.tr(title)))
+        .collect()
+}
+
 /// Renders the body content area
 fn render_body_content(f: &mut Frame, app: &App, area: Rect) {
     let is_active = matches!(app.current_screen, CurrentScreen::Values)
         && matches!(app.values_screen, ValuesScreen::Body);
+    if let Some(fields) = &app.form_input {
+        render_form_content(f, app, fields, is_active, area);
+        return;
+    }
     let is_editing = matches!(app.current_screen, CurrentScreen::EditingBody);
-    let block = create_block("Request Body", is_active, is_editing);
+    let block = create_block(&app.theme, app.tr("Request Body"), is_active, is_editing);
+
+    if is_editing {
+        render_text_editor(
+            f,
+            area,
+            block,
+            &app.body_input,
+            &app.body_cursor,
+            &app.body_scroll,
+        );
+        return;
+    }
 
-    let content = if app.body_input.is_empty() {
-        if is_active && !is_editing {
-            "Press 'i' to edit body...\n\nTip: Use JSON, XML, or plain text\nNavigation: Ctrl+j/k between sections, h/l for tabs".to_string()
+    if app.body_input.is_empty() {
+        let placeholder = app.tr(if is_active {
+            "Press 'i' to edit body, Ctrl+E to open it in $EDITOR...\n\nTip: Use JSON, XML, or plain text; Space switches to multipart form data\nNavigation: Ctrl+j/k between sections, h/l for tabs"
         } else {
-            "Body (empty)".to_string()
-        }
-    } else {
-        app.body_input.clone()
-    };
+            "Body (empty)"
+        });
+        f.render_widget(Paragraph::new(placeholder).block(block), area);
+        return;
+    }
+
+    // Scrolled with j/k, no further than the last page
+    let lines = app.body_input.lines().count();
+    let height = area.height.saturating_sub(2) as usize;
+    let top = app.body_scroll.get().min(lines.saturating_sub(height));
+    app.body_scroll.set(top);
+    let paragraph = Paragraph::new(app.body_input.clone())
+        .block(block)
+        .scroll((top as u16, 0));
+    f.render_widget(paragraph, area);
+
+    if lines > height {
+        let mut scroll_state = ScrollbarState::new(lines.saturating_sub(height)).position(top);
+        f.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(Some("↑"))
+                .end_symbol(Some("↓")),
+            area,
+            &mut scroll_state,
+        );
+    }
+}
+
+/// Renders `text` with the cursor in it, scrolled just enough to keep the
+/// cursor line in view
+fn render_text_editor(
+    f: &mut Frame,
+    area: Rect,
+    block: Block,
+    text: &str,
+    cursor: &Cursor,
+    scroll: &Cell<usize>,
+) {
+    let (row, column) = cursor.position(text);
+    let height = area.height.saturating_sub(2).max(1) as usize;
+    let top = scroll.get().clamp((row + 1).saturating_sub(height), row);
+    scroll.set(top);
 
-    let paragraph = Paragraph::new(content).block(block);
+    let paragraph = Paragraph::new(text.to_string())
+        .block(block)
+        .scroll((top as u16, 0));
     f.render_widget(paragraph, area);
 
-    // Set cursor position when editing
+    let right = area.x + area.width.saturating_sub(2);
+    f.set_cursor_position(Position {
+        x: (area.x + 1 + column as u16).min(right),
+        y: area.y + 1 + (row - top) as u16,
+    });
+}
+
+/// Renders the headers or params being edited as text
+fn render_bulk_content(f: &mut Frame, app: &App, area: Rect, title: &str) {
+    let block = create_block(&app.theme, title, false, true);
+    render_text_editor(
+        f,
+        area,
+        block,
+        &app.bulk_input,
+        &app.bulk_cursor,
+        &app.bulk_scroll,
+    );
+}
+
+/// Renders the fields of a multipart body
+fn render_form_content(
+    f: &mut Frame,
+    app: &App,
+    fields: &[(String, String)],
+    is_active: bool,
+    area: Rect,
+) {
+    let is_editing = matches!(app.current_screen, CurrentScreen::EditingForm);
+    let block = create_block(
+        &app.theme,
+        app.tr("Request Body (multipart)"),
+        is_active,
+        is_editing,
+    );
+
+    let mut items: Vec<ListItem> = fields
+        .iter()
+        .map(|(name, value)| {
+            let line = match form::file_path(value) {
+                Some(path) => Line::from(vec![
+                    Span::raw(format!("{} = ", name)),
+                    Span::styled("file ", Style::default().fg(app.theme.muted)),
+                    Span::raw(path.to_string()),
+                ]),
+                None => Line::from(format!("{} = {}", name, value)),
+            };
+            ListItem::new(line)
+        })
+        .collect();
+
     if is_editing {
-        let lines: Vec<&str> = app.body_input.lines().collect();
-        let last_line = lines.last().unwrap_or(&"");
-        let cursor_y = area.y + 1 + lines.len().saturating_sub(1) as u16;
-        let cursor_x = area.x + 1 + last_line.len() as u16;
-        f.set_cursor_position(Position {
-            x: cursor_x,
-            y: cursor_y,
-        });
+        items.push(ListItem::new(Line::from(Span::styled(
+            app.current_form_input.clone(),
+            Style::default().fg(app.theme.highlight),
+        ))));
+    } else if is_active {
+        let hints = [
+            "Press 'i' to add name=value or name=@path (Tab completes the path)",
+            "An empty value removes the field; Space switches back to a raw body",
+        ];
+        items.extend(hints.iter().map(|hint| {
+            ListItem::new(Line::from(Span::styled(
+                *hint,
+                Style::default().fg(app.theme.muted),
+            )))
+        }));
+    } else if items.is_empty() {
+        items.push(ListItem::new(Line::from("No form fields")));
     }
+
+    let list = List::new(items).block(block);
+    f.render_widget(list, area);
 }
 
 /// Renders the headers content area
 fn render_headers_content(f: &mut Frame, app: &App, area: Rect) {
+    if app.current_screen == CurrentScreen::EditingBulk {
+        let title = app.tr("Headers: Name: value per line, // switches off, Esc applies");
+        render_bulk_content(f, app, area, title);
+        return;
+    }
     let is_active = matches!(app.current_screen, CurrentScreen::Values)
         && matches!(app.values_screen, ValuesScreen::Headers);
     let is_editing = matches!(app.current_screen, CurrentScreen::EditingHeaders);
-    let block = create_block("Headers", is_active, is_editing);
-
-    let mut items: Vec<ListItem> = app
-        .headers_input
-        .iter()
-        .map(|(key, value)| ListItem::new(Line::from(format!("{}: {}", key, value))))
-        .collect();
+    let block = create_block(&app.theme, app.tr("Headers"), is_active, is_editing);
+
+    let mut items = entry_items(
+        &app.theme,
+        &app.headers_input,
+        &app.disabled_headers,
+        |(key, value)| format!("{}: {}", key, value),
+    );
+    if is_active {
+        highlight_entry(&app.theme, &mut items, app.selected_header);
+    }
 
-    // Add current input line if editing
+    // Add current input line if editing, in place of the header edited
     if is_editing {
-        let current_input = if app.current_header_value.is_empty() {
+        let current_input = if app.current_header_key.contains(':') {
+            format!("{} {}", app.current_header_key, app.current_header_value)
+        } else if app.current_header_value.is_empty() {
             format!("{}:", app.current_header_key)
         } else {
             format!("{}: {}", app.current_header_key, app.current_header_value)
         };
-        items.push(ListItem::new(Line::from(Span::styled(
+        let current = ListItem::new(Line::from(Span::styled(
             current_input,
-            Style::default().fg(TEXT_COLOR_HIGHLIGHT),
-        ))));
+            Style::default().fg(app.theme.highlight),
+        )));
+        put_item(&mut items, app.editing_header_index, current);
     } else if items.is_empty() {
         if is_active {
             items.push(ListItem::new(Line::from("Press 'i' to add headers...")));
@@ -257,28 +537,45 @@ fn render_headers_content(f: &mut Frame, app: &App, area: Rect) {
 
 /// Renders the parameters content area
 fn render_params_content(f: &mut Frame, app: &App, area: Rect) {
+    if app.current_screen == CurrentScreen::EditingBulk {
+        let title = app.tr("Params: name=value per line, // switches off, Esc applies");
+        render_bulk_content(f, app, area, title);
+        return;
+    }
     let is_active = matches!(app.current_screen, CurrentScreen::Values)
         && matches!(app.values_screen, ValuesScreen::Params);
     let is_editing = matches!(app.current_screen, CurrentScreen::EditingParams);
-    let block = create_block("Query Parameters", is_active, is_editing);
-
-    let mut items: Vec<ListItem> = app
-        .params_input
-        .iter()
-        .map(|(key, value)| ListItem::new(Line::from(format!("{}={}", key, value))))
-        .collect();
+    let block = create_block(
+        &app.theme,
+        app.tr("Query Parameters"),
+        is_active,
+        is_editing,
+    );
+
+    let mut items = entry_items(
+        &app.theme,
+        &app.params_input,
+        &app.disabled_params,
+        |(key, value)| format!("{}={}", key, value),
+    );
+    if is_active {
+        highlight_entry(&app.theme, &mut items, app.selected_param);
+    }
 
-    // Add current input line if editing
+    // Add current input line if editing, in place of the param edited
     if is_editing {
-        let current_input = if app.current_param_value.is_empty() {
+        let current_input = if app.current_param_key.contains('=') {
+            format!("{}{}", app.current_param_key, app.current_param_value)
+        } else if app.current_param_value.is_empty() {
             format!("{}=", app.current_param_key)
         } else {
             format!("{}={}", app.current_param_key, app.current_param_value)
         };
-        items.push(ListItem::new(Line::from(Span::styled(
+        let current = ListItem::new(Line::from(Span::styled(
             current_input,
-            Style::default().fg(TEXT_COLOR_HIGHLIGHT),
-        ))));
+            Style::default().fg(app.theme.highlight),
+        )));
+        put_item(&mut items, app.editing_param_index, current);
     } else if items.is_empty() {
         if is_active {
             items.push(ListItem::new(Line::from("Press 'i' to add parameters...")));
@@ -294,34 +591,257 @@ fn render_params_content(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(list, area);
 }
 
+/// Headers or params with a checkbox, the disabled ones dimmed after the
+/// others; without disabled ones the list looks as it always did
+fn entry_items<'a>(
+    theme: &Theme,
+    enabled: &[(String, String)],
+    disabled: &[(String, String)],
+    format: impl Fn(&(String, String)) -> String,
+) -> Vec<ListItem<'a>> {
+    if disabled.is_empty() {
+        return enabled
+            .iter()
+            .map(|entry| ListItem::new(Line::from(format(entry))))
+            .collect();
+    }
+    let on = enabled
+        .iter()
+        .map(|entry| ListItem::new(Line::from(format!("[x] {}", format(entry)))));
+    let off = disabled.iter().map(|entry| {
+        ListItem::new(Line::from(Span::styled(
+            format!("[ ] {}", format(entry)),
+            Style::default().fg(theme.muted),
+        )))
+    });
+    on.chain(off).collect()
+}
+
+/// Marks the entry picked with j/k
+fn highlight_entry(theme: &Theme, items: &mut [ListItem], selected: Option<usize>) {
+    if let Some(item) = selected.and_then(|index| items.get_mut(index)) {
+        *item = item.clone().style(Style::default().bg(theme.selection));
+    }
+}
+
+/// Replaces the item at `index` or, without one, appends it
+fn put_item<'a>(items: &mut Vec<ListItem<'a>>, index: Option<usize>, item: ListItem<'a>) {
+    match index.and_then(|index| items.get_mut(index)) {
+        Some(slot) => *slot = item,
+        None => items.push(item),
+    }
+}
+
+/// Renders the authentication configuration area
+fn render_auth_content(f: &mut Frame, app: &App, area: Rect) {
+    let is_active = matches!(app.current_screen, CurrentScreen::Values)
+        && matches!(app.values_screen, ValuesScreen::Auth);
+    let is_editing = matches!(app.current_screen, CurrentScreen::EditingAuth);
+    let block = create_block(&app.theme, app.tr("Authentication"), is_active, is_editing);
+
+    let auth = &app.auth_input;
+    let mut items: Vec<ListItem> = vec![ListItem::new(Line::from(vec![
+        Span::raw("Type: "),
+        Span::styled(auth.kind.label(), Style::default().fg(app.theme.highlight)),
+    ]))];
+
+    for (key, value) in &auth.fields {
+        let shown = if Auth::is_secret_field(key) {
+            "*".repeat(value.chars().count().min(12))
+        } else {
+            value.clone()
+        };
+        items.push(ListItem::new(Line::from(format!("{}={}", key, shown))));
+    }
+
+    if is_editing {
+        items.push(ListItem::new(Line::from(Span::styled(
+            app.current_auth_input.clone(),
+            Style::default().fg(app.theme.highlight),
+        ))));
+    } else if auth.kind == AuthKind::None {
+        if is_active {
+            items.push(ListItem::new(Line::from(
+                "Press Space to choose an auth type",
+            )));
+        }
+    } else {
+        let missing = auth.missing_fields();
+        if !missing.is_empty() {
+            items.push(ListItem::new(Line::from(Span::styled(
+                format!("Missing: {}", missing.join(", ")),
+                Style::default().fg(app.theme.muted),
+            ))));
+        }
+        if is_active {
+            items.push(ListItem::new(Line::from(Span::styled(
+                format!(
+                    "Press 'i' to set key=value (optional: {})",
+                    auth.kind.optional_fields().join(", ")
+                ),
+                Style::default().fg(app.theme.muted),
+            ))));
+            if matches!(auth.kind, AuthKind::HmacSignature | AuthKind::AwsSigV4) {
+                items.push(ListItem::new(Line::from(Span::styled(
+                    "Press 'p' to preview the signature",
+                    Style::default().fg(app.theme.muted),
+                ))));
+            }
+        }
+    }
+
+    let list = List::new(items).block(block);
+    f.render_widget(list, area);
+}
+
+/// Renders the per-request connection options
+fn render_options_content(f: &mut Frame, app: &App, area: Rect) {
+    let is_active = matches!(app.current_screen, CurrentScreen::Values)
+        && matches!(app.values_screen, ValuesScreen::Options);
+    let is_editing = matches!(app.current_screen, CurrentScreen::EditingOptions);
+    let block = create_block(&app.theme, app.tr("Options"), is_active, is_editing);
+
+    let own = app.options_input.entries();
+    let from_env = app
+        .active_environment()
+        .map(|env| env.options.entries())
+        .unwrap_or_default();
+    let mut items: Vec<ListItem> = Vec::new();
+    for (key, value) in app.effective_options(&app.options_input).entries() {
+        let shown = if RequestOptions::is_secret(key) {
+            "*".repeat(value.chars().count().min(12))
+        } else {
+            value
+        };
+        let mut spans = vec![Span::raw(format!("{}={}", key, shown))];
+        let is_set = |entries: &[(&str, String)]| entries.iter().any(|(k, _)| *k == key);
+        let source = if is_set(&own) {
+            None
+        } else if is_set(&from_env) {
+            Some(" (environment)")
+        } else {
+            Some(" (config)")
+        };
+        if let Some(source) = source {
+            spans.push(Span::styled(source, Style::default().fg(app.theme.muted)));
+        }
+        items.push(ListItem::new(Line::from(spans)));
+    }
+
+    if is_editing {
+        items.push(ListItem::new(Line::from(Span::styled(
+            app.current_options_input.clone(),
+            Style::default().fg(app.theme.highlight),
+        ))));
+    } else if is_active {
+        items.push(ListItem::new(Line::from(Span::styled(
+            format!(
+                "Press 'i' to set key=value ({})",
+                RequestOptions::KEYS.join(", ")
+            ),
+            Style::default().fg(app.theme.muted),
+        ))));
+    }
+
+    let list = List::new(items).block(block);
+    f.render_widget(list, area);
+}
+
+/// Renders the assertions checked after each send
+fn render_tests_content(f: &mut Frame, app: &App, area: Rect) {
+    let is_active = matches!(app.current_screen, CurrentScreen::Values)
+        && matches!(app.values_screen, ValuesScreen::Tests);
+    let is_editing = matches!(app.current_screen, CurrentScreen::EditingTests);
+    let block = create_block(&app.theme, app.tr("Tests"), is_active, is_editing);
+
+    if is_editing {
+        render_text_editor(
+            f,
+            area,
+            block,
+            &app.tests_input,
+            &app.tests_cursor,
+            &app.tests_scroll,
+        );
+        return;
+    }
+
+    let content = if !app.tests_input.is_empty() {
+        app.tests_input.clone()
+    } else if is_active {
+        "Press 'i' to write assertions, one per line, checked after each send:\n\n\
+         status == 200\nheader Content-Type contains json\njson.body.id exists\ntime < 500"
+            .to_string()
+    } else {
+        "No tests".to_string()
+    };
+    f.render_widget(Paragraph::new(content).block(block), area);
+}
+
 /// Renders the response section
 pub fn render_response_section(f: &mut Frame, app: &App, area: Rect) {
     let tab = &app.tabs[app.selected_tab];
 
-    if let Some(response) = &tab.response {
+    if let Some(in_flight) = &tab.in_flight {
+        render_sending(f, app, in_flight, area);
+    } else if let Some(response) = &tab.response {
         let (tabs_area, content_area) = create_response_layout(area);
 
         // Render response tabs
         render_response_tabs(f, app, tabs_area);
 
-        // Render response content
-        render_response_content(f, app, response, content_area);
+        // Render response content, beside the compared tab's if any
+        match app.compared_tab() {
+            Some(compared) => {
+                let (left, right) = create_compare_layout(content_area);
+                render_response_content(f, app, response, left);
+                render_compare_content(f, app, compared, right);
+            }
+            None => render_response_content(f, app, response, content_area),
+        }
     } else {
         render_empty_response(f, app, area);
     }
 }
 
-/// Renders the response tabs (Headers/Body)
+/// Renders the response tabs (Headers/Body), with the panel, test results
+/// and sent request when there are any
 fn render_response_tabs(f: &mut Frame, app: &App, area: Rect) {
-    let titles = [Line::from("Headers"), Line::from("Body")];
-    let tabs = Tabs::new(titles)
-        .select(app.response_tab_selected)
-        .highlight_style(Style::default().fg(TEXT_COLOR_HIGHLIGHT))
+    let titles = response_tab_titles(app);
+    let selected = titles
+        .iter()
+        .position(|&(index, _)| index == app.response_tab_selected);
+    let tabs = Tabs::new(titles.into_iter().map(|(_, title)| title))
+        .select(selected)
+        .highlight_style(Style::default().fg(app.theme.highlight))
         .divider(" ")
         .padding("", "");
     f.render_widget(tabs, area);
 }
 
+/// The response tabs shown, by index with their titles
+pub fn response_tab_titles(app: &App) -> Vec<(usize, Line<'_>)> {
+    let tab = &app.tabs[app.selected_tab];
+    let shown = [
+        true,
+        true,
+        true,
+        tab.panel.is_some(),
+        tab.response
+            .as_ref()
+            .is_some_and(|response| !response.tests.is_empty()),
+        tab.response
+            .as_ref()
+            .is_some_and(|response| response.sent.is_some()),
+    ];
+    ["Headers", "Body", "TLS", "Panel", "Tests", "Request"]
+        .into_iter()
+        .enumerate()
+        .filter(|&(index, _)| shown[index])
+        .map(|(index, title)| (index, Line::from(app.tr(title))))
+        .collect()
+}
+
 /// Renders the response content with scrolling
 fn render_response_content(
     f: &mut Frame,
@@ -330,10 +850,166 @@ fn render_response_content(
     area: Rect,
 ) {
     let is_active = matches!(app.current_screen, CurrentScreen::Response);
+    let is_editing_note = matches!(
+        app.current_screen,
+        CurrentScreen::EditingNote | CurrentScreen::Searching | CurrentScreen::EditingFilter
+    );
+    let filtered = app.filtered_body();
+    let body = app.body_text();
+
+    // Status code and metrics in title, and which kept response this is
+    let name = match app.tabs[app.selected_tab].response_position() {
+        (shown, kept) if kept > 1 => format!("Response {}/{}", shown, kept),
+        _ => "Response".to_string(),
+    };
+    let prefix = match &response.version {
+        Some(version) => format!("{} - {} Status: ", name, version),
+        None => format!("{} - Status: ", name),
+    };
+    let mut title = String::new();
+    if let Some(transfer) = &response.transfer {
+        title.push_str(&format!(" | {}", transfer.summary()));
+    }
+    if let Some(charset) = &response.charset {
+        title.push_str(&format!(" | {}", charset.summary()));
+    }
+    if let Some(wire) = response.wire_summary() {
+        title.push_str(&format!(" | {}", wire));
+    } else if let Some(bytes) = &response.bytes {
+        title.push_str(&format!(" | binary: {} bytes", bytes.len()));
+    }
+    if let Some(stream) = response.stream_summary() {
+        title.push_str(&format!(" | {}", stream));
+    }
+    if let Some(metrics) = &response.metrics {
+        title.push_str(&format!(" | {}", metrics.summary()));
+    }
+    if let Some(latency) = app.tabs[app.selected_tab].latency.summary() {
+        title.push_str(&format!(" | {}", latency));
+    }
+    if !response.tests.is_empty() {
+        title.push_str(&format!(" | {}", assertions::summary(&response.tests)));
+    }
+    let changed = match response.changed {
+        Some(true) => Span::styled(
+            format!(" | {}", app.tr("body changed")),
+            Style::default()
+                .fg(app.theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Some(false) => Span::styled(
+            format!(" | {}", app.tr("body unchanged")),
+            Style::default().fg(app.theme.muted),
+        ),
+        None => Span::raw(""),
+    };
+    let title = Line::from(vec![
+        Span::raw(prefix),
+        status_span(&app.theme, response),
+        changed,
+        Span::raw(title),
+    ]);
+    let mut block = create_block(
+        &app.theme,
+        title,
+        is_active || is_editing_note,
+        is_editing_note,
+    );
+    if response.tls.as_ref().is_some_and(|tls| tls.pin_mismatch()) {
+        block = block
+            .border_style(Style::default().fg(app.theme.error))
+            .title(
+                Line::from(Span::styled(
+                    format!(" {} ", app.tr("CERTIFICATE PIN MISMATCH")),
+                    Style::default()
+                        .fg(app.theme.text)
+                        .bg(app.theme.error)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .centered(),
+            );
+    }
+    if let Some(cached) = &response.cached {
+        let stored = cached
+            .stored
+            .with_timezone(&chrono::Local)
+            .format("%H:%M:%S");
+        let note = if response.cached_shown {
+            "NOT MODIFIED - showing the cached copy from {time}"
+        } else {
+            "NOT MODIFIED - the cached copy from {time} is current (K shows it)"
+        };
+        let note = app.i18n.format(note, &[("time", &stored)]);
+        block = block.title(
+            Line::from(Span::styled(
+                format!(" {} ", note),
+                Style::default()
+                    .fg(app.theme.success)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .centered(),
+        );
+    }
+    if let Some(rate_limit) = &response.rate_limit {
+        let color = if rate_limit.is_low() {
+            app.theme.error
+        } else {
+            app.theme.success
+        };
+        block = block.title(
+            Line::from(Span::styled(
+                format!(" {} ", rate_limit.summary()),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ))
+            .right_aligned(),
+        );
+    }
+    if app.current_screen == CurrentScreen::Searching {
+        block = block.title_bottom(Line::from(Span::styled(
+            format!(" /{}_ ", app.search_input),
+            Style::default().fg(app.theme.highlight),
+        )));
+    } else if let Some(search) = app
+        .search
+        .as_ref()
+        .filter(|_| app.response_tab_selected == 1)
+    {
+        block = block.title_bottom(
+            Line::from(Span::styled(
+                format!(" {} ", search.summary(&body)),
+                Style::default().fg(app.theme.highlight),
+            ))
+            .right_aligned(),
+        );
+    }
+    if app.current_screen == CurrentScreen::EditingFilter {
+        block = block.title_bottom(Line::from(Span::styled(
+            format!(" Filter: {}_ ", app.filter_input),
+            Style::default().fg(app.theme.highlight),
+        )));
+    } else if let Some(filter) = &app.tabs[app.selected_tab].filter {
+        block = block.title_bottom(Line::from(Span::styled(
+            format!(" Filter: {} ", filter),
+            Style::default().fg(app.theme.highlight),
+        )));
+    }
+    if app.current_screen == CurrentScreen::EditingNote {
+        block = block.title_bottom(Line::from(Span::styled(
+            format!(" Note: {}_ ", app.note_input),
+            Style::default().fg(app.theme.highlight),
+        )));
+    } else if let Some(note) = &response.note {
+        block = block.title_bottom(Line::from(Span::styled(
+            format!(" Note: {} ", note),
+            Style::default().fg(app.theme.highlight),
+        )));
+    }
 
-    // Status code in title
-    let title = format!("Response - Status: {}", response.status_code);
-    let block = create_block(&title, is_active, false);
+    // Binary bodies are dumped a screenful at a time, however long they are
+    let hex_bytes = response
+        .hex_bytes()
+        .filter(|_| app.response_tab_selected == 1);
+    let visible = area.height.saturating_sub(2) as usize;
 
     // Select content based on active tab
     let content: Vec<Line> = if app.response_tab_selected == 0 {
@@ -347,28 +1023,86 @@ fn render_response_content(
                 .map(|(k, v)| Line::from(format!("{}: {}", k, v)))
                 .collect()
         }
+    } else if app.response_tab_selected == 2 {
+        tls_lines(&app.theme, response.tls.as_ref(), chrono::Utc::now())
+    } else if app.response_tab_selected == 3 {
+        panel_lines(
+            &app.theme,
+            app.tabs[app.selected_tab].panel.as_deref(),
+            &response.body,
+        )
+    } else if app.response_tab_selected == 4 {
+        test_lines(app, &response.tests)
+    } else if app.response_tab_selected == 5 {
+        match &response.sent {
+            Some(sent) => sent_lines(&app.theme, sent, response.version.as_deref()),
+            None => vec![Line::from("No request recorded")],
+        }
+    } else if let Some(bytes) = hex_bytes {
+        hex_lines(&app.theme, bytes, app.response_scroll, visible)
+    } else if let Some(tree) = &app.response_tree {
+        tree_lines(&app.theme, tree)
+    } else if let Some(table) = response.table.as_ref().filter(|_| app.response_table) {
+        table_lines(&app.theme, table, app.table_column)
+    } else if !response.parts.is_empty() {
+        multipart_body_lines(&app.theme, response, app.response_part_selected)
+    } else if let Some(Err(e)) = &filtered {
+        vec![Line::from(Span::styled(
+            e.to_string(),
+            Style::default().fg(app.theme.error),
+        ))]
+    } else if let Some(search) = &app.search {
+        search_lines(&app.theme, &body, search)
+    } else if response.is_json() || filtered.is_some() {
+        json_lines(&app.theme, &body)
+    } else if response.is_xml() {
+        xml_lines(&app.theme, &body)
     } else {
         // Body
-        response
-            .body
-            .lines()
+        body.lines()
             .map(|line| Line::from(line.to_string()))
             .collect()
     };
+    let content_height = match hex_bytes {
+        Some(bytes) => hexdump::line_count(bytes.len()),
+        None => content.len(),
+    };
 
-    let scroll_offset = app.response_scroll as u16;
+    // The tree view scrolls to keep its selection in sight, and the headers
+    // no further than their last page
+    let headers_top = app
+        .response_headers_scroll
+        .get()
+        .min(content_height.saturating_sub(visible));
+    let scroll_offset = match &app.response_tree {
+        _ if app.response_tab_selected == 0 => {
+            app.response_headers_scroll.set(headers_top);
+            headers_top as u16
+        }
+        _ if hex_bytes.is_some() => 0,
+        Some(tree) if app.response_tab_selected == 1 => {
+            tree.selected.saturating_sub(visible.saturating_sub(1)) as u16
+        }
+        _ => app.response_scroll as u16,
+    };
+    // Lines aren't wrapped; the body scrolls sideways up to its widest one
+    let column = if app.response_tab_selected == 1 {
+        let widest = content.iter().map(Line::width).max().unwrap_or(0);
+        let width = area.width.saturating_sub(2) as usize;
+        let column = app.response_column.get().min(widest.saturating_sub(width));
+        app.response_column.set(column);
+        column
+    } else {
+        0
+    };
     let paragraph = Paragraph::new(content)
         .block(block)
-        .scroll((scroll_offset, 0));
+        .scroll((scroll_offset, column as u16));
     f.render_widget(paragraph, area);
 
     // Render scrollbar for body content
     if app.response_tab_selected == 1 && !response.body.is_empty() {
-        let content_height = response.body.lines().count();
-        let mut scroll_state = app
-            .response_scroll_state
-            .clone()
-            .content_length(content_height);
+        let mut scroll_state = app.response_scroll_state.content_length(content_height);
 
         f.render_stateful_widget(
             Scrollbar::new(ScrollbarOrientation::VerticalRight)
@@ -378,17 +1112,506 @@ fn render_response_content(
             &mut scroll_state,
         );
     }
+    if app.response_tab_selected == 0 && content_height > visible {
+        let mut scroll_state =
+            ScrollbarState::new(content_height.saturating_sub(visible)).position(headers_top);
+        f.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(Some("↑"))
+                .end_symbol(Some("↓")),
+            area,
+            &mut scroll_state,
+        );
+    }
+}
+
+/// The status and reason phrase, colored by class: cyan for 1xx, green for
+/// 2xx, yellow for 3xx and red for 4xx/5xx
+fn status_span(theme: &Theme, response: &crate::logic::response::Response) -> Span<'static> {
+    let color = match response.status_code {
+        100..=199 => theme.accent,
+        200..=299 => theme.success,
+        300..=399 => theme.highlight,
+        400..=599 => theme.error,
+        _ => theme.muted,
+    };
+    Span::styled(
+        response.status_text(),
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Renders another tab's response in compare mode, scrolled together with
+/// the current one
+fn render_compare_content(f: &mut Frame, app: &App, tab: &Tab, area: Rect) {
+    let Some(response) = &tab.response else {
+        return;
+    };
+    let title = Line::from(vec![
+        Span::raw(format!("Compare - {} Status: ", tab.name)),
+        status_span(&app.theme, response),
+    ]);
+    let content: Vec<Line> = if app.response_tab_selected == 0 {
+        response
+            .headers
+            .iter()
+            .map(|(k, v)| Line::from(format!("{}: {}", k, v)))
+            .collect()
+    } else if response.is_json() {
+        json_lines(&app.theme, &response.body)
+    } else if response.is_xml() {
+        xml_lines(&app.theme, &response.body)
+    } else {
+        response
+            .body
+            .lines()
+            .map(|line| Line::from(line.to_string()))
+            .collect()
+    };
+    let (scroll, column) = if app.response_tab_selected == 0 {
+        (app.response_headers_scroll.get(), 0)
+    } else {
+        (app.response_scroll, app.response_column.get())
+    };
+    let paragraph = Paragraph::new(content)
+        .block(create_block(&app.theme, title, false, false))
+        .scroll((scroll as u16, column as u16));
+    f.render_widget(paragraph, area);
+}
+
+/// The `count` hex dump lines from line `first` on: offset, bytes and their
+/// printable characters
+fn hex_lines(theme: &Theme, bytes: &[u8], first: usize, count: usize) -> Vec<Line<'static>> {
+    (first..first.saturating_add(count))
+        .map_while(|index| hexdump::line(bytes, index))
+        .map(|line| {
+            Line::from(vec![
+                Span::styled(line.offset, Style::default().fg(theme.muted)),
+                Span::raw("  "),
+                Span::raw(line.hex),
+                Span::styled(" │", Style::default().fg(theme.muted)),
+                Span::styled(line.ascii, Style::default().fg(theme.accent)),
+                Span::styled("│", Style::default().fg(theme.muted)),
+            ])
+        })
+        .collect()
+}
+
+/// Rows of the JSON tree view, with the selected row highlighted
+fn tree_lines(theme: &Theme, tree: &Tree) -> Vec<Line<'static>> {
+    tree.rows()
+        .into_iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let marker = match row.open {
+                Some(true) => "▾ ",
+                Some(false) => "▸ ",
+                None => "  ",
+            };
+            let mut spans = vec![Span::raw(format!("{}{}", "  ".repeat(row.depth), marker))];
+            if let Some(key) = row.key {
+                spans.push(Span::styled(key, token_style(theme, Token::Key)));
+                spans.push(Span::styled(": ", token_style(theme, Token::Punctuation)));
+            }
+            if row.open.is_some() {
+                spans.push(Span::styled(row.value, Style::default().fg(theme.muted)));
+            } else {
+                spans.extend(
+                    highlight::json_line(&row.value)
+                        .into_iter()
+                        .map(|(token, text)| {
+                            Span::styled(text.to_string(), token_style(theme, token))
+                        }),
+                );
+            }
+            let line = Line::from(spans);
+            if index == tree.selected {
+                line.style(Style::default().bg(theme.selection))
+            } else {
+                line
+            }
+        })
+        .collect()
+}
+
+/// Body lines with JSON syntax highlighting
+fn json_lines(theme: &Theme, body: &str) -> Vec<Line<'static>> {
+    highlighted_lines(theme, body, highlight::json_line)
+}
+
+/// Body lines with XML tags highlighted
+fn xml_lines(theme: &Theme, body: &str) -> Vec<Line<'static>> {
+    highlighted_lines(theme, body, highlight::xml_line)
+}
+
+fn highlighted_lines(
+    theme: &Theme,
+    body: &str,
+    tokenize: fn(&str) -> Vec<(Token, &str)>,
+) -> Vec<Line<'static>> {
+    body.lines()
+        .map(|line| {
+            let spans: Vec<Span> = tokenize(line)
+                .into_iter()
+                .map(|(token, text)| Span::styled(text.to_string(), token_style(theme, token)))
+                .collect();
+            Line::from(spans)
+        })
+        .collect()
+}
+
+fn token_style(theme: &Theme, token: Token) -> Style {
+    match token {
+        Token::Key | Token::Tag => Style::default().fg(theme.accent),
+        Token::String => Style::default().fg(theme.success),
+        Token::Number => Style::default().fg(theme.number),
+        Token::Literal | Token::Attribute => Style::default().fg(theme.highlight),
+        Token::Punctuation | Token::Comment => Style::default().fg(theme.muted),
+        Token::Other => Style::default(),
+    }
+}
+
+/// Body lines with the search matches highlighted, the current one brightest
+fn search_lines(theme: &Theme, body: &str, search: &Search) -> Vec<Line<'static>> {
+    let matches = search.matches(body);
+    let current = search.current.min(matches.len().saturating_sub(1));
+    let mut matches = matches.into_iter().enumerate().peekable();
+    body.lines()
+        .enumerate()
+        .map(|(number, line)| {
+            let mut spans = Vec::new();
+            let mut end = 0;
+            while let Some((index, found)) = matches.next_if(|(_, found)| found.line == number) {
+                spans.push(Span::raw(line[end..found.start].to_string()));
+                let style = if index == current {
+                    Style::default()
+                        .fg(theme.selected_text)
+                        .bg(theme.highlight)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.selected_text).bg(theme.muted)
+                };
+                spans.push(Span::styled(
+                    line[found.start..found.end].to_string(),
+                    style,
+                ));
+                end = found.end;
+            }
+            spans.push(Span::raw(line[end..].to_string()));
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Lines of a custom response panel, or why it can't be shown
+fn panel_lines(theme: &Theme, template: Option<&str>, body: &str) -> Vec<Line<'static>> {
+    let Some(template) = template else {
+        return vec![Line::from("No panel for this request")];
+    };
+    match panel::render(template, body) {
+        Ok(text) => text
+            .lines()
+            .map(|line| Line::from(line.to_string()))
+            .collect(),
+        Err(e) => vec![Line::from(Span::styled(
+            e.to_string(),
+            Style::default().fg(theme.error),
+        ))],
+    }
+}
+
+/// Lines of a request as sent, the request line and headers marked like
+/// `curl -v`; `version` is the protocol the response came over
+pub fn sent_lines(theme: &Theme, sent: &SentRequest, version: Option<&str>) -> Vec<Line<'static>> {
+    sent.lines(version)
+        .into_iter()
+        .map(|line| {
+            let color = if line.starts_with('*') {
+                theme.muted
+            } else if line.starts_with('>') {
+                theme.highlight
+            } else {
+                theme.text
+            };
+            Line::from(Span::styled(line, Style::default().fg(color)))
+        })
+        .collect()
+}
+
+/// Lines of the test results, a mark and the assertion each, with what was
+/// found instead under a failed one
+fn test_lines(app: &App, results: &[TestResult]) -> Vec<Line<'static>> {
+    let theme = &app.theme;
+    let mut lines = Vec::new();
+    for result in results {
+        let (mark, color) = if result.passed {
+            (app.tr("PASS"), theme.success)
+        } else {
+            (app.tr("FAIL"), theme.error)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} ", mark),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(result.assertion.clone()),
+        ]));
+        if let Some(detail) = &result.detail {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{:indent$}{}",
+                    "",
+                    detail,
+                    indent = mark.chars().count() + 1
+                ),
+                Style::default().fg(theme.muted),
+            )));
+        }
+    }
+    lines
+}
+
+/// Renders a latency histogram as a bar chart with the percentiles above it
+///
+/// `throughput` is shown as a requests-per-second counter when given, so a
+/// caller redrawing during a run gets a live view.
+pub fn render_latency_histogram(
+    f: &mut Frame,
+    theme: &Theme,
+    area: Rect,
+    histogram: &LatencyHistogram,
+    throughput: Option<f64>,
+) {
+    let [summary_area, chart_area] =
+        Layout::vertical([Constraint::Length(2), Constraint::Min(3)]).areas(area);
+
+    let mut counts = format!("{} requests", histogram.len());
+    if let Some(mean) = histogram.mean() {
+        counts.push_str(&format!(", mean {}", format_duration(mean)));
+    }
+    if let Some(rps) = throughput {
+        counts.push_str(&format!(", {:.1} req/s", rps));
+    }
+    let summary = Paragraph::new(vec![
+        Line::from(counts),
+        Line::from(Span::styled(
+            histogram.summary(),
+            Style::default().fg(theme.highlight),
+        )),
+    ]);
+    f.render_widget(summary, summary_area);
+
+    // Roughly one bucket per label width, within reason
+    let bucket_count = (chart_area.width / 9).clamp(1, 12);
+    let bars: Vec<Bar> = histogram
+        .buckets(bucket_count as usize)
+        .into_iter()
+        .map(|(lower, count)| {
+            Bar::default()
+                .value(count)
+                .label(Line::from(format_duration(lower)))
+        })
+        .collect();
+    let chart = BarChart::default()
+        .data(BarGroup::default().bars(&bars))
+        .bar_width((chart_area.width / bucket_count).saturating_sub(1).max(1))
+        .bar_gap(1)
+        .bar_style(Style::default().fg(theme.success))
+        .value_style(
+            Style::default()
+                .fg(theme.selected_text)
+                .bg(theme.success)
+                .add_modifier(Modifier::BOLD),
+        );
+    f.render_widget(chart, chart_area);
+}
+
+/// Lines for the table view, starting at column `first`
+///
+/// The first column shown is highlighted since it's the one `s` sorts by.
+fn table_lines(theme: &Theme, table: &Table, first: usize) -> Vec<Line<'static>> {
+    let widths = table.widths();
+    let first = first.min(table.columns.len().saturating_sub(1));
+    let pad = |text: &str, width: usize| {
+        let count = text.chars().count();
+        if count > width {
+            let cut: String = text.chars().take(width.saturating_sub(1)).collect();
+            format!("{}…  ", cut)
+        } else {
+            format!("{}{}  ", text, " ".repeat(width - count))
+        }
+    };
+
+    let header: Vec<Span> = (first..table.columns.len())
+        .map(|index| {
+            let mut name = table.columns[index].clone();
+            match table.sorted_by {
+                Some((column, false)) if column == index => name.push_str(" ▲"),
+                Some((column, true)) if column == index => name.push_str(" ▼"),
+                _ => {}
+            }
+            let mut style = Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD);
+            if index == first {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            Span::styled(pad(&name, widths[index]), style)
+        })
+        .collect();
+
+    let mut lines = vec![Line::from(header)];
+    lines.extend(table.rows.iter().map(|row| {
+        Line::from(
+            (first..table.columns.len())
+                .map(|index| pad(&row[index], widths[index]))
+                .collect::<String>(),
+        )
+    }));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{} rows, column {}/{} (, . scroll columns, s sort, w save CSV, Y copy CSV, v text view)",
+            table.rows.len(),
+            first + 1,
+            table.columns.len()
+        ),
+        Style::default().fg(theme.muted),
+    )));
+    lines
+}
+
+/// Lines for the TLS tab of a response
+fn tls_lines(
+    theme: &Theme,
+    tls: Option<&TlsDetails>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Vec<Line<'static>> {
+    let Some(tls) = tls else {
+        return vec![Line::from("Not an HTTPS response")];
+    };
+    let field = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:<12}", label),
+                Style::default().fg(theme.highlight),
+            ),
+            Span::raw(value),
+        ])
+    };
+    let unknown = || match &tls.probe_error {
+        _ if tls.probing => "looking up…".to_string(),
+        Some(e) => format!("unknown ({})", e),
+        None => "unknown".to_string(),
+    };
+
+    let mut lines = vec![
+        field("Host", format!("{}:{}", tls.host, tls.port)),
+        field("Protocol", tls.protocol.clone().unwrap_or_else(unknown)),
+        field("Cipher", tls.cipher.clone().unwrap_or_else(unknown)),
+        Line::from(""),
+    ];
+    match &tls.certificate {
+        Some(cert) => {
+            let validity = format!(
+                "{} to {} ({})",
+                cert.not_before.format("%Y-%m-%d"),
+                cert.not_after.format("%Y-%m-%d"),
+                cert.expiry_summary(now)
+            );
+            let validity_style = if cert.is_expired(now) {
+                Style::default().fg(theme.error)
+            } else {
+                Style::default()
+            };
+            lines.push(field("Subject", cert.subject.clone()));
+            lines.push(field("Issuer", cert.issuer.clone()));
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:<12}", "Valid"),
+                    Style::default().fg(theme.highlight),
+                ),
+                Span::styled(validity, validity_style),
+            ]));
+            if !cert.alt_names.is_empty() {
+                lines.push(field("Names", cert.alt_names.join(", ")));
+            }
+            lines.push(field("SHA-256", cert.fingerprint.clone()));
+        }
+        None => lines.push(Line::from("No server certificate was reported")),
+    }
+    if let Some(pinned) = &tls.pinned {
+        let (text, style) = if tls.pin_mismatch() {
+            (
+                format!("MISMATCH, expected {}", pinned),
+                Style::default()
+                    .fg(theme.error)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            ("matches".to_string(), Style::default().fg(theme.success))
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:<12}", "Pin"),
+                Style::default().fg(theme.highlight),
+            ),
+            Span::styled(text, style),
+        ]));
+    }
+    lines
+}
+
+/// Lines for one part of a multipart body, headed by a part selector
+fn multipart_body_lines(
+    theme: &Theme,
+    response: &crate::logic::response::Response,
+    selected: usize,
+) -> Vec<Line<'static>> {
+    let index = selected.min(response.parts.len() - 1);
+    let part = &response.parts[index];
+
+    let mut summary = format!("Part {}/{}", index + 1, response.parts.len());
+    if let Some(name) = part.name() {
+        summary.push_str(&format!(" \"{}\"", name));
+    }
+    summary.push_str(&format!(
+        " ({})",
+        part.content_type().unwrap_or("text/plain")
+    ));
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            summary,
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("  [/] switch part", Style::default().fg(theme.muted)),
+    ])];
+    lines.extend(part.headers.iter().map(|(k, v)| {
+        Line::from(Span::styled(
+            format!("{}: {}", k, v),
+            Style::default().fg(theme.muted),
+        ))
+    }));
+    lines.push(Line::from(""));
+    lines.extend(
+        part.rendered_body()
+            .lines()
+            .map(|line| Line::from(line.to_string())),
+    );
+    lines
 }
 
 /// Renders empty response placeholder
 fn render_empty_response(f: &mut Frame, app: &App, area: Rect) {
     let is_active = matches!(app.current_screen, CurrentScreen::Response);
-    let block = create_block("Response", is_active, false);
+    let block = create_block(&app.theme, app.tr("Response"), is_active, false);
 
     let help_text = if matches!(app.current_screen, CurrentScreen::Response) {
-        "No response yet.\n\nPress Enter to send request\nPress ? for help"
+        app.tr("No response yet.\n\nPress Enter to send request\nPress ? for help")
     } else {
-        "No response yet."
+        app.tr("No response yet.")
     };
 
     let paragraph = Paragraph::new(help_text)
@@ -397,32 +1620,178 @@ fn render_empty_response(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(paragraph, area);
 }
 
+/// Renders the response area of a tab waiting for its response
+fn render_sending(f: &mut Frame, app: &App, in_flight: &InFlight, area: Rect) {
+    let is_active = matches!(app.current_screen, CurrentScreen::Response);
+    let block = create_block(&app.theme, app.tr("Response"), is_active, false);
+    let text = vec![
+        Line::from(Span::styled(
+            app.i18n
+                .format("Sending… {elapsed}", &[("elapsed", &in_flight.label())]),
+            Style::default()
+                .fg(app.theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            in_flight.url.clone(),
+            Style::default().fg(app.theme.muted),
+        )),
+    ];
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center);
+    f.render_widget(paragraph, area);
+}
+
 /// Renders the status bar at the bottom
 pub fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
-    let help_text = "Press ? for help | Enter: Send Request | q: Quit";
+    let help_text = app.tr("Press ? for help | Enter: Send Request | q: Quit");
 
     // Show current tab info if multiple tabs
     let tab_info = if app.tabs.len() > 1 {
-        format!(" | Tab {}/{}", app.selected_tab + 1, app.tabs.len())
+        format!(
+            " | {}",
+            app.i18n.format(
+                "Tab {current}/{count}",
+                &[
+                    ("current", &(app.selected_tab + 1)),
+                    ("count", &app.tabs.len())
+                ],
+            )
+        )
     } else {
         String::new()
     };
 
     // Show current screen info
     let screen_info = match app.current_screen {
-        CurrentScreen::EditingUrl => " | Editing URL",
-        CurrentScreen::EditingBody => " | Editing Body",
-        CurrentScreen::EditingHeaders => " | Editing Headers",
-        CurrentScreen::EditingParams => " | Editing Params",
-        CurrentScreen::Help => " | Help",
+        CurrentScreen::EditingUrl => "Editing URL",
+        CurrentScreen::EditingBody => "Editing Body",
+        CurrentScreen::EditingForm => "Editing Form",
+        CurrentScreen::EditingHeaders => "Editing Headers",
+        CurrentScreen::EditingParams => "Editing Params",
+        CurrentScreen::EditingBulk => "Bulk Edit",
+        CurrentScreen::EditingTests => "Editing Tests",
+        CurrentScreen::EditingAuth => "Editing Auth",
+        CurrentScreen::EditingOptions => "Editing Options",
+        CurrentScreen::EditingNote => "Editing Note",
+        CurrentScreen::Searching => "Search",
+        CurrentScreen::EditingFilter => "Filter",
+        CurrentScreen::ResendWith => "Resend With",
+        CurrentScreen::EditingWatch => "Watch",
+        CurrentScreen::EditingPoll => "Poll",
+        CurrentScreen::Help => "Help",
+        CurrentScreen::Export => "Export",
+        CurrentScreen::Collections => "Collections",
+        CurrentScreen::Timeline => "Timeline",
+        CurrentScreen::Finder => "Find",
+        CurrentScreen::EditingImportPath => "Import File",
+        CurrentScreen::ImportReview => "Review Import",
+        CurrentScreen::Copy => "Copy",
+        CurrentScreen::PasteImport => "Import Paste",
+        CurrentScreen::Cookies => "Cookies",
+        CurrentScreen::EditingCookie => "Editing Cookie",
+        CurrentScreen::SigningPreview => "Signature Preview",
+        CurrentScreen::DryRun => "Dry Run",
+        CurrentScreen::Latency => "Latency",
+        CurrentScreen::EditingMatrix | CurrentScreen::MatrixResults => "Matrix",
+        CurrentScreen::RunResults => "Run",
+        CurrentScreen::EditingLoad | CurrentScreen::LoadResults => "Load Test",
+        CurrentScreen::Response if app.response_zoomed => "Zoomed, z restores",
         _ => "",
     };
+    let screen_info = if screen_info.is_empty() {
+        String::new()
+    } else {
+        format!(" | {}", app.tr(screen_info))
+    };
+
+    let env_info = match app.active_environment() {
+        Some(env) => format!(
+            " | {}",
+            app.i18n.format("Env: {name}", &[("name", &env.name)])
+        ),
+        None => String::new(),
+    };
+
+    // Watching and polling go on in their own tab, which is named when it's
+    // another one
+    let tab_summary = |id: usize, summary: String| match app
+        .tab_index(id)
+        .filter(|&index| index != app.selected_tab)
+    {
+        Some(index) => format!(" | {}: {}", app.tabs[index].name, summary),
+        None => format!(" | {}", summary),
+    };
+    let watch_info = match &app.watch {
+        Some((id, watch)) => tab_summary(*id, watch.summary(&app.i18n)),
+        None => String::new(),
+    };
+    let poll_info = match &app.poll {
+        Some((id, poll)) => tab_summary(*id, poll.summary(std::time::Instant::now(), &app.i18n)),
+        None => String::new(),
+    };
+
+    let http_info = match &app.tabs[app.selected_tab].http_file {
+        Some((path, _)) => {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let text = if app.send_on_save {
+                "{name} (send on save)"
+            } else {
+                "{name} (reload on save)"
+            };
+            format!(" | {}", app.i18n.format(text, &[("name", &name)]))
+        }
+        None => String::new(),
+    };
+
+    // Counts down to the window of a 429 so it's visible from every screen
+    let rate_info = match app.rate_limit_wait(chrono::Utc::now()) {
+        Some((wait, true)) => format!(
+            "{} | ",
+            app.i18n.format(
+                "Rate limited, retrying in {wait}",
+                &[("wait", &format_wait(wait))]
+            )
+        ),
+        Some((0, false)) => format!("{} | ", app.tr("Rate limited, the window is open again")),
+        Some((wait, false)) => format!(
+            "{} | ",
+            app.i18n.format(
+                "Rate limited, the window opens in {wait}",
+                &[("wait", &format_wait(wait))]
+            )
+        ),
+        None => String::new(),
+    };
+
+    // A toast, or else a first-use hint, takes the place of the generic help
+    let (help_text, style) = match (&app.toast, app.hint()) {
+        (Some((toast, _)), _) => (
+            toast.clone(),
+            Style::default()
+                .fg(app.theme.success)
+                .add_modifier(Modifier::BOLD),
+        ),
+        (None, Some(hint)) => (
+            app.i18n.format(
+                "Hint: {hint} (Z: dismiss)",
+                &[("hint", &app.tr(hint.message()))],
+            ),
+            Style::default().fg(app.theme.highlight),
+        ),
+        (None, None) => (help_text.to_string(), Style::default().fg(app.theme.muted)),
+    };
 
-    let status_text = format!("{}{}{}", help_text, tab_info, screen_info);
+    let status_text = format!(
+        "{}{}{}{}{}{}{}{}",
+        rate_info, help_text, tab_info, env_info, watch_info, poll_info, http_info, screen_info
+    );
     let truncated_text = truncate_text(&status_text, area.width.saturating_sub(4) as usize);
 
     let status_paragraph = Paragraph::new(truncated_text)
-        .style(Style::default().fg(TEXT_COLOR_MUTED))
+        .style(style)
         .block(Block::default().borders(Borders::TOP));
 
     f.render_widget(status_paragraph, area);
@@ -450,17 +1819,107 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_table_lines() {
+        let mut table = Table::from_json(
+            r#"[{"id": 1, "name": "Ada", "role": "admin"}, {"id": 2, "name": "Grace"}]"#,
+        )
+        .unwrap();
+        table.sort_by(1);
+        let text = |lines: Vec<Line>| -> Vec<String> {
+            lines.iter().map(|line| line.to_string()).collect()
+        };
+
+        let lines = text(table_lines(&Theme::default(), &table, 0));
+        assert_eq!(lines[0], "id    name ▲  role    ");
+        assert_eq!(lines[1], "1     Ada     admin   ");
+        assert_eq!(lines[2], "2     Grace           ");
+        assert!(lines[3].starts_with("2 rows, column 1/3"));
+
+        let lines = text(table_lines(&Theme::default(), &table, 2));
+        assert_eq!(lines[0], "role    ");
+        assert!(lines[3].starts_with("2 rows, column 3/3"));
+    }
+
+    #[test]
+    fn test_render_latency_histogram() {
+        let backend = TestBackend::new(60, 12);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let histogram =
+            LatencyHistogram::from_samples([20, 25, 30, 80].map(std::time::Duration::from_millis));
+
+        terminal
+            .draw(|f| {
+                render_latency_histogram(f, &Theme::default(), f.area(), &histogram, Some(12.5))
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("4 requests, mean 39 ms, 12.5 req/s"));
+        assert!(text.contains("p50 25 ms"));
+        assert!(text.contains("20 ms"));
+    }
+
+    #[test]
+    fn test_tls_lines() {
+        let now = chrono::Utc::now();
+        assert_eq!(tls_lines(&Theme::default(), None, now).len(), 1);
+
+        let tls = TlsDetails {
+            host: "example.com".to_string(),
+            port: 443,
+            probe_error: Some("connection refused".to_string()),
+            ..Default::default()
+        };
+        let text: Vec<String> = tls_lines(&Theme::default(), Some(&tls), now)
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        assert!(text[0].ends_with("example.com:443"));
+        assert!(text[1].ends_with("unknown (connection refused)"));
+
+        let pinned = TlsDetails {
+            pinned: Some("AB".repeat(32)),
+            ..tls
+        };
+        let last = tls_lines(&Theme::default(), Some(&pinned), now)
+            .last()
+            .unwrap()
+            .to_string();
+        assert!(last.contains("MISMATCH"), "{}", last);
+    }
+
     #[test]
     fn test_render_url_input() {
         let backend = TestBackend::new(80, 3);
         let mut terminal = Terminal::new(backend).unwrap();
-        let app = create_test_app();
+        let mut app = create_test_app();
+        app.url_input = "http://a.test/users".to_string();
+        app.params_input.push(("page".to_string(), "2".to_string()));
 
         terminal
             .draw(|f| {
                 render_url_input(f, &app, f.area());
             })
             .unwrap();
+        // Shown with the query the params make up
+        let buffer = terminal.backend().buffer();
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("URL: http://a.test/users?page=2"));
+        app.params_input.clear();
+
+        // The cursor follows the edit position, not the end of the URL
+        app.url_input = "http://a.test".to_string();
+        app.start_url_edit();
+        app.url_cursor.home(&app.url_input);
+        app.url_cursor.right(&app.url_input);
+        terminal
+            .draw(|f| render_url_input(f, &app, f.area()))
+            .unwrap();
+        let (_, url_area) = create_url_layout(Rect::new(0, 0, 80, 3));
+        let cursor = terminal.get_cursor_position().unwrap();
+        assert_eq!((cursor.x, cursor.y), (url_area.x + 7, 1));
     }
 
     #[test]
@@ -476,6 +1935,61 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_render_body_editing_scrolls() {
+        let mut terminal = Terminal::new(TestBackend::new(40, 7)).unwrap();
+        let mut app = create_test_app();
+        app.body_input = (0..20)
+            .map(|i| format!("line {}", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        app.start_body_edit();
+        let first_line = |terminal: &Terminal<TestBackend>| {
+            let buffer = terminal.backend().buffer();
+            (1..9).map(|x| buffer[(x, 1)].symbol()).collect::<String>()
+        };
+
+        terminal
+            .draw(|f| render_body_content(f, &app, f.area()))
+            .unwrap();
+        assert_eq!(first_line(&terminal), "line 15 ");
+        let cursor = terminal.get_cursor_position().unwrap();
+        assert_eq!((cursor.x, cursor.y), (8, 5));
+
+        // Moving up inside the view doesn't scroll until the top is passed
+        for _ in 0..6 {
+            app.body_cursor.up(&app.body_input);
+        }
+        terminal
+            .draw(|f| render_body_content(f, &app, f.area()))
+            .unwrap();
+        assert_eq!(first_line(&terminal), "line 13 ");
+        let cursor = terminal.get_cursor_position().unwrap();
+        assert_eq!((cursor.x, cursor.y), (8, 1));
+    }
+
+    #[test]
+    fn test_render_auth_section() {
+        let backend = TestBackend::new(80, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = create_test_app();
+        app.current_screen = CurrentScreen::Values;
+        app.values_screen = ValuesScreen::Auth;
+        app.auth_input.kind = AuthKind::OAuth2ClientCredentials;
+        app.auth_input.set_field("client_secret", "hunter2");
+
+        terminal
+            .draw(|f| {
+                render_values_section(f, &app, f.area());
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let rendered: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(rendered.contains("client_secret=*******"));
+        assert!(!rendered.contains("hunter2"));
+    }
+
     #[test]
     fn test_render_response_section() {
         let backend = TestBackend::new(80, 10);
@@ -489,6 +2003,62 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_render_response_metrics() {
+        use crate::logic::metrics::Metrics;
+        use std::time::Duration;
+
+        let backend = TestBackend::new(120, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = create_test_app();
+        let metrics = Metrics {
+            elapsed: Duration::from_millis(42),
+            reused_connection: true,
+            phases: Default::default(),
+        };
+        let mut response =
+            crate::logic::response::Response::new_unchecked(200, String::new(), "ok".to_string());
+        response.metrics = Some(metrics);
+        app.tabs[0].response = Some(response);
+
+        terminal
+            .draw(|f| {
+                render_response_section(f, &app, f.area());
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let content: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("42 ms, reused connection"));
+    }
+
+    #[test]
+    fn test_render_rate_limit() {
+        let backend = TestBackend::new(120, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = create_test_app();
+        let mut response =
+            crate::logic::response::Response::new_unchecked(429, String::new(), String::new());
+        response.rate_limit = Some(crate::logic::ratelimit::RateLimit {
+            limit: Some(100),
+            remaining: Some(0),
+            ..Default::default()
+        });
+        app.tabs[0].response = Some(response);
+
+        terminal
+            .draw(|f| {
+                render_response_section(f, &app, f.area());
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let content: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("quota 0/100"));
+        let cell = buffer.content().iter().find(|c| c.symbol() == "q").unwrap();
+        assert_eq!(cell.fg, app.theme.error);
+    }
+
     #[test]
     fn test_render_status_bar() {
         let backend = TestBackend::new(80, 3);
@@ -4,12 +4,58 @@
 //! providing a clean separation between layout logic and component rendering.
 
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use serde::Deserialize;
+
+/// Terminals at least this wide get the split layout in `auto` mode
+pub const SPLIT_MIN_WIDTH: u16 = 160;
+
+/// How the request and the response are arranged, `[ui] layout` in the
+/// config file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutMode {
+    /// The response below the request
+    #[default]
+    Stacked,
+    /// The request on the left, the response on the right
+    Split,
+    /// Split on terminals at least [`SPLIT_MIN_WIDTH`] columns wide
+    Auto,
+}
+
+impl LayoutMode {
+    /// Whether a terminal `width` columns wide gets the split layout
+    pub fn is_split(self, width: u16) -> bool {
+        match self {
+            LayoutMode::Stacked => false,
+            LayoutMode::Split => true,
+            LayoutMode::Auto => width >= SPLIT_MIN_WIDTH,
+        }
+    }
+
+    /// The mode the layout key switches to
+    pub fn next(self) -> LayoutMode {
+        match self {
+            LayoutMode::Stacked => LayoutMode::Split,
+            LayoutMode::Split => LayoutMode::Auto,
+            LayoutMode::Auto => LayoutMode::Stacked,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LayoutMode::Stacked => "stacked",
+            LayoutMode::Split => "split",
+            LayoutMode::Auto => "auto",
+        }
+    }
+}
 
 /// Main application layout structure
 ///
 /// This struct holds all the areas for the main application components,
 /// making it easy to pass layout information between functions.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct MainLayout {
     pub tabs_area: Rect,
     pub url_area: Rect,
@@ -44,6 +90,75 @@ pub fn create_main_layout(area: Rect) -> MainLayout {
     }
 }
 
+/// Creates the side-by-side layout for wide terminals
+///
+/// The URL and the values make up the left column and the response takes
+/// the full height of the right one; tabs and status bar span both.
+pub fn create_split_layout(area: Rect) -> MainLayout {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Tabs section
+            Constraint::Min(8),    // Request and response side by side
+            Constraint::Length(3), // Status bar
+        ])
+        .split(area);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[1]);
+    let request = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // URL input section
+            Constraint::Min(5),    // Values section
+        ])
+        .split(columns[0]);
+
+    MainLayout {
+        tabs_area: rows[0],
+        url_area: request[0],
+        values_area: request[1],
+        response_area: columns[1],
+        status_area: rows[2],
+    }
+}
+
+/// Creates the layout of a zoomed response: the response over everything
+/// but the status bar
+pub fn create_zoomed_layout(area: Rect) -> (Rect, Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(8),    // Response section
+            Constraint::Length(3), // Status bar
+        ])
+        .split(area);
+
+    (chunks[0], chunks[1])
+}
+
+/// Finds the title of a tab bar at `offset` columns from its left edge
+///
+/// The titles are `widths` wide and laid out like ratatui's `Tabs`, with
+/// `padding` columns on either side of each title and a divider `divider`
+/// columns wide between them. Clicking a divider hits no title.
+pub fn tab_at(widths: &[usize], padding: usize, divider: usize, offset: u16) -> Option<usize> {
+    let offset = offset as usize;
+    let mut start = 0;
+    for (i, width) in widths.iter().enumerate() {
+        let end = start + padding + width + padding;
+        if offset < end {
+            return Some(i);
+        }
+        start = end + divider;
+        if offset < start {
+            return None;
+        }
+    }
+    None
+}
+
 /// Creates a two-column layout for the URL input section
 ///
 /// Splits the URL area into method selector and URL input field.
@@ -89,6 +204,18 @@ pub fn create_response_layout(area: Rect) -> (Rect, Rect) {
     (chunks[0], chunks[1])
 }
 
+/// Creates the two side-by-side panes of compare mode
+///
+/// The current tab's response goes left, the compared one right.
+pub fn create_compare_layout(area: Rect) -> (Rect, Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    (chunks[0], chunks[1])
+}
+
 /// Creates a popup layout with specified dimensions
 ///
 /// This is used for dialogs, help screens, and error messages.
@@ -248,7 +375,41 @@ mod tests {
         assert!(layout.values_area.height >= 8);
         assert!(layout.response_area.height >= 8);
         // Should have extra space distributed between values and response
-        assert_eq!(layout.values_area.height + layout.response_area.height, 31); // 40 - 9 = 31
+        assert_eq!(layout.values_area.height + layout.response_area.height, 31);
+        // 40 - 9 = 31
+    }
+
+    #[test]
+    fn test_create_split_layout() {
+        let area = Rect::new(0, 0, 200, 50);
+        let layout = create_split_layout(area);
+
+        assert_eq!(layout.tabs_area.width, 200);
+        assert_eq!(layout.status_area.width, 200);
+        assert_eq!(layout.url_area, Rect::new(0, 3, 100, 3));
+        assert_eq!(layout.values_area, Rect::new(0, 6, 100, 41));
+        assert_eq!(layout.response_area, Rect::new(100, 3, 100, 44));
+
+        assert!(!LayoutMode::Stacked.is_split(200));
+        assert!(LayoutMode::Split.is_split(80));
+        assert!(LayoutMode::Auto.is_split(SPLIT_MIN_WIDTH));
+        assert!(!LayoutMode::Auto.is_split(SPLIT_MIN_WIDTH - 1));
+        assert_eq!(LayoutMode::Auto.next(), LayoutMode::Stacked);
+    }
+
+    #[test]
+    fn test_tab_at() {
+        // " Tab 1 │ Users │ x "
+        let widths = [5, 5, 1];
+        assert_eq!(tab_at(&widths, 1, 1, 0), Some(0));
+        assert_eq!(tab_at(&widths, 1, 1, 6), Some(0));
+        assert_eq!(tab_at(&widths, 1, 1, 7), None);
+        assert_eq!(tab_at(&widths, 1, 1, 8), Some(1));
+        assert_eq!(tab_at(&widths, 1, 1, 18), Some(2));
+        assert_eq!(tab_at(&widths, 1, 1, 19), None);
+        // "Body Headers"
+        assert_eq!(tab_at(&[4, 7], 0, 1, 3), Some(0));
+        assert_eq!(tab_at(&[4, 7], 0, 1, 5), Some(1));
     }
 
     #[test]
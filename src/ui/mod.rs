@@ -9,6 +9,7 @@ pub mod components;
 pub mod layouts;
 pub mod popups;
 pub mod renderer;
+pub mod theme;
 
 pub use layouts::*;
 pub use renderer::ui;
@@ -16,34 +17,25 @@ pub use renderer::ui;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style},
-    text::Span,
+    text::{Line, Span},
     widgets::{Block, Borders},
 };
 
-/// Common UI constants and utilities
-pub const BORDER_COLOR_ACTIVE: Color = Color::Green;
-pub const BORDER_COLOR_INACTIVE: Color = Color::White;
-pub const BORDER_COLOR_EDITING: Color = Color::Yellow;
-pub const BORDER_COLOR_ERROR: Color = Color::Red;
-
-pub const TEXT_COLOR_NORMAL: Color = Color::White;
-pub const TEXT_COLOR_HIGHLIGHT: Color = Color::Yellow;
-#[allow(dead_code)]
-pub const TEXT_COLOR_ERROR: Color = Color::Red;
-#[allow(dead_code)]
-pub const TEXT_COLOR_SUCCESS: Color = Color::Green;
-#[allow(dead_code)]
-pub const TEXT_COLOR_INFO: Color = Color::Blue;
-pub const TEXT_COLOR_MUTED: Color = Color::Gray;
+pub use theme::Theme;
 
 /// Creates a styled block with appropriate border color based on state
-pub fn create_block(title: &str, is_active: bool, is_editing: bool) -> Block {
+pub fn create_block<'a>(
+    theme: &Theme,
+    title: impl Into<Line<'a>>,
+    is_active: bool,
+    is_editing: bool,
+) -> Block<'a> {
     let border_color = if is_editing {
-        BORDER_COLOR_EDITING
+        theme.border_editing
     } else if is_active {
-        BORDER_COLOR_ACTIVE
+        theme.border_active
     } else {
-        BORDER_COLOR_INACTIVE
+        theme.border_inactive
     };
 
     Block::default()
@@ -53,16 +45,14 @@ pub fn create_block(title: &str, is_active: bool, is_editing: bool) -> Block {
 }
 
 /// Creates a styled block for error display
-pub fn create_error_block(title: &str) -> Block {
+pub fn create_error_block<'a>(theme: &Theme, title: &'a str) -> Block<'a> {
     Block::default()
         .title(title)
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(BORDER_COLOR_ERROR))
+        .border_style(Style::default().fg(theme.border_error))
 }
 
-
-
 /// Utility function to calculate fixed size centered popup
 #[cfg(test)]
 pub fn centered_rect_fixed(width: u16, height: u16, area: Rect) -> Rect {
@@ -78,17 +68,17 @@ pub fn centered_rect_fixed(width: u16, height: u16, area: Rect) -> Rect {
 }
 
 /// Creates a status line with method color coding
-pub fn get_method_color(method: &crate::logic::HttpMethod) -> Color {
+pub fn get_method_color(theme: &Theme, method: &crate::logic::HttpMethod) -> Color {
     match method {
-        crate::logic::HttpMethod::GET => Color::Green,
-        crate::logic::HttpMethod::POST => Color::Blue,
-        crate::logic::HttpMethod::PUT => Color::Yellow,
-        crate::logic::HttpMethod::DELETE => Color::Red,
+        crate::logic::HttpMethod::GET => theme.success,
+        crate::logic::HttpMethod::POST => theme.info,
+        crate::logic::HttpMethod::PUT => theme.highlight,
+        crate::logic::HttpMethod::DELETE => theme.error,
     }
 }
 
 /// Creates styled text for HTTP methods
-pub fn method_text(method: &crate::logic::HttpMethod) -> Span {
+pub fn method_text<'a>(theme: &Theme, method: &'a crate::logic::HttpMethod) -> Span<'a> {
     let method_str = match method {
         crate::logic::HttpMethod::GET => "GET",
         crate::logic::HttpMethod::POST => "POST",
@@ -96,7 +86,10 @@ pub fn method_text(method: &crate::logic::HttpMethod) -> Span {
         crate::logic::HttpMethod::DELETE => "DELETE",
     };
 
-    Span::styled(method_str, Style::default().fg(get_method_color(method)))
+    Span::styled(
+        method_str,
+        Style::default().fg(get_method_color(theme, method)),
+    )
 }
 
 /// Truncates text to fit within a given width
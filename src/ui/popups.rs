@@ -13,11 +13,12 @@ use ratatui::{
     Frame,
 };
 
-use super::{
-    create_error_block, create_fixed_popup_layout, create_popup_layout, TEXT_COLOR_HIGHLIGHT,
-    TEXT_COLOR_MUTED, TEXT_COLOR_NORMAL,
-};
-use crate::app::App;
+use super::components::{render_latency_histogram, sent_lines};
+use super::{create_error_block, create_fixed_popup_layout, create_popup_layout, Theme};
+use crate::app::{App, CurrentScreen};
+use crate::export::{copy::CopyFormat, ExportFormat};
+use crate::logic::metrics::format_duration;
+use crate::logic::runner;
 
 /// Renders the help popup with key bindings and navigation help
 pub fn render_help_popup(f: &mut Frame, app: &App) {
@@ -37,9 +38,9 @@ pub fn render_help_popup(f: &mut Frame, app: &App) {
         } else if description.is_empty() {
             // Section header
             lines.push(Line::from(Span::styled(
-                key.to_string(),
+                app.tr(key).to_string(),
                 Style::default()
-                    .fg(TEXT_COLOR_HIGHLIGHT)
+                    .fg(app.theme.highlight)
                     .add_modifier(Modifier::BOLD),
             )));
         } else {
@@ -48,23 +49,23 @@ pub fn render_help_popup(f: &mut Frame, app: &App) {
                 Span::styled(
                     format!("{:15}", key),
                     Style::default()
-                        .fg(Color::Green)
+                        .fg(app.theme.success)
                         .add_modifier(Modifier::BOLD),
                 ),
                 Span::raw(" "),
                 Span::styled(
-                    description.to_string(),
-                    Style::default().fg(TEXT_COLOR_NORMAL),
+                    app.tr(description).to_string(),
+                    Style::default().fg(app.theme.text),
                 ),
             ]));
         }
     }
 
     let help_block = Block::default()
-        .title(" Restless - Key Bindings ")
+        .title(format!(" {} ", app.tr("Restless - Key Bindings")))
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(TEXT_COLOR_HIGHLIGHT));
+        .border_style(Style::default().fg(app.theme.highlight));
 
     let help_paragraph = Paragraph::new(lines)
         .block(help_block)
@@ -79,10 +80,9 @@ pub fn render_help_popup(f: &mut Frame, app: &App) {
 /// Renders scroll indicator for the help popup
 fn render_help_scroll_indicator(f: &mut Frame, app: &App, popup_area: Rect, total_items: usize) {
     if app.help_scroll > 0 || app.help_scroll < total_items.saturating_sub(1) {
-        let scroll_info = format!(
-            "j/k to scroll, Esc to close ({}/{})",
-            app.help_scroll + 1,
-            total_items
+        let scroll_info = app.i18n.format(
+            "j/k to scroll, Esc to close ({current}/{count})",
+            &[("current", &(app.help_scroll + 1)), ("count", &total_items)],
         );
 
         let scroll_area = Rect {
@@ -93,14 +93,1306 @@ fn render_help_scroll_indicator(f: &mut Frame, app: &App, popup_area: Rect, tota
         };
 
         let scroll_text = Paragraph::new(scroll_info)
-            .style(Style::default().fg(TEXT_COLOR_MUTED))
+            .style(Style::default().fg(app.theme.muted))
             .alignment(Alignment::Center);
         f.render_widget(scroll_text, scroll_area);
     }
 }
 
+/// Renders the export popup with a format selector and a preview
+pub fn render_export_popup(f: &mut Frame, app: &App) {
+    let popup_area = create_popup_layout(f.area(), 80, 80);
+    f.render_widget(Clear, popup_area);
+
+    let mut format_spans = vec![Span::raw(" Format: ")];
+    for format in ExportFormat::all() {
+        let style = if format == app.export_format {
+            Style::default()
+                .fg(app.theme.highlight)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(app.theme.muted)
+        };
+        format_spans.push(Span::styled(format!("[{}]", app.tr(format.label())), style));
+        format_spans.push(Span::raw(" "));
+    }
+
+    let mut lines = vec![Line::from(format_spans), Line::from("")];
+    let content = app.export_current(app.export_format);
+    lines.extend(
+        content
+            .lines()
+            .skip(app.export_scroll)
+            .map(|line| Line::from(line.to_string())),
+    );
+
+    let export_block = Block::default()
+        .title(format!(" {} ", app.tr("Export Request")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+
+    let paragraph = Paragraph::new(lines).block(export_block);
+    f.render_widget(paragraph, popup_area);
+
+    let instruction_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + popup_area.height.saturating_sub(1),
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+    let instruction_text =
+        Paragraph::new(app.tr("h/l format, j/k scroll, s save to file, Esc close"))
+            .style(Style::default().fg(app.theme.muted))
+            .alignment(Alignment::Center);
+    f.render_widget(instruction_text, instruction_area);
+}
+
+/// Renders the copy-format menu for the current response
+pub fn render_copy_popup(f: &mut Frame, app: &App) {
+    let popup_area = create_fixed_popup_layout(f.area(), 44, CopyFormat::ALL.len() as u16 + 4);
+    f.render_widget(Clear, popup_area);
+
+    let lines: Vec<Line> = CopyFormat::ALL
+        .iter()
+        .map(|format| {
+            if *format == app.copy_format {
+                Line::from(Span::styled(
+                    format!("> {}", format.label()),
+                    Style::default()
+                        .fg(app.theme.highlight)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Line::from(Span::styled(
+                    format!("  {}", format.label()),
+                    Style::default().fg(app.theme.text),
+                ))
+            }
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(format!(" {} ", app.tr("Copy Response")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    f.render_widget(
+        Paragraph::new(lines).block(block.padding(ratatui::widgets::Padding::uniform(1))),
+        popup_area,
+    );
+}
+
+/// Prompt for a header or param that only applies to the next send
+pub fn render_resend_popup(f: &mut Frame, app: &App) {
+    let popup_area = create_fixed_popup_layout(f.area(), 60, 7);
+    f.render_widget(Clear, popup_area);
+
+    let lines = vec![
+        Line::from(Span::styled(
+            format!("{}_", app.resend_input),
+            Style::default().fg(app.theme.highlight),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            app.tr("'Header: value' or 'param=value', Enter to send"),
+            Style::default().fg(app.theme.muted),
+        )),
+    ];
+
+    let block = Block::default()
+        .title(format!(" {} ", app.tr("Resend With")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    f.render_widget(
+        Paragraph::new(lines).block(block.padding(ratatui::widgets::Padding::horizontal(1))),
+        popup_area,
+    );
+}
+
+/// Renders the prompt for the variable and values of a matrix send
+pub fn render_matrix_prompt(f: &mut Frame, app: &App) {
+    let popup_area = create_fixed_popup_layout(f.area(), 60, 7);
+    f.render_widget(Clear, popup_area);
+
+    let lines = vec![
+        Line::from(Span::styled(
+            format!("{}_", app.matrix_input),
+            Style::default().fg(app.theme.highlight),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            app.tr("'name = value, value, ...', Enter sends one request per value"),
+            Style::default().fg(app.theme.muted),
+        )),
+    ];
+
+    let block = Block::default()
+        .title(format!(" {} ", app.tr("Matrix Send")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    f.render_widget(
+        Paragraph::new(lines).block(block.padding(ratatui::widgets::Padding::horizontal(1))),
+        popup_area,
+    );
+}
+
+/// Renders the status and latency of each value of the last matrix send
+pub fn render_matrix_popup(f: &mut Frame, app: &App) {
+    let Some((variable, outcomes)) = &app.matrix_results else {
+        return;
+    };
+    let popup_area = create_popup_layout(f.area(), 70, 60);
+    f.render_widget(Clear, popup_area);
+
+    let width = outcomes
+        .iter()
+        .map(|outcome| outcome.value.chars().count())
+        .chain([variable.chars().count()])
+        .max()
+        .unwrap_or(0);
+    let mut lines = vec![Line::from(Span::styled(
+        format!("{:width$}  {:>6}  {:>8}", variable, "Status", "Time"),
+        Style::default()
+            .fg(app.theme.muted)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    for outcome in outcomes {
+        let (status, color) = match &outcome.status {
+            Ok(code) if *code < 400 => (code.to_string(), app.theme.success),
+            Ok(code) => (code.to_string(), app.theme.error),
+            Err(_) => ("failed".to_string(), app.theme.error),
+        };
+        let mut spans = vec![
+            Span::styled(
+                format!("{:width$}  ", outcome.value),
+                Style::default().fg(app.theme.text),
+            ),
+            Span::styled(format!("{:>6}", status), Style::default().fg(color)),
+            Span::raw(format!("  {:>8}", format_duration(outcome.elapsed))),
+        ];
+        if let Err(e) = &outcome.status {
+            spans.push(Span::styled(
+                format!("  {}", e),
+                Style::default().fg(app.theme.muted),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let block = Block::default()
+        .title(format!(" {} - {} ", app.tr("Matrix"), variable))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    f.render_widget(
+        Paragraph::new(lines).block(block.padding(ratatui::widgets::Padding::uniform(1))),
+        popup_area,
+    );
+
+    let instruction_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + popup_area.height.saturating_sub(1),
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+    f.render_widget(
+        Paragraph::new(app.tr("Esc/M: close")).style(Style::default().fg(app.theme.muted)),
+        instruction_area,
+    );
+}
+
+/// Renders the prompt for the number of requests and concurrency of a load
+/// test
+pub fn render_load_prompt(f: &mut Frame, app: &App) {
+    let popup_area = create_fixed_popup_layout(f.area(), 60, 7);
+    f.render_widget(Clear, popup_area);
+
+    let lines = vec![
+        Line::from(Span::styled(
+            format!("{}_", app.load_input),
+            Style::default().fg(app.theme.highlight),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            app.tr("'requests x concurrency', Enter sends the request that often"),
+            Style::default().fg(app.theme.muted),
+        )),
+    ];
+
+    let block = Block::default()
+        .title(format!(" {} ", app.tr("Load Test")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    f.render_widget(
+        Paragraph::new(lines).block(block.padding(ratatui::widgets::Padding::horizontal(1))),
+        popup_area,
+    );
+}
+
+/// Renders the progress of the running load test, or the throughput, status
+/// codes and latency histogram of the last one
+pub fn render_load_popup(f: &mut Frame, app: &App) {
+    let popup_area = create_popup_layout(f.area(), 80, 70);
+    f.render_widget(Clear, popup_area);
+
+    let target = match (&app.load_test, &app.load_report) {
+        (Some(test), _) => test.target.as_str(),
+        (None, Some((target, _))) => target.as_str(),
+        (None, None) => "",
+    };
+    let block = Block::default()
+        .title(format!(" {} - {} ", app.tr("Load Test"), target))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let instructions = if let Some(test) = &app.load_test {
+        let line = app.i18n.format(
+            "{done}/{total} requests sent, {concurrency} at a time, {elapsed}",
+            &[
+                ("done", &test.done()),
+                ("total", &test.load.total),
+                ("concurrency", &test.load.concurrency),
+                ("elapsed", &format_duration(test.started.elapsed())),
+            ],
+        );
+        let [text_area, histogram_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Min(3)]).areas(inner);
+        f.render_widget(
+            Paragraph::new(line).style(Style::default().fg(app.theme.text)),
+            text_area,
+        );
+        render_latency_histogram(
+            f,
+            &app.theme,
+            histogram_area,
+            &test.latency(),
+            Some(test.requests_per_second()),
+        );
+        app.tr("Esc: stop")
+    } else if let Some((_, report)) = &app.load_report {
+        let mut lines = Vec::new();
+        let mut statuses = vec![Span::styled(
+            format!("{} ", app.tr("Status:")),
+            Style::default().fg(app.theme.muted),
+        )];
+        for (status, count) in &report.statuses {
+            let color = if *status < 400 {
+                app.theme.success
+            } else {
+                app.theme.error
+            };
+            statuses.push(Span::styled(
+                format!("{} ×{}  ", status, count),
+                Style::default().fg(color),
+            ));
+        }
+        if report.statuses.is_empty() {
+            statuses.push(Span::raw("-"));
+        }
+        lines.push(Line::from(statuses));
+        for (error, count) in &report.errors {
+            lines.push(Line::from(Span::styled(
+                app.i18n.format(
+                    "Failed ×{count}: {error}",
+                    &[("count", count), ("error", error)],
+                ),
+                Style::default().fg(app.theme.error),
+            )));
+        }
+        lines.push(Line::from(Span::styled(
+            app.i18n.format(
+                "{count} sent in {elapsed}",
+                &[
+                    ("count", &report.sent()),
+                    ("elapsed", &format_duration(report.elapsed)),
+                ],
+            ),
+            Style::default().fg(app.theme.muted),
+        )));
+        let [text_area, histogram_area] = Layout::vertical([
+            Constraint::Length(lines.len() as u16 + 1),
+            Constraint::Min(3),
+        ])
+        .areas(inner);
+        f.render_widget(Paragraph::new(lines), text_area);
+        render_latency_histogram(
+            f,
+            &app.theme,
+            histogram_area,
+            &report.latency,
+            Some(report.requests_per_second()),
+        );
+        app.tr("r: run again, Esc: close")
+    } else {
+        app.tr("Esc: stop")
+    };
+
+    let instruction_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + popup_area.height.saturating_sub(1),
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+    f.render_widget(
+        Paragraph::new(instructions).style(Style::default().fg(app.theme.muted)),
+        instruction_area,
+    );
+}
+
+/// Renders the status, time and test results of each request of the last run,
+/// with the failed tests under their request, and while it runs the request
+/// being sent and those still to send
+pub fn render_run_popup(f: &mut Frame, app: &App) {
+    let Some((title, steps)) = &app.run_results else {
+        return;
+    };
+    let popup_area = create_popup_layout(f.area(), 80, 70);
+    f.render_widget(Clear, popup_area);
+
+    let current = app.run.as_ref().and_then(|run| run.current.as_ref());
+    let pending = app.run.iter().flat_map(|run| &run.pending);
+    let width = steps
+        .iter()
+        .map(|step| step.name.as_str())
+        .chain(current.map(|send| send.name.as_str()))
+        .chain(pending.clone().map(|(name, _)| name.as_str()))
+        .map(|name| name.chars().count())
+        .chain([4])
+        .max()
+        .unwrap_or(0);
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "{:width$}  {:>6}  {:>8}  {:>6}",
+            app.tr("Name"),
+            app.tr("Status"),
+            app.tr("Time"),
+            app.tr("Tests")
+        ),
+        Style::default()
+            .fg(app.theme.muted)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    for step in steps {
+        let status = match &step.status {
+            Ok(code) => code.to_string(),
+            Err(_) => app.tr("failed").to_string(),
+        };
+        let color = if step.passed() {
+            app.theme.success
+        } else {
+            app.theme.error
+        };
+        let passed = step.tests.iter().filter(|result| result.passed).count();
+        let tests = if step.tests.is_empty() {
+            "-".to_string()
+        } else {
+            format!("{}/{}", passed, step.tests.len())
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:width$}  ", step.name),
+                Style::default().fg(app.theme.text),
+            ),
+            Span::styled(format!("{:>6}", status), Style::default().fg(color)),
+            Span::raw(format!("  {:>8}", format_duration(step.elapsed))),
+            Span::styled(format!("  {:>6}", tests), Style::default().fg(color)),
+            Span::styled(
+                format!("  {} {}", step.method, step.url),
+                Style::default().fg(app.theme.muted),
+            ),
+        ]));
+        if let Err(e) = &step.status {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", e),
+                Style::default().fg(app.theme.muted),
+            )));
+        }
+        for result in step.tests.iter().filter(|result| !result.passed) {
+            let detail = result
+                .detail
+                .as_ref()
+                .map(|detail| format!(" ({})", detail))
+                .unwrap_or_default();
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {} ", app.tr("FAIL")),
+                    Style::default()
+                        .fg(app.theme.error)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(result.assertion.clone()),
+                Span::styled(detail, Style::default().fg(app.theme.muted)),
+            ]));
+        }
+    }
+    if let Some(send) = current {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:width$}  ", send.name),
+                Style::default().fg(app.theme.text),
+            ),
+            Span::styled(
+                app.i18n.format(
+                    "Sending… {elapsed}",
+                    &[("elapsed", &format_duration(send.started.elapsed()))],
+                ),
+                Style::default().fg(app.theme.highlight),
+            ),
+        ]));
+    }
+    for (name, _) in pending {
+        lines.push(Line::from(Span::styled(
+            format!("{:width$}  {}", name, app.tr("waiting")),
+            Style::default().fg(app.theme.muted),
+        )));
+    }
+
+    let progress = match &app.run {
+        Some(run) => format!("{}/{}", steps.len(), run.total),
+        None => runner::summary(steps, &app.i18n),
+    };
+    let block = Block::default()
+        .title(format!(" {} - {} - {} ", app.tr("Run"), title, progress))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    f.render_widget(
+        Paragraph::new(lines)
+            .scroll((app.run_scroll as u16, 0))
+            .block(block.padding(ratatui::widgets::Padding::uniform(1))),
+        popup_area,
+    );
+
+    let instruction_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + popup_area.height.saturating_sub(1),
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+    f.render_widget(
+        Paragraph::new(if app.run.is_some() {
+            app.tr("j/k scroll, Esc stop")
+        } else {
+            app.tr("j/k scroll, Esc close")
+        })
+        .style(Style::default().fg(app.theme.muted)),
+        instruction_area,
+    );
+}
+
+/// Renders the prompt for the file or directory whose changes re-send
+pub fn render_watch_popup(f: &mut Frame, app: &App) {
+    let popup_area = create_fixed_popup_layout(f.area(), 60, 7);
+    f.render_widget(Clear, popup_area);
+
+    let lines = vec![
+        Line::from(Span::styled(
+            format!("{}_", app.watch_input),
+            Style::default().fg(app.theme.highlight),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            app.tr("File or directory, Tab completes, empty stops watching"),
+            Style::default().fg(app.theme.muted),
+        )),
+    ];
+
+    let block = Block::default()
+        .title(format!(" {} ", app.tr("Re-send On Change")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    f.render_widget(
+        Paragraph::new(lines).block(block.padding(ratatui::widgets::Padding::horizontal(1))),
+        popup_area,
+    );
+}
+
+/// Renders the prompt for the polling interval
+pub fn render_poll_popup(f: &mut Frame, app: &App) {
+    let popup_area = create_fixed_popup_layout(f.area(), 60, 7);
+    f.render_widget(Clear, popup_area);
+
+    let lines = vec![
+        Line::from(Span::styled(
+            format!("{}_", app.poll_input),
+            Style::default().fg(app.theme.highlight),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            app.tr("Seconds like 5 or 30s, or minutes like 2m; empty stops polling"),
+            Style::default().fg(app.theme.muted),
+        )),
+    ];
+
+    let block = Block::default()
+        .title(format!(" {} ", app.tr("Re-send Every")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    f.render_widget(
+        Paragraph::new(lines).block(block.padding(ratatui::widgets::Padding::horizontal(1))),
+        popup_area,
+    );
+}
+
+/// Asks whether a pasted curl command or raw HTTP request should be imported
+pub fn render_paste_import_popup(f: &mut Frame, app: &App) {
+    let Some((kind, text)) = &app.paste_import else {
+        return;
+    };
+    let popup_area = create_popup_layout(f.area(), 70, 50);
+    f.render_widget(Clear, popup_area);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            app.i18n.format(
+                "This looks like a {kind}.",
+                &[("kind", &app.tr(kind.label()))],
+            ),
+            Style::default()
+                .fg(app.theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    match kind.import(text) {
+        Some(Ok(request)) => {
+            lines.push(Line::from(format!("{} {}", request.method, request.url)));
+            for (key, value) in &request.headers {
+                lines.push(Line::from(Span::styled(
+                    format!("{}: {}", key, value),
+                    Style::default().fg(app.theme.text),
+                )));
+            }
+            if let Some(body) = &request.body {
+                lines.push(Line::from(Span::styled(
+                    app.i18n
+                        .format("Body: {count} bytes", &[("count", &body.len())]),
+                    Style::default().fg(app.theme.text),
+                )));
+            }
+        }
+        Some(Err(e)) => lines.push(Line::from(Span::styled(
+            app.i18n.format("Can't import: {error}", &[("error", &e)]),
+            Style::default().fg(app.theme.error),
+        ))),
+        None => {}
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        app.tr("Enter/y: import into this tab   n: paste as text   Esc: cancel"),
+        Style::default().fg(app.theme.highlight),
+    )));
+
+    let block = Block::default()
+        .title(format!(" {} ", app.tr("Import Paste")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block.padding(ratatui::widgets::Padding::uniform(1)))
+            .wrap(Wrap { trim: false }),
+        popup_area,
+    );
+}
+
+/// Renders the latency distribution of the current tab's sends
+pub fn render_latency_popup(f: &mut Frame, app: &App) {
+    let popup_area = create_popup_layout(f.area(), 80, 70);
+    f.render_widget(Clear, popup_area);
+
+    let stats = &app.tabs[app.selected_tab].latency;
+    let block = Block::default()
+        .title(format!(" {} - {} ", app.tr("Latency"), stats.target))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+    render_latency_histogram(f, &app.theme, inner, &stats.histogram(), None);
+
+    let instruction_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + popup_area.height.saturating_sub(1),
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+    f.render_widget(
+        Paragraph::new(app.tr("Esc/L: close")).style(Style::default().fg(app.theme.muted)),
+        instruction_area,
+    );
+}
+
+/// Renders the canonical request, string-to-sign and headers of the signature
+pub fn render_signing_popup(f: &mut Frame, app: &App) {
+    let popup_area = create_popup_layout(f.area(), 80, 80);
+    f.render_widget(Clear, popup_area);
+
+    let section = |title: &str| {
+        Line::from(Span::styled(
+            title.to_string(),
+            Style::default()
+                .fg(app.theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+
+    let mut lines = Vec::new();
+    match app.signing_preview() {
+        Ok(Some(signature)) => {
+            lines.push(Line::from(Span::styled(
+                app.tr(
+                    "Timestamps are taken now; the signature sent with the request will differ.",
+                ),
+                Style::default().fg(app.theme.muted),
+            )));
+            lines.push(Line::from(""));
+            if let Some(canonical) = &signature.canonical_request {
+                lines.push(section(app.tr("Canonical request")));
+                lines.extend(canonical.lines().map(|l| Line::from(l.to_string())));
+                lines.push(Line::from(""));
+            }
+            lines.push(section(app.tr("String to sign")));
+            lines.extend(
+                signature
+                    .string_to_sign
+                    .lines()
+                    .map(|l| Line::from(l.to_string())),
+            );
+            lines.push(Line::from(""));
+            lines.push(section(app.tr("Headers added")));
+            lines.extend(signature.headers.iter().map(|(k, v)| {
+                Line::from(vec![
+                    Span::styled(format!("{}: ", k), Style::default().fg(app.theme.success)),
+                    Span::raw(v.clone()),
+                ])
+            }));
+        }
+        Ok(None) => lines.push(Line::from(app.tr("This auth type doesn't sign requests."))),
+        Err(e) => lines.push(Line::from(Span::styled(
+            app.get_error_message(&e),
+            Style::default().fg(app.theme.error),
+        ))),
+    }
+
+    let block = Block::default()
+        .title(format!(
+            " {} - {} ",
+            app.tr("Signature Preview"),
+            app.auth_input.kind.label()
+        ))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((app.signing_scroll as u16, 0));
+    f.render_widget(paragraph, popup_area);
+
+    let instruction_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + popup_area.height.saturating_sub(1),
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+    let instruction_text = Paragraph::new(app.tr("j/k scroll, Esc close"))
+        .style(Style::default().fg(app.theme.muted))
+        .alignment(Alignment::Center);
+    f.render_widget(instruction_text, instruction_area);
+}
+
+/// Renders the request a dry run built, as it would go on the wire
+pub fn render_dry_run_popup(f: &mut Frame, app: &App) {
+    let popup_area = create_popup_layout(f.area(), 80, 80);
+    f.render_widget(Clear, popup_area);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            app.tr("Nothing was sent. Enter sends it now."),
+            Style::default().fg(app.theme.muted),
+        )),
+        Line::from(""),
+    ];
+    if let Some(sent) = &app.dry_run {
+        lines.extend(sent_lines(&app.theme, sent, None));
+    }
+
+    let block = Block::default()
+        .title(format!(" {} ", app.tr("Dry Run")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((app.dry_run_scroll as u16, 0));
+    f.render_widget(paragraph, popup_area);
+
+    let instruction_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + popup_area.height.saturating_sub(1),
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+    let instruction_text = Paragraph::new(app.tr("j/k scroll, Enter send, Esc close"))
+        .style(Style::default().fg(app.theme.muted))
+        .alignment(Alignment::Center);
+    f.render_widget(instruction_text, instruction_area);
+}
+
+/// Renders the cookie jar grouped by domain, with the input line when editing
+pub fn render_cookies_popup(f: &mut Frame, app: &App) {
+    let popup_area = create_popup_layout(f.area(), 80, 80);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", app.tr("Cookies")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let editing = app.current_screen == CurrentScreen::EditingCookie;
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(if editing { 3 } else { 0 }),
+        ])
+        .split(inner);
+
+    let cookies = app.cookie_jar.list();
+    let mut lines = Vec::new();
+    let mut selected_line = 0;
+    let mut domain: Option<&str> = None;
+    for (index, cookie) in cookies.iter().enumerate() {
+        if domain != Some(cookie.domain.as_str()) {
+            domain = Some(&cookie.domain);
+            let label = if cookie.host_only {
+                cookie.domain.clone()
+            } else {
+                format!("{} (and subdomains)", cookie.domain)
+            };
+            lines.push(Line::from(Span::styled(
+                label,
+                Style::default()
+                    .fg(app.theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+
+        let mut flags = vec![cookie.path.clone()];
+        if cookie.secure {
+            flags.push("Secure".to_string());
+        }
+        if cookie.http_only {
+            flags.push("HttpOnly".to_string());
+        }
+        flags.push(match cookie.expires {
+            Some(expires) => format!("expires {}", expires.format("%Y-%m-%d %H:%M UTC")),
+            None => "session".to_string(),
+        });
+        let text = format!("  {}={}  [{}]", cookie.name, cookie.value, flags.join(", "));
+
+        if index == app.cookie_selected {
+            selected_line = lines.len();
+            lines.push(Line::from(Span::styled(
+                text,
+                Style::default()
+                    .fg(app.theme.selected_text)
+                    .bg(app.theme.highlight),
+            )));
+        } else {
+            lines.push(Line::from(Span::styled(
+                text,
+                Style::default().fg(app.theme.text),
+            )));
+        }
+    }
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            app.tr("The cookie jar is empty. Cookies set by responses show up here; press a to add one."),
+            Style::default().fg(app.theme.muted),
+        )));
+    }
+
+    let visible = rows[0].height.saturating_sub(1) as usize;
+    let offset = selected_line.saturating_sub(visible.saturating_sub(1));
+    f.render_widget(
+        Paragraph::new(lines.into_iter().skip(offset).collect::<Vec<_>>()),
+        rows[0],
+    );
+
+    if editing {
+        let title = if app.cookie_editing.is_some() {
+            app.tr("Edit cookie")
+        } else {
+            app.tr("New cookie (name=value; Domain=host)")
+        };
+        let input = Paragraph::new(app.cookie_input.as_str()).block(
+            Block::default()
+                .title(format!(" {} ", title))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.highlight)),
+        );
+        f.render_widget(input, rows[1]);
+        f.set_cursor_position((
+            rows[1].x + 1 + app.cookie_input.chars().count() as u16,
+            rows[1].y + 1,
+        ));
+    }
+
+    let instruction_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + popup_area.height.saturating_sub(1),
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+    let instructions = if editing {
+        app.tr("Enter save, Esc cancel")
+    } else {
+        app.tr("j/k select, a add, i edit, d delete, D clear all, Esc close")
+    };
+    let instruction_text = Paragraph::new(instructions)
+        .style(Style::default().fg(app.theme.muted))
+        .alignment(Alignment::Center);
+    f.render_widget(instruction_text, instruction_area);
+}
+
+/// Renders the collection browser: saved requests on the left, notes on the right
+pub fn render_collections_popup(f: &mut Frame, app: &App) {
+    let popup_area = create_popup_layout(f.area(), 80, 80);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", app.tr("Collections")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let importing = app.current_screen == CurrentScreen::EditingImportPath;
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(if importing { 3 } else { 0 }),
+        ])
+        .split(inner);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(rows[0]);
+
+    let selected = app
+        .collection_entries()
+        .get(app.collection_selected)
+        .copied();
+    let mut lines = Vec::new();
+    let mut selected_line = 0;
+    for (c, collection) in app.collections.iter().enumerate() {
+        lines.push(Line::from(Span::styled(
+            collection.name.clone(),
+            Style::default()
+                .fg(app.theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for (r, saved) in collection.requests.iter().enumerate() {
+            let text = format!("  {:6} {}", saved.method.to_uppercase(), saved.name);
+            if selected == Some((c, r)) {
+                selected_line = lines.len();
+                lines.push(Line::from(Span::styled(
+                    text,
+                    Style::default()
+                        .fg(app.theme.selected_text)
+                        .bg(app.theme.highlight),
+                )));
+            } else {
+                lines.push(Line::from(Span::styled(
+                    text,
+                    Style::default().fg(app.theme.text),
+                )));
+            }
+        }
+    }
+    let visible = columns[0].height.saturating_sub(1) as usize;
+    let offset = selected_line.saturating_sub(visible.saturating_sub(1));
+    f.render_widget(
+        Paragraph::new(lines.into_iter().skip(offset).collect::<Vec<_>>()),
+        columns[0],
+    );
+
+    let mut details = Vec::new();
+    if let Some((c, r)) = selected {
+        let collection = &app.collections[c];
+        let saved = &collection.requests[r];
+        details.push(Line::from(Span::styled(
+            saved.name.clone(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+        details.push(Line::from(Span::styled(
+            format!("{} {}", saved.method.to_uppercase(), saved.url),
+            Style::default().fg(app.theme.success),
+        )));
+        let auth = match &saved.auth {
+            Some(auth) => auth.kind.label().to_string(),
+            None => format!("{} (from collection)", collection.auth.kind.label()),
+        };
+        details.push(Line::from(Span::styled(
+            format!("Auth: {}", auth),
+            Style::default().fg(app.theme.muted),
+        )));
+        details.push(Line::from(""));
+        let notes = if saved.description.is_empty() {
+            &collection.description
+        } else {
+            &saved.description
+        };
+        details.push(Line::from(Span::styled(
+            notes.clone(),
+            Style::default().fg(app.theme.text),
+        )));
+    } else {
+        details.push(Line::from(Span::styled(
+            app.tr("No saved requests"),
+            Style::default().fg(app.theme.muted),
+        )));
+    }
+    f.render_widget(
+        Paragraph::new(details)
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::LEFT)),
+        columns[1],
+    );
+
+    if importing {
+        let input = Paragraph::new(app.import_input.as_str()).block(
+            Block::default()
+                .title(format!(
+                    " {} ",
+                    app.tr("Import HAR/Postman/OpenAPI file or open a .http file (Tab completes)")
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(app.theme.highlight)),
+        );
+        f.render_widget(input, rows[1]);
+        f.set_cursor_position((
+            rows[1].x + 1 + app.import_input.chars().count() as u16,
+            rows[1].y + 1,
+        ));
+    }
+
+    let instruction_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + popup_area.height.saturating_sub(1),
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+    let instructions = if importing {
+        app.tr("Enter review import (.http files open directly), Esc cancel")
+    } else {
+        app.tr("j/k select, Enter open in tab, R run all, r revisions, i import file, a send .http on save, Esc close")
+    };
+    let instruction_text = Paragraph::new(instructions)
+        .style(Style::default().fg(app.theme.muted))
+        .alignment(Alignment::Center);
+    f.render_widget(instruction_text, instruction_area);
+}
+
+/// Renders the timeline of a saved request: when it changed and how, with
+/// the selected version beside it
+pub fn render_timeline_popup(f: &mut Frame, app: &App) {
+    let popup_area = create_popup_layout(f.area(), 70, 60);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", app.tr("Revisions")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(inner);
+
+    let lines: Vec<Line> = app
+        .timeline
+        .iter()
+        .enumerate()
+        .map(|(i, version)| {
+            let changes = if version.changes.is_empty() {
+                app.tr("first seen").to_string()
+            } else {
+                version.changes.join(", ")
+            };
+            let text = format!(
+                "{}  {}",
+                version
+                    .saved_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M"),
+                changes
+            );
+            let style = if i == app.timeline_selected {
+                Style::default()
+                    .fg(app.theme.selected_text)
+                    .bg(app.theme.highlight)
+            } else {
+                Style::default().fg(app.theme.text)
+            };
+            Line::from(Span::styled(text, style))
+        })
+        .collect();
+    let visible = columns[0].height.saturating_sub(1) as usize;
+    let offset = app
+        .timeline_selected
+        .saturating_sub(visible.saturating_sub(1));
+    f.render_widget(
+        Paragraph::new(lines.into_iter().skip(offset).collect::<Vec<_>>()),
+        columns[0],
+    );
+
+    let mut details = Vec::new();
+    if let Some(version) = app.timeline.get(app.timeline_selected) {
+        let saved = &version.request;
+        details.push(Line::from(Span::styled(
+            format!("{} {}", saved.method.to_uppercase(), saved.url),
+            Style::default().fg(app.theme.success),
+        )));
+        for (key, value) in &saved.headers {
+            details.push(Line::from(format!("{}: {}", key, value)));
+        }
+        for (name, value) in &saved.params {
+            details.push(Line::from(Span::styled(
+                format!("?{}={}", name, value),
+                Style::default().fg(app.theme.muted),
+            )));
+        }
+        if let Some(body) = &saved.body {
+            details.push(Line::from(""));
+            details.extend(body.lines().map(|line| Line::from(line.to_string())));
+        }
+    }
+    f.render_widget(
+        Paragraph::new(details)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::LEFT)),
+        columns[1],
+    );
+
+    let instruction_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + popup_area.height.saturating_sub(1),
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+    let instruction_text = Paragraph::new(app.tr("j/k select, Enter restore and open, Esc back"))
+        .style(Style::default().fg(app.theme.muted))
+        .alignment(Alignment::Center);
+    f.render_widget(instruction_text, instruction_area);
+}
+
+/// Renders the finder: the query and the items matching it, matched letters
+/// in bold
+pub fn render_finder_popup(f: &mut Frame, app: &App) {
+    let Some(finder) = &app.finder else {
+        return;
+    };
+    let popup_area = create_popup_layout(f.area(), 70, 60);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", app.tr("Find")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(1)])
+        .split(inner);
+
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled("> ", Style::default().fg(app.theme.muted)),
+            Span::styled(
+                format!("{}_", finder.input),
+                Style::default().fg(app.theme.highlight),
+            ),
+            Span::styled(
+                format!("  {}", finder.matches.len()),
+                Style::default().fg(app.theme.muted),
+            ),
+        ])),
+        rows[0],
+    );
+
+    let visible = rows[1].height.saturating_sub(1) as usize;
+    let offset = finder.selected.saturating_sub(visible.saturating_sub(1));
+    let lines: Vec<Line> = finder
+        .matches
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(i, (index, matched))| {
+            let item = finder.item(*index);
+            let base = if i == finder.selected {
+                Style::default()
+                    .fg(app.theme.selected_text)
+                    .bg(app.theme.highlight)
+            } else {
+                Style::default().fg(app.theme.text)
+            };
+            let mut spans = vec![Span::styled(
+                format!("{:8}", item.kind()),
+                if i == finder.selected {
+                    base
+                } else {
+                    Style::default().fg(app.theme.muted)
+                },
+            )];
+            spans.extend(item.text().chars().enumerate().map(|(n, c)| {
+                let style = if matched.positions.binary_search(&n).is_ok() {
+                    base.add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+                } else {
+                    base
+                };
+                Span::styled(c.to_string(), style)
+            }));
+            Line::from(spans)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), rows[1]);
+
+    let instruction_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + popup_area.height.saturating_sub(1),
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+    let instruction_text =
+        Paragraph::new(app.tr("Type to narrow, Up/Down select, Enter open, Esc close"))
+            .style(Style::default().fg(app.theme.muted))
+            .alignment(Alignment::Center);
+    f.render_widget(instruction_text, instruction_area);
+}
+
+/// Renders the review of an import: each request and what happens to it
+pub fn render_import_review_popup(f: &mut Frame, app: &App) {
+    let Some(review) = &app.import_review else {
+        return;
+    };
+    let popup_area = create_popup_layout(f.area(), 80, 80);
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!(" {} ", app.tr("Review Import")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.highlight));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let target = if review.index.is_some() {
+        review.collection.name.clone()
+    } else {
+        app.i18n.format(
+            "{name} (new collection)",
+            &[("name", &review.collection.name)],
+        )
+    };
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("{} → {}", review.source, target),
+            Style::default()
+                .fg(app.theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            app.i18n.format(
+                "{count} requests, {conflicts} already in the collection",
+                &[
+                    ("count", &review.items.len()),
+                    ("conflicts", &review.conflicts()),
+                ],
+            ),
+            Style::default().fg(app.theme.muted),
+        )),
+        Line::from(""),
+    ];
+    let header_lines = lines.len();
+    for (index, item) in review.items.iter().enumerate() {
+        let action = match item.existing {
+            Some(_) => item.resolution.label(),
+            None => "new",
+        };
+        let mut text = format!(
+            "{:<10} {:6} {}",
+            format!("[{}]", action),
+            item.request.method,
+            item.request.name
+        );
+        if item.count > 1 {
+            text.push_str(&format!(" ×{}", item.count));
+        }
+        if let Some(existing) = item.existing {
+            text.push_str(&format!(
+                "  = {}",
+                review.collection.requests[existing].name
+            ));
+        }
+        let style = if index == review.selected {
+            Style::default()
+                .fg(app.theme.selected_text)
+                .bg(app.theme.highlight)
+        } else if item.existing.is_some() {
+            Style::default().fg(app.theme.highlight)
+        } else {
+            Style::default().fg(app.theme.text)
+        };
+        lines.push(Line::from(Span::styled(text, style)));
+    }
+
+    let visible = inner.height.saturating_sub(1) as usize;
+    let selected_line = header_lines + review.selected;
+    let offset = selected_line.saturating_sub(visible.saturating_sub(1));
+    f.render_widget(
+        Paragraph::new(lines.into_iter().skip(offset).collect::<Vec<_>>()),
+        inner,
+    );
+
+    let instruction_area = Rect {
+        x: popup_area.x + 2,
+        y: popup_area.y + popup_area.height.saturating_sub(1),
+        width: popup_area.width.saturating_sub(4),
+        height: 1,
+    };
+    let instruction_text = Paragraph::new(
+        app.tr("m/s/d merge/skip/duplicate (M/S/D for all), Enter import, Esc cancel"),
+    )
+    .style(Style::default().fg(app.theme.muted))
+    .alignment(Alignment::Center);
+    f.render_widget(instruction_text, instruction_area);
+}
+
 /// Renders an error popup with the given error message
-pub fn render_error_popup(f: &mut Frame, error_message: &str) {
+pub fn render_error_popup(f: &mut Frame, app: &App, error_message: &str) {
     // Calculate popup area - smaller than help popup
     let popup_area = create_fixed_popup_layout(f.area(), 60, 8);
 
@@ -108,7 +1400,8 @@ pub fn render_error_popup(f: &mut Frame, error_message: &str) {
     f.render_widget(Clear, popup_area);
 
     // Create error content
-    let error_block = create_error_block(" Error ");
+    let title = format!(" {} ", app.tr("Error"));
+    let error_block = create_error_block(&app.theme, &title);
 
     // Split error message into lines that fit the popup width
     let max_width = popup_area.width.saturating_sub(4) as usize;
@@ -122,11 +1415,11 @@ pub fn render_error_popup(f: &mut Frame, error_message: &str) {
     f.render_widget(error_paragraph, popup_area);
 
     // Add instruction to close
-    render_error_close_instruction(f, popup_area);
+    render_error_close_instruction(f, app, popup_area);
 }
 
 /// Wraps error text to fit within the popup width
-fn wrap_error_text(text: &str, max_width: usize) -> Vec<Line> {
+fn wrap_error_text(text: &str, max_width: usize) -> Vec<Line<'_>> {
     let mut lines = Vec::new();
 
     for paragraph in text.split('\n') {
@@ -164,7 +1457,7 @@ fn wrap_error_text(text: &str, max_width: usize) -> Vec<Line> {
 }
 
 /// Renders instruction to close the error popup
-fn render_error_close_instruction(f: &mut Frame, popup_area: Rect) {
+fn render_error_close_instruction(f: &mut Frame, app: &App, popup_area: Rect) {
     let instruction_area = Rect {
         x: popup_area.x + 2,
         y: popup_area.y + popup_area.height.saturating_sub(1),
@@ -172,15 +1465,21 @@ fn render_error_close_instruction(f: &mut Frame, popup_area: Rect) {
         height: 1,
     };
 
-    let instruction_text = Paragraph::new("Press any key to dismiss")
-        .style(Style::default().fg(TEXT_COLOR_MUTED))
+    let instruction_text = Paragraph::new(app.tr("Press any key to dismiss"))
+        .style(Style::default().fg(app.theme.muted))
         .alignment(Alignment::Center);
     f.render_widget(instruction_text, instruction_area);
 }
 
 /// Renders a confirmation dialog with Yes/No options
 #[cfg(test)]
-pub fn render_confirmation_popup(f: &mut Frame, title: &str, message: &str, selected: bool) {
+pub fn render_confirmation_popup(
+    f: &mut Frame,
+    theme: &Theme,
+    title: &str,
+    message: &str,
+    selected: bool,
+) {
     let popup_area = create_fixed_popup_layout(f.area(), 50, 10);
 
     // Clear the background
@@ -200,7 +1499,7 @@ pub fn render_confirmation_popup(f: &mut Frame, title: &str, message: &str, sele
         .title(title)
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(TEXT_COLOR_HIGHLIGHT));
+        .border_style(Style::default().fg(theme.highlight));
 
     let message_paragraph = Paragraph::new(message)
         .block(message_block)
@@ -210,11 +1509,11 @@ pub fn render_confirmation_popup(f: &mut Frame, title: &str, message: &str, sele
     f.render_widget(message_paragraph, popup_layout[0]);
 
     // Render button area
-    render_confirmation_buttons(f, popup_layout[1], selected);
+    render_confirmation_buttons(f, theme, popup_layout[1], selected);
 }
 
 /// Renders Yes/No buttons for confirmation dialog
-fn render_confirmation_buttons(f: &mut Frame, area: Rect, yes_selected: bool) {
+fn render_confirmation_buttons(f: &mut Frame, theme: &Theme, area: Rect, yes_selected: bool) {
     let button_layout = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
@@ -223,11 +1522,11 @@ fn render_confirmation_buttons(f: &mut Frame, area: Rect, yes_selected: bool) {
     // Yes button
     let yes_style = if yes_selected {
         Style::default()
-            .fg(Color::Black)
-            .bg(Color::Green)
+            .fg(theme.selected_text)
+            .bg(theme.success)
             .add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(Color::Green)
+        Style::default().fg(theme.success)
     };
 
     let yes_button = Paragraph::new("Yes")
@@ -240,11 +1539,11 @@ fn render_confirmation_buttons(f: &mut Frame, area: Rect, yes_selected: bool) {
     // No button
     let no_style = if !yes_selected {
         Style::default()
-            .fg(Color::Black)
-            .bg(Color::Red)
+            .fg(theme.selected_text)
+            .bg(theme.error)
             .add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(Color::Red)
+        Style::default().fg(theme.error)
     };
 
     let no_button = Paragraph::new("No")
@@ -257,7 +1556,7 @@ fn render_confirmation_buttons(f: &mut Frame, area: Rect, yes_selected: bool) {
 
 /// Renders a loading popup with a spinner
 #[cfg(test)]
-pub fn render_loading_popup(f: &mut Frame, message: &str, spinner_state: usize) {
+pub fn render_loading_popup(f: &mut Frame, theme: &Theme, message: &str, spinner_state: usize) {
     let popup_area = create_fixed_popup_layout(f.area(), 40, 6);
 
     // Clear the background
@@ -267,7 +1566,7 @@ pub fn render_loading_popup(f: &mut Frame, message: &str, spinner_state: usize)
         .title(" Loading ")
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Blue));
+        .border_style(Style::default().fg(theme.info));
 
     // Spinner animation
     let spinner_chars = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
@@ -283,7 +1582,7 @@ pub fn render_loading_popup(f: &mut Frame, message: &str, spinner_state: usize)
 
 /// Renders an information popup with just a message
 #[cfg(test)]
-pub fn render_info_popup(f: &mut Frame, title: &str, message: &str) {
+pub fn render_info_popup(f: &mut Frame, theme: &Theme, title: &str, message: &str) {
     let popup_area = create_fixed_popup_layout(f.area(), 50, 8);
 
     // Clear the background
@@ -293,7 +1592,7 @@ pub fn render_info_popup(f: &mut Frame, title: &str, message: &str) {
         .title(format!(" {} ", title))
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Blue));
+        .border_style(Style::default().fg(theme.info));
 
     let info_paragraph = Paragraph::new(message)
         .block(info_block)
@@ -311,7 +1610,7 @@ pub fn render_info_popup(f: &mut Frame, title: &str, message: &str) {
     };
 
     let instruction_text = Paragraph::new("Press any key to continue")
-        .style(Style::default().fg(TEXT_COLOR_MUTED))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
     f.render_widget(instruction_text, instruction_area);
 }
@@ -345,11 +1644,12 @@ mod tests {
     fn test_render_error_popup() {
         let backend = TestBackend::new(80, 24);
         let mut terminal = Terminal::new(backend).unwrap();
+        let app = App::new();
         let error_message = "Test error message";
 
         terminal
             .draw(|f| {
-                render_error_popup(f, error_message);
+                render_error_popup(f, &app, error_message);
             })
             .unwrap();
     }
@@ -367,6 +1667,108 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_render_export_popup() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new();
+        app.url_input = "https://example.com".to_string();
+
+        terminal
+            .draw(|f| {
+                render_export_popup(f, &app);
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_render_copy_popup() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let app = App::new();
+
+        terminal
+            .draw(|f| {
+                render_copy_popup(f, &app);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let content: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("> Headers as curl -H flags"));
+    }
+
+    #[test]
+    fn test_render_signing_popup() {
+        let backend = TestBackend::new(120, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new();
+        app.url_input = "https://s3.amazonaws.com/bucket".to_string();
+        app.auth_input.kind = crate::logic::auth::AuthKind::AwsSigV4;
+        for (k, v) in [
+            ("access_key", "AKID"),
+            ("secret_key", "secret"),
+            ("region", "us-east-1"),
+            ("service", "s3"),
+        ] {
+            app.auth_input.set_field(k, v);
+        }
+
+        terminal
+            .draw(|f| {
+                render_signing_popup(f, &app);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let content: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("Canonical request"));
+        assert!(content.contains("host:s3.amazonaws.com"));
+        assert!(content.contains("String to sign"));
+    }
+
+    #[test]
+    fn test_render_cookies_popup() {
+        let backend = TestBackend::new(100, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new();
+        app.cookie_jar = std::sync::Arc::new(crate::logic::cookies::CookieJar::default());
+        app.cookie_jar.insert(
+            crate::logic::cookies::Cookie::parse_manual("sid=abc; Domain=example.com; Secure")
+                .unwrap(),
+        );
+
+        terminal
+            .draw(|f| {
+                render_cookies_popup(f, &app);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let content: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("example.com"));
+        assert!(content.contains("sid=abc  [/, Secure, session]"));
+    }
+
+    #[test]
+    fn test_render_collections_popup() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new();
+        app.collection_selected = 3;
+
+        terminal
+            .draw(|f| {
+                render_collections_popup(f, &app);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let content: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(content.contains("Learn restless"));
+        assert!(content.contains("4. Variables"));
+    }
+
     #[test]
     fn test_render_confirmation_popup() {
         let backend = TestBackend::new(80, 24);
@@ -374,7 +1776,7 @@ mod tests {
 
         terminal
             .draw(|f| {
-                render_confirmation_popup(f, "Confirm", "Are you sure?", true);
+                render_confirmation_popup(f, &Theme::default(), "Confirm", "Are you sure?", true);
             })
             .unwrap();
     }
@@ -386,7 +1788,7 @@ mod tests {
 
         terminal
             .draw(|f| {
-                render_loading_popup(f, "Sending request...", 0);
+                render_loading_popup(f, &Theme::default(), "Sending request...", 0);
             })
             .unwrap();
     }
@@ -398,7 +1800,12 @@ mod tests {
 
         terminal
             .draw(|f| {
-                render_info_popup(f, "Information", "This is an info message");
+                render_info_popup(
+                    f,
+                    &Theme::default(),
+                    "Information",
+                    "This is an info message",
+                );
             })
             .unwrap();
     }
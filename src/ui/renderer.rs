@@ -7,13 +7,20 @@
 
 use super::{
     components::{
-        render_response_section, render_status_bar, render_tabs, render_url_input,
-        render_values_section,
+        render_header_suggestions, render_response_section, render_status_bar, render_tabs,
+        render_url_input, render_url_suggestions, render_values_section,
+    },
+    layouts::{create_main_layout, create_split_layout, create_zoomed_layout, MainLayout},
+    popups::{
+        render_collections_popup, render_cookies_popup, render_copy_popup, render_dry_run_popup,
+        render_error_popup, render_export_popup, render_finder_popup, render_help_popup,
+        render_import_review_popup, render_latency_popup, render_load_popup, render_load_prompt,
+        render_matrix_popup, render_matrix_prompt, render_paste_import_popup, render_poll_popup,
+        render_resend_popup, render_run_popup, render_signing_popup, render_timeline_popup,
+        render_watch_popup,
     },
-    layouts::create_main_layout,
-    popups::{render_error_popup, render_help_popup},
 };
-use crate::app::App;
+use crate::app::{App, CurrentScreen};
 use ratatui::Frame;
 
 /// Main UI rendering function
@@ -21,11 +28,29 @@ use ratatui::Frame;
 /// This is the entry point for all UI rendering. It coordinates the rendering
 /// of all UI components and handles popups.
 pub fn ui(f: &mut Frame, app: &mut App, error_message: &Option<String>) {
-    // Create the main application layout
-    let layout = create_main_layout(f.area());
+    if app.response_zoomed {
+        let (response_area, status_area) = create_zoomed_layout(f.area());
+        render_response_section(f, app, response_area);
+        render_status_bar(f, app, status_area);
+        app.screen_layout = MainLayout {
+            response_area,
+            status_area,
+            ..Default::default()
+        };
+        render_popups(f, app, error_message);
+        return;
+    }
+
+    // Create the main application layout, side by side when configured
+    let layout = if app.layout.is_split(f.area().width) {
+        create_split_layout(f.area())
+    } else {
+        create_main_layout(f.area())
+    };
 
     // Render main application components
     render_main_content(f, app, &layout);
+    app.screen_layout = layout;
 
     // Render popups on top of main content
     render_popups(f, app, error_message);
@@ -39,6 +64,9 @@ fn render_main_content(f: &mut Frame, app: &mut App, layout: &crate::ui::layouts
     render_values_section(f, app, layout.values_area);
     render_response_section(f, app, layout.response_area);
     render_status_bar(f, app, layout.status_area);
+    // Dropdowns over the sections below them
+    render_url_suggestions(f, app, layout.url_area);
+    render_header_suggestions(f, app, layout.values_area);
 }
 
 /// Renders any active popups over the main content
@@ -46,8 +74,95 @@ fn render_popups(f: &mut Frame, app: &App, error_message: &Option<String>) {
     // Help popup takes precedence over error popup
     if app.help_visible {
         render_help_popup(f, app);
-    } else if let Some(error) = error_message {
-        render_error_popup(f, error);
+        return;
+    }
+
+    if app.current_screen == CurrentScreen::Export {
+        render_export_popup(f, app);
+    }
+
+    if matches!(
+        app.current_screen,
+        CurrentScreen::Collections | CurrentScreen::EditingImportPath
+    ) {
+        render_collections_popup(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::Timeline {
+        render_collections_popup(f, app);
+        render_timeline_popup(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::ImportReview {
+        render_import_review_popup(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::Finder {
+        render_finder_popup(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::Copy {
+        render_copy_popup(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::SigningPreview {
+        render_signing_popup(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::DryRun {
+        render_dry_run_popup(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::Latency {
+        render_latency_popup(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::EditingMatrix {
+        render_matrix_prompt(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::MatrixResults {
+        render_matrix_popup(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::RunResults {
+        render_run_popup(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::EditingLoad {
+        render_load_prompt(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::LoadResults {
+        render_load_popup(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::ResendWith {
+        render_resend_popup(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::EditingPoll {
+        render_poll_popup(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::EditingWatch {
+        render_watch_popup(f, app);
+    }
+
+    if app.current_screen == CurrentScreen::PasteImport {
+        render_paste_import_popup(f, app);
+    }
+
+    if matches!(
+        app.current_screen,
+        CurrentScreen::Cookies | CurrentScreen::EditingCookie
+    ) {
+        render_cookies_popup(f, app);
+    }
+
+    // Errors are drawn last so they appear above other dialogs
+    if let Some(error) = error_message {
+        render_error_popup(f, app, error);
     }
 }
 
@@ -0,0 +1,238 @@
+//! Color schemes
+//!
+//! A theme names the colors the interface is drawn with by what they mean,
+//! not what they look like. `theme = "dracula"` under `[ui]` picks one of
+//! the built-in themes, and a `[theme]` table in the config file changes
+//! single colors of it:
+//!
+//! ```toml
+//! [ui]
+//! theme = "gruvbox"
+//!
+//! [theme]
+//! highlight = "#fe8019"
+//! muted = "darkgray"
+//! ```
+//!
+//! Colors are names like `red` or `lightblue`, `#rrggbb`, or a number from
+//! the 256-color palette.
+
+use crate::error::{RestlessError, Result};
+use ratatui::style::Color;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    /// Border of the focused section
+    pub border_active: Color,
+    pub border_inactive: Color,
+    /// Border of a section being edited
+    pub border_editing: Color,
+    pub border_error: Color,
+    pub text: Color,
+    /// Selections, keys in help texts and other things to look at first
+    pub highlight: Color,
+    pub error: Color,
+    pub success: Color,
+    pub info: Color,
+    /// Hints and other secondary text
+    pub muted: Color,
+    /// JSON keys, XML tags and informational statuses
+    pub accent: Color,
+    /// Numbers in highlighted bodies
+    pub number: Color,
+    /// Background of dropdowns
+    pub surface: Color,
+    /// Background of the selected line
+    pub selection: Color,
+    /// Text drawn on a highlighted background
+    pub selected_text: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            border_active: Color::Green,
+            border_inactive: Color::White,
+            border_editing: Color::Yellow,
+            border_error: Color::Red,
+            text: Color::White,
+            highlight: Color::Yellow,
+            error: Color::Red,
+            success: Color::Green,
+            info: Color::Blue,
+            muted: Color::Gray,
+            accent: Color::Cyan,
+            number: Color::Magenta,
+            surface: Color::Rgb(30, 30, 30),
+            selection: Color::Rgb(60, 60, 60),
+            selected_text: Color::Black,
+        }
+    }
+}
+
+/// Names of the built-in themes
+pub const BUILT_IN: &[&str] = &["default", "gruvbox", "dracula", "solarized"];
+
+impl Theme {
+    /// Color names accepted by [`Theme::set`]
+    pub const KEYS: &'static [&'static str] = &[
+        "border_active",
+        "border_inactive",
+        "border_editing",
+        "border_error",
+        "text",
+        "highlight",
+        "error",
+        "success",
+        "info",
+        "muted",
+        "accent",
+        "number",
+        "surface",
+        "selection",
+        "selected_text",
+    ];
+
+    /// A built-in theme by name
+    pub fn built_in(name: &str) -> Option<Theme> {
+        let rgb = |hex: u32| Color::Rgb((hex >> 16) as u8, (hex >> 8) as u8, hex as u8);
+        match name {
+            "default" => Some(Theme::default()),
+            "gruvbox" => Some(Theme {
+                border_active: rgb(0xb8bb26),
+                border_inactive: rgb(0xa89984),
+                border_editing: rgb(0xfabd2f),
+                border_error: rgb(0xfb4934),
+                text: rgb(0xebdbb2),
+                highlight: rgb(0xfabd2f),
+                error: rgb(0xfb4934),
+                success: rgb(0xb8bb26),
+                info: rgb(0x83a598),
+                muted: rgb(0x928374),
+                accent: rgb(0x8ec07c),
+                number: rgb(0xd3869b),
+                surface: rgb(0x3c3836),
+                selection: rgb(0x504945),
+                selected_text: rgb(0x282828),
+            }),
+            "dracula" => Some(Theme {
+                border_active: rgb(0xbd93f9),
+                border_inactive: rgb(0x6272a4),
+                border_editing: rgb(0xffb86c),
+                border_error: rgb(0xff5555),
+                text: rgb(0xf8f8f2),
+                highlight: rgb(0xf1fa8c),
+                error: rgb(0xff5555),
+                success: rgb(0x50fa7b),
+                info: rgb(0xbd93f9),
+                muted: rgb(0x6272a4),
+                accent: rgb(0x8be9fd),
+                number: rgb(0xff79c6),
+                surface: rgb(0x21222c),
+                selection: rgb(0x44475a),
+                selected_text: rgb(0x282a36),
+            }),
+            "solarized" => Some(Theme {
+                border_active: rgb(0x268bd2),
+                border_inactive: rgb(0x586e75),
+                border_editing: rgb(0xb58900),
+                border_error: rgb(0xdc322f),
+                text: rgb(0x93a1a1),
+                highlight: rgb(0xb58900),
+                error: rgb(0xdc322f),
+                success: rgb(0x859900),
+                info: rgb(0x268bd2),
+                muted: rgb(0x657b83),
+                accent: rgb(0x2aa198),
+                number: rgb(0xd33682),
+                surface: rgb(0x073642),
+                selection: rgb(0x073642),
+                selected_text: rgb(0x002b36),
+            }),
+            _ => None,
+        }
+    }
+
+    /// The built-in theme `name` (the default without one) with the colors
+    /// of `overrides` changed
+    pub fn load(name: Option<&str>, overrides: &BTreeMap<String, String>) -> Result<Theme> {
+        let name = name.unwrap_or("default");
+        let mut theme = Theme::built_in(name).ok_or_else(|| {
+            RestlessError::configuration(format!(
+                "Unknown theme '{}' (expected one of: {})",
+                name,
+                BUILT_IN.join(", ")
+            ))
+        })?;
+        for (key, value) in overrides {
+            theme.set(key, value)?;
+        }
+        Ok(theme)
+    }
+
+    /// Changes one color by name
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let color = Color::from_str(value).map_err(|_| {
+            RestlessError::configuration(format!("Invalid color '{}' for {}", value, key))
+        })?;
+        let slot = match key {
+            "border_active" => &mut self.border_active,
+            "border_inactive" => &mut self.border_inactive,
+            "border_editing" => &mut self.border_editing,
+            "border_error" => &mut self.border_error,
+            "text" => &mut self.text,
+            "highlight" => &mut self.highlight,
+            "error" => &mut self.error,
+            "success" => &mut self.success,
+            "info" => &mut self.info,
+            "muted" => &mut self.muted,
+            "accent" => &mut self.accent,
+            "number" => &mut self.number,
+            "surface" => &mut self.surface,
+            "selection" => &mut self.selection,
+            "selected_text" => &mut self.selected_text,
+            _ => {
+                return Err(RestlessError::configuration(format!(
+                    "Unknown theme color '{}' (expected one of: {})",
+                    key,
+                    Self::KEYS.join(", ")
+                )))
+            }
+        };
+        *slot = color;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_theme() {
+        let none = BTreeMap::new();
+        assert_eq!(Theme::load(None, &none).unwrap(), Theme::default());
+        for name in BUILT_IN {
+            assert!(Theme::built_in(name).is_some());
+        }
+        let dracula = Theme::load(Some("dracula"), &none).unwrap();
+        assert_eq!(dracula.accent, Color::Rgb(0x8b, 0xe9, 0xfd));
+        assert!(Theme::load(Some("neon"), &none).is_err());
+
+        let overrides = BTreeMap::from([
+            ("highlight".to_string(), "#fe8019".to_string()),
+            ("muted".to_string(), "darkgray".to_string()),
+        ]);
+        let theme = Theme::load(Some("gruvbox"), &overrides).unwrap();
+        assert_eq!(theme.highlight, Color::Rgb(0xfe, 0x80, 0x19));
+        assert_eq!(theme.muted, Color::DarkGray);
+        assert_eq!(theme.error, Color::Rgb(0xfb, 0x49, 0x34));
+
+        let unknown = BTreeMap::from([("glow".to_string(), "red".to_string())]);
+        assert!(Theme::load(None, &unknown).is_err());
+        let invalid = BTreeMap::from([("text".to_string(), "#12".to_string())]);
+        assert!(Theme::load(None, &invalid).is_err());
+    }
+}